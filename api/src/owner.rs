@@ -21,20 +21,28 @@ use uuid::Uuid;
 use crate::config::{MQSConfig, TorConfig, WalletConfig};
 use crate::core::core::Transaction;
 use crate::core::global;
-use crate::impls::create_sender;
+use crate::impls::adapters::deliver_swap_message;
+use crate::impls::{create_sender, get_mwcmqs_brocker, Address, Publisher};
 use crate::keychain::{Identifier, Keychain};
 use crate::libwallet::api_impl::foreign;
 use crate::libwallet::api_impl::owner_updater::{start_updater_log_thread, StatusMessage};
-use crate::libwallet::api_impl::{owner, owner_swap, owner_updater};
-use crate::libwallet::proof::proofaddress;
+use crate::libwallet::api_impl::{
+	owner, owner_contacts, owner_multisig, owner_payment_requests, owner_schedule, owner_swap,
+	owner_updater, owner_utxo,
+};
+use crate::libwallet::proof::proofaddress::{self, ProvableAddress};
 use crate::libwallet::proof::tx_proof::TxProof;
 use crate::libwallet::swap::fsm::state::{StateEtaInfo, StateId, StateProcessRespond};
+use crate::libwallet::swap::message::Message;
 use crate::libwallet::swap::types::{Action, SwapTransactionsConfirmations};
-use crate::libwallet::swap::{message::Message, swap::Swap, swap::SwapJournalRecord};
+use crate::libwallet::swap::{swap::Swap, swap::SwapJournalRecord};
 use crate::libwallet::{
-	AcctPathMapping, Error, ErrorKind, InitTxArgs, IssueInvoiceTxArgs, NodeClient,
-	NodeHeightResult, OutputCommitMapping, PaymentProof, Slate, SlatePurpose, SlateVersion,
-	SwapStartArgs, TxLogEntry, VersionedSlate, WalletInfo, WalletInst, WalletLCProvider,
+	AcctPathMapping, BatchPayout, ConsolidateArgs, Contact, Error, ErrorKind, EventNotifier,
+	FeeEstimate, InitTxArgs, IssueInvoiceTxArgs, MultisigAccount, NodeClient, NodeHeightResult,
+	OutputCommitMapping, PaymentExecutor, PaymentProof, PaymentRequest, PaymentRequestArgs,
+	SchedulePaymentArgs, ScheduledPayment, Slate, SlatePurpose, SlateVersion, SplitArgs,
+	SwapStartArgs, TxExportArgs, TxLogEntry, TxQueryArgs, UtxoTx, VersionedSlate, ViewKey,
+	WalletInfo, WalletInst, WalletLCProvider,
 };
 use crate::util::logger::LoggingConfig;
 use crate::util::secp::key::SecretKey;
@@ -85,6 +93,10 @@ where
 	/// Optional TOR configuration, holding address of sender and
 	/// data directory
 	tor_config: Mutex<Option<TorConfig>>,
+	/// Optional destination for the wallet events detected by the updater process
+	event_notifier: Mutex<Option<EventNotifier>>,
+	/// Names of the wallets hosted by the owner API listener next to this one
+	hosted_wallet_names: Arc<Mutex<Vec<String>>>,
 
 	/// updater log thread. Expected to be removed at next rebase
 	updater_log_thread: Option<JoinHandle<()>>,
@@ -112,6 +124,35 @@ where
 	}
 }
 
+/// Sends the scheduled payments from the updater thread, the same way as
+/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) with the send arguments does
+struct OwnerPaymentExecutor<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	status_tx: Mutex<Option<Sender<StatusMessage>>>,
+	tor_config: Option<TorConfig>,
+}
+
+impl<L, C, K> PaymentExecutor for OwnerPaymentExecutor<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	fn send(&self, keychain_mask: Option<&SecretKey>, args: &InitTxArgs) -> Result<Slate, Error> {
+		let owner = Owner::new(
+			self.wallet_inst.clone(),
+			self.status_tx.lock().clone(),
+			self.tor_config.clone(),
+		);
+		owner.init_send_tx(keychain_mask, args, 1)
+	}
+}
+
 impl<L, C, K> Owner<L, C, K>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
@@ -230,6 +271,8 @@ where
 			status_tx: Mutex::new(Some(tx)),
 			updater_messages,
 			tor_config: Mutex::new(tor_config),
+			event_notifier: Mutex::new(None),
+			hosted_wallet_names: Arc::new(Mutex::new(vec![])),
 			updater_log_thread: handle,
 			updater_log_running_state: running,
 		}
@@ -248,6 +291,34 @@ where
 		*lock = tor_config;
 	}
 
+	/// Share the list of the named wallets hosted by the owner API listener. The listener
+	/// keeps the list up to date, [`list_wallets`](struct.Owner.html#method.list_wallets)
+	/// returns it.
+	///
+	/// # Arguments
+	/// * `names` - The list of the hosted wallet names, shared with the listener
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_hosted_wallet_names(&mut self, names: Arc<Mutex<Vec<String>>>) {
+		self.hosted_wallet_names = names;
+	}
+
+	/// Set the destination for the wallet events (transaction received, confirmed,
+	/// cancelled, swap state changes, etc). Events are detected by the updater process,
+	/// so the notifier need to be set before calling
+	/// [`start_updater`](struct.Owner.html#method.start_updater)
+	///
+	/// # Arguments
+	/// * `event_notifier` - The optional [EventNotifier](#) to use
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_event_notifier(&self, event_notifier: Option<EventNotifier>) {
+		let mut lock = self.event_notifier.lock();
+		*lock = event_notifier;
+	}
+
 	/// Returns a list of accounts stored in the wallet (i.e. mappings between
 	/// user-specified labels and BIP32 derivation paths.
 	/// # Arguments
@@ -461,6 +532,147 @@ where
 		)
 	}
 
+	/// Sets or clears the label of a wallet output.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `commit` - Hex encoded commitment of the wallet output.
+	/// * `label` - The label to set, `None` clears the label.
+	///
+	/// # Returns
+	/// * Result Containing:
+	/// * `Ok(())` if the value was stored
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// for instance if the output doesn't belong to the wallet.
+	///
+	/// # Remarks
+	///
+	/// Labels are stored in the wallet database and returned with the output data by
+	/// [`retrieve_outputs`](struct.Owner.html#method.retrieve_outputs).
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.retrieve_outputs(None, false, false, None);
+	///
+	/// if let Ok((_, output_mappings)) = result {
+	///     if let Some(m) = output_mappings.first() {
+	///         let commit = grin_wallet_util::grin_util::to_hex(&m.commit.0);
+	///         let result = api_owner.set_output_label(None, &commit, Some("savings".to_string()));
+	///     }
+	/// }
+	/// ```
+
+	pub fn set_output_label(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		commit: &str,
+		label: Option<String>,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_output_label(&mut **w, keychain_mask, commit, label)
+	}
+
+	/// Sets or clears a free text note for a wallet output.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `commit` - Hex encoded commitment of the wallet output.
+	/// * `note` - The free text note to set, `None` clears the note.
+	///
+	/// # Returns
+	/// * Result Containing:
+	/// * `Ok(())` if the value was stored
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// for instance if the output doesn't belong to the wallet.
+	///
+	/// # Remarks
+	///
+	/// Notes are stored in the wallet database and returned with the output data by
+	/// [`retrieve_outputs`](struct.Owner.html#method.retrieve_outputs).
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.retrieve_outputs(None, false, false, None);
+	///
+	/// if let Ok((_, output_mappings)) = result {
+	///     if let Some(m) = output_mappings.first() {
+	///         let commit = grin_wallet_util::grin_util::to_hex(&m.commit.0);
+	///         let result = api_owner.set_output_note(None, &commit, Some("Received from the exchange".to_string()));
+	///     }
+	/// }
+	/// ```
+
+	pub fn set_output_note(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		commit: &str,
+		note: Option<String>,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_output_note(&mut **w, keychain_mask, commit, note)
+	}
+
+	/// Freezes or unfreezes a wallet output.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `commit` - Hex encoded commitment of the wallet output.
+	/// * `frozen` - `true` to freeze the output, `false` to unfreeze it.
+	///
+	/// # Returns
+	/// * Result Containing:
+	/// * `Ok(())` if the value was stored
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// for instance if the output doesn't belong to the wallet.
+	///
+	/// # Remarks
+	///
+	/// Frozen outputs are never selected as inputs for new transactions, including the case when
+	/// they are listed explicitly in [`InitTxArgs`](../grin_wallet_libwallet/api_impl/types/struct.InitTxArgs.html).
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.retrieve_outputs(None, false, false, None);
+	///
+	/// if let Ok((_, output_mappings)) = result {
+	///     if let Some(m) = output_mappings.first() {
+	///         let commit = grin_wallet_util::grin_util::to_hex(&m.commit.0);
+	///         let result = api_owner.set_output_frozen(None, &commit, true);
+	///     }
+	/// }
+	/// ```
+
+	pub fn set_output_frozen(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		commit: &str,
+		frozen: bool,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_output_frozen(&mut **w, keychain_mask, commit, frozen)
+	}
+
 	/// Returns a list of [Transaction Log Entries](../grin_wallet_libwallet/types/struct.TxLogEntry.html)
 	/// from the active account in the wallet.
 	///
@@ -542,6 +754,153 @@ where
 		Ok(res)
 	}
 
+	/// Returns the [Transaction Log Entries](../grin_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// from the active account that match the query. The entries can be filtered by type, confirmed and
+	/// cancelled state, date and height range, amount range and counterparty address, then sorted and
+	/// paginated.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node (via the [`NodeClient`](../grin_wallet_libwallet/types/trait.NodeClient.html)
+	/// provided during wallet instantiation). If `false`, the results will
+	/// contain transaction information that may be out-of-date (from the last time
+	/// the wallet's output set was refreshed against the node).
+	/// Note this setting is ignored if the updater process is running via a call to
+	/// [`start_updater`](struct.Owner.html#method.start_updater)
+	/// * `query` - [`TxQueryArgs`](../grin_wallet_libwallet/api_impl/types/struct.TxQueryArgs.html),
+	/// the filters, the sort order, the offset and the limit.
+	///
+	/// # Returns
+	/// * `(bool, Vec<TxLogEntry)` - A tuple:
+	/// * The first `bool` element indicates whether the data was successfully
+	/// refreshed from the node.
+	/// * The second element contains the matching
+	/// [TxLogEntries](../grin_wallet_libwallet/types/struct.TxLogEntry.html)
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// // The latest 10 confirmed transactions
+	/// let query = TxQueryArgs {
+	///     confirmed: Some(true),
+	///     sort_order: TxQuerySortOrder::Desc,
+	///     limit: Some(10),
+	///     ..Default::default()
+	/// };
+	///
+	/// let result = api_owner.query_txs(None, true, &query);
+	///
+	/// if let Ok((was_updated, tx_log_entries)) = result {
+	///     //...
+	/// }
+	/// ```
+
+	pub fn query_txs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		refresh_from_node: bool,
+		query: &TxQueryArgs,
+	) -> Result<(bool, Vec<TxLogEntry>), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let refresh_from_node = match self.updater_running.load(Ordering::Relaxed) {
+			true => false,
+			false => refresh_from_node,
+		};
+		let mut res = owner::query_txs(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+			query,
+		)?;
+		if self.doctest_mode {
+			res.1 = res
+				.1
+				.into_iter()
+				.map(|mut t| {
+					t.confirmation_ts = Some(Utc.ymd(2019, 1, 15).and_hms(16, 1, 26));
+					t.creation_ts = Utc.ymd(2019, 1, 15).and_hms(16, 1, 26);
+					t
+				})
+				.collect();
+		}
+		Ok(res)
+	}
+
+	/// Exports [Transaction Log Entries](../grin_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// in CSV or JSON format, for accounting and tax reporting. Every record contains amounts,
+	/// fee, counterparty address, kernel excess, confirmation timestamps and the payment proof status.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node (via the [`NodeClient`](../grin_wallet_libwallet/types/trait.NodeClient.html)
+	/// provided during wallet instantiation). If `false`, the results will
+	/// contain transaction information that may be out-of-date (from the last time
+	/// the wallet's output set was refreshed against the node).
+	/// Note this setting is ignored if the updater process is running via a call to
+	/// [`start_updater`](struct.Owner.html#method.start_updater)
+	/// * `args` - [`TxExportArgs`](../grin_wallet_libwallet/api_impl/types/struct.TxExportArgs.html),
+	/// account, date and height filters and the output format.
+	///
+	/// # Returns
+	/// * `(bool, String)` - A tuple:
+	/// * The first `bool` element indicates whether the data was successfully
+	/// refreshed from the node.
+	/// * The second element contains the exported CSV or JSON text.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	/// let args = TxExportArgs {
+	///     account: Some("default".to_owned()),
+	///     format: TxExportFormat::Json,
+	///     ..Default::default()
+	/// };
+	///
+	/// let result = api_owner.export_txs(None, true, &args);
+	///
+	/// if let Ok((was_updated, json)) = result {
+	///     //...
+	/// }
+	/// ```
+
+	pub fn export_txs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		refresh_from_node: bool,
+		args: &TxExportArgs,
+	) -> Result<(bool, String), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let refresh_from_node = match self.updater_running.load(Ordering::Relaxed) {
+			true => false,
+			false => refresh_from_node,
+		};
+		owner::export_txs(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+			args,
+		)
+	}
+
 	/// Returns summary information from the active account in the wallet.
 	///
 	/// # Arguments
@@ -836,22 +1195,23 @@ where
 		}
 	}
 
-	/// Issues a new invoice transaction slate, essentially a `request for payment`.
-	/// The slate created by this function will contain the amount, an output for the amount,
-	/// as well as round 1 of singature creation complete. The slate should then be send
-	/// to the payer, who should add their inputs and signature data and return the slate
-	/// via the [Foreign API's `finalize_invoice_tx`](struct.Foreign.html#method.finalize_invoice_tx) method.
+	/// Estimates the fee of a send transaction. The outputs are selected the same way as
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) would select them, but no slate
+	/// is created, no outputs are locked and the wallet data is not changed.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `args` - [`IssueInvoiceTxArgs`](../grin_wallet_libwallet/types/struct.IssueInvoiceTxArgs.html),
-	/// invoice transaction initialization arguments. See struct documentation for further detail.
+	/// * `args` - [`InitTxArgs`](../grin_wallet_libwallet/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments. Only the amount, account, confirmations, outputs,
+	/// sweep and coin selection arguments are used. If `amount` is zero or `sweep` is set, the
+	/// estimate is done for the maximum amount that can be sent.
 	///
 	/// # Returns
-	/// * ``Ok([`slate`](../grin_wallet_libwallet/slate/struct.Slate.html))` if successful,
-	/// containing the updated slate.
+	/// * ``Ok([`FeeEstimate`](../grin_wallet_libwallet/api_impl/types/struct.FeeEstimate.html))`
+	/// with the amount, fee, inputs, change, transaction weight and the maximum amount that can be sent.
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// Not enough funds for the amount is reported as an error.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
@@ -860,18 +1220,76 @@ where
 	///
 	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
 	///
-	/// let args = IssueInvoiceTxArgs {
-	///     amount: 60_000_000_000,
+	/// let args = InitTxArgs {
+	///     amount: 2_000_000_000,
+	///     minimum_confirmations: 2,
+	///     num_change_outputs: 1,
+	///     selection_strategy: Some(CoinSelectionStrategy::Smallest),
 	///     ..Default::default()
 	/// };
-	/// let result = api_owner.issue_invoice_tx(None, &args);
+	/// let result = api_owner.estimate_fee(None, &args);
 	///
-	/// if let Ok(slate) = result {
-	///     // if okay, send to the payer to add their inputs
-	///     // . . .
+	/// if let Ok(estimate) = result {
+	///     println!("fee: {}, can send up to: {}", estimate.fee, estimate.max_amount);
 	/// }
 	/// ```
-	pub fn issue_invoice_tx(
+
+	pub fn estimate_fee(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: &InitTxArgs,
+	) -> Result<FeeEstimate, Error> {
+		if args.minimum_confirmations < 1 {
+			return Err(ErrorKind::ClientCallback(
+				"Minimum_confirmations can not be smaller than 1".to_owned(),
+			)
+			.into());
+		}
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Watch only wallet has no keychain, for others the mask is validated
+		if w.view_key().is_none() {
+			w.keychain(keychain_mask)?;
+		}
+		owner::estimate_fee(&mut **w, args, 1)
+	}
+
+	/// Issues a new invoice transaction slate, essentially a `request for payment`.
+	/// The slate created by this function will contain the amount, an output for the amount,
+	/// as well as round 1 of singature creation complete. The slate should then be send
+	/// to the payer, who should add their inputs and signature data and return the slate
+	/// via the [Foreign API's `finalize_invoice_tx`](struct.Foreign.html#method.finalize_invoice_tx) method.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`IssueInvoiceTxArgs`](../grin_wallet_libwallet/types/struct.IssueInvoiceTxArgs.html),
+	/// invoice transaction initialization arguments. See struct documentation for further detail.
+	///
+	/// # Returns
+	/// * ``Ok([`slate`](../grin_wallet_libwallet/slate/struct.Slate.html))` if successful,
+	/// containing the updated slate.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let args = IssueInvoiceTxArgs {
+	///     amount: 60_000_000_000,
+	///     ..Default::default()
+	/// };
+	/// let result = api_owner.issue_invoice_tx(None, &args);
+	///
+	/// if let Ok(slate) = result {
+	///     // if okay, send to the payer to add their inputs
+	///     // . . .
+	/// }
+	/// ```
+	pub fn issue_invoice_tx(
 		&self,
 		keychain_mask: Option<&SecretKey>,
 		args: &IssueInvoiceTxArgs,
@@ -1112,161 +1530,171 @@ where
 		Ok(slate_res)
 	}
 
-	/// Posts a completed transaction to the listening node for validation and inclusion in a block
-	/// for mining.
+	/// Initiates a batch payout, a single transaction that pays many recipients. Every
+	/// recipient gets its own [`Slate`](../grin_wallet_libwallet/slate/struct.Slate.html),
+	/// that is exchanged with the recipient as a usual send slate (file, slatepack, Tor or MQS),
+	/// so the recipients don't need any batch support. Every slate has its own kernel,
+	/// the first one also carries the sender's inputs and change outputs. The transaction fee
+	/// is calculated for the whole transaction and shared by the kernels.
+	///
+	/// Unlike [`init_send_tx`](struct.Owner.html#method.init_send_tx) the outputs are locked
+	/// by this call. The batch is recorded in the transaction log as a single sent transaction,
+	/// its `batch_payouts` list has an entry for every recipient.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `tx` - A completed [`Transaction`](../grin_core/core/transaction/struct.Transaction.html),
-	/// typically the `tx` field in the transaction [`Slate`](../grin_wallet_libwallet/slate/struct.Slate.html).
-	/// * `fluff` - Instruct the node whether to use the Dandelion protocol when posting the
-	/// transaction. If `true`, the node should skip the Dandelion phase and broadcast the
-	/// transaction to all peers immediately. If `false`, the node will follow dandelion logic and
-	/// initiate the stem phase.
+	/// * `args` - [`InitTxArgs`](../grin_wallet_libwallet/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments. The `amount` is ignored, lock later, estimates,
+	/// payment proofs and `send_args` are not supported.
+	/// * `payouts` - The recipients, [`BatchPayout`](../grin_wallet_libwallet/api_impl/types/struct.BatchPayout.html)
+	/// amounts and addresses.
 	///
 	/// # Returns
-	/// * `Ok(())` if successful
+	/// * a result containing:
+	/// * The slates, one for every recipient in the order of `payouts`. Once all of them are
+	/// returned by the recipients, the transaction can be completed by
+	/// [`finalize_batch_tx`](struct.Owner.html#method.finalize_batch_tx).
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
-	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
 	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
 	/// let args = InitTxArgs {
 	///     src_acct_name: None,
-	///     amount: 2_000_000_000,
-	///     minimum_confirmations: 10,
+	///     minimum_confirmations: 2,
 	///     max_outputs: 500,
 	///     num_change_outputs: 1,
 	///     selection_strategy_is_use_all: false,
-	///     message: Some("Post this tx".to_owned()),
+	///     message: Some("Mining rewards".to_owned()),
 	///     ..Default::default()
 	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	&args,
-	/// 	1,
-	/// );
-	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, None, 0);
-	///		//
-	///		// Retrieve slate back from recipient
-	///		//
-	///		let res = api_owner.finalize_tx(None, &slate);
-	///		let res = api_owner.post_tx(None, &slate.tx, true);
+	/// let payouts = vec![
+	///     BatchPayout {
+	///         amount: 1_000_000_000,
+	///         address: None,
+	///     },
+	///     BatchPayout {
+	///         amount: 2_000_000_000,
+	///         address: None,
+	///     },
+	/// ];
+	/// let result = api_owner.init_batch_send_tx(None, &args, &payouts);
+	///
+	/// if let Ok(slates) = result {
+	/// 	// Send every slate to its recipient
+	/// 	// ...
 	/// }
 	/// ```
 
-	pub fn post_tx(
+	pub fn init_batch_send_tx(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		tx: &Transaction,
-		fluff: bool,
-	) -> Result<(), Error> {
-		let client = {
-			let mut w_lock = self.wallet_inst.lock();
-			let w = w_lock.lc_provider()?.wallet_inst()?;
-			// Test keychain mask, to keep API consistent
-			let _ = w.keychain(keychain_mask)?;
-			w.w2n_client().clone()
-		};
-		owner::post_tx(&client, tx, fluff)
+		args: &InitTxArgs,
+		payouts: &[BatchPayout],
+	) -> Result<Vec<Slate>, Error> {
+		if args.send_args.is_some() {
+			return Err(ErrorKind::ClientCallback(
+				"Batch payout slates can't be sent with send_args".to_owned(),
+			)
+			.into());
+		}
+		if args.minimum_confirmations < 1 {
+			return Err(ErrorKind::ClientCallback(
+				"Minimum_confirmations can not be smaller than 1".to_owned(),
+			)
+			.into());
+		}
+
+		owner::update_wallet_state(self.wallet_inst.clone(), keychain_mask, &None)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::init_batch_send_tx(&mut **w, keychain_mask, args, payouts, self.doctest_mode)
 	}
 
-	/// Cancels a transaction. This entails:
-	/// * Setting the transaction status to either `TxSentCancelled` or `TxReceivedCancelled`
-	/// * Deleting all change outputs or recipient outputs associated with the transaction
-	/// * Setting the status of all assocatied inputs from `Locked` to `Spent` so they can be
-	/// used in new transactions.
+	/// Finalizes a batch payout created by
+	/// [`init_batch_send_tx`](struct.Owner.html#method.init_batch_send_tx). Every slate
+	/// must be returned by its recipient. The kernels are signed and aggregated with all inputs
+	/// and outputs into a single transaction, that is stored in the wallet and returned.
 	///
-	/// Transactions can be cancelled by transaction log id or slate id (call with either set to
-	/// Some, not both)
+	/// Note that this function DOES NOT POST the transaction to a node, use
+	/// [`post_tx`](struct.Owner.html#method.post_tx) for that.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `tx_id` - If present, cancel by the [`TxLogEntry`](../grin_wallet_libwallet/types/struct.TxLogEntry.html) id
-	/// for the transaction.
-	///
-	/// * `tx_slate_id` - If present, cancel by the Slate id.
+	/// * `slates` - The slates of the batch returned by the recipients, in any order.
 	///
 	/// # Returns
-	/// * `Ok(())` if successful
+	/// * ``Ok([`Transaction`](../grin_core/core/transaction/struct.Transaction.html))` if successful,
+	/// containing the aggregated transaction.
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
-	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
 	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
 	/// let args = InitTxArgs {
 	///     src_acct_name: None,
-	///     amount: 2_000_000_000,
-	///     minimum_confirmations: 10,
+	///     minimum_confirmations: 2,
 	///     max_outputs: 500,
 	///     num_change_outputs: 1,
 	///     selection_strategy_is_use_all: false,
-	///     message: Some("Cancel this tx".to_owned()),
 	///     ..Default::default()
 	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	&args,
-	/// 	1,
-	/// );
-	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, None, 0);
-	///		//
-	///		// We didn't get the slate back, or something else went wrong
-	///		//
-	///		let res = api_owner.cancel_tx(None, None, Some(slate.id.clone()));
+	/// let payouts = vec![
+	///     BatchPayout {
+	///         amount: 1_000_000_000,
+	///         address: None,
+	///     },
+	///     BatchPayout {
+	///         amount: 2_000_000_000,
+	///         address: None,
+	///     },
+	/// ];
+	/// let result = api_owner.init_batch_send_tx(None, &args, &payouts);
+	///
+	/// if let Ok(slates) = result {
+	/// 	// Send every slate to its recipient
+	/// 	// ...
+	/// 	// Retrieve the slates back from the recipients
+	/// 	//
+	/// 	if let Ok(tx) = api_owner.finalize_batch_tx(None, &slates) {
+	/// 		let res = api_owner.post_tx(None, &tx, true);
+	/// 	}
 	/// }
 	/// ```
 
-	pub fn cancel_tx(
+	pub fn finalize_batch_tx(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		tx_id: Option<u32>,
-		tx_slate_id: Option<Uuid>,
-	) -> Result<(), Error> {
-		let tx = {
-			let t = self.status_tx.lock();
-			t.clone()
-		};
-		owner::cancel_tx(
-			self.wallet_inst.clone(),
-			keychain_mask,
-			&tx,
-			tx_id,
-			tx_slate_id,
-		)
+		slates: &[Slate],
+	) -> Result<Transaction, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::finalize_batch_tx(&mut **w, keychain_mask, slates, true, self.doctest_mode)
 	}
 
-	/// Retrieves the stored transaction associated with a TxLogEntry. Can be used even after the
-	/// transaction has completed.
+	/// Merges the smallest spendable outputs of the account into one output. The merge is a
+	/// self send, the fee is paid from the merged value. Large sets of outputs are merged by
+	/// several transactions, so every transaction fits into a block. The transactions are
+	/// posted to the node.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `tx_log_entry` - A [`TxLogEntry`](../grin_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// * `args` - [`ConsolidateArgs`](../grin_wallet_libwallet/api_impl/types/struct.ConsolidateArgs.html),
+	/// the account, number of outputs to merge and the limit of inputs per transaction.
+	/// * `preview` - If `true`, only the planned transactions are returned, nothing is created or posted.
 	///
 	/// # Returns
-	/// * Ok with the stored  [`Transaction`](../grin_core/core/transaction/struct.Transaction.html)
-	/// if successful
+	/// * ``Ok(Vec<[`UtxoTx`](../grin_wallet_libwallet/api_impl/types/struct.UtxoTx.html)>)`
+	/// with the inputs, resulting output and fee of every transaction.
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1274,56 +1702,54 @@ where
 	/// ```
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let api_owner = Owner::new(wallet.clone(), None, None);
-	/// let update_from_node = true;
-	/// let tx_id = None;
-	/// let tx_slate_id = None;
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
 	///
-	/// // Return all TxLogEntries
-	/// let result = api_owner.retrieve_txs(None, update_from_node, tx_id, tx_slate_id);
+	/// let args = ConsolidateArgs {
+	///     minimum_confirmations: 10,
+	///     num_outputs: Some(100),
+	///     ..Default::default()
+	/// };
+	/// let result = api_owner.consolidate_outputs(None, &args, true);
 	///
-	/// if let Ok((was_updated, tx_log_entries)) = result {
-	///     let stored_tx = api_owner.get_stored_tx(None, &tx_log_entries[0]).unwrap();
-	///     //...
+	/// if let Ok(txs) = result {
+	///     for tx in txs {
+	///         println!("merge {} outputs, fee {}", tx.inputs.len(), tx.fee);
+	///     }
 	/// }
 	/// ```
 
-	// TODO: Should be accepting an id, not an entire entry struct
-	pub fn get_stored_tx(
+	pub fn consolidate_outputs(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		tx_log_entry: &TxLogEntry,
-	) -> Result<Option<Transaction>, Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let w = w_lock.lc_provider()?.wallet_inst()?;
-		// Test keychain mask, to keep API consistent
-		let _ = w.keychain(keychain_mask)?;
-		owner::get_stored_tx(&**w, tx_log_entry)
-	}
-
-	/// Loads a stored transaction from a file
-	pub fn load_stored_tx(&self, file: &String) -> Result<Transaction, Error> {
+		args: &ConsolidateArgs,
+		preview: bool,
+	) -> Result<Vec<UtxoTx>, Error> {
+		if args.minimum_confirmations < 1 {
+			return Err(ErrorKind::ClientCallback(
+				"Minimum_confirmations can not be smaller than 1".to_owned(),
+			)
+			.into());
+		}
+		owner::update_wallet_state(self.wallet_inst.clone(), keychain_mask, &None)?;
 		let mut w_lock = self.wallet_inst.lock();
 		let w = w_lock.lc_provider()?.wallet_inst()?;
-		owner::load_stored_tx(&**w, file)
+		owner_utxo::consolidate_outputs(&mut **w, keychain_mask, args, preview)
 	}
 
-	/// Verifies all messages in the slate match their public keys.
-	///
-	/// The optional messages themselves are part of the `participant_data` field within the slate.
-	/// Messages are signed with the same key used to sign for the paricipant's inputs, and can thus be
-	/// verified with the public key found in the `public_blind_excess` field. This function is a
-	/// simple helper to returns whether all signatures in the participant data match their public
-	/// keys.
+	/// Splits an output into the outputs of the requested amounts. The split is a self send,
+	/// the rest of the output value, minus the fee, goes to a change output. The transaction
+	/// is posted to the node.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `slate` - The transaction [`Slate`](../grin_wallet_libwallet/slate/struct.Slate.html).
+	/// * `args` - [`SplitArgs`](../grin_wallet_libwallet/api_impl/types/struct.SplitArgs.html),
+	/// the commitment of the output to split and the amounts of the new outputs.
+	/// * `preview` - If `true`, only the planned transaction is returned, nothing is created or posted.
 	///
 	/// # Returns
-	/// * `Ok(())` if successful and the signatures validate
+	/// * ``Ok([`UtxoTx`](../grin_wallet_libwallet/api_impl/types/struct.UtxoTx.html))`
+	/// with the input, resulting outputs and fee.
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1332,52 +1758,771 @@ where
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
 	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
-	/// let args = InitTxArgs {
+	///
+	/// let args = SplitArgs {
 	///     src_acct_name: None,
-	///     amount: 2_000_000_000,
 	///     minimum_confirmations: 10,
-	///     max_outputs: 500,
-	///     num_change_outputs: 1,
-	///     selection_strategy_is_use_all: false,
-	///     message: Some("Just verify messages".to_owned()),
-	///     ..Default::default()
+	///     output: "087df32304c5d4ae8b2af0bc31e700019d722910ef87dd4eec3197b80b207e3045".to_owned(),
+	///     amounts: vec![1_000_000_000, 1_000_000_000, 2_000_000_000],
+	///     fluff: false,
 	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	&args,
-	/// 	1,
-	/// );
+	/// let result = api_owner.split_output(None, &args, true);
 	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, None, 0);
-	///		//
-	///		// Retrieve slate back from recipient
-	///		//
-	///		let res = api_owner.verify_slate_messages(None, &slate);
+	/// if let Ok(tx) = result {
+	///     println!("new outputs {:?}, fee {}", tx.outputs, tx.fee);
 	/// }
 	/// ```
-	pub fn verify_slate_messages(
+
+	pub fn split_output(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		slate: &Slate,
-	) -> Result<(), Error> {
-		{
-			let mut w_lock = self.wallet_inst.lock();
-			let w = w_lock.lc_provider()?.wallet_inst()?;
-			// Test keychain mask, to keep API consistent
-			let _ = w.keychain(keychain_mask)?;
+		args: &SplitArgs,
+		preview: bool,
+	) -> Result<UtxoTx, Error> {
+		if args.minimum_confirmations < 1 {
+			return Err(ErrorKind::ClientCallback(
+				"Minimum_confirmations can not be smaller than 1".to_owned(),
+			)
+			.into());
 		}
-		owner::verify_slate_messages(slate)
+		owner::update_wallet_state(self.wallet_inst.clone(), keychain_mask, &None)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_utxo::split_output(&mut **w, keychain_mask, args, preview)
 	}
 
-	/// Scans the entire UTXO set from the node, identify which outputs belong to the given wallet
-	/// update the wallet state to be consistent with what's currently in the UTXO set.
+	/// Schedules a payment that is sent once or with a fixed interval. Payments are stored in the
+	/// wallet and sent by the updater thread (see [`start_updater`](struct.Owner.html#method.start_updater))
+	/// when they are due. The transaction is sent with the `http` or `mwcmqs` sender, finalized
+	/// and posted, the same way as [`init_send_tx`](struct.Owner.html#method.init_send_tx) does
+	/// with `send_args`. Failed sends are retried with exponential backoff, up to `max_retries`
+	/// times.
 	///
-	/// This function can be used to repair wallet state, particularly by restoring outputs that may
-	/// be missing if the wallet owner has cancelled transactions locally that were then successfully
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`SchedulePaymentArgs`](../grin_wallet_libwallet/api_impl/types/struct.SchedulePaymentArgs.html),
+	/// the payment amount, destination and schedule.
+	///
+	/// # Returns
+	/// * ``Ok([`ScheduledPayment`](../grin_wallet_libwallet/types/struct.ScheduledPayment.html))`
+	/// the stored payment.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let args = SchedulePaymentArgs {
+	///     src_acct_name: None,
+	///     amount: 2_000_000_000,
+	///     minimum_confirmations: 10,
+	///     message: Some("Rent".to_owned()),
+	///     method: "mwcmqs".to_owned(),
+	///     dest: "xmgEvZ4MCCGMJnRnNXKHBbHmSGWQchJ9TqLs8qRdxyr8Sm9W3rBt".to_owned(),
+	///     apisecret: None,
+	///     fluff: false,
+	///     start_time: None,
+	///     interval_secs: Some(30 * 24 * 3600),
+	///     max_retries: 5,
+	/// };
+	/// let result = api_owner.schedule_payment(None, &args);
+	///
+	/// if let Ok(payment) = result {
+	///     println!("payment {} is sent at {}", payment.id, payment.next_run);
+	/// }
+	/// ```
+
+	pub fn schedule_payment(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: &SchedulePaymentArgs,
+	) -> Result<ScheduledPayment, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_schedule::schedule_payment(&mut **w, keychain_mask, args)
+	}
+
+	/// Returns the scheduled payments with the latest send attempts, ordered by the next
+	/// payment time.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * ``Ok(Vec<[`ScheduledPayment`](../grin_wallet_libwallet/types/struct.ScheduledPayment.html)>)`
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.scheduled_payments(None);
+	/// assert!(result.is_ok());
+	/// ```
+
+	pub fn scheduled_payments(
+		&self,
+		_keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<ScheduledPayment>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_schedule::scheduled_payments(&**w)
+	}
+
+	/// Pauses or resumes a scheduled payment. A resumed recurring payment continues from its
+	/// next interval, the payments missed during the pause are not sent.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - Id of the scheduled payment.
+	/// * `active` - `false` to pause the payment, `true` to resume it.
+	///
+	/// # Returns
+	/// * ``Ok([`ScheduledPayment`](../grin_wallet_libwallet/types/struct.ScheduledPayment.html))`
+	/// the updated payment.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let id = Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap();
+	/// let result = api_owner.set_scheduled_payment_active(None, &id, false);
+	/// assert!(result.is_err());
+	/// ```
+
+	pub fn set_scheduled_payment_active(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		id: &Uuid,
+		active: bool,
+	) -> Result<ScheduledPayment, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_schedule::set_scheduled_payment_active(&mut **w, keychain_mask, id, active)
+	}
+
+	/// Deletes a scheduled payment. Transactions that are already sent are not affected.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - Id of the scheduled payment.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let id = Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap();
+	/// let result = api_owner.delete_scheduled_payment(None, &id);
+	/// assert!(result.is_err());
+	/// ```
+
+	pub fn delete_scheduled_payment(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		id: &Uuid,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_schedule::delete_scheduled_payment(&mut **w, keychain_mask, id)
+	}
+
+	/// Adds a contact to the address book, or updates the contact with the same name. The
+	/// contact name can be used instead of the address by the `send`, `swap_start` and `invoice`
+	/// commands, and the transaction listings show it for the matching addresses.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `contact` - [`Contact`](../grin_wallet_libwallet/types/struct.Contact.html) to save. The name
+	/// is up to 32 letters, digits, '_' or '-', starting with a letter. If the proof address is not
+	/// specified, it is taken from the Tor or MQS address.
+	///
+	/// # Returns
+	/// * ``Ok([`Contact`](../grin_wallet_libwallet/types/struct.Contact.html))` the stored contact.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let contact = Contact {
+	///     name: "alice".to_owned(),
+	///     address: "http://127.0.0.1:3415".to_owned(),
+	///     method: "http".to_owned(),
+	///     proof_address: None,
+	///     apisecret: None,
+	/// };
+	/// let result = api_owner.save_contact(None, &contact);
+	/// assert!(result.is_ok());
+	/// ```
+
+	pub fn save_contact(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		contact: &Contact,
+	) -> Result<Contact, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_contacts::save_contact(&mut **w, keychain_mask, contact)
+	}
+
+	/// Returns the address book contacts, ordered by name.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * ``Ok(Vec<[`Contact`](../grin_wallet_libwallet/types/struct.Contact.html)>)`
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.contacts(None);
+	/// assert!(result.is_ok());
+	/// ```
+
+	pub fn contacts(&self, _keychain_mask: Option<&SecretKey>) -> Result<Vec<Contact>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_contacts::contacts(&**w)
+	}
+
+	/// Returns the address book contact by name.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `name` - Contact name.
+	///
+	/// # Returns
+	/// * ``Ok([`Contact`](../grin_wallet_libwallet/types/struct.Contact.html))`
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered
+	/// or the contact is not found.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.get_contact(None, "alice");
+	/// assert!(result.is_err());
+	/// ```
+
+	pub fn get_contact(
+		&self,
+		_keychain_mask: Option<&SecretKey>,
+		name: &str,
+	) -> Result<Contact, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_contacts::get_contact(&**w, name)
+	}
+
+	/// Deletes the address book contact. Transactions with the contact are not affected.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `name` - Contact name.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.delete_contact(None, "alice");
+	/// assert!(result.is_err());
+	/// ```
+
+	pub fn delete_contact(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		name: &str,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_contacts::delete_contact(&mut **w, keychain_mask, name)
+	}
+
+	/// Creates a payment request to this wallet. The request is shared with the payer as a URI,
+	/// see [`payment_request_uri`](struct.Owner.html#method.payment_request_uri). The paying
+	/// transaction has the request id as the slate id, the wallet accepts it only while the
+	/// request is open and tracks the request until the transaction is confirmed.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`PaymentRequestArgs`](../grin_wallet_libwallet/api_impl/types/struct.PaymentRequestArgs.html),
+	/// amount, payee address, memo, expiry and proof requirement of the request.
+	///
+	/// # Returns
+	/// * ``Ok([`PaymentRequest`](../grin_wallet_libwallet/types/struct.PaymentRequest.html))` the stored request.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let args = PaymentRequestArgs {
+	///     amount: 2_000_000_000,
+	///     address: "http://127.0.0.1:3415".to_owned(),
+	///     memo: Some("Order 42".to_owned()),
+	///     expiry_blocks: Some(1440),
+	///     expiry_time: None,
+	///     require_proof: false,
+	/// };
+	/// let result = api_owner.create_payment_request(None, &args);
+	/// if let Ok(request) = result {
+	///     let uri = api_owner.payment_request_uri(None, &request.id);
+	///     assert!(uri.is_ok());
+	/// }
+	/// ```
+
+	pub fn create_payment_request(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: &PaymentRequestArgs,
+	) -> Result<PaymentRequest, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_payment_requests::create_payment_request(&mut **w, keychain_mask, args)
+	}
+
+	/// Returns the payment requests of this wallet, oldest first. The status of the open requests
+	/// is updated from the transaction log: paid if the transaction is confirmed, expired if it
+	/// wasn't received before the expiry.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * ``Ok(Vec<[`PaymentRequest`](../grin_wallet_libwallet/types/struct.PaymentRequest.html)>)`
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.payment_requests(None);
+	/// assert!(result.is_ok());
+	/// ```
+
+	pub fn payment_requests(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<PaymentRequest>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_payment_requests::payment_requests(&mut **w, keychain_mask)
+	}
+
+	/// Cancels the open payment request. The wallet doesn't accept payments for it any more.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - Payment request id.
+	///
+	/// # Returns
+	/// * ``Ok([`PaymentRequest`](../grin_wallet_libwallet/types/struct.PaymentRequest.html))` the cancelled request.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered
+	/// or the request is not open.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.cancel_payment_request(None, &Uuid::new_v4());
+	/// assert!(result.is_err());
+	/// ```
+
+	pub fn cancel_payment_request(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		id: &Uuid,
+	) -> Result<PaymentRequest, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_payment_requests::cancel_payment_request(&mut **w, keychain_mask, id)
+	}
+
+	/// Returns the URI of the payment request to share with the payer, for example
+	/// `mwc:http://host:3415?amount=2&id=<uuid>&memo=Order%2042&expiry_height=1000`.
+	/// The URI is short enough for a QR code.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - Payment request id.
+	///
+	/// # Returns
+	/// * `Ok(String)` the request URI
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let result = api_owner.payment_request_uri(None, &Uuid::new_v4());
+	/// assert!(result.is_err());
+	/// ```
+
+	pub fn payment_request_uri(
+		&self,
+		_keychain_mask: Option<&SecretKey>,
+		id: &Uuid,
+	) -> Result<String, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		let request = owner_payment_requests::get_payment_request(&**w, id)?;
+		Ok(owner_payment_requests::payment_request_uri(&request))
+	}
+
+	/// Decodes the payment request URI on the payer side. To pay the request, call
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) with the request amount,
+	/// `payment_request_id` set to the request id and `payment_proof_recipient_address` set to
+	/// the request proof address, and send the slate to the request address.
+	///
+	/// # Arguments
+	/// * `uri` - Payment request URI.
+	///
+	/// # Returns
+	/// * ``Ok([`PaymentRequest`](../grin_wallet_libwallet/types/struct.PaymentRequest.html))` the decoded request.
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if the URI is invalid.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let uri = "mwc:http://127.0.0.1:3415?amount=2.5&id=4f7c5f3a-0c0f-4bd6-9e54-9ab4f1d2c3e1";
+	/// let request = api_owner.decode_payment_request(uri).unwrap();
+	/// assert_eq!(request.amount, 2_500_000_000);
+	/// ```
+
+	pub fn decode_payment_request(&self, uri: &str) -> Result<PaymentRequest, Error> {
+		owner_payment_requests::parse_payment_request_uri(uri)
+	}
+
+	/// Posts a completed transaction to the listening node for validation and inclusion in a block
+	/// for mining.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `tx` - A completed [`Transaction`](../grin_core/core/transaction/struct.Transaction.html),
+	/// typically the `tx` field in the transaction [`Slate`](../grin_wallet_libwallet/slate/struct.Slate.html).
+	/// * `fluff` - Instruct the node whether to use the Dandelion protocol when posting the
+	/// transaction. If `true`, the node should skip the Dandelion phase and broadcast the
+	/// transaction to all peers immediately. If `false`, the node will follow dandelion logic and
+	/// initiate the stem phase.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	/// let args = InitTxArgs {
+	///     src_acct_name: None,
+	///     amount: 2_000_000_000,
+	///     minimum_confirmations: 10,
+	///     max_outputs: 500,
+	///     num_change_outputs: 1,
+	///     selection_strategy_is_use_all: false,
+	///     message: Some("Post this tx".to_owned()),
+	///     ..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	&args,
+	/// 	1,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, None, 0);
+	///		//
+	///		// Retrieve slate back from recipient
+	///		//
+	///		let res = api_owner.finalize_tx(None, &slate);
+	///		let res = api_owner.post_tx(None, &slate.tx, true);
+	/// }
+	/// ```
+
+	pub fn post_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tx: &Transaction,
+		fluff: bool,
+	) -> Result<(), Error> {
+		let client = {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+			w.w2n_client().clone()
+		};
+		owner::post_tx(&client, tx, fluff)
+	}
+
+	/// Cancels a transaction. This entails:
+	/// * Setting the transaction status to either `TxSentCancelled` or `TxReceivedCancelled`
+	/// * Deleting all change outputs or recipient outputs associated with the transaction
+	/// * Setting the status of all assocatied inputs from `Locked` to `Spent` so they can be
+	/// used in new transactions.
+	///
+	/// Transactions can be cancelled by transaction log id or slate id (call with either set to
+	/// Some, not both)
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `tx_id` - If present, cancel by the [`TxLogEntry`](../grin_wallet_libwallet/types/struct.TxLogEntry.html) id
+	/// for the transaction.
+	///
+	/// * `tx_slate_id` - If present, cancel by the Slate id.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	/// let args = InitTxArgs {
+	///     src_acct_name: None,
+	///     amount: 2_000_000_000,
+	///     minimum_confirmations: 10,
+	///     max_outputs: 500,
+	///     num_change_outputs: 1,
+	///     selection_strategy_is_use_all: false,
+	///     message: Some("Cancel this tx".to_owned()),
+	///     ..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	&args,
+	/// 	1,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, None, 0);
+	///		//
+	///		// We didn't get the slate back, or something else went wrong
+	///		//
+	///		let res = api_owner.cancel_tx(None, None, Some(slate.id.clone()));
+	/// }
+	/// ```
+
+	pub fn cancel_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<(), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::cancel_tx(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			tx_id,
+			tx_slate_id,
+		)
+	}
+
+	/// Retrieves the stored transaction associated with a TxLogEntry. Can be used even after the
+	/// transaction has completed.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `tx_log_entry` - A [`TxLogEntry`](../grin_wallet_libwallet/types/struct.TxLogEntry.html)
+	///
+	/// # Returns
+	/// * Ok with the stored  [`Transaction`](../grin_core/core/transaction/struct.Transaction.html)
+	/// if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	/// let update_from_node = true;
+	/// let tx_id = None;
+	/// let tx_slate_id = None;
+	///
+	/// // Return all TxLogEntries
+	/// let result = api_owner.retrieve_txs(None, update_from_node, tx_id, tx_slate_id);
+	///
+	/// if let Ok((was_updated, tx_log_entries)) = result {
+	///     let stored_tx = api_owner.get_stored_tx(None, &tx_log_entries[0]).unwrap();
+	///     //...
+	/// }
+	/// ```
+
+	// TODO: Should be accepting an id, not an entire entry struct
+	pub fn get_stored_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tx_log_entry: &TxLogEntry,
+	) -> Result<Option<Transaction>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::get_stored_tx(&**w, tx_log_entry)
+	}
+
+	/// Loads a stored transaction from a file
+	pub fn load_stored_tx(&self, file: &String) -> Result<Transaction, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::load_stored_tx(&**w, file)
+	}
+
+	/// Verifies all messages in the slate match their public keys.
+	///
+	/// The optional messages themselves are part of the `participant_data` field within the slate.
+	/// Messages are signed with the same key used to sign for the paricipant's inputs, and can thus be
+	/// verified with the public key found in the `public_blind_excess` field. This function is a
+	/// simple helper to returns whether all signatures in the participant data match their public
+	/// keys.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `slate` - The transaction [`Slate`](../grin_wallet_libwallet/slate/struct.Slate.html).
+	///
+	/// # Returns
+	/// * `Ok(())` if successful and the signatures validate
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	/// let args = InitTxArgs {
+	///     src_acct_name: None,
+	///     amount: 2_000_000_000,
+	///     minimum_confirmations: 10,
+	///     max_outputs: 500,
+	///     num_change_outputs: 1,
+	///     selection_strategy_is_use_all: false,
+	///     message: Some("Just verify messages".to_owned()),
+	///     ..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	&args,
+	/// 	1,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, None, 0);
+	///		//
+	///		// Retrieve slate back from recipient
+	///		//
+	///		let res = api_owner.verify_slate_messages(None, &slate);
+	/// }
+	/// ```
+	pub fn verify_slate_messages(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+	) -> Result<(), Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		owner::verify_slate_messages(slate)
+	}
+
+	/// Scans the entire UTXO set from the node, identify which outputs belong to the given wallet
+	/// update the wallet state to be consistent with what's currently in the UTXO set.
+	///
+	/// This function can be used to repair wallet state, particularly by restoring outputs that may
+	/// be missing if the wallet owner has cancelled transactions locally that were then successfully
 	/// posted to the chain.
 	///
 	/// This operation scans the entire chain, and is expected to be time intensive. It is imperative
@@ -1435,14 +2580,52 @@ where
 			let t = self.status_tx.lock();
 			t.clone()
 		};
-		owner::scan(
-			self.wallet_inst.clone(),
-			keychain_mask,
-			start_height,
-			delete_unconfirmed,
-			&tx,
-			true,
-		)
+		owner::scan(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			start_height,
+			delete_unconfirmed,
+			&tx,
+			true,
+		)
+	}
+
+	/// Resumes the interrupted [`scan`](struct.Owner.html#method.scan). The scan saves its
+	/// progress (the last processed PMMR index and the outputs found so far) in the wallet DB,
+	/// the resumed scan continues from there with the same height range and parameters, so
+	/// the result is the same as the one of the uninterrupted scan. The wallet is brought
+	/// up to the current chain tip by the next update.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// there is no interrupted scan or the chain was changed since the scan was interrupted.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone(), None, None);
+	/// let result = api_owner.resume_scan(None);
+	///
+	/// if let Ok(_) = result {
+	///     // Interrupted scan is finished
+	///     // ...
+	/// }
+	/// ```
+
+	pub fn resume_scan(&self, keychain_mask: Option<&SecretKey>) -> Result<(), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::resume_scan(self.wallet_inst.clone(), keychain_mask, &tx)
 	}
 
 	/// Dump wallet data (outputs,transactions) into the logs
@@ -1678,7 +2861,8 @@ where
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
 	/// * `mnemonic`: If present, restore the wallet seed from the given mnemonic instead of creating
 	/// a new random seed.
 	/// * `mnemonic_length`: Desired length of mnemonic in bytes (16 or 32, either 12 or 24 words).
@@ -1710,50 +2894,327 @@ where
 	/// let api_owner = Owner::new(wallet.clone(), None, None);
 	/// let _ = api_owner.set_top_level_directory(dir);
 	///
-	/// // Create configuration
-	/// let result = api_owner.create_config(&ChainTypes::Mainnet,None,  None, None, None);
+	/// // Create configuration
+	/// let result = api_owner.create_config(&ChainTypes::Mainnet,None,  None, None, None);
+	///
+	///	// create new wallet wirh random seed
+	///	let pw = ZeroingString::from("my_password");
+	/// let result = api_owner.create_wallet(None, None, 0, pw, None);
+	///
+	/// if let Ok(r) = result {
+	///     //...
+	/// }
+	/// ```
+
+	pub fn create_wallet(
+		&self,
+		name: Option<&str>,
+		mnemonic: Option<ZeroingString>,
+		mnemonic_length: u32,
+		password: ZeroingString,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.create_wallet(
+			name,
+			mnemonic,
+			mnemonic_length as usize,
+			password,
+			self.doctest_mode,
+			wallet_data_dir,
+		)
+	}
+
+	/// Creates a new watch-only wallet from the view key exported with
+	/// [`get_view_key`](struct.Owner.html#method.get_view_key). The watch-only wallet finds
+	/// the outputs and tracks the balance of the original wallet, but it has no seed, so it can't
+	/// sign or spend anything.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	/// * `view_key`: View key of the original wallet, hex encoded.
+	/// * `password`: The password used to encrypt/decrypt the `wallet.view` file
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use grin_core::global::ChainTypes;
+	///
+	/// let dir = "path/to/wallet/dir";
+	///
+	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
+	/// # let dir = dir
+	/// #   .path()
+	/// #   .to_str()
+	/// #   .ok_or("Failed to convert tmpdir path to string.".to_owned())
+	/// #   .unwrap();
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	/// let _ = api_owner.set_top_level_directory(dir);
+	///
+	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	///
+	/// let view_key = "9bd7a7bd57f6d3d5ccd1b5bbcbc6da0b9fa28ed9d0c1d5d7a4f1a5bcc6d6e7f2";
+	/// let pw = ZeroingString::from("my_password");
+	/// let result = api_owner.create_view_wallet(None, view_key, pw, None);
+	///
+	/// if let Ok(r) = result {
+	///     //...
+	/// }
+	/// ```
+
+	pub fn create_view_wallet(
+		&self,
+		name: Option<&str>,
+		view_key: &str,
+		password: ZeroingString,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error> {
+		let view_key = ViewKey::from_hex(view_key)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.create_view_wallet(name, &view_key, password, wallet_data_dir)
+	}
+
+	/// `Opens` a wallet, populating the internal keychain with the encrypted seed, and optionally
+	/// returning a `keychain_mask` token to the caller to provide in all future calls.
+	/// If using a mask, the seed will be stored in-memory XORed against the `keychain_mask`, and
+	/// will not be useable if the mask is not provided.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	/// * `password`: The password to use to open the wallet
+	/// a new random seed.
+	/// * `use_mask`: Whether to create and return a mask which much be provided in all future
+	/// API calls.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use grin_core::global::ChainTypes;
+	///
+	/// // note that the WalletInst struct does not necessarily need to contain an
+	/// // instantiated wallet
+	/// let dir = "path/to/wallet/dir";
+	///
+	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
+	/// # let dir = dir
+	/// #   .path()
+	/// #   .to_str()
+	/// #   .ok_or("Failed to convert tmpdir path to string.".to_owned())
+	/// #   .unwrap();
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	/// let _ = api_owner.set_top_level_directory(dir);
+	///
+	/// // Create configuration
+	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None,  None, None, None);
+	///
+	///	// create new wallet wirh random seed
+	///	let pw = ZeroingString::from("my_password");
+	/// let _ = api_owner.create_wallet(None, None, 0, pw.clone(), None);
+	///
+	/// let result = api_owner.open_wallet(None, pw, true, None);
+	///
+	/// if let Ok(m) = result {
+	///     // use this mask in all subsequent calls
+	///     let mask = m;
+	/// }
+	/// ```
+
+	pub fn open_wallet(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		use_mask: bool,
+		wallet_data_dir: Option<&str>,
+	) -> Result<Option<SecretKey>, Error> {
+		// just return a representative string for doctest mode
+		if self.doctest_mode {
+			return Ok(Some(SecretKey::from_slice(
+				&from_hex("d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868")
+					.unwrap(),
+			)?));
+		}
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.open_wallet(name, password, use_mask, self.doctest_mode, wallet_data_dir)
+	}
+
+	/// `Close` a wallet, removing the master seed from memory.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use grin_core::global::ChainTypes;
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let res = api_owner.close_wallet(None);
+	///
+	/// if let Ok(_) = res {
+	///     // ...
+	/// }
+	/// ```
+
+	pub fn close_wallet(&self, name: Option<&str>) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.close_wallet(name)
+	}
+
+	/// List the named wallets that are hosted by the owner API listener next to the default
+	/// one. A named wallet is hosted from the moment it is opened by name until it is closed.
+	///
+	/// # Arguments
+	///
+	/// * None
+	///
+	/// # Returns
+	/// * Ok with the sorted names of the hosted wallets
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone(), None, None);
+	/// let result = api_owner.list_wallets();
+	///
+	/// if let Ok(names) = result {
+	///     println!("Hosted wallets: {:?}", names);
+	///     //...
+	/// }
+	/// ```
+
+	pub fn list_wallets(&self) -> Result<Vec<String>, Error> {
+		Ok(self.hosted_wallet_names.lock().clone())
+	}
+
+	/// Return the BIP39 mnemonic for the given wallet. This function will decrypt
+	/// the wallet's seed file with the given password, and thus does not need the
+	/// wallet to be open.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	/// * `password`: The password used to encrypt the seed file.
+	///
+	/// # Returns
+	/// * Ok(BIP-39 mneminc) if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use grin_core::global::ChainTypes;
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let res = api_owner.get_mnemonic(None, pw, None);
+	///
+	/// if let Ok(mne) = res {
+	///     // ...
+	/// }
+	/// ```
+	pub fn get_mnemonic(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		wallet_data_dir: Option<&str>,
+	) -> Result<ZeroingString, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.get_mnemonic(name, password, wallet_data_dir)
+	}
+
+	/// Creates a full backup of the wallet: seed, database, saved transactions, transaction
+	/// proofs and swap trades are packed into a single archive encrypted with the wallet
+	/// password. Unlike the recovery phrase, the backup keeps the transaction log, labels,
+	/// messages and payment proofs that can't be recovered from the chain. The wallet must be
+	/// closed, see [`close_wallet`](struct.Owner.html#method.close_wallet).
+	///
+	/// # Arguments
+	///
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	/// * `password`: The wallet password, it is used to encrypt the archive as well.
+	/// * `backup_file`: Path to the archive to create.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone(), None, None);
 	///
-	///	// create new wallet wirh random seed
 	///	let pw = ZeroingString::from("my_password");
-	/// let result = api_owner.create_wallet(None, None, 0, pw, None);
+	/// let res = api_owner.backup_wallet(None, pw, "wallet.backup", None);
 	///
-	/// if let Ok(r) = result {
-	///     //...
+	/// if let Ok(_) = res {
+	///     // ...
 	/// }
 	/// ```
-
-	pub fn create_wallet(
+	pub fn backup_wallet(
 		&self,
 		name: Option<&str>,
-		mnemonic: Option<ZeroingString>,
-		mnemonic_length: u32,
 		password: ZeroingString,
+		backup_file: &str,
 		wallet_data_dir: Option<&str>,
 	) -> Result<(), Error> {
 		let mut w_lock = self.wallet_inst.lock();
 		let lc = w_lock.lc_provider()?;
-		lc.create_wallet(
-			name,
-			mnemonic,
-			mnemonic_length as usize,
-			password,
-			self.doctest_mode,
-			wallet_data_dir,
-		)
+		lc.backup_wallet(name, password, backup_file, wallet_data_dir)
 	}
 
-	/// `Opens` a wallet, populating the internal keychain with the encrypted seed, and optionally
-	/// returning a `keychain_mask` token to the caller to provide in all future calls.
-	/// If using a mask, the seed will be stored in-memory XORed against the `keychain_mask`, and
-	/// will not be useable if the mask is not provided.
+	/// Restores the wallet from the archive created with
+	/// [`backup_wallet`](struct.Owner.html#method.backup_wallet). The wallet must not exist, the
+	/// restored wallet can be opened with the password it had when the backup was made.
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
-	/// * `password`: The password to use to open the wallet
-	/// a new random seed.
-	/// * `use_mask`: Whether to create and return a mask which much be provided in all future
-	/// API calls.
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	/// * `password`: The password the archive was created with.
+	/// * `backup_file`: Path to the archive.
 	///
 	/// # Returns
 	/// * Ok if successful
@@ -1764,63 +3225,42 @@ where
 	/// ```
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use grin_core::global::ChainTypes;
-	///
-	/// // note that the WalletInst struct does not necessarily need to contain an
-	/// // instantiated wallet
-	/// let dir = "path/to/wallet/dir";
-	///
-	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
-	/// # let dir = dir
-	/// #   .path()
-	/// #   .to_str()
-	/// #   .ok_or("Failed to convert tmpdir path to string.".to_owned())
-	/// #   .unwrap();
-	/// let api_owner = Owner::new(wallet.clone(), None, None);
-	/// let _ = api_owner.set_top_level_directory(dir);
-	///
-	/// // Create configuration
-	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None,  None, None, None);
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone(), None, None);
 	///
-	///	// create new wallet wirh random seed
 	///	let pw = ZeroingString::from("my_password");
-	/// let _ = api_owner.create_wallet(None, None, 0, pw.clone(), None);
-	///
-	/// let result = api_owner.open_wallet(None, pw, true, None);
+	/// let res = api_owner.restore_wallet(Some("restored"), pw, "wallet.backup", None);
 	///
-	/// if let Ok(m) = result {
-	///     // use this mask in all subsequent calls
-	///     let mask = m;
+	/// if let Ok(_) = res {
+	///     // ...
 	/// }
 	/// ```
-
-	pub fn open_wallet(
+	pub fn restore_wallet(
 		&self,
 		name: Option<&str>,
 		password: ZeroingString,
-		use_mask: bool,
+		backup_file: &str,
 		wallet_data_dir: Option<&str>,
-	) -> Result<Option<SecretKey>, Error> {
-		// just return a representative string for doctest mode
-		if self.doctest_mode {
-			return Ok(Some(SecretKey::from_slice(
-				&from_hex("d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868")
-					.unwrap(),
-			)?));
-		}
+	) -> Result<(), Error> {
 		let mut w_lock = self.wallet_inst.lock();
 		let lc = w_lock.lc_provider()?;
-		lc.open_wallet(name, password, use_mask, self.doctest_mode, wallet_data_dir)
+		lc.restore_wallet(name, password, backup_file, wallet_data_dir)
 	}
 
-	/// `Close` a wallet, removing the master seed from memory.
+	/// Migrates the wallet DB to the current schema version. The migrations run in a single
+	/// batch, the DB files are copied into the `db_backup_v<version>` directory first.
+	/// Opening the wallet applies the pending migrations as well, so the DB of the opened
+	/// wallet has nothing to migrate.
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
+	/// * `dry_run`: Only report the pending migrations.
 	///
 	/// # Returns
-	/// * Ok if successful
+	/// * Ok with the applied (or pending, for the dry run) migrations as
+	/// (schema version, description) if successful
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1828,35 +3268,40 @@ where
 	/// ```
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use grin_core::global::ChainTypes;
-	///
 	/// // Set up as above
 	/// # let api_owner = Owner::new(wallet.clone(), None, None);
 	///
-	/// let res = api_owner.close_wallet(None);
+	/// let res = api_owner.migrate_wallet_db(None, true, None);
 	///
-	/// if let Ok(_) = res {
-	///     // ...
+	/// if let Ok(pending) = res {
+	///     for (version, description) in pending {
+	///         println!("{}: {}", version, description);
+	///     }
 	/// }
 	/// ```
-
-	pub fn close_wallet(&self, name: Option<&str>) -> Result<(), Error> {
+	pub fn migrate_wallet_db(
+		&self,
+		name: Option<&str>,
+		dry_run: bool,
+		wallet_data_dir: Option<&str>,
+	) -> Result<Vec<(u32, String)>, Error> {
 		let mut w_lock = self.wallet_inst.lock();
 		let lc = w_lock.lc_provider()?;
-		lc.close_wallet(name)
+		lc.migrate_wallet_db(name, dry_run, wallet_data_dir)
 	}
 
-	/// Return the BIP39 mnemonic for the given wallet. This function will decrypt
-	/// the wallet's seed file with the given password, and thus does not need the
-	/// wallet to be open.
+	/// Copies the LMDB wallet DB, the saved transactions and the payment proofs into a new
+	/// SQLite DB in the wallet data directory. The LMDB data is left as is, the wallet switches
+	/// to SQLite once `db_backend = "sqlite"` is set in the config. The wallet must not be open
+	/// and must be built with the `sqlite` feature.
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
-	/// * `password`: The password used to encrypt the seed file.
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
 	///
 	/// # Returns
-	/// * Ok(BIP-39 mneminc) if successful
+	/// * Ok with the path of the SQLite DB if successful
 	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1864,27 +3309,23 @@ where
 	/// ```
 	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use grin_core::global::ChainTypes;
-	///
 	/// // Set up as above
 	/// # let api_owner = Owner::new(wallet.clone(), None, None);
 	///
-	///	let pw = ZeroingString::from("my_password");
-	/// let res = api_owner.get_mnemonic(None, pw, None);
+	/// let res = api_owner.migrate_wallet_db_to_sqlite(None, None);
 	///
-	/// if let Ok(mne) = res {
-	///     // ...
+	/// if let Ok(db_file) = res {
+	///     println!("SQLite wallet DB: {}", db_file);
 	/// }
 	/// ```
-	pub fn get_mnemonic(
+	pub fn migrate_wallet_db_to_sqlite(
 		&self,
 		name: Option<&str>,
-		password: ZeroingString,
 		wallet_data_dir: Option<&str>,
-	) -> Result<ZeroingString, Error> {
+	) -> Result<String, Error> {
 		let mut w_lock = self.wallet_inst.lock();
 		let lc = w_lock.lc_provider()?;
-		lc.get_mnemonic(name, password, wallet_data_dir)
+		lc.migrate_wallet_db_to_sqlite(name, wallet_data_dir)
 	}
 
 	/// Changes a wallet's password, meaning the old seed file is decrypted with the old password,
@@ -1897,7 +3338,8 @@ where
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
 	/// * `old`: The password used to encrypt the existing seed file (i.e. old password)
 	/// * `new`: The password to be used to encrypt the new seed file
 	///
@@ -1943,7 +3385,8 @@ where
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `name`: Wallet name. `None` is the default wallet, a named wallet is stored in
+	/// the sub directory of the top level directory with the same name.
 	///
 	/// # Returns
 	/// * Ok if successful
@@ -2036,11 +3479,23 @@ where
 			Some(m) => Some(m.clone()),
 			None => None,
 		};
+		let event_notifier = self.event_notifier.lock().clone();
+		let payment_executor: Arc<dyn PaymentExecutor> = Arc::new(OwnerPaymentExecutor {
+			wallet_inst: self.wallet_inst.clone(),
+			status_tx: Mutex::new(tx_inner.clone()),
+			tor_config: self.tor_config.lock().clone(),
+		});
 		let _ = thread::Builder::new()
 			.name("wallet-updater".to_string())
 			.spawn(move || {
 				let u = updater_inner.lock();
-				if let Err(e) = u.run(frequency, keychain_mask, &tx_inner) {
+				if let Err(e) = u.run(
+					frequency,
+					keychain_mask,
+					&tx_inner,
+					event_notifier,
+					Some(payment_executor),
+				) {
 					error!("Wallet state updater failed with error: {}", e);
 				}
 			})?;
@@ -2163,6 +3618,40 @@ where
 		owner::get_mqs_address(self.wallet_inst.clone(), keychain_mask)
 	}
 
+	/// Retrieve the view key of the wallet. The view key allows to identify the wallet outputs
+	/// at the chain, but not to spend them. Use it with
+	/// [`create_view_wallet`](struct.Owner.html#method.create_view_wallet) to build a watch-only
+	/// copy of this wallet.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	///
+	/// # Returns
+	/// * Ok with the hex encoded view key
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # grin_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone(), None, None);
+	///
+	/// let res = api_owner.get_view_key(None);
+	///
+	/// if let Ok(_) = res {
+	///   // ...
+	/// }
+	///
+	/// ```
+
+	pub fn get_view_key(&self, keychain_mask: Option<&SecretKey>) -> Result<String, Error> {
+		let view_key = owner::get_view_key(self.wallet_inst.clone(), keychain_mask)?;
+		Ok(view_key.to_hex())
+	}
+
 	/// Retrieve the Tor or wallet public address associated with the wallet. This address can be changed with
 	/// address index. In this case it will affect all wallet public addresses
 	///
@@ -2336,6 +3825,173 @@ where
 		owner::verify_payment_proof(self.wallet_inst.clone(), keychain_mask, proof)
 	}
 
+	/// Create a new multisig account with a shared output. This wallet is the first co-signer
+	/// and creates the funding transaction. The funding inputs are locked until the account is
+	/// funded or the funding transaction is cancelled.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - Funding transaction arguments, `amount` is the shared output amount.
+	/// * `num_participants` - Number of co-signers, including this wallet.
+	/// * `threshold` - Number of co-signers that are needed to spend the shared output.
+	/// * `contributions` - Amount that every co-signer pays for the shared output, in the order
+	/// of joining. They have to sum up to `args.amount`. If None, this wallet pays for all of it.
+	///
+	/// # Returns
+	/// * The account to pass to the next co-signer for
+	/// [`multisig_process`](struct.Owner.html#method.multisig_process).
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	pub fn multisig_create(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: &InitTxArgs,
+		num_participants: usize,
+		threshold: usize,
+		contributions: Option<Vec<u64>>,
+	) -> Result<MultisigAccount, Error> {
+		// Updating wallet state first because we need to select outputs.
+		owner::update_wallet_state(self.wallet_inst.clone(), keychain_mask, &None)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::multisig_create(
+			&mut **w,
+			keychain_mask,
+			args,
+			num_participants,
+			threshold,
+			contributions,
+		)
+	}
+
+	/// Process the multisig account received from another co-signer. The wallet joins the
+	/// account if it isn't a co-signer yet, then does every signing step that is due. Co-signers
+	/// pass the account to each other until the funding co-signer finalizes the funding
+	/// transaction. Spending goes the same way between the signers.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `account` - The account as received from the other co-signer.
+	/// * `args` - Selection of the inputs that pay our contribution to the shared output, used
+	/// when the wallet joins the account. `amount` is ignored, the contribution is defined by the
+	/// account. If None, the defaults are used.
+	///
+	/// # Returns
+	/// * The account to pass to the next co-signer, and the finalized transaction if this wallet
+	/// completed it. The transaction needs to be posted with
+	/// [`post_tx`](struct.Owner.html#method.post_tx).
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	pub fn multisig_process(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		account: &MultisigAccount,
+		args: Option<&InitTxArgs>,
+	) -> Result<(MultisigAccount, Option<Transaction>), Error> {
+		let joining = {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			w.get_multisig_account(&account.id)?.is_none()
+		};
+		if joining {
+			// Updating wallet state first because we might need to select outputs.
+			owner::update_wallet_state(self.wallet_inst.clone(), keychain_mask, &None)?;
+		}
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::multisig_process(&mut **w, keychain_mask, account, args)
+	}
+
+	/// Start spending the shared output of a funded multisig account. The funds go to this
+	/// wallet or to the recipient once the other signers sign the spending transaction.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `account_id` - The multisig account.
+	/// * `signers` - Co-signers (by their index) that sign the spending transaction. There have to
+	/// be `threshold` of them, including this wallet co-signer.
+	/// * `amount` - Amount for the recipient. If None, all of the shared output minus the fee.
+	/// The change goes to this wallet.
+	/// * `address` - Recipient address. If None, the shared output minus the fee goes to this
+	/// wallet. The recipient gets the slate from
+	/// [`multisig_spend_slate`](struct.Owner.html#method.multisig_spend_slate) once every signer
+	/// processed the account.
+	///
+	/// # Returns
+	/// * The account to pass to the next signer for
+	/// [`multisig_process`](struct.Owner.html#method.multisig_process).
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+	pub fn multisig_spend(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		account_id: &Uuid,
+		signers: &[usize],
+		amount: Option<u64>,
+		address: Option<String>,
+	) -> Result<MultisigAccount, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::multisig_spend(
+			&mut **w,
+			keychain_mask,
+			account_id,
+			signers,
+			amount,
+			address,
+		)
+	}
+
+	/// Retrieve the slate for the recipient of the multisig spending. It is ready once every
+	/// signer processed the account. The recipient receives it as a regular send slate, its
+	/// response goes to
+	/// [`multisig_spend_response`](struct.Owner.html#method.multisig_spend_response).
+	pub fn multisig_spend_slate(
+		&self,
+		_keychain_mask: Option<&SecretKey>,
+		account_id: &Uuid,
+	) -> Result<Slate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::multisig_spend_slate(&**w, account_id)
+	}
+
+	/// Apply the response slate of the multisig spending recipient and sign the spending
+	/// transaction. Returns the account to pass to the other signers, and the finalized
+	/// transaction if this wallet completed it, same as
+	/// [`multisig_process`](struct.Owner.html#method.multisig_process).
+	pub fn multisig_spend_response(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+	) -> Result<(MultisigAccount, Option<Transaction>), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::multisig_spend_response(&mut **w, keychain_mask, slate)
+	}
+
+	/// List the multisig accounts of the wallet
+	pub fn multisig_accounts(
+		&self,
+		_keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<MultisigAccount>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::multisig_accounts(&**w)
+	}
+
+	/// Retrieve the multisig account. The shareable copy of it can be passed to the other
+	/// co-signers again with [`MultisigAccount::export`](../grin_wallet_libwallet/struct.MultisigAccount.html#method.export).
+	pub fn multisig_account(
+		&self,
+		_keychain_mask: Option<&SecretKey>,
+		account_id: &Uuid,
+	) -> Result<MultisigAccount, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner_multisig::get_account(&**w, account_id)
+	}
+
 	/// Start swap trade process. Return SwapID that can be used to check the status or perform further action.
 	pub fn swap_start(
 		&self,
@@ -2359,6 +4015,19 @@ where
 		)
 	}
 
+	/// Create a Buyer swap trade from the offer message content. Return the swap trade Id.
+	pub fn swap_create_from_offer_message(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		message: String,
+	) -> Result<String, Error> {
+		owner_swap::swap_create_from_offer_message(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&message,
+		)
+	}
+
 	/// List all available swap operations. SwapId & Status
 	pub fn swap_list(
 		&self,
@@ -2500,6 +4169,108 @@ where
 		)
 	}
 
+	/// Process the swap action the same way as [`swap_process`](struct.Owner.html#method.swap_process),
+	/// delivering the swap messages with transports that are already running in this process.
+	/// This is the method used by the Owner JSON-RPC API, where the caller can't provide
+	/// a message delivery closure. The files of the wallet host are never used, the messages
+	/// are passed in and out as content.
+	///
+	/// Supported methods (stored with the swap trade): `mwcmqs` (the mwcmqs listener must be running),
+	/// `tor` (the tor configuration must be set with [`set_tor_config`](struct.Owner.html#method.set_tor_config))
+	/// and `file` (the message is returned to the caller, who delivers it to the other party).
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `swap_id` - Swap trade Id.
+	/// * `apisecret` - API secret of the other party wallet, if required.
+	/// * `message` - Income message from the other party, if the file method is used. It is
+	/// processed before the action.
+	/// * `buyer_refund_address` - Secondary currency refund address for the Buyer.
+	/// * `secondary_fee` - Secondary currency transaction fee.
+	/// * `secondary_address` - Secondary currency redeem or refund address.
+	/// * `electrum_node_uri1` - Optional ElectrumX node URI.
+	/// * `electrum_node_uri2` - Optional ElectrumX failover node URI.
+	///
+	/// # Returns
+	/// * Ok([`StateProcessRespond`](../grin_wallet_libwallet/swap/fsm/state/struct.StateProcessRespond.html))
+	/// with the new state and the next expected action, and the message (json string) for the
+	/// other party if the file method is used
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered.
+
+	pub fn swap_process_with_transport(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		swap_id: &str,
+		apisecret: Option<String>,
+		message: Option<String>,
+		buyer_refund_address: Option<String>,
+		secondary_fee: Option<f32>,
+		secondary_address: Option<String>,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<(StateProcessRespond, Option<String>), Error> {
+		if let Some(message) = message {
+			self.swap_income_message(keychain_mask, message)?;
+		}
+
+		let tor_config = self.tor_config.lock().clone();
+		// Offer needs our return address. For tor it is the wallet public onion address.
+		let tor_address = self
+			.get_wallet_public_address(keychain_mask)
+			.map(|pk| ProvableAddress::from_tor_pub_key(&pk).public_key)
+			.ok();
+		let out_message = Arc::new(Mutex::new(None));
+		let out_message2 = out_message.clone();
+
+		let message_sender = move |swap_message: Message,
+		                           method: String,
+		                           dest: String|
+		      -> Result<(bool, String), Error> {
+			if method == "file" {
+				// Caller delivers the message, it is considered as delivered
+				out_message2.lock().replace(swap_message.to_json()?);
+				return Ok((true, format!("{} {}", method, dest)));
+			}
+			deliver_swap_message(
+				swap_message,
+				&method,
+				&dest,
+				&apisecret,
+				tor_config,
+				|method| match method {
+					"mwcmqs" => Ok(get_mwcmqs_brocker()
+						.ok_or(ErrorKind::SwapError(
+							"mwcmqs listener is not running".to_string(),
+						))?
+						.0
+						.get_publisher_address()
+						.map_err(|e| {
+							ErrorKind::SwapError(format!("Unable to get publisher address, {}", e))
+						})?
+						.get_full_name()),
+					_ => Ok(tor_address.ok_or(ErrorKind::SwapError(
+						"Unable to build the wallet tor address".to_string(),
+					))?),
+				},
+			)
+		};
+
+		let respond = self.swap_process(
+			keychain_mask,
+			swap_id,
+			message_sender,
+			None,
+			buyer_refund_address,
+			secondary_fee,
+			secondary_address,
+			electrum_node_uri1,
+			electrum_node_uri2,
+		)?;
+		let out_message = out_message.lock().take();
+		Ok((respond, out_message))
+	}
+
 	/// Process swap income message
 	pub fn swap_income_message(
 		&self,
@@ -2597,7 +4368,11 @@ macro_rules! doctest_helper_setup_doc_env {
 		use api::{Foreign, Owner};
 		use config::WalletConfig;
 		use impls::{DefaultLCProvider, DefaultWalletImpl, HTTPNodeClient};
-		use libwallet::{BlockFees, InitTxArgs, IssueInvoiceTxArgs, Slate, WalletInst};
+		use libwallet::{
+			BatchPayout, BlockFees, CoinSelectionStrategy, ConsolidateArgs, Contact, InitTxArgs,
+			IssueInvoiceTxArgs, PaymentRequestArgs, SchedulePaymentArgs, Slate, SplitArgs,
+			TxExportArgs, TxExportFormat, TxQueryArgs, TxQuerySortOrder, WalletInst,
+		};
 
 		use uuid::Uuid;
 
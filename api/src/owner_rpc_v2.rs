@@ -23,7 +23,7 @@ use crate::libwallet::slate_versions::v3::TransactionV3;
 use crate::libwallet::{
 	AcctPathMapping, ErrorKind, InitTxArgs, IssueInvoiceTxArgs, NodeClient, NodeHeightResult,
 	OutputCommitMapping, PaymentProof, Slate, SlatePurpose, SlateVersion, StatusMessage,
-	TxLogEntry, VersionedSlate, WalletInfo, WalletLCProvider,
+	TxLogEntry, TxQueryArgs, VersionedSlate, WalletInfo, WalletLCProvider,
 };
 use crate::types::{SlatepackInfo, TxLogEntryAPI};
 use crate::util;
@@ -315,6 +315,82 @@ pub trait OwnerRpcV2: Sync + Send {
 		tx_slate_id: Option<Uuid>,
 	) -> Result<(bool, Vec<TxLogEntryAPI>), ErrorKind>;
 
+	/**
+	Networked version of [Owner::query_txs](struct.Owner.html#method.query_txs).
+
+	# Json rpc example
+
+	```
+		# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "query_txs",
+			"params": {
+				"refresh_from_node": true,
+				"query": {
+					"tx_types": ["ConfirmedCoinbase"],
+					"confirmed": true,
+					"min_amount": "1000000000",
+					"sort_field": "id",
+					"sort_order": "desc",
+					"limit": 1
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+		  "id": 1,
+		  "jsonrpc": "2.0",
+		  "result": {
+			"Ok": [
+			  true,
+			  [
+				{
+				  "address": null,
+				  "amount_credited": "2380952380",
+				  "amount_debited": "0",
+				  "confirmation_ts": "2019-01-15T16:01:26Z",
+				  "confirmed": true,
+				  "creation_ts": "2019-01-15T16:01:26Z",
+				  "fee": null,
+				  "id": 1,
+				  "input_commits": [],
+				  "kernel_excess": "09f7677adc7caf8bb44a4ee27d27dfe9ffa1010847a18b182bbb7100bb02f9259e",
+				  "kernel_offset": null,
+				  "kernel_lookup_min_height": 2,
+				  "messages": null,
+				  "num_inputs": 0,
+				  "num_outputs": 1,
+				  "output_commits": [
+					"098778ce2243fa34e5876c8cb7f6dbbbd6a5649c1561973a807a6811941c12363c"
+				  ],
+				  "output_height": 2,
+				  "parent_key_id": "0200000000000000000000000000000000",
+				  "payment_proof": null,
+				  "stored_tx": null,
+				  "ttl_cutoff_height": null,
+				  "tx_slate_id": null,
+				  "tx_type": "ConfirmedCoinbase"
+				}
+			  ]
+			]
+		  }
+		}
+	# "#
+	# , false, 2, false, false, false, false, true);
+	```
+	*/
+
+	fn query_txs(
+		&self,
+		refresh_from_node: bool,
+		query: TxQueryArgs,
+	) -> Result<(bool, Vec<TxLogEntryAPI>), ErrorKind>;
+
 	/**
 	Networked version of [Owner::retrieve_summary_info](struct.Owner.html#method.retrieve_summary_info).
 
@@ -2847,6 +2923,23 @@ where
 			})
 	}
 
+	fn query_txs(
+		&self,
+		refresh_from_node: bool,
+		query: TxQueryArgs,
+	) -> Result<(bool, Vec<TxLogEntryAPI>), ErrorKind> {
+		Owner::query_txs(self, None, refresh_from_node, &query)
+			.map_err(|e| e.kind())
+			.map(|(b, tx)| {
+				(
+					b,
+					tx.iter()
+						.map(|t| TxLogEntryAPI::from_txlogemtry(t))
+						.collect(),
+				)
+			})
+	}
+
 	fn retrieve_summary_info(
 		&self,
 		refresh_from_node: bool,
@@ -3205,6 +3298,11 @@ pub fn run_doctest_owner(
 	let mask1 = lc
 		.open_wallet(None, empty_string.clone(), use_token, true, None)
 		.unwrap();
+	// swap trades are stored next to the wallet data
+	grin_wallet_libwallet::swap::trades::init_swap_trade_backend(
+		&format!("{}/wallet1", test_dir),
+		&None,
+	);
 	let wallet1 = Arc::new(Mutex::new(wallet1));
 
 	if mask1.is_some() {
@@ -19,11 +19,18 @@ use crate::config::{MQSConfig, TorConfig, WalletConfig};
 use crate::core::core::Transaction;
 use crate::core::global;
 use crate::keychain::{Identifier, Keychain};
+use crate::libwallet::api_impl::owner_swap::SwapListInfo;
 use crate::libwallet::slate_versions::v3::TransactionV3;
+use crate::libwallet::swap::fsm::state::{StateEtaInfo, StateId, StateProcessRespond};
+use crate::libwallet::swap::message::Message;
+use crate::libwallet::swap::swap::{Swap, SwapJournalRecord};
+use crate::libwallet::swap::types::{Action, SwapTransactionsConfirmations};
 use crate::libwallet::{
-	AcctPathMapping, ErrorKind, InitTxArgs, IssueInvoiceTxArgs, NodeClient, NodeHeightResult,
-	OutputCommitMapping, PaymentProof, Slate, SlatePurpose, SlateVersion, StatusMessage,
-	TxLogEntry, VersionedSlate, WalletInfo, WalletLCProvider,
+	AcctPathMapping, BatchPayout, ConsolidateArgs, Contact, ErrorKind, FeeEstimate, InitTxArgs,
+	IssueInvoiceTxArgs, MultisigAccount, NodeClient, NodeHeightResult, OutputCommitMapping,
+	PaymentProof, PaymentRequest, PaymentRequestArgs, SchedulePaymentArgs, ScheduledPayment, Slate,
+	SlatePurpose, SlateVersion, SplitArgs, StatusMessage, SwapStartArgs, TxExportArgs, TxLogEntry,
+	TxQueryArgs, UtxoTx, VersionedSlate, WalletInfo, WalletLCProvider,
 };
 use crate::types::{SlatepackInfo, TxLogEntryAPI};
 use crate::util;
@@ -226,6 +233,123 @@ pub trait OwnerRpcV3 {
 		tx_id: Option<u32>,
 	) -> Result<(bool, Vec<OutputCommitMapping>), ErrorKind>;
 
+	/**
+	Networked version of [Owner::set_output_label](struct.Owner.html#method.set_output_label).
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_output_label",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
+			"label": "savings"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": null
+	  }
+	}
+	# "#
+	# , true, 2, false, false, false, false, true);
+	```
+	*/
+	fn set_output_label(
+		&self,
+		token: Token,
+		commit: String,
+		label: Option<String>,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_output_note](struct.Owner.html#method.set_output_note).
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_output_note",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
+			"note": "Received from the exchange"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": null
+	  }
+	}
+	# "#
+	# , true, 2, false, false, false, false, true);
+	```
+	*/
+	fn set_output_note(
+		&self,
+		token: Token,
+		commit: String,
+		note: Option<String>,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_output_frozen](struct.Owner.html#method.set_output_frozen).
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_output_frozen",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
+			"frozen": true
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": null
+	  }
+	}
+	# "#
+	# , true, 2, false, false, false, false, true);
+	```
+	*/
+	fn set_output_frozen(
+		&self,
+		token: Token,
+		commit: String,
+		frozen: bool,
+	) -> Result<(), ErrorKind>;
+
 	/**
 	Networked version of [Owner::retrieve_txs](struct.Owner.html#method.retrieve_txs).
 
@@ -326,6 +450,132 @@ pub trait OwnerRpcV3 {
 		tx_slate_id: Option<Uuid>,
 	) -> Result<(bool, Vec<TxLogEntryAPI>), ErrorKind>;
 
+	/**
+	Networked version of [Owner::query_txs](struct.Owner.html#method.query_txs).
+
+	# Json rpc example
+
+	```
+		# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "query_txs",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"refresh_from_node": true,
+				"query": {
+					"tx_types": ["ConfirmedCoinbase"],
+					"confirmed": true,
+					"min_amount": "1000000000",
+					"sort_field": "id",
+					"sort_order": "desc",
+					"limit": 1
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+		  "id": 1,
+		  "jsonrpc": "2.0",
+		  "result": {
+			"Ok": [
+			  true,
+			  [
+				{
+				  "address": null,
+				  "amount_credited": "2380952380",
+				  "amount_debited": "0",
+				  "confirmation_ts": "2019-01-15T16:01:26Z",
+				  "confirmed": true,
+				  "creation_ts": "2019-01-15T16:01:26Z",
+				  "fee": null,
+				  "id": 1,
+				  "input_commits": [],
+				  "kernel_excess": "09f7677adc7caf8bb44a4ee27d27dfe9ffa1010847a18b182bbb7100bb02f9259e",
+				  "kernel_offset": null,
+				  "kernel_lookup_min_height": 2,
+				  "messages": null,
+				  "num_inputs": 0,
+				  "num_outputs": 1,
+				  "output_commits": [
+					"098778ce2243fa34e5876c8cb7f6dbbbd6a5649c1561973a807a6811941c12363c"
+				  ],
+				  "output_height": 2,
+				  "parent_key_id": "0200000000000000000000000000000000",
+				  "payment_proof": null,
+				  "stored_tx": null,
+				  "ttl_cutoff_height": null,
+				  "tx_slate_id": null,
+				  "tx_type": "ConfirmedCoinbase"
+				}
+			  ]
+			]
+		  }
+		}
+	# "#
+	# , true, 2, false, false, false, false, true);
+	```
+	*/
+
+	fn query_txs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		query: TxQueryArgs,
+	) -> Result<(bool, Vec<TxLogEntryAPI>), ErrorKind>;
+
+	/**
+	Networked version of [Owner::export_txs](struct.Owner.html#method.export_txs).
+
+	```
+		# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "export_txs",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"refresh_from_node": true,
+				"args": {
+					"account": "default",
+					"start_date": null,
+					"end_date": null,
+					"start_height": "1000",
+					"end_height": null,
+					"format": "csv"
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+		  "id": 1,
+		  "jsonrpc": "2.0",
+		  "result": {
+			"Ok": [
+			  true,
+			  "account,id,tx_slate_id,tx_type,creation_ts,confirmed,confirmation_ts,height,amount_credited,amount_debited,fee,address,kernel_excess,payment_proof\n"
+			]
+		  }
+		}
+	# "#
+	# , true, 2, false, false, false, false, true);
+	```
+	*/
+
+	fn export_txs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		args: TxExportArgs,
+	) -> Result<(bool, String), ErrorKind>;
+
 	/**
 	Networked version of [Owner::retrieve_summary_info](struct.Owner.html#method.retrieve_summary_info).
 
@@ -394,6 +644,7 @@ pub trait OwnerRpcV3 {
 				"max_outputs": 500,
 				"num_change_outputs": 1,
 				"selection_strategy_is_use_all": true,
+				"selection_strategy": "all",
 				"message": "my message",
 				"target_slate_version": null,
 				"payment_proof_recipient_address": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
@@ -728,6 +979,47 @@ pub trait OwnerRpcV3 {
 
 	fn init_send_tx(&self, token: Token, args: InitTxArgs) -> Result<VersionedSlate, ErrorKind>;
 
+	/**
+	Networked version of [Owner::estimate_fee](struct.Owner.html#method.estimate_fee).
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "estimate_fee",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"src_acct_name": null,
+				"amount": "2000000000",
+				"minimum_confirmations": 0,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": false,
+				"selection_strategy": "smallest"
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "ClientCallback": "Minimum_confirmations can not be smaller than 1"
+		}
+	  }
+	}
+	# "#
+	# , true, 4, false, false, false, false, false);
+	```
+	*/
+	fn estimate_fee(&self, token: Token, args: InitTxArgs) -> Result<FeeEstimate, ErrorKind>;
+
 	/**
 	Networked version of [Owner::issue_invoice_tx](struct.Owner.html#method.issue_invoice_tx).
 
@@ -1815,87 +2107,68 @@ pub trait OwnerRpcV3 {
 		-> Result<VersionedSlate, ErrorKind>;
 
 	/**
-	Networked version of [Owner::post_tx](struct.Owner.html#method.post_tx).
+	Networked version of [Owner::init_batch_send_tx](struct.Owner.html#method.init_batch_send_tx).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"id": 1,
-		"method": "post_tx",
+		"method": "init_batch_send_tx",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx": {
-				"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
-				"body": {
-					"inputs": [
-						{
-						  "commit": "098778ce2243fa34e5876c8cb7f6dbbbd6a5649c1561973a807a6811941c12363c",
-						  "features": "Coinbase"
-						},
-						{
-						  "commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
-						  "features": "Coinbase"
-						}
-					],
-					"outputs": [
-						{
-						  "commit": "082967b3fe580cd110355010ef45450314fb067720db01b0e6873bb083d76708c9",
-						  "features": "Plain",
-						  "proof": "828bb24121aa0332c872062a42a8333c3ef81f8ae37d24053d953217368b3cada90410a50509a0b9fcbb5aded41397fc00ca1ff5acdac20d48afb0a3281d21e7026d32fdc6c5157461a35f98a809ffa09187c1e170ea24652ad213b7e4c9878654ac3dd9a8915eaf742db53182fcb42d2d341fbdfe8bd31bd001f4ff2c1ca9f9b1531da29137214f211edb7a5eb8f494cb8945f8527dd25bf7e698515043db4249540720008a708db5342230d05b069c094688ccb7c07d4a4a2293ea76cf999c555dc0ddc757891c360db1901bbb4dc20cae997f875f8de482d8160e05d60f9b0135e0fc313d8f953db78f1ea252449dd81cfa22dd895512ed39d566f0924542b543d25fc9fc7a819d228f3b0ee5e381f088f54893e86437dafc49dd923b3e6dff956ca843f951910379531fac9bb5fd01a182dd32a4c597f92da3c01af37cb9b0ec984500884438e74e54d7e76fa1ae7241d5050b13376310b24761634a6f6eb7cf000082f50ed7c1899d7918023d4f877586f964932a7af72e7a4984ddecfdd1921a2e1b80b00d6bd2e64a3f4cb6915a27a8d17a69d163cf45220a13fcddd15dc2bb91ae4f1b6a67224ab3b23e8d7d785df178ec78a84cf42cea086426f563822c8a4271a0b89bb21f84b643dbf1de21b6395039d673a376492767199fa36ccd9a13628ce61695424091acc16059450d59bc59fa7879e7306f5727217211b0264a6a560f886d520e41406ef45b1668805b88d246c5b2ca5a1762042c85be34fcd420ac3843f32236d079b4bd57d6b8d8013d9d18f8efb55e8e443cd9e1af9b144e7a56c8c6be0138af3b4a6c99bee9109bed2bce2e5145e736b125a2ec19aaf3fff713f6897fdd4158ce2ab04706b062ca2847bf70259c0fc4b0d390dc7fdaf0362047f775a912bd22da9d40f04d9790bcd5ece4b36b74c6c340b48c2926b916e8a9"
-						},
-						{
-						  "commit": "096e1669267c22ecb38c466d73b8578261d8e91c14dd66702dd5bf34f4232e10db",
-						  "features": "Plain",
-						  "proof": "7d567b0895a1103d19446929da8b98f2086819507ddce4b9dbb5ce6327107744e74aba59ef1834937da1b86eb7c1c1b0bc11d1c5d5ec79d25bc1e52aed1656f60d46f6878ba5ca8639efdbb9203e378e91171c11527c4a34713f06dc22f58ca4a08e68d83ff897e61cfc145fe376fa428b55e25cf20d15f10b9054778229798b30fb4e45d817a5053b682dcf591481a3c8174cfbba81e31aa525d5b884ca7a016713178f26c0fe8ae1f88b5382f8e70c4d91fb3828c0f307d828aa028281d3551525e68d20827ab0e6785c6b5747e895dcd38429b44e62b7f6c1c921d87ae954a9dd6e967ac52e6cd13a1d4bb2f1434da25a0723ef9c869cc573019577552dd0e0f808f8cc57723b041320025f6433779fe907998a4ec7606bf884b2199253b502065bed8e0625c2df858d6508c1aa44deddc68d06d00d81e97720e23e15a3464ed4733fc547e9fb772e563a1dbcd27ac55e40f674f9006e7dd4465444f3eb7527cb01905dee69a51cf2fc1810c861dd0834e7649d594c3e1740d85343a6b63c8a9e0a0f63059031899b38dfd9a192034d54029bd35e683ccab46282519b26cae20d398b754357abe1cf0370890f2897b5d8ada4fb3da777a8f8f1daa4197a380e6734504117dd2a92ea1917f174c44c59e0b50c6b7a5f9eb14e6d96cb6b3e5dbcb3d0eaf0e4aac1b6616d674bb708b7559e37de608e8a828bee7f25f627e2f06d9a87e8d651ade39e1e65db7204b94abc0b7ca6fdd75aadeeac6a876b6297e38039734ebdfa9a555152b4293cb00e423a66d64f827afa4748dd6fdc1dc33332bffb820dacbf5a6d347042db985bbd9cf476dceb45d6978035ba03d25612243fc164c0a902017ce7ffd632d041fa3c56554739e78c6d725ecbfdaa0739d3649239fb53294b7a46ee6ed403bf3815f6c78f06a8ca4e3c9b066234f7574fb6ea8f17d199"
-						}
-					],
-					"kernels": [
-						{
-						  "excess": "08b3b8b83c622f630141a66c9cad96e19c78f745e4e2ddea85439f05d14a404640",
-						  "excess_sig": "66074d25a751c4743342c90ad8ead9454daa00d9b9aed29bca321036d16c4b4d1f1ac30ec6809c5e1a983a83af0deb0635b892e5e0ea3a3bd7f68be99f721348",
-						  "features": "Plain",
-						  "fee": "7000000",
-						  "lock_height": "0"
-						}
-					]
-				}
+			"args": {
+				"src_acct_name": null,
+				"amount": "0",
+				"minimum_confirmations": 2,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": true,
+				"message": "my message",
+				"target_slate_version": null,
+				"payment_proof_recipient_address": null,
+				"ttl_blocks": null,
+				"send_args": null
 			},
-			"fluff": false
-		}
+			"payouts": []
+		},
+		"id": 1
 	}
 	# "#
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Batch payout needs at least one recipient"
 		}
+	  }
 	}
 	# "#
-	# , true, 5, true, true, true, false, true);
+	# , true, 4, false, false, false, false, false);
 	```
-	 */
-
-	fn post_tx(&self, token: Token, tx: TransactionV3, fluff: bool) -> Result<(), ErrorKind>;
+	*/
+	fn init_batch_send_tx(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+		payouts: Vec<BatchPayout>,
+	) -> Result<Vec<VersionedSlate>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::cancel_tx](struct.Owner.html#method.cancel_tx).
-
+	Networked version of [Owner::finalize_batch_tx](struct.Owner.html#method.finalize_batch_tx).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "cancel_tx",
+		"method": "finalize_batch_tx",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx_id": null,
-			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
+			"slates": []
 		},
 		"id": 1
 	}
@@ -1903,24 +2176,43 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Batch payout doesn't have any slates"
 		}
+	  }
 	}
 	# "#
-	# , true, 5, true, true, false, false, true);
-	#
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn finalize_batch_tx(
+		&self,
+		token: Token,
+		slates: Vec<VersionedSlate>,
+	) -> Result<TransactionV3, ErrorKind>;
+
+	/**
+	Networked version of [Owner::consolidate_outputs](struct.Owner.html#method.consolidate_outputs).
+
+	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "cancel_tx",
+		"method": "consolidate_outputs",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx_id": 5,
-			"tx_slate_id": null
+			"args": {
+				"src_acct_name": null,
+				"minimum_confirmations": "0",
+				"num_outputs": 100,
+				"max_inputs": 500,
+				"fluff": false
+			},
+			"preview": true
 		},
 		"id": 1
 	}
@@ -1928,41 +2220,46 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "ClientCallback": "Minimum_confirmations can not be smaller than 1"
 		}
+	  }
 	}
 	# "#
-	# , true, 5, true, true, false, false, true);
+	# , true, 4, false, false, false, false, false);
 	```
-	 */
-	fn cancel_tx(
+	*/
+	fn consolidate_outputs(
 		&self,
 		token: Token,
-		tx_id: Option<u32>,
-		tx_slate_id: Option<Uuid>,
-	) -> Result<(), ErrorKind>;
+		args: ConsolidateArgs,
+		preview: bool,
+	) -> Result<Vec<UtxoTx>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_stored_tx](struct.Owner.html#method.get_stored_tx).
+	Networked version of [Owner::split_output](struct.Owner.html#method.split_output).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_stored_tx",
-		"id": 1,
+		"method": "split_output",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx": {
-				"stored_tx": "0436430c-2b02-624c-2032-570501212b00.mwctx",
-				"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00",
-				"tx_type": "TxSent"
-			}
-		}
+			"args": {
+				"src_acct_name": null,
+				"minimum_confirmations": "0",
+				"output": "087df32304c5d4ae8b2af0bc31e700019d722910ef87dd4eec3197b80b207e3045",
+				"amounts": [1000000000, 2000000000],
+				"fluff": false
+			},
+			"preview": true
+		},
+		"id": 1
 	}
 	# "#
 	# ,
@@ -1971,150 +2268,82 @@ pub trait OwnerRpcV3 {
 	  "id": 1,
 	  "jsonrpc": "2.0",
 	  "result": {
-		"Ok": {
-		  "body": {
-			"inputs": [
-			  {
-				"commit": "098778ce2243fa34e5876c8cb7f6dbbbd6a5649c1561973a807a6811941c12363c",
-				"features": "Coinbase"
-			  },
-			  {
-				"commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
-				"features": "Coinbase"
-			  }
-			],
-			"kernels": [
-			  {
-				"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-				"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-				"features": "Plain",
-				"fee": "7000000",
-				"lock_height": "0"
-			  }
-			],
-			"outputs": [
-			  {
-				"commit": "082967b3fe580cd110355010ef45450314fb067720db01b0e6873bb083d76708c9",
-				"features": "Plain",
-				"proof": "828bb24121aa0332c872062a42a8333c3ef81f8ae37d24053d953217368b3cada90410a50509a0b9fcbb5aded41397fc00ca1ff5acdac20d48afb0a3281d21e7026d32fdc6c5157461a35f98a809ffa09187c1e170ea24652ad213b7e4c9878654ac3dd9a8915eaf742db53182fcb42d2d341fbdfe8bd31bd001f4ff2c1ca9f9b1531da29137214f211edb7a5eb8f494cb8945f8527dd25bf7e698515043db4249540720008a708db5342230d05b069c094688ccb7c07d4a4a2293ea76cf999c555dc0ddc757891c360db1901bbb4dc20cae997f875f8de482d8160e05d60f9b0135e0fc313d8f953db78f1ea252449dd81cfa22dd895512ed39d566f0924542b543d25fc9fc7a819d228f3b0ee5e381f088f54893e86437dafc49dd923b3e6dff956ca843f951910379531fac9bb5fd01a182dd32a4c597f92da3c01af37cb9b0ec984500884438e74e54d7e76fa1ae7241d5050b13376310b24761634a6f6eb7cf000082f50ed7c1899d7918023d4f877586f964932a7af72e7a4984ddecfdd1921a2e1b80b00d6bd2e64a3f4cb6915a27a8d17a69d163cf45220a13fcddd15dc2bb91ae4f1b6a67224ab3b23e8d7d785df178ec78a84cf42cea086426f563822c8a4271a0b89bb21f84b643dbf1de21b6395039d673a376492767199fa36ccd9a13628ce61695424091acc16059450d59bc59fa7879e7306f5727217211b0264a6a560f886d520e41406ef45b1668805b88d246c5b2ca5a1762042c85be34fcd420ac3843f32236d079b4bd57d6b8d8013d9d18f8efb55e8e443cd9e1af9b144e7a56c8c6be0138af3b4a6c99bee9109bed2bce2e5145e736b125a2ec19aaf3fff713f6897fdd4158ce2ab04706b062ca2847bf70259c0fc4b0d390dc7fdaf0362047f775a912bd22da9d40f04d9790bcd5ece4b36b74c6c340b48c2926b916e8a9"
-			  },
-			  {
-				"commit": "096e1669267c22ecb38c466d73b8578261d8e91c14dd66702dd5bf34f4232e10db",
-				"features": "Plain",
-				"proof": "7d567b0895a1103d19446929da8b98f2086819507ddce4b9dbb5ce6327107744e74aba59ef1834937da1b86eb7c1c1b0bc11d1c5d5ec79d25bc1e52aed1656f60d46f6878ba5ca8639efdbb9203e378e91171c11527c4a34713f06dc22f58ca4a08e68d83ff897e61cfc145fe376fa428b55e25cf20d15f10b9054778229798b30fb4e45d817a5053b682dcf591481a3c8174cfbba81e31aa525d5b884ca7a016713178f26c0fe8ae1f88b5382f8e70c4d91fb3828c0f307d828aa028281d3551525e68d20827ab0e6785c6b5747e895dcd38429b44e62b7f6c1c921d87ae954a9dd6e967ac52e6cd13a1d4bb2f1434da25a0723ef9c869cc573019577552dd0e0f808f8cc57723b041320025f6433779fe907998a4ec7606bf884b2199253b502065bed8e0625c2df858d6508c1aa44deddc68d06d00d81e97720e23e15a3464ed4733fc547e9fb772e563a1dbcd27ac55e40f674f9006e7dd4465444f3eb7527cb01905dee69a51cf2fc1810c861dd0834e7649d594c3e1740d85343a6b63c8a9e0a0f63059031899b38dfd9a192034d54029bd35e683ccab46282519b26cae20d398b754357abe1cf0370890f2897b5d8ada4fb3da777a8f8f1daa4197a380e6734504117dd2a92ea1917f174c44c59e0b50c6b7a5f9eb14e6d96cb6b3e5dbcb3d0eaf0e4aac1b6616d674bb708b7559e37de608e8a828bee7f25f627e2f06d9a87e8d651ade39e1e65db7204b94abc0b7ca6fdd75aadeeac6a876b6297e38039734ebdfa9a555152b4293cb00e423a66d64f827afa4748dd6fdc1dc33332bffb820dacbf5a6d347042db985bbd9cf476dceb45d6978035ba03d25612243fc164c0a902017ce7ffd632d041fa3c56554739e78c6d725ecbfdaa0739d3649239fb53294b7a46ee6ed403bf3815f6c78f06a8ca4e3c9b066234f7574fb6ea8f17d199"
-			  }
-			]
-		  },
-		  "offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		"Err": {
+		  "ClientCallback": "Minimum_confirmations can not be smaller than 1"
 		}
 	  }
 	}
 	# "#
-	# , true, 5, true, true, false, false, false);
+	# , true, 4, false, false, false, false, false);
 	```
-	 */
-	fn get_stored_tx(
+	*/
+	fn split_output(
 		&self,
 		token: Token,
-		tx: &TxLogEntryAPI,
-	) -> Result<Option<TransactionV3>, ErrorKind>;
+		args: SplitArgs,
+		preview: bool,
+	) -> Result<UtxoTx, ErrorKind>;
 
 	/**
-	Networked version of [Owner::verify_slate_messages](struct.Owner.html#method.verify_slate_messages).
+	Networked version of [Owner::schedule_payment](struct.Owner.html#method.schedule_payment).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "verify_slate_messages",
-		"id": 1,
+		"method": "schedule_payment",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"slate": {
-				"amount": "6000000000",
-				"fee": "8000000",
-				"height": "4",
-				"id": "0436430c-2b02-624c-2032-570501212b00",
-				"lock_height": "4",
-				"ttl_cutoff_height": null,
-				"num_participants": 2,
-				"participant_data": [
-				{
-					"id": "0",
-					"message": "my message",
-					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
-					"part_sig": null,
-					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
-					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-				}
-				],
-				"tx": {
-					"body": {
-						"inputs": [
-						{
-							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
-							"features": "Coinbase"
-						}
-						],
-						"kernels": [
-						{
-							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-							"features": "HeightLocked",
-							"fee": "8000000",
-							"lock_height": "4"
-						}
-						],
-						"outputs": [
-						{
-							"commit": "094be57c91787fc2033d5d97fae099f1a6ddb37ea48370f1a138f09524c767fdd3",
-							"features": "Plain",
-							"proof": "2a42e9e902b70ce44e1fccb14de87ee0a97100bddf12c6bead1b9c5f4eb60300f29c13094fa12ffeee238fb4532b18f6b61cf51b23c1c7e1ad2e41560dc27edc0a2b9e647a0b3e4e806fced5b65e61d0f1f5197d3e2285c632d359e27b6b9206b2caffea4f67e0c7a2812e7a22c134b98cf89bd43d9f28b8bec25cce037a0ac5b1ae8f667e54e1250813a5263004486b4465ad4e641ab2b535736ea26535a11013564f08f483b7dab1c2bcc3ee38eadf2f7850eff7e3459a4bbabf9f0cf6c50d0c0a4120565cd4a2ce3e354c11721cd695760a24c70e0d5a0dfc3c5dcd51dfad6de2c237a682f36dc0b271f21bb3655e5333016aaa42c2efa1446e5f3c0a79ec417c4d30f77556951cb0f05dbfafb82d9f95951a9ea241fda2a6388f73ace036b98acce079f0e4feebccc96290a86dcc89118a901210b245f2d114cf94396e4dbb461e82aa26a0581389707957968c7cdc466213bb1cd417db207ef40c05842ab67a01a9b96eb1430ebc26e795bb491258d326d5174ad549401059e41782121e506744af8af9d8e493644a87d613600888541cbbe538c625883f3eb4aa3102c5cfcc25de8e97af8927619ce6a731b3b8462d51d993066b935b0648d2344ad72e4fd70f347fbd81041042e5ea31cc7b2e3156a920b80ecba487b950ca32ca95fae85b759c936246ecf441a9fdd95e8fee932d6782cdec686064018c857efc47fb4b2a122600d5fdd79af2486f44df7e629184e1c573bc0a9b3feb40b190ef2861a1ab45e2ac2201b9cd42e495deea247269820ed32389a2810ad6c0f9a296d2a2d9c54089fed50b7f5ecfcd33ab9954360e1d7f5598c32128cfcf2a1d8bf14616818da8a5343bfa88f0eedf392e9d4ab1ace1b60324129cd4852c2e27813a9cf71a6ae6229a4fcecc1a756b3e664c5f50af333082616815a3bec8fc0b75b8e4e767d719"
-						}
-						]
-					},
-					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
-					"payment_proof": null
-				},
-				"version_info": {
-					"orig_version": 3,
-					"version": 3,
-					"block_header_version": 2
-				}
+			"args": {
+				"src_acct_name": null,
+				"amount": "2000000000",
+				"minimum_confirmations": "10",
+				"message": "Rent",
+				"method": "ftp",
+				"dest": "xmgEvZ4MCCGMJnRnNXKHBbHmSGWQchJ9TqLs8qRdxyr8Sm9W3rBt",
+				"apisecret": null,
+				"fluff": false,
+				"start_time": null,
+				"interval_secs": 2592000,
+				"max_retries": 5
 			}
-		}
+		},
+		"id": 1
 	}
 	# "#
 	# ,
 	# r#"
 	{
-		"jsonrpc": "2.0",
-		"id": 1,
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Unsupported payment method ftp, expected http or mwcmqs"
 		}
+	  }
 	}
 	# "#
-	# ,true, 0 ,false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-	fn verify_slate_messages(&self, token: Token, slate: VersionedSlate) -> Result<(), ErrorKind>;
+	fn schedule_payment(
+		&self,
+		token: Token,
+		args: SchedulePaymentArgs,
+	) -> Result<ScheduledPayment, ErrorKind>;
 
 	/**
-	Networked version of [Owner::scan](struct.Owner.html#method.scan).
-
+	Networked version of [Owner::scheduled_payments](struct.Owner.html#method.scheduled_payments).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "scan",
+		"method": "scheduled_payments",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"start_height": 1,
-			"delete_unconfirmed": false
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
 		},
 		"id": 1
 	}
@@ -2122,34 +2351,31 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
-		}
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": []
+	  }
 	}
 	# "#
-	# , true, 1, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
-	 */
-	fn scan(
-		&self,
-		token: Token,
-		start_height: Option<u64>,
-		delete_unconfirmed: bool,
-	) -> Result<(), ErrorKind>;
+	*/
+	fn scheduled_payments(&self, token: Token) -> Result<Vec<ScheduledPayment>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::node_height](struct.Owner.html#method.node_height).
+	Networked version of [Owner::set_scheduled_payment_active](struct.Owner.html#method.set_scheduled_payment_active).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "node_height",
+		"method": "set_scheduled_payment_active",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00",
+			"active": false
 		},
 		"id": 1
 	}
@@ -2157,86 +2383,37 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": {
-				"header_hash": "d4b3d3c40695afd8c7760f8fc423565f7d41310b7a4e1c4a4a7950a66f16240d",
-				"height": "5",
-				"updated_from_node": true
-			}
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Scheduled payment 0436430c-2b02-624c-2032-570501212b00 is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 5, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
-	 */
-	fn node_height(&self, token: Token) -> Result<NodeHeightResult, ErrorKind>;
-
-	/**
-		Initializes the secure JSON-RPC API. This function must be called and a shared key
-		established before any other OwnerAPI JSON-RPC function can be called.
-
-		The shared key will be derived using ECDH with the provided public key on the secp256k1 curve. This
-		function will return its public key used in the derivation, which the caller should multiply by its
-		private key to derive the shared key.
-
-		Once the key is established, all further requests and responses are encrypted and decrypted with the
-		following parameters:
-		* AES-256 in GCM mode with 128-bit tags and 96 bit nonces
-		* 12 byte nonce which must be included in each request/response to use on the decrypting side
-		* Empty vector for additional data
-		* Suffix length = AES-256 GCM mode tag length = 16 bytes
-		*
-
-		Fully-formed JSON-RPC requests (as documented) should be encrypted using these parameters, encoded
-		into base64 and included with the one-time nonce in a request for the `encrypted_request_v3` method
-		as follows:
-
-		```
-		# let s = r#"
-		{
-			 "jsonrpc": "2.0",
-			 "method": "encrypted_request_v3",
-			 "id": "1",
-			 "params": {
-					"nonce": "ef32...",
-					"body_enc": "e0bcd..."
-			 }
-		}
-		# "#;
-		```
-
-		With a typical response being:
-
-		```
-		# let s = r#"{
-		{
-			 "jsonrpc": "2.0",
-			 "method": "encrypted_response_v3",
-			 "id": "1",
-			 "Ok": {
-					"nonce": "340b...",
-					"body_enc": "3f09c..."
-			 }
-		}
-		# }"#;
-		```
-
 	*/
-
-	fn init_secure_api(&self, ecdh_pubkey: ECDHPubkey) -> Result<ECDHPubkey, ErrorKind>;
+	fn set_scheduled_payment_active(
+		&self,
+		token: Token,
+		id: Uuid,
+		active: bool,
+	) -> Result<ScheduledPayment, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_top_level_directory](struct.Owner.html#method.get_top_level_directory).
+	Networked version of [Owner::delete_scheduled_payment](struct.Owner.html#method.delete_scheduled_payment).
 
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_top_level_directory",
+		"method": "delete_scheduled_payment",
 		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00"
 		},
 		"id": 1
 	}
@@ -2244,29 +2421,38 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": "/doctest/dir"
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Scheduled payment 0436430c-2b02-624c-2032-570501212b00 is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 5, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn get_top_level_directory(&self) -> Result<String, ErrorKind>;
+	fn delete_scheduled_payment(&self, token: Token, id: Uuid) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::set_top_level_directory](struct.Owner.html#method.set_top_level_directory).
+	Networked version of [Owner::save_contact](struct.Owner.html#method.save_contact).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "set_top_level_directory",
+		"method": "save_contact",
 		"params": {
-			"dir": "/home/wallet_user/my_wallet_dir"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"contact": {
+				"name": "alice",
+				"address": "http://127.0.0.1:3415",
+				"method": "http",
+				"proof_address": null,
+				"apisecret": null
+			}
 		},
 		"id": 1
 	}
@@ -2274,69 +2460,35 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "name": "alice",
+		  "address": "http://127.0.0.1:3415",
+		  "method": "http",
+		  "proof_address": null,
+		  "apisecret": null
 		}
+	  }
 	}
 	# "#
-	# , true, 5, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn set_top_level_directory(&self, dir: String) -> Result<(), ErrorKind>;
+	fn save_contact(&self, token: Token, contact: Contact) -> Result<Contact, ErrorKind>;
 
 	/**
-	Networked version of [Owner::create_config](struct.Owner.html#method.create_config).
+	Networked version of [Owner::contacts](struct.Owner.html#method.contacts).
 
-	The `wallet_config` ,`logging_config` and `mqs_config` parameters can be `null`, the examples
-	below are for illustration. Note that the values provided for `log_file_path` and `data_file_dir`
-	will be ignored and replaced with the actual values based on the value of `get_top_level_directory`
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "create_config",
+		"method": "contacts",
 		"params": {
-			"chain_type": "Mainnet",
-			"wallet_config": {
-				"chain_type": null,
-				"api_listen_interface": "127.0.0.1",
-				"api_listen_port": 3415,
-				"owner_api_listen_port": 3420,
-				"api_secret_path": null,
-				"node_api_secret_path": null,
-				"check_node_api_http_addr": "http://127.0.0.1:3413",
-				"owner_api_include_foreign": false,
-				"data_file_dir": "/path/to/data/file/dir",
-				"no_commit_cache": null,
-				"tls_certificate_file": null,
-				"tls_certificate_key": null,
-				"dark_background_color_scheme": null
-			},
-			"logging_config": {
-				"log_to_stdout": false,
-				"stdout_log_level": "Info",
-				"log_to_file": true,
-				"file_log_level": "Debug",
-				"log_file_path": "/path/to/log/file",
-				"log_file_append": true,
-				"log_max_size": null,
-				"log_max_files": null,
-				"tui_running": null
-			},
-			"tor_config" : {
-				"use_tor_listener": true,
-				"socks_proxy_addr": "127.0.0.1:9050",
-				"send_config_dir": ".",
-				"socks_running": false
-			},
-			"mqs_config" : {
-				"mwcmqs_domain": "mqs.mwc.mw",
-				"mwcmqs_port": 443
-			}
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
 		},
 		"id": 1
 	}
@@ -2344,38 +2496,30 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
-		}
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": []
+	  }
 	}
 	# "#
-	# , true, 5, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-	fn create_config(
-		&self,
-		chain_type: global::ChainTypes,
-		wallet_config: Option<WalletConfig>,
-		logging_config: Option<LoggingConfig>,
-		tor_config: Option<TorConfig>,
-		mqs_config: Option<MQSConfig>,
-	) -> Result<(), ErrorKind>;
+	fn contacts(&self, token: Token) -> Result<Vec<Contact>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::create_wallet](struct.Owner.html#method.create_wallet).
+	Networked version of [Owner::get_contact](struct.Owner.html#method.get_contact).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "create_wallet",
+		"method": "get_contact",
 		"params": {
-			"name": null,
-			"mnemonic": null,
-			"mnemonic_length": 32,
-			"password": "my_secret_password"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"name": "alice"
 		},
 		"id": 1
 	}
@@ -2383,36 +2527,32 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Contact alice is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn create_wallet(
-		&self,
-		name: Option<String>,
-		mnemonic: Option<String>,
-		mnemonic_length: u32,
-		password: String,
-	) -> Result<(), ErrorKind>;
+	fn get_contact(&self, token: Token, name: String) -> Result<Contact, ErrorKind>;
 
 	/**
-	Networked version of [Owner::open_wallet](struct.Owner.html#method.open_wallet).
+	Networked version of [Owner::delete_contact](struct.Owner.html#method.delete_contact).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "open_wallet",
+		"method": "delete_contact",
 		"params": {
-			"name": null,
-			"password": "my_secret_password"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"name": "alice"
 		},
 		"id": 1
 	}
@@ -2420,29 +2560,39 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": "d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868"
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Contact alice is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn open_wallet(&self, name: Option<String>, password: String) -> Result<Token, ErrorKind>;
+	fn delete_contact(&self, token: Token, name: String) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::close_wallet](struct.Owner.html#method.close_wallet).
+	Networked version of [Owner::create_payment_request](struct.Owner.html#method.create_payment_request).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "close_wallet",
+		"method": "create_payment_request",
 		"params": {
-			"name": null
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"amount": "0",
+				"address": "http://127.0.0.1:3415",
+				"memo": "Order 42",
+				"expiry_blocks": "1440",
+				"expiry_time": null,
+				"require_proof": false
+			}
 		},
 		"id": 1
 	}
@@ -2450,30 +2600,35 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Payment request amount can't be zero"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn close_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
+	fn create_payment_request(
+		&self,
+		token: Token,
+		args: PaymentRequestArgs,
+	) -> Result<PaymentRequest, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_mnemonic](struct.Owner.html#method.get_mnemonic).
+	Networked version of [Owner::payment_requests](struct.Owner.html#method.payment_requests).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_mnemonic",
+		"method": "payment_requests",
 		"params": {
-			"name": null,
-			"password": ""
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
 		},
 		"id": 1
 	}
@@ -2481,31 +2636,30 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": "fat twenty mean degree forget shell check candy immense awful flame next during february bulb bike sun wink theory day kiwi embrace peace lunch"
-		}
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": []
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn get_mnemonic(&self, name: Option<String>, password: String) -> Result<String, ErrorKind>;
+	fn payment_requests(&self, token: Token) -> Result<Vec<PaymentRequest>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::change_password](struct.Owner.html#method.change_password).
+	Networked version of [Owner::cancel_payment_request](struct.Owner.html#method.cancel_payment_request).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "change_password",
+		"method": "cancel_payment_request",
 		"params": {
-			"name": null,
-			"old": "",
-			"new": "new_password"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "4f7c5f3a-0c0f-4bd6-9e54-9ab4f1d2c3e1"
 		},
 		"id": 1
 	}
@@ -2513,33 +2667,32 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Payment request 4f7c5f3a-0c0f-4bd6-9e54-9ab4f1d2c3e1 is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-	fn change_password(
-		&self,
-		name: Option<String>,
-		old: String,
-		new: String,
-	) -> Result<(), ErrorKind>;
+	fn cancel_payment_request(&self, token: Token, id: Uuid) -> Result<PaymentRequest, ErrorKind>;
 
 	/**
-	Networked version of [Owner::delete_wallet](struct.Owner.html#method.delete_wallet).
+	Networked version of [Owner::payment_request_uri](struct.Owner.html#method.payment_request_uri).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "delete_wallet",
+		"method": "payment_request_uri",
 		"params": {
-			"name": null
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "4f7c5f3a-0c0f-4bd6-9e54-9ab4f1d2c3e1"
 		},
 		"id": 1
 	}
@@ -2547,29 +2700,31 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Payment request 4f7c5f3a-0c0f-4bd6-9e54-9ab4f1d2c3e1 is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-	fn delete_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
+	fn payment_request_uri(&self, token: Token, id: Uuid) -> Result<String, ErrorKind>;
 
 	/**
-	Networked version of [Owner::start_updated](struct.Owner.html#method.start_updater).
+	Networked version of [Owner::decode_payment_request](struct.Owner.html#method.decode_payment_request).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "start_updater",
+		"method": "decode_payment_request",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"frequency": 30000
+			"uri": "mwc:http://127.0.0.1:3415?amount=2.5"
 		},
 		"id": 1
 	}
@@ -2577,56 +2732,101 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "GenericError": "Invalid payment request 'mwc:http://127.0.0.1:3415?amount=2.5', id is missing"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn start_updater(&self, token: Token, frequency: u32) -> Result<(), ErrorKind>;
+	fn decode_payment_request(&self, uri: String) -> Result<PaymentRequest, ErrorKind>;
 
 	/**
-	Networked version of [Owner::stop_updater](struct.Owner.html#method.stop_updater).
+	Networked version of [Owner::multisig_create](struct.Owner.html#method.multisig_create).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "stop_updater",
-		"params": null,
+		"method": "multisig_create",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"src_acct_name": null,
+				"amount": "2000000000",
+				"minimum_confirmations": 2,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": true,
+				"message": null,
+				"target_slate_version": null,
+				"payment_proof_recipient_address": null,
+				"ttl_blocks": null,
+				"send_args": null
+			},
+			"num_participants": 1,
+			"threshold": 1,
+			"contributions": null
+		},
 		"id": 1
 	}
 	# "#
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": null
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "MultisigError": "Multisig account needs from 2 to 10 co-signers"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 4, false, false, false, false, false);
 	```
 	*/
-	fn stop_updater(&self) -> Result<(), ErrorKind>;
+	fn multisig_create(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+		num_participants: usize,
+		threshold: usize,
+		contributions: Option<Vec<u64>>,
+	) -> Result<MultisigAccount, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_updater_messages](struct.Owner.html#method.get_updater_messages).
+	Networked version of [Owner::multisig_process](struct.Owner.html#method.multisig_process).
+	Returns the account to pass to the next co-signer and the finalized transaction, if any.
+	*/
+	fn multisig_process(
+		&self,
+		token: Token,
+		account: MultisigAccount,
+		args: Option<InitTxArgs>,
+	) -> Result<(MultisigAccount, Option<TransactionV3>), ErrorKind>;
+
+	/**
+	Networked version of [Owner::multisig_spend](struct.Owner.html#method.multisig_spend).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_updater_messages",
+		"method": "multisig_spend",
 		"params": {
-			"count": 1
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"account_id": "0436430c-2b02-624c-2032-570501212b00",
+			"signers": [1],
+			"amount": null,
+			"address": null
 		},
 		"id": 1
 	}
@@ -2634,27 +2834,56 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": []
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Err": {
+		  "MultisigError": "Multisig account 0436430c-2b02-624c-2032-570501212b00 is not found"
 		}
+	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
+	fn multisig_spend(
+		&self,
+		token: Token,
+		account_id: Uuid,
+		signers: Vec<usize>,
+		amount: Option<u64>,
+		address: Option<String>,
+	) -> Result<MultisigAccount, ErrorKind>;
 
-	fn get_updater_messages(&self, count: u32) -> Result<Vec<StatusMessage>, ErrorKind>;
+	/**
+	Networked version of [Owner::multisig_spend_slate](struct.Owner.html#method.multisig_spend_slate).
+	Returns the plain slate for the spending recipient.
+	*/
+	fn multisig_spend_slate(
+		&self,
+		token: Token,
+		account_id: Uuid,
+	) -> Result<VersionedSlate, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_mqs_address](struct.Owner.html#method.get_mqs_address).
+	Networked version of [Owner::multisig_spend_response](struct.Owner.html#method.multisig_spend_response).
+	Returns the account to pass to the next signer and the finalized transaction, if any.
+	*/
+	fn multisig_spend_response(
+		&self,
+		token: Token,
+		slate: VersionedSlate,
+	) -> Result<(MultisigAccount, Option<TransactionV3>), ErrorKind>;
+
+	/**
+	Networked version of [Owner::multisig_accounts](struct.Owner.html#method.multisig_accounts).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_mqs_address",
+		"method": "multisig_accounts",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
 		},
@@ -2667,66 +2896,104 @@ pub trait OwnerRpcV3 {
 	  "id": 1,
 	  "jsonrpc": "2.0",
 	  "result": {
-		"Ok": {
-		  "public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
-			"domain": "",
-		  "port": null
-		}
+		"Ok": []
 	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
+	fn multisig_accounts(&self, token: Token) -> Result<Vec<MultisigAccount>, ErrorKind>;
 
-	fn get_mqs_address(&self, token: Token) -> Result<ProvableAddress, ErrorKind>;
+	/**
+	Networked version of [Owner::multisig_account](struct.Owner.html#method.multisig_account).
+	*/
+	fn multisig_account(
+		&self,
+		token: Token,
+		account_id: Uuid,
+	) -> Result<MultisigAccount, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_wallet_public_address](struct.Owner.html#method.get_wallet_public_address).
+	Networked version of [Owner::post_tx](struct.Owner.html#method.post_tx).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_wallet_public_address",
+		"id": 1,
+		"method": "post_tx",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
-		},
-		"id": 1
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"tx": {
+				"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"body": {
+					"inputs": [
+						{
+						  "commit": "098778ce2243fa34e5876c8cb7f6dbbbd6a5649c1561973a807a6811941c12363c",
+						  "features": "Coinbase"
+						},
+						{
+						  "commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
+						  "features": "Coinbase"
+						}
+					],
+					"outputs": [
+						{
+						  "commit": "082967b3fe580cd110355010ef45450314fb067720db01b0e6873bb083d76708c9",
+						  "features": "Plain",
+						  "proof": "828bb24121aa0332c872062a42a8333c3ef81f8ae37d24053d953217368b3cada90410a50509a0b9fcbb5aded41397fc00ca1ff5acdac20d48afb0a3281d21e7026d32fdc6c5157461a35f98a809ffa09187c1e170ea24652ad213b7e4c9878654ac3dd9a8915eaf742db53182fcb42d2d341fbdfe8bd31bd001f4ff2c1ca9f9b1531da29137214f211edb7a5eb8f494cb8945f8527dd25bf7e698515043db4249540720008a708db5342230d05b069c094688ccb7c07d4a4a2293ea76cf999c555dc0ddc757891c360db1901bbb4dc20cae997f875f8de482d8160e05d60f9b0135e0fc313d8f953db78f1ea252449dd81cfa22dd895512ed39d566f0924542b543d25fc9fc7a819d228f3b0ee5e381f088f54893e86437dafc49dd923b3e6dff956ca843f951910379531fac9bb5fd01a182dd32a4c597f92da3c01af37cb9b0ec984500884438e74e54d7e76fa1ae7241d5050b13376310b24761634a6f6eb7cf000082f50ed7c1899d7918023d4f877586f964932a7af72e7a4984ddecfdd1921a2e1b80b00d6bd2e64a3f4cb6915a27a8d17a69d163cf45220a13fcddd15dc2bb91ae4f1b6a67224ab3b23e8d7d785df178ec78a84cf42cea086426f563822c8a4271a0b89bb21f84b643dbf1de21b6395039d673a376492767199fa36ccd9a13628ce61695424091acc16059450d59bc59fa7879e7306f5727217211b0264a6a560f886d520e41406ef45b1668805b88d246c5b2ca5a1762042c85be34fcd420ac3843f32236d079b4bd57d6b8d8013d9d18f8efb55e8e443cd9e1af9b144e7a56c8c6be0138af3b4a6c99bee9109bed2bce2e5145e736b125a2ec19aaf3fff713f6897fdd4158ce2ab04706b062ca2847bf70259c0fc4b0d390dc7fdaf0362047f775a912bd22da9d40f04d9790bcd5ece4b36b74c6c340b48c2926b916e8a9"
+						},
+						{
+						  "commit": "096e1669267c22ecb38c466d73b8578261d8e91c14dd66702dd5bf34f4232e10db",
+						  "features": "Plain",
+						  "proof": "7d567b0895a1103d19446929da8b98f2086819507ddce4b9dbb5ce6327107744e74aba59ef1834937da1b86eb7c1c1b0bc11d1c5d5ec79d25bc1e52aed1656f60d46f6878ba5ca8639efdbb9203e378e91171c11527c4a34713f06dc22f58ca4a08e68d83ff897e61cfc145fe376fa428b55e25cf20d15f10b9054778229798b30fb4e45d817a5053b682dcf591481a3c8174cfbba81e31aa525d5b884ca7a016713178f26c0fe8ae1f88b5382f8e70c4d91fb3828c0f307d828aa028281d3551525e68d20827ab0e6785c6b5747e895dcd38429b44e62b7f6c1c921d87ae954a9dd6e967ac52e6cd13a1d4bb2f1434da25a0723ef9c869cc573019577552dd0e0f808f8cc57723b041320025f6433779fe907998a4ec7606bf884b2199253b502065bed8e0625c2df858d6508c1aa44deddc68d06d00d81e97720e23e15a3464ed4733fc547e9fb772e563a1dbcd27ac55e40f674f9006e7dd4465444f3eb7527cb01905dee69a51cf2fc1810c861dd0834e7649d594c3e1740d85343a6b63c8a9e0a0f63059031899b38dfd9a192034d54029bd35e683ccab46282519b26cae20d398b754357abe1cf0370890f2897b5d8ada4fb3da777a8f8f1daa4197a380e6734504117dd2a92ea1917f174c44c59e0b50c6b7a5f9eb14e6d96cb6b3e5dbcb3d0eaf0e4aac1b6616d674bb708b7559e37de608e8a828bee7f25f627e2f06d9a87e8d651ade39e1e65db7204b94abc0b7ca6fdd75aadeeac6a876b6297e38039734ebdfa9a555152b4293cb00e423a66d64f827afa4748dd6fdc1dc33332bffb820dacbf5a6d347042db985bbd9cf476dceb45d6978035ba03d25612243fc164c0a902017ce7ffd632d041fa3c56554739e78c6d725ecbfdaa0739d3649239fb53294b7a46ee6ed403bf3815f6c78f06a8ca4e3c9b066234f7574fb6ea8f17d199"
+						}
+					],
+					"kernels": [
+						{
+						  "excess": "08b3b8b83c622f630141a66c9cad96e19c78f745e4e2ddea85439f05d14a404640",
+						  "excess_sig": "66074d25a751c4743342c90ad8ead9454daa00d9b9aed29bca321036d16c4b4d1f1ac30ec6809c5e1a983a83af0deb0635b892e5e0ea3a3bd7f68be99f721348",
+						  "features": "Plain",
+						  "fee": "7000000",
+						  "lock_height": "0"
+						}
+					]
+				}
+			},
+			"fluff": false
+		}
 	}
 	# "#
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": {
-		  "public_key": "fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd",
-		  "domain": "",
-		  "port": null
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
-	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 5, true, true, true, false, true);
 	```
-	*/
+	 */
 
-	fn get_wallet_public_address(&self, token: Token) -> Result<ProvableAddress, ErrorKind>;
+	fn post_tx(&self, token: Token, tx: TransactionV3, fluff: bool) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::retrieve_payment_proof](struct.Owner.html#method.retrieve_payment_proof).
+	Networked version of [Owner::cancel_tx](struct.Owner.html#method.cancel_tx).
+
+
 	```
-	# // Legacy non compact case
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "retrieve_payment_proof",
+		"method": "cancel_tx",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"refresh_from_node": true,
 			"tx_id": null,
 			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
 		},
@@ -2736,41 +3003,24 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": {
-		  "amount": "2000000000",
-		  "excess": "08b3b8b83c622f630141a66c9cad96e19c78f745e4e2ddea85439f05d14a404640",
-		  "recipient_address": {
-			"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
-			"domain": "",
-			"port": null
-		  },
-		  "recipient_sig": "30440220050ccd7244a8e1bcad8724a26bef6e0bc3df85f09dfc41870635711627955c4c02202b3d3599a7371bcc685315876c54cdf956a8c990ce6526f6be8e50591bde3be2",
-		  "sender_address": {
-			"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
-			"domain": "",
-			"port": null
-		  },
-		  "sender_sig": "3045022100945b57de1e8b9f7863c4f4c5698d5617ffa55748c80a8324729f98ce5ef86509022063f6bc511d80046f6f21c9476344ed8d948234cc32a0b022d720161798e09861"
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
-	  }
 	}
 	# "#
-	# , true, 5, true, true, true, true, false);
+	# , true, 5, true, true, false, false, true);
 	#
-	# // Comapact slate case, kernel is different now.
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "retrieve_payment_proof",
+		"method": "cancel_tx",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"refresh_from_node": true,
-			"tx_id": null,
-			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b01"
+			"tx_id": 5,
+			"tx_slate_id": null
 		},
 		"id": 1
 	}
@@ -2778,52 +3028,1063 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": {
-		  "amount": "2000000000",
-		  "excess": "09eac5f5872fa5e08e0c29fd900f1b8f77ff3ad1d0d1c46aeb202cbf92363fe0af",
-		  "recipient_address": {
-			"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
-			"domain": "",
-			"port": null
-		  },
-		  "recipient_sig": "304402204417c3b64709c7e38197103b6979dacee4e41c3ee44d89ea75191f553e2bbcc2022044c52df100fffd080c95575c92329b6ddbd0c77545e31ee439112655918fe0ee",
-		  "sender_address": {
-			"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
-			"domain": "",
-			"port": null
-		  },
-		  "sender_sig": "3044022001d10e4e1fd303748120a45cfcdedcd8b1abc1ffe8ff59d35ddb2ec5b7c2e1a902207dab490bd26d16784933724c559e5a1e4dc56a6c0941f4dd70a22b0c4cfbcc40"
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
-	  }
 	}
 	# "#
-	# , true, 5, true, true, true, true, true);
+	# , true, 5, true, true, false, false, true);
 	```
-	*/
-
-	fn retrieve_payment_proof(
+	 */
+	fn cancel_tx(
 		&self,
 		token: Token,
-		refresh_from_node: bool,
 		tx_id: Option<u32>,
 		tx_slate_id: Option<Uuid>,
-	) -> Result<PaymentProof, ErrorKind>;
+	) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::verify_payment_proof](struct.Owner.html#method.verify_payment_proof).
+	Networked version of [Owner::get_stored_tx](struct.Owner.html#method.get_stored_tx).
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "verify_payment_proof",
+		"method": "get_stored_tx",
+		"id": 1,
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"proof": {
-			  "amount": "2000000000",
+			"tx": {
+				"stored_tx": "0436430c-2b02-624c-2032-570501212b00.mwctx",
+				"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00",
+				"tx_type": "TxSent"
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "body": {
+			"inputs": [
+			  {
+				"commit": "098778ce2243fa34e5876c8cb7f6dbbbd6a5649c1561973a807a6811941c12363c",
+				"features": "Coinbase"
+			  },
+			  {
+				"commit": "0910c1752100733bae49e877286835aab76d5856ef8139b6c6e3f51798aa461b03",
+				"features": "Coinbase"
+			  }
+			],
+			"kernels": [
+			  {
+				"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+				"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+				"features": "Plain",
+				"fee": "7000000",
+				"lock_height": "0"
+			  }
+			],
+			"outputs": [
+			  {
+				"commit": "082967b3fe580cd110355010ef45450314fb067720db01b0e6873bb083d76708c9",
+				"features": "Plain",
+				"proof": "828bb24121aa0332c872062a42a8333c3ef81f8ae37d24053d953217368b3cada90410a50509a0b9fcbb5aded41397fc00ca1ff5acdac20d48afb0a3281d21e7026d32fdc6c5157461a35f98a809ffa09187c1e170ea24652ad213b7e4c9878654ac3dd9a8915eaf742db53182fcb42d2d341fbdfe8bd31bd001f4ff2c1ca9f9b1531da29137214f211edb7a5eb8f494cb8945f8527dd25bf7e698515043db4249540720008a708db5342230d05b069c094688ccb7c07d4a4a2293ea76cf999c555dc0ddc757891c360db1901bbb4dc20cae997f875f8de482d8160e05d60f9b0135e0fc313d8f953db78f1ea252449dd81cfa22dd895512ed39d566f0924542b543d25fc9fc7a819d228f3b0ee5e381f088f54893e86437dafc49dd923b3e6dff956ca843f951910379531fac9bb5fd01a182dd32a4c597f92da3c01af37cb9b0ec984500884438e74e54d7e76fa1ae7241d5050b13376310b24761634a6f6eb7cf000082f50ed7c1899d7918023d4f877586f964932a7af72e7a4984ddecfdd1921a2e1b80b00d6bd2e64a3f4cb6915a27a8d17a69d163cf45220a13fcddd15dc2bb91ae4f1b6a67224ab3b23e8d7d785df178ec78a84cf42cea086426f563822c8a4271a0b89bb21f84b643dbf1de21b6395039d673a376492767199fa36ccd9a13628ce61695424091acc16059450d59bc59fa7879e7306f5727217211b0264a6a560f886d520e41406ef45b1668805b88d246c5b2ca5a1762042c85be34fcd420ac3843f32236d079b4bd57d6b8d8013d9d18f8efb55e8e443cd9e1af9b144e7a56c8c6be0138af3b4a6c99bee9109bed2bce2e5145e736b125a2ec19aaf3fff713f6897fdd4158ce2ab04706b062ca2847bf70259c0fc4b0d390dc7fdaf0362047f775a912bd22da9d40f04d9790bcd5ece4b36b74c6c340b48c2926b916e8a9"
+			  },
+			  {
+				"commit": "096e1669267c22ecb38c466d73b8578261d8e91c14dd66702dd5bf34f4232e10db",
+				"features": "Plain",
+				"proof": "7d567b0895a1103d19446929da8b98f2086819507ddce4b9dbb5ce6327107744e74aba59ef1834937da1b86eb7c1c1b0bc11d1c5d5ec79d25bc1e52aed1656f60d46f6878ba5ca8639efdbb9203e378e91171c11527c4a34713f06dc22f58ca4a08e68d83ff897e61cfc145fe376fa428b55e25cf20d15f10b9054778229798b30fb4e45d817a5053b682dcf591481a3c8174cfbba81e31aa525d5b884ca7a016713178f26c0fe8ae1f88b5382f8e70c4d91fb3828c0f307d828aa028281d3551525e68d20827ab0e6785c6b5747e895dcd38429b44e62b7f6c1c921d87ae954a9dd6e967ac52e6cd13a1d4bb2f1434da25a0723ef9c869cc573019577552dd0e0f808f8cc57723b041320025f6433779fe907998a4ec7606bf884b2199253b502065bed8e0625c2df858d6508c1aa44deddc68d06d00d81e97720e23e15a3464ed4733fc547e9fb772e563a1dbcd27ac55e40f674f9006e7dd4465444f3eb7527cb01905dee69a51cf2fc1810c861dd0834e7649d594c3e1740d85343a6b63c8a9e0a0f63059031899b38dfd9a192034d54029bd35e683ccab46282519b26cae20d398b754357abe1cf0370890f2897b5d8ada4fb3da777a8f8f1daa4197a380e6734504117dd2a92ea1917f174c44c59e0b50c6b7a5f9eb14e6d96cb6b3e5dbcb3d0eaf0e4aac1b6616d674bb708b7559e37de608e8a828bee7f25f627e2f06d9a87e8d651ade39e1e65db7204b94abc0b7ca6fdd75aadeeac6a876b6297e38039734ebdfa9a555152b4293cb00e423a66d64f827afa4748dd6fdc1dc33332bffb820dacbf5a6d347042db985bbd9cf476dceb45d6978035ba03d25612243fc164c0a902017ce7ffd632d041fa3c56554739e78c6d725ecbfdaa0739d3649239fb53294b7a46ee6ed403bf3815f6c78f06a8ca4e3c9b066234f7574fb6ea8f17d199"
+			  }
+			]
+		  },
+		  "offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		}
+	  }
+	}
+	# "#
+	# , true, 5, true, true, false, false, false);
+	```
+	 */
+	fn get_stored_tx(
+		&self,
+		token: Token,
+		tx: &TxLogEntryAPI,
+	) -> Result<Option<TransactionV3>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::verify_slate_messages](struct.Owner.html#method.verify_slate_messages).
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "verify_slate_messages",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"slate": {
+				"amount": "6000000000",
+				"fee": "8000000",
+				"height": "4",
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"lock_height": "4",
+				"ttl_cutoff_height": null,
+				"num_participants": 2,
+				"participant_data": [
+				{
+					"id": "0",
+					"message": "my message",
+					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
+					"part_sig": null,
+					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
+					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+				}
+				],
+				"tx": {
+					"body": {
+						"inputs": [
+						{
+							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
+							"features": "Coinbase"
+						}
+						],
+						"kernels": [
+						{
+							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+							"features": "HeightLocked",
+							"fee": "8000000",
+							"lock_height": "4"
+						}
+						],
+						"outputs": [
+						{
+							"commit": "094be57c91787fc2033d5d97fae099f1a6ddb37ea48370f1a138f09524c767fdd3",
+							"features": "Plain",
+							"proof": "2a42e9e902b70ce44e1fccb14de87ee0a97100bddf12c6bead1b9c5f4eb60300f29c13094fa12ffeee238fb4532b18f6b61cf51b23c1c7e1ad2e41560dc27edc0a2b9e647a0b3e4e806fced5b65e61d0f1f5197d3e2285c632d359e27b6b9206b2caffea4f67e0c7a2812e7a22c134b98cf89bd43d9f28b8bec25cce037a0ac5b1ae8f667e54e1250813a5263004486b4465ad4e641ab2b535736ea26535a11013564f08f483b7dab1c2bcc3ee38eadf2f7850eff7e3459a4bbabf9f0cf6c50d0c0a4120565cd4a2ce3e354c11721cd695760a24c70e0d5a0dfc3c5dcd51dfad6de2c237a682f36dc0b271f21bb3655e5333016aaa42c2efa1446e5f3c0a79ec417c4d30f77556951cb0f05dbfafb82d9f95951a9ea241fda2a6388f73ace036b98acce079f0e4feebccc96290a86dcc89118a901210b245f2d114cf94396e4dbb461e82aa26a0581389707957968c7cdc466213bb1cd417db207ef40c05842ab67a01a9b96eb1430ebc26e795bb491258d326d5174ad549401059e41782121e506744af8af9d8e493644a87d613600888541cbbe538c625883f3eb4aa3102c5cfcc25de8e97af8927619ce6a731b3b8462d51d993066b935b0648d2344ad72e4fd70f347fbd81041042e5ea31cc7b2e3156a920b80ecba487b950ca32ca95fae85b759c936246ecf441a9fdd95e8fee932d6782cdec686064018c857efc47fb4b2a122600d5fdd79af2486f44df7e629184e1c573bc0a9b3feb40b190ef2861a1ab45e2ac2201b9cd42e495deea247269820ed32389a2810ad6c0f9a296d2a2d9c54089fed50b7f5ecfcd33ab9954360e1d7f5598c32128cfcf2a1d8bf14616818da8a5343bfa88f0eedf392e9d4ab1ace1b60324129cd4852c2e27813a9cf71a6ae6229a4fcecc1a756b3e664c5f50af333082616815a3bec8fc0b75b8e4e767d719"
+						}
+						]
+					},
+					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
+					"payment_proof": null
+				},
+				"version_info": {
+					"orig_version": 3,
+					"version": 3,
+					"block_header_version": 2
+				}
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"id": 1,
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# ,true, 0 ,false, false, false, false, true);
+	```
+	*/
+	fn verify_slate_messages(&self, token: Token, slate: VersionedSlate) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::scan](struct.Owner.html#method.scan).
+
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "scan",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"start_height": 1,
+			"delete_unconfirmed": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 1, false, false, false, false, true);
+	```
+	 */
+	fn scan(
+		&self,
+		token: Token,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::node_height](struct.Owner.html#method.node_height).
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "node_height",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"header_hash": "d4b3d3c40695afd8c7760f8fc423565f7d41310b7a4e1c4a4a7950a66f16240d",
+				"height": "5",
+				"updated_from_node": true
+			}
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false, true);
+	```
+	 */
+	fn node_height(&self, token: Token) -> Result<NodeHeightResult, ErrorKind>;
+
+	/**
+		Initializes the secure JSON-RPC API. This function must be called and a shared key
+		established before any other OwnerAPI JSON-RPC function can be called.
+
+		The shared key will be derived using ECDH with the provided public key on the secp256k1 curve. This
+		function will return its public key used in the derivation, which the caller should multiply by its
+		private key to derive the shared key.
+
+		Once the key is established, all further requests and responses are encrypted and decrypted with the
+		following parameters:
+		* AES-256 in GCM mode with 128-bit tags and 96 bit nonces
+		* 12 byte nonce which must be included in each request/response to use on the decrypting side
+		* Empty vector for additional data
+		* Suffix length = AES-256 GCM mode tag length = 16 bytes
+		*
+
+		Fully-formed JSON-RPC requests (as documented) should be encrypted using these parameters, encoded
+		into base64 and included with the one-time nonce in a request for the `encrypted_request_v3` method
+		as follows:
+
+		```
+		# let s = r#"
+		{
+			 "jsonrpc": "2.0",
+			 "method": "encrypted_request_v3",
+			 "id": "1",
+			 "params": {
+					"nonce": "ef32...",
+					"body_enc": "e0bcd..."
+			 }
+		}
+		# "#;
+		```
+
+		With a typical response being:
+
+		```
+		# let s = r#"{
+		{
+			 "jsonrpc": "2.0",
+			 "method": "encrypted_response_v3",
+			 "id": "1",
+			 "Ok": {
+					"nonce": "340b...",
+					"body_enc": "3f09c..."
+			 }
+		}
+		# }"#;
+		```
+
+	*/
+
+	fn init_secure_api(&self, ecdh_pubkey: ECDHPubkey) -> Result<ECDHPubkey, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_top_level_directory](struct.Owner.html#method.get_top_level_directory).
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_top_level_directory",
+		"params": {
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "/doctest/dir"
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false, true);
+	```
+	*/
+
+	fn get_top_level_directory(&self) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_top_level_directory](struct.Owner.html#method.set_top_level_directory).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_top_level_directory",
+		"params": {
+			"dir": "/home/wallet_user/my_wallet_dir"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false, true);
+	```
+	*/
+
+	fn set_top_level_directory(&self, dir: String) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::create_config](struct.Owner.html#method.create_config).
+
+	The `wallet_config` ,`logging_config` and `mqs_config` parameters can be `null`, the examples
+	below are for illustration. Note that the values provided for `log_file_path` and `data_file_dir`
+	will be ignored and replaced with the actual values based on the value of `get_top_level_directory`
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "create_config",
+		"params": {
+			"chain_type": "Mainnet",
+			"wallet_config": {
+				"chain_type": null,
+				"api_listen_interface": "127.0.0.1",
+				"api_listen_port": 3415,
+				"owner_api_listen_port": 3420,
+				"api_secret_path": null,
+				"node_api_secret_path": null,
+				"check_node_api_http_addr": "http://127.0.0.1:3413",
+				"owner_api_include_foreign": false,
+				"data_file_dir": "/path/to/data/file/dir",
+				"no_commit_cache": null,
+				"tls_certificate_file": null,
+				"tls_certificate_key": null,
+				"dark_background_color_scheme": null
+			},
+			"logging_config": {
+				"log_to_stdout": false,
+				"stdout_log_level": "Info",
+				"log_to_file": true,
+				"file_log_level": "Debug",
+				"log_file_path": "/path/to/log/file",
+				"log_file_append": true,
+				"log_max_size": null,
+				"log_max_files": null,
+				"tui_running": null
+			},
+			"tor_config" : {
+				"use_tor_listener": true,
+				"socks_proxy_addr": "127.0.0.1:9050",
+				"send_config_dir": ".",
+				"socks_running": false
+			},
+			"mqs_config" : {
+				"mwcmqs_domain": "mqs.mwc.mw",
+				"mwcmqs_port": 443
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false, true);
+	```
+	*/
+	fn create_config(
+		&self,
+		chain_type: global::ChainTypes,
+		wallet_config: Option<WalletConfig>,
+		logging_config: Option<LoggingConfig>,
+		tor_config: Option<TorConfig>,
+		mqs_config: Option<MQSConfig>,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::create_wallet](struct.Owner.html#method.create_wallet).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "create_wallet",
+		"params": {
+			"name": null,
+			"mnemonic": null,
+			"mnemonic_length": 32,
+			"password": "my_secret_password"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn create_wallet(
+		&self,
+		name: Option<String>,
+		mnemonic: Option<String>,
+		mnemonic_length: u32,
+		password: String,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::create_view_wallet](struct.Owner.html#method.create_view_wallet).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "create_view_wallet",
+		"params": {
+			"name": null,
+			"view_key": "0102",
+			"password": "my_secret_password"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"ViewKeyError": "Expecting 32 bytes, get 2"
+			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn create_view_wallet(
+		&self,
+		name: Option<String>,
+		view_key: String,
+		password: String,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::open_wallet](struct.Owner.html#method.open_wallet).
+
+	The owner API listener can host several wallets at once. Opening a wallet with a `name`
+	starts hosting it next to the default one, with its own token and updater thread. All calls
+	made with that token are served by the named wallet until it is closed with `close_wallet`.
+	The names of the hosted wallets are returned by `list_wallets`.
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "open_wallet",
+		"params": {
+			"name": null,
+			"password": "my_secret_password"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868"
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn open_wallet(&self, name: Option<String>, password: String) -> Result<Token, ErrorKind>;
+
+	/**
+	Networked version of [Owner::close_wallet](struct.Owner.html#method.close_wallet).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "close_wallet",
+		"params": {
+			"name": null
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn close_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::list_wallets](struct.Owner.html#method.list_wallets).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "list_wallets",
+		"params": {
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn list_wallets(&self) -> Result<Vec<String>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_mnemonic](struct.Owner.html#method.get_mnemonic).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_mnemonic",
+		"params": {
+			"name": null,
+			"password": ""
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "fat twenty mean degree forget shell check candy immense awful flame next during february bulb bike sun wink theory day kiwi embrace peace lunch"
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn get_mnemonic(&self, name: Option<String>, password: String) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::backup_wallet](struct.Owner.html#method.backup_wallet).
+	The `backup_file` path is resolved on the host that runs the wallet. The wallet must be
+	closed with `close_wallet` first.
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "backup_wallet",
+		"params": {
+			"name": null,
+			"password": "",
+			"backup_file": "wallet.backup"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Lifecycle": "Wallet is open, please close it before the backup"
+			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn backup_wallet(
+		&self,
+		name: Option<String>,
+		password: String,
+		backup_file: String,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::restore_wallet](struct.Owner.html#method.restore_wallet).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "restore_wallet",
+		"params": {
+			"name": null,
+			"password": "",
+			"backup_file": "wallet.backup"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Lifecycle": "Wallet already exists, please restore the backup into a new location"
+			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn restore_wallet(
+		&self,
+		name: Option<String>,
+		password: String,
+		backup_file: String,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::change_password](struct.Owner.html#method.change_password).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "change_password",
+		"params": {
+			"name": null,
+			"old": "",
+			"new": "new_password"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+	fn change_password(
+		&self,
+		name: Option<String>,
+		old: String,
+		new: String,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::delete_wallet](struct.Owner.html#method.delete_wallet).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "delete_wallet",
+		"params": {
+			"name": null
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+	fn delete_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::start_updated](struct.Owner.html#method.start_updater).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "start_updater",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"frequency": 30000
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn start_updater(&self, token: Token, frequency: u32) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::stop_updater](struct.Owner.html#method.stop_updater).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "stop_updater",
+		"params": null,
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+	fn stop_updater(&self) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_updater_messages](struct.Owner.html#method.get_updater_messages).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_updater_messages",
+		"params": {
+			"count": 1
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn get_updater_messages(&self, count: u32) -> Result<Vec<StatusMessage>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_mqs_address](struct.Owner.html#method.get_mqs_address).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_mqs_address",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
+			"domain": "",
+		  "port": null
+		}
+	  }
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn get_mqs_address(&self, token: Token) -> Result<ProvableAddress, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_view_key](struct.Owner.html#method.get_view_key).
+	Returns the hex encoded view key to create a watch-only copy of the wallet with
+	`create_view_wallet`.
+	*/
+
+	fn get_view_key(&self, token: Token) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_wallet_public_address](struct.Owner.html#method.get_wallet_public_address).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_wallet_public_address",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "public_key": "fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd",
+		  "domain": "",
+		  "port": null
+		}
+	  }
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn get_wallet_public_address(&self, token: Token) -> Result<ProvableAddress, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_payment_proof](struct.Owner.html#method.retrieve_payment_proof).
+	```
+	# // Legacy non compact case
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_payment_proof",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"refresh_from_node": true,
+			"tx_id": null,
+			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "amount": "2000000000",
+		  "excess": "08b3b8b83c622f630141a66c9cad96e19c78f745e4e2ddea85439f05d14a404640",
+		  "recipient_address": {
+			"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
+			"domain": "",
+			"port": null
+		  },
+		  "recipient_sig": "30440220050ccd7244a8e1bcad8724a26bef6e0bc3df85f09dfc41870635711627955c4c02202b3d3599a7371bcc685315876c54cdf956a8c990ce6526f6be8e50591bde3be2",
+		  "sender_address": {
+			"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
+			"domain": "",
+			"port": null
+		  },
+		  "sender_sig": "3045022100945b57de1e8b9f7863c4f4c5698d5617ffa55748c80a8324729f98ce5ef86509022063f6bc511d80046f6f21c9476344ed8d948234cc32a0b022d720161798e09861"
+		}
+	  }
+	}
+	# "#
+	# , true, 5, true, true, true, true, false);
+	#
+	# // Comapact slate case, kernel is different now.
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_payment_proof",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"refresh_from_node": true,
+			"tx_id": null,
+			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b01"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "amount": "2000000000",
+		  "excess": "09eac5f5872fa5e08e0c29fd900f1b8f77ff3ad1d0d1c46aeb202cbf92363fe0af",
+		  "recipient_address": {
+			"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
+			"domain": "",
+			"port": null
+		  },
+		  "recipient_sig": "304402204417c3b64709c7e38197103b6979dacee4e41c3ee44d89ea75191f553e2bbcc2022044c52df100fffd080c95575c92329b6ddbd0c77545e31ee439112655918fe0ee",
+		  "sender_address": {
+			"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
+			"domain": "",
+			"port": null
+		  },
+		  "sender_sig": "3044022001d10e4e1fd303748120a45cfcdedcd8b1abc1ffe8ff59d35ddb2ec5b7c2e1a902207dab490bd26d16784933724c559e5a1e4dc56a6c0941f4dd70a22b0c4cfbcc40"
+		}
+	  }
+	}
+	# "#
+	# , true, 5, true, true, true, true, true);
+	```
+	*/
+
+	fn retrieve_payment_proof(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<PaymentProof, ErrorKind>;
+
+	/**
+	Networked version of [Owner::verify_payment_proof](struct.Owner.html#method.verify_payment_proof).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "verify_payment_proof",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"proof": {
+			  "amount": "2000000000",
 			  "excess": "08b3b8b83c622f630141a66c9cad96e19c78f745e4e2ddea85439f05d14a404640",
 			  "recipient_address": {
 				"domain": "",
@@ -2831,13 +4092,499 @@ pub trait OwnerRpcV3 {
 				"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2"
 
 			  },
-			  "recipient_sig": "30440220050ccd7244a8e1bcad8724a26bef6e0bc3df85f09dfc41870635711627955c4c02202b3d3599a7371bcc685315876c54cdf956a8c990ce6526f6be8e50591bde3be2",
-			  "sender_address": {
-				"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
-				"domain": "",
-				"port": null
+			  "recipient_sig": "30440220050ccd7244a8e1bcad8724a26bef6e0bc3df85f09dfc41870635711627955c4c02202b3d3599a7371bcc685315876c54cdf956a8c990ce6526f6be8e50591bde3be2",
+			  "sender_address": {
+				"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
+				"domain": "",
+				"port": null
+			  },
+			 "sender_sig": "3045022100945b57de1e8b9f7863c4f4c5698d5617ffa55748c80a8324729f98ce5ef86509022063f6bc511d80046f6f21c9476344ed8d948234cc32a0b022d720161798e09861"
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": [
+				true,
+				false
+			]
+		}
+	}
+	# "#
+	# , true, 5, true, true, true, true, false);
+	#
+	# // Compact slate case
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "verify_payment_proof",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"proof": {
+				  "amount": "2000000000",
+				  "excess": "09eac5f5872fa5e08e0c29fd900f1b8f77ff3ad1d0d1c46aeb202cbf92363fe0af",
+				  "recipient_address": {
+					"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
+					"domain": "",
+					"port": null
+				  },
+				  "recipient_sig": "304402204417c3b64709c7e38197103b6979dacee4e41c3ee44d89ea75191f553e2bbcc2022044c52df100fffd080c95575c92329b6ddbd0c77545e31ee439112655918fe0ee",
+				  "sender_address": {
+					"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
+					"domain": "",
+					"port": null
+				  },
+				  "sender_sig": "3044022001d10e4e1fd303748120a45cfcdedcd8b1abc1ffe8ff59d35ddb2ec5b7c2e1a902207dab490bd26d16784933724c559e5a1e4dc56a6c0941f4dd70a22b0c4cfbcc40"
+				}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": [
+				true,
+				false
+			]
+		}
+	}
+	# "#
+	# , true, 5, true, true, true, true, true);
+	```
+	*/
+
+	fn verify_payment_proof(
+		&self,
+		token: Token,
+		proof: PaymentProof,
+	) -> Result<(bool, bool), ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_tor_config](struct.Owner.html#method.set_tor_config).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_tor_config",
+		"params": {
+			"tor_config": {
+				"use_tor_listener": true,
+				"socks_proxy_addr": "127.0.0.1:59050",
+				"send_config_dir": ".",
+				"socks_running": false
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+	fn set_tor_config(&self, tor_config: Option<TorConfig>) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::encode_slatepack_message](struct.Owner.html#method.encode_slatepack_message).
+	```
+	# // Compact slate processing, V3
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "encode_slatepack_message",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"recipient": {
+					"public_key" : "3zvywmzxtlm5db6kud3sc3sjjeet4hdr3crcoxpul6h3fnlecvevepqd",
+					"domain": "",
+					"port": null
+				},
+			"content" : "InvoiceInitial",
+			"address_index" : null,
+			"slate": {
+			  "amount": "2000000000",
+			  "coin_type": null,
+			  "compact_slate": true,
+			  "fee": "0",
+			  "height": "4",
+			  "id": "0436430c-2b02-624c-2032-570501212b02",
+			  "lock_height": "0",
+			  "network_type": null,
+			  "num_participants": 2,
+			  "participant_data": [
+				{
+				  "id": "0",
+				  "message": "Please give me your coins",
+				  "message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b8e466fa4596adb9436b38e35feb34c4af52bdf913034b7ae425a35437d521c07",
+				  "part_sig": null,
+				  "public_blind_excess": "02e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
+				  "public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+				}
+			  ],
+			  "payment_proof": null,
+			  "ttl_cutoff_height": null,
+			  "tx": {
+				"body": {
+				  "inputs": [],
+				  "kernels": [
+					{
+					  "excess": "000000000000000000000000000000000000000000000000000000000000000000",
+					  "excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+					  "features": "Plain",
+					  "fee": "0",
+					  "lock_height": "0"
+					}
+				  ],
+				  "outputs": [
+					{
+					  "commit": "088119ed65640d33407d84da4992850eb6a5c2b68ad2ff2323dee51495599bc42d",
+					  "features": "Plain",
+					  "proof": "5035e8cc9a8f35353bf73124ef12b3f7cff7dbcfcc8476c796f62bdf48000e7c87a0a70f2deb44f502bf3be08302d2affb51ae9b7b7d21b96752dc9bd22932520c46311cc0492b1a8e5bcd5c12df5eda2a05860c9db2ac178a2c1c5c01acf3859b068c927a300a4d883b03f03a062dd8475174d8d1770dca2d24e60a8899907b8b425346f1c75c8febaf4b21d81666d9fb6af62f8059f55677a8cef90e64be362d6c7232e009209fbe4a1b1918211109d3d16f08fc018b1a3d3bd11be9495a6a40cbb433130f74b2e0fd4d97da78e623f329922e07a791aab6c93a477449c04894cfdba37a3748fd7fd7203b93e73b00299e367efa5411cd5da70104dc25fda3497c3c99bda84f3bce4c205cb27d72979bdcbfa495599d9804cba3096319c3c5c4aaeeadbda2b185196a3b5785c3e68de0ec260cb1450cfbe0934c78f61a4df8632018e731016aa82dab83f09670534e04c044d20eaa2b9281bdf6d3677be6fab54203b95701c8a962638e78706b3024c61994b420705934f9f7fdd36bc00431cea462edbabbef2aea62cf422a736f02f8852c53996d0e663648f67838b2f084db39b115de1dc05047803071e1ac2ce25e5d2ecf41a83f12adb88ee336ba6e04b52a59fe138245ed2a2ff46ff38221ee7fcf311bb330947766d8f695ec990efe63df358bd17d15d825c42b8de93cf740a22a0328781e76e92f210ba0ae989c4290f3035b208b27a616076b6873e851f3b5b74ad8bbd01cbebcc7b5d0c0d7c4604136106d1086f71b467d06c7c91caf913fc2bc588762fd63ce4ed2f85b1befdd4fa29ae073b943fc00fc9a675a676d6d3be03e1b7ac351379966fc5bcf8584508b975974fd98c3062861e588453a96296fae97488f42662f55af630389a436707940a673a36e19fc720c859660eabc9de31b4e48cef26b88b8a3af462c8ad62f461714"
+					}
+				  ]
+				},
+				"offset": "0000000000000000000000000000000000000000000000000000000000000000"
+			  },
+			  "version_info": {
+				"block_header_version": 2,
+				"orig_version": 3,
+				"version": 3
+			  }
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": "BEGINSLATEPACK. CD7MfBUbThCtDiA cdRUEr4KKm4Uwn4 z1LzfJ29o61nKAW Qc8WjGcpXHfn6po dYi5seYKNurEkMf MDJyLEQN9mUXDvy ModjyEmuTtpEDF1 xE286XvRdYPNkjM BnXg7sdzuHK1xVL iK5srPup1vAyEhM GJDGcLxFP4dyWdN zVqNsa6pMy8WJzv QPtF784fKDzPh7Z BPDvNXzvAz5nSkL 1c2FFvQzrvZudCy 1x33VwLDER6UzyD kpFFfxGqx5NLeTG Qy19AHrEUes1ecR wqPBSiGd3t8mdAB 44muWRz9AcriAbH ntZWUzgzyWVH5m2 nMQzau7fmpZbRqi PpZERxsVWxPksh1 UfrgtULqitKWVQ8 rD9NCbj5czuXf. ENDSLATEPACK."
+	  }
+	}
+	# "#
+	# ,true, 4, false, false, false, false, true);
+	#
+	# // Converting slate into non encrypted binary, recipient is null
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "encode_slatepack_message",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"recipient": null,
+			"content" : "InvoiceInitial",
+			"address_index" : null,
+			"slate": {
+			  "amount": "2000000000",
+			  "coin_type": null,
+			  "compact_slate": true,
+			  "fee": "0",
+			  "height": "4",
+			  "id": "0436430c-2b02-624c-2032-570501212b02",
+			  "lock_height": "0",
+			  "network_type": null,
+			  "num_participants": 2,
+			  "participant_data": [
+				{
+				  "id": "0",
+				  "message": null,
+				  "message_sig": null,
+				  "part_sig": null,
+				  "public_blind_excess": "02e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
+				  "public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+				}
+			  ],
+			  "payment_proof": null,
+			  "ttl_cutoff_height": null,
+			  "tx": {
+				"body": {
+				  "inputs": [],
+				  "kernels": [
+					{
+					  "excess": "000000000000000000000000000000000000000000000000000000000000000000",
+					  "excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+					  "features": "Plain",
+					  "fee": "0",
+					  "lock_height": "0"
+					}
+				  ],
+				  "outputs": [
+					{
+					  "commit": "088119ed65640d33407d84da4992850eb6a5c2b68ad2ff2323dee51495599bc42d",
+					  "features": "Plain",
+					  "proof": "5035e8cc9a8f35353bf73124ef12b3f7cff7dbcfcc8476c796f62bdf48000e7c87a0a70f2deb44f502bf3be08302d2affb51ae9b7b7d21b96752dc9bd22932520c46311cc0492b1a8e5bcd5c12df5eda2a05860c9db2ac178a2c1c5c01acf3859b068c927a300a4d883b03f03a062dd8475174d8d1770dca2d24e60a8899907b8b425346f1c75c8febaf4b21d81666d9fb6af62f8059f55677a8cef90e64be362d6c7232e009209fbe4a1b1918211109d3d16f08fc018b1a3d3bd11be9495a6a40cbb433130f74b2e0fd4d97da78e623f329922e07a791aab6c93a477449c04894cfdba37a3748fd7fd7203b93e73b00299e367efa5411cd5da70104dc25fda3497c3c99bda84f3bce4c205cb27d72979bdcbfa495599d9804cba3096319c3c5c4aaeeadbda2b185196a3b5785c3e68de0ec260cb1450cfbe0934c78f61a4df8632018e731016aa82dab83f09670534e04c044d20eaa2b9281bdf6d3677be6fab54203b95701c8a962638e78706b3024c61994b420705934f9f7fdd36bc00431cea462edbabbef2aea62cf422a736f02f8852c53996d0e663648f67838b2f084db39b115de1dc05047803071e1ac2ce25e5d2ecf41a83f12adb88ee336ba6e04b52a59fe138245ed2a2ff46ff38221ee7fcf311bb330947766d8f695ec990efe63df358bd17d15d825c42b8de93cf740a22a0328781e76e92f210ba0ae989c4290f3035b208b27a616076b6873e851f3b5b74ad8bbd01cbebcc7b5d0c0d7c4604136106d1086f71b467d06c7c91caf913fc2bc588762fd63ce4ed2f85b1befdd4fa29ae073b943fc00fc9a675a676d6d3be03e1b7ac351379966fc5bcf8584508b975974fd98c3062861e588453a96296fae97488f42662f55af630389a436707940a673a36e19fc720c859660eabc9de31b4e48cef26b88b8a3af462c8ad62f461714"
+					}
+				  ]
+				},
+				"offset": "0000000000000000000000000000000000000000000000000000000000000000"
+			  },
+			  "version_info": {
+				"block_header_version": 2,
+				"orig_version": 3,
+				"version": 3
+			  }
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": "BEGINSLATE_BIN. 9ahjQefP9gsCcVt 25Po4VP34y95yxE wMmTzzckUkh1tu3 y7WwT5j1ZTL7UyC 4byFhRQM4BmhM92 Y1ukWPJ8BVdpEGU MAJUrU2YbXFLAYT tdqamYotCv4Co3z keD8RdPpX4b. ENDSLATE_BIN."
+	  }
+	}
+	# "#
+	# ,true, 4, false, false, false, false, true);
+	```
+	*/
+
+	fn encode_slatepack_message(
+		&self,
+		token: Token,
+		slate: VersionedSlate,
+		content: SlatePurpose,
+		recipient: Option<ProvableAddress>,
+		address_index: Option<u32>,
+	) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::decode_slatepack_message](struct.Owner.html#method.decode_slatepack_message).
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "decode_slatepack_message",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"address_index": null,
+			"message": "BEGINSLATEPACK. BMPbuLeVjyFSo36 NvoGhFVYSFxK58D 7ENH2twNyFzuukP mHsGxDrNnBRg5Vd fbKWgneT6YxtUR2 nSmKgFrokk1NEYY qA5cHKVdKaRaKCA J9oX86S4ToB2GEC yHKuKQRe8mwgxn3 PnjdLhtxvuLvXaD 5G8FAqEdWF2RNy1 jAH2xaUsus2onwt 1QM8oENbqUXSSP1 rxo54eupY5ECeiP RW9NY4tM1M6DEf1 BDRhK13rkJRyBzV o2fyjddd4HftuFe d3eHyC1ZEhSxitC 3mi5CmPYzV1JmMp bjwDKGH3jonnxfr 7Ah3ysctRymaXUM 4dH1Ln6UVvD5umC nyam9s4XusiCAky x7VaZGR36MmLzyw eMHSNXBYi12hmq3 D4iouD74YEcPSKD 2JoV5YqZGueBRH4 p8c47hboNujhko2 SPqzY2LiecEfkSK CojyRCQH1C5TE2K QbqNVMniZQQjmN4 TxZNr2kx1D7GFGH CriBfZDoDyDHtYk S4bE6TqA8WGE7oA 9AM2XMoCsZpVyS9 TYTM6wAwXAPhMZU ELo5bZBpMC5GnwN 3LDyFAxuPjxj2Bw eVQNKhLNtUpcstW x1gkYDTNtQTaXyD n8s9oTE7cuJZ5YK 1oooXzP9T31Mmzf kahNCcmtEZipQVb 8e1ba7ormFGegug RS7TTutor3EfxDC yCBRZBuwxFf3Uxq B55wb7yZN53fAMm Zn8SBTxNBzuZiX8 16GyVZx9xbrcB64 krz7XigXGf7hWFd wmuvYqzj7yqson1 hrNftRpEhJbvk3h QFJd7AcxwHwJH2L GdT2xe3DAqBtW4e zgSkQjNnoPhPhii JXBZay4z4eaKBie 1J9KFb6YcXHtcsH dffsHHgXs6GQRZG 71od7BKQVzTWuwv r95CZp6kSpYMqfd G5MfFpjBcD9v9bq WvkoYPuWN6Jf7dA S2fXznZReqLmZzt TGtcahQF5iPLmTr MoE15UceR5bNMBY MAN3AzctE3Vr2fa BDmaMrpHvRCb7pw Y2QUv2R6ZoztGtW Tssap8D7KJtp3g9 fg7kByVQSceSvQE opCAtY7VACJdyag 9D2jmuXZLCawnLM cH3E7MuLqNADMcj PebyUeKE93ttcmW JYGifAGr6cS7WYC Ujwrzg9EhhfjVSd KpArBHx1wvXUzdP kZojuqo2cr7DVqD zQGfUDWGXMbHYbX UJuLtbwFnkQt7Cg rjnn4kj4HZfx7fj uLeS3VaBtMyxLGF 5yECTWgQ2HcGnSK iRDMM9dqncWR6rc 1nXYww6LfUzZxj7 WB1ioS9kAD3XZXw SQX8VZ54tvPbBJ3 pGz3q9RxdvbhcS2 cgxj8K4qCMjJfoM AxDNdcpD2gXuy79 whZfDJ4H6gYvTsp uUfQazqTD46yudj UhXn5X1tZgcExVx FwDUVMTHUTmqmjT 9iJ4Xox2K9HDytN XuJoEk7nH2DCQyi NgPGg4FTibENxZv DB22bb2FQR7W8mu 2hjsx8CWnqWJ56u oyYCXkqynhcKLtb G18knjmUYx3xjyN BVe1u5XHw8HJak5 C9goSRBv29hbf6z Mm4hH7waqL5kAVC vo3vwBrVPeA8K8X QE2JoJ8vuVCFXbM GWJRLnAiYTcUxtq i5aNtAgViPt6GRA 7K5rYdxRAeTc6Ym NZDeoGHoPKrLHwF D5cb8YB7JkmEtnR puX22WD1pagcRph uZJ8xw2gt6RQFQN ypXgHrDFv6W8HNX jNRDvFHpbrmceRR DTBPeWwdpYoTogg E7gnQE1U1ULRi3R XLJJLY5fAVKesTN HyutbMxBvJ3DUz3 CfWDXrRZgG6vQDM 8CboKtLz7dAs5Ui GTLbS5ZYXxpKQbY TXmfDW4wVNXS5G2 NA8gokJPRZ9phXt tYhDG3E9pfVK4mK 8Gmf9Mt6iPRxzvf aQkwRY81peGzJSz ZpPVXAhNFXjnENN MTW8pvTVfYH4Dxt Sabo3XdiBusHygj A8PbFAon6fCAySo jworMXbsSKrrFyA eM7whDYiZJVYFwR SJnqGPTnMtJy9pZ JzsUoTLJFUegFXr YVeHFDTcBJ3qniU Kbc9RsmRrTv512C HajdTQmdrVDR9c7 wC4wcRm7rmXTqny xMYLL6v62RhWcPz C315C3PZtXyxzke WhsCxvctHcS4Wd7 sVLRNvFsxfbN7be FkAjdTLP7oo2Aq9 6bn3GWTypxAdQyZ kWDxEaq8jcBDgR1 EpSLVeJJ7Vd4CrN PxMNvhhTdJs5nUp WzuZNfRNZhajTNZ FLgjbUTUnyk3u92 9PLbPmhSLvVZZHU Yw5PPiaKWWgx1AZ EpY2TxjvgMFRXJy CTFHhms1FAjDE7Q LNizD7TPNyCSbM2 Ha3RZHxPHt8gqSJ ZVbVpFdPJcWWNrQ ms9hr66LU144dgo KLKJedbLSg1sLMo vW8GA5e8hyCMQZG 3JqJKCXnfh7dyjP 5zNRzKYd9FdiR71 gSQBgVEVRavCpJV aXKco2M8kNWdPtw ZNgX. ENDSLATEPACK."
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "content": "SendResponse",
+		  "recipient": {
+			"public_key": "fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd",
+			"domain": "",
+			"port": null
+		  },
+		  "sender": {
+			"public_key": "7rky2tvk763cq5kvhyxv7zkjxfytmao3qttqvoc6fsiawo4kzgii7bqd",
+			"domain": "",
+			"port": null
+		  },
+		  "slate": {
+			"amount": "0",
+			"coin_type": "mwc",
+			"compact_slate": true,
+			"fee": "0",
+			"height": "5",
+			"id": "0436430c-2b02-624c-2032-570501212b00",
+			"lock_height": "0",
+			"network_type": "automatedtests",
+			"num_participants": 2,
+			"participant_data": [
+			  {
+				"id": "1",
+				"message": null,
+				"message_sig": null,
+				"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bb9128fbee3070329b28c635090138e2b78fe1fbb840117b2f65777508179be0a",
+				"public_blind_excess": "02e3c128e436510500616fef3f9a22b15ca015f407c8c5cf96c9059163c873828f",
+				"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+			  }
+			],
+			"payment_proof": null,
+			"ttl_cutoff_height": null,
+			"tx": {
+			  "body": {
+				"inputs": [],
+				"kernels": [
+				  {
+					"excess": "08e3c128e436510500616fef3f9a22b15ca015f407c8c5cf96c9059163c873828f",
+					"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+					"features": "Plain",
+					"fee": "0",
+					"lock_height": "0"
+				  }
+				],
+				"outputs": [
+				  {
+					"commit": "082967b3fe580cd110355010ef45450314fb067720db01b0e6873bb083d76708c9",
+					"features": "Plain",
+					"proof": "828bb24121aa0332c872062a42a8333c3ef81f8ae37d24053d953217368b3cada90410a50509a0b9fcbb5aded41397fc00ca1ff5acdac20d48afb0a3281d21e7026d32fdc6c5157461a35f98a809ffa09187c1e170ea24652ad213b7e4c9878654ac3dd9a8915eaf742db53182fcb42d2d341fbdfe8bd31bd001f4ff2c1ca9f9b1531da29137214f211edb7a5eb8f494cb8945f8527dd25bf7e698515043db4249540720008a708db5342230d05b069c094688ccb7c07d4a4a2293ea76cf999c555dc0ddc757891c360db1901bbb4dc20cae997f875f8de482d8160e05d60f9b0135e0fc313d8f953db78f1ea252449dd81cfa22dd895512ed39d566f0924542b543d25fc9fc7a819d228f3b0ee5e381f088f54893e86437dafc49dd923b3e6dff956ca843f951910379531fac9bb5fd01a182dd32a4c597f92da3c01af37cb9b0ec984500884438e74e54d7e76fa1ae7241d5050b13376310b24761634a6f6eb7cf000082f50ed7c1899d7918023d4f877586f964932a7af72e7a4984ddecfdd1921a2e1b80b00d6bd2e64a3f4cb6915a27a8d17a69d163cf45220a13fcddd15dc2bb91ae4f1b6a67224ab3b23e8d7d785df178ec78a84cf42cea086426f563822c8a4271a0b89bb21f84b643dbf1de21b6395039d673a376492767199fa36ccd9a13628ce61695424091acc16059450d59bc59fa7879e7306f5727217211b0264a6a560f886d520e41406ef45b1668805b88d246c5b2ca5a1762042c85be34fcd420ac3843f32236d079b4bd57d6b8d8013d9d18f8efb55e8e443cd9e1af9b144e7a56c8c6be0138af3b4a6c99bee9109bed2bce2e5145e736b125a2ec19aaf3fff713f6897fdd4158ce2ab04706b062ca2847bf70259c0fc4b0d390dc7fdaf0362047f775a912bd22da9d40f04d9790bcd5ece4b36b74c6c340b48c2926b916e8a9"
+				  },
+				  {
+					"commit": "096e1669267c22ecb38c466d73b8578261d8e91c14dd66702dd5bf34f4232e10db",
+					"features": "Plain",
+					"proof": "7d567b0895a1103d19446929da8b98f2086819507ddce4b9dbb5ce6327107744e74aba59ef1834937da1b86eb7c1c1b0bc11d1c5d5ec79d25bc1e52aed1656f60d46f6878ba5ca8639efdbb9203e378e91171c11527c4a34713f06dc22f58ca4a08e68d83ff897e61cfc145fe376fa428b55e25cf20d15f10b9054778229798b30fb4e45d817a5053b682dcf591481a3c8174cfbba81e31aa525d5b884ca7a016713178f26c0fe8ae1f88b5382f8e70c4d91fb3828c0f307d828aa028281d3551525e68d20827ab0e6785c6b5747e895dcd38429b44e62b7f6c1c921d87ae954a9dd6e967ac52e6cd13a1d4bb2f1434da25a0723ef9c869cc573019577552dd0e0f808f8cc57723b041320025f6433779fe907998a4ec7606bf884b2199253b502065bed8e0625c2df858d6508c1aa44deddc68d06d00d81e97720e23e15a3464ed4733fc547e9fb772e563a1dbcd27ac55e40f674f9006e7dd4465444f3eb7527cb01905dee69a51cf2fc1810c861dd0834e7649d594c3e1740d85343a6b63c8a9e0a0f63059031899b38dfd9a192034d54029bd35e683ccab46282519b26cae20d398b754357abe1cf0370890f2897b5d8ada4fb3da777a8f8f1daa4197a380e6734504117dd2a92ea1917f174c44c59e0b50c6b7a5f9eb14e6d96cb6b3e5dbcb3d0eaf0e4aac1b6616d674bb708b7559e37de608e8a828bee7f25f627e2f06d9a87e8d651ade39e1e65db7204b94abc0b7ca6fdd75aadeeac6a876b6297e38039734ebdfa9a555152b4293cb00e423a66d64f827afa4748dd6fdc1dc33332bffb820dacbf5a6d347042db985bbd9cf476dceb45d6978035ba03d25612243fc164c0a902017ce7ffd632d041fa3c56554739e78c6d725ecbfdaa0739d3649239fb53294b7a46ee6ed403bf3815f6c78f06a8ca4e3c9b066234f7574fb6ea8f17d199"
+				  }
+				]
+			  },
+			  "offset": "97e0fccd0b805d10065a4f8ca46aa6cc73f0962e829c7f13836e2c8371da6293"
+			},
+			"version_info": {
+			  "block_header_version": 1,
+			  "orig_version": 3,
+			  "version": 3
+			}
+		  }
+		}
+	  }
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	#
+	# // Decode not encrypted slate pack
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "decode_slatepack_message",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"address_index": null,
+			"message": "BEGINSLATE_BIN. 9ahjQefP9gsCcVt 25Po4VP34y95yxE wMmTzzckUkh1tu3 y7WwT5j1ZTL7UyC 4byFhRQM4BmhM92 Y1ukWPJ8BVdpEGU MAJUrU2YbXFLAYT tdqamYotCv4Co3z keD8RdPpX4b. ENDSLATE_BIN."
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "content": "InvoiceInitial",
+		  "recipient": null,
+		  "sender": null,
+		  "slate": {
+			"amount": "2000000000",
+			"coin_type": "mwc",
+			"compact_slate": true,
+			"fee": "0",
+			"height": "4",
+			"id": "0436430c-2b02-624c-2032-570501212b02",
+			"lock_height": "0",
+			"network_type": "automatedtests",
+			"num_participants": 2,
+			"participant_data": [
+			  {
+				"id": "0",
+				"message": null,
+				"message_sig": null,
+				"part_sig": null,
+				"public_blind_excess": "02e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
+				"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+			  }
+			],
+			"payment_proof": null,
+			"ttl_cutoff_height": null,
+			"tx": {
+			  "body": {
+				"inputs": [],
+				"kernels": [
+				  {
+					"excess": "09e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
+					"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+					"features": "Plain",
+					"fee": "0",
+					"lock_height": "0"
+				  }
+				],
+				"outputs": []
 			  },
-			 "sender_sig": "3045022100945b57de1e8b9f7863c4f4c5698d5617ffa55748c80a8324729f98ce5ef86509022063f6bc511d80046f6f21c9476344ed8d948234cc32a0b022d720161798e09861"
+			  "offset": "0000000000000000000000000000000000000000000000000000000000000000"
+			},
+			"version_info": {
+			  "block_header_version": 1,
+			  "orig_version": 3,
+			  "version": 3
+			}
+		  }
+		}
+	  }
+	}
+	# "#
+	# , true, 0, false, false, false, false, true);
+	```
+	*/
+
+	fn decode_slatepack_message(
+		&self,
+		token: Token,
+		message: String,
+		address_index: Option<u32>,
+	) -> Result<SlatepackInfo, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_start](struct.Owner.html#method.swap_start).
+	Starts a new swap trade as a Seller. Returns the swap trade Id.
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_start",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"params": {
+				"mwc_amount": 1000000000,
+				"secondary_currency": "xyz",
+				"secondary_amount": "0.01",
+				"secondary_redeem_address": "mjdcskZm4Kwi5Wn7ufeDYHnbJGmXT3uFGa",
+				"secondary_fee": null,
+				"seller_lock_first": true,
+				"minimum_confirmations": 1,
+				"mwc_confirmations": 20,
+				"secondary_confirmations": 6,
+				"message_exchange_time_sec": 3600,
+				"redeem_time_sec": 3600,
+				"buyer_communication_method": "file",
+				"buyer_communication_address": "offer.swap",
+				"electrum_node_uri1": null,
+				"electrum_node_uri2": null,
+				"dry_run": true
 			}
 		},
 		"id": 1
@@ -2849,39 +4596,32 @@ pub trait OwnerRpcV3 {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": [
-				true,
-				false
-			]
+			"Err": {
+				"SwapError": "Swap Invalid currency: xyz"
+			}
 		}
 	}
 	# "#
-	# , true, 5, true, true, true, true, false);
-	#
-	# // Compact slate case
+	# , true, 4, false, false, false, false, false);
+	```
+	*/
+	fn swap_start(&self, token: Token, params: SwapStartArgs) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_create_from_offer_message](struct.Owner.html#method.swap_create_from_offer_message).
+	Creates a Buyer swap trade from the offer message (json string). Returns the swap trade Id.
+
+	# Json rpc example
+
+	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "verify_payment_proof",
+		"method": "swap_create_from_offer",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"proof": {
-				  "amount": "2000000000",
-				  "excess": "09eac5f5872fa5e08e0c29fd900f1b8f77ff3ad1d0d1c46aeb202cbf92363fe0af",
-				  "recipient_address": {
-					"public_key": "xmgceW7Z2phenRwaBeKvTRZkPMJarwLFa8h5LW5bdHKucaKTeuE2",
-					"domain": "",
-					"port": null
-				  },
-				  "recipient_sig": "304402204417c3b64709c7e38197103b6979dacee4e41c3ee44d89ea75191f553e2bbcc2022044c52df100fffd080c95575c92329b6ddbd0c77545e31ee439112655918fe0ee",
-				  "sender_address": {
-					"public_key": "xmgwbyjMEMBojnVadEkwVi1GyL1WPiVE5dziQf3TLedHdrVBPGw5",
-					"domain": "",
-					"port": null
-				  },
-				  "sender_sig": "3044022001d10e4e1fd303748120a45cfcdedcd8b1abc1ffe8ff59d35ddb2ec5b7c2e1a902207dab490bd26d16784933724c559e5a1e4dc56a6c0941f4dd70a22b0c4cfbcc40"
-				}
+			"message": "{}"
 		},
 		"id": 1
 	}
@@ -2892,38 +4632,102 @@ pub trait OwnerRpcV3 {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": [
-				true,
-				false
-			]
+			"Err": {
+				"SwapError": "Swap Serde error: Unable to parse Swap Message from {}, missing field `id` at line 1 column 2"
+			}
 		}
 	}
 	# "#
-	# , true, 5, true, true, true, true, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
+	fn swap_create_from_offer(&self, token: Token, message: String) -> Result<String, ErrorKind>;
 
-	fn verify_payment_proof(
-		&self,
-		token: Token,
-		proof: PaymentProof,
-	) -> Result<(bool, bool), ErrorKind>;
+	/**
+	Networked version of [Owner::swap_list](struct.Owner.html#method.swap_list).
+	Lists all swap trades. If `do_check` is true, the status of the active trades will be refreshed.
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_list",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"do_check": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn swap_list(&self, token: Token, do_check: bool) -> Result<Vec<SwapListInfo>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::set_tor_config](struct.Owner.html#method.set_tor_config).
+	Networked version of [Owner::swap_get](struct.Owner.html#method.swap_get).
+	Returns the swap trade data.
+
+	# Json rpc example
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "set_tor_config",
+		"method": "swap_get",
 		"params": {
-			"tor_config": {
-				"use_tor_listener": true,
-				"socks_proxy_addr": "127.0.0.1:59050",
-				"send_config_dir": ".",
-				"socks_running": false
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
 			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn swap_get(&self, token: Token, swap_id: String) -> Result<Swap, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_delete](struct.Owner.html#method.swap_delete).
+	Deletes the swap trade. Only trades in a final state can be deleted.
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_delete",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00"
 		},
 		"id": 1
 	}
@@ -2934,83 +4738,76 @@ pub trait OwnerRpcV3 {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
+			}
 		}
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-	fn set_tor_config(&self, tor_config: Option<TorConfig>) -> Result<(), ErrorKind>;
+	fn swap_delete(&self, token: Token, swap_id: String) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::encode_slatepack_message](struct.Owner.html#method.encode_slatepack_message).
+	Networked version of [Owner::swap_dump](struct.Owner.html#method.swap_dump).
+	Returns the swap trade file content.
+
+	# Json rpc example
+
 	```
-	# // Compact slate processing, V3
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "encode_slatepack_message",
+		"method": "swap_dump",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"recipient": {
-					"public_key" : "3zvywmzxtlm5db6kud3sc3sjjeet4hdr3crcoxpul6h3fnlecvevepqd",
-					"domain": "",
-					"port": null
-				},
-			"content" : "InvoiceInitial",
-			"address_index" : null,
-			"slate": {
-			  "amount": "2000000000",
-			  "coin_type": null,
-			  "compact_slate": true,
-			  "fee": "0",
-			  "height": "4",
-			  "id": "0436430c-2b02-624c-2032-570501212b02",
-			  "lock_height": "0",
-			  "network_type": null,
-			  "num_participants": 2,
-			  "participant_data": [
-				{
-				  "id": "0",
-				  "message": "Please give me your coins",
-				  "message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b8e466fa4596adb9436b38e35feb34c4af52bdf913034b7ae425a35437d521c07",
-				  "part_sig": null,
-				  "public_blind_excess": "02e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
-				  "public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-				}
-			  ],
-			  "payment_proof": null,
-			  "ttl_cutoff_height": null,
-			  "tx": {
-				"body": {
-				  "inputs": [],
-				  "kernels": [
-					{
-					  "excess": "000000000000000000000000000000000000000000000000000000000000000000",
-					  "excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-					  "features": "Plain",
-					  "fee": "0",
-					  "lock_height": "0"
-					}
-				  ],
-				  "outputs": [
-					{
-					  "commit": "088119ed65640d33407d84da4992850eb6a5c2b68ad2ff2323dee51495599bc42d",
-					  "features": "Plain",
-					  "proof": "5035e8cc9a8f35353bf73124ef12b3f7cff7dbcfcc8476c796f62bdf48000e7c87a0a70f2deb44f502bf3be08302d2affb51ae9b7b7d21b96752dc9bd22932520c46311cc0492b1a8e5bcd5c12df5eda2a05860c9db2ac178a2c1c5c01acf3859b068c927a300a4d883b03f03a062dd8475174d8d1770dca2d24e60a8899907b8b425346f1c75c8febaf4b21d81666d9fb6af62f8059f55677a8cef90e64be362d6c7232e009209fbe4a1b1918211109d3d16f08fc018b1a3d3bd11be9495a6a40cbb433130f74b2e0fd4d97da78e623f329922e07a791aab6c93a477449c04894cfdba37a3748fd7fd7203b93e73b00299e367efa5411cd5da70104dc25fda3497c3c99bda84f3bce4c205cb27d72979bdcbfa495599d9804cba3096319c3c5c4aaeeadbda2b185196a3b5785c3e68de0ec260cb1450cfbe0934c78f61a4df8632018e731016aa82dab83f09670534e04c044d20eaa2b9281bdf6d3677be6fab54203b95701c8a962638e78706b3024c61994b420705934f9f7fdd36bc00431cea462edbabbef2aea62cf422a736f02f8852c53996d0e663648f67838b2f084db39b115de1dc05047803071e1ac2ce25e5d2ecf41a83f12adb88ee336ba6e04b52a59fe138245ed2a2ff46ff38221ee7fcf311bb330947766d8f695ec990efe63df358bd17d15d825c42b8de93cf740a22a0328781e76e92f210ba0ae989c4290f3035b208b27a616076b6873e851f3b5b74ad8bbd01cbebcc7b5d0c0d7c4604136106d1086f71b467d06c7c91caf913fc2bc588762fd63ce4ed2f85b1befdd4fa29ae073b943fc00fc9a675a676d6d3be03e1b7ac351379966fc5bcf8584508b975974fd98c3062861e588453a96296fae97488f42662f55af630389a436707940a673a36e19fc720c859660eabc9de31b4e48cef26b88b8a3af462c8ad62f461714"
-					}
-				  ]
-				},
-				"offset": "0000000000000000000000000000000000000000000000000000000000000000"
-			  },
-			  "version_info": {
-				"block_header_version": 2,
-				"orig_version": 3,
-				"version": 3
-			  }
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
 			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn swap_dump(&self, token: Token, swap_id: String) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_adjust](struct.Owner.html#method.swap_adjust).
+	Adjusts the swap trade state. `method` and `destination` are required for `adjust_cmd`='destination'.
+	Returns the new state and the expected action.
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_adjust",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00",
+			"adjust_cmd": "cancel",
+			"method": null,
+			"destination": null,
+			"secondary_address": null,
+			"secondary_fee": null,
+			"electrum_node_uri1": null,
+			"electrum_node_uri2": null
 		},
 		"id": 1
 	}
@@ -3018,76 +4815,49 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": "BEGINSLATEPACK. CD7MfBUbThCtDiA cdRUEr4KKm4Uwn4 z1LzfJ29o61nKAW Qc8WjGcpXHfn6po dYi5seYKNurEkMf MDJyLEQN9mUXDvy ModjyEmuTtpEDF1 xE286XvRdYPNkjM BnXg7sdzuHK1xVL iK5srPup1vAyEhM GJDGcLxFP4dyWdN zVqNsa6pMy8WJzv QPtF784fKDzPh7Z BPDvNXzvAz5nSkL 1c2FFvQzrvZudCy 1x33VwLDER6UzyD kpFFfxGqx5NLeTG Qy19AHrEUes1ecR wqPBSiGd3t8mdAB 44muWRz9AcriAbH ntZWUzgzyWVH5m2 nMQzau7fmpZbRqi PpZERxsVWxPksh1 UfrgtULqitKWVQ8 rD9NCbj5czuXf. ENDSLATEPACK."
-	  }
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
+			}
+		}
 	}
 	# "#
-	# ,true, 4, false, false, false, false, true);
-	#
-	# // Converting slate into non encrypted binary, recipient is null
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn swap_adjust(
+		&self,
+		token: Token,
+		swap_id: String,
+		adjust_cmd: String,
+		method: Option<String>,
+		destination: Option<String>,
+		secondary_address: Option<String>,
+		secondary_fee: Option<f32>,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<(StateId, Action), ErrorKind>;
+
+	/**
+	Networked version of [Owner::update_swap_status_action](struct.Owner.html#method.update_swap_status_action).
+	Refreshes the swap trade. Returns: state, expected action, action time limit, roadmap,
+	journal records and the last error.
+
+	# Json rpc example
+
+	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "encode_slatepack_message",
+		"method": "update_swap_status_action",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"recipient": null,
-			"content" : "InvoiceInitial",
-			"address_index" : null,
-			"slate": {
-			  "amount": "2000000000",
-			  "coin_type": null,
-			  "compact_slate": true,
-			  "fee": "0",
-			  "height": "4",
-			  "id": "0436430c-2b02-624c-2032-570501212b02",
-			  "lock_height": "0",
-			  "network_type": null,
-			  "num_participants": 2,
-			  "participant_data": [
-				{
-				  "id": "0",
-				  "message": null,
-				  "message_sig": null,
-				  "part_sig": null,
-				  "public_blind_excess": "02e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
-				  "public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-				}
-			  ],
-			  "payment_proof": null,
-			  "ttl_cutoff_height": null,
-			  "tx": {
-				"body": {
-				  "inputs": [],
-				  "kernels": [
-					{
-					  "excess": "000000000000000000000000000000000000000000000000000000000000000000",
-					  "excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-					  "features": "Plain",
-					  "fee": "0",
-					  "lock_height": "0"
-					}
-				  ],
-				  "outputs": [
-					{
-					  "commit": "088119ed65640d33407d84da4992850eb6a5c2b68ad2ff2323dee51495599bc42d",
-					  "features": "Plain",
-					  "proof": "5035e8cc9a8f35353bf73124ef12b3f7cff7dbcfcc8476c796f62bdf48000e7c87a0a70f2deb44f502bf3be08302d2affb51ae9b7b7d21b96752dc9bd22932520c46311cc0492b1a8e5bcd5c12df5eda2a05860c9db2ac178a2c1c5c01acf3859b068c927a300a4d883b03f03a062dd8475174d8d1770dca2d24e60a8899907b8b425346f1c75c8febaf4b21d81666d9fb6af62f8059f55677a8cef90e64be362d6c7232e009209fbe4a1b1918211109d3d16f08fc018b1a3d3bd11be9495a6a40cbb433130f74b2e0fd4d97da78e623f329922e07a791aab6c93a477449c04894cfdba37a3748fd7fd7203b93e73b00299e367efa5411cd5da70104dc25fda3497c3c99bda84f3bce4c205cb27d72979bdcbfa495599d9804cba3096319c3c5c4aaeeadbda2b185196a3b5785c3e68de0ec260cb1450cfbe0934c78f61a4df8632018e731016aa82dab83f09670534e04c044d20eaa2b9281bdf6d3677be6fab54203b95701c8a962638e78706b3024c61994b420705934f9f7fdd36bc00431cea462edbabbef2aea62cf422a736f02f8852c53996d0e663648f67838b2f084db39b115de1dc05047803071e1ac2ce25e5d2ecf41a83f12adb88ee336ba6e04b52a59fe138245ed2a2ff46ff38221ee7fcf311bb330947766d8f695ec990efe63df358bd17d15d825c42b8de93cf740a22a0328781e76e92f210ba0ae989c4290f3035b208b27a616076b6873e851f3b5b74ad8bbd01cbebcc7b5d0c0d7c4604136106d1086f71b467d06c7c91caf913fc2bc588762fd63ce4ed2f85b1befdd4fa29ae073b943fc00fc9a675a676d6d3be03e1b7ac351379966fc5bcf8584508b975974fd98c3062861e588453a96296fae97488f42662f55af630389a436707940a673a36e19fc720c859660eabc9de31b4e48cef26b88b8a3af462c8ad62f461714"
-					}
-				  ]
-				},
-				"offset": "0000000000000000000000000000000000000000000000000000000000000000"
-			  },
-			  "version_info": {
-				"block_header_version": 2,
-				"orig_version": 3,
-				"version": 3
-			  }
-			}
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00",
+			"electrum_node_uri1": null,
+			"electrum_node_uri2": null
 		},
 		"id": 1
 	}
@@ -3095,38 +4865,53 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": "BEGINSLATE_BIN. 9ahjQefP9gsCcVt 25Po4VP34y95yxE wMmTzzckUkh1tu3 y7WwT5j1ZTL7UyC 4byFhRQM4BmhM92 Y1ukWPJ8BVdpEGU MAJUrU2YbXFLAYT tdqamYotCv4Co3z keD8RdPpX4b. ENDSLATE_BIN."
-	  }
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
+			}
+		}
 	}
 	# "#
-	# ,true, 4, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn encode_slatepack_message(
+	fn update_swap_status_action(
 		&self,
 		token: Token,
-		slate: VersionedSlate,
-		content: SlatePurpose,
-		recipient: Option<ProvableAddress>,
-		address_index: Option<u32>,
-	) -> Result<String, ErrorKind>;
+		swap_id: String,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<
+		(
+			StateId,
+			Action,
+			Option<i64>,
+			Vec<StateEtaInfo>,
+			Vec<SwapJournalRecord>,
+			Option<String>,
+		),
+		ErrorKind,
+	>;
 
 	/**
-	Networked version of [Owner::decode_slatepack_message](struct.Owner.html#method.decode_slatepack_message).
+	Networked version of [Owner::get_swap_tx_tstatus](struct.Owner.html#method.get_swap_tx_tstatus).
+	Returns confirmations of the MWC and secondary currency transactions involved into the swap trade.
+
+	# Json rpc example
+
 	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "decode_slatepack_message",
+		"method": "get_swap_tx_tstatus",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"address_index": null,
-			"message": "BEGINSLATEPACK. BMPbuLeVjyFSo36 NvoGhFVYSFxK58D 7ENH2twNyFzuukP mHsGxDrNnBRg5Vd fbKWgneT6YxtUR2 nSmKgFrokk1NEYY qA5cHKVdKaRaKCA J9oX86S4ToB2GEC yHKuKQRe8mwgxn3 PnjdLhtxvuLvXaD 5G8FAqEdWF2RNy1 jAH2xaUsus2onwt 1QM8oENbqUXSSP1 rxo54eupY5ECeiP RW9NY4tM1M6DEf1 BDRhK13rkJRyBzV o2fyjddd4HftuFe d3eHyC1ZEhSxitC 3mi5CmPYzV1JmMp bjwDKGH3jonnxfr 7Ah3ysctRymaXUM 4dH1Ln6UVvD5umC nyam9s4XusiCAky x7VaZGR36MmLzyw eMHSNXBYi12hmq3 D4iouD74YEcPSKD 2JoV5YqZGueBRH4 p8c47hboNujhko2 SPqzY2LiecEfkSK CojyRCQH1C5TE2K QbqNVMniZQQjmN4 TxZNr2kx1D7GFGH CriBfZDoDyDHtYk S4bE6TqA8WGE7oA 9AM2XMoCsZpVyS9 TYTM6wAwXAPhMZU ELo5bZBpMC5GnwN 3LDyFAxuPjxj2Bw eVQNKhLNtUpcstW x1gkYDTNtQTaXyD n8s9oTE7cuJZ5YK 1oooXzP9T31Mmzf kahNCcmtEZipQVb 8e1ba7ormFGegug RS7TTutor3EfxDC yCBRZBuwxFf3Uxq B55wb7yZN53fAMm Zn8SBTxNBzuZiX8 16GyVZx9xbrcB64 krz7XigXGf7hWFd wmuvYqzj7yqson1 hrNftRpEhJbvk3h QFJd7AcxwHwJH2L GdT2xe3DAqBtW4e zgSkQjNnoPhPhii JXBZay4z4eaKBie 1J9KFb6YcXHtcsH dffsHHgXs6GQRZG 71od7BKQVzTWuwv r95CZp6kSpYMqfd G5MfFpjBcD9v9bq WvkoYPuWN6Jf7dA S2fXznZReqLmZzt TGtcahQF5iPLmTr MoE15UceR5bNMBY MAN3AzctE3Vr2fa BDmaMrpHvRCb7pw Y2QUv2R6ZoztGtW Tssap8D7KJtp3g9 fg7kByVQSceSvQE opCAtY7VACJdyag 9D2jmuXZLCawnLM cH3E7MuLqNADMcj PebyUeKE93ttcmW JYGifAGr6cS7WYC Ujwrzg9EhhfjVSd KpArBHx1wvXUzdP kZojuqo2cr7DVqD zQGfUDWGXMbHYbX UJuLtbwFnkQt7Cg rjnn4kj4HZfx7fj uLeS3VaBtMyxLGF 5yECTWgQ2HcGnSK iRDMM9dqncWR6rc 1nXYww6LfUzZxj7 WB1ioS9kAD3XZXw SQX8VZ54tvPbBJ3 pGz3q9RxdvbhcS2 cgxj8K4qCMjJfoM AxDNdcpD2gXuy79 whZfDJ4H6gYvTsp uUfQazqTD46yudj UhXn5X1tZgcExVx FwDUVMTHUTmqmjT 9iJ4Xox2K9HDytN XuJoEk7nH2DCQyi NgPGg4FTibENxZv DB22bb2FQR7W8mu 2hjsx8CWnqWJ56u oyYCXkqynhcKLtb G18knjmUYx3xjyN BVe1u5XHw8HJak5 C9goSRBv29hbf6z Mm4hH7waqL5kAVC vo3vwBrVPeA8K8X QE2JoJ8vuVCFXbM GWJRLnAiYTcUxtq i5aNtAgViPt6GRA 7K5rYdxRAeTc6Ym NZDeoGHoPKrLHwF D5cb8YB7JkmEtnR puX22WD1pagcRph uZJ8xw2gt6RQFQN ypXgHrDFv6W8HNX jNRDvFHpbrmceRR DTBPeWwdpYoTogg E7gnQE1U1ULRi3R XLJJLY5fAVKesTN HyutbMxBvJ3DUz3 CfWDXrRZgG6vQDM 8CboKtLz7dAs5Ui GTLbS5ZYXxpKQbY TXmfDW4wVNXS5G2 NA8gokJPRZ9phXt tYhDG3E9pfVK4mK 8Gmf9Mt6iPRxzvf aQkwRY81peGzJSz ZpPVXAhNFXjnENN MTW8pvTVfYH4Dxt Sabo3XdiBusHygj A8PbFAon6fCAySo jworMXbsSKrrFyA eM7whDYiZJVYFwR SJnqGPTnMtJy9pZ JzsUoTLJFUegFXr YVeHFDTcBJ3qniU Kbc9RsmRrTv512C HajdTQmdrVDR9c7 wC4wcRm7rmXTqny xMYLL6v62RhWcPz C315C3PZtXyxzke WhsCxvctHcS4Wd7 sVLRNvFsxfbN7be FkAjdTLP7oo2Aq9 6bn3GWTypxAdQyZ kWDxEaq8jcBDgR1 EpSLVeJJ7Vd4CrN PxMNvhhTdJs5nUp WzuZNfRNZhajTNZ FLgjbUTUnyk3u92 9PLbPmhSLvVZZHU Yw5PPiaKWWgx1AZ EpY2TxjvgMFRXJy CTFHhms1FAjDE7Q LNizD7TPNyCSbM2 Ha3RZHxPHt8gqSJ ZVbVpFdPJcWWNrQ ms9hr66LU144dgo KLKJedbLSg1sLMo vW8GA5e8hyCMQZG 3JqJKCXnfh7dyjP 5zNRzKYd9FdiR71 gSQBgVEVRavCpJV aXKco2M8kNWdPtw ZNgX. ENDSLATEPACK."
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00",
+			"electrum_node_uri1": null,
+			"electrum_node_uri2": null
 		},
 		"id": 1
 	}
@@ -3134,92 +4919,50 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": {
-		  "content": "SendResponse",
-		  "recipient": {
-			"public_key": "fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd",
-			"domain": "",
-			"port": null
-		  },
-		  "sender": {
-			"public_key": "7rky2tvk763cq5kvhyxv7zkjxfytmao3qttqvoc6fsiawo4kzgii7bqd",
-			"domain": "",
-			"port": null
-		  },
-		  "slate": {
-			"amount": "0",
-			"coin_type": "mwc",
-			"compact_slate": true,
-			"fee": "0",
-			"height": "5",
-			"id": "0436430c-2b02-624c-2032-570501212b00",
-			"lock_height": "0",
-			"network_type": "automatedtests",
-			"num_participants": 2,
-			"participant_data": [
-			  {
-				"id": "1",
-				"message": null,
-				"message_sig": null,
-				"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bb9128fbee3070329b28c635090138e2b78fe1fbb840117b2f65777508179be0a",
-				"public_blind_excess": "02e3c128e436510500616fef3f9a22b15ca015f407c8c5cf96c9059163c873828f",
-				"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-			  }
-			],
-			"payment_proof": null,
-			"ttl_cutoff_height": null,
-			"tx": {
-			  "body": {
-				"inputs": [],
-				"kernels": [
-				  {
-					"excess": "08e3c128e436510500616fef3f9a22b15ca015f407c8c5cf96c9059163c873828f",
-					"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-					"features": "Plain",
-					"fee": "0",
-					"lock_height": "0"
-				  }
-				],
-				"outputs": [
-				  {
-					"commit": "082967b3fe580cd110355010ef45450314fb067720db01b0e6873bb083d76708c9",
-					"features": "Plain",
-					"proof": "828bb24121aa0332c872062a42a8333c3ef81f8ae37d24053d953217368b3cada90410a50509a0b9fcbb5aded41397fc00ca1ff5acdac20d48afb0a3281d21e7026d32fdc6c5157461a35f98a809ffa09187c1e170ea24652ad213b7e4c9878654ac3dd9a8915eaf742db53182fcb42d2d341fbdfe8bd31bd001f4ff2c1ca9f9b1531da29137214f211edb7a5eb8f494cb8945f8527dd25bf7e698515043db4249540720008a708db5342230d05b069c094688ccb7c07d4a4a2293ea76cf999c555dc0ddc757891c360db1901bbb4dc20cae997f875f8de482d8160e05d60f9b0135e0fc313d8f953db78f1ea252449dd81cfa22dd895512ed39d566f0924542b543d25fc9fc7a819d228f3b0ee5e381f088f54893e86437dafc49dd923b3e6dff956ca843f951910379531fac9bb5fd01a182dd32a4c597f92da3c01af37cb9b0ec984500884438e74e54d7e76fa1ae7241d5050b13376310b24761634a6f6eb7cf000082f50ed7c1899d7918023d4f877586f964932a7af72e7a4984ddecfdd1921a2e1b80b00d6bd2e64a3f4cb6915a27a8d17a69d163cf45220a13fcddd15dc2bb91ae4f1b6a67224ab3b23e8d7d785df178ec78a84cf42cea086426f563822c8a4271a0b89bb21f84b643dbf1de21b6395039d673a376492767199fa36ccd9a13628ce61695424091acc16059450d59bc59fa7879e7306f5727217211b0264a6a560f886d520e41406ef45b1668805b88d246c5b2ca5a1762042c85be34fcd420ac3843f32236d079b4bd57d6b8d8013d9d18f8efb55e8e443cd9e1af9b144e7a56c8c6be0138af3b4a6c99bee9109bed2bce2e5145e736b125a2ec19aaf3fff713f6897fdd4158ce2ab04706b062ca2847bf70259c0fc4b0d390dc7fdaf0362047f775a912bd22da9d40f04d9790bcd5ece4b36b74c6c340b48c2926b916e8a9"
-				  },
-				  {
-					"commit": "096e1669267c22ecb38c466d73b8578261d8e91c14dd66702dd5bf34f4232e10db",
-					"features": "Plain",
-					"proof": "7d567b0895a1103d19446929da8b98f2086819507ddce4b9dbb5ce6327107744e74aba59ef1834937da1b86eb7c1c1b0bc11d1c5d5ec79d25bc1e52aed1656f60d46f6878ba5ca8639efdbb9203e378e91171c11527c4a34713f06dc22f58ca4a08e68d83ff897e61cfc145fe376fa428b55e25cf20d15f10b9054778229798b30fb4e45d817a5053b682dcf591481a3c8174cfbba81e31aa525d5b884ca7a016713178f26c0fe8ae1f88b5382f8e70c4d91fb3828c0f307d828aa028281d3551525e68d20827ab0e6785c6b5747e895dcd38429b44e62b7f6c1c921d87ae954a9dd6e967ac52e6cd13a1d4bb2f1434da25a0723ef9c869cc573019577552dd0e0f808f8cc57723b041320025f6433779fe907998a4ec7606bf884b2199253b502065bed8e0625c2df858d6508c1aa44deddc68d06d00d81e97720e23e15a3464ed4733fc547e9fb772e563a1dbcd27ac55e40f674f9006e7dd4465444f3eb7527cb01905dee69a51cf2fc1810c861dd0834e7649d594c3e1740d85343a6b63c8a9e0a0f63059031899b38dfd9a192034d54029bd35e683ccab46282519b26cae20d398b754357abe1cf0370890f2897b5d8ada4fb3da777a8f8f1daa4197a380e6734504117dd2a92ea1917f174c44c59e0b50c6b7a5f9eb14e6d96cb6b3e5dbcb3d0eaf0e4aac1b6616d674bb708b7559e37de608e8a828bee7f25f627e2f06d9a87e8d651ade39e1e65db7204b94abc0b7ca6fdd75aadeeac6a876b6297e38039734ebdfa9a555152b4293cb00e423a66d64f827afa4748dd6fdc1dc33332bffb820dacbf5a6d347042db985bbd9cf476dceb45d6978035ba03d25612243fc164c0a902017ce7ffd632d041fa3c56554739e78c6d725ecbfdaa0739d3649239fb53294b7a46ee6ed403bf3815f6c78f06a8ca4e3c9b066234f7574fb6ea8f17d199"
-				  }
-				]
-			  },
-			  "offset": "97e0fccd0b805d10065a4f8ca46aa6cc73f0962e829c7f13836e2c8371da6293"
-			},
-			"version_info": {
-			  "block_header_version": 1,
-			  "orig_version": 3,
-			  "version": 3
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
 			}
-		  }
 		}
-	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
-	#
-	# // Decode not encrypted slate pack
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn get_swap_tx_tstatus(
+		&self,
+		token: Token,
+		swap_id: String,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<SwapTransactionsConfirmations, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_process_with_transport](struct.Owner.html#method.swap_process_with_transport).
+	Processes the expected action of the swap trade. Messages for the other party are delivered
+	with the method that is stored with the trade (mwcmqs, tor or file). With the file method the
+	income `message` is passed in and the message for the other party is returned as json string.
+
+	# Json rpc example
+
+	```
 	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "decode_slatepack_message",
+		"method": "swap_process",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"address_index": null,
-			"message": "BEGINSLATE_BIN. 9ahjQefP9gsCcVt 25Po4VP34y95yxE wMmTzzckUkh1tu3 y7WwT5j1ZTL7UyC 4byFhRQM4BmhM92 Y1ukWPJ8BVdpEGU MAJUrU2YbXFLAYT tdqamYotCv4Co3z keD8RdPpX4b. ENDSLATE_BIN."
+			"swap_id": "0436430c-2b02-624c-2032-570501212b00",
+			"apisecret": null,
+			"message": null,
+			"buyer_refund_address": null,
+			"secondary_fee": null,
+			"secondary_address": null,
+			"electrum_node_uri1": null,
+			"electrum_node_uri2": null
 		},
 		"id": 1
 	}
@@ -3227,71 +4970,71 @@ pub trait OwnerRpcV3 {
 	# ,
 	# r#"
 	{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": {
-		  "content": "InvoiceInitial",
-		  "recipient": null,
-		  "sender": null,
-		  "slate": {
-			"amount": "2000000000",
-			"coin_type": "mwc",
-			"compact_slate": true,
-			"fee": "0",
-			"height": "4",
-			"id": "0436430c-2b02-624c-2032-570501212b02",
-			"lock_height": "0",
-			"network_type": "automatedtests",
-			"num_participants": 2,
-			"participant_data": [
-			  {
-				"id": "0",
-				"message": null,
-				"message_sig": null,
-				"part_sig": null,
-				"public_blind_excess": "02e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
-				"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-			  }
-			],
-			"payment_proof": null,
-			"ttl_cutoff_height": null,
-			"tx": {
-			  "body": {
-				"inputs": [],
-				"kernels": [
-				  {
-					"excess": "09e89cce4499ac1e9bb498dab9e3fab93cc40cd3d26c04a0292e00f4bf272499ec",
-					"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-					"features": "Plain",
-					"fee": "0",
-					"lock_height": "0"
-				  }
-				],
-				"outputs": []
-			  },
-			  "offset": "0000000000000000000000000000000000000000000000000000000000000000"
-			},
-			"version_info": {
-			  "block_header_version": 1,
-			  "orig_version": 3,
-			  "version": 3
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap trade 0436430c-2b02-624c-2032-570501212b00 not found"
+			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false, false);
+	```
+	*/
+	fn swap_process(
+		&self,
+		token: Token,
+		swap_id: String,
+		apisecret: Option<String>,
+		message: Option<String>,
+		buyer_refund_address: Option<String>,
+		secondary_fee: Option<f32>,
+		secondary_address: Option<String>,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<(StateProcessRespond, Option<String>), ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_income_message](struct.Owner.html#method.swap_income_message).
+	Processes the swap message (json string) received from the other party. Returns the
+	acknowledge message if it needs to be sent back.
+
+	# Json rpc example
+
+	```
+	# grin_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_income_message",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"message": "{}"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SwapError": "Swap Serde error: Unable to parse Swap Message from {}, missing field `id` at line 1 column 2"
 			}
-		  }
 		}
-	  }
 	}
 	# "#
-	# , true, 0, false, false, false, false, true);
+	# , true, 0, false, false, false, false, false);
 	```
 	*/
-
-	fn decode_slatepack_message(
+	fn swap_income_message(
 		&self,
 		token: Token,
 		message: String,
-		address_index: Option<u32>,
-	) -> Result<SlatepackInfo, ErrorKind>;
+	) -> Result<Option<Message>, ErrorKind>;
 }
 
 impl<L, C, K> OwnerRpcV3 for Owner<L, C, K>
@@ -3331,6 +5074,36 @@ where
 		.map_err(|e| e.kind())
 	}
 
+	fn set_output_label(
+		&self,
+		token: Token,
+		commit: String,
+		label: Option<String>,
+	) -> Result<(), ErrorKind> {
+		Owner::set_output_label(self, (&token.keychain_mask).as_ref(), &commit, label)
+			.map_err(|e| e.kind())
+	}
+
+	fn set_output_note(
+		&self,
+		token: Token,
+		commit: String,
+		note: Option<String>,
+	) -> Result<(), ErrorKind> {
+		Owner::set_output_note(self, (&token.keychain_mask).as_ref(), &commit, note)
+			.map_err(|e| e.kind())
+	}
+
+	fn set_output_frozen(
+		&self,
+		token: Token,
+		commit: String,
+		frozen: bool,
+	) -> Result<(), ErrorKind> {
+		Owner::set_output_frozen(self, (&token.keychain_mask).as_ref(), &commit, frozen)
+			.map_err(|e| e.kind())
+	}
+
 	fn retrieve_txs(
 		&self,
 		token: Token,
@@ -3356,6 +5129,44 @@ where
 		})
 	}
 
+	fn query_txs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		query: TxQueryArgs,
+	) -> Result<(bool, Vec<TxLogEntryAPI>), ErrorKind> {
+		Owner::query_txs(
+			self,
+			(&token.keychain_mask).as_ref(),
+			refresh_from_node,
+			&query,
+		)
+		.map_err(|e| e.kind())
+		.map(|(b, tx)| {
+			(
+				b,
+				tx.iter()
+					.map(|t| TxLogEntryAPI::from_txlogemtry(t))
+					.collect(),
+			)
+		})
+	}
+
+	fn export_txs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		args: TxExportArgs,
+	) -> Result<(bool, String), ErrorKind> {
+		Owner::export_txs(
+			self,
+			(&token.keychain_mask).as_ref(),
+			refresh_from_node,
+			&args,
+		)
+		.map_err(|e| e.kind())
+	}
+
 	fn retrieve_summary_info(
 		&self,
 		token: Token,
@@ -3383,6 +5194,10 @@ where
 			.map_err(|e| ErrorKind::SlatepackEncodeError(format!("{}", e)))?)
 	}
 
+	fn estimate_fee(&self, token: Token, args: InitTxArgs) -> Result<FeeEstimate, ErrorKind> {
+		Owner::estimate_fee(self, (&token.keychain_mask).as_ref(), &args).map_err(|e| e.kind())
+	}
+
 	fn issue_invoice_tx(
 		&self,
 		token: Token,
@@ -3481,6 +5296,225 @@ where
 		Ok(res_slate)
 	}
 
+	fn init_batch_send_tx(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+		payouts: Vec<BatchPayout>,
+	) -> Result<Vec<VersionedSlate>, ErrorKind> {
+		let slates =
+			Owner::init_batch_send_tx(self, (&token.keychain_mask).as_ref(), &args, &payouts)
+				.map_err(|e| e.kind())?;
+		slates
+			.into_iter()
+			.map(|slate| {
+				let version = slate.lowest_version();
+				VersionedSlate::into_version_plain(slate, version).map_err(|e| {
+					ErrorKind::SlatepackEncodeError(format!("Unable to encode the slate, {}", e))
+				})
+			})
+			.collect()
+	}
+
+	fn finalize_batch_tx(
+		&self,
+		token: Token,
+		slates: Vec<VersionedSlate>,
+	) -> Result<TransactionV3, ErrorKind> {
+		let mut batch = Vec::with_capacity(slates.len());
+		for in_slate in slates {
+			let (slate, _content, _sender) =
+				Owner::decrypt_versioned_slate(self, (&token.keychain_mask).as_ref(), in_slate)
+					.map_err(|e| ErrorKind::SlatepackDecodeError(format!("{}", e)))?;
+			batch.push(slate);
+		}
+		Owner::finalize_batch_tx(self, (&token.keychain_mask).as_ref(), &batch)
+			.map(TransactionV3::from)
+			.map_err(|e| e.kind())
+	}
+
+	fn consolidate_outputs(
+		&self,
+		token: Token,
+		args: ConsolidateArgs,
+		preview: bool,
+	) -> Result<Vec<UtxoTx>, ErrorKind> {
+		Owner::consolidate_outputs(self, (&token.keychain_mask).as_ref(), &args, preview)
+			.map_err(|e| e.kind())
+	}
+
+	fn split_output(
+		&self,
+		token: Token,
+		args: SplitArgs,
+		preview: bool,
+	) -> Result<UtxoTx, ErrorKind> {
+		Owner::split_output(self, (&token.keychain_mask).as_ref(), &args, preview)
+			.map_err(|e| e.kind())
+	}
+
+	fn schedule_payment(
+		&self,
+		token: Token,
+		args: SchedulePaymentArgs,
+	) -> Result<ScheduledPayment, ErrorKind> {
+		Owner::schedule_payment(self, (&token.keychain_mask).as_ref(), &args).map_err(|e| e.kind())
+	}
+
+	fn scheduled_payments(&self, token: Token) -> Result<Vec<ScheduledPayment>, ErrorKind> {
+		Owner::scheduled_payments(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn set_scheduled_payment_active(
+		&self,
+		token: Token,
+		id: Uuid,
+		active: bool,
+	) -> Result<ScheduledPayment, ErrorKind> {
+		Owner::set_scheduled_payment_active(self, (&token.keychain_mask).as_ref(), &id, active)
+			.map_err(|e| e.kind())
+	}
+
+	fn delete_scheduled_payment(&self, token: Token, id: Uuid) -> Result<(), ErrorKind> {
+		Owner::delete_scheduled_payment(self, (&token.keychain_mask).as_ref(), &id)
+			.map_err(|e| e.kind())
+	}
+
+	fn save_contact(&self, token: Token, contact: Contact) -> Result<Contact, ErrorKind> {
+		Owner::save_contact(self, (&token.keychain_mask).as_ref(), &contact).map_err(|e| e.kind())
+	}
+
+	fn contacts(&self, token: Token) -> Result<Vec<Contact>, ErrorKind> {
+		Owner::contacts(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn get_contact(&self, token: Token, name: String) -> Result<Contact, ErrorKind> {
+		Owner::get_contact(self, (&token.keychain_mask).as_ref(), &name).map_err(|e| e.kind())
+	}
+
+	fn delete_contact(&self, token: Token, name: String) -> Result<(), ErrorKind> {
+		Owner::delete_contact(self, (&token.keychain_mask).as_ref(), &name).map_err(|e| e.kind())
+	}
+
+	fn create_payment_request(
+		&self,
+		token: Token,
+		args: PaymentRequestArgs,
+	) -> Result<PaymentRequest, ErrorKind> {
+		Owner::create_payment_request(self, (&token.keychain_mask).as_ref(), &args)
+			.map_err(|e| e.kind())
+	}
+
+	fn payment_requests(&self, token: Token) -> Result<Vec<PaymentRequest>, ErrorKind> {
+		Owner::payment_requests(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn cancel_payment_request(&self, token: Token, id: Uuid) -> Result<PaymentRequest, ErrorKind> {
+		Owner::cancel_payment_request(self, (&token.keychain_mask).as_ref(), &id)
+			.map_err(|e| e.kind())
+	}
+
+	fn payment_request_uri(&self, token: Token, id: Uuid) -> Result<String, ErrorKind> {
+		Owner::payment_request_uri(self, (&token.keychain_mask).as_ref(), &id).map_err(|e| e.kind())
+	}
+
+	fn decode_payment_request(&self, uri: String) -> Result<PaymentRequest, ErrorKind> {
+		Owner::decode_payment_request(self, &uri).map_err(|e| e.kind())
+	}
+
+	fn multisig_create(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+		num_participants: usize,
+		threshold: usize,
+		contributions: Option<Vec<u64>>,
+	) -> Result<MultisigAccount, ErrorKind> {
+		Owner::multisig_create(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&args,
+			num_participants,
+			threshold,
+			contributions,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn multisig_process(
+		&self,
+		token: Token,
+		account: MultisigAccount,
+		args: Option<InitTxArgs>,
+	) -> Result<(MultisigAccount, Option<TransactionV3>), ErrorKind> {
+		let (account, tx) = Owner::multisig_process(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&account,
+			args.as_ref(),
+		)
+		.map_err(|e| e.kind())?;
+		Ok((account, tx.map(TransactionV3::from)))
+	}
+
+	fn multisig_spend(
+		&self,
+		token: Token,
+		account_id: Uuid,
+		signers: Vec<usize>,
+		amount: Option<u64>,
+		address: Option<String>,
+	) -> Result<MultisigAccount, ErrorKind> {
+		Owner::multisig_spend(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&account_id,
+			&signers,
+			amount,
+			address,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn multisig_spend_slate(
+		&self,
+		token: Token,
+		account_id: Uuid,
+	) -> Result<VersionedSlate, ErrorKind> {
+		let slate = Owner::multisig_spend_slate(self, (&token.keychain_mask).as_ref(), &account_id)
+			.map_err(|e| e.kind())?;
+		let version = slate.lowest_version();
+		VersionedSlate::into_version_plain(slate, version)
+			.map_err(|e| ErrorKind::SlatepackEncodeError(format!("{}", e)))
+	}
+
+	fn multisig_spend_response(
+		&self,
+		token: Token,
+		in_slate: VersionedSlate,
+	) -> Result<(MultisigAccount, Option<TransactionV3>), ErrorKind> {
+		let (slate_from, _content, _sender) =
+			Owner::decrypt_versioned_slate(self, (&token.keychain_mask).as_ref(), in_slate)
+				.map_err(|e| ErrorKind::SlatepackDecodeError(format!("{}", e)))?;
+		let (account, tx) =
+			Owner::multisig_spend_response(self, (&token.keychain_mask).as_ref(), &slate_from)
+				.map_err(|e| e.kind())?;
+		Ok((account, tx.map(TransactionV3::from)))
+	}
+
+	fn multisig_accounts(&self, token: Token) -> Result<Vec<MultisigAccount>, ErrorKind> {
+		Owner::multisig_accounts(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn multisig_account(
+		&self,
+		token: Token,
+		account_id: Uuid,
+	) -> Result<MultisigAccount, ErrorKind> {
+		Owner::multisig_account(self, (&token.keychain_mask).as_ref(), &account_id)
+			.map_err(|e| e.kind())
+	}
+
 	fn tx_lock_outputs(
 		&self,
 		token: Token,
@@ -3683,6 +5717,17 @@ where
 		.map_err(|e| e.kind())
 	}
 
+	fn create_view_wallet(
+		&self,
+		name: Option<String>,
+		view_key: String,
+		password: String,
+	) -> Result<(), ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::create_view_wallet(self, n, &view_key, ZeroingString::from(password), None)
+			.map_err(|e| e.kind())
+	}
+
 	fn open_wallet(&self, name: Option<String>, password: String) -> Result<Token, ErrorKind> {
 		let n = name.as_ref().map(|s| s.as_str());
 		let sec_key = Owner::open_wallet(self, n, ZeroingString::from(password), true, None)
@@ -3697,6 +5742,10 @@ where
 		Owner::close_wallet(self, n).map_err(|e| e.kind())
 	}
 
+	fn list_wallets(&self) -> Result<Vec<String>, ErrorKind> {
+		Owner::list_wallets(self).map_err(|e| e.kind())
+	}
+
 	fn get_mnemonic(&self, name: Option<String>, password: String) -> Result<String, ErrorKind> {
 		let n = name.as_ref().map(|s| s.as_str());
 		let res = Owner::get_mnemonic(self, n, ZeroingString::from(password), None)
@@ -3704,6 +5753,28 @@ where
 		Ok((&*res).to_string())
 	}
 
+	fn backup_wallet(
+		&self,
+		name: Option<String>,
+		password: String,
+		backup_file: String,
+	) -> Result<(), ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::backup_wallet(self, n, ZeroingString::from(password), &backup_file, None)
+			.map_err(|e| e.kind())
+	}
+
+	fn restore_wallet(
+		&self,
+		name: Option<String>,
+		password: String,
+		backup_file: String,
+	) -> Result<(), ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::restore_wallet(self, n, ZeroingString::from(password), &backup_file, None)
+			.map_err(|e| e.kind())
+	}
+
 	fn change_password(
 		&self,
 		name: Option<String>,
@@ -3751,6 +5822,10 @@ where
 		Ok(public_proof_address)
 	}
 
+	fn get_view_key(&self, token: Token) -> Result<String, ErrorKind> {
+		Owner::get_view_key(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
 	fn get_wallet_public_address(&self, token: Token) -> Result<ProvableAddress, ErrorKind> {
 		let address = Owner::get_wallet_public_address(self, (&token.keychain_mask).as_ref())
 			.map_err(|e| e.kind())?;
@@ -3858,6 +5933,138 @@ where
 			content,
 		})
 	}
+
+	fn swap_start(&self, token: Token, params: SwapStartArgs) -> Result<String, ErrorKind> {
+		Owner::swap_start(self, (&token.keychain_mask).as_ref(), &params).map_err(|e| e.kind())
+	}
+
+	fn swap_create_from_offer(&self, token: Token, message: String) -> Result<String, ErrorKind> {
+		Owner::swap_create_from_offer_message(self, (&token.keychain_mask).as_ref(), message)
+			.map_err(|e| e.kind())
+	}
+
+	fn swap_list(&self, token: Token, do_check: bool) -> Result<Vec<SwapListInfo>, ErrorKind> {
+		Owner::swap_list(self, (&token.keychain_mask).as_ref(), do_check).map_err(|e| e.kind())
+	}
+
+	fn swap_get(&self, token: Token, swap_id: String) -> Result<Swap, ErrorKind> {
+		Owner::swap_get(self, (&token.keychain_mask).as_ref(), swap_id).map_err(|e| e.kind())
+	}
+
+	fn swap_delete(&self, token: Token, swap_id: String) -> Result<(), ErrorKind> {
+		Owner::swap_delete(self, (&token.keychain_mask).as_ref(), swap_id).map_err(|e| e.kind())
+	}
+
+	fn swap_dump(&self, token: Token, swap_id: String) -> Result<String, ErrorKind> {
+		Owner::swap_dump(self, (&token.keychain_mask).as_ref(), swap_id).map_err(|e| e.kind())
+	}
+
+	fn swap_adjust(
+		&self,
+		token: Token,
+		swap_id: String,
+		adjust_cmd: String,
+		method: Option<String>,
+		destination: Option<String>,
+		secondary_address: Option<String>,
+		secondary_fee: Option<f32>,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<(StateId, Action), ErrorKind> {
+		Owner::swap_adjust(
+			self,
+			(&token.keychain_mask).as_ref(),
+			swap_id,
+			adjust_cmd,
+			method,
+			destination,
+			secondary_address,
+			secondary_fee,
+			electrum_node_uri1,
+			electrum_node_uri2,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn update_swap_status_action(
+		&self,
+		token: Token,
+		swap_id: String,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<
+		(
+			StateId,
+			Action,
+			Option<i64>,
+			Vec<StateEtaInfo>,
+			Vec<SwapJournalRecord>,
+			Option<String>,
+		),
+		ErrorKind,
+	> {
+		Owner::update_swap_status_action(
+			self,
+			(&token.keychain_mask).as_ref(),
+			swap_id,
+			electrum_node_uri1,
+			electrum_node_uri2,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn get_swap_tx_tstatus(
+		&self,
+		token: Token,
+		swap_id: String,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<SwapTransactionsConfirmations, ErrorKind> {
+		Owner::get_swap_tx_tstatus(
+			self,
+			(&token.keychain_mask).as_ref(),
+			swap_id,
+			electrum_node_uri1,
+			electrum_node_uri2,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn swap_process(
+		&self,
+		token: Token,
+		swap_id: String,
+		apisecret: Option<String>,
+		message: Option<String>,
+		buyer_refund_address: Option<String>,
+		secondary_fee: Option<f32>,
+		secondary_address: Option<String>,
+		electrum_node_uri1: Option<String>,
+		electrum_node_uri2: Option<String>,
+	) -> Result<(StateProcessRespond, Option<String>), ErrorKind> {
+		Owner::swap_process_with_transport(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&swap_id,
+			apisecret,
+			message,
+			buyer_refund_address,
+			secondary_fee,
+			secondary_address,
+			electrum_node_uri1,
+			electrum_node_uri2,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn swap_income_message(
+		&self,
+		token: Token,
+		message: String,
+	) -> Result<Option<Message>, ErrorKind> {
+		Owner::swap_income_message(self, (&token.keychain_mask).as_ref(), message)
+			.map_err(|e| e.kind())
+	}
 }
 
 // Keeping as a placeholder for doc tests
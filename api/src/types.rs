@@ -14,10 +14,11 @@
 use crate::core::libtx::secp_ser;
 use crate::keychain::Identifier;
 use crate::libwallet::dalek_ser;
-use crate::libwallet::{Error, ErrorKind};
 use crate::libwallet::{
-	ParticipantMessages, StoredProofInfo, TxLogEntry, TxLogEntryType, VersionedSlate,
+	BatchPayoutEntry, MultisigTxInfo, ParticipantMessages, StoredProofInfo, TxLogEntry,
+	TxLogEntryType, VersionedSlate,
 };
+use crate::libwallet::{Error, ErrorKind};
 use crate::util::secp::key::{PublicKey, SecretKey};
 use crate::util::secp::pedersen;
 use crate::util::{from_hex, to_hex};
@@ -410,6 +411,12 @@ pub struct TxLogEntryAPI {
 	/// Output commits as Strings, defined for send & recieve
 	#[serde(default)]
 	pub output_commits: Vec<String>,
+	/// Recipients of the batch payout, defined for the batch send only
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub batch_payouts: Option<Vec<BatchPayoutEntry>>,
+	/// Multisig account that is funded or spent by this transaction
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub multisig: Option<MultisigTxInfo>,
 }
 
 impl TxLogEntryAPI {
@@ -439,6 +446,8 @@ impl TxLogEntryAPI {
 			payment_proof: tle.payment_proof.clone(),
 			input_commits: tle.input_commits.iter().map(|c| to_hex(&c.0)).collect(),
 			output_commits: tle.output_commits.iter().map(|c| to_hex(&c.0)).collect(),
+			batch_payouts: tle.batch_payouts.clone(),
+			multisig: tle.multisig.clone(),
 		}
 	}
 
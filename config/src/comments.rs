@@ -124,6 +124,44 @@ fn comments() -> HashMap<String, String> {
 	retval.insert(
 		"[wallet.swap_electrumx_addr]".to_string(),
 		"
+#Wallet events (tx received, tx confirmed, tx cancelled, TTL expired, swap state changed,
+#scan finished) are reported by the updater process. Events can be posted to a webhook,
+#requests are signed with HMAC-SHA256 in the 'X-MWC-Signature' header if the secret is set.
+#event_webhook_url = \"http://127.0.0.1:8080/wallet_events\"
+#event_webhook_secret = \"\"
+#Delivery retries per wallet update. Undelivered events are kept in the wallet DB and
+#sent again after the next update, also after the wallet restart.
+#event_webhook_retries = 5
+#Events can be appended to a file, one JSON object per line
+#event_log_file = \"wallet_events.jsonl\"
+#Number of confirmations for the TxConfirmed event
+#event_confirmations = 1
+
+#Wallet DB backend, \"lmdb\" (default) or \"sqlite\". SQLite needs the wallet built with the
+#'sqlite' feature, the existing LMDB wallet is moved with 'mwc-wallet migrate --to-sqlite'
+#db_backend = \"lmdb\"
+
+#Backup nodes, calls go to the node with the best tip and latency and fail over to the others
+#backup_node_api_http_addrs = [\"http://127.0.0.1:13413\"]
+#Number of nodes that must return the same kernel and outputs (check node and backup nodes),
+#default is 1. The nodes must be in sync, otherwise the lookups of the new outputs fail
+#node_quorum = 2
+
+#Number of threads for the UTXO scanning (scan and restore). Up to 4 of them are requesting
+#the outputs from the node, all of them are rewinding the bulletproofs
+#scan_threads = 4
+
+#Private update mode for the shared public nodes. The wallet never sends its outputs and kernels
+#to the node, the UTXO ranges and the blocks are downloaded by height and matched locally.
+#It takes more traffic and time than the default update. Payment proofs can be verified only
+#for the confirmed transactions of this wallet, atomic swap trades are not supported
+#private_update = false
+
+#Max depth of the chain reorg that is reverted by the wallet update. The confirmations above the
+#fork point are reverted and confirmed again by the scan. Deeper reorg stops the update, it
+#is more likely the node problem. Default is 1440 blocks
+#max_reorg_depth = 1440
+
 # Electrum X servers that are used for Atomic Swap operations. Each Secondary Currency need
 # its own dedicated Electrum X instance. We highly advise to use your own instance, instead of
 # using those community servers.
@@ -36,4 +36,5 @@ pub use crate::config::{
 };
 pub use crate::types::{
 	ConfigError, GlobalWalletConfig, GlobalWalletConfigMembers, MQSConfig, TorConfig, WalletConfig,
+	WalletDbBackend,
 };
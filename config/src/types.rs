@@ -42,6 +42,16 @@ pub struct WalletConfig {
 	/// The api address of a running server node against which transaction inputs
 	/// will be checked during send
 	pub check_node_api_http_addr: String,
+	/// Backup nodes. Calls fail over to them when the check node lags or is down
+	pub backup_node_api_http_addrs: Option<Vec<String>>,
+	/// Number of nodes that must return the same kernel and outputs, default is 1
+	pub node_quorum: Option<usize>,
+	/// Number of threads for the UTXO scanning, default is 4
+	pub scan_threads: Option<usize>,
+	/// Update the wallet without sending its commits to the node, default is false
+	pub private_update: Option<bool>,
+	/// Max depth of the chain reorg that the wallet update reverts, default is 1440 blocks
+	pub max_reorg_depth: Option<u64>,
 	/// Whether to include foreign API endpoints on the Owner API
 	pub owner_api_include_foreign: Option<bool>,
 	/// Whether to include the mwcmqs listener
@@ -62,6 +72,19 @@ pub struct WalletConfig {
 	pub dark_background_color_scheme: Option<bool>,
 	/// Wallet data directory. Default none is 'wallet_data'
 	pub wallet_data_dir: Option<String>,
+	/// URL where the wallet events are posted as JSON
+	pub event_webhook_url: Option<String>,
+	/// Secret for the HMAC-SHA256 signature of the webhook requests
+	pub event_webhook_secret: Option<String>,
+	/// Number of webhook delivery retries per wallet update, default is 5. Undelivered
+	/// events are kept in the wallet DB and sent again after the next update.
+	pub event_webhook_retries: Option<u32>,
+	/// File where the wallet events are appended as JSON lines
+	pub event_log_file: Option<String>,
+	/// Number of confirmations for the 'TxConfirmed' event, default is 1
+	pub event_confirmations: Option<u64>,
+	/// Wallet DB backend, default is LMDB
+	pub db_backend: Option<WalletDbBackend>,
 	/// Electrum nodes for secondary coins
 	/// Key: <coin>_[main|test]_[1|2]
 	/// Value: url
@@ -78,6 +101,11 @@ impl Default for WalletConfig {
 			api_secret_path: Some(".owner_api_secret".to_string()),
 			node_api_secret_path: Some(".api_secret".to_string()),
 			check_node_api_http_addr: "http://127.0.0.1:3413".to_string(),
+			backup_node_api_http_addrs: None,
+			node_quorum: None,
+			scan_threads: None,
+			private_update: None,
+			max_reorg_depth: None,
 			owner_api_include_foreign: Some(false),
 			owner_api_include_mqs_listener: Some(false),
 			data_file_dir: ".".to_string(),
@@ -87,6 +115,12 @@ impl Default for WalletConfig {
 			tls_certificate_key: None,
 			dark_background_color_scheme: Some(true),
 			wallet_data_dir: None,
+			event_webhook_url: None,
+			event_webhook_secret: None,
+			event_webhook_retries: None,
+			event_log_file: None,
+			event_confirmations: None,
+			db_backend: None,
 			swap_electrumx_addr: Some(
 				[
 					("btc_main_1", "btc.main1.swap.mwc.mw:18337"),
@@ -123,6 +157,22 @@ impl Default for WalletConfig {
 	}
 }
 
+/// Storage of the wallet data
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WalletDbBackend {
+	/// LMDB key-value store, the default
+	Lmdb,
+	/// SQLite DB with relational tables, needs the wallet built with the 'sqlite' feature
+	Sqlite,
+}
+
+impl Default for WalletDbBackend {
+	fn default() -> Self {
+		WalletDbBackend::Lmdb
+	}
+}
+
 impl WalletConfig {
 	/// API Listen address
 	pub fn api_listen_addr(&self) -> String {
@@ -19,26 +19,32 @@ use crate::apiwallet::Owner;
 use crate::config::{MQSConfig, TorConfig, WalletConfig, WALLET_CONFIG_FILE_NAME};
 use crate::core::{core, global};
 use crate::error::{Error, ErrorKind};
+use crate::hosted_wallets::WalletInstFactory;
 use crate::impls::{create_sender, SlateGetter as _};
 use crate::impls::{PathToSlateGetter, PathToSlatePutter, SlatePutter};
 use crate::keychain;
-use crate::libwallet::{InitTxArgs, IssueInvoiceTxArgs, NodeClient, WalletLCProvider};
+use crate::libwallet::{
+	BatchPayout, CoinSelectionStrategy, Contact, InitTxArgs, IssueInvoiceTxArgs, MultisigAccount,
+	MultisigStatus, NodeClient, PaymentRequestArgs, SchedulePaymentArgs, TxExportArgs, TxQueryArgs,
+	ViewKey, WalletLCProvider,
+};
 use crate::util::secp::key::SecretKey;
 use crate::util::{Mutex, ZeroingString};
 use crate::{controller, display};
 use chrono::Utc;
 use ed25519_dalek::{PublicKey as DalekPublicKey, SecretKey as DalekSecretKey};
-use grin_wallet_impls::adapters::{create_swap_message_sender, validate_tor_address};
+use grin_wallet_impls::adapters::{deliver_swap_message, validate_tor_address};
 use grin_wallet_impls::{Address, MWCMQSAddress, Publisher};
-use grin_wallet_libwallet::api_impl::owner_swap;
+use grin_wallet_libwallet::api_impl::{owner_contacts, owner_swap};
 use grin_wallet_libwallet::proof::proofaddress::{self, ProvableAddress};
 use grin_wallet_libwallet::proof::tx_proof::TxProof;
-use grin_wallet_libwallet::slatepack::SlatePurpose;
+use grin_wallet_libwallet::slatepack::{SlatePurpose, SlatepackArmor};
 use grin_wallet_libwallet::swap::message;
 use grin_wallet_libwallet::swap::trades;
 use grin_wallet_libwallet::swap::types::Action;
 use grin_wallet_libwallet::{Slate, TxLogEntry, WalletInst};
 use serde_json as json;
+use std::cmp;
 use std::fs::File;
 use std::io;
 use std::io::{Read, Write};
@@ -73,6 +79,8 @@ pub struct InitArgs {
 	pub password: ZeroingString,
 	pub config: WalletConfig,
 	pub recovery_phrase: Option<ZeroingString>,
+	/// Hex encoded view key, creates a watch-only wallet
+	pub view_key: Option<String>,
 	pub restore: bool,
 }
 
@@ -97,6 +105,14 @@ where
 		None,
 		None,
 	)?;
+	if let Some(view_key) = args.view_key {
+		// Watch-only wallet doesn't have a seed, so there is no recovery phrase to show
+		let view_key = ViewKey::from_hex(&view_key)?;
+		p.create_view_wallet(None, &view_key, args.password, wallet_data_dir)?;
+		println!("Watch-only wallet is created. It can't sign or spend the funds.");
+		return Ok(());
+	}
+
 	p.create_wallet(
 		None,
 		args.recovery_phrase,
@@ -133,6 +149,86 @@ where
 	Ok(())
 }
 
+/// Arguments for backup and restore commands
+pub struct BackupArgs {
+	pub password: ZeroingString,
+	pub file: String,
+}
+
+pub fn backup<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	args: BackupArgs,
+	wallet_data_dir: Option<&str>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	owner_api.backup_wallet(None, args.password, &args.file, wallet_data_dir)?;
+	println!("Wallet backup is saved at {}", args.file);
+	println!("The backup is encrypted with the current wallet password, it is needed to restore the wallet.");
+	Ok(())
+}
+
+pub fn restore<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	g_args: &GlobalArgs,
+	args: BackupArgs,
+	wallet_data_dir: Option<&str>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut w_lock = owner_api.wallet_inst.lock();
+	let p = w_lock.lc_provider()?;
+	p.create_config(
+		&g_args.chain_type,
+		WALLET_CONFIG_FILE_NAME,
+		None,
+		None,
+		None,
+		None,
+	)?;
+	p.restore_wallet(None, args.password, &args.file, wallet_data_dir)?;
+	println!("Wallet is restored from {}", args.file);
+	Ok(())
+}
+
+pub fn migrate<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	dry_run: bool,
+	to_sqlite: bool,
+	wallet_data_dir: Option<&str>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	if to_sqlite {
+		let db_file = owner_api.migrate_wallet_db_to_sqlite(None, wallet_data_dir)?;
+		println!("Wallet DB is copied into {}", db_file);
+		println!("Set db_backend = \"sqlite\" in the wallet config to use it");
+		return Ok(());
+	}
+	let migrations = owner_api.migrate_wallet_db(None, dry_run, wallet_data_dir)?;
+	if migrations.is_empty() {
+		println!("Wallet DB is up to date, nothing to migrate");
+		return Ok(());
+	}
+	match dry_run {
+		true => println!("Pending wallet DB migrations:"),
+		false => println!("Applied wallet DB migrations:"),
+	}
+	for (version, description) in migrations {
+		println!("  {}: {}", version, description);
+	}
+	Ok(())
+}
+
 /// Arguments for listen command
 pub struct ListenArgs {
 	pub method: String,
@@ -213,6 +309,7 @@ pub fn owner_api<L, C, K>(
 	tor_config: &TorConfig,
 	mqs_config: &MQSConfig,
 	g_args: &GlobalArgs,
+	wallet_factory: Option<WalletInstFactory<L, C, K>>,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + Send + Sync + 'static,
@@ -234,6 +331,10 @@ where
 		)?;
 	}
 
+	// Wallet events are reported by the updater, started with 'start_updater' call
+	let event_notifier = grin_wallet_impls::create_event_notifier(config)
+		.map_err(|e| ErrorKind::ArgumentError(format!("Invalid events configuration, {}", e)))?;
+
 	// Now Owner API
 	controller::owner_listener(
 		owner_api.wallet_inst.clone(),
@@ -243,6 +344,8 @@ where
 		g_args.tls_conf.clone(),
 		config.owner_api_include_foreign.clone(),
 		Some(tor_config.clone()),
+		wallet_factory,
+		event_notifier,
 	)
 	.map_err(|e| ErrorKind::LibWallet(format!("Unable to start Listener, {}", e)))?;
 	Ok(())
@@ -294,12 +397,84 @@ where
 	Ok(())
 }
 
+// Start the listener that receives the responses for this send method, if it isn't running yet
+fn start_send_listener<L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	method: &str,
+	api_listen_addr: String,
+	tls_conf: Option<TLSConfig>,
+	tor_config: Option<TorConfig>,
+	mqs_config: Option<MQSConfig>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	match method {
+		"mwcmqs" => {
+			if grin_wallet_impls::adapters::get_mwcmqs_brocker().is_none() {
+				//check to see if mqs_config is there, if not, return error
+				let mqs_config_unwrapped;
+				match mqs_config {
+					Some(s) => {
+						mqs_config_unwrapped = s;
+					}
+					None => {
+						return Err(ErrorKind::MQSConfig(format!("NO MQS config!")).into());
+					}
+				}
+
+				let km = keychain_mask.map(|k| k.clone());
+
+				//start the listener finalize tx
+				let _ = controller::init_start_mwcmqs_listener(
+					wallet_inst.clone(),
+					mqs_config_unwrapped,
+					Arc::new(Mutex::new(km)),
+					false,
+					//None,
+				)?;
+				thread::sleep(Duration::from_millis(2000));
+			}
+		}
+		"http" => {
+			if !controller::is_foreign_api_running() {
+				let tor_config = tor_config.clone().ok_or(ErrorKind::GenericError(
+					"Tor configuration is not defined".to_string(),
+				))?;
+				let wallet_inst2 = wallet_inst.clone();
+				let km = keychain_mask.map(|k| k.clone());
+
+				let _api_thread = thread::Builder::new()
+					.name("wallet-http-listener".to_string())
+					.spawn(move || {
+						let res = controller::foreign_listener(
+							wallet_inst2,
+							Arc::new(Mutex::new(km)),
+							&api_listen_addr,
+							tls_conf,
+							tor_config.use_tor_listener,
+						);
+						if let Err(e) = res {
+							error!("Error starting http listener: {}", e);
+						}
+					});
+				thread::sleep(Duration::from_millis(2000));
+			}
+		}
+		_ => {}
+	}
+	Ok(())
+}
+
 /// Arguments for the send command
 pub struct SendArgs {
 	pub amount: u64,
 	pub message: Option<String>,
 	pub minimum_confirmations: u64,
-	pub selection_strategy: String,
+	pub selection_strategy: CoinSelectionStrategy,
 	pub estimate_selection_strategies: bool,
 	pub method: String,
 	pub dest: String,
@@ -316,6 +491,39 @@ pub struct SendArgs {
 	pub outputs: Option<Vec<String>>, // Outputs to use. If None, all outputs can be used
 	pub slatepack_recipient: Option<ProvableAddress>, // Destination for slatepack. The address will be the same as for payment_proof_address. The role is different.
 	pub late_lock: bool,
+	pub max: bool,                   // send everything, the fee is paid from the amount
+	pub contact: bool,               // dest is an address book contact name
+	pub method_is_set: bool,         // method is specified, otherwise the contact method is used
+	pub request_payment_proof: bool, // the contact proof address is used if payment_proof_address is not set
+	pub payment_request_id: Option<Uuid>, // the paid payment request, used as the slate id
+}
+
+// Replace the contact name with the contact address. The method, apisecret and proof address
+// of the contact are used unless they are specified.
+fn apply_send_contact(args: &mut SendArgs, contact: &Contact) -> Result<(), Error> {
+	if args.method_is_set && args.method != contact.method {
+		return Err(ErrorKind::ArgumentError(format!(
+			"Contact {} is reachable with {} method, not with {}",
+			contact.name, contact.method, args.method
+		))
+		.into());
+	}
+	args.method = contact.method.clone();
+	args.dest = contact.address.clone();
+	if args.apisecret.is_none() {
+		args.apisecret = contact.apisecret.clone();
+	}
+	if args.request_payment_proof && args.payment_proof_address.is_none() {
+		let address = contact
+			.proof_address
+			.clone()
+			.ok_or(ErrorKind::ArgumentError(format!(
+				"Contact {} doesn't have a proof address, please specify proof_address",
+				contact.name
+			)))?;
+		args.payment_proof_address = Some(address);
+	}
+	Ok(())
 }
 
 pub fn send<L, C, K>(
@@ -326,7 +534,7 @@ pub fn send<L, C, K>(
 	tls_conf: Option<TLSConfig>,
 	tor_config: Option<TorConfig>,
 	mqs_config: Option<MQSConfig>,
-	args: SendArgs,
+	mut args: SendArgs,
 	dark_scheme: bool,
 ) -> Result<(), Error>
 where
@@ -334,29 +542,40 @@ where
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
+	if args.contact {
+		let contact = owner_api.get_contact(keychain_mask, &args.dest)?;
+		apply_send_contact(&mut args, &contact)?;
+	}
 	let wallet_inst = owner_api.wallet_inst.clone();
 	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
 		if args.estimate_selection_strategies {
-			let mut strategies: Vec<(&str, u64, u64)> = Vec::new();
-			for strategy in vec!["smallest", "all"] {
+			let mut strategies: Vec<(String, u64, u64)> = Vec::new();
+			for strategy in CoinSelectionStrategy::all_strategies() {
 				let init_args = InitTxArgs {
 					src_acct_name: None,
 					amount: args.amount,
 					minimum_confirmations: args.minimum_confirmations,
 					max_outputs: args.max_outputs as u32,
 					num_change_outputs: args.change_outputs as u32,
-					selection_strategy_is_use_all: strategy == "all",
+					selection_strategy_is_use_all: strategy.is_use_all(),
+					selection_strategy: Some(strategy),
 					estimate_only: Some(true),
 					exclude_change_outputs: Some(args.exclude_change_outputs),
 					minimum_confirmations_change_outputs: args.minimum_confirmations_change_outputs,
 					address: args.address.clone(),
 					outputs: args.outputs.clone(),
+					sweep: Some(args.max),
 					..Default::default()
 				};
 				let slate = api.init_send_tx(m, &init_args, 1)?;
-				strategies.push((strategy, slate.amount, slate.fee));
+				strategies.push((strategy.to_string(), slate.amount, slate.fee));
 			}
-			display::estimate(args.amount, strategies, dark_scheme);
+			// With --max everything is locked, the amount is what remains after the fee
+			let amount = match strategies.first() {
+				Some((_, total, fee)) if args.max => total - fee,
+				_ => args.amount,
+			};
+			display::estimate(amount, strategies, dark_scheme);
 		} else {
 			let mut init_args = InitTxArgs {
 				src_acct_name: None,
@@ -364,7 +583,8 @@ where
 				minimum_confirmations: args.minimum_confirmations,
 				max_outputs: args.max_outputs as u32,
 				num_change_outputs: args.change_outputs as u32,
-				selection_strategy_is_use_all: args.selection_strategy == "all",
+				selection_strategy_is_use_all: args.selection_strategy.is_use_all(),
+				selection_strategy: Some(args.selection_strategy),
 				message: args.message.clone(),
 				target_slate_version: args.target_slate_version,
 				payment_proof_recipient_address: args.payment_proof_address.clone(),
@@ -375,64 +595,21 @@ where
 				minimum_confirmations_change_outputs: args.minimum_confirmations_change_outputs,
 				outputs: args.outputs.clone(),
 				late_lock: Some(args.late_lock),
+				sweep: Some(args.max),
+				payment_request_id: args.payment_request_id,
 				..Default::default()
 			};
 
 			//if it is mwcmqs, start listner first.
-			match args.method.as_str() {
-				"mwcmqs" => {
-					if grin_wallet_impls::adapters::get_mwcmqs_brocker().is_none() {
-						//check to see if mqs_config is there, if not, return error
-						let mqs_config_unwrapped;
-						match mqs_config {
-							Some(s) => {
-								mqs_config_unwrapped = s;
-							}
-							None => {
-								return Err(ErrorKind::MQSConfig(format!("NO MQS config!")).into());
-							}
-						}
-
-						let km = keychain_mask.map(|k| k.clone());
-
-						//start the listener finalize tx
-						let _ = controller::init_start_mwcmqs_listener(
-							wallet_inst.clone(),
-							mqs_config_unwrapped,
-							Arc::new(Mutex::new(km)),
-							false,
-							//None,
-						)?;
-						thread::sleep(Duration::from_millis(2000));
-					}
-				}
-				"http" => {
-					if !controller::is_foreign_api_running() {
-						let tor_config = tor_config.clone().ok_or(ErrorKind::GenericError(
-							"Tor configuration is not defined".to_string(),
-						))?;
-						let wallet_inst2 = wallet_inst.clone();
-						let km = keychain_mask.map(|k| k.clone());
-
-						let _api_thread = thread::Builder::new()
-							.name("wallet-http-listener".to_string())
-							.spawn(move || {
-								let res = controller::foreign_listener(
-									wallet_inst2,
-									Arc::new(Mutex::new(km)),
-									&api_listen_addr,
-									tls_conf,
-									tor_config.use_tor_listener,
-								);
-								if let Err(e) = res {
-									error!("Error starting http listener: {}", e);
-								}
-							});
-						thread::sleep(Duration::from_millis(2000));
-					}
-				}
-				_ => {}
-			}
+			start_send_listener(
+				wallet_inst.clone(),
+				keychain_mask,
+				&args.method,
+				api_listen_addr,
+				tls_conf,
+				tor_config.clone(),
+				mqs_config,
+			)?;
 
 			// Creating sender because we need to request other wallet version first
 			let sender_info = match args.method.as_str() {
@@ -456,7 +633,7 @@ where
 				Ok(s) => {
 					info!(
 						"Tx created: {} mwc to {} (strategy '{}')",
-						core::amount_to_hr_string(args.amount, false),
+						core::amount_to_hr_string(s.amount, false),
 						args.dest,
 						args.selection_strategy,
 					);
@@ -812,107 +989,920 @@ where
 		content = content2;
 		slatepack_format = slatepack_format2;
 
-		Ok(())
-	})?;
+		Ok(())
+	})?;
+
+	// Note!!! grin wallet was able to detect if it is invoice by using 'different' participant Ids (issuer use 1, fouset 0)
+	//    Unfortunatelly it is breaks mwc713 backward compatibility (issuer Participant Id 0, fouset 1)
+	//    We choose backward compatibility as more impotant, that is why we need 'is_invoice' flag to compensate that.
+
+	if is_invoice {
+		if !(content == SlatePurpose::FullSlate || content == SlatePurpose::InvoiceResponse) {
+			return Err(ErrorKind::ArgumentError(format!(
+				"Wrong slate content. Expecting InvoiceResponse, get {:?}",
+				content
+			))
+			.into());
+		}
+
+		let km = match keychain_mask.as_ref() {
+			None => None,
+			Some(&m) => Some(m.to_owned()),
+		};
+		controller::foreign_single_use(owner_api.wallet_inst.clone(), km, |api| {
+			if let Err(e) = api.verify_slate_messages(&slate) {
+				error!("Error validating participant messages: {}", e);
+				return Err(ErrorKind::LibWallet(format!(
+					"Unable to validate slate messages, {}",
+					e
+				))
+				.into());
+			}
+			slate = api.finalize_invoice_tx(&mut slate)?;
+			Ok(())
+		})?;
+	} else {
+		if !(content == SlatePurpose::FullSlate || content == SlatePurpose::SendResponse) {
+			return Err(ErrorKind::ArgumentError(format!(
+				"Wrong slate content. Expecting SendResponse, get {:?}",
+				content
+			))
+			.into());
+		}
+
+		controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+			if let Err(e) = api.verify_slate_messages(m, &slate) {
+				error!("Error validating participant messages: {}", e);
+				return Err(ErrorKind::LibWallet(format!(
+					"Unable to validate slate messages, {}",
+					e
+				))
+				.into());
+			}
+			slate = api.finalize_tx(m, &mut slate)?;
+			Ok(())
+		})?;
+	}
+
+	if !args.nopost {
+		controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+			let result = api.post_tx(m, &slate.tx, args.fluff);
+			match result {
+				Ok(_) => {
+					info!(
+						"Transaction sent successfully, check the wallet again for confirmation."
+					);
+					Ok(())
+				}
+				Err(e) => {
+					error!("Tx not sent: {}", e);
+					return Err(ErrorKind::LibWallet(format!("Unable to post slate, {}", e)).into());
+				}
+			}
+		})?;
+	}
+
+	if args.dest.is_some() {
+		controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+			let slatepack_secret = {
+				let mut w_lock = api.wallet_inst.lock();
+				let w = w_lock.lc_provider()?.wallet_inst()?;
+				let keychain = w.keychain(m)?;
+				let slatepack_secret = proofaddress::payment_proof_address_secret(&keychain, None)?;
+				let slatepack_secret =
+					DalekSecretKey::from_bytes(&slatepack_secret.0).map_err(|e| {
+						ErrorKind::GenericError(format!("Unable to build secret, {}", e))
+					})?;
+				slatepack_secret
+			};
+
+			// save to a destination not as a slatepack
+			PathToSlatePutter::build_encrypted(
+				Some((&args.dest.unwrap()).into()),
+				SlatePurpose::FullSlate,
+				DalekPublicKey::from(&slatepack_secret),
+				sender,
+				slatepack_format,
+			)
+			.put_tx(&slate, &slatepack_secret, false)?;
+
+			Ok(())
+		})?;
+	}
+
+	Ok(())
+}
+
+/// Arguments for the estimate_fee command
+pub struct EstimateFeeArgs {
+	pub amount: u64, // zero for the maximum amount that can be sent
+	pub minimum_confirmations: u64,
+	pub selection_strategy: CoinSelectionStrategy,
+	pub change_outputs: usize,
+	pub max_outputs: usize,
+	pub exclude_change_outputs: bool,
+	pub minimum_confirmations_change_outputs: u64,
+	pub outputs: Option<Vec<String>>,
+}
+
+pub fn estimate_fee<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: EstimateFeeArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		// estimate_fee works with the local data, so refresh it first
+		let (validated, _) = api.retrieve_summary_info(m, true, args.minimum_confirmations)?;
+		let init_args = InitTxArgs {
+			src_acct_name: None,
+			amount: args.amount,
+			minimum_confirmations: args.minimum_confirmations,
+			max_outputs: args.max_outputs as u32,
+			num_change_outputs: args.change_outputs as u32,
+			selection_strategy_is_use_all: args.selection_strategy.is_use_all(),
+			selection_strategy: Some(args.selection_strategy),
+			exclude_change_outputs: Some(args.exclude_change_outputs),
+			minimum_confirmations_change_outputs: args.minimum_confirmations_change_outputs,
+			outputs: args.outputs.clone(),
+			..Default::default()
+		};
+		let estimate = api.estimate_fee(m, &init_args)?;
+		display::fee_estimate(&estimate, validated, dark_scheme);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Arguments for the consolidate command
+pub struct ConsolidateArgs {
+	pub num_outputs: Option<usize>,
+	pub max_inputs: usize,
+	pub minimum_confirmations: u64,
+	pub preview: bool,
+	pub fluff: bool,
+}
+
+pub fn consolidate<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: ConsolidateArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let consolidate_args = crate::libwallet::ConsolidateArgs {
+			src_acct_name: None,
+			minimum_confirmations: args.minimum_confirmations,
+			num_outputs: args.num_outputs,
+			max_inputs: args.max_inputs,
+			fluff: args.fluff,
+		};
+		let txs = api.consolidate_outputs(m, &consolidate_args, args.preview)?;
+		display::utxo_txs("Consolidation", &txs, args.preview, dark_scheme);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Arguments for the split command
+pub struct SplitArgs {
+	pub output: String,
+	pub amounts: Vec<u64>,
+	pub minimum_confirmations: u64,
+	pub preview: bool,
+	pub fluff: bool,
+}
+
+pub fn split<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: SplitArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let split_args = crate::libwallet::SplitArgs {
+			src_acct_name: None,
+			minimum_confirmations: args.minimum_confirmations,
+			output: args.output.clone(),
+			amounts: args.amounts.clone(),
+			fluff: args.fluff,
+		};
+		let tx = api.split_output(m, &split_args, args.preview)?;
+		display::utxo_txs("Split", &[tx], args.preview, dark_scheme);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Recipient of the batch payout
+pub struct BatchRecipient {
+	pub dest: String,
+	pub amount: u64,
+	pub slatepack_recipient: Option<ProvableAddress>,
+}
+
+/// Arguments for the send_batch command
+pub struct SendBatchArgs {
+	pub recipients: Vec<BatchRecipient>,
+	pub message: Option<String>,
+	pub minimum_confirmations: u64,
+	pub selection_strategy: CoinSelectionStrategy,
+	pub method: String,
+	pub apisecret: Option<String>,
+	pub change_outputs: usize,
+	pub fluff: bool,
+	pub max_outputs: usize,
+	pub target_slate_version: Option<u16>,
+	pub ttl_blocks: Option<u64>,
+}
+
+pub fn send_batch<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	api_listen_addr: String,
+	tls_conf: Option<TLSConfig>,
+	tor_config: Option<TorConfig>,
+	mqs_config: Option<MQSConfig>,
+	args: SendBatchArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let wallet_inst = owner_api.wallet_inst.clone();
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		start_send_listener(
+			wallet_inst,
+			keychain_mask,
+			&args.method,
+			api_listen_addr,
+			tls_conf,
+			tor_config.clone(),
+			mqs_config,
+		)?;
+
+		// Every recipient must be able to read its slate, the lowest version is used for all of them
+		let mut target_slate_version = args.target_slate_version;
+		let mut senders = vec![];
+		if args.method == "http" || args.method == "mwcmqs" {
+			for r in &args.recipients {
+				let sender =
+					create_sender(&args.method, &r.dest, &args.apisecret, tor_config.clone())?;
+				let other_wallet_version = sender.check_other_wallet_version(&r.dest)?;
+				if args.target_slate_version.is_none() {
+					if let Some((version, _)) = &other_wallet_version {
+						let version = version.to_numeric_version() as u16;
+						target_slate_version =
+							Some(target_slate_version.map_or(version, |v| cmp::min(v, version)));
+					}
+				}
+				senders.push((sender, other_wallet_version));
+			}
+		}
+
+		let init_args = InitTxArgs {
+			src_acct_name: None,
+			minimum_confirmations: args.minimum_confirmations,
+			max_outputs: args.max_outputs as u32,
+			num_change_outputs: args.change_outputs as u32,
+			selection_strategy_is_use_all: args.selection_strategy.is_use_all(),
+			selection_strategy: Some(args.selection_strategy),
+			message: args.message.clone(),
+			target_slate_version,
+			ttl_blocks: args.ttl_blocks,
+			..Default::default()
+		};
+		let payouts: Vec<BatchPayout> = args
+			.recipients
+			.iter()
+			.map(|r| BatchPayout {
+				amount: r.amount,
+				address: Some(r.dest.clone()),
+			})
+			.collect();
+
+		let slates = api
+			.init_batch_send_tx(m, &init_args, &payouts)
+			.map_err(|e| {
+				info!("Batch tx not created: {}", e);
+				ErrorKind::LibWallet(format!("Unable to create batch send slates, {}", e))
+			})?;
+		info!(
+			"Batch tx created: {} mwc to {} recipients (strategy '{}')",
+			core::amount_to_hr_string(payouts.iter().map(|p| p.amount).sum(), false),
+			payouts.len(),
+			args.selection_strategy,
+		);
+
+		let (slatepack_secret, slatepack_sender) = {
+			let mut w_lock = api.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let keychain = w.keychain(keychain_mask)?;
+			let slatepack_secret =
+				proofaddress::payment_proof_address_dalek_secret(&keychain, None)?;
+			let slate_pub_key = DalekPublicKey::from(&slatepack_secret);
+			(slatepack_secret, slate_pub_key)
+		};
+
+		let mut recipients = vec![];
+		for r in &args.recipients {
+			recipients.push(match &r.slatepack_recipient {
+				Some(sp_address) => Some(sp_address.tor_public_key()?),
+				None => None,
+			});
+		}
+
+		if args.method == "file" || args.method == "slatepack" {
+			for ((slate, r), recipient) in slates.iter().zip(&args.recipients).zip(recipients) {
+				PathToSlatePutter::build_encrypted(
+					Some((&r.dest).into()),
+					SlatePurpose::SendInitial,
+					slatepack_sender,
+					recipient,
+					args.method == "slatepack",
+				)
+				.put_tx(slate, &slatepack_secret, false)
+				.map_err(|e| {
+					ErrorKind::IO(format!("Unable to store the file at {}, {}", r.dest, e))
+				})?;
+				println!(
+					"Slate [{}] for {} is saved at {}",
+					slate.id,
+					core::amount_to_hr_string(slate.amount, false),
+					r.dest
+				);
+			}
+			println!("Please finalize the batch with 'finalize_batch' when all recipients respond");
+			return Ok(());
+		}
+
+		let mut responses = vec![];
+		for ((slate, (sender, wallet_info)), recipient) in
+			slates.iter().zip(senders).zip(recipients)
+		{
+			let response: Result<Slate, Error> = (|| {
+				let mut response = sender.send_tx(
+					slate,
+					SlatePurpose::SendInitial,
+					&slatepack_secret,
+					recipient,
+					wallet_info,
+				)?;
+				// Restore back ttl, because it can be gone
+				response.ttl_cutoff_height = slate.ttl_cutoff_height.clone();
+				// Checking is sender didn't do any harm to slate
+				Slate::compare_slates_send(slate, &response)?;
+				api.verify_slate_messages(m, &response)?;
+				Ok(response)
+			})();
+			match response {
+				Ok(response) => responses.push(response),
+				Err(e) => {
+					error!("Batch payout to {} failed: {}", slate.id, e);
+					// Whole batch is one transaction, without every recipient it can't be finished
+					api.cancel_tx(m, None, Some(slates[0].id))?;
+					return Err(ErrorKind::LibWallet(format!(
+						"Unable to send batch slate {}, the batch is cancelled, {}",
+						slate.id, e
+					))
+					.into());
+				}
+			}
+		}
+
+		let tx = api.finalize_batch_tx(m, &responses)?;
+
+		match api.post_tx(m, &tx, args.fluff) {
+			Ok(_) => {
+				info!(
+					"batch [{}] finalized successfully",
+					slates[0].id.to_string()
+				);
+				println!(
+					"batch [{}] finalized successfully",
+					slates[0].id.to_string()
+				);
+				Ok(())
+			}
+			Err(e) => {
+				error!("Tx sent fail: {}", e);
+				Err(ErrorKind::LibWallet(format!("Unable to post batch transaction, {}", e)).into())
+			}
+		}
+	})?;
+	Ok(())
+}
+
+/// Finalize batch command args
+pub struct FinalizeBatchArgs {
+	pub input_files: Vec<String>,
+	pub fluff: bool,
+	pub nopost: bool,
+}
+
+pub fn finalize_batch<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: FinalizeBatchArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let slatepack_secret = {
+			let mut w_lock = api.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let keychain = w.keychain(m)?;
+			proofaddress::payment_proof_address_dalek_secret(&keychain, None)?
+		};
+
+		let mut slates = vec![];
+		for file_name in &args.input_files {
+			let (slate, _sender, _recipient, content, _slatepack_format) =
+				PathToSlateGetter::build_form_path(file_name.into())
+					.get_tx(&slatepack_secret)?
+					.to_slate()?;
+			if !(content == SlatePurpose::FullSlate || content == SlatePurpose::SendResponse) {
+				return Err(ErrorKind::ArgumentError(format!(
+					"Wrong slate content at {}. Expecting SendResponse, get {:?}",
+					file_name, content
+				))
+				.into());
+			}
+			if let Err(e) = api.verify_slate_messages(m, &slate) {
+				error!("Error validating participant messages: {}", e);
+				return Err(ErrorKind::LibWallet(format!(
+					"Unable to validate slate messages, {}",
+					e
+				))
+				.into());
+			}
+			slates.push(slate);
+		}
+
+		let tx = api.finalize_batch_tx(m, &slates)?;
+
+		if !args.nopost {
+			if let Err(e) = api.post_tx(m, &tx, args.fluff) {
+				error!("Tx not sent: {}", e);
+				return Err(ErrorKind::LibWallet(format!("Unable to post slate, {}", e)).into());
+			}
+			info!("Transaction sent successfully, check the wallet again for confirmation.");
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+// Multisig account operation
+#[derive(PartialEq)]
+pub enum MultisigSubcommand {
+	Create,
+	Process,
+	Spend,
+	Response,
+	List,
+	Show,
+}
+
+/// Arguments for the multisig command
+pub struct MultisigArgs {
+	/// What we want to do with a multisig account
+	pub subcommand: MultisigSubcommand,
+	/// Multisig account that we are working with
+	pub account_id: Option<Uuid>,
+	/// File with the account that was received from other co-signer, or with the
+	/// recipient response
+	pub input_file: Option<String>,
+	/// Armored account that was received from other co-signer, or the recipient response
+	pub input_slatepack_message: Option<String>,
+	/// File to write the account for the next co-signer
+	pub output_file: Option<String>,
+	/// Number of co-signers, for create only
+	pub num_participants: usize,
+	/// Number of co-signers that are needed to spend, for create only
+	pub threshold: usize,
+	/// Shared output amount for create, amount for the recipient for spend
+	pub amount: Option<u64>,
+	/// Amount that every co-signer pays, for create only
+	pub contributions: Option<Vec<u64>>,
+	/// Recipient of the spending, for spend only
+	pub dest: Option<String>,
+	pub minimum_confirmations: u64,
+	pub selection_strategy: CoinSelectionStrategy,
+	/// Co-signers that sign the spending transaction, for spend only
+	pub signers: Vec<usize>,
+	pub fluff: bool,
+}
+
+pub fn multisig<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: MultisigArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let slatepack_secret = {
+			let mut w_lock = api.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let keychain = w.keychain(m)?;
+			proofaddress::payment_proof_address_dalek_secret(&keychain, None)?
+		};
+		let init_args = InitTxArgs {
+			src_acct_name: None,
+			amount: args.amount.unwrap_or(0),
+			minimum_confirmations: args.minimum_confirmations,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: args.selection_strategy.is_use_all(),
+			selection_strategy: Some(args.selection_strategy),
+			..Default::default()
+		};
+		let (account, tx) = match args.subcommand {
+			MultisigSubcommand::List => {
+				let accounts = api.multisig_accounts(m)?;
+				display::multisig_accounts(&accounts, dark_scheme);
+				return Ok(());
+			}
+			MultisigSubcommand::Show => {
+				let account_id = args.account_id.ok_or(ErrorKind::ArgumentError(
+					"Please define multisig account id".to_string(),
+				))?;
+				(api.multisig_account(m, &account_id)?, None)
+			}
+			MultisigSubcommand::Create => {
+				let account = api
+					.multisig_create(
+						m,
+						&init_args,
+						args.num_participants,
+						args.threshold,
+						args.contributions.clone(),
+					)
+					.map_err(|e| {
+						ErrorKind::LibWallet(format!("Unable to create multisig account, {}", e))
+					})?;
+				info!(
+					"Multisig account {} is created, {}-of-{} for {} MWC",
+					account.id,
+					account.threshold,
+					account.num_participants,
+					core::amount_to_hr_string(account.amount, false),
+				);
+				(account, None)
+			}
+			MultisigSubcommand::Process => {
+				let content = match &args.input_file {
+					Some(file_name) => {
+						let mut content = String::new();
+						File::open(file_name)
+							.and_then(|mut f| f.read_to_string(&mut content))
+							.map_err(|e| {
+								ErrorKind::IO(format!("Unable to read file {}, {}", file_name, e))
+							})?;
+						content
+					}
+					None => {
+						args.input_slatepack_message
+							.clone()
+							.ok_or(ErrorKind::ArgumentError(
+								"Please specify 'input' or 'content' argument".to_string(),
+							))?
+					}
+				};
+				let account = multisig_account_from_str(&content)?;
+				api.multisig_process(m, &account, Some(&init_args))
+					.map_err(|e| {
+						ErrorKind::LibWallet(format!("Unable to process multisig account, {}", e))
+					})?
+			}
+			MultisigSubcommand::Spend => {
+				let account_id = args.account_id.ok_or(ErrorKind::ArgumentError(
+					"Please define multisig account id".to_string(),
+				))?;
+				let account = api
+					.multisig_spend(
+						m,
+						&account_id,
+						&args.signers,
+						args.amount,
+						args.dest.clone(),
+					)
+					.map_err(|e| {
+						ErrorKind::LibWallet(format!("Unable to spend multisig account, {}", e))
+					})?;
+				(account, None)
+			}
+			MultisigSubcommand::Response => {
+				let slate_pkg = match &args.input_file {
+					Some(file_name) => PathToSlateGetter::build_form_path(file_name.into())
+						.get_tx(&slatepack_secret)?,
+					None => match &args.input_slatepack_message {
+						Some(message) => PathToSlateGetter::build_form_str(message.clone())
+							.get_tx(&slatepack_secret)?,
+						None => {
+							return Err(ErrorKind::ArgumentError(
+								"Please specify 'input' or 'content' argument".to_string(),
+							)
+							.into())
+						}
+					},
+				};
+				let (slate, _sender, _recipient, _content, _slatepack_format) =
+					slate_pkg.to_slate()?;
+				api.multisig_spend_response(m, &slate).map_err(|e| {
+					ErrorKind::LibWallet(format!("Unable to apply the recipient response, {}", e))
+				})?
+			}
+		};
+
+		if let Some(tx) = tx {
+			if let Err(e) = api.post_tx(m, &tx, args.fluff) {
+				error!("Tx not sent: {}", e);
+				return Err(ErrorKind::LibWallet(format!("Unable to post slate, {}", e)).into());
+			}
+			info!("Transaction sent successfully, check the wallet again for confirmation.");
+		}
+
+		display::multisig_account(&account, dark_scheme);
+
+		match &args.output_file {
+			Some(file_name) => {
+				let mut file = File::create(file_name).map_err(|e| {
+					ErrorKind::GenericError(format!("Unable to create file {}, {}", file_name, e))
+				})?;
+				let content = json::to_string_pretty(&account.export()).map_err(|e| {
+					ErrorKind::Format(format!("Unable to serialize multisig account, {}", e))
+				})?;
+				file.write_all(content.as_bytes()).map_err(|e| {
+					ErrorKind::GenericError(format!("Unable to write file {}, {}", file_name, e))
+				})?;
+				info!(
+					"Multisig account is saved to {}, please pass it to the next co-signer",
+					file_name
+				);
+			}
+			None => {
+				if args.subcommand != MultisigSubcommand::Show {
+					println!("Multisig account: {}", multisig_account_to_str(&account)?);
+				}
+			}
+		}
+
+		// Every signer is done with the first round, the slate can go to the recipient
+		if account.status == MultisigStatus::Spending {
+			if let Ok(slate) = api.multisig_spend_slate(m, &account.id) {
+				let slate_file = args.output_file.as_ref().map(|f| format!("{}.slate", f));
+				let slate_str = PathToSlatePutter::build_plain(
+					slate_file.clone().map(|f| f.into()),
+				)
+				.put_tx(&slate, &slatepack_secret, false)?;
+				match slate_file {
+					Some(f) => info!(
+						"Slate for the recipient is saved to {}, please pass the recipient response to 'multisig --response'",
+						f
+					),
+					None => println!("Slate for the recipient: {}", slate_str),
+				}
+			}
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Armored multisig account. Co-signers can pass it as text, the same way as slatepacks.
+fn multisig_account_to_str(account: &MultisigAccount) -> Result<String, Error> {
+	let data = json::to_vec(&account.export())
+		.map_err(|e| ErrorKind::Format(format!("Unable to serialize multisig account, {}", e)))?;
+	Ok(SlatepackArmor::encode(&data, false)?)
+}
+
+/// Multisig account from the armored text or json
+fn multisig_account_from_str(content: &str) -> Result<MultisigAccount, Error> {
+	let data = if content.trim_start().starts_with('{') {
+		content.as_bytes().to_vec()
+	} else {
+		SlatepackArmor::decode(content.as_bytes())?.0
+	};
+	let account = json::from_slice(&data)
+		.map_err(|e| ErrorKind::Format(format!("Unable to parse multisig account, {}", e)))?;
+	Ok(account)
+}
+
+// Scheduled payment operation
+#[derive(PartialEq)]
+pub enum ScheduleSubcommand {
+	Add,
+	List,
+	Pause,
+	Resume,
+	Delete,
+}
+
+/// Arguments for the schedule command
+pub struct ScheduleArgs {
+	/// What we want to do with a scheduled payment
+	pub subcommand: ScheduleSubcommand,
+	/// Payment that we are working with, for pause, resume and delete
+	pub id: Option<Uuid>,
+	/// New payment, for add only
+	pub payment: Option<SchedulePaymentArgs>,
+}
+
+pub fn schedule<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: ScheduleArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let id = || {
+			args.id.ok_or(ErrorKind::ArgumentError(
+				"Please define scheduled payment id".to_string(),
+			))
+		};
+		match args.subcommand {
+			ScheduleSubcommand::Add => {
+				let payment_args = args.payment.as_ref().ok_or(ErrorKind::ArgumentError(
+					"Please define the payment".to_string(),
+				))?;
+				let payment = api.schedule_payment(m, payment_args).map_err(|e| {
+					ErrorKind::LibWallet(format!("Unable to schedule the payment, {}", e))
+				})?;
+				info!(
+					"Payment {} of {} MWC to {} is scheduled at {}",
+					payment.id,
+					core::amount_to_hr_string(payment.amount, false),
+					payment.dest,
+					payment.next_run,
+				);
+				display::scheduled_payments(&vec![payment], dark_scheme);
+			}
+			ScheduleSubcommand::List => {
+				let payments = api.scheduled_payments(m)?;
+				display::scheduled_payments(&payments, dark_scheme);
+			}
+			ScheduleSubcommand::Pause | ScheduleSubcommand::Resume => {
+				let active = args.subcommand == ScheduleSubcommand::Resume;
+				let payment = api.set_scheduled_payment_active(m, &id()?, active)?;
+				display::scheduled_payments(&vec![payment], dark_scheme);
+			}
+			ScheduleSubcommand::Delete => {
+				let id = id()?;
+				api.delete_scheduled_payment(m, &id)?;
+				info!("Scheduled payment {} is deleted", id);
+			}
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+// Address book operation
+#[derive(PartialEq)]
+pub enum ContactsSubcommand {
+	Add,
+	List,
+	Delete,
+}
+
+/// Arguments for the contacts command
+pub struct ContactsArgs {
+	/// What we want to do with the address book
+	pub subcommand: ContactsSubcommand,
+	/// Contact to add, or the name of the contact to delete
+	pub contact: Contact,
+}
+
+pub fn contacts<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: ContactsArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		match args.subcommand {
+			ContactsSubcommand::Add => {
+				let contact = api.save_contact(m, &args.contact).map_err(|e| {
+					ErrorKind::LibWallet(format!("Unable to save the contact, {}", e))
+				})?;
+				info!("Contact {} is saved", contact.name);
+				display::contacts(&vec![contact], dark_scheme);
+			}
+			ContactsSubcommand::List => {
+				let contacts = api.contacts(m)?;
+				display::contacts(&contacts, dark_scheme);
+			}
+			ContactsSubcommand::Delete => {
+				api.delete_contact(m, &args.contact.name)?;
+				info!("Contact {} is deleted", args.contact.name);
+			}
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+// Payment request operation
+#[derive(PartialEq)]
+pub enum PaymentRequestSubcommand {
+	Create,
+	List,
+	Cancel,
+	Uri,
+}
 
-	// Note!!! grin wallet was able to detect if it is invoice by using 'different' participant Ids (issuer use 1, fouset 0)
-	//    Unfortunatelly it is breaks mwc713 backward compatibility (issuer Participant Id 0, fouset 1)
-	//    We choose backward compatibility as more impotant, that is why we need 'is_invoice' flag to compensate that.
+/// Arguments for the payment_request command
+pub struct PaymentRequestCmdArgs {
+	/// What we want to do with a payment request
+	pub subcommand: PaymentRequestSubcommand,
+	/// Request that we are working with, for cancel and uri
+	pub id: Option<Uuid>,
+	/// New request, for create only
+	pub request: Option<PaymentRequestArgs>,
+}
 
-	if is_invoice {
-		if !(content == SlatePurpose::FullSlate || content == SlatePurpose::InvoiceResponse) {
-			return Err(ErrorKind::ArgumentError(format!(
-				"Wrong slate content. Expecting InvoiceResponse, get {:?}",
-				content
+pub fn payment_request<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: PaymentRequestCmdArgs,
+	dark_scheme: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let id = || {
+			args.id.ok_or(ErrorKind::ArgumentError(
+				"Please define payment request id".to_string(),
 			))
-			.into());
-		}
-
-		let km = match keychain_mask.as_ref() {
-			None => None,
-			Some(&m) => Some(m.to_owned()),
 		};
-		controller::foreign_single_use(owner_api.wallet_inst.clone(), km, |api| {
-			if let Err(e) = api.verify_slate_messages(&slate) {
-				error!("Error validating participant messages: {}", e);
-				return Err(ErrorKind::LibWallet(format!(
-					"Unable to validate slate messages, {}",
-					e
-				))
-				.into());
+		match args.subcommand {
+			PaymentRequestSubcommand::Create => {
+				let request_args = args.request.as_ref().ok_or(ErrorKind::ArgumentError(
+					"Please define the payment request".to_string(),
+				))?;
+				let request = api.create_payment_request(m, request_args).map_err(|e| {
+					ErrorKind::LibWallet(format!("Unable to create the payment request, {}", e))
+				})?;
+				info!(
+					"Payment request {} for {} MWC is created",
+					request.id,
+					core::amount_to_hr_string(request.amount, false),
+				);
+				display::payment_requests(&vec![request.clone()], dark_scheme);
+				println!("URI: {}", api.payment_request_uri(m, &request.id)?);
 			}
-			slate = api.finalize_invoice_tx(&mut slate)?;
-			Ok(())
-		})?;
-	} else {
-		if !(content == SlatePurpose::FullSlate || content == SlatePurpose::SendResponse) {
-			return Err(ErrorKind::ArgumentError(format!(
-				"Wrong slate content. Expecting SendResponse, get {:?}",
-				content
-			))
-			.into());
-		}
-
-		controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
-			if let Err(e) = api.verify_slate_messages(m, &slate) {
-				error!("Error validating participant messages: {}", e);
-				return Err(ErrorKind::LibWallet(format!(
-					"Unable to validate slate messages, {}",
-					e
-				))
-				.into());
+			PaymentRequestSubcommand::List => {
+				let requests = api.payment_requests(m)?;
+				display::payment_requests(&requests, dark_scheme);
 			}
-			slate = api.finalize_tx(m, &mut slate)?;
-			Ok(())
-		})?;
-	}
-
-	if !args.nopost {
-		controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
-			let result = api.post_tx(m, &slate.tx, args.fluff);
-			match result {
-				Ok(_) => {
-					info!(
-						"Transaction sent successfully, check the wallet again for confirmation."
-					);
-					Ok(())
-				}
-				Err(e) => {
-					error!("Tx not sent: {}", e);
-					return Err(ErrorKind::LibWallet(format!("Unable to post slate, {}", e)).into());
-				}
+			PaymentRequestSubcommand::Cancel => {
+				let request = api.cancel_payment_request(m, &id()?)?;
+				display::payment_requests(&vec![request], dark_scheme);
 			}
-		})?;
-	}
-
-	if args.dest.is_some() {
-		controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
-			let slatepack_secret = {
-				let mut w_lock = api.wallet_inst.lock();
-				let w = w_lock.lc_provider()?.wallet_inst()?;
-				let keychain = w.keychain(m)?;
-				let slatepack_secret = proofaddress::payment_proof_address_secret(&keychain, None)?;
-				let slatepack_secret =
-					DalekSecretKey::from_bytes(&slatepack_secret.0).map_err(|e| {
-						ErrorKind::GenericError(format!("Unable to build secret, {}", e))
-					})?;
-				slatepack_secret
-			};
-
-			// save to a destination not as a slatepack
-			PathToSlatePutter::build_encrypted(
-				Some((&args.dest.unwrap()).into()),
-				SlatePurpose::FullSlate,
-				DalekPublicKey::from(&slatepack_secret),
-				sender,
-				slatepack_format,
-			)
-			.put_tx(&slate, &slatepack_secret, false)?;
-
-			Ok(())
-		})?;
-	}
-
+			PaymentRequestSubcommand::Uri => {
+				println!("URI: {}", api.payment_request_uri(m, &id()?)?);
+			}
+		}
+		Ok(())
+	})?;
 	Ok(())
 }
 
@@ -922,18 +1912,41 @@ pub struct IssueInvoiceArgs {
 	pub dest: String,
 	/// issue invoice tx args
 	pub issue_args: IssueInvoiceTxArgs,
+	/// address book contact that is the slatepack recipient
+	pub slatepack_contact: Option<String>,
 }
 
 pub fn issue_invoice_tx<L, C, K>(
 	owner_api: &mut Owner<L, C, K>,
 	keychain_mask: Option<&SecretKey>,
-	args: IssueInvoiceArgs,
+	mut args: IssueInvoiceArgs,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
+	// The invoice is encrypted for the contact proof address. The contact address is recorded,
+	// so the transaction is listed with the contact name.
+	if let Some(name) = &args.slatepack_contact {
+		let contact = owner_api.get_contact(keychain_mask, name)?;
+		let address = contact
+			.proof_address
+			.ok_or(ErrorKind::ArgumentError(format!(
+				"Contact {} doesn't have a proof address to use as the slatepack recipient",
+				name
+			)))?;
+		if address.tor_public_key().is_err() {
+			return Err(ErrorKind::ArgumentError(format!(
+				"Contact {} proof address is not a tor PK address, it can't be a slatepack recipient",
+				name
+			))
+			.into());
+		}
+		args.issue_args.slatepack_recipient = Some(address);
+		args.issue_args.address = Some(contact.address);
+	}
+
 	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
 		let mut recipient: Option<DalekPublicKey> = None;
 		if let Some(sp_address) = &args.issue_args.slatepack_recipient {
@@ -969,7 +1982,7 @@ where
 pub struct ProcessInvoiceArgs {
 	pub message: Option<String>,
 	pub minimum_confirmations: u64,
-	pub selection_strategy: String,
+	pub selection_strategy: CoinSelectionStrategy,
 	pub method: String,
 	pub dest: String,
 	pub max_outputs: usize,
@@ -1015,20 +2028,21 @@ where
 	let wallet_inst = owner_api.wallet_inst.clone();
 	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
 		if args.estimate_selection_strategies {
-			let mut strategies: Vec<(&str, u64, u64)> = Vec::new();
-			for strategy in vec!["smallest", "all"] {
+			let mut strategies: Vec<(String, u64, u64)> = Vec::new();
+			for strategy in CoinSelectionStrategy::all_strategies() {
 				let init_args = InitTxArgs {
 					src_acct_name: None,
 					amount: slate.amount,
 					minimum_confirmations: args.minimum_confirmations,
 					max_outputs: args.max_outputs as u32,
 					num_change_outputs: 1u32,
-					selection_strategy_is_use_all: strategy == "all",
+					selection_strategy_is_use_all: strategy.is_use_all(),
+					selection_strategy: Some(strategy),
 					estimate_only: Some(true),
 					..Default::default()
 				};
 				let slate = api.init_send_tx(m, &init_args, 1)?;
-				strategies.push((strategy, slate.amount, slate.fee));
+				strategies.push((strategy.to_string(), slate.amount, slate.fee));
 			}
 			display::estimate(slate.amount, strategies, dark_scheme);
 		} else {
@@ -1038,7 +2052,8 @@ where
 				minimum_confirmations: args.minimum_confirmations,
 				max_outputs: args.max_outputs as u32,
 				num_change_outputs: 1u32,
-				selection_strategy_is_use_all: args.selection_strategy == "all",
+				selection_strategy_is_use_all: args.selection_strategy.is_use_all(),
+				selection_strategy: Some(args.selection_strategy),
 				message: args.message.clone(),
 				ttl_blocks: args.ttl_blocks,
 				send_args: None,
@@ -1169,10 +2184,47 @@ where
 	Ok(())
 }
 
+/// Output label, note and freeze command args
+pub struct OutputMetadataArgs {
+	pub commit: String,
+	/// New label, `Some(None)` clears it
+	pub label: Option<Option<String>>,
+	/// New note, `Some(None)` clears it
+	pub note: Option<Option<String>>,
+	pub frozen: Option<bool>,
+}
+
+pub fn output_metadata<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: OutputMetadataArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		if let Some(label) = &args.label {
+			api.set_output_label(m, &args.commit, label.clone())?;
+		}
+		if let Some(note) = &args.note {
+			api.set_output_note(m, &args.commit, note.clone())?;
+		}
+		if let Some(frozen) = args.frozen {
+			api.set_output_frozen(m, &args.commit, frozen)?;
+		}
+		info!("Output {} is updated", args.commit);
+		Ok(())
+	})?;
+	Ok(())
+}
+
 /// Txs command args
 pub struct TxsArgs {
 	pub id: Option<u32>,
 	pub tx_slate_id: Option<Uuid>,
+	pub query: TxQueryArgs, // filters, sorting and pagination if no particular transaction is requested
 }
 
 pub fn txs<L, C, K>(
@@ -1190,8 +2242,12 @@ where
 	let updater_running = owner_api.updater_running.load(Ordering::Relaxed);
 	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
 		let res = api.node_height(m)?;
-		let (validated, txs) = api.retrieve_txs(m, true, args.id, args.tx_slate_id)?;
 		let include_status = !args.id.is_some() && !args.tx_slate_id.is_some();
+		let (validated, txs) = match include_status {
+			true => api.query_txs(m, true, &args.query)?,
+			false => api.retrieve_txs(m, true, args.id, args.tx_slate_id)?,
+		};
+		let contacts = api.contacts(m)?;
 		display::txs(
 			&g_args.account,
 			res.height,
@@ -1201,6 +2257,9 @@ where
 			dark_scheme,
 			true, // mwc-wallet alwways show the full info because it is advanced tool
 			|tx: &TxLogEntry| tx.payment_proof.is_some(), // it is how mwc-wallet address proofs feature
+			|address: &str| {
+				owner_contacts::contact_for_address(&contacts, address).map(|c| c.name.clone())
+			},
 		)?;
 
 		// if given a particular transaction id or uuid, also get and display associated
@@ -1231,6 +2290,7 @@ where
 			for tx in txs {
 				display::tx_messages(&tx, dark_scheme)?;
 				display::payment_proof(&tx)?;
+				display::batch_payouts(&tx, dark_scheme)?;
 			}
 		}
 
@@ -1239,6 +2299,50 @@ where
 	Ok(())
 }
 
+/// Transaction log export command args
+pub struct ExportTxsArgs {
+	pub file: Option<String>,
+	pub all_accounts: bool,
+	pub export_args: TxExportArgs,
+}
+
+pub fn export_txs<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	g_args: &GlobalArgs,
+	args: ExportTxsArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut export_args = args.export_args;
+	if !args.all_accounts {
+		export_args.account = Some(g_args.account.clone());
+	}
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let (validated, res) = api.export_txs(m, true, &export_args)?;
+		if !validated {
+			warn!("Wallet data wasn't refreshed from the node, the export may be out of date");
+		}
+		match &args.file {
+			Some(file_name) => {
+				let mut file = File::create(file_name).map_err(|e| {
+					ErrorKind::GenericError(format!("Unable to create file {}, {}", file_name, e))
+				})?;
+				file.write_all(res.as_bytes()).map_err(|e| {
+					ErrorKind::GenericError(format!("Unable to write file {}, {}", file_name, e))
+				})?;
+				info!("Transactions are exported to {}", file_name);
+			}
+			None => print!("{}", res),
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
 /// Post
 pub struct PostArgs {
 	pub input: String,
@@ -1405,6 +2509,7 @@ pub struct CheckArgs {
 	pub delete_unconfirmed: bool,
 	pub start_height: Option<u64>,
 	pub backwards_from_tip: Option<u64>,
+	pub resume: bool,
 }
 
 pub fn scan<L, C, K>(
@@ -1418,6 +2523,19 @@ where
 	K: keychain::Keychain + 'static,
 {
 	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		if args.resume {
+			warn!("Resuming the interrupted output scan ...");
+			return match api.resume_scan(m) {
+				Ok(_) => {
+					warn!("Wallet check complete",);
+					Ok(())
+				}
+				Err(e) => {
+					error!("Wallet check failed: {}", e);
+					Err(ErrorKind::LibWallet(format!("Wallet check failed, {}", e)).into())
+				}
+			};
+		}
 		let tip_height = api.node_height(m)?.height;
 		let start_height = match args.backwards_from_tip {
 			Some(b) => tip_height.saturating_sub(b),
@@ -1444,6 +2562,28 @@ where
 }
 
 /// Payment Proof Address
+pub fn view_key<L, C, K>(
+	owner_api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, m| {
+		let view_key = api.get_view_key(m)?;
+		println!();
+		println!("View key: {}", view_key);
+		println!();
+		println!("The view key reveals all outputs and balances of this wallet. Keep it private.");
+		println!("Use it with 'init --view_key' to create a watch-only wallet.");
+		println!();
+		Ok(())
+	})?;
+	Ok(())
+}
+
 pub fn address<L, C, K>(
 	owner_api: &mut Owner<L, C, K>,
 	_g_args: &GlobalArgs,
@@ -1617,6 +2757,26 @@ where
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
+	// Buyer address can be an address book contact name. Contacts with http method are reachable with tor.
+	let mut args = args.clone();
+	if args.buyer_communication_method != "file"
+		&& owner_contacts::is_contact_name(&args.buyer_communication_address)
+	{
+		let contact = owner_api.get_contact(keychain_mask, &args.buyer_communication_address)?;
+		let method = match contact.method.as_str() {
+			"http" => "tor",
+			m => m,
+		};
+		if method != args.buyer_communication_method {
+			return Err(ErrorKind::ArgumentError(format!(
+				"Contact {} is reachable with {} method, not with {}",
+				contact.name, method, args.buyer_communication_method
+			))
+			.into());
+		}
+		args.buyer_communication_address = contact.address;
+	}
+
 	match args.buyer_communication_method.as_str() {
 		"mwcmqs" => {
 			// Validating destination address
@@ -1640,7 +2800,7 @@ where
 	}
 
 	controller::owner_single_use(None, keychain_mask, Some(owner_api), |api, _m| {
-		let result = api.swap_start(keychain_mask, args);
+		let result = api.swap_start(keychain_mask, &args);
 		match result {
 			Ok(swap_id) => {
 				println!("Seller Swap trade is created: {}", swap_id);
@@ -2078,135 +3238,86 @@ where
 
 			// Creating message delivery transport as a closure
 			let apisecret = args.apisecret.clone();
-			let swap_id2 = swap_id.clone();
 			let wallet_inst2 = wallet_inst.clone();
 			let message_sender = move |swap_message: message::Message,
 			                           method: String,
 			                           dest: String|
 			      -> Result<(bool, String), crate::libwallet::Error> {
-				let destination_str = format!("{} {}", method, dest);
-				let from_address;
-
-				// Starting the listener first. For this case we know that they are not started yet
-				// And there will be a single call only.
-				match method.as_str() {
-					"mwcmqs" => {
-						if grin_wallet_impls::adapters::get_mwcmqs_brocker().is_none() {
-							let _ = controller::start_mwcmqs_listener(
-								wallet_inst2,
-								mqs_config.expect("No MQS config found!").clone(),
-								false,
-								Arc::new(Mutex::new(km)),
-								true,
-							)
-							.map_err(|e| {
-								crate::libwallet::ErrorKind::SwapError(format!(
-									"Unable to start mwcmqs listener, {}",
-									e
-								))
-							})?;
-							thread::sleep(Duration::from_millis(2000));
+				let (ack, destination) = deliver_swap_message(
+					swap_message,
+					&method,
+					&dest,
+					&apisecret,
+					tor_config.clone(),
+					// Starting the listener first. For this case we know that they are not started yet
+					// And there will be a single call only.
+					|method| match method {
+						"mwcmqs" => {
+							if grin_wallet_impls::adapters::get_mwcmqs_brocker().is_none() {
+								let _ = controller::start_mwcmqs_listener(
+									wallet_inst2,
+									mqs_config.expect("No MQS config found!").clone(),
+									false,
+									Arc::new(Mutex::new(km)),
+									true,
+								)
+								.map_err(|e| {
+									crate::libwallet::ErrorKind::SwapError(format!(
+										"Unable to start mwcmqs listener, {}",
+										e
+									))
+								})?;
+								thread::sleep(Duration::from_millis(2000));
+							}
+							Ok(grin_wallet_impls::adapters::get_mwcmqs_brocker()
+								.ok_or(crate::libwallet::ErrorKind::SwapError(
+									"Unable to start mwcmqs listener".to_string(),
+								))?
+								.0
+								.get_publisher_address()
+								.map_err(|e| {
+									crate::libwallet::ErrorKind::SwapError(format!(
+										"Unable to get publisher address {}",
+										e
+									))
+								})?
+								.get_full_name())
 						}
-						from_address = grin_wallet_impls::adapters::get_mwcmqs_brocker()
-							.ok_or(crate::libwallet::ErrorKind::SwapError(
-								"Unable to start mwcmqs listener".to_string(),
-							))?
-							.0
-							.get_publisher_address()
-							.map_err(|e| {
-								crate::libwallet::ErrorKind::SwapError(format!(
-									"Unable to get publisher address {}",
-									e
-								))
-							})?
-							.get_full_name();
-					}
-					"tor" => {
-						if !controller::is_foreign_api_running() {
-							let tor_config = tor_config.clone().ok_or(
+						_ => {
+							if !controller::is_foreign_api_running() {
+								let tor_config = tor_config.clone().ok_or(
+									crate::libwallet::ErrorKind::GenericError(
+										"Tor configuration is not defined".to_string(),
+									),
+								)?;
+								let _api_thread = thread::Builder::new()
+									.name("wallet-http-listener".to_string())
+									.spawn(move || {
+										let res = controller::foreign_listener(
+											wallet_inst2,
+											Arc::new(Mutex::new(km)),
+											&api_listen_addr,
+											tls_conf,
+											tor_config.use_tor_listener,
+										);
+										if let Err(e) = res {
+											error!("Error starting http listener: {}", e);
+										}
+									});
+								thread::sleep(Duration::from_millis(2000));
+							}
+							Ok(controller::get_current_tor_address().ok_or(
 								crate::libwallet::ErrorKind::GenericError(
-									"Tor configuration is not defined".to_string(),
+									"Tor is not running".to_string(),
 								),
-							)?;
-							let _api_thread = thread::Builder::new()
-								.name("wallet-http-listener".to_string())
-								.spawn(move || {
-									let res = controller::foreign_listener(
-										wallet_inst2,
-										Arc::new(Mutex::new(km)),
-										&api_listen_addr,
-										tls_conf,
-										tor_config.use_tor_listener,
-									);
-									if let Err(e) = res {
-										error!("Error starting http listener: {}", e);
-									}
-								});
-							thread::sleep(Duration::from_millis(2000));
+							)?)
 						}
-						from_address = controller::get_current_tor_address().ok_or(
-							crate::libwallet::ErrorKind::GenericError(
-								"Tor is not running".to_string(),
-							),
-						)?;
-					}
-					"file" => {
-						// File, let's process it here
-						let msg_str = swap_message.to_json()?;
-						let mut file = File::create(dest.clone())?;
-						file.write_all(msg_str.as_bytes()).map_err(|e| {
-							crate::libwallet::ErrorKind::SwapError(format!(
-								"Unable to store message data to the destination file, {}",
-								e
-							))
-						})?;
-						println!("Message is written into the file {}", dest);
-						return Ok((true, destination_str)); // ack if true, because file is concidered as delivered
-					}
-					_ => {
-						error!("Please specify a method (mwcmqs, tor, or file) for transporting swap messages to the other party with whom you're doing the swap!");
-						return Err(crate::libwallet::Error::from(
-							crate::libwallet::ErrorKind::SwapError(
-								"Expected 'method' argument is not found".to_string(),
-							),
-						));
-					}
-				}
-
-				// File is processed, the online send will be handled here
-				let sender = create_swap_message_sender(
-					method.as_str(),
-					dest.as_str(),
-					&apisecret,
-					tor_config,
-				)
-				.map_err(|e| {
-					crate::libwallet::ErrorKind::SwapError(format!(
-						"Unable to create message sender, {}",
-						e
-					))
-				})?;
-
-				let mut swap_message = swap_message;
-				if let message::Update::Offer(offer_update) = &mut swap_message.inner {
-					offer_update.from_address = from_address;
+					},
+				)?;
+				if method == "file" {
+					println!("Message is written into the file {}", dest);
 				}
-
-				let ack = sender
-					.send_swap_message(&swap_message)
-					.map_err(|e| {
-						ErrorKind::LibWallet(format!(
-							"Failure in sending swap message {} by {}: {}",
-							swap_id2, method, e
-						))
-					})
-					.map_err(|e| {
-						crate::libwallet::ErrorKind::SwapError(format!(
-							"Unable to deliver the message, {}",
-							e
-						))
-					})?;
-				Ok((ack, destination_str))
+				Ok((ack, destination))
 			};
 
 			let result = owner_swap::swap_process(
@@ -2366,37 +3477,18 @@ where
 
 			// Creating message delivery transport as a closure
 			let apisecret = args.apisecret.clone();
-			let swap_id2 = swap_id.clone();
 			let message_sender = move |swap_message: message::Message,
 			                           method: String,
 			                           destination: String|
 			      -> Result<(bool, String), crate::libwallet::Error> {
-				// File is processed, the online send will be handled here
-				let sender = create_swap_message_sender(
-					method.as_str(),
-					destination.as_str(),
+				deliver_swap_message(
+					swap_message,
+					&method,
+					&destination,
 					&apisecret,
 					tor_config,
+					|_| Ok(from_address),
 				)
-				.map_err(|e| {
-					crate::libwallet::ErrorKind::SwapError(format!(
-						"Unable to create message sender, {}",
-						e
-					))
-				})?;
-
-				let mut swap_message = swap_message;
-				if let message::Update::Offer(offer_update) = &mut swap_message.inner {
-					offer_update.from_address = from_address;
-				}
-
-				let ack = sender.send_swap_message(&swap_message).map_err(|e| {
-					crate::libwallet::ErrorKind::SwapError(format!(
-						"Unable to deliver the message {} by {}: {}",
-						swap_id2, method, e
-					))
-				})?;
-				Ok((ack, format!("{} {}", method, destination)))
 			};
 
 			// Calling mostly for params and environment validation. Also it is a nice chance to print the status of the deal that will be started
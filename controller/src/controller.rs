@@ -16,7 +16,7 @@
 //! invocations) as needed.
 use crate::api::{self, ApiServer, BasicAuthMiddleware, ResponseFuture, Router, TLSConfig};
 use crate::libwallet::{
-	NodeClient, NodeVersionInfo, Slate, WalletInst, WalletLCProvider,
+	EventNotifier, NodeClient, NodeVersionInfo, Slate, WalletInst, WalletLCProvider,
 	GRIN_BLOCK_HEADER_VERSION,
 };
 use crate::util::secp::key::SecretKey;
@@ -42,6 +42,7 @@ use crate::apiwallet::{
 };
 use crate::config::{MQSConfig, TorConfig};
 use crate::core::global;
+use crate::hosted_wallets::{HostedWallets, WalletInstFactory};
 use crate::impls::tor::config as tor_config;
 use crate::impls::tor::process as tor_process;
 use crate::keychain::Keychain;
@@ -369,6 +370,7 @@ impl<L, C, K> Controller<L, C, K>
 					/// as many outputs as are needed to meet the amount, (and no more) starting with the smallest
 					/// value outputs.
 					selection_strategy_is_use_all: false,
+					selection_strategy: None,
 					message: None,
 					/// Optionally set the output target slate version (acceptable
 					/// down to the minimum slate version compatible with the current. If `None` the slate
@@ -394,6 +396,12 @@ impl<L, C, K> Controller<L, C, K>
 					late_lock: Some(false),
 					// other waller recipient for encrypted slatepack.
 					slatepack_recipient: None,
+					// Send the amount, not everything
+					sweep: Some(false),
+					// Invoice is not a payment request
+					payment_request_id: None,
+					// New slate id is generated
+					slate_id: None,
 				};
 
 				*slate = owner_api.process_invoice_tx((&mask).as_ref(), slate, &params)?;
@@ -701,6 +709,8 @@ pub fn owner_listener<L, C, K>(
 	tls_config: Option<TLSConfig>,
 	owner_api_include_foreign: Option<bool>,
 	tor_config: Option<TorConfig>,
+	wallet_factory: Option<WalletInstFactory<L, C, K>>,
+	event_notifier: Option<EventNotifier>,
 ) -> Result<(), Error>
 	where
 		L: WalletLCProvider<'static, C, K> + 'static,
@@ -739,6 +749,8 @@ pub fn owner_listener<L, C, K>(
 		keychain_mask.clone(),
 		tor_config,
 		running_foreign,
+		wallet_factory,
+		event_notifier,
 	);
 
 	router
@@ -965,6 +977,9 @@ pub struct OwnerAPIHandlerV3<L, C, K>
 	/// Whether we're running the foreign API on the same port, and therefore
 	/// have to store the mask in-process
 	pub running_foreign: bool,
+
+	/// Named wallets, hosted in addition to the default one
+	pub hosted_wallets: Arc<HostedWallets<L, C, K>>,
 }
 
 pub struct OwnerV3Helpers;
@@ -1190,7 +1205,7 @@ impl OwnerV3Helpers {
 
 impl<L, C, K> OwnerAPIHandlerV3<L, C, K>
 	where
-		L: WalletLCProvider<'static, C, K>,
+		L: WalletLCProvider<'static, C, K> + 'static,
 		C: NodeClient + 'static,
 		K: Keychain + 'static,
 {
@@ -1200,9 +1215,14 @@ impl<L, C, K> OwnerAPIHandlerV3<L, C, K>
 		keychain_mask: Arc<Mutex<Option<SecretKey>>>,
 		tor_config: Option<TorConfig>,
 		running_foreign: bool,
+		wallet_factory: Option<WalletInstFactory<L, C, K>>,
+		event_notifier: Option<EventNotifier>,
 	) -> OwnerAPIHandlerV3<L, C, K> {
-		let owner_api = Owner::new(wallet.clone(), None, tor_config.clone());
+		let hosted_wallets = Arc::new(HostedWallets::new(wallet_factory, tor_config.clone()));
+		let mut owner_api = Owner::new(wallet.clone(), None, tor_config.clone());
 		owner_api.set_tor_config(tor_config);
+		owner_api.set_event_notifier(event_notifier);
+		owner_api.set_hosted_wallet_names(hosted_wallets.shared_names());
 		let owner_api = Arc::new(owner_api);
 		OwnerAPIHandlerV3 {
 			wallet,
@@ -1210,6 +1230,7 @@ impl<L, C, K> OwnerAPIHandlerV3<L, C, K>
 			shared_key: Arc::new(Mutex::new(None)),
 			keychain_mask: keychain_mask,
 			running_foreign,
+			hosted_wallets,
 		}
 	}
 
@@ -1219,6 +1240,7 @@ impl<L, C, K> OwnerAPIHandlerV3<L, C, K>
 	mask: Arc<Mutex<Option<SecretKey>>>,
 	running_foreign: bool,
 	api: Arc<Owner<L, C, K>>,
+	hosted_wallets: Arc<HostedWallets<L, C, K>>,
 ) -> Result<serde_json::Value, Error> {
 	let mut val: serde_json::Value = parse_body(req).await?;
 	let mut is_init_secure_api = OwnerV3Helpers::is_init_secure_api(&val);
@@ -1242,11 +1264,29 @@ impl<L, C, K> OwnerAPIHandlerV3<L, C, K>
 	is_init_secure_api = OwnerV3Helpers::is_init_secure_api(&val);
 	// also need to intercept open/close wallet requests
 	let is_open_wallet = OwnerV3Helpers::is_open_wallet(&val);
-	match OwnerRpcV3::handle_request(&*api, val) {
+	// requests are routed to the hosted wallet by name or token, the rest (including
+	// 'list_wallets') are served by the default wallet
+	let req = val.clone();
+	let mut hosted_name = None;
+	let reply = match hosted_wallets.route(&val) {
+		Ok(None) => OwnerRpcV3::handle_request(&*api, val),
+		Ok(Some((name, hosted_api))) => {
+			hosted_name = Some(name);
+			OwnerRpcV3::handle_request(&*hosted_api, val)
+		}
+		Err(e) => MaybeReply::Reply(serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": val["id"],
+			"result": { "Err": { "GenericError": format!("{}", e) } }
+		})),
+	};
+	match reply {
 		MaybeReply::Reply(mut r) => {
 			let (_was_error, unencrypted_intercept) =
 				OwnerV3Helpers::check_error_response(&r.clone());
-			if is_open_wallet && running_foreign {
+			if let Some(name) = &hosted_name {
+				hosted_wallets.update(name, &req, &r);
+			} else if is_open_wallet && running_foreign {
 				OwnerV3Helpers::update_mask(mask, &r.clone());
 			}
 			if was_encrypted {
@@ -1285,11 +1325,12 @@ impl<L, C, K> OwnerAPIHandlerV3<L, C, K>
 	mask: Arc<Mutex<Option<SecretKey>>>,
 	running_foreign: bool,
 	api: Arc<Owner<L, C, K>>,
+	hosted_wallets: Arc<HostedWallets<L, C, K>>,
 ) -> Result<Response<Body>, Error> {
 	//Here is a wrapper to call future from that.
 	// Issue that we can't call future form future
 	let handler = move || -> Pin<Box<dyn std::future::Future<Output=Result<serde_json::Value, Error>>>> {
-		let future = Self::call_api(req, key, mask, running_foreign, api);
+		let future = Self::call_api(req, key, mask, running_foreign, api, hosted_wallets);
 		Box::pin(future)
 	};
 	let res = crate::executor::RunHandlerInThread::new(handler).await?;
@@ -1310,9 +1351,12 @@ impl<L, C, K> api::Handler for OwnerAPIHandlerV3<L, C, K>
 		let mask = self.keychain_mask.clone();
 		let running_foreign = self.running_foreign;
 		let api = self.owner_api.clone();
+		let hosted_wallets = self.hosted_wallets.clone();
 
 		Box::pin(async move {
-			match Self::handle_post_request(req, key, mask, running_foreign, api).await {
+			match Self::handle_post_request(req, key, mask, running_foreign, api, hosted_wallets)
+				.await
+			{
 				Ok(r) => Ok(r),
 				Err(e) => {
 					error!("Request Error: {:?}", e);
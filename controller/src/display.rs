@@ -18,7 +18,8 @@ use crate::libwallet::swap::fsm::state::StateEtaInfo;
 use crate::libwallet::swap::swap;
 use crate::libwallet::swap::types::{Action, Role};
 use crate::libwallet::{
-	AcctPathMapping, Error, OutputCommitMapping, OutputStatus, TxLogEntry, WalletInfo,
+	AcctPathMapping, Contact, Error, FeeEstimate, MultisigAccount, OutputCommitMapping,
+	OutputStatus, PaymentRequest, ScheduledPayment, TxLogEntry, UtxoTx, WalletInfo,
 };
 
 use crate::util;
@@ -58,7 +59,9 @@ pub fn outputs(
 		bMG->"Coinbase?",
 		bMG->"# Confirms",
 		bMG->"Value",
-		bMG->"Tx"
+		bMG->"Tx",
+		bMG->"Label",
+		bMG->"Note"
 	]);
 
 	for m in outputs {
@@ -76,6 +79,19 @@ pub fn outputs(
 			OutputStatus::Unconfirmed if m.output.is_coinbase => "Mining".to_string(),
 			_ => format!("{}", m.output.status),
 		};
+		let (label, note, frozen) = match &m.metadata {
+			Some(md) => (
+				md.label.clone().unwrap_or_default(),
+				md.note.clone().unwrap_or_default(),
+				md.frozen,
+			),
+			None => (String::new(), String::new(), false),
+		};
+		let status = if frozen && m.output.status != OutputStatus::Spent {
+			format!("{} (Frozen)", status)
+		} else {
+			status
+		};
 
 		let num_confirmations = format!("{}", m.output.num_confirmations(cur_height));
 		let value = format!("{}", core::amount_to_hr_string(m.output.value, false));
@@ -95,6 +111,8 @@ pub fn outputs(
 				bFB->num_confirmations,
 				bFG->value,
 				bFC->tx,
+				bFC->label,
+				bFB->note,
 			]);
 		} else {
 			table.add_row(row![
@@ -107,6 +125,8 @@ pub fn outputs(
 				bFB->num_confirmations,
 				bFG->value,
 				bFD->tx,
+				bFD->label,
+				bFB->note,
 			]);
 		}
 	}
@@ -135,6 +155,7 @@ pub fn txs(
 	dark_background_color_scheme: bool,
 	show_full_info: bool,
 	has_proof: impl Fn(&TxLogEntry) -> bool,
+	contact_name: impl Fn(&str) -> Option<String>,
 ) -> Result<(), Error> {
 	println!();
 	println!(
@@ -197,9 +218,13 @@ pub fn txs(
 			None => String::from(""),
 		};
 
+		// address book contacts are shown by name
 		let address = match &t.address {
-			Some(addr) => addr,
-			None => "",
+			Some(addr) => match contact_name(addr) {
+				Some(name) => format!("{} ({})", name, addr),
+				None => addr.clone(),
+			},
+			None => "".to_owned(),
 		};
 		let entry_type = format!("{}", t.tx_type);
 		let creation_ts = format!("{}", t.creation_ts.format("%Y-%m-%d %H:%M:%S"));
@@ -435,9 +460,9 @@ pub fn info(
 pub fn estimate(
 	amount: u64,
 	strategies: Vec<(
-		&str, // strategy
-		u64,  // total amount to be locked
-		u64,  // fee
+		String, // strategy
+		u64,    // total amount to be locked
+		u64,    // fee
 	)>,
 	dark_background_color_scheme: bool,
 ) {
@@ -473,6 +498,116 @@ pub fn estimate(
 	println!();
 }
 
+/// Display the fee estimate of a send transaction
+pub fn fee_estimate(estimate: &FeeEstimate, validated: bool, dark_background_color_scheme: bool) {
+	println!(
+		"\nFee estimation for sending {}:\n",
+		amount_to_hr_string(estimate.amount, false)
+	);
+
+	let mut table = table!();
+	let rows = vec![
+		("Fee", amount_to_hr_string(estimate.fee, false)),
+		("Will be locked", amount_to_hr_string(estimate.total, false)),
+		("Change", amount_to_hr_string(estimate.change, false)),
+		("Change outputs", estimate.num_change_outputs.to_string()),
+		("Inputs", estimate.inputs.len().to_string()),
+		("Transaction weight", estimate.weight.to_string()),
+		(
+			"Max amount to send",
+			amount_to_hr_string(estimate.max_amount, false),
+		),
+	];
+	for (name, value) in rows {
+		if dark_background_color_scheme {
+			table.add_row(row![bFC->name, FY->value]);
+		} else {
+			table.add_row(row![bFD->name, FB->value]);
+		}
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+
+	if !estimate.inputs.is_empty() {
+		println!("Inputs:");
+		for out in &estimate.inputs {
+			println!(
+				"  {}  {}",
+				out.commit.clone().unwrap_or_default(),
+				amount_to_hr_string(out.value, false)
+			);
+		}
+		println!();
+	}
+
+	if !validated {
+		println!(
+			"\nWARNING: Wallet failed to verify data against a live chain. \
+			 The above is from local cache and only valid up to the given height! \
+			 (is your `mwc server` offline or broken?)"
+		);
+	}
+}
+
+/// Display the consolidation or split transactions
+pub fn utxo_txs(title: &str, txs: &[UtxoTx], preview: bool, dark_background_color_scheme: bool) {
+	if preview {
+		println!("\n{} preview, nothing is sent:\n", title);
+	} else {
+		println!("\n{} transactions are posted:\n", title);
+	}
+
+	let mut table = table!();
+	table.set_titles(row![
+		bMG->"Tx",
+		bMG->"Inputs",
+		bMG->"Input Value",
+		bMG->"New Outputs",
+		bMG->"Fee",
+		bMG->"Slate ID",
+	]);
+	for (i, tx) in txs.iter().enumerate() {
+		let input_value = amount_to_hr_string(tx.inputs.iter().map(|o| o.value).sum(), false);
+		let outputs = tx
+			.outputs
+			.iter()
+			.map(|a| amount_to_hr_string(*a, false))
+			.collect::<Vec<String>>()
+			.join(", ");
+		let fee = amount_to_hr_string(tx.fee, false);
+		let slate_id = match tx.tx_slate_id {
+			Some(id) => id.to_string(),
+			None => "None".to_owned(),
+		};
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->i + 1,
+				bFB->tx.inputs.len(),
+				FG->input_value,
+				FY->outputs,
+				FR->fee,
+				bFD->slate_id,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->i + 1,
+				bFB->tx.inputs.len(),
+				FG->input_value,
+				FB->outputs,
+				FR->fee,
+				bFD->slate_id,
+			]);
+		}
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!(
+		"\nTotal fee: {}\n",
+		amount_to_hr_string(txs.iter().map(|t| t.fee).sum(), false)
+	);
+}
+
 /// Display list of wallet accounts in a pretty way
 pub fn accounts(acct_mappings: Vec<AcctPathMapping>) {
 	println!("\n____ Wallet Accounts ____\n",);
@@ -613,6 +748,363 @@ pub fn payment_proof(tx: &TxLogEntry) -> Result<(), Error> {
 	Ok(())
 }
 
+/// Display recipients of the batch payout
+pub fn batch_payouts(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Result<(), Error> {
+	let payouts = match &tx.batch_payouts {
+		None => return Ok(()),
+		Some(p) => p,
+	};
+
+	println!();
+	println!(
+		"{}",
+		format!("Batch Payouts - Transaction '{}'", tx.id,).magenta()
+	);
+
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->"Slate Id",
+		bMG->"Address",
+		bMG->"Amount",
+		bMG->"Fee",
+		bMG->"Kernel Excess",
+	]);
+
+	for p in payouts {
+		let slate_id = format!("{}", p.slate_id);
+		let address = p.address.clone().unwrap_or("None".to_owned());
+		let amount = core::amount_to_hr_string(p.amount, true);
+		let fee = core::amount_to_hr_string(p.fee, true);
+		let kernel_excess = match p.kernel_excess {
+			Some(e) => util::to_hex(&e.0),
+			None => "None".to_owned(),
+		};
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->slate_id,
+				bFC->address,
+				bFG->amount,
+				bFR->fee,
+				bFB->kernel_excess,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->slate_id,
+				bFD->address,
+				bFG->amount,
+				bFR->fee,
+				bFB->kernel_excess,
+			]);
+		}
+	}
+
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+
+	Ok(())
+}
+
+/// Display multisig accounts in a pretty way
+pub fn multisig_accounts(accounts: &Vec<MultisigAccount>, dark_background_color_scheme: bool) {
+	println!("\n____ Multisig Accounts ____\n",);
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->"Account Id",
+		bMG->"Status",
+		bMG->"Co-signers",
+		bMG->"Amount",
+		bMG->"Shared Output",
+	]);
+
+	for a in accounts {
+		let id = format!("{}", a.id);
+		let status = format!("{}", a.status);
+		let co_signers = format!("{}-of-{}", a.threshold, a.num_participants);
+		let amount = core::amount_to_hr_string(a.amount, true);
+		let commit = match &a.commit {
+			Some(c) => util::to_hex(&c.0),
+			None => "None".to_owned(),
+		};
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->id,
+				bFB->status,
+				bFC->co_signers,
+				bFG->amount,
+				bFD->commit,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->id,
+				bFB->status,
+				bFD->co_signers,
+				bFG->amount,
+				bFD->commit,
+			]);
+		}
+	}
+
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
+/// Display scheduled payments and the result of their latest send attempt
+pub fn scheduled_payments(payments: &Vec<ScheduledPayment>, dark_background_color_scheme: bool) {
+	println!("\n____ Scheduled Payments ____\n",);
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->"Payment Id",
+		bMG->"Status",
+		bMG->"Amount",
+		bMG->"Method",
+		bMG->"Destination",
+		bMG->"Next Payment",
+		bMG->"Interval",
+		bMG->"Retries",
+		bMG->"Last Result",
+	]);
+
+	for p in payments {
+		let id = format!("{}", p.id);
+		let status = format!("{}", p.status);
+		let amount = core::amount_to_hr_string(p.amount, true);
+		let next_run = match p.due_time() {
+			Some(t) => format!("{}", t.format("%Y-%m-%d %H:%M:%S")),
+			None => "None".to_owned(),
+		};
+		let interval = match p.interval_secs {
+			Some(i) => format!("{} s", i),
+			None => "Once".to_owned(),
+		};
+		let retries = format!("{}/{}", p.retries, p.max_retries);
+		let last_result = match p.history.last() {
+			Some(r) => match (&r.tx_slate_id, &r.error) {
+				(_, Some(e)) => format!("{} failed: {}", r.time.format("%Y-%m-%d %H:%M:%S"), e),
+				(Some(id), None) => format!("{} sent {}", r.time.format("%Y-%m-%d %H:%M:%S"), id),
+				(None, None) => format!("{}", r.time.format("%Y-%m-%d %H:%M:%S")),
+			},
+			None => "None".to_owned(),
+		};
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->id,
+				bFB->status,
+				bFG->amount,
+				bFC->p.method,
+				bFC->p.dest,
+				bFB->next_run,
+				bFC->interval,
+				bFC->retries,
+				bFC->last_result,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->id,
+				bFB->status,
+				bFG->amount,
+				bFD->p.method,
+				bFD->p.dest,
+				bFB->next_run,
+				bFD->interval,
+				bFD->retries,
+				bFD->last_result,
+			]);
+		}
+	}
+
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
+/// Display address book contacts
+pub fn contacts(contacts: &Vec<Contact>, dark_background_color_scheme: bool) {
+	println!("\n____ Contacts ____\n",);
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->"Name",
+		bMG->"Method",
+		bMG->"Address",
+		bMG->"Proof Address",
+		bMG->"API Secret",
+	]);
+
+	for c in contacts {
+		let proof_address = match &c.proof_address {
+			Some(a) => a.public_key.clone(),
+			None => "None".to_owned(),
+		};
+		let apisecret = match c.apisecret {
+			Some(_) => "Yes".to_owned(),
+			None => "None".to_owned(),
+		};
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->c.name,
+				bFB->c.method,
+				bFC->c.address,
+				bFC->proof_address,
+				bFC->apisecret,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->c.name,
+				bFB->c.method,
+				bFD->c.address,
+				bFD->proof_address,
+				bFD->apisecret,
+			]);
+		}
+	}
+
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
+/// Display payment requests
+pub fn payment_requests(requests: &Vec<PaymentRequest>, dark_background_color_scheme: bool) {
+	println!("\n____ Payment Requests ____\n",);
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->"Request Id",
+		bMG->"Status",
+		bMG->"Amount",
+		bMG->"Address",
+		bMG->"Memo",
+		bMG->"Expires",
+		bMG->"Proof",
+		bMG->"Created",
+	]);
+
+	for r in requests {
+		let id = format!("{}", r.id);
+		let status = format!("{}", r.status);
+		let amount = core::amount_to_hr_string(r.amount, true);
+		let memo = r.memo.clone().unwrap_or("None".to_owned());
+		let expires = match (r.expiry_height, r.expiry_time) {
+			(Some(h), Some(t)) => format!("height {}, {}", h, t.format("%Y-%m-%d %H:%M:%S")),
+			(Some(h), None) => format!("height {}", h),
+			(None, Some(t)) => format!("{}", t.format("%Y-%m-%d %H:%M:%S")),
+			(None, None) => "Never".to_owned(),
+		};
+		let proof = match r.proof_address {
+			Some(_) => "Yes".to_owned(),
+			None => "No".to_owned(),
+		};
+		let created = format!("{}", r.created.format("%Y-%m-%d %H:%M:%S"));
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->id,
+				bFB->status,
+				bFG->amount,
+				bFC->r.address,
+				bFC->memo,
+				bFB->expires,
+				bFC->proof,
+				bFC->created,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->id,
+				bFB->status,
+				bFG->amount,
+				bFD->r.address,
+				bFD->memo,
+				bFB->expires,
+				bFD->proof,
+				bFD->created,
+			]);
+		}
+	}
+
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
+/// Display multisig account and the progress of its co-signers
+pub fn multisig_account(account: &MultisigAccount, dark_background_color_scheme: bool) {
+	println!();
+	println!(
+		"{}",
+		format!(
+			"Multisig Account '{}' - {}-of-{}, {}",
+			account.id, account.threshold, account.num_participants, account.status
+		)
+		.magenta()
+	);
+	println!(
+		"Amount: {} MWC",
+		core::amount_to_hr_string(account.amount, false)
+	);
+	if let Some(c) = &account.commit {
+		println!("Shared output: {}", util::to_hex(&c.0));
+	}
+	if let Some(local) = &account.local {
+		println!("This wallet is co-signer {}", local.index);
+	}
+	if let Some(spend) = &account.spend {
+		println!(
+			"Spending by co-signers {:?}, slate {}",
+			spend.signers, spend.slate.id
+		);
+		if let Some(address) = &spend.address {
+			println!(
+				"Recipient: {}, amount: {} MWC",
+				address,
+				core::amount_to_hr_string(spend.slate.amount, false)
+			);
+		}
+	}
+
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->"Co-signer",
+		bMG->"Public Key",
+		bMG->"Contribution",
+		bMG->"Shares Issued",
+	]);
+
+	for (i, p) in account.participants.iter().enumerate() {
+		let index = format!("{}", i);
+		let public_key = util::to_hex(&p.public_key.serialize_vec(true));
+		let contribution = match account.contributions.get(i) {
+			Some(c) => core::amount_to_hr_string(*c, true),
+			None if i == 0 => core::amount_to_hr_string(account.amount, true),
+			None => core::amount_to_hr_string(0, true),
+		};
+		let shares = format!("{}", !p.shares.is_empty());
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->index,
+				bFD->public_key,
+				bFY->contribution,
+				bFB->shares,
+			]);
+		} else {
+			table.add_row(row![
+				bFD->index,
+				bFD->public_key,
+				bFB->contribution,
+				bFB->shares,
+			]);
+		}
+	}
+
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
 /// Display list of wallet accounts in a pretty way
 pub fn swap_trades(trades: Vec<(String, String)>) {
 	println!("\n____ Swap trades ____\n",);
@@ -0,0 +1,207 @@
+// Copyright 2019 The Grin Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Named wallets hosted by a single owner API listener. Every hosted wallet has its
+//! own Owner API instance, so it has its own keychain mask (V3 token) and updater thread.
+
+use crate::apiwallet::Owner;
+use crate::config::TorConfig;
+use crate::keychain::Keychain;
+use crate::libwallet::{NodeClient, WalletInst, WalletLCProvider};
+use crate::util::secp::key::SecretKey;
+use crate::util::{from_hex, Mutex};
+use crate::{Error, ErrorKind};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Creates a new, not yet opened, wallet instance for a hosted wallet
+pub type WalletInstFactory<L, C, K> =
+	Arc<dyn Fn() -> Result<Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>, Error> + Send + Sync>;
+
+/// Lifecycle methods of the V3 API that take the wallet name as a first parameter
+const NAMED_METHODS: &[&str] = &[
+	"create_wallet",
+	"open_wallet",
+	"close_wallet",
+	"get_mnemonic",
+	"change_password",
+	"delete_wallet",
+];
+
+struct HostedWallet<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	owner: Arc<Owner<L, C, K>>,
+	/// Keychain mask returned by 'open_wallet', it is the V3 token of this wallet
+	token: Option<SecretKey>,
+}
+
+/// Wallets hosted by the owner API listener in addition to the default (unnamed) one
+pub struct HostedWallets<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	factory: Option<WalletInstFactory<L, C, K>>,
+	tor_config: Option<TorConfig>,
+	wallets: Mutex<HashMap<String, HostedWallet<L, C, K>>>,
+	/// Sorted names of the hosted wallets, shared with the Owner API of the default wallet
+	names: Arc<Mutex<Vec<String>>>,
+}
+
+impl<L, C, K> HostedWallets<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Create an empty set of hosted wallets. Without a factory only the default
+	/// wallet can be served.
+	pub fn new(factory: Option<WalletInstFactory<L, C, K>>, tor_config: Option<TorConfig>) -> Self {
+		HostedWallets {
+			factory,
+			tor_config,
+			wallets: Mutex::new(HashMap::new()),
+			names: Arc::new(Mutex::new(vec![])),
+		}
+	}
+
+	/// Names of the hosted wallets
+	pub fn names(&self) -> Vec<String> {
+		self.names.lock().clone()
+	}
+
+	/// Names of the hosted wallets that are kept up to date while wallets are opened and closed
+	pub fn shared_names(&self) -> Arc<Mutex<Vec<String>>> {
+		self.names.clone()
+	}
+
+	fn update_names(&self, wallets: &HashMap<String, HostedWallet<L, C, K>>) {
+		let mut names: Vec<String> = wallets.keys().cloned().collect();
+		names.sort();
+		*self.names.lock() = names;
+	}
+
+	/// Owner API of the hosted wallet
+	pub fn get(&self, name: &str) -> Option<Arc<Owner<L, C, K>>> {
+		self.wallets.lock().get(name).map(|w| w.owner.clone())
+	}
+
+	/// Owner API of the hosted wallet. If the wallet isn't hosted yet, a new not opened
+	/// instance is created for it.
+	pub fn get_or_create(&self, name: &str) -> Result<Arc<Owner<L, C, K>>, Error> {
+		let mut wallets = self.wallets.lock();
+		if let Some(w) = wallets.get(name) {
+			return Ok(w.owner.clone());
+		}
+		let factory = self.factory.as_ref().ok_or_else(|| {
+			ErrorKind::GenericError("This owner API listener can't host named wallets".to_string())
+		})?;
+		let owner = Owner::new(factory()?, None, self.tor_config.clone());
+		owner.set_tor_config(self.tor_config.clone());
+		let owner = Arc::new(owner);
+		wallets.insert(
+			name.to_owned(),
+			HostedWallet {
+				owner: owner.clone(),
+				token: None,
+			},
+		);
+		self.update_names(&wallets);
+		Ok(owner)
+	}
+
+	/// Name and Owner API of the hosted wallet that was opened with this token
+	pub fn find_by_token(&self, token: &SecretKey) -> Option<(String, Arc<Owner<L, C, K>>)> {
+		self.wallets
+			.lock()
+			.iter()
+			.find(|(_, w)| w.token.as_ref() == Some(token))
+			.map(|(name, w)| (name.clone(), w.owner.clone()))
+	}
+
+	/// Stop the wallet updater and stop hosting the wallet
+	pub fn remove(&self, name: &str) {
+		let removed = {
+			let mut wallets = self.wallets.lock();
+			let removed = wallets.remove(name);
+			self.update_names(&wallets);
+			removed
+		};
+		if let Some(w) = removed {
+			let _ = w.owner.stop_updater();
+		}
+	}
+
+	/// Select the hosted wallet that serves the V3 request. Lifecycle calls are routed by
+	/// the wallet name ('open_wallet' starts hosting the wallet), the rest by the token.
+	/// None means that the request belongs to the default wallet.
+	pub fn route(&self, req: &Value) -> Result<Option<(String, Arc<Owner<L, C, K>>)>, Error> {
+		let method = req["method"].as_str().unwrap_or("");
+		if NAMED_METHODS.contains(&method) {
+			return match request_param(req, "name", 0).as_str() {
+				None => Ok(None),
+				Some(name) if method == "open_wallet" => {
+					Ok(Some((name.to_owned(), self.get_or_create(name)?)))
+				}
+				Some(name) => Ok(self.get(name).map(|o| (name.to_owned(), o))),
+			};
+		}
+		let token = request_param(req, "token", 0)
+			.as_str()
+			.and_then(|t| from_hex(t).ok())
+			.and_then(|t| SecretKey::from_slice(&t).ok());
+		Ok(token.and_then(|t| self.find_by_token(&t)))
+	}
+
+	/// Update the hosted wallet from the response of the lifecycle call. Successful
+	/// 'open_wallet' issues a new token, 'close_wallet' and 'delete_wallet' stop hosting it.
+	pub fn update(&self, name: &str, req: &Value, res: &Value) {
+		let ok = res["result"].get("Ok").is_some();
+		match req["method"].as_str() {
+			Some("open_wallet") => {
+				if ok {
+					let token = res["result"]["Ok"]
+						.as_str()
+						.and_then(|t| from_hex(t).ok())
+						.and_then(|t| SecretKey::from_slice(&t).ok());
+					if let Some(w) = self.wallets.lock().get_mut(name) {
+						w.token = token;
+					}
+				} else {
+					// Failed to open the wallet that wasn't opened before, nothing to host
+					let opened = self.wallets.lock().get(name).map(|w| w.token.is_some());
+					if opened == Some(false) {
+						self.remove(name);
+					}
+				}
+			}
+			Some("close_wallet") | Some("delete_wallet") if ok => self.remove(name),
+			_ => {}
+		}
+	}
+}
+
+/// Value of the request parameter, passed either by name or by position
+fn request_param<'a>(req: &'a Value, name: &str, index: usize) -> &'a Value {
+	match &req["params"] {
+		Value::Array(params) => params.get(index).unwrap_or(&Value::Null),
+		params => &params[name],
+	}
+}
@@ -39,5 +39,6 @@ pub mod controller;
 pub mod display;
 mod error;
 pub mod executor;
+pub mod hosted_wallets;
 
 pub use crate::error::{Error, ErrorKind};
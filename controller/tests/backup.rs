@@ -0,0 +1,197 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test full wallet backup and restore
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_keychain as keychain;
+use grin_wallet_util::grin_util as util;
+
+use self::core::global;
+use self::keychain::ExtKeychain;
+use self::libwallet::{InitTxArgs, WalletInst};
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{DefaultLCProvider, DefaultWalletImpl};
+use std::fs;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use util::{Mutex, ZeroingString};
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn backup_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		_wallet2,
+		_mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+
+	let proxy_tx = wallet_proxy.tx.clone();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	// Transaction with a message, it can't be restored from the chain
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			message: Some("backup me".to_owned()),
+			..Default::default()
+		};
+		let mut slate = api.init_send_tx(m, &args, 1)?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	let backup_file = format!("{}/wallet1.backup", test_dir);
+	let mut txs = vec![];
+	let mut info = None;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		txs = api.retrieve_txs(m, true, None, None)?.1;
+		info = Some(api.retrieve_summary_info(m, false, 1)?.1);
+		// DB of the open wallet can't be copied
+		assert!(api
+			.backup_wallet(None, ZeroingString::from(""), &backup_file, None)
+			.is_err());
+		Ok(())
+	})?;
+	let info = info.unwrap();
+	{
+		let mut w_lock = wallet1.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.close_wallet(None)?;
+		assert!(lc
+			.backup_wallet(None, ZeroingString::from("wrong"), &backup_file, None)
+			.is_err());
+		lc.backup_wallet(None, ZeroingString::from(""), &backup_file, None)?;
+	}
+
+	// Restore into a new location
+	let restored_client = LocalWalletClient::new("restored", proxy_tx);
+	let mut restored = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(
+		restored_client,
+	)?)
+		as Box<
+			dyn WalletInst<
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>;
+	{
+		let lc = restored.lc_provider()?;
+		lc.set_top_level_directory(&format!("{}/restored", test_dir))?;
+		assert!(lc
+			.restore_wallet(None, ZeroingString::from("wrong"), &backup_file, None)
+			.is_err());
+		lc.restore_wallet(None, ZeroingString::from(""), &backup_file, None)?;
+		// Can't restore over the existing wallet
+		assert!(lc
+			.restore_wallet(None, ZeroingString::from(""), &backup_file, None)
+			.is_err());
+		lc.open_wallet(None, ZeroingString::from(""), false, false, None)?;
+	}
+	let restored = Arc::new(Mutex::new(restored));
+
+	// Restored wallet has the same data without a chain scan
+	wallet::controller::owner_single_use(Some(restored.clone()), None, None, |api, m| {
+		let (_, restored_txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(restored_txs.len(), txs.len());
+		for (r, t) in restored_txs.iter().zip(txs.iter()) {
+			assert_eq!(r.tx_slate_id, t.tx_slate_id);
+			assert_eq!(r.messages.is_some(), t.messages.is_some());
+			assert_eq!(r.amount_credited, t.amount_credited);
+			assert_eq!(r.amount_debited, t.amount_debited);
+		}
+		assert!(restored_txs.iter().any(|t| t.messages.is_some()));
+		let (_, restored_info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(restored_info.total, info.total);
+		assert_eq!(
+			restored_info.amount_currently_spendable,
+			info.amount_currently_spendable
+		);
+		Ok(())
+	})?;
+
+	// Damaged archive is rejected
+	let mut archive = fs::read(&backup_file).unwrap();
+	let last = archive.len() - 1;
+	archive[last] ^= 0xff;
+	fs::write(&backup_file, archive).unwrap();
+	{
+		let mut w_lock = restored.lock();
+		let lc = w_lock.lc_provider()?;
+		assert!(lc
+			.restore_wallet(Some("other"), ZeroingString::from(""), &backup_file, None)
+			.is_err());
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn backup_restore() {
+	let test_dir = "test_output/backup_restore";
+	setup(test_dir);
+	if let Err(e) = backup_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
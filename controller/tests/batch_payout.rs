@@ -0,0 +1,462 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test batch payout to several recipients in a single transaction
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::core::libtx::tx_fee;
+use self::libwallet::proof::proofaddress;
+use self::libwallet::slatepack::SlatePurpose;
+use self::libwallet::{BatchPayout, InitTxArgs, OutputStatus, Slate, TxLogEntryType};
+use ed25519_dalek::{PublicKey as DalekPublicKey, SecretKey as DalekSecretKey};
+use impls::adapters::SlateGetData;
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{PathToSlateGetter, PathToSlatePutter, SlateGetter, SlatePutter};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_slatepack(
+	slate: &Slate,
+	content: SlatePurpose,
+	file_name: &str,
+	sender: DalekPublicKey,
+	recipient: DalekPublicKey,
+	sender_secret: &DalekSecretKey,
+) -> Result<(), libwallet::Error> {
+	PathToSlatePutter::build_encrypted(
+		Some(file_name.into()),
+		content,
+		sender,
+		Some(recipient),
+		true,
+	)
+	.put_tx(&slate, &sender_secret, true)
+	.map_err(|e| libwallet::ErrorKind::GenericError(format!("Unable to store the slate, {}", e)))?;
+	Ok(())
+}
+
+fn slate_from_packed(file: &str, dec_key: &DalekSecretKey) -> Result<Slate, libwallet::Error> {
+	match PathToSlateGetter::build_form_path(file.into())
+		.get_tx(dec_key)
+		.map_err(|e| {
+			libwallet::ErrorKind::GenericError(format!("Unable to read the slate, {}", e))
+		})? {
+		SlateGetData::PlainSlate(_) => Err(libwallet::ErrorKind::GenericError(
+			"Not found expected encrypted slatepack, found in plain format only".to_string(),
+		)
+		.into()),
+		SlateGetData::Slatepack(sp) => Ok(sp.to_result_slate()),
+	}
+}
+
+fn batch_payout_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3_i,
+		test_dir,
+		"wallet3",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask3 = (&mask3_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let payouts = vec![
+		BatchPayout {
+			amount: reward / 4,
+			address: Some("wallet2".to_string()),
+		},
+		BatchPayout {
+			amount: reward / 2,
+			address: Some("wallet3".to_string()),
+		},
+	];
+
+	let mut slates: Vec<Slate> = vec![];
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		assert!(api.init_batch_send_tx(m, &args, &[]).is_err());
+
+		slates = api.init_batch_send_tx(m, &args, &payouts)?;
+		assert_eq!(slates.len(), 2);
+		// Inputs and change are in the first slate only
+		assert_eq!(slates[0].tx.inputs().len(), 1);
+		assert!(slates[1].tx.inputs().is_empty());
+		assert!(slates[1].tx.outputs().is_empty());
+
+		slates[0] = client1.send_tx_slate_direct("wallet2", &slates[0])?;
+		slates[1] = client1.send_tx_slate_direct("wallet3", &slates[1])?;
+
+		// Every recipient must respond
+		assert!(api.finalize_batch_tx(m, &slates[..1]).is_err());
+
+		let tx = api.finalize_batch_tx(m, &slates)?;
+		assert_eq!(tx.kernels().len(), 2);
+		assert_eq!(tx.outputs().len(), 3);
+		api.post_tx(m, &tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// Batch is a single confirmed transaction that pays the fee of every kernel
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (refreshed, txs) = api.retrieve_txs(m, true, None, Some(slates[0].id))?;
+		assert!(refreshed);
+		let tx = txs[0].clone();
+		assert!(tx.confirmed);
+		assert_eq!(tx.fee, Some(tx_fee(1, 3, 2, None)));
+		assert_eq!(
+			tx.amount_debited - tx.amount_credited,
+			reward / 4 + reward / 2 + tx_fee(1, 3, 2, None)
+		);
+		let batch_payouts = tx.batch_payouts.unwrap();
+		assert_eq!(batch_payouts.len(), 2);
+		for (p, s) in batch_payouts.iter().zip(slates.iter()) {
+			assert_eq!(p.slate_id, s.id);
+			assert_eq!(p.amount, s.amount);
+			assert!(p.kernel_excess.is_some());
+		}
+		assert_eq!(
+			batch_payouts.iter().map(|p| p.fee).sum::<u64>(),
+			tx_fee(1, 3, 2, None)
+		);
+		Ok(())
+	})?;
+
+	// Recipients got the coins
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_currently_spendable, reward / 4);
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(slates[0].id))?;
+		assert!(txs[0].confirmed);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(Some(wallet3.clone()), mask3, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_currently_spendable, reward / 2);
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(slates[1].id))?;
+		assert!(txs[0].confirmed);
+		Ok(())
+	})?;
+
+	// Cancelled batch unlocks the inputs and deletes the context of every slate
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		let slates = api.init_batch_send_tx(m, &args, &payouts)?;
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slates[0].id))?;
+		assert_eq!(txs[0].batch_payouts.as_ref().unwrap().len(), 2);
+		{
+			let mut w_lock = api.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			for s in &slates {
+				assert!(w.get_private_context(m, s.id.as_bytes(), 0).is_ok());
+			}
+		}
+
+		api.cancel_tx(m, None, Some(slates[0].id))?;
+		let (_, outputs) = api.retrieve_outputs(m, false, false, None)?;
+		assert!(outputs
+			.iter()
+			.all(|o| o.output.status != OutputStatus::Locked));
+		let mut w_lock = api.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		for s in &slates {
+			assert!(w.get_private_context(m, s.id.as_bytes(), 0).is_err());
+		}
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+/// Batch payout with compact slates, every slate goes to its recipient as a slatepack
+fn batch_payout_slatepack_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3_i,
+		test_dir,
+		"wallet3",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask3 = (&mask3_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	// Slatepack keys of the sender and the recipients
+	let mut keys: Vec<(DalekPublicKey, DalekSecretKey)> = vec![];
+	for (wallet, mask) in &[
+		(wallet1.clone(), mask1),
+		(wallet2.clone(), mask2),
+		(wallet3.clone(), mask3),
+	] {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let mut w_lock = api.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let sec_key = proofaddress::payment_proof_address_dalek_secret(&w.keychain(m)?, None)?;
+			keys.push((DalekPublicKey::from(&sec_key), sec_key));
+			Ok(())
+		})?;
+	}
+
+	let payouts = vec![
+		BatchPayout {
+			amount: reward / 4,
+			address: Some("wallet2".to_string()),
+		},
+		BatchPayout {
+			amount: reward / 2,
+			address: Some("wallet3".to_string()),
+		},
+	];
+
+	let mut slates: Vec<Slate> = vec![];
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			target_slate_version: Some(4),
+			..Default::default()
+		};
+		slates = api.init_batch_send_tx(m, &args, &payouts)?;
+		assert_eq!(slates.len(), 2);
+		assert!(slates.iter().all(|s| s.compact_slate));
+		Ok(())
+	})?;
+
+	// Every recipient gets its slatepack and responds with another one
+	let recipients = vec![
+		(wallet2.clone(), mask2_i.clone()),
+		(wallet3.clone(), mask3_i.clone()),
+	];
+	let mut responses: Vec<Slate> = vec![];
+	for (i, (slate, (wallet, mask))) in slates.iter().zip(recipients.into_iter()).enumerate() {
+		let (sender_pk, sender_sk) = &keys[0];
+		let (recipient_pk, recipient_sk) = &keys[i + 1];
+		let send_file = format!("{}/batch_{}_S1.slatepack", test_dir, i);
+		let receive_file = format!("{}/batch_{}_S2.slatepack", test_dir, i);
+		output_slatepack(
+			slate,
+			SlatePurpose::SendInitial,
+			&send_file,
+			sender_pk.clone(),
+			recipient_pk.clone(),
+			sender_sk,
+		)?;
+
+		let receive_slate = slate_from_packed(&send_file, recipient_sk)?;
+		assert_eq!(receive_slate.id, slate.id);
+		wallet::controller::foreign_single_use(wallet, mask, |api| {
+			let slate = api.receive_tx(&receive_slate, None, None, None)?;
+			output_slatepack(
+				&slate,
+				SlatePurpose::SendResponse,
+				&receive_file,
+				recipient_pk.clone(),
+				sender_pk.clone(),
+				recipient_sk,
+			)?;
+			Ok(())
+		})?;
+		responses.push(slate_from_packed(&receive_file, sender_sk)?);
+	}
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		// Responses can come back in any order
+		responses.reverse();
+		let tx = api.finalize_batch_tx(m, &responses)?;
+		assert_eq!(tx.kernels().len(), 2);
+		assert_eq!(tx.inputs().len(), 1);
+		assert_eq!(tx.outputs().len(), 3);
+		api.post_tx(m, &tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// Sender has a single entry with a payout per recipient
+	let fee = tx_fee(1, 3, 2, None);
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(slates[0].id))?;
+		assert_eq!(txs.len(), 1);
+		let tx = txs[0].clone();
+		assert!(tx.confirmed);
+		assert_eq!(tx.tx_type, TxLogEntryType::TxSent);
+		assert_eq!(tx.fee, Some(fee));
+		assert_eq!(
+			tx.amount_debited - tx.amount_credited,
+			reward / 4 + reward / 2 + fee
+		);
+		let batch_payouts = tx.batch_payouts.unwrap();
+		assert_eq!(batch_payouts.len(), 2);
+		for ((p, s), b) in batch_payouts.iter().zip(slates.iter()).zip(payouts.iter()) {
+			assert_eq!(p.slate_id, s.id);
+			assert_eq!(p.amount, b.amount);
+			assert_eq!(p.address, b.address);
+			assert!(p.kernel_excess.is_some());
+		}
+
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, 8 * reward - reward / 4 - reward / 2 - fee);
+		Ok(())
+	})?;
+
+	// Every recipient has its own received entry
+	let recipients = vec![
+		(wallet2.clone(), mask2, reward / 4),
+		(wallet3.clone(), mask3, reward / 2),
+	];
+	for ((wallet, mask, amount), slate) in recipients.into_iter().zip(slates.iter()) {
+		wallet::controller::owner_single_use(Some(wallet), mask, None, |api, m| {
+			let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+			assert_eq!(info.amount_currently_spendable, amount);
+			let (_, txs) = api.retrieve_txs(m, true, None, Some(slate.id))?;
+			assert_eq!(txs.len(), 1);
+			assert!(txs[0].confirmed);
+			assert_eq!(txs[0].tx_type, TxLogEntryType::TxReceived);
+			assert_eq!(txs[0].amount_credited, amount);
+			Ok(())
+		})?;
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn batch_payout() {
+	let test_dir = "test_output/batch_payout";
+	setup(test_dir);
+	if let Err(e) = batch_payout_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
+
+#[test]
+fn batch_payout_slatepack() {
+	let test_dir = "test_output/batch_payout_slatepack";
+	setup(test_dir);
+	if let Err(e) = batch_payout_slatepack_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
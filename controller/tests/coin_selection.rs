@@ -0,0 +1,194 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test coin selection strategies
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::core::libtx::tx_fee;
+use self::libwallet::{CoinSelectionStrategy, InitTxArgs, Slate};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn estimate_args(amount: u64, strategy: CoinSelectionStrategy) -> InitTxArgs {
+	InitTxArgs {
+		src_acct_name: None,
+		amount,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy: Some(strategy),
+		estimate_only: Some(true),
+		..Default::default()
+	}
+}
+
+fn coin_selection_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// few values to keep things shorter
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+
+	// Mine into wallet 1, all coinbase outputs have the same value
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+
+		// All: everything that is spendable
+		let est = api.init_send_tx(m, &estimate_args(reward, CoinSelectionStrategy::All), 1)?;
+		assert_eq!(est.amount, info.amount_currently_spendable);
+
+		// Exact match, single output covers amount and fee, no change
+		let exact_amount = reward - tx_fee(1, 1, 1, None);
+		let est = api.init_send_tx(
+			m,
+			&estimate_args(exact_amount, CoinSelectionStrategy::BranchAndBound),
+			1,
+		)?;
+		assert_eq!(est.amount, reward);
+		assert_eq!(est.fee, tx_fee(1, 1, 1, None));
+
+		// No exact match, falls back to the smallest outputs with change
+		let est = api.init_send_tx(
+			m,
+			&estimate_args(reward / 2, CoinSelectionStrategy::BranchAndBound),
+			1,
+		)?;
+		assert_eq!(est.amount, reward);
+		assert_eq!(est.fee, tx_fee(1, 2, 1, None));
+
+		let est = api.init_send_tx(
+			m,
+			&estimate_args(reward * 2, CoinSelectionStrategy::LargestFirst),
+			1,
+		)?;
+		assert_eq!(est.amount, reward * 3);
+		assert_eq!(est.fee, tx_fee(3, 2, 1, None));
+
+		let est = api.init_send_tx(
+			m,
+			&estimate_args(reward / 2, CoinSelectionStrategy::OldestFirst),
+			1,
+		)?;
+		assert_eq!(est.amount, reward);
+
+		// Every coinbase output comes from its own transaction, privacy mode has to mix
+		// as few of them as possible
+		let est = api.init_send_tx(
+			m,
+			&estimate_args(reward / 2, CoinSelectionStrategy::Privacy),
+			1,
+		)?;
+		assert_eq!(est.amount, reward);
+		let est = api.init_send_tx(
+			m,
+			&estimate_args(reward * 2, CoinSelectionStrategy::Privacy),
+			1,
+		)?;
+		assert_eq!(est.amount, reward * 3);
+		Ok(())
+	})?;
+
+	// Send with exact match, sender doesn't get any change back
+	let mut slate = Slate::blank(2, false);
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward - tx_fee(1, 1, 1, None),
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy: Some(CoinSelectionStrategy::BranchAndBound),
+			..Default::default()
+		};
+		slate = api.init_send_tx(m, &args, 1)?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (refreshed, txs) = api.retrieve_txs(m, true, None, Some(slate.id))?;
+		assert!(refreshed);
+		let tx = txs[0].clone();
+		assert!(tx.confirmed);
+		assert_eq!(tx.num_inputs, 1);
+		assert_eq!(tx.num_outputs, 0);
+		assert_eq!(tx.amount_credited, 0);
+		assert_eq!(tx.amount_debited, reward);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn coin_selection() {
+	let test_dir = "test_output/coin_selection";
+	setup(test_dir);
+	if let Err(e) = coin_selection_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -22,7 +22,7 @@ use grin_wallet_util::grin_util as util;
 
 use self::core::global;
 use self::keychain::ExtKeychain;
-use self::libwallet::WalletInst;
+use self::libwallet::{NodeClient, WalletInst};
 use impls::test_framework::{LocalWalletClient, WalletProxy};
 use impls::{DefaultLCProvider, DefaultWalletImpl};
 use std::fs;
@@ -90,35 +90,27 @@ pub fn create_wallet_proxy(
 	WalletProxy::new(test_dir)
 }
 
-pub fn create_local_wallet(
+pub fn create_local_wallet<C>(
 	test_dir: &str,
 	name: &str,
 	mnemonic: Option<ZeroingString>,
-	client: LocalWalletClient,
+	client: C,
 	create_mask: bool,
 ) -> (
 	Arc<
 		Mutex<
 			Box<
-				dyn WalletInst<
-					'static,
-					DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
-					LocalWalletClient,
-					ExtKeychain,
-				>,
+				dyn WalletInst<'static, DefaultLCProvider<'static, C, ExtKeychain>, C, ExtKeychain>,
 			>,
 		>,
 	>,
 	Option<SecretKey>,
-) {
-	let mut wallet = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(client).unwrap())
-		as Box<
-			dyn WalletInst<
-				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
-				LocalWalletClient,
-				ExtKeychain,
-			>,
-		>;
+)
+where
+	C: NodeClient + 'static,
+{
+	let mut wallet = Box::new(DefaultWalletImpl::<C>::new(client).unwrap())
+		as Box<dyn WalletInst<DefaultLCProvider<'static, C, ExtKeychain>, C, ExtKeychain>>;
 	let lc = wallet.lc_provider().unwrap();
 	let _ = lc.set_top_level_directory(&format!("{}/{}", test_dir, name));
 	lc.create_wallet(None, mnemonic, 32, ZeroingString::from(""), false, None)
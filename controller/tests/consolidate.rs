@@ -0,0 +1,213 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test consolidation and splitting of the outputs
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::core::libtx::tx_fee;
+use self::libwallet::{ConsolidateArgs, OutputStatus, SplitArgs};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn consolidate_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let args = ConsolidateArgs {
+		minimum_confirmations: 2,
+		num_outputs: Some(5),
+		max_inputs: 3,
+		..Default::default()
+	};
+
+	// 5 outputs are merged by 2 transactions
+	let mut planned = vec![];
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+
+		planned = api.consolidate_outputs(m, &args, true)?;
+		assert_eq!(planned.len(), 2);
+		assert_eq!(planned[0].inputs.len(), 3);
+		assert_eq!(planned[1].inputs.len(), 2);
+		assert_eq!(planned[0].fee, tx_fee(3, 1, 1, None));
+		assert_eq!(planned[1].fee, tx_fee(2, 1, 1, None));
+		for tx in &planned {
+			assert!(tx.tx_slate_id.is_none());
+			assert_eq!(tx.outputs.len(), 1);
+			assert_eq!(
+				tx.outputs[0] + tx.fee,
+				tx.inputs.iter().map(|o| o.value).sum::<u64>()
+			);
+		}
+
+		// Preview doesn't change anything
+		let (_, info2) = api.retrieve_summary_info(m, true, 2)?;
+		assert_eq!(
+			info.amount_currently_spendable,
+			info2.amount_currently_spendable
+		);
+		assert_eq!(info2.amount_locked, 0);
+
+		// Nothing to merge
+		assert!(api
+			.consolidate_outputs(
+				m,
+				&ConsolidateArgs {
+					num_outputs: Some(1),
+					..args.clone()
+				},
+				true
+			)
+			.is_err());
+
+		let txs = api.consolidate_outputs(m, &args, false)?;
+		assert_eq!(txs.len(), 2);
+		for (tx, plan) in txs.iter().zip(planned.iter()) {
+			assert!(tx.tx_slate_id.is_some());
+			assert_eq!(tx.fee, plan.fee);
+			assert_eq!(tx.outputs, plan.outputs);
+		}
+		planned = txs;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, None)?;
+		for tx in &planned {
+			// Inputs are spent, the merged output is confirmed
+			for input in &tx.inputs {
+				assert!(outputs
+					.iter()
+					.all(|o| o.output.commit != input.commit
+						|| o.output.status == OutputStatus::Spent));
+			}
+			assert!(outputs.iter().any(
+				|o| o.output.value == tx.outputs[0] && o.output.status == OutputStatus::Unspent
+			));
+
+			let (_, txs) = api.retrieve_txs(m, true, None, tx.tx_slate_id)?;
+			assert!(txs.iter().all(|t| t.confirmed));
+		}
+		Ok(())
+	})?;
+
+	// Split a coinbase into 2 outputs and change
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, None)?;
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		let output = outputs
+			.iter()
+			.find(|o| {
+				o.output.value == reward
+					&& o.output.eligible_to_spend(info.last_confirmed_height, 2)
+			})
+			.unwrap();
+		let args = SplitArgs {
+			src_acct_name: None,
+			minimum_confirmations: 2,
+			output: output.output.commit.clone().unwrap(),
+			amounts: vec![reward / 4, reward / 8],
+			fluff: false,
+		};
+
+		let plan = api.split_output(m, &args, true)?;
+		assert_eq!(plan.fee, tx_fee(1, 3, 1, None));
+		assert_eq!(
+			plan.outputs,
+			vec![
+				reward / 4,
+				reward / 8,
+				reward - reward / 4 - reward / 8 - plan.fee
+			]
+		);
+
+		// Too much to split
+		assert!(api
+			.split_output(
+				m,
+				&SplitArgs {
+					amounts: vec![reward, 1],
+					..args.clone()
+				},
+				true
+			)
+			.is_err());
+
+		let tx = api.split_output(m, &args, false)?;
+		assert_eq!(tx.fee, plan.fee);
+		assert!(tx.tx_slate_id.is_some());
+		planned = vec![tx];
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, None)?;
+		for amount in &planned[0].outputs {
+			assert!(outputs
+				.iter()
+				.any(|o| o.output.value == *amount && o.output.status == OutputStatus::Unspent));
+		}
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn consolidate() {
+	let test_dir = "test_output/consolidate";
+	setup(test_dir);
+	if let Err(e) = consolidate_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
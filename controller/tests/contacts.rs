@@ -0,0 +1,137 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the address book
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::{owner_contacts, Contact};
+use impls::test_framework::LocalWalletClient;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+const TOR_ADDRESS: &str = "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid";
+
+fn contacts_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let contact = |name: &str, address: &str, method: &str| Contact {
+		name: name.to_owned(),
+		address: address.to_owned(),
+		method: method.to_owned(),
+		proof_address: None,
+		apisecret: None,
+	};
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		// Names can't be mixed up with addresses and file names
+		assert!(owner_contacts::is_contact_name("alice"));
+		assert!(owner_contacts::is_contact_name("bob_2-x"));
+		assert!(!owner_contacts::is_contact_name("2bob"));
+		assert!(!owner_contacts::is_contact_name("bob.tx"));
+		assert!(!owner_contacts::is_contact_name(TOR_ADDRESS));
+		assert!(api
+			.save_contact(m, &contact("alice bob", "http://127.0.0.1:3415", "http"))
+			.is_err());
+
+		// Address must match the method
+		assert!(api
+			.save_contact(m, &contact("alice", "127.0.0.1:3415", "http"))
+			.is_err());
+		assert!(api
+			.save_contact(m, &contact("alice", "http://127.0.0.1:3415", "ftp"))
+			.is_err());
+
+		// Proof address is taken from the Tor address
+		let bob = api.save_contact(m, &contact("bob", TOR_ADDRESS, "http"))?;
+		assert_eq!(bob.proof_address.unwrap().public_key, TOR_ADDRESS);
+		let mut alice = contact("alice", "http://127.0.0.1:3415", "http");
+		alice.apisecret = Some("secret".to_owned());
+		let alice = api.save_contact(m, &alice)?;
+		assert!(alice.proof_address.is_none());
+
+		// Update by name
+		let carol = api.save_contact(m, &contact("carol", "http://127.0.0.1:3416", "http"))?;
+		let carol = api.save_contact(m, &contact(&carol.name, "http://127.0.0.1:3417", "http"))?;
+		assert_eq!(api.get_contact(m, "carol")?.address, carol.address);
+
+		let contacts = api.contacts(m)?;
+		let names: Vec<&str> = contacts.iter().map(|c| c.name.as_str()).collect();
+		assert_eq!(names, vec!["alice", "bob", "carol"]);
+		assert_eq!(contacts[0].apisecret, Some("secret".to_owned()));
+
+		// Transaction addresses are matched regardless of the url form
+		let name = |address: &str| {
+			owner_contacts::contact_for_address(&contacts, address).map(|c| c.name.clone())
+		};
+		assert_eq!(name("http://127.0.0.1:3415/"), Some("alice".to_owned()));
+		assert_eq!(
+			name(&format!("http://{}.onion", TOR_ADDRESS)),
+			Some("bob".to_owned())
+		);
+		assert_eq!(name(TOR_ADDRESS), Some("bob".to_owned()));
+		assert_eq!(name("http://127.0.0.1:3418"), None);
+		assert_eq!(name(""), None);
+
+		api.delete_contact(m, "carol")?;
+		assert!(api.delete_contact(m, "carol").is_err());
+		assert!(api.get_contact(m, "carol").is_err());
+		assert_eq!(api.contacts(m)?.len(), 2);
+		Ok(())
+	})?;
+
+	// let logs catch up
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn contacts() {
+	let test_dir = "test_output/contacts";
+	setup(test_dir);
+	if let Err(e) = contacts_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
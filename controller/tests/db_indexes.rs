@@ -0,0 +1,147 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the wallet DB secondary indexes
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::{InitTxArgs, Slate, TxLogEntryType};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn db_indexes_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.create_account_path(m, "listener")?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	// Self send, both log entries share the slate id
+	let mut slate = Slate::blank(2, false);
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		slate = api.init_send_tx(m, &args, 1)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		wallet::controller::foreign_single_use(wallet1.clone(), mask1_i.clone(), |api| {
+			slate = api.receive_tx(&slate, None, Some("listener"), None)?;
+			Ok(())
+		})?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	{
+		wallet_inst!(wallet1, w);
+
+		let txs = w.get_tx_log_by_slate_id(&slate.id)?;
+		assert_eq!(txs.len(), 2);
+		assert!(txs.iter().any(|t| t.tx_type == TxLogEntryType::TxSent));
+		assert!(txs.iter().any(|t| t.tx_type == TxLogEntryType::TxReceived));
+		assert_ne!(txs[0].parent_key_id, txs[1].parent_key_id);
+		assert!(w.get_tx_log_by_slate_id(&Uuid::new_v4())?.is_empty());
+
+		let excess = slate.tx.kernels()[0].excess;
+		let txs = w.get_tx_log_by_kernel_excess(&excess)?;
+		assert!(txs
+			.iter()
+			.any(|t| t.tx_type == TxLogEntryType::TxSent && t.tx_slate_id == Some(slate.id)));
+
+		// Every output with a commit can be found by it
+		let outputs: Vec<_> = w.iter().collect();
+		for out in &outputs {
+			let commit = out.commit.as_ref().unwrap();
+			let found = w.get_outputs_by_commit(commit)?;
+			assert_eq!(found.len(), 1);
+			assert_eq!(found[0].key_id, out.key_id);
+		}
+		assert!(w.get_outputs_by_commit("08ff")?.is_empty());
+
+		// Updates replace the old index entries
+		let mut tx = w.get_tx_log_by_slate_id(&slate.id)?.remove(0);
+		let new_id = Uuid::new_v4();
+		tx.tx_slate_id = Some(new_id);
+		let out = outputs[0].clone();
+		{
+			let mut batch = w.batch(mask1)?;
+			batch.save_tx_log_entry(tx.clone(), &tx.parent_key_id)?;
+			batch.delete(&out.key_id, &out.mmr_index)?;
+			batch.commit()?;
+		}
+		assert_eq!(w.get_tx_log_by_slate_id(&slate.id)?.len(), 1);
+		assert_eq!(w.get_tx_log_by_slate_id(&new_id)?[0].id, tx.id);
+		assert!(w
+			.get_outputs_by_commit(out.commit.as_ref().unwrap())?
+			.is_empty());
+	}
+
+	// let logs catch up
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn db_indexes() {
+	let test_dir = "test_output/db_indexes";
+	setup(test_dir);
+	if let Err(e) = db_indexes_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
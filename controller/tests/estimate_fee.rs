@@ -0,0 +1,160 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test fee estimation without building a transaction
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::core::libtx::tx_fee;
+use self::libwallet::{CoinSelectionStrategy, InitTxArgs};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn estimate_fee_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		let spendable = info.amount_currently_spendable;
+		// Every spendable output is a coinbase
+		let num_spendable = (spendable / reward) as usize;
+		assert!(num_spendable > 1);
+
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			selection_strategy: Some(CoinSelectionStrategy::Smallest),
+			..Default::default()
+		};
+
+		// The same fee as the transaction would have
+		let estimate = api.estimate_fee(m, &args)?;
+		assert_eq!(estimate.amount, reward / 2);
+		assert_eq!(estimate.inputs.len(), 1);
+		assert_eq!(estimate.fee, tx_fee(1, 2, 1, None));
+		assert_eq!(estimate.weight, tx_fee(1, 2, 1, Some(1)));
+		assert_eq!(estimate.num_change_outputs, 1);
+		assert_eq!(
+			estimate.total,
+			estimate.amount + estimate.fee + estimate.change
+		);
+		let slate = api.init_send_tx(
+			m,
+			&InitTxArgs {
+				estimate_only: Some(true),
+				..args.clone()
+			},
+			1,
+		)?;
+		assert_eq!(estimate.fee, slate.fee);
+
+		// Send max spends every spendable output without change
+		assert_eq!(
+			estimate.max_amount,
+			spendable - tx_fee(num_spendable, 1, 1, None)
+		);
+		let max = api.estimate_fee(
+			m,
+			&InitTxArgs {
+				amount: 0,
+				..args.clone()
+			},
+		)?;
+		assert_eq!(max.amount, estimate.max_amount);
+		assert_eq!(max.inputs.len(), num_spendable);
+		assert_eq!(max.change, 0);
+		assert_eq!(max.num_change_outputs, 0);
+		assert_eq!(max.amount + max.fee, spendable);
+
+		// Too much to send
+		assert!(api
+			.estimate_fee(
+				m,
+				&InitTxArgs {
+					amount: max.amount + 1,
+					..args.clone()
+				},
+			)
+			.is_err());
+		assert!(api
+			.estimate_fee(
+				m,
+				&InitTxArgs {
+					minimum_confirmations: 0,
+					..args.clone()
+				},
+			)
+			.is_err());
+
+		// Nothing is locked and no transactions are created
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		assert_eq!(info.amount_currently_spendable, spendable);
+		assert_eq!(info.amount_locked, 0);
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+		assert!(txs.iter().all(|t| t.tx_slate_id.is_none()));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn estimate_fee() {
+	let test_dir = "test_output/estimate_fee";
+	setup(test_dir);
+	if let Err(e) = estimate_fee_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
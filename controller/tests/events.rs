@@ -0,0 +1,284 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test wallet events reported by the updater thread
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_api as api;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::{
+	EventNotifier, EventSink, InitTxArgs, TxLogEntryType, WalletEvent, WalletEventType,
+};
+use impls::events::JsonLinesEventSink;
+use impls::test_framework::{self, LocalWalletClient};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+// Receiver that is never available
+struct FailingEventSink {
+	attempts: AtomicUsize,
+}
+
+impl EventSink for FailingEventSink {
+	fn notify(&self, _event: &WalletEvent) -> Result<(), libwallet::Error> {
+		self.attempts.fetch_add(1, Ordering::Relaxed);
+		Err(libwallet::ErrorKind::GenericError("receiver is down".to_string()).into())
+	}
+}
+
+fn read_events(file: &str) -> Vec<WalletEvent> {
+	fs::read_to_string(file)
+		.unwrap_or_default()
+		.lines()
+		.filter_map(|l| serde_json::from_str(l).ok())
+		.collect()
+}
+
+// Wait until the updater reports the events that match the filter
+fn wait_events<F>(file: &str, count: usize, filter: F) -> Vec<WalletEvent>
+where
+	F: Fn(&WalletEventType) -> bool,
+{
+	for _ in 0..60 {
+		let events: Vec<WalletEvent> = read_events(file)
+			.into_iter()
+			.filter(|e| filter(&e.event))
+			.collect();
+		if events.len() >= count {
+			return events;
+		}
+		thread::sleep(Duration::from_millis(500));
+	}
+	panic!("Expected wallet events are not reported");
+}
+
+fn events_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	// Update runs in a separate thread, so we can't do the local chain type
+	global::init_global_chain_type(global::ChainTypes::AutomatedTesting);
+
+	let events_file = format!("{}/events.jsonl", test_dir);
+	let sink = JsonLinesEventSink::new(&events_file)?;
+	let owner_api = api::Owner::new(wallet2.clone(), None, None);
+	owner_api.set_event_notifier(Some(EventNotifier::new(Arc::new(sink), 3)));
+	owner_api.start_updater(mask2, Duration::from_secs(1))?;
+
+	// First update is a baseline, only the scan is reported
+	wait_events(&events_file, 1, |e| match e {
+		WalletEventType::ScanFinished { .. } => true,
+		_ => false,
+	});
+
+	let send = |message: &str, finalize: bool| -> Result<(), wallet::Error> {
+		wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+			let args = InitTxArgs {
+				src_acct_name: None,
+				amount: reward / 4,
+				minimum_confirmations: 2,
+				max_outputs: 500,
+				num_change_outputs: 1,
+				selection_strategy_is_use_all: false,
+				message: Some(message.to_owned()),
+				..Default::default()
+			};
+			let mut slate = api.init_send_tx(m, &args, 1)?;
+			slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+			api.tx_lock_outputs(m, &slate, None, 0)?;
+			if finalize {
+				slate = api.finalize_tx(m, &slate)?;
+				api.post_tx(m, &slate.tx, false)?;
+			}
+			Ok(())
+		})
+	};
+
+	// Incoming payment is reported when it is received and when it has 3 confirmations
+	send("paid", true)?;
+	let received = wait_events(&events_file, 1, |e| match e {
+		WalletEventType::TxReceived { .. } => true,
+		_ => false,
+	});
+	let tx_slate_id = match &received[0].event {
+		WalletEventType::TxReceived { tx } => {
+			assert_eq!(tx.tx_type, TxLogEntryType::TxReceived);
+			assert_eq!(tx.amount_credited, reward / 4);
+			tx.tx_slate_id.clone()
+		}
+		_ => unreachable!(),
+	};
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 1, false);
+	thread::sleep(Duration::from_secs(3));
+	let confirmed = |e: &WalletEventType| match e {
+		WalletEventType::TxConfirmed { .. } => true,
+		_ => false,
+	};
+	assert!(read_events(&events_file)
+		.iter()
+		.all(|e| !confirmed(&e.event)));
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	let events = wait_events(&events_file, 1, confirmed);
+	match &events[0].event {
+		WalletEventType::TxConfirmed { tx, confirmations } => {
+			assert_eq!(tx.tx_slate_id, tx_slate_id);
+			assert_eq!(*confirmations, 3);
+		}
+		_ => unreachable!(),
+	}
+
+	// Cancelled payment
+	send("cancel me", false)?;
+	let received = wait_events(&events_file, 2, |e| match e {
+		WalletEventType::TxReceived { .. } => true,
+		_ => false,
+	});
+	let tx_id = match &received[1].event {
+		WalletEventType::TxReceived { tx } => tx.id,
+		_ => unreachable!(),
+	};
+	owner_api.cancel_tx(mask2, Some(tx_id), None)?;
+	let cancelled = wait_events(&events_file, 1, |e| match e {
+		WalletEventType::TxCancelled { .. } => true,
+		_ => false,
+	});
+	match &cancelled[0].event {
+		WalletEventType::TxCancelled { tx } => {
+			assert_eq!(tx.id, tx_id);
+			assert_eq!(tx.tx_type, TxLogEntryType::TxReceivedCancelled);
+		}
+		_ => unreachable!(),
+	}
+
+	// Every event is reported once
+	let events = read_events(&events_file);
+	assert_eq!(events.iter().filter(|e| confirmed(&e.event)).count(), 1);
+	assert_eq!(
+		events
+			.iter()
+			.filter(|e| match e.event {
+				WalletEventType::TxReceived { .. } | WalletEventType::TxCancelled { .. } => true,
+				_ => false,
+			})
+			.count(),
+		3
+	);
+
+	owner_api.stop_updater()?;
+	thread::sleep(Duration::from_secs(2));
+
+	// Payment received while the updater is stopped is reported after the restart.
+	// The first restart can't deliver it, the event is kept until the receiver is back.
+	send("paid while stopped", true)?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 1, false);
+
+	let failing_sink = Arc::new(FailingEventSink {
+		attempts: AtomicUsize::new(0),
+	});
+	let owner_api = api::Owner::new(wallet2.clone(), None, None);
+	owner_api.set_event_notifier(Some(EventNotifier::new(failing_sink.clone(), 3)));
+	owner_api.start_updater(mask2, Duration::from_secs(1))?;
+	for _ in 0..60 {
+		if failing_sink.attempts.load(Ordering::Relaxed) >= 2 {
+			break;
+		}
+		thread::sleep(Duration::from_millis(500));
+	}
+	owner_api.stop_updater()?;
+	thread::sleep(Duration::from_secs(2));
+	assert!(failing_sink.attempts.load(Ordering::Relaxed) >= 2);
+	assert_eq!(read_events(&events_file).len(), events.len());
+
+	let sink = JsonLinesEventSink::new(&events_file)?;
+	let owner_api = api::Owner::new(wallet2.clone(), None, None);
+	owner_api.set_event_notifier(Some(EventNotifier::new(Arc::new(sink), 3)));
+	owner_api.start_updater(mask2, Duration::from_secs(1))?;
+	let received = wait_events(&events_file, 3, |e| match e {
+		WalletEventType::TxReceived { .. } => true,
+		_ => false,
+	});
+	assert_eq!(received.len(), 3);
+	match &received[2].event {
+		WalletEventType::TxReceived { tx } => {
+			assert_eq!(tx.tx_type, TxLogEntryType::TxReceived);
+			assert_eq!(tx.amount_credited, reward / 4);
+			assert_ne!(tx.tx_slate_id, tx_slate_id);
+		}
+		_ => unreachable!(),
+	}
+
+	owner_api.stop_updater()?;
+	thread::sleep(Duration::from_secs(2));
+	Ok(())
+}
+
+#[test]
+fn wallet_events() {
+	let test_dir = "test_output/wallet_events";
+	setup(test_dir);
+	if let Err(e) = events_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
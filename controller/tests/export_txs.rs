@@ -0,0 +1,163 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test transaction log export
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::{
+	InitTxArgs, Slate, TxExportArgs, TxExportFormat, TxExportRecord, TxLogEntryType,
+};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn export_txs_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let mut slate = Slate::blank(2, false);
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		slate = api.init_send_tx(m, &args, 1)?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+
+		// Everything, as JSON
+		let args = TxExportArgs {
+			format: TxExportFormat::Json,
+			..Default::default()
+		};
+		let (_, json) = api.export_txs(m, false, &args)?;
+		let records: Vec<TxExportRecord> = serde_json::from_str(&json).unwrap();
+		assert_eq!(records.len(), txs.len());
+		let sent = records
+			.iter()
+			.find(|r| r.tx_type == TxLogEntryType::TxSent)
+			.unwrap();
+		assert_eq!(sent.account, "default");
+		assert_eq!(sent.tx_slate_id, Some(slate.id.to_string()));
+		assert!(sent.confirmed);
+		assert!(sent.confirmation_ts.is_some());
+		assert_eq!(sent.fee, Some(slate.fee));
+		assert_eq!(
+			sent.amount_debited - sent.amount_credited,
+			reward / 2 + slate.fee
+		);
+		assert!(sent.kernel_excess.is_some());
+		assert_eq!(sent.payment_proof, "none");
+
+		// Height filter, only the coinbase outputs from the first blocks
+		let args = TxExportArgs {
+			start_height: Some(1),
+			end_height: Some(2),
+			..Default::default()
+		};
+		let (_, csv) = api.export_txs(m, false, &args)?;
+		let lines: Vec<&str> = csv.lines().collect();
+		assert_eq!(lines.len(), 3);
+		assert_eq!(lines[0], TxExportRecord::csv_header());
+		assert!(lines[1..].iter().all(|l| l.contains("ConfirmedCoinbase")));
+
+		// Date filter, nothing is created in the future
+		let args = TxExportArgs {
+			start_date: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+			..Default::default()
+		};
+		let (_, csv) = api.export_txs(m, false, &args)?;
+		assert_eq!(csv.lines().count(), 1);
+
+		// Unknown account
+		let args = TxExportArgs {
+			account: Some("unknown".to_owned()),
+			..Default::default()
+		};
+		assert!(api.export_txs(m, false, &args).is_err());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn export_txs() {
+	let test_dir = "test_output/export_txs";
+	setup(test_dir);
+	if let Err(e) = export_txs_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
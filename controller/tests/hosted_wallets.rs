@@ -0,0 +1,181 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test several named wallets hosted by one owner API
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_keychain as keychain;
+use grin_wallet_util::grin_util as util;
+
+use self::keychain::ExtKeychain;
+use self::libwallet::WalletInst;
+use easy_jsonrpc_mw::{Handler, MaybeReply};
+use grin_wallet_api::{Owner, OwnerRpcV3};
+use impls::test_framework::LocalWalletClient;
+use impls::{DefaultLCProvider, DefaultWalletImpl};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use util::{Mutex, ZeroingString};
+use wallet::hosted_wallets::{HostedWallets, WalletInstFactory};
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+type TestLCProvider = DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>;
+
+// Same routing as the owner API V3 handler does
+fn call(
+	hosted: &HostedWallets<TestLCProvider, LocalWalletClient, ExtKeychain>,
+	default_api: &Owner<TestLCProvider, LocalWalletClient, ExtKeychain>,
+	method: &str,
+	params: Value,
+) -> Value {
+	let req = json!({
+		"jsonrpc": "2.0",
+		"method": method,
+		"params": params,
+		"id": 1
+	});
+	let route = hosted.route(&req).unwrap();
+	let reply = match &route {
+		None => OwnerRpcV3::handle_request(default_api, req.clone()),
+		Some((_, api)) => OwnerRpcV3::handle_request(&**api, req.clone()),
+	};
+	let res = match reply {
+		MaybeReply::Reply(r) => r,
+		MaybeReply::DontReply => panic!("No reply for {}", method),
+	};
+	if let Some((name, _)) = &route {
+		hosted.update(name, &req, &res);
+	}
+	res["result"].clone()
+}
+
+fn hosted_wallets_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	let wallet_proxy = create_wallet_proxy(test_dir);
+	let client = LocalWalletClient::new("wallet", wallet_proxy.tx.clone());
+
+	let factory: WalletInstFactory<TestLCProvider, LocalWalletClient, ExtKeychain> = {
+		let client = client.clone();
+		Arc::new(move || {
+			let mut wallet = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(client.clone())?)
+				as Box<dyn WalletInst<TestLCProvider, LocalWalletClient, ExtKeychain>>;
+			wallet.lc_provider()?.set_top_level_directory(test_dir)?;
+			Ok(Arc::new(Mutex::new(wallet)))
+		})
+	};
+
+	let mut default_api = Owner::new(factory()?, None, None);
+	let hosted = HostedWallets::new(Some(factory), None);
+	default_api.set_hosted_wallet_names(hosted.shared_names());
+
+	// Named wallets are created in their own directories
+	for name in &["alice", "bob"] {
+		let res = call(
+			&hosted,
+			&default_api,
+			"create_wallet",
+			json!({"name": name, "mnemonic": null, "mnemonic_length": 32, "password": ""}),
+		);
+		assert_eq!(res["Ok"], Value::Null);
+	}
+	assert!(default_api
+		.create_wallet(Some("../escape"), None, 32, ZeroingString::from(""), None)
+		.is_err());
+	assert!(hosted.names().is_empty());
+
+	// Every opened wallet gets its own token
+	let res = call(
+		&hosted,
+		&default_api,
+		"open_wallet",
+		json!({"name": "alice", "password": ""}),
+	);
+	let alice_token = res["Ok"].as_str().unwrap().to_owned();
+	let res = call(
+		&hosted,
+		&default_api,
+		"open_wallet",
+		json!({"name": "bob", "password": ""}),
+	);
+	let bob_token = res["Ok"].as_str().unwrap().to_owned();
+	assert_ne!(alice_token, bob_token);
+	assert_eq!(hosted.names(), vec!["alice".to_string(), "bob".to_string()]);
+	let res = call(&hosted, &default_api, "list_wallets", json!({}));
+	assert_eq!(res["Ok"], json!(["alice", "bob"]));
+
+	// Wallet doesn't exist, nothing new is hosted
+	let res = call(
+		&hosted,
+		&default_api,
+		"open_wallet",
+		json!({"name": "carol", "password": ""}),
+	);
+	assert!(res.get("Err").is_some());
+	assert_eq!(hosted.names().len(), 2);
+
+	// Calls are routed by the token
+	let res = call(
+		&hosted,
+		&default_api,
+		"create_account_path",
+		json!({"token": alice_token, "label": "savings"}),
+	);
+	assert!(res.get("Ok").is_some());
+	let res = call(&hosted, &default_api, "accounts", json!([alice_token]));
+	assert_eq!(res["Ok"].as_array().unwrap().len(), 2);
+	let res = call(
+		&hosted,
+		&default_api,
+		"accounts",
+		json!({ "token": bob_token }),
+	);
+	assert_eq!(res["Ok"].as_array().unwrap().len(), 1);
+
+	// Default wallet isn't opened, it can't serve the calls
+	let res = call(&hosted, &default_api, "accounts", json!({ "token": null }));
+	assert!(res.get("Err").is_some());
+
+	// Closed wallet isn't hosted any more
+	let res = call(
+		&hosted,
+		&default_api,
+		"close_wallet",
+		json!({ "name": "alice" }),
+	);
+	assert!(res.get("Ok").is_some());
+	assert_eq!(hosted.names(), vec!["bob".to_string()]);
+	let res = call(&hosted, &default_api, "list_wallets", json!([]));
+	assert_eq!(res["Ok"], json!(["bob"]));
+	let res = call(
+		&hosted,
+		&default_api,
+		"accounts",
+		json!({ "token": alice_token }),
+	);
+	assert!(res.get("Err").is_some());
+
+	Ok(())
+}
+
+#[test]
+fn hosted_wallets() {
+	let test_dir = "test_output/hosted_wallets";
+	setup(test_dir);
+	if let Err(e) = hosted_wallets_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
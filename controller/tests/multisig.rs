@@ -0,0 +1,446 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test multisig account funding and spending
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::core::libtx::tx_fee;
+use self::libwallet::{InitTxArgs, MultisigAccount, MultisigStatus, Slate};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn multisig_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3_i,
+		test_dir,
+		"wallet3",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask3 = (&mask3_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let amount = reward / 2;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	// Wallet1 creates the account and pays for the shared output
+	let mut account: Option<MultisigAccount> = None;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		assert!(api.multisig_create(m, &args, 3, 4, None).is_err());
+		assert!(api.multisig_create(m, &args, 1, 1, None).is_err());
+
+		let a = api.multisig_create(m, &args, 3, 2, None)?;
+		assert_eq!(a.status, MultisigStatus::Joining);
+		assert_eq!(a.participants.len(), 1);
+		assert!(a.local.is_none());
+		account = Some(a);
+		Ok(())
+	})?;
+	let mut account = account.unwrap();
+
+	// The account goes around the co-signers until wallet1 finalizes the funding transaction
+	let co_signers = vec![
+		(wallet2.clone(), mask2),
+		(wallet3.clone(), mask3),
+		(wallet1.clone(), mask1),
+	];
+	let mut funding_tx = None;
+	for (wallet, mask) in co_signers.iter().cycle().take(9) {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let (a, tx) = api.multisig_process(m, &account, None)?;
+			account = a;
+			funding_tx = tx;
+			Ok(())
+		})?;
+		if funding_tx.is_some() {
+			break;
+		}
+	}
+	assert_eq!(account.status, MultisigStatus::Funded);
+	assert_eq!(account.participants.len(), 3);
+	let commit = account.commit.clone().unwrap();
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.post_tx(m, funding_tx.as_ref().unwrap(), false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// Funding transaction is a regular send for wallet1
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (refreshed, txs) = api.retrieve_txs(m, true, None, Some(account.funding.id))?;
+		assert!(refreshed);
+		assert!(txs[0].confirmed);
+		assert_eq!(
+			txs[0].amount_debited - txs[0].amount_credited,
+			amount + txs[0].fee.unwrap()
+		);
+		let info = txs[0].multisig.clone().unwrap();
+		assert_eq!(info.account_id, account.id);
+		assert!(info.funding);
+		assert_eq!(info.commit, Some(commit.clone()));
+		Ok(())
+	})?;
+
+	// Other co-signers learn that the account is funded
+	for (wallet, mask) in &co_signers[..2] {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let (a, tx) = api.multisig_process(m, &account, None)?;
+			assert!(tx.is_none());
+			assert_eq!(a.status, MultisigStatus::Funded);
+			let accounts = api.multisig_accounts(m)?;
+			assert_eq!(accounts.len(), 1);
+			assert_eq!(accounts[0].commit, Some(commit.clone()));
+			Ok(())
+		})?;
+	}
+
+	// Wallet2 and wallet3 spend the shared output to wallet2, wallet1 doesn't participate
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		assert!(api.multisig_spend(m, &account.id, &[], None, None).is_err());
+		assert!(api
+			.multisig_spend(m, &account.id, &[0, 2], None, None)
+			.is_err());
+		account = api.multisig_spend(m, &account.id, &[2], None, None)?;
+		assert_eq!(account.status, MultisigStatus::Spending);
+		Ok(())
+	})?;
+	let mut spend_tx = None;
+	for (wallet, mask) in &[(wallet3.clone(), mask3), (wallet2.clone(), mask2)] {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let (a, tx) = api.multisig_process(m, &account, None)?;
+			account = a;
+			spend_tx = tx;
+			Ok(())
+		})?;
+	}
+	assert_eq!(account.status, MultisigStatus::Spent);
+
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		api.post_tx(m, spend_tx.as_ref().unwrap(), false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// Wallet2 got the shared output minus the fee
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(
+			info.amount_currently_spendable,
+			amount - tx_fee(1, 1, 1, None)
+		);
+		let spend = account.spend.clone().unwrap();
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(spend.slate.id))?;
+		assert!(txs[0].confirmed);
+		let info = txs[0].multisig.clone().unwrap();
+		assert_eq!(info.account_id, account.id);
+		assert!(!info.funding);
+		Ok(())
+	})?;
+
+	// Spent account can't be spent again
+	wallet::controller::owner_single_use(Some(wallet3.clone()), mask3, None, |api, m| {
+		assert!(api
+			.multisig_spend(m, &account.id, &[1], None, None)
+			.is_err());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+/// 2-of-2 account, both co-signers pay for the shared output, the spending goes to a third party
+fn multisig_contributions_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3_i,
+		test_dir,
+		"wallet3",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask3 = (&mask3_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let contributions = vec![reward / 2, reward / 4];
+	let amount: u64 = contributions.iter().sum();
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet2.clone(), mask2, 5, false);
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+	let mut account: Option<MultisigAccount> = None;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		assert!(api
+			.multisig_create(m, &args, 2, 2, Some(vec![reward / 2, reward / 2]))
+			.is_err());
+		assert!(api
+			.multisig_create(m, &args, 2, 2, Some(vec![amount]))
+			.is_err());
+		account = Some(api.multisig_create(m, &args, 2, 2, Some(contributions.clone()))?);
+		Ok(())
+	})?;
+	let mut account = account.unwrap();
+
+	// Wallet2 adds its inputs when joining
+	let co_signers = vec![(wallet2.clone(), mask2), (wallet1.clone(), mask1)];
+	let mut funding_tx = None;
+	for (wallet, mask) in co_signers.iter().cycle().take(6) {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let (a, tx) = api.multisig_process(m, &account, Some(&args))?;
+			account = a;
+			funding_tx = tx;
+			Ok(())
+		})?;
+		if funding_tx.is_some() {
+			break;
+		}
+	}
+	assert_eq!(account.status, MultisigStatus::Funded);
+	let funding_tx = funding_tx.unwrap();
+	assert_eq!(funding_tx.kernels().len(), 1);
+	assert_eq!(funding_tx.inputs().len(), 2);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.post_tx(m, &funding_tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// Every co-signer paid its part and the fee for its inputs and change
+	for ((wallet, mask), contribution) in co_signers.iter().rev().zip(contributions.iter()) {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let (_, txs) = api.retrieve_txs(m, true, None, Some(account.funding.id))?;
+			assert_eq!(txs.len(), 1);
+			assert!(txs[0].confirmed);
+			assert_eq!(
+				txs[0].amount_debited - txs[0].amount_credited,
+				contribution + txs[0].fee.unwrap()
+			);
+			assert!(txs[0].multisig.clone().unwrap().funding);
+			Ok(())
+		})?;
+	}
+	// Wallet1 spends a part of the shared output to wallet3, the change is its own
+	let send_amount = reward / 3;
+	let fee = tx_fee(1, 2, 1, None);
+	let change = amount - send_amount - fee;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		assert!(api
+			.multisig_spend(m, &account.id, &[1], Some(send_amount), None)
+			.is_err());
+		assert!(api
+			.multisig_spend(
+				m,
+				&account.id,
+				&[1],
+				Some(amount),
+				Some("wallet3".to_string())
+			)
+			.is_err());
+		account = api.multisig_spend(
+			m,
+			&account.id,
+			&[1],
+			Some(send_amount),
+			Some("wallet3".to_string()),
+		)?;
+		// Wallet2 didn't add its nonce yet
+		assert!(api.multisig_spend_slate(m, &account.id).is_err());
+		Ok(())
+	})?;
+
+	let mut slate: Option<Slate> = None;
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (a, tx) = api.multisig_process(m, &account, None)?;
+		assert!(tx.is_none());
+		account = a;
+		slate = Some(api.multisig_spend_slate(m, &account.id)?);
+		Ok(())
+	})?;
+
+	// Wallet3 receives the regular send slate
+	let mut slate = slate.unwrap();
+	wallet::controller::foreign_single_use(wallet3.clone(), mask3_i.clone(), |api| {
+		slate = api.receive_tx(&slate, None, None, None)?;
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (a, tx) = api.multisig_spend_response(m, &slate)?;
+		assert!(tx.is_none());
+		account = a;
+		Ok(())
+	})?;
+	let mut spend_tx = None;
+	for (wallet, mask) in &co_signers {
+		wallet::controller::owner_single_use(Some(wallet.clone()), *mask, None, |api, m| {
+			let (a, tx) = api.multisig_process(m, &account, None)?;
+			account = a;
+			spend_tx = tx;
+			Ok(())
+		})?;
+	}
+	assert_eq!(account.status, MultisigStatus::Spent);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.post_tx(m, spend_tx.as_ref().unwrap(), false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet2.clone(), mask2, 3, false);
+
+	wallet::controller::owner_single_use(Some(wallet3.clone()), mask3, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_currently_spendable, send_amount);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let spend = account.spend.clone().unwrap();
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(spend.slate.id))?;
+		assert_eq!(txs.len(), 1);
+		assert!(txs[0].confirmed);
+		assert_eq!(txs[0].amount_credited, change);
+		assert!(!txs[0].multisig.clone().unwrap().funding);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn multisig() {
+	let test_dir = "test_output/multisig";
+	setup(test_dir);
+	if let Err(e) = multisig_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
+
+#[test]
+fn multisig_contributions() {
+	let test_dir = "test_output/multisig_contributions";
+	setup(test_dir);
+	if let Err(e) = multisig_contributions_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,135 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test output labels, notes and frozen outputs
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_util as util;
+
+use self::core::global;
+use self::libwallet::{InitTxArgs, OutputStatus};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_metadata_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, None)?;
+		let commit = outputs
+			.iter()
+			.find(|o| o.output.status == OutputStatus::Unspent)
+			.map(|o| util::to_hex(&o.commit.0))
+			.unwrap();
+
+		// Unknown outputs are rejected
+		assert!(api
+			.set_output_label(m, "08aaaa", Some("label".to_string()))
+			.is_err());
+
+		api.set_output_label(m, &commit, Some("savings".to_string()))?;
+		api.set_output_note(m, &commit, Some("mined".to_string()))?;
+
+		let (_, outputs) = api.retrieve_outputs(m, false, false, None)?;
+		let out = outputs
+			.iter()
+			.find(|o| util::to_hex(&o.commit.0) == commit)
+			.unwrap();
+		let metadata = out.metadata.clone().unwrap();
+		assert_eq!(metadata.label, Some("savings".to_string()));
+		assert_eq!(metadata.note, Some("mined".to_string()));
+		assert!(!metadata.frozen);
+
+		// Frozen output is not selected, even if asked explicitly
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		api.set_output_frozen(m, &commit, true)?;
+
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			estimate_only: Some(true),
+			..Default::default()
+		};
+		let est = api.init_send_tx(m, &args, 1)?;
+		assert_eq!(est.amount, info.amount_currently_spendable - reward);
+
+		let args = InitTxArgs {
+			outputs: Some(vec![commit.clone()]),
+			..args
+		};
+		assert!(api.init_send_tx(m, &args, 1).is_err());
+
+		api.set_output_frozen(m, &commit, false)?;
+		let est = api.init_send_tx(m, &args, 1)?;
+		assert_eq!(est.amount, reward);
+
+		// Nothing is left, the metadata is gone
+		api.set_output_label(m, &commit, None)?;
+		api.set_output_note(m, &commit, None)?;
+		let (_, outputs) = api.retrieve_outputs(m, false, false, None)?;
+		assert!(outputs.iter().all(|o| o.metadata.is_none()));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn output_metadata() {
+	let test_dir = "test_output/output_metadata";
+	setup(test_dir);
+	if let Err(e) = output_metadata_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,246 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test payment requests, paid directly and with invoices
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::{
+	InitTxArgs, IssueInvoiceTxArgs, PaymentRequestArgs, PaymentRequestStatus, Slate,
+};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn payment_requests_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let request_args = |expiry_blocks: Option<u64>, require_proof: bool| PaymentRequestArgs {
+		amount: reward / 2,
+		address: "wallet2".to_owned(),
+		memo: Some("Order 42 & more".to_owned()),
+		expiry_blocks,
+		expiry_time: None,
+		require_proof,
+	};
+
+	// Open request, request with a proof, request that expires and a cancelled one
+	let mut requests = vec![];
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let mut args = request_args(None, false);
+		args.amount = 0;
+		assert!(api.create_payment_request(m, &args).is_err());
+
+		requests.push(api.create_payment_request(m, &request_args(Some(100), false))?);
+		requests.push(api.create_payment_request(m, &request_args(None, true))?);
+		requests.push(api.create_payment_request(m, &request_args(Some(0), false))?);
+		requests.push(api.create_payment_request(m, &request_args(None, false))?);
+		assert!(requests[0].expiry_height.is_some());
+		assert!(requests[1].proof_address.is_some());
+
+		let r = api.cancel_payment_request(m, &requests[3].id)?;
+		assert_eq!(r.status, PaymentRequestStatus::Cancelled);
+		assert!(api.cancel_payment_request(m, &requests[3].id).is_err());
+
+		// The URI has everything the payer needs
+		let uri = api.payment_request_uri(m, &requests[0].id)?;
+		assert!(uri.starts_with("mwc:wallet2?"));
+		let decoded = api.decode_payment_request(&uri)?;
+		assert_eq!(decoded.id, requests[0].id);
+		assert_eq!(decoded.amount, requests[0].amount);
+		assert_eq!(decoded.address, requests[0].address);
+		assert_eq!(decoded.memo, requests[0].memo);
+		assert_eq!(decoded.expiry_height, requests[0].expiry_height);
+		let uri = api.payment_request_uri(m, &requests[1].id)?;
+		let decoded = api.decode_payment_request(&uri)?;
+		assert_eq!(
+			decoded.proof_address.unwrap().public_key,
+			requests[1].proof_address.clone().unwrap().public_key
+		);
+		assert!(api.decode_payment_request("mwc:wallet2?amount=1").is_err());
+		assert!(api.decode_payment_request("bitcoin:wallet2").is_err());
+		Ok(())
+	})?;
+	let (open, with_proof, expiring, cancelled) =
+		(&requests[0], &requests[1], &requests[2], &requests[3]);
+
+	// Wallet 1 pays the request, the request id is the slate id
+	let pay = |id: &Uuid| -> Result<(), wallet::Error> {
+		wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+			let args = InitTxArgs {
+				src_acct_name: None,
+				amount: reward / 2,
+				minimum_confirmations: 2,
+				max_outputs: 500,
+				num_change_outputs: 1,
+				selection_strategy_is_use_all: true,
+				payment_request_id: Some(id.clone()),
+				..Default::default()
+			};
+			let mut slate = api.init_send_tx(m, &args, 1)?;
+			assert_eq!(&slate.id, id);
+			slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+			api.tx_lock_outputs(m, &slate, None, 0)?;
+			slate = api.finalize_tx(m, &slate)?;
+			api.post_tx(m, &slate.tx, false)?;
+			Ok(())
+		})
+	};
+
+	pay(&open.id)?;
+	// Can't be paid twice
+	assert!(pay(&open.id).is_err());
+	// Payee rejects the cancelled request and the payment without a proof
+	assert!(pay(&cancelled.id).is_err());
+	assert!(pay(&with_proof.id).is_err());
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	let status = |id: &Uuid| -> Result<PaymentRequestStatus, wallet::Error> {
+		let mut status = None;
+		wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+			api.retrieve_summary_info(m, true, 1)?;
+			status = api
+				.payment_requests(m)?
+				.into_iter()
+				.find(|r| &r.id == id)
+				.map(|r| r.status);
+			Ok(())
+		})?;
+		Ok(status.unwrap())
+	};
+
+	// Paid after the confirmation, expired by height
+	assert_eq!(status(&open.id)?, PaymentRequestStatus::Paid);
+	assert_eq!(status(&with_proof.id)?, PaymentRequestStatus::Open);
+	assert_eq!(status(&expiring.id)?, PaymentRequestStatus::Expired);
+	assert_eq!(status(&cancelled.id)?, PaymentRequestStatus::Cancelled);
+	assert!(pay(&expiring.id).is_err());
+
+	// Invoice for the request takes the amount and the memo from it
+	let mut slate = Slate::blank(2, false);
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let mut args = IssueInvoiceTxArgs {
+			amount: reward,
+			payment_request_id: Some(with_proof.id.clone()),
+			..Default::default()
+		};
+		assert!(api.issue_invoice_tx(m, &args).is_err());
+		args.payment_request_id = Some(cancelled.id.clone());
+		args.amount = 0;
+		assert!(api.issue_invoice_tx(m, &args).is_err());
+
+		args.payment_request_id = Some(with_proof.id.clone());
+		slate = api.issue_invoice_tx(m, &args)?;
+		assert_eq!(slate.id, with_proof.id);
+		assert_eq!(slate.amount, with_proof.amount);
+		assert!(api.issue_invoice_tx(m, &args).is_err());
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: slate.amount,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			..Default::default()
+		};
+		slate = api.process_invoice_tx(m, &slate, &args)?;
+		api.tx_lock_outputs(m, &slate, None, 1)?;
+		Ok(())
+	})?;
+
+	wallet::controller::foreign_single_use(wallet2.clone(), mask2_i.clone(), |api| {
+		slate = api.finalize_invoice_tx(&slate)?;
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	assert_eq!(status(&with_proof.id)?, PaymentRequestStatus::Paid);
+
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_currently_spendable, reward);
+		Ok(())
+	})?;
+
+	// let logs catch up
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn payment_requests() {
+	let test_dir = "test_output/payment_requests";
+	setup(test_dir);
+	if let Err(e) = payment_requests_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
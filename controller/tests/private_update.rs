@@ -0,0 +1,176 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test of the private update mode, the wallet commits are not sent to the node
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_keychain as keychain;
+
+use self::core::global;
+use self::keychain::ExtKeychain;
+use self::libwallet::{InitTxArgs, OutputStatus, ScanConfig};
+use impls::test_framework::{self, CountingNodeClient, LocalWalletClient, WalletProxy};
+use impls::DefaultLCProvider;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, setup};
+
+fn private_update_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+
+	let mut wallet_proxy: WalletProxy<
+		DefaultLCProvider<CountingNodeClient<LocalWalletClient>, ExtKeychain>,
+		CountingNodeClient<LocalWalletClient>,
+		ExtKeychain,
+	> = WalletProxy::new(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	// Node calls are counted to check that the wallet commits and kernels are never sent
+	let client1 = LocalWalletClient::new("wallet1", wallet_proxy.tx.clone());
+	let node_client1 = CountingNodeClient::new(client1.clone());
+	let (wallet1, mask1_i) =
+		common::create_local_wallet(test_dir, "wallet1", None, node_client1.clone(), false);
+	wallet_proxy.add_wallet(
+		"wallet1",
+		client1.get_send_instance(),
+		wallet1.clone(),
+		mask1_i.clone(),
+	);
+	let mask1 = (&mask1_i).as_ref();
+	let client2 = LocalWalletClient::new("wallet2", wallet_proxy.tx.clone());
+	let node_client2 = CountingNodeClient::new(client2.clone());
+	let (wallet2, mask2_i) =
+		common::create_local_wallet(test_dir, "wallet2", None, node_client2.clone(), false);
+	wallet_proxy.add_wallet(
+		"wallet2",
+		client2.get_send_instance(),
+		wallet2.clone(),
+		mask2_i.clone(),
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	for wallet in &[wallet1.clone(), wallet2.clone()] {
+		wallet_inst!(wallet, w);
+		w.set_scan_config(ScanConfig {
+			private_update: true,
+			..ScanConfig::default()
+		});
+	}
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let mut bh = 10u64;
+	let _ =
+		test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, bh as usize, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward * 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			..Default::default()
+		};
+		let slate_i = api.init_send_tx(m, &args, 1)?;
+		let slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		let slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		bh += 1;
+		Ok(())
+	})?;
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	bh += 3;
+
+	// Full refresh validates the outputs and kernels by the downloaded chain data
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.scan(m, Some(1), false)?;
+		let (_, wallet1_info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(wallet1_info.total, bh * reward - reward * 2);
+
+		// Inputs of the send are gone from the UTXO set
+		let (_, outputs) = api.retrieve_outputs(m, true, false, None)?;
+		assert!(outputs
+			.iter()
+			.any(|o| o.output.status == OutputStatus::Spent));
+		assert!(outputs
+			.iter()
+			.all(|o| o.output.status != OutputStatus::Locked));
+
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert!(txs.iter().all(|tx| tx.confirmed));
+		Ok(())
+	})?;
+	assert_eq!(node_client1.calls("get_outputs_from_node"), 0);
+	assert_eq!(node_client1.calls("get_kernel"), 0);
+	assert!(node_client1.calls("get_outputs_by_pmmr_index") > 0);
+	assert!(node_client1.calls("get_blocks_by_height") > 0);
+
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		api.scan(m, Some(1), false)?;
+		let (_, wallet2_info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(wallet2_info.total, reward * 2);
+
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.len(), 1);
+		assert!(txs[0].confirmed);
+		Ok(())
+	})?;
+	assert_eq!(node_client2.calls("get_outputs_from_node"), 0);
+	assert_eq!(node_client2.calls("get_kernel"), 0);
+
+	// Regular update after more blocks
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	bh += 3;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (wallet1_refreshed, wallet1_info) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(wallet1_refreshed);
+		assert_eq!(wallet1_info.last_confirmed_height, bh);
+		assert_eq!(wallet1_info.total, bh * reward - reward * 2);
+		Ok(())
+	})?;
+	assert_eq!(node_client1.calls("get_outputs_from_node"), 0);
+	assert_eq!(node_client1.calls("get_kernel"), 0);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn private_update() {
+	let test_dir = "test_output/private_update";
+	setup(test_dir);
+	if let Err(e) = private_update_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
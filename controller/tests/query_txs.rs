@@ -0,0 +1,212 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test transaction log queries
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::{
+	InitTxArgs, Slate, TxLogEntry, TxLogEntryType, TxQueryArgs, TxQuerySortField, TxQuerySortOrder,
+};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn query_txs_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	// One confirmed send to wallet2 and one cancelled send
+	let mut sent = Slate::blank(2, false);
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		sent = api.init_send_tx(m, &args, 1)?;
+		sent = client1.send_tx_slate_direct("wallet2", &sent)?;
+		api.tx_lock_outputs(m, &sent, Some("wallet2".to_owned()), 0)?;
+		sent = api.finalize_tx(m, &sent)?;
+		api.post_tx(m, &sent.tx, false)?;
+
+		let slate = api.init_send_tx(m, &args, 1)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		api.cancel_tx(m, None, Some(slate.id))?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, all) = api.retrieve_txs(m, true, None, None)?;
+		let ids = |txs: &Vec<TxLogEntry>| txs.iter().map(|t| t.id).collect::<Vec<u32>>();
+		let query = |q: TxQueryArgs| -> Result<Vec<TxLogEntry>, wallet::Error> {
+			Ok(api.query_txs(m, false, &q)?.1)
+		};
+
+		// Empty query returns the same as retrieve_txs
+		assert_eq!(ids(&query(TxQueryArgs::default())?), ids(&all));
+
+		// Type, confirmed and cancelled state
+		let txs = query(TxQueryArgs {
+			tx_types: Some(vec![TxLogEntryType::TxSent]),
+			..Default::default()
+		})?;
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].tx_slate_id, Some(sent.id));
+		let cancelled = query(TxQueryArgs {
+			cancelled: Some(true),
+			..Default::default()
+		})?;
+		assert_eq!(cancelled.len(), 1);
+		assert_eq!(cancelled[0].tx_type, TxLogEntryType::TxSentCancelled);
+		let txs = query(TxQueryArgs {
+			cancelled: Some(false),
+			..Default::default()
+		})?;
+		assert_eq!(txs.len(), all.len() - 1);
+		let txs = query(TxQueryArgs {
+			confirmed: Some(false),
+			..Default::default()
+		})?;
+		assert_eq!(ids(&txs), ids(&cancelled));
+
+		// Counterparty address, case insensitive
+		let txs = query(TxQueryArgs {
+			address: Some("WALLET2".to_owned()),
+			..Default::default()
+		})?;
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].tx_slate_id, Some(sent.id));
+
+		// Amount range excludes the coinbase rewards
+		let txs = query(TxQueryArgs {
+			min_amount: Some(reward / 2),
+			max_amount: Some(reward / 2 + sent.fee),
+			..Default::default()
+		})?;
+		assert!(txs.iter().any(|t| t.tx_slate_id == Some(sent.id)));
+		assert!(txs
+			.iter()
+			.all(|t| t.tx_type != TxLogEntryType::ConfirmedCoinbase));
+
+		// Height range, only the coinbase outputs from the first blocks
+		let txs = query(TxQueryArgs {
+			start_height: Some(1),
+			end_height: Some(2),
+			..Default::default()
+		})?;
+		assert_eq!(txs.len(), 2);
+		assert!(txs
+			.iter()
+			.all(|t| t.tx_type == TxLogEntryType::ConfirmedCoinbase));
+
+		// Date range, nothing is created in the future
+		let txs = query(TxQueryArgs {
+			start_date: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+			..Default::default()
+		})?;
+		assert!(txs.is_empty());
+
+		// Sorting and pagination
+		let txs = query(TxQueryArgs {
+			sort_field: TxQuerySortField::Id,
+			sort_order: TxQuerySortOrder::Desc,
+			offset: Some(1),
+			limit: Some(2),
+			..Default::default()
+		})?;
+		let mut expected = ids(&all);
+		expected.sort();
+		expected.reverse();
+		assert_eq!(ids(&txs), expected[1..3].to_vec());
+		let txs = query(TxQueryArgs {
+			sort_field: TxQuerySortField::Amount,
+			sort_order: TxQuerySortOrder::Desc,
+			..Default::default()
+		})?;
+		assert!(txs
+			.windows(2)
+			.all(|w| TxQueryArgs::amount(&w[0]) >= TxQueryArgs::amount(&w[1])));
+		let txs = query(TxQueryArgs {
+			offset: Some(all.len() as u32),
+			..Default::default()
+		})?;
+		assert!(txs.is_empty());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn query_txs() {
+	let test_dir = "test_output/query_txs";
+	setup(test_dir);
+	if let Err(e) = query_txs_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,251 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test of the chain reorg handling
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::libwallet::api_impl::owner;
+use self::libwallet::{OutputStatus, ScanConfig, StatusMessage};
+use impls::test_framework::{self, LocalWalletClient};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn reorg_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (wallet1_refreshed, wallet1_info) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(wallet1_refreshed);
+		assert_eq!(wallet1_info.last_confirmed_height, 10);
+		assert_eq!(wallet1_info.total, 10 * reward);
+		Ok(())
+	})?;
+
+	// Fork from the height 6 is mined by wallet2, it is longer, so the blocks 7-10 are gone
+	let mut prev = chain.get_header_by_height(6).unwrap();
+	for _ in 0..6 {
+		prev = test_framework::award_fork_block_to_wallet(&chain, &prev, wallet2.clone(), mask2)?;
+	}
+	assert_eq!(chain.head().unwrap().height, 12);
+
+	// Reorg deeper than allowed is not reverted, the update is skipped
+	{
+		wallet_inst!(wallet1, w);
+		w.set_scan_config(ScanConfig {
+			max_reorg_depth: 2,
+			..ScanConfig::default()
+		});
+	}
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (tx, rx) = channel();
+		assert!(!owner::update_wallet_state(
+			api.wallet_inst.clone(),
+			m,
+			&Some(tx)
+		)?);
+		let messages: Vec<StatusMessage> = rx.try_iter().collect();
+		assert!(messages
+			.iter()
+			.any(|m| matches!(m, StatusMessage::Warning(_))));
+		assert!(!messages
+			.iter()
+			.any(|m| matches!(m, StatusMessage::Reorg(_, _))));
+
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.iter().filter(|tx| tx.confirmed).count(), 10);
+		Ok(())
+	})?;
+
+	// Confirmations above the fork point are reverted
+	{
+		wallet_inst!(wallet1, w);
+		w.set_scan_config(ScanConfig {
+			max_reorg_depth: 100,
+			..ScanConfig::default()
+		});
+	}
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (tx, rx) = channel();
+		assert!(owner::update_wallet_state(
+			api.wallet_inst.clone(),
+			m,
+			&Some(tx)
+		)?);
+		let fork_heights: Vec<u64> = rx
+			.try_iter()
+			.filter_map(|m| match m {
+				StatusMessage::Reorg(fork_height, _) => Some(fork_height),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(fork_heights, vec![6]);
+
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.iter().filter(|tx| tx.confirmed).count(), 6);
+		assert!(txs
+			.iter()
+			.filter(|tx| tx.output_height > 6)
+			.all(|tx| !tx.confirmed));
+
+		let (_, outputs) = api.retrieve_outputs(m, true, false, None)?;
+		let unspent: Vec<u64> = outputs
+			.iter()
+			.filter(|o| o.output.status == OutputStatus::Unspent)
+			.map(|o| o.output.height)
+			.collect();
+		assert_eq!(unspent.len(), 6);
+		assert!(unspent.iter().all(|height| *height <= 6));
+
+		let (_, wallet1_info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(wallet1_info.last_confirmed_height, 12);
+		assert_eq!(wallet1_info.total, 6 * reward);
+		assert_eq!(wallet1_info.amount_awaiting_confirmation, 0);
+
+		// Next update has nothing to revert
+		let (tx, rx) = channel();
+		assert!(owner::update_wallet_state(
+			api.wallet_inst.clone(),
+			m,
+			&Some(tx)
+		)?);
+		assert!(!rx
+			.try_iter()
+			.any(|m| matches!(m, StatusMessage::Reorg(_, _))));
+		Ok(())
+	})?;
+
+	// Fork miner has the rewards
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (wallet2_refreshed, wallet2_info) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(wallet2_refreshed);
+		assert_eq!(wallet2_info.last_confirmed_height, 12);
+		assert_eq!(wallet2_info.total, 6 * reward);
+		Ok(())
+	})?;
+
+	// Wallet1 is updated after every block, the fork from the height 18 is between the blocks
+	// 16 and 20 that would be left by the exponential gaps
+	for _ in 0..8 {
+		let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 1, false);
+		wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+			let (wallet1_refreshed, _) = api.retrieve_summary_info(m, true, 1)?;
+			assert!(wallet1_refreshed);
+			Ok(())
+		})?;
+	}
+	let mut prev = chain.get_header_by_height(18).unwrap();
+	for _ in 0..3 {
+		prev = test_framework::award_fork_block_to_wallet(&chain, &prev, wallet2.clone(), mask2)?;
+	}
+	assert_eq!(chain.head().unwrap().height, 21);
+
+	// Reorg of 2 blocks is within the max depth and only the blocks 19 and 20 are reverted
+	{
+		wallet_inst!(wallet1, w);
+		w.set_scan_config(ScanConfig {
+			max_reorg_depth: 2,
+			..ScanConfig::default()
+		});
+	}
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (tx, rx) = channel();
+		assert!(owner::update_wallet_state(
+			api.wallet_inst.clone(),
+			m,
+			&Some(tx)
+		)?);
+		let fork_heights: Vec<u64> = rx
+			.try_iter()
+			.filter_map(|m| match m {
+				StatusMessage::Reorg(fork_height, _) => Some(fork_height),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(fork_heights, vec![18]);
+
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.iter().filter(|tx| tx.confirmed).count(), 12);
+		assert!(txs
+			.iter()
+			.filter(|tx| tx.output_height > 18)
+			.all(|tx| !tx.confirmed));
+
+		let (_, wallet1_info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(wallet1_info.last_confirmed_height, 21);
+		assert_eq!(wallet1_info.total, 12 * reward);
+		assert_eq!(wallet1_info.amount_awaiting_confirmation, 0);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn reorg() {
+	let test_dir = "test_output/reorg";
+	setup(test_dir);
+	if let Err(e) = reorg_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,202 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test resuming of the interrupted scan
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_util as util;
+
+use self::core::global;
+use self::libwallet::internal::scan;
+use self::libwallet::{NodeClient, OutputCommitMapping, ScanCheckpoint};
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+use util::ZeroingString;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn scan_resume_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let seed_phrase = "affair pistol cancel crush garment candy ancient flag work \
+	                   market crush dry stand focus mutual weapon offer ceiling rival turn team spring \
+	                   where swift";
+	let seed_phrase = Some(ZeroingString::from(seed_phrase));
+
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	// recovered from the seed, the scan is interrupted and resumed here
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+	// recovered from the seed, the uninterrupted scan is done here
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3_i,
+		test_dir,
+		"wallet3",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask3 = (&mask3_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	// Reference result of the uninterrupted scan
+	let mut expected: Vec<OutputCommitMapping> = vec![];
+	wallet::controller::owner_single_use(Some(wallet3.clone()), mask3, None, |api, m| {
+		api.scan(m, None, false)?;
+		expected = api.retrieve_outputs(m, true, false, None)?.1;
+		Ok(())
+	})?;
+	assert_eq!(expected.len(), 10);
+
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		// Nothing to resume
+		assert!(api.resume_scan(m).is_err());
+
+		// Emulate the scan that was interrupted in the middle of the PMMR range
+		let mut w_lock = api.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		let client = w.w2n_client().clone();
+		let (tip_height, tip_hash, _) = client.get_chain_tip()?;
+		let (start_index, end_index) = client.height_range_to_pmmr_indices(1, Some(tip_height))?;
+		let mid_index = (start_index + end_index) / 2;
+		let keychain = w.keychain(m)?;
+		let found = scan::collect_chain_outputs(
+			&keychain,
+			client,
+			start_index,
+			Some(mid_index),
+			w.scan_config().threads,
+			&None,
+			false,
+		)?;
+		assert!(!found.is_empty());
+		assert!(found.len() < expected.len());
+
+		let mut checkpoint = ScanCheckpoint::new(1, tip_height, tip_hash, false);
+		checkpoint.next_pmmr_index = Some(mid_index + 1);
+		let mut batch = w.batch(m)?;
+		for output in &found {
+			batch.save_scan_checkpoint_output(output)?;
+		}
+		batch.save_scan_checkpoint(&checkpoint)?;
+		batch.commit()?;
+		assert_eq!(w.get_scan_checkpoint()?, Some(checkpoint));
+		assert_eq!(w.scan_checkpoint_outputs()?.len(), found.len());
+		Ok(())
+	})?;
+
+	// Chain is the same, so the scan is resumed
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		api.resume_scan(m)?;
+		let outputs = api.retrieve_outputs(m, true, false, None)?.1;
+		let summary = |outputs: &Vec<OutputCommitMapping>| {
+			let mut res: Vec<(String, u64, u64, String)> = outputs
+				.iter()
+				.map(|o| {
+					(
+						util::to_hex(&o.commit.0),
+						o.output.value,
+						o.output.height,
+						o.output.status.to_string(),
+					)
+				})
+				.collect();
+			res.sort();
+			res
+		};
+		assert_eq!(summary(&outputs), summary(&expected));
+
+		// Checkpoint is gone with the finished scan
+		let mut w_lock = api.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		assert!(w.get_scan_checkpoint()?.is_none());
+		assert!(w.scan_checkpoint_outputs()?.is_empty());
+		Ok(())
+	})?;
+
+	// Interrupted scan of the chain that was changed can't be resumed
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		{
+			let mut w_lock = api.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let (tip_height, _, _) = w.w2n_client().get_chain_tip()?;
+			let checkpoint = ScanCheckpoint::new(1, tip_height, "0000".to_owned(), false);
+			let mut batch = w.batch(m)?;
+			batch.save_scan_checkpoint(&checkpoint)?;
+			batch.commit()?;
+		}
+		assert!(api.resume_scan(m).is_err());
+
+		// New scan replaces the checkpoint and deletes it when it is done
+		api.scan(m, None, false)?;
+		let mut w_lock = api.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		assert!(w.get_scan_checkpoint()?.is_none());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn scan_resume() {
+	let test_dir = "test_output/scan_resume";
+	setup(test_dir);
+	if let Err(e) = scan_resume_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
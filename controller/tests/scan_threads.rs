@@ -0,0 +1,180 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test of the parallel UTXO scanning
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_util as util;
+
+use self::core::global;
+use self::libwallet::internal::scan;
+use self::libwallet::{NodeClient, OutputCommitMapping, ScanConfig, StatusMessage};
+use impls::test_framework::{self, LocalWalletClient};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+use util::ZeroingString;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn scan_threads_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let seed_phrase = "affair pistol cancel crush garment candy ancient flag work \
+	                   market crush dry stand focus mutual weapon offer ceiling rival turn team spring \
+	                   where swift";
+	let seed_phrase = Some(ZeroingString::from(seed_phrase));
+
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	// recovered from the seed with the single scanning thread
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+	// recovered from the seed with many scanning threads
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3_i,
+		test_dir,
+		"wallet3",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask3 = (&mask3_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 30, false);
+
+	let summary = |outputs: &Vec<OutputCommitMapping>| {
+		let mut res: Vec<(String, u64, u64, String)> = outputs
+			.iter()
+			.map(|o| {
+				(
+					util::to_hex(&o.commit.0),
+					o.output.value,
+					o.output.height,
+					o.output.status.to_string(),
+				)
+			})
+			.collect();
+		res.sort();
+		res
+	};
+
+	{
+		wallet_inst!(wallet2, w);
+		w.set_scan_config(ScanConfig {
+			threads: 1,
+			..ScanConfig::default()
+		});
+	}
+	let mut expected: Vec<OutputCommitMapping> = vec![];
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		api.scan(m, None, false)?;
+		expected = api.retrieve_outputs(m, true, false, None)?.1;
+		Ok(())
+	})?;
+	assert_eq!(expected.len(), 30);
+
+	{
+		wallet_inst!(wallet3, w);
+		w.set_scan_config(ScanConfig {
+			threads: 8,
+			..ScanConfig::default()
+		});
+	}
+	wallet::controller::owner_single_use(Some(wallet3.clone()), mask3, None, |api, m| {
+		api.scan(m, None, false)?;
+		let outputs = api.retrieve_outputs(m, true, false, None)?.1;
+		assert_eq!(summary(&outputs), summary(&expected));
+
+		// Results and progress are reported in the PMMR index order
+		let mut w_lock = api.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		let client = w.w2n_client().clone();
+		let (start_index, end_index) = client.height_range_to_pmmr_indices(1, None)?;
+		let keychain = w.keychain(m)?;
+		let (tx, rx) = channel();
+		let found = scan::collect_chain_outputs(
+			&keychain,
+			client,
+			start_index,
+			Some(end_index),
+			8,
+			&Some(tx),
+			false,
+		)?;
+		assert_eq!(found.len(), expected.len());
+		assert!(found.windows(2).all(|o| o[0].mmr_index < o[1].mmr_index));
+
+		let progress: Vec<u8> = rx
+			.try_iter()
+			.filter_map(|m| match m {
+				StatusMessage::Scanning(_, _, perc) => Some(perc),
+				_ => None,
+			})
+			.collect();
+		assert!(progress.len() > 1);
+		assert!(progress.windows(2).all(|p| p[0] <= p[1]));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn scan_threads() {
+	let test_dir = "test_output/scan_threads";
+	setup(test_dir);
+	if let Err(e) = scan_threads_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
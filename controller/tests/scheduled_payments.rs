@@ -0,0 +1,319 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test scheduled and recurring payments
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_api as api;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_keychain as keychain;
+use grin_wallet_util::grin_util as util;
+
+use self::core::global;
+use self::keychain::ExtKeychain;
+use self::libwallet::{
+	owner_schedule, ErrorKind, InitTxArgs, PaymentExecutor, SchedulePaymentArgs,
+	ScheduledPaymentStatus, Slate, TxLogEntryType, WalletInst,
+};
+use chrono::{Duration as ChronoDuration, Utc};
+use impls::test_framework::{self, LocalWalletClient};
+use impls::DefaultLCProvider;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use util::secp::key::SecretKey;
+use util::Mutex;
+use uuid::Uuid;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+// Sends the payments directly to the other wallet of the proxy, fails for unknown wallets.
+// With fail_post the finalized transaction is not posted and the send fails.
+struct TestExecutor {
+	wallet: Arc<
+		Mutex<
+			Box<
+				dyn WalletInst<
+					'static,
+					DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+					LocalWalletClient,
+					ExtKeychain,
+				>,
+			>,
+		>,
+	>,
+	client: LocalWalletClient,
+	fail_post: bool,
+}
+
+impl PaymentExecutor for TestExecutor {
+	fn send(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: &InitTxArgs,
+	) -> Result<Slate, libwallet::Error> {
+		let dest = args.send_args.as_ref().unwrap().dest.clone();
+		if dest != "wallet2" {
+			return Err(ErrorKind::GenericError(format!("Unable to reach {}", dest)).into());
+		}
+		let mut args = args.clone();
+		args.send_args = None;
+
+		let api = api::Owner::new(self.wallet.clone(), None, None);
+		let mut slate = api.init_send_tx(keychain_mask, &args, 1)?;
+		slate = self.client.send_tx_slate_direct(&dest, &slate)?;
+		api.tx_lock_outputs(keychain_mask, &slate, args.address.clone(), 0)?;
+		slate = api.finalize_tx(keychain_mask, &slate)?;
+		if self.fail_post {
+			return Err(ErrorKind::ClientCallback("Node is not responding".to_owned()).into());
+		}
+		api.post_tx(keychain_mask, &slate.tx, false)?;
+		Ok(slate)
+	}
+}
+
+fn scheduled_payments_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let payment_args = |dest: &str, interval_secs: Option<u64>| SchedulePaymentArgs {
+		src_acct_name: None,
+		amount: reward / 10,
+		minimum_confirmations: 2,
+		message: Some("scheduled".to_owned()),
+		method: "mwcmqs".to_owned(),
+		dest: dest.to_owned(),
+		apisecret: None,
+		fluff: false,
+		start_time: None,
+		interval_secs,
+		max_retries: 1,
+	};
+
+	// Recurring payment, one time payment that can't be delivered and a payment in the future
+	let mut payments = vec![];
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let mut args = payment_args("wallet2", None);
+		args.method = "ftp".to_owned();
+		assert!(api.schedule_payment(m, &args).is_err());
+		args = payment_args("wallet2", Some(0));
+		assert!(api.schedule_payment(m, &args).is_err());
+
+		payments.push(api.schedule_payment(m, &payment_args("wallet2", Some(3600)))?);
+		payments.push(api.schedule_payment(m, &payment_args("wallet3", None))?);
+		let mut args = payment_args("wallet2", Some(3600));
+		args.start_time = Some(Utc::now() + ChronoDuration::days(1));
+		payments.push(api.schedule_payment(m, &args)?);
+		assert_eq!(api.scheduled_payments(m)?.len(), 3);
+		Ok(())
+	})?;
+	let (recurring, failing, future) = (&payments[0], &payments[1], &payments[2]);
+
+	let executor = TestExecutor {
+		wallet: wallet1.clone(),
+		client: client1.clone(),
+		fail_post: false,
+	};
+	owner_schedule::run_due_payments(wallet1.clone(), mask1, &executor)?;
+
+	let get = |id: Uuid| -> Result<libwallet::ScheduledPayment, wallet::Error> {
+		let mut payment = None;
+		wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+			payment = api.scheduled_payments(m)?.into_iter().find(|p| p.id == id);
+			Ok(())
+		})?;
+		Ok(payment.unwrap())
+	};
+
+	// Recurring payment is sent and moved to the next interval
+	let p = get(recurring.id)?;
+	assert_eq!(p.status, ScheduledPaymentStatus::Active);
+	assert_eq!(p.history.len(), 1);
+	assert!(p.history[0].tx_slate_id.is_some());
+	assert!(p.history[0].error.is_none());
+	assert_eq!(p.retries, 0);
+	assert!(p.next_run > Utc::now() + ChronoDuration::minutes(59));
+
+	// Failed payment waits for the retry
+	let p = get(failing.id)?;
+	assert_eq!(p.status, ScheduledPaymentStatus::Active);
+	assert_eq!(p.retries, 1);
+	assert!(p.history[0].error.is_some());
+	assert!(p.retry_at.unwrap() > Utc::now());
+
+	// Future payment is not touched
+	let p = get(future.id)?;
+	assert!(p.history.is_empty());
+
+	// Nothing is due now, no more payments are sent
+	owner_schedule::run_due_payments(wallet1.clone(), mask1, &executor)?;
+	assert_eq!(get(recurring.id)?.history.len(), 1);
+	assert_eq!(get(failing.id)?.history.len(), 1);
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (_, wallet2_info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(wallet2_info.amount_currently_spendable, reward / 10);
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+		assert_eq!(txs.len(), 1);
+		Ok(())
+	})?;
+
+	// The last retry fails, one time payment is given up
+	{
+		wallet_inst!(wallet1, w);
+		let p = owner_schedule::record_payment_result(
+			&mut **w,
+			mask1,
+			&failing.id,
+			Utc::now(),
+			Err("Unable to reach wallet3".to_owned()),
+		)?;
+		assert_eq!(p.status, ScheduledPaymentStatus::Failed);
+		assert_eq!(p.retry_at, None);
+		assert_eq!(p.history.len(), 2);
+		assert_eq!(p.history[1].retry, 1);
+	}
+
+	// Pause, resume and delete
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let p = api.set_scheduled_payment_active(m, &recurring.id, false)?;
+		assert_eq!(p.status, ScheduledPaymentStatus::Paused);
+		assert!(p.due_time().is_none());
+		let p = api.set_scheduled_payment_active(m, &recurring.id, true)?;
+		assert_eq!(p.status, ScheduledPaymentStatus::Active);
+		assert!(api
+			.set_scheduled_payment_active(m, &failing.id, true)
+			.is_err());
+
+		api.delete_scheduled_payment(m, &future.id)?;
+		assert!(api.delete_scheduled_payment(m, &future.id).is_err());
+		assert_eq!(api.scheduled_payments(m)?.len(), 2);
+		Ok(())
+	})?;
+
+	// Post fails after the transaction is finalized. The payment is not sent again, the
+	// same transaction is posted and the payment is done when it is confirmed.
+	let mut once = None;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		once = Some(api.schedule_payment(m, &payment_args("wallet2", None))?);
+		Ok(())
+	})?;
+	let once = once.unwrap();
+	let failing_post = TestExecutor {
+		wallet: wallet1.clone(),
+		client: client1.clone(),
+		fail_post: true,
+	};
+	owner_schedule::run_due_payments(wallet1.clone(), mask1, &failing_post)?;
+	let p = get(once.id)?;
+	assert_eq!(p.status, ScheduledPaymentStatus::Active);
+	assert!(p.history.is_empty());
+	assert_eq!(p.retries, 0);
+	let slate_id = p.in_flight.unwrap();
+
+	// The updater refreshes the wallet before the payments are sent
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		api.retrieve_summary_info(m, true, 1)?;
+		Ok(())
+	})?;
+	owner_schedule::run_due_payments(wallet1.clone(), mask1, &executor)?;
+	let p = get(once.id)?;
+	assert_eq!(p.status, ScheduledPaymentStatus::Completed);
+	assert_eq!(p.in_flight, None);
+	assert_eq!(p.history.len(), 1);
+	assert_eq!(p.history[0].tx_slate_id, Some(slate_id));
+	assert!(p.history[0].error.is_none());
+
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+		let sent: Vec<_> = txs
+			.iter()
+			.filter(|t| t.tx_type == TxLogEntryType::TxSent)
+			.collect();
+		assert_eq!(sent.len(), 2);
+		assert!(sent.iter().all(|t| t.confirmed));
+		assert_eq!(
+			sent.iter()
+				.filter(|t| t.tx_slate_id == Some(slate_id))
+				.count(),
+			1
+		);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (_, wallet2_info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(wallet2_info.total, reward / 10 * 2);
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+		assert_eq!(txs.len(), 2);
+		assert!(txs.iter().all(|t| t.confirmed));
+		Ok(())
+	})?;
+
+	// let logs catch up
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn scheduled_payments() {
+	let test_dir = "test_output/scheduled_payments";
+	setup(test_dir);
+	if let Err(e) = scheduled_payments_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
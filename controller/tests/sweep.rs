@@ -0,0 +1,169 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sending everything with the fee deducted from the amount
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+
+use self::core::global;
+use self::core::libtx::tx_fee;
+use self::libwallet::InitTxArgs;
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn sweep_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		sweep: Some(true),
+		..Default::default()
+	};
+
+	// Sweep of the chosen output, amount is ignored
+	let mut swept = 0;
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		let (_, outputs) = api.retrieve_outputs(m, false, false, None)?;
+		let commit = outputs
+			.iter()
+			.find(|o| o.output.eligible_to_spend(info.last_confirmed_height, 2))
+			.and_then(|o| o.output.commit.clone())
+			.unwrap();
+		let args = InitTxArgs {
+			amount: reward * 100,
+			outputs: Some(vec![commit.clone()]),
+			..args.clone()
+		};
+
+		let mut slate = api.init_send_tx(m, &args, 1)?;
+		assert_eq!(slate.amount, reward - tx_fee(1, 1, 1, None));
+		assert_eq!(slate.fee, tx_fee(1, 1, 1, None));
+		assert_eq!(slate.tx.inputs().len(), 1);
+		// No change output
+		assert!(slate.tx.outputs().is_empty());
+
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		swept = slate.amount;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+
+	wallet::controller::owner_single_use(Some(wallet2.clone()), mask2, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_currently_spendable, swept);
+		Ok(())
+	})?;
+
+	// Sweep of the whole account leaves nothing to spend
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		let spendable = info.amount_currently_spendable;
+		let num_inputs = (spendable / reward) as usize;
+
+		// Late lock can't know the amount
+		assert!(api
+			.init_send_tx(
+				m,
+				&InitTxArgs {
+					late_lock: Some(true),
+					..args.clone()
+				},
+				1
+			)
+			.is_err());
+
+		let mut slate = api.init_send_tx(m, &args, 1)?;
+		assert_eq!(slate.tx.inputs().len(), num_inputs);
+		assert_eq!(slate.fee, tx_fee(num_inputs, 1, 1, None));
+		assert_eq!(slate.amount + slate.fee, spendable);
+
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		assert_eq!(slate.tx.outputs().len(), 1);
+		api.post_tx(m, &slate.tx, false)?;
+
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		assert_eq!(info.amount_currently_spendable, 0);
+		assert_eq!(info.amount_locked, spendable);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn sweep() {
+	let test_dir = "test_output/sweep";
+	setup(test_dir);
+	if let Err(e) = sweep_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,225 @@
+// Copyright 2019 The Grin Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test watch-only wallet created from the view key
+#[macro_use]
+extern crate log;
+extern crate grin_wallet_controller as wallet;
+extern crate grin_wallet_impls as impls;
+extern crate grin_wallet_libwallet as libwallet;
+
+use grin_wallet_util::grin_core as core;
+use grin_wallet_util::grin_keychain as keychain;
+use grin_wallet_util::grin_util as util;
+
+use self::core::global;
+use self::keychain::ExtKeychain;
+use self::libwallet::{
+	ErrorKind, InitTxArgs, TxLogEntry, TxLogEntryType, ViewKey, WalletInfo, WalletInst,
+};
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{DefaultLCProvider, DefaultWalletImpl};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use util::{Mutex, ZeroingString};
+
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn watch_only_test_impl(test_dir: &'static str) -> Result<(), wallet::Error> {
+	global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Export the view key of wallet1
+	let mut view_key = String::new();
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		view_key = api.get_view_key(m)?;
+		Ok(())
+	})?;
+	assert_eq!(ViewKey::from_hex(&view_key)?.to_hex(), view_key);
+
+	// Watch-only copy of wallet1
+	let view_client = LocalWalletClient::new("view", wallet_proxy.tx.clone());
+	let mut view_wallet = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(
+		view_client.clone(),
+	)?)
+		as Box<
+			dyn WalletInst<
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>;
+	{
+		let lc = view_wallet.lc_provider()?;
+		lc.set_top_level_directory(&format!("{}/view", test_dir))?;
+		assert!(ViewKey::from_hex("0102").is_err());
+		lc.create_view_wallet(
+			None,
+			&ViewKey::from_hex(&view_key)?,
+			ZeroingString::from(""),
+			None,
+		)?;
+		assert!(lc.wallet_exists(None, None)?);
+		let mask = lc.open_wallet(None, ZeroingString::from(""), true, false, None)?;
+		assert!(mask.is_none());
+	}
+	let view_wallet = Arc::new(Mutex::new(view_wallet));
+	wallet_proxy.add_wallet(
+		"view",
+		view_client.get_send_instance(),
+		view_wallet.clone(),
+		None,
+	);
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		global::set_local_chain_type(global::ChainTypes::AutomatedTesting);
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::MWC_FIRST_GROUP_REWARD;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let check_balances = || -> Result<WalletInfo, wallet::Error> {
+		let mut wallet1_info = None;
+		wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+			let (refreshed, info) = api.retrieve_summary_info(m, true, 1)?;
+			assert!(refreshed);
+			wallet1_info = Some(info);
+			Ok(())
+		})?;
+		let wallet1_info = wallet1_info.unwrap();
+		wallet::controller::owner_single_use(Some(view_wallet.clone()), None, None, |api, m| {
+			let (refreshed, info) = api.retrieve_summary_info(m, true, 1)?;
+			assert!(refreshed);
+			assert_eq!(
+				info.last_confirmed_height,
+				wallet1_info.last_confirmed_height
+			);
+			assert_eq!(info.total, wallet1_info.total);
+			assert_eq!(
+				info.amount_currently_spendable,
+				wallet1_info.amount_currently_spendable
+			);
+			assert_eq!(info.amount_immature, wallet1_info.amount_immature);
+			Ok(())
+		})?;
+		Ok(wallet1_info)
+	};
+
+	// Watch-only wallet finds all mined outputs
+	let info = check_balances()?;
+	assert_eq!(info.total, reward * 5);
+	wallet::controller::owner_single_use(Some(view_wallet.clone()), None, None, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, false, None)?;
+		assert_eq!(outputs.len(), 5);
+		assert_eq!(api.get_view_key(m)?, view_key);
+		Ok(())
+	})?;
+
+	// Spent outputs and change are tracked as well
+	wallet::controller::owner_single_use(Some(wallet1.clone()), mask1, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		let mut slate = api.init_send_tx(m, &args, 1)?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, None, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet2.clone(), mask2, 3, false);
+	check_balances()?;
+
+	// The spend is in the history of the watch-only wallet
+	wallet::controller::owner_single_use(Some(view_wallet.clone()), None, None, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		let sent: Vec<&TxLogEntry> = txs
+			.iter()
+			.filter(|t| t.tx_type == TxLogEntryType::TxSent)
+			.collect();
+		assert_eq!(sent.len(), 1);
+		assert!(sent[0].confirmed);
+		assert_eq!(sent[0].amount_debited, reward);
+		assert_eq!(sent[0].input_commits.len(), 1);
+		Ok(())
+	})?;
+
+	// Watch-only wallet can't spend
+	wallet::controller::owner_single_use(Some(view_wallet.clone()), None, None, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward / 4,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		match api.init_send_tx(m, &args, 1) {
+			Err(e) => assert_eq!(e.kind(), ErrorKind::WatchOnlyWallet),
+			Ok(_) => panic!("Watch-only wallet created a transaction"),
+		}
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn watch_only() {
+	let test_dir = "test_output/watch_only";
+	setup(test_dir);
+	if let Err(e) = watch_only_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
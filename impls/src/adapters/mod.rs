@@ -22,7 +22,8 @@ pub use self::http::HttpDataSender;
 
 use crate::config::{TorConfig, WalletConfig};
 use crate::error::{Error, ErrorKind};
-use crate::libwallet::swap::message::Message;
+use crate::libwallet;
+use crate::libwallet::swap::message::{Message, Update};
 use crate::libwallet::Slate;
 use crate::tor::config::complete_tor_address;
 use crate::util::ZeroingString;
@@ -32,6 +33,8 @@ use grin_wallet_libwallet::{SlateVersion, Slatepacker};
 pub use mwcmq::{
 	get_mwcmqs_brocker, init_mwcmqs_access_data, MWCMQPublisher, MWCMQSubscriber, MwcMqsChannel,
 };
+use std::fs::File;
+use std::io::Write;
 pub use types::{
 	Address, AddressType, CloseReason, HttpsAddress, MWCMQSAddress, Publisher, Subscriber,
 	SubscriptionHandler,
@@ -242,6 +245,64 @@ pub fn create_swap_message_sender(
 	})
 }
 
+/// Deliver the swap message to the other party with `method` (mwcmqs, tor or file) and
+/// return the acknowledge flag and the destination for the trade journal. `from_address` builds
+/// our return address for the offer from the method, it isn't called for the file method.
+/// The file is considered as delivered once the message is written into it.
+pub fn deliver_swap_message<F>(
+	swap_message: Message,
+	method: &str,
+	dest: &str,
+	apisecret: &Option<String>,
+	tor_config: Option<TorConfig>,
+	from_address: F,
+) -> Result<(bool, String), libwallet::Error>
+where
+	F: FnOnce(&str) -> Result<String, libwallet::Error>,
+{
+	let destination_str = format!("{} {}", method, dest);
+	match method {
+		"file" => {
+			let msg_str = swap_message.to_json()?;
+			File::create(dest)
+				.and_then(|mut file| file.write_all(msg_str.as_bytes()))
+				.map_err(|e| {
+					libwallet::ErrorKind::SwapError(format!(
+						"Unable to store message data to the destination file, {}",
+						e
+					))
+				})?;
+			return Ok((true, destination_str));
+		}
+		"mwcmqs" | "tor" => (),
+		_ => {
+			return Err(libwallet::ErrorKind::SwapError(format!(
+				"Unsupported method '{}' for transporting swap messages. Expected mwcmqs, tor or file",
+				method
+			))
+			.into());
+		}
+	}
+
+	let from_address = from_address(method)?;
+	let sender = create_swap_message_sender(method, dest, apisecret, tor_config).map_err(|e| {
+		libwallet::ErrorKind::SwapError(format!("Unable to create message sender, {}", e))
+	})?;
+
+	let mut swap_message = swap_message;
+	if let Update::Offer(offer_update) = &mut swap_message.inner {
+		offer_update.from_address = from_address;
+	}
+
+	let ack = sender.send_swap_message(&swap_message).map_err(|e| {
+		libwallet::ErrorKind::SwapError(format!(
+			"Unable to deliver the message {} by {}: {}",
+			swap_message.id, method, e
+		))
+	})?;
+	Ok((ack, destination_str))
+}
+
 /// Validate and complete TOR address.
 pub fn validate_tor_address(dest: &str) -> Result<String, Error> {
 	// will test if this is a tor address and fill out
@@ -28,16 +28,20 @@ use crate::store::{self, option_to_not_found, to_key, to_key_u64, u64_to_key};
 
 use crate::core::core::Transaction;
 use crate::core::ser;
+use crate::libwallet::internal::scan::OutputResult;
 use crate::libwallet::{
-	AcctPathMapping, Context, Error, ErrorKind, NodeClient, OutputData, ScannedBlockInfo,
-	TxLogEntry, TxProof, WalletBackend, WalletOutputBatch,
+	AcctPathMapping, Contact, Context, Error, ErrorKind, MultisigAccount, NodeClient, OutputData,
+	OutputMetadata, PaymentRequest, ScanCheckpoint, ScanConfig, ScannedBlockInfo, ScheduledPayment,
+	TxLogEntry, TxProof, ViewKey, WalletBackend, WalletEventState, WalletOutputBatch,
 };
 use crate::util::secp::constants::SECRET_KEY_SIZE;
 use crate::util::secp::key::SecretKey;
+use crate::util::secp::pedersen;
 use crate::util::{self, secp};
 
 use rand::rngs::mock::StepRng;
 use rand::thread_rng;
+use uuid::Uuid;
 
 pub const DB_DIR: &str = "db";
 pub const TX_SAVE_DIR: &str = "saved_txs";
@@ -50,6 +54,43 @@ const TX_LOG_ENTRY_PREFIX: u8 = b't';
 const TX_LOG_ID_PREFIX: u8 = b'i';
 const ACCOUNT_PATH_MAPPING_PREFIX: u8 = b'a';
 const LAST_SCANNED_BLOCK: u8 = b'm'; // pre v3.0 was l
+const OUTPUT_METADATA_PREFIX: u8 = b'n';
+const MULTISIG_ACCOUNT_PREFIX: u8 = b'g';
+const SCHEDULED_PAYMENT_PREFIX: u8 = b's';
+const CONTACT_PREFIX: u8 = b'b';
+const PAYMENT_REQUEST_PREFIX: u8 = b'r';
+const SCAN_CHECKPOINT_PREFIX: u8 = b'h';
+const SCAN_OUTPUT_PREFIX: u8 = b'f';
+const EVENT_STATE_PREFIX: u8 = b'e';
+// Secondary indexes
+const TX_SLATE_ID_INDEX_PREFIX: u8 = b'u';
+const TX_KERNEL_INDEX_PREFIX: u8 = b'k';
+const OUTPUT_COMMIT_INDEX_PREFIX: u8 = b'q';
+const DB_SCHEMA_VERSION_PREFIX: u8 = b'v';
+
+// Index entries carry everything in the key, the value is a placeholder
+const INDEX_VALUE: u32 = 0;
+
+/// Migration of the wallet DB to the next schema version
+struct Migration {
+	/// Schema version after the migration
+	version: u32,
+	description: &'static str,
+	migrate: fn(&store::Batch) -> Result<(), Error>,
+}
+
+/// Migrations in the order of the schema versions. A format change of the stored data gets
+/// the next version and a migration here, the last version is the current one.
+const MIGRATIONS: &[Migration] = &[Migration {
+	version: 1,
+	description: "Build the slate id, commit and kernel indexes",
+	migrate: build_indexes,
+}];
+
+/// Schema version of the wallet DB created by this build
+pub fn db_schema_version() -> u32 {
+	MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
 
 /// test to see if database files exist in the current directory. If so,
 /// use a DB backend for all operations
@@ -58,9 +99,161 @@ pub fn wallet_db_exists(data_file_dir: &str) -> bool {
 	db_path.exists()
 }
 
+fn output_key(id: &Identifier, mmr_index: &Option<u64>) -> Vec<u8> {
+	match mmr_index {
+		Some(i) => to_key_u64(OUTPUT_PREFIX, &mut id.to_bytes().to_vec(), *i),
+		None => to_key(OUTPUT_PREFIX, &mut id.to_bytes().to_vec()),
+	}
+}
+
+fn tx_log_key(parent_id: &Identifier, id: u32) -> Vec<u8> {
+	to_key_u64(
+		TX_LOG_ENTRY_PREFIX,
+		&mut parent_id.to_bytes().to_vec(),
+		id as u64,
+	)
+}
+
+/// Index key is the index prefix, the indexed value and the key of the record without its
+/// prefix. Several records can share the same value, the record key is restored from the
+/// index key.
+fn index_key(prefix: u8, value: &[u8], record_key: &[u8]) -> Vec<u8> {
+	let mut key = Vec::with_capacity(value.len() + record_key.len());
+	key.push(prefix);
+	key.extend_from_slice(value);
+	key.extend_from_slice(&record_key[1..]);
+	key
+}
+
+fn output_index_keys(out: &OutputData, out_key: &[u8]) -> Vec<Vec<u8>> {
+	match &out.commit {
+		Some(commit) => vec![index_key(
+			OUTPUT_COMMIT_INDEX_PREFIX,
+			commit.as_bytes(),
+			out_key,
+		)],
+		None => vec![],
+	}
+}
+
+fn tx_log_index_keys(tx: &TxLogEntry, tx_key: &[u8]) -> Vec<Vec<u8>> {
+	let mut keys = vec![];
+	if let Some(slate_id) = &tx.tx_slate_id {
+		keys.push(index_key(
+			TX_SLATE_ID_INDEX_PREFIX,
+			slate_id.as_bytes(),
+			tx_key,
+		));
+	}
+	if let Some(excess) = &tx.kernel_excess {
+		keys.push(index_key(TX_KERNEL_INDEX_PREFIX, &excess.0, tx_key));
+	}
+	keys
+}
+
+/// Replace the index entries of the updated record
+fn update_index_keys(
+	db: &store::Batch,
+	old_keys: Vec<Vec<u8>>,
+	new_keys: Vec<Vec<u8>>,
+) -> Result<(), Error> {
+	for key in old_keys.iter().filter(|k| !new_keys.contains(k)) {
+		let _ = db.delete(key);
+	}
+	for key in new_keys {
+		db.put_ser(&key, &INDEX_VALUE)?;
+	}
+	Ok(())
+}
+
+/// (Re)build the secondary indexes from the outputs and the transaction log
+fn build_indexes(batch: &store::Batch) -> Result<(), Error> {
+	for prefix in &[
+		TX_SLATE_ID_INDEX_PREFIX,
+		TX_KERNEL_INDEX_PREFIX,
+		OUTPUT_COMMIT_INDEX_PREFIX,
+	] {
+		let keys: Vec<Vec<u8>> = batch
+			.iter(&[*prefix])?
+			.map(|o: (Vec<u8>, u32)| o.0)
+			.collect();
+		for key in keys {
+			batch.delete(&key)?;
+		}
+	}
+
+	let outputs: Vec<(Vec<u8>, OutputData)> = batch.iter(&[OUTPUT_PREFIX])?.collect();
+	for (key, out) in outputs {
+		update_index_keys(batch, vec![], output_index_keys(&out, &key))?;
+	}
+	let txs: Vec<(Vec<u8>, TxLogEntry)> = batch.iter(&[TX_LOG_ENTRY_PREFIX])?.collect();
+	for (key, tx) in txs {
+		update_index_keys(batch, vec![], tx_log_index_keys(&tx, &key))?;
+	}
+	Ok(())
+}
+
+fn stored_schema_version(store: &store::Store) -> Result<u32, Error> {
+	Ok(store.get_ser(&[DB_SCHEMA_VERSION_PREFIX])?.unwrap_or(0))
+}
+
+/// Migrations that will be applied when the wallet DB is opened, as (version, description)
+pub fn pending_migrations(data_file_dir: &str) -> Result<Vec<(u32, String)>, Error> {
+	let db_path = path::Path::new(data_file_dir).join(DB_DIR);
+	if is_new_db(&db_path) {
+		return Ok(vec![]);
+	}
+	let store = store::Store::new(db_path.to_str().unwrap(), None, Some(DB_DIR), None)?;
+	let version = stored_schema_version(&store)?;
+	Ok(MIGRATIONS
+		.iter()
+		.filter(|m| m.version > version)
+		.map(|m| (m.version, m.description.to_string()))
+		.collect())
+}
+
+/// Run `f` while holding the write transaction of the wallet DB, so no other process can commit
+/// to it until `f` returns. The transaction is never committed. The wallet must not be opened by
+/// this process, LMDB doesn't allow the second environment for the same DB.
+pub(crate) fn with_db_write_lock<T, F>(data_file_dir: &str, f: F) -> Result<T, Error>
+where
+	F: FnOnce() -> T,
+{
+	let db_path = path::Path::new(data_file_dir).join(DB_DIR);
+	if is_new_db(&db_path) {
+		return Ok(f());
+	}
+	let store = store::Store::new(db_path.to_str().unwrap(), None, Some(DB_DIR), None)?;
+	let _batch = store.batch()?;
+	Ok(f())
+}
+
+// DB directory doesn't exist or has no files yet
+fn is_new_db(db_path: &Path) -> bool {
+	fs::read_dir(db_path)
+		.map(|mut d| d.next().is_none())
+		.unwrap_or(true)
+}
+
+// Copy the DB files, except the LMDB lock file, before the migration
+fn copy_db_dir(from: &Path, to: &Path) -> Result<(), Error> {
+	let io_err = |e: std::io::Error| ErrorKind::IO(format!("Unable to backup wallet DB, {}", e));
+	fs::create_dir_all(to).map_err(io_err)?;
+	for entry in fs::read_dir(from).map_err(io_err)? {
+		let entry = entry.map_err(io_err)?;
+		let target = to.join(entry.file_name());
+		if entry.path().is_dir() {
+			copy_db_dir(&entry.path(), &target)?;
+		} else if entry.file_name() != "lock.mdb" {
+			fs::copy(entry.path(), &target).map_err(io_err)?;
+		}
+	}
+	Ok(())
+}
+
 /// Helper to derive XOR keys for storing private transaction keys in the DB
 /// (blind_xor_key, nonce_xor_key)
-fn private_ctx_xor_keys<K>(
+pub(crate) fn private_ctx_xor_keys<K>(
 	keychain: &K,
 	slate_id: &[u8],
 ) -> Result<([u8; SECRET_KEY_SIZE], [u8; SECRET_KEY_SIZE]), Error>
@@ -91,6 +284,29 @@ where
 	Ok((ret_blind, ret_nonce))
 }
 
+/// Wallet DB records, for the migration to another backend
+#[cfg(feature = "sqlite")]
+pub(crate) struct DbContent {
+	pub outputs: Vec<OutputData>,
+	pub tx_log: Vec<TxLogEntry>,
+	pub accounts: Vec<AcctPathMapping>,
+	/// (parent key id, next child index)
+	pub child_indexes: Vec<(Identifier, u64)>,
+	/// (parent key id, next tx log id)
+	pub tx_log_ids: Vec<(Identifier, u64)>,
+	/// (parent key id, height)
+	pub confirmed_heights: Vec<(Identifier, u64)>,
+	pub scanned_blocks: Vec<ScannedBlockInfo>,
+	/// (slate id, participant id, XOR-masked context)
+	pub private_contexts: Vec<(Vec<u8>, usize, Context)>,
+	pub output_metadata: Vec<OutputMetadata>,
+	pub multisig_accounts: Vec<MultisigAccount>,
+	pub scheduled_payments: Vec<ScheduledPayment>,
+	pub contacts: Vec<Contact>,
+	pub payment_requests: Vec<PaymentRequest>,
+	pub event_state: Option<WalletEventState>,
+}
+
 pub struct LMDBBackend<'ck, C, K>
 where
 	C: NodeClient + 'ck,
@@ -102,6 +318,10 @@ where
 	pub keychain: Option<K>,
 	/// Check value for XORed keychain seed
 	pub master_checksum: Box<Option<Blake2bResult>>,
+	/// View key of the watch-only wallet
+	view_key: Option<ViewKey>,
+	/// Update and scan settings
+	scan_config: ScanConfig,
 	/// Parent path to use by default for output operations
 	parent_key_id: Identifier,
 	/// wallet to node client
@@ -123,6 +343,8 @@ where
 		fs::create_dir_all(&stored_tx_path)
 			.expect("Couldn't create wallet backend tx storage directory!");
 
+		// New DB gets the current schema version, there is nothing to migrate
+		let new_db = is_new_db(&db_path);
 		let store = store::Store::new(db_path.to_str().unwrap(), None, Some(DB_DIR), None)?;
 
 		// Make sure default wallet derivation path always exists
@@ -143,6 +365,14 @@ where
 			batch.commit()?;
 		}
 
+		if new_db {
+			let batch = store.batch()?;
+			batch.put_ser(&[DB_SCHEMA_VERSION_PREFIX], &db_schema_version())?;
+			batch.commit()?;
+		} else {
+			LMDBBackend::<C, K>::migrate(&store, data_file_dir)?;
+		}
+
 		TxProof::init_proof_backend(data_file_dir)?;
 
 		let res = LMDBBackend {
@@ -150,6 +380,8 @@ where
 			data_file_dir: data_file_dir.to_owned(),
 			keychain: None,
 			master_checksum: Box::new(None),
+			view_key: None,
+			scan_config: ScanConfig::default(),
 			parent_key_id: LMDBBackend::<C, K>::default_path(),
 			w2n_client: n_client,
 			_phantom: &PhantomData,
@@ -164,6 +396,124 @@ where
 		ExtKeychain::derive_key_id(2, 0, 0, 0, 0)
 	}
 
+	/// Apply the pending migrations. The DB directory is copied into `db_backup_v<version>`
+	/// first, all migrations run in a single batch together with the new schema version.
+	fn migrate(store: &store::Store, data_file_dir: &str) -> Result<(), Error> {
+		let version = stored_schema_version(store)?;
+		let current = db_schema_version();
+		if version > current {
+			return Err(ErrorKind::GenericError(format!(
+				"Wallet DB schema version {} is newer than supported version {}, please update the wallet",
+				version, current
+			))
+			.into());
+		}
+		if version == current {
+			return Ok(());
+		}
+
+		let data_dir = path::Path::new(data_file_dir);
+		let backup_dir = data_dir.join(format!("{}_backup_v{}", DB_DIR, version));
+		if backup_dir.exists() {
+			fs::remove_dir_all(&backup_dir).map_err(|e| {
+				ErrorKind::IO(format!("Unable to remove old wallet DB backup, {}", e))
+			})?;
+		}
+		copy_db_dir(&data_dir.join(DB_DIR), &backup_dir)?;
+		info!(
+			"Wallet DB schema version {} is backed up at {:?}",
+			version, backup_dir
+		);
+
+		let batch = store.batch()?;
+		for m in MIGRATIONS.iter().filter(|m| m.version > version) {
+			info!(
+				"Migrating wallet DB to version {}: {}",
+				m.version, m.description
+			);
+			(m.migrate)(&batch)?;
+		}
+		batch.put_ser(&[DB_SCHEMA_VERSION_PREFIX], &current)?;
+		batch.commit()?;
+		Ok(())
+	}
+
+	/// Records found by the index value
+	fn index_lookup<T: ser::Readable>(
+		&self,
+		prefix: u8,
+		value: &[u8],
+		record_prefix: u8,
+	) -> Result<Vec<T>, Error> {
+		let mut search_key = vec![prefix];
+		search_key.extend_from_slice(value);
+		let record_keys: Vec<Vec<u8>> = self
+			.db
+			.iter(&search_key)?
+			.map(|o: (Vec<u8>, u32)| {
+				let mut key = vec![record_prefix];
+				key.extend_from_slice(&o.0[search_key.len()..]);
+				key
+			})
+			.collect();
+
+		let mut records = vec![];
+		for key in record_keys {
+			if let Some(r) = self.db.get_ser(&key)? {
+				records.push(r);
+			}
+		}
+		Ok(records)
+	}
+
+	/// All records of the wallet DB, private contexts are kept XOR-masked
+	#[cfg(feature = "sqlite")]
+	pub(crate) fn export(&self) -> Result<DbContent, Error> {
+		// Id of the record is the key without the prefix
+		let key_id = |prefix: u8, key: &[u8]| {
+			let head = to_key(prefix, &mut vec![]).len();
+			Identifier::from_bytes(&key[head..])
+		};
+		let counters = |prefix: u8| -> Result<Vec<(Identifier, u64)>, Error> {
+			let mut res = vec![];
+			for (key, value) in self.db.iter::<u32>(&[prefix])? {
+				res.push((key_id(prefix, &key), value as u64));
+			}
+			Ok(res)
+		};
+
+		let mut private_contexts = vec![];
+		let head = to_key(PRIVATE_TX_CONTEXT_PREFIX, &mut vec![]).len();
+		for (key, ctx) in self.db.iter::<Context>(&[PRIVATE_TX_CONTEXT_PREFIX])? {
+			let (slate_id, participant_id) = key[head..].split_at(key.len() - head - 8);
+			let mut id = [0u8; 8];
+			id.copy_from_slice(participant_id);
+			private_contexts.push((slate_id.to_vec(), u64::from_be_bytes(id) as usize, ctx));
+		}
+
+		let mut confirmed_heights = vec![];
+		for (key, height) in self.db.iter::<u64>(&[CONFIRMED_HEIGHT_PREFIX])? {
+			confirmed_heights.push((key_id(CONFIRMED_HEIGHT_PREFIX, &key), height));
+		}
+
+		Ok(DbContent {
+			outputs: self.db.iter(&[OUTPUT_PREFIX])?.map(|o| o.1).collect(),
+			tx_log: self.db.iter(&[TX_LOG_ENTRY_PREFIX])?.map(|o| o.1).collect(),
+			accounts: self.acct_path_iter().collect(),
+			child_indexes: counters(DERIV_PREFIX)?,
+			tx_log_ids: counters(TX_LOG_ID_PREFIX)?,
+			confirmed_heights,
+			scanned_blocks: self.db.iter(&[LAST_SCANNED_BLOCK])?.map(|o| o.1).collect(),
+			private_contexts,
+			output_metadata: self.output_metadata_iter().collect(),
+			multisig_accounts: self.multisig_account_iter().collect(),
+			scheduled_payments: self.scheduled_payment_iter().collect(),
+			contacts: self.contact_iter().collect(),
+			payment_requests: self.payment_request_iter().collect(),
+			event_state: self.get_event_state()?,
+		})
+	}
+
 	/// Just test to see if database files exist in the current directory. If
 	/// so, use a DB backend for all operations
 	pub fn exists(data_file_dir: &str) -> bool {
@@ -221,9 +571,26 @@ where
 	/// Close wallet
 	fn close(&mut self) -> Result<(), Error> {
 		self.keychain = None;
+		self.view_key = None;
 		Ok(())
 	}
 
+	fn set_view_key(&mut self, view_key: ViewKey) {
+		self.view_key = Some(view_key);
+	}
+
+	fn view_key(&self) -> Option<ViewKey> {
+		self.view_key.clone()
+	}
+
+	fn set_scan_config(&mut self, config: ScanConfig) {
+		self.scan_config = config;
+	}
+
+	fn scan_config(&self) -> ScanConfig {
+		self.scan_config.clone()
+	}
+
 	/// Return the keychain being used, cloned with XORed token value
 	/// for temporary use
 	fn keychain(&self, mask: Option<&SecretKey>) -> Result<K, Error> {
@@ -244,7 +611,10 @@ where
 				}
 				Ok(k_masked)
 			}
-			None => Err(ErrorKind::KeychainDoesntExist.into()),
+			None => match self.view_key {
+				Some(_) => Err(ErrorKind::WatchOnlyWallet.into()),
+				None => Err(ErrorKind::KeychainDoesntExist.into()),
+			},
 		}
 	}
 
@@ -296,10 +666,7 @@ where
 	}
 
 	fn get(&self, id: &Identifier, mmr_index: &Option<u64>) -> Result<OutputData, Error> {
-		let key = match mmr_index {
-			Some(i) => to_key_u64(OUTPUT_PREFIX, &mut id.to_bytes().to_vec(), *i),
-			None => to_key(OUTPUT_PREFIX, &mut id.to_bytes().to_vec()),
-		};
+		let key = output_key(id, mmr_index);
 		option_to_not_found(self.db.get_ser(&key), || format!("Key Id: {}", id))
 			.map_err(|e| e.into())
 	}
@@ -417,12 +784,20 @@ where
 		)
 	}
 
+	fn store_tx_proof(&self, uuid: &str, proof: &TxProof) -> Result<(), Error> {
+		proof.store_tx_proof(&self.data_file_dir, uuid)
+	}
+
+	fn get_stored_tx_proof(&self, uuid: &str) -> Result<TxProof, Error> {
+		TxProof::get_stored_tx_proof(&self.data_file_dir, uuid)
+	}
+
 	fn batch<'a>(
 		&'a mut self,
 		keychain_mask: Option<&SecretKey>,
 	) -> Result<Box<dyn WalletOutputBatch<K> + 'a>, Error> {
 		Ok(Box::new(Batch {
-			_store: self,
+			store: self,
 			db: RefCell::new(Some(self.db.batch()?)),
 			keychain: Some(self.keychain(keychain_mask)?),
 		}))
@@ -430,7 +805,7 @@ where
 
 	fn batch_no_mask<'a>(&'a mut self) -> Result<Box<dyn WalletOutputBatch<K> + 'a>, Error> {
 		Ok(Box::new(Batch {
-			_store: self,
+			store: self,
 			db: RefCell::new(Some(self.db.batch()?)),
 			keychain: None,
 		}))
@@ -499,6 +874,111 @@ where
 
 		Ok(blocks)
 	}
+
+	fn get_scan_checkpoint(&self) -> Result<Option<ScanCheckpoint>, Error> {
+		self.db
+			.get_ser(&[SCAN_CHECKPOINT_PREFIX])
+			.map_err(|e| e.into())
+	}
+
+	fn scan_checkpoint_outputs(&self) -> Result<Vec<OutputResult>, Error> {
+		// Keys are big endian PMMR indexes
+		Ok(self.db.iter(&[SCAN_OUTPUT_PREFIX])?.map(|o| o.1).collect())
+	}
+
+	fn get_event_state(&self) -> Result<Option<WalletEventState>, Error> {
+		self.db.get_ser(&[EVENT_STATE_PREFIX]).map_err(|e| e.into())
+	}
+
+	fn get_outputs_by_commit(&self, commit: &str) -> Result<Vec<OutputData>, Error> {
+		// Commit is a hex string, a shorter string is a prefix of the longer ones
+		let outputs: Vec<OutputData> =
+			self.index_lookup(OUTPUT_COMMIT_INDEX_PREFIX, commit.as_bytes(), OUTPUT_PREFIX)?;
+		Ok(outputs
+			.into_iter()
+			.filter(|o| o.commit.as_ref().map(|c| c == commit).unwrap_or(false))
+			.collect())
+	}
+
+	fn get_tx_log_by_slate_id(&self, slate_id: &Uuid) -> Result<Vec<TxLogEntry>, Error> {
+		self.index_lookup(
+			TX_SLATE_ID_INDEX_PREFIX,
+			slate_id.as_bytes(),
+			TX_LOG_ENTRY_PREFIX,
+		)
+	}
+
+	fn get_tx_log_by_kernel_excess(
+		&self,
+		excess: &pedersen::Commitment,
+	) -> Result<Vec<TxLogEntry>, Error> {
+		self.index_lookup(TX_KERNEL_INDEX_PREFIX, &excess.0, TX_LOG_ENTRY_PREFIX)
+	}
+
+	fn get_output_metadata(&self, commit: &str) -> Result<Option<OutputMetadata>, Error> {
+		let key = to_key(OUTPUT_METADATA_PREFIX, &mut commit.as_bytes().to_vec());
+		self.db.get_ser(&key).map_err(|e| e.into())
+	}
+
+	fn output_metadata_iter<'a>(&'a self) -> Box<dyn Iterator<Item = OutputMetadata> + 'a> {
+		Box::new(
+			self.db
+				.iter(&[OUTPUT_METADATA_PREFIX])
+				.unwrap()
+				.map(|o| o.1),
+		)
+	}
+
+	fn get_multisig_account(&self, id: &Uuid) -> Result<Option<MultisigAccount>, Error> {
+		let key = to_key(MULTISIG_ACCOUNT_PREFIX, &mut id.as_bytes().to_vec());
+		self.db.get_ser(&key).map_err(|e| e.into())
+	}
+
+	fn multisig_account_iter<'a>(&'a self) -> Box<dyn Iterator<Item = MultisigAccount> + 'a> {
+		Box::new(
+			self.db
+				.iter(&[MULTISIG_ACCOUNT_PREFIX])
+				.unwrap()
+				.map(|o| o.1),
+		)
+	}
+
+	fn get_scheduled_payment(&self, id: &Uuid) -> Result<Option<ScheduledPayment>, Error> {
+		let key = to_key(SCHEDULED_PAYMENT_PREFIX, &mut id.as_bytes().to_vec());
+		self.db.get_ser(&key).map_err(|e| e.into())
+	}
+
+	fn scheduled_payment_iter<'a>(&'a self) -> Box<dyn Iterator<Item = ScheduledPayment> + 'a> {
+		Box::new(
+			self.db
+				.iter(&[SCHEDULED_PAYMENT_PREFIX])
+				.unwrap()
+				.map(|o| o.1),
+		)
+	}
+
+	fn get_contact(&self, name: &str) -> Result<Option<Contact>, Error> {
+		let key = to_key(CONTACT_PREFIX, &mut name.as_bytes().to_vec());
+		self.db.get_ser(&key).map_err(|e| e.into())
+	}
+
+	fn contact_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Contact> + 'a> {
+		Box::new(self.db.iter(&[CONTACT_PREFIX]).unwrap().map(|o| o.1))
+	}
+
+	fn get_payment_request(&self, id: &Uuid) -> Result<Option<PaymentRequest>, Error> {
+		let key = to_key(PAYMENT_REQUEST_PREFIX, &mut id.as_bytes().to_vec());
+		self.db.get_ser(&key).map_err(|e| e.into())
+	}
+
+	fn payment_request_iter<'a>(&'a self) -> Box<dyn Iterator<Item = PaymentRequest> + 'a> {
+		Box::new(
+			self.db
+				.iter(&[PAYMENT_REQUEST_PREFIX])
+				.unwrap()
+				.map(|o| o.1),
+		)
+	}
 }
 
 /// An atomic batch in which all changes can be committed all at once or
@@ -508,7 +988,7 @@ where
 	C: NodeClient,
 	K: Keychain,
 {
-	_store: &'a LMDBBackend<'a, C, K>,
+	store: &'a LMDBBackend<'a, C, K>,
 	db: RefCell<Option<store::Batch<'a>>>,
 	/// Keychain
 	keychain: Option<K>,
@@ -525,23 +1005,24 @@ where
 	}
 
 	fn save(&mut self, out: OutputData) -> Result<(), Error> {
-		// Save the output data to the db.
+		// Save the output data to the db, updating the commit index.
 		{
-			let key = match out.mmr_index {
-				Some(i) => to_key_u64(OUTPUT_PREFIX, &mut out.key_id.to_bytes().to_vec(), i),
-				None => to_key(OUTPUT_PREFIX, &mut out.key_id.to_bytes().to_vec()),
+			let key = output_key(&out.key_id, &out.mmr_index);
+			let br = self.db.borrow();
+			let db = br.as_ref().unwrap();
+			let old_keys = match db.get_ser::<OutputData>(&key)? {
+				Some(old) => output_index_keys(&old, &key),
+				None => vec![],
 			};
-			self.db.borrow().as_ref().unwrap().put_ser(&key, &out)?;
+			update_index_keys(db, old_keys, output_index_keys(&out, &key))?;
+			db.put_ser(&key, &out)?;
 		}
 
 		Ok(())
 	}
 
 	fn get(&self, id: &Identifier, mmr_index: &Option<u64>) -> Result<OutputData, Error> {
-		let key = match mmr_index {
-			Some(i) => to_key_u64(OUTPUT_PREFIX, &mut id.to_bytes().to_vec(), *i),
-			None => to_key(OUTPUT_PREFIX, &mut id.to_bytes().to_vec()),
-		};
+		let key = output_key(id, mmr_index);
 		option_to_not_found(self.db.borrow().as_ref().unwrap().get_ser(&key), || {
 			format!("Key ID: {}", id)
 		})
@@ -561,13 +1042,15 @@ where
 	}
 
 	fn delete(&mut self, id: &Identifier, mmr_index: &Option<u64>) -> Result<(), Error> {
-		// Delete the output data.
+		// Delete the output data and its index entries.
 		{
-			let key = match mmr_index {
-				Some(i) => to_key_u64(OUTPUT_PREFIX, &mut id.to_bytes().to_vec(), *i),
-				None => to_key(OUTPUT_PREFIX, &mut id.to_bytes().to_vec()),
-			};
-			let _ = self.db.borrow().as_ref().unwrap().delete(&key);
+			let key = output_key(id, mmr_index);
+			let br = self.db.borrow();
+			let db = br.as_ref().unwrap();
+			if let Some(old) = db.get_ser::<OutputData>(&key)? {
+				update_index_keys(db, output_index_keys(&old, &key), vec![])?;
+			}
+			let _ = db.delete(&key);
 		}
 
 		Ok(())
@@ -652,10 +1135,14 @@ where
 
 		let mut step = 4;
 		let mut start = heights.pop().unwrap_or(1);
+		// Blocks within the max reorg depth are kept, they locate the fork point of the reorg
+		let keep_height = start.saturating_sub(self.store.scan_config.max_reorg_depth);
 
 		while let Some(h) = heights.pop() {
 			assert!(h < start);
-			if start - h < step {
+			if h >= keep_height {
+				start = h;
+			} else if start - h < step {
 				db.delete(&u64_to_key(LAST_SCANNED_BLOCK, h))?;
 			} else {
 				start = h;
@@ -681,16 +1168,15 @@ where
 		tx_in: TxLogEntry,
 		parent_id: &Identifier,
 	) -> Result<(), Error> {
-		let tx_log_key = to_key_u64(
-			TX_LOG_ENTRY_PREFIX,
-			&mut parent_id.to_bytes().to_vec(),
-			tx_in.id as u64,
-		);
-		self.db
-			.borrow()
-			.as_ref()
-			.unwrap()
-			.put_ser(&tx_log_key, &tx_in)?;
+		let key = tx_log_key(parent_id, tx_in.id);
+		let br = self.db.borrow();
+		let db = br.as_ref().unwrap();
+		let old_keys = match db.get_ser::<TxLogEntry>(&key)? {
+			Some(old) => tx_log_index_keys(&old, &key),
+			None => vec![],
+		};
+		update_index_keys(db, old_keys, tx_log_index_keys(&tx_in, &key))?;
+		db.put_ser(&key, &tx_in)?;
 		Ok(())
 	}
 
@@ -756,6 +1242,98 @@ where
 		self.save(out.clone())
 	}
 
+	fn save_output_metadata(&mut self, metadata: &OutputMetadata) -> Result<(), Error> {
+		let key = to_key(
+			OUTPUT_METADATA_PREFIX,
+			&mut metadata.commit.as_bytes().to_vec(),
+		);
+		self.db.borrow().as_ref().unwrap().put_ser(&key, metadata)?;
+		Ok(())
+	}
+
+	fn delete_output_metadata(&mut self, commit: &str) -> Result<(), Error> {
+		let key = to_key(OUTPUT_METADATA_PREFIX, &mut commit.as_bytes().to_vec());
+		let _ = self.db.borrow().as_ref().unwrap().delete(&key);
+		Ok(())
+	}
+
+	fn save_multisig_account(&mut self, account: &MultisigAccount) -> Result<(), Error> {
+		let key = to_key(MULTISIG_ACCOUNT_PREFIX, &mut account.id.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().put_ser(&key, account)?;
+		Ok(())
+	}
+
+	fn save_scheduled_payment(&mut self, payment: &ScheduledPayment) -> Result<(), Error> {
+		let key = to_key(
+			SCHEDULED_PAYMENT_PREFIX,
+			&mut payment.id.as_bytes().to_vec(),
+		);
+		self.db.borrow().as_ref().unwrap().put_ser(&key, payment)?;
+		Ok(())
+	}
+
+	fn delete_scheduled_payment(&mut self, id: &Uuid) -> Result<(), Error> {
+		let key = to_key(SCHEDULED_PAYMENT_PREFIX, &mut id.as_bytes().to_vec());
+		let _ = self.db.borrow().as_ref().unwrap().delete(&key);
+		Ok(())
+	}
+
+	fn save_contact(&mut self, contact: &Contact) -> Result<(), Error> {
+		let key = to_key(CONTACT_PREFIX, &mut contact.name.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().put_ser(&key, contact)?;
+		Ok(())
+	}
+
+	fn delete_contact(&mut self, name: &str) -> Result<(), Error> {
+		let key = to_key(CONTACT_PREFIX, &mut name.as_bytes().to_vec());
+		let _ = self.db.borrow().as_ref().unwrap().delete(&key);
+		Ok(())
+	}
+
+	fn save_payment_request(&mut self, request: &PaymentRequest) -> Result<(), Error> {
+		let key = to_key(PAYMENT_REQUEST_PREFIX, &mut request.id.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().put_ser(&key, request)?;
+		Ok(())
+	}
+
+	fn save_scan_checkpoint(&mut self, checkpoint: &ScanCheckpoint) -> Result<(), Error> {
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&[SCAN_CHECKPOINT_PREFIX], checkpoint)?;
+		Ok(())
+	}
+
+	fn save_scan_checkpoint_output(&mut self, output: &OutputResult) -> Result<(), Error> {
+		let key = u64_to_key(SCAN_OUTPUT_PREFIX, output.mmr_index);
+		self.db.borrow().as_ref().unwrap().put_ser(&key, output)?;
+		Ok(())
+	}
+
+	fn delete_scan_checkpoint(&mut self) -> Result<(), Error> {
+		let br = self.db.borrow();
+		let db = br.as_ref().unwrap();
+		let keys: Vec<Vec<u8>> = db
+			.iter(&[SCAN_OUTPUT_PREFIX])?
+			.map(|o: (Vec<u8>, OutputResult)| o.0)
+			.collect();
+		for key in keys {
+			db.delete(&key)?;
+		}
+		let _ = db.delete(&[SCAN_CHECKPOINT_PREFIX]);
+		Ok(())
+	}
+
+	fn save_event_state(&mut self, state: &WalletEventState) -> Result<(), Error> {
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&[EVENT_STATE_PREFIX], state)?;
+		Ok(())
+	}
+
 	fn save_private_context(
 		&mut self,
 		slate_id: &[u8],
@@ -807,3 +1385,64 @@ where
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::libwallet::TxLogEntryType;
+	use crate::test_framework::LocalWalletClient;
+	use std::sync::mpsc::channel;
+
+	type Backend<'a> = LMDBBackend<'a, LocalWalletClient, ExtKeychain>;
+
+	#[test]
+	fn db_migrations() {
+		let test_dir = "test_output/db_migrations";
+		let _ = fs::remove_dir_all(test_dir);
+		let (tx, _rx) = channel();
+		let client = LocalWalletClient::new("wallet", tx);
+		let slate_id = Uuid::new_v4();
+
+		// New DB has the current version, nothing to migrate
+		{
+			let w = Backend::new(test_dir, client.clone()).unwrap();
+			assert_eq!(stored_schema_version(&w.db).unwrap(), db_schema_version());
+
+			// Old DB: the entry without the index and no schema version
+			let mut entry = TxLogEntry::new(Backend::default_path(), TxLogEntryType::TxSent, 0);
+			entry.tx_slate_id = Some(slate_id);
+			let batch = w.db.batch().unwrap();
+			batch
+				.put_ser(&tx_log_key(&entry.parent_key_id, entry.id), &entry)
+				.unwrap();
+			batch.delete(&[DB_SCHEMA_VERSION_PREFIX]).unwrap();
+			batch.commit().unwrap();
+			assert!(w.get_tx_log_by_slate_id(&slate_id).unwrap().is_empty());
+		}
+
+		let pending = pending_migrations(test_dir).unwrap();
+		assert_eq!(
+			pending.iter().map(|m| m.0).collect::<Vec<u32>>(),
+			MIGRATIONS.iter().map(|m| m.version).collect::<Vec<u32>>()
+		);
+
+		// Opening the wallet applies the migrations after the backup
+		{
+			let w = Backend::new(test_dir, client.clone()).unwrap();
+			assert_eq!(w.get_tx_log_by_slate_id(&slate_id).unwrap().len(), 1);
+			assert!(Path::new(test_dir)
+				.join(format!("{}_backup_v0", DB_DIR))
+				.exists());
+
+			// DB of a newer wallet version
+			let batch = w.db.batch().unwrap();
+			batch
+				.put_ser(&[DB_SCHEMA_VERSION_PREFIX], &(db_schema_version() + 1))
+				.unwrap();
+			batch.commit().unwrap();
+		}
+		assert!(Backend::new(test_dir, client).is_err());
+
+		let _ = fs::remove_dir_all(test_dir);
+	}
+}
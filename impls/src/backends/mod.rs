@@ -13,5 +13,20 @@
 // limitations under the License.
 
 mod lmdb;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
-pub use self::lmdb::{wallet_db_exists, LMDBBackend};
+use std::path::Path;
+
+pub use self::lmdb::{db_schema_version, pending_migrations, wallet_db_exists, LMDBBackend};
+pub(crate) use self::lmdb::{with_db_write_lock, DB_DIR, TX_SAVE_DIR};
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::{migrate_from_lmdb, SqliteBackend};
+
+/// SQLite wallet DB file, relative to the wallet data directory
+pub const SQLITE_DB_FILE: &str = "wallet.sqlite";
+
+/// Check if the SQLite wallet DB exists in the wallet data directory
+pub fn sqlite_db_exists(data_file_dir: &str) -> bool {
+	Path::new(data_file_dir).join(SQLITE_DB_FILE).exists()
+}
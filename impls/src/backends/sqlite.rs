@@ -0,0 +1,1703 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SQLite wallet backend. Every record is a row of its own table: the columns hold the
+//! fields that are worth querying (amounts, heights, statuses, commits, slate ids) and the
+//! `data` column holds the whole record as json, the backend reads the records from it.
+//! Private contexts are stored XOR-masked, the same way as in LMDB.
+
+use std::cell::Cell;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use rusqlite::types::FromSql;
+use rusqlite::{params, Connection, OptionalExtension, ToSql, NO_PARAMS};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::backends::lmdb::{private_ctx_xor_keys, LMDBBackend, TX_SAVE_DIR};
+use crate::backends::SQLITE_DB_FILE;
+use crate::blake2::blake2b::{Blake2b, Blake2bResult};
+use crate::core::core::Transaction;
+use crate::core::ser;
+use crate::keychain::{ChildNumber, ExtKeychain, Identifier, Keychain, SwitchCommitmentType};
+use crate::libwallet::internal::scan::OutputResult;
+use crate::libwallet::proof::tx_proof::TX_PROOF_SAVE_DIR;
+use crate::libwallet::{
+	AcctPathMapping, Contact, Context, Error, ErrorKind, MultisigAccount, NodeClient, OutputData,
+	OutputMetadata, PaymentRequest, ScanCheckpoint, ScanConfig, ScannedBlockInfo, ScheduledPayment,
+	TxLogEntry, TxProof, ViewKey, WalletBackend, WalletEventState, WalletOutputBatch,
+};
+use crate::util::secp::constants::SECRET_KEY_SIZE;
+use crate::util::secp::key::SecretKey;
+use crate::util::secp::{self, pedersen};
+use crate::util::{self, Mutex};
+
+use rand::rngs::mock::StepRng;
+use rand::thread_rng;
+use uuid::Uuid;
+
+/// Version of the tables layout, stored as the DB user_version
+const SQLITE_SCHEMA_VERSION: u32 = 2;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS outputs (
+	key_id TEXT NOT NULL,
+	mmr_index INTEGER,
+	root_key_id TEXT NOT NULL,
+	n_child INTEGER NOT NULL,
+	commitment TEXT,
+	value INTEGER NOT NULL,
+	status TEXT NOT NULL,
+	height INTEGER NOT NULL,
+	lock_height INTEGER NOT NULL,
+	is_coinbase INTEGER NOT NULL,
+	tx_log_entry INTEGER,
+	data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS outputs_key_id ON outputs (key_id, mmr_index);
+CREATE INDEX IF NOT EXISTS outputs_commitment ON outputs (commitment);
+
+CREATE TABLE IF NOT EXISTS tx_log (
+	parent_key_id TEXT NOT NULL,
+	id INTEGER NOT NULL,
+	tx_slate_id TEXT,
+	tx_type TEXT NOT NULL,
+	address TEXT,
+	creation_ts TEXT NOT NULL,
+	confirmation_ts TEXT,
+	confirmed INTEGER NOT NULL,
+	output_height INTEGER NOT NULL,
+	num_inputs INTEGER NOT NULL,
+	num_outputs INTEGER NOT NULL,
+	amount_credited INTEGER NOT NULL,
+	amount_debited INTEGER NOT NULL,
+	fee INTEGER,
+	kernel_excess TEXT,
+	stored_tx TEXT,
+	data TEXT NOT NULL,
+	PRIMARY KEY (parent_key_id, id)
+);
+CREATE INDEX IF NOT EXISTS tx_log_slate_id ON tx_log (tx_slate_id);
+CREATE INDEX IF NOT EXISTS tx_log_kernel_excess ON tx_log (kernel_excess);
+
+CREATE TABLE IF NOT EXISTS tx_log_ids (
+	parent_key_id TEXT PRIMARY KEY,
+	next_id INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS child_indexes (
+	parent_key_id TEXT PRIMARY KEY,
+	child_index INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS confirmed_heights (
+	parent_key_id TEXT PRIMARY KEY,
+	height INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS scanned_blocks (
+	height INTEGER PRIMARY KEY,
+	hash TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS accounts (
+	label TEXT PRIMARY KEY,
+	path TEXT NOT NULL,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS private_contexts (
+	slate_id BLOB NOT NULL,
+	participant_id INTEGER NOT NULL,
+	data TEXT NOT NULL,
+	PRIMARY KEY (slate_id, participant_id)
+);
+CREATE TABLE IF NOT EXISTS stored_txs (
+	name TEXT PRIMARY KEY,
+	tx TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tx_proofs (
+	slate_id TEXT PRIMARY KEY,
+	proof TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS output_metadata (
+	commitment TEXT PRIMARY KEY,
+	label TEXT,
+	frozen INTEGER NOT NULL,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS multisig_accounts (
+	id TEXT PRIMARY KEY,
+	amount INTEGER NOT NULL,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS scheduled_payments (
+	id TEXT PRIMARY KEY,
+	amount INTEGER NOT NULL,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS contacts (
+	name TEXT PRIMARY KEY,
+	address TEXT NOT NULL,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS payment_requests (
+	id TEXT PRIMARY KEY,
+	amount INTEGER NOT NULL,
+	address TEXT NOT NULL,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS scan_checkpoint (
+	id INTEGER PRIMARY KEY CHECK (id = 0),
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS scan_outputs (
+	mmr_index INTEGER PRIMARY KEY,
+	data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS event_state (
+	id INTEGER PRIMARY KEY CHECK (id = 0),
+	data TEXT NOT NULL
+);
+";
+
+fn db_error(e: rusqlite::Error) -> Error {
+	ErrorKind::Backend(format!("SQLite error, {}", e)).into()
+}
+
+fn to_json<T: Serialize>(record: &T) -> Result<String, Error> {
+	serde_json::to_string(record).map_err(|e| {
+		ErrorKind::Backend(format!("Unable to convert the record to json, {}", e)).into()
+	})
+}
+
+fn from_json<T: DeserializeOwned>(data: &str) -> Result<T, Error> {
+	serde_json::from_str(data)
+		.map_err(|e| ErrorKind::Backend(format!("Unable to read the record json, {}", e)).into())
+}
+
+fn sqlite_db_path(data_file_dir: &str) -> PathBuf {
+	Path::new(data_file_dir).join(SQLITE_DB_FILE)
+}
+
+/// Open the DB, the missing tables are created for the new DB or the DB of an older version
+fn open_db(db_file: &Path) -> Result<Connection, Error> {
+	let conn = Connection::open(db_file).map_err(db_error)?;
+	let version: u32 = conn
+		.query_row("PRAGMA user_version", NO_PARAMS, |row| row.get(0))
+		.map_err(db_error)?;
+	if version > SQLITE_SCHEMA_VERSION {
+		return Err(ErrorKind::GenericError(format!(
+			"Wallet DB schema version {} is newer than supported version {}, please update the wallet",
+			version, SQLITE_SCHEMA_VERSION
+		))
+		.into());
+	}
+	if version < SQLITE_SCHEMA_VERSION {
+		conn.execute_batch(&format!(
+			"BEGIN; {} PRAGMA user_version = {}; COMMIT;",
+			SCHEMA, SQLITE_SCHEMA_VERSION
+		))
+		.map_err(db_error)?;
+	}
+	Ok(conn)
+}
+
+/// Records from the `data` column, it must be the first column of the query
+fn query_records<T: DeserializeOwned>(
+	conn: &Connection,
+	sql: &str,
+	params: &[&dyn ToSql],
+) -> Result<Vec<T>, Error> {
+	let mut stmt = conn.prepare_cached(sql).map_err(db_error)?;
+	let rows = stmt
+		.query_map(params, |row| row.get::<_, String>(0))
+		.map_err(db_error)?;
+	let mut records = vec![];
+	for data in rows {
+		records.push(from_json(&data.map_err(db_error)?)?);
+	}
+	Ok(records)
+}
+
+fn query_record<T: DeserializeOwned>(
+	conn: &Connection,
+	sql: &str,
+	params: &[&dyn ToSql],
+) -> Result<Option<T>, Error> {
+	Ok(query_records(conn, sql, params)?.into_iter().next())
+}
+
+fn query_value<T: FromSql>(
+	conn: &Connection,
+	sql: &str,
+	params: &[&dyn ToSql],
+) -> Result<Option<T>, Error> {
+	conn.query_row(sql, params, |row| row.get(0))
+		.optional()
+		.map_err(db_error)
+}
+
+fn execute(conn: &Connection, sql: &str, params: &[&dyn ToSql]) -> Result<usize, Error> {
+	conn.prepare_cached(sql)
+		.and_then(|mut stmt| stmt.execute(params))
+		.map_err(db_error)
+}
+
+fn outputs(conn: &Connection) -> Result<Vec<OutputData>, Error> {
+	query_records(
+		conn,
+		"SELECT data FROM outputs ORDER BY key_id, mmr_index",
+		NO_PARAMS,
+	)
+}
+
+fn get_output(
+	conn: &Connection,
+	id: &Identifier,
+	mmr_index: &Option<u64>,
+) -> Result<OutputData, Error> {
+	query_record(
+		conn,
+		"SELECT data FROM outputs WHERE key_id = ?1 AND mmr_index IS ?2",
+		params![id.to_hex(), mmr_index.map(|i| i as i64)],
+	)?
+	.ok_or_else(|| ErrorKind::Backend(format!("DB Not Found Error: Key Id: {}", id)).into())
+}
+
+fn put_output(conn: &Connection, out: &OutputData) -> Result<(), Error> {
+	delete_output(conn, &out.key_id, &out.mmr_index)?;
+	execute(
+		conn,
+		"INSERT INTO outputs (key_id, mmr_index, root_key_id, n_child, commitment, value, status, \
+		 height, lock_height, is_coinbase, tx_log_entry, data) \
+		 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+		params![
+			out.key_id.to_hex(),
+			out.mmr_index.map(|i| i as i64),
+			out.root_key_id.to_hex(),
+			out.n_child,
+			out.commit,
+			out.value as i64,
+			out.status.to_string(),
+			out.height as i64,
+			out.lock_height as i64,
+			out.is_coinbase,
+			out.tx_log_entry,
+			to_json(out)?,
+		],
+	)?;
+	Ok(())
+}
+
+fn delete_output(conn: &Connection, id: &Identifier, mmr_index: &Option<u64>) -> Result<(), Error> {
+	execute(
+		conn,
+		"DELETE FROM outputs WHERE key_id = ?1 AND mmr_index IS ?2",
+		params![id.to_hex(), mmr_index.map(|i| i as i64)],
+	)?;
+	Ok(())
+}
+
+fn tx_log_entries(conn: &Connection) -> Result<Vec<TxLogEntry>, Error> {
+	query_records(
+		conn,
+		"SELECT data FROM tx_log ORDER BY parent_key_id, id",
+		NO_PARAMS,
+	)
+}
+
+fn put_tx_log_entry(
+	conn: &Connection,
+	tx: &TxLogEntry,
+	parent_id: &Identifier,
+) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO tx_log (parent_key_id, id, tx_slate_id, tx_type, address, \
+		 creation_ts, confirmation_ts, confirmed, output_height, num_inputs, num_outputs, \
+		 amount_credited, amount_debited, fee, kernel_excess, stored_tx, data) \
+		 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+		params![
+			parent_id.to_hex(),
+			tx.id,
+			tx.tx_slate_id.map(|id| id.to_string()),
+			format!("{:?}", tx.tx_type),
+			tx.address,
+			tx.creation_ts.to_rfc3339(),
+			tx.confirmation_ts.map(|ts| ts.to_rfc3339()),
+			tx.confirmed,
+			tx.output_height as i64,
+			tx.num_inputs as i64,
+			tx.num_outputs as i64,
+			tx.amount_credited as i64,
+			tx.amount_debited as i64,
+			tx.fee.map(|f| f as i64),
+			tx.kernel_excess.map(|e| util::to_hex(&e.0)),
+			tx.stored_tx,
+			to_json(tx)?,
+		],
+	)?;
+	Ok(())
+}
+
+fn acct_paths(conn: &Connection) -> Result<Vec<AcctPathMapping>, Error> {
+	query_records(conn, "SELECT data FROM accounts ORDER BY label", NO_PARAMS)
+}
+
+fn put_acct_path(conn: &Connection, mapping: &AcctPathMapping) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO accounts (label, path, data) VALUES (?1, ?2, ?3)",
+		params![mapping.label, mapping.path.to_hex(), to_json(mapping)?],
+	)?;
+	Ok(())
+}
+
+/// Counter of the account, stored in the table with (parent_key_id, <column>) columns
+fn get_counter(
+	conn: &Connection,
+	table: &str,
+	column: &str,
+	parent_id: &Identifier,
+) -> Result<u64, Error> {
+	let value: Option<i64> = query_value(
+		conn,
+		&format!("SELECT {} FROM {} WHERE parent_key_id = ?1", column, table),
+		params![parent_id.to_hex()],
+	)?;
+	Ok(value.unwrap_or(0) as u64)
+}
+
+fn put_counter(
+	conn: &Connection,
+	table: &str,
+	column: &str,
+	parent_id: &Identifier,
+	value: u64,
+) -> Result<(), Error> {
+	execute(
+		conn,
+		&format!(
+			"INSERT OR REPLACE INTO {} (parent_key_id, {}) VALUES (?1, ?2)",
+			table, column
+		),
+		params![parent_id.to_hex(), value as i64],
+	)?;
+	Ok(())
+}
+
+fn put_scanned_block(conn: &Connection, block: &ScannedBlockInfo) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO scanned_blocks (height, hash) VALUES (?1, ?2)",
+		params![block.height as i64, block.hash],
+	)?;
+	Ok(())
+}
+
+/// Context is expected to be XOR-masked already
+fn put_private_context(
+	conn: &Connection,
+	slate_id: &[u8],
+	participant_id: usize,
+	ctx: &Context,
+) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO private_contexts (slate_id, participant_id, data) \
+		 VALUES (?1, ?2, ?3)",
+		params![slate_id, participant_id as i64, to_json(ctx)?],
+	)?;
+	Ok(())
+}
+
+fn put_stored_tx(conn: &Connection, name: &str, tx_hex: &str) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO stored_txs (name, tx) VALUES (?1, ?2)",
+		params![name, tx_hex],
+	)?;
+	Ok(())
+}
+
+fn put_tx_proof(conn: &Connection, slate_id: &str, proof_json: &str) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO tx_proofs (slate_id, proof) VALUES (?1, ?2)",
+		params![slate_id, proof_json],
+	)?;
+	Ok(())
+}
+
+fn put_output_metadata(conn: &Connection, metadata: &OutputMetadata) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO output_metadata (commitment, label, frozen, data) \
+		 VALUES (?1, ?2, ?3, ?4)",
+		params![
+			metadata.commit,
+			metadata.label,
+			metadata.frozen,
+			to_json(metadata)?
+		],
+	)?;
+	Ok(())
+}
+
+fn put_multisig_account(conn: &Connection, account: &MultisigAccount) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO multisig_accounts (id, amount, data) VALUES (?1, ?2, ?3)",
+		params![
+			account.id.to_string(),
+			account.amount as i64,
+			to_json(account)?
+		],
+	)?;
+	Ok(())
+}
+
+fn put_scheduled_payment(conn: &Connection, payment: &ScheduledPayment) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO scheduled_payments (id, amount, data) VALUES (?1, ?2, ?3)",
+		params![
+			payment.id.to_string(),
+			payment.amount as i64,
+			to_json(payment)?
+		],
+	)?;
+	Ok(())
+}
+
+fn put_contact(conn: &Connection, contact: &Contact) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO contacts (name, address, data) VALUES (?1, ?2, ?3)",
+		params![contact.name, contact.address, to_json(contact)?],
+	)?;
+	Ok(())
+}
+
+fn put_payment_request(conn: &Connection, request: &PaymentRequest) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO payment_requests (id, amount, address, data) \
+		 VALUES (?1, ?2, ?3, ?4)",
+		params![
+			request.id.to_string(),
+			request.amount as i64,
+			request.address,
+			to_json(request)?
+		],
+	)?;
+	Ok(())
+}
+
+fn put_event_state(conn: &Connection, state: &WalletEventState) -> Result<(), Error> {
+	execute(
+		conn,
+		"INSERT OR REPLACE INTO event_state (id, data) VALUES (0, ?1)",
+		params![to_json(state)?],
+	)?;
+	Ok(())
+}
+
+fn tx_from_hex(tx_hex: &str) -> Result<Transaction, Error> {
+	let tx_bin = util::from_hex(tx_hex.trim()).map_err(|e| {
+		ErrorKind::StoredTransactionError(format!("Unable to decode the data, {}", e))
+	})?;
+	Ok(
+		ser::deserialize(&mut &tx_bin[..], ser::ProtocolVersion(1)).map_err(|e| {
+			ErrorKind::StoredTransactionError(format!("Unable to deserialize the data, {}", e))
+		})?,
+	)
+}
+
+/// Files of the directory as (file name, content), for the files with one of the extensions
+fn read_data_files(dir: &Path, extensions: &[&str]) -> Result<Vec<(String, String)>, Error> {
+	let io_err = |e: std::io::Error| ErrorKind::IO(format!("Unable to read {:?}, {}", dir, e));
+	let mut files = vec![];
+	if !dir.is_dir() {
+		return Ok(files);
+	}
+	for entry in fs::read_dir(dir).map_err(io_err)? {
+		let path = entry.map_err(io_err)?.path();
+		let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+		if !path.is_file() || !extensions.contains(&ext) {
+			continue;
+		}
+		if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+			files.push((name.to_owned(), fs::read_to_string(&path).map_err(io_err)?));
+		}
+	}
+	Ok(files)
+}
+
+/// Copy the LMDB wallet DB, the saved transactions and the payment proofs into a new SQLite
+/// DB in the same data directory. LMDB data is left as is. Returns the path of the SQLite DB.
+pub fn migrate_from_lmdb<C, K>(data_file_dir: &str, n_client: C) -> Result<String, Error>
+where
+	C: NodeClient,
+	K: Keychain,
+{
+	let db_file = sqlite_db_path(data_file_dir);
+	if db_file.exists() {
+		return Err(ErrorKind::GenericError(format!(
+			"SQLite wallet DB already exists at {:?}",
+			db_file
+		))
+		.into());
+	}
+	let content = LMDBBackend::<C, K>::new(data_file_dir, n_client)?.export()?;
+
+	// DB is built in the temporary file, so the failed migration leaves nothing behind
+	let tmp_file = db_file.with_extension("sqlite.tmp");
+	let _ = fs::remove_file(&tmp_file);
+	let res = (|| -> Result<(), Error> {
+		let conn = open_db(&tmp_file)?;
+		conn.execute_batch("BEGIN").map_err(db_error)?;
+		for out in &content.outputs {
+			put_output(&conn, out)?;
+		}
+		for tx in &content.tx_log {
+			put_tx_log_entry(&conn, tx, &tx.parent_key_id)?;
+		}
+		for acct in &content.accounts {
+			put_acct_path(&conn, acct)?;
+		}
+		for (parent_id, index) in &content.child_indexes {
+			put_counter(&conn, "child_indexes", "child_index", parent_id, *index)?;
+		}
+		for (parent_id, id) in &content.tx_log_ids {
+			put_counter(&conn, "tx_log_ids", "next_id", parent_id, *id)?;
+		}
+		for (parent_id, height) in &content.confirmed_heights {
+			put_counter(&conn, "confirmed_heights", "height", parent_id, *height)?;
+		}
+		for block in &content.scanned_blocks {
+			put_scanned_block(&conn, block)?;
+		}
+		for (slate_id, participant_id, ctx) in &content.private_contexts {
+			put_private_context(&conn, slate_id, *participant_id, ctx)?;
+		}
+		for metadata in &content.output_metadata {
+			put_output_metadata(&conn, metadata)?;
+		}
+		for account in &content.multisig_accounts {
+			put_multisig_account(&conn, account)?;
+		}
+		for payment in &content.scheduled_payments {
+			put_scheduled_payment(&conn, payment)?;
+		}
+		for contact in &content.contacts {
+			put_contact(&conn, contact)?;
+		}
+		for request in &content.payment_requests {
+			put_payment_request(&conn, request)?;
+		}
+		if let Some(state) = &content.event_state {
+			put_event_state(&conn, state)?;
+		}
+
+		let data_dir = Path::new(data_file_dir);
+		for (name, tx_hex) in read_data_files(&data_dir.join(TX_SAVE_DIR), &["mwctx", "grintx"])? {
+			put_stored_tx(&conn, &name, tx_hex.trim())?;
+		}
+		for (name, proof) in read_data_files(&data_dir.join(TX_PROOF_SAVE_DIR), &["proof"])? {
+			put_tx_proof(&conn, name.trim_end_matches(".proof"), &proof)?;
+		}
+		conn.execute_batch("COMMIT").map_err(db_error)?;
+		Ok(())
+	})();
+
+	match res {
+		Ok(()) => {
+			fs::rename(&tmp_file, &db_file)
+				.map_err(|e| ErrorKind::IO(format!("Unable to create SQLite wallet DB, {}", e)))?;
+			info!(
+				"Wallet DB at {} is copied into {:?}",
+				data_file_dir, db_file
+			);
+			Ok(db_file.to_str().unwrap_or_default().to_owned())
+		}
+		Err(e) => {
+			let _ = fs::remove_file(&tmp_file);
+			Err(e)
+		}
+	}
+}
+
+pub struct SqliteBackend<'ck, C, K>
+where
+	C: NodeClient + 'ck,
+	K: Keychain + 'ck,
+{
+	conn: Mutex<Connection>,
+	data_file_dir: String,
+	/// Keychain
+	pub keychain: Option<K>,
+	/// Check value for XORed keychain seed
+	pub master_checksum: Box<Option<Blake2bResult>>,
+	/// View key of the watch-only wallet
+	view_key: Option<ViewKey>,
+	/// Update and scan settings
+	scan_config: ScanConfig,
+	/// Parent path to use by default for output operations
+	parent_key_id: Identifier,
+	/// wallet to node client
+	w2n_client: C,
+	///phantom
+	_phantom: &'ck PhantomData<C>,
+}
+
+impl<'ck, C, K> SqliteBackend<'ck, C, K>
+where
+	C: NodeClient + 'ck,
+	K: Keychain + 'ck,
+{
+	pub fn new(data_file_dir: &str, n_client: C) -> Result<Self, Error> {
+		fs::create_dir_all(data_file_dir)
+			.map_err(|e| ErrorKind::IO(format!("Unable to create wallet data directory, {}", e)))?;
+		let conn = open_db(&sqlite_db_path(data_file_dir))?;
+
+		// Make sure default wallet derivation path always exists
+		let default_account = AcctPathMapping {
+			label: "default".to_owned(),
+			path: SqliteBackend::<C, K>::default_path(),
+		};
+		put_acct_path(&conn, &default_account)?;
+
+		Ok(SqliteBackend {
+			conn: Mutex::new(conn),
+			data_file_dir: data_file_dir.to_owned(),
+			keychain: None,
+			master_checksum: Box::new(None),
+			view_key: None,
+			scan_config: ScanConfig::default(),
+			parent_key_id: SqliteBackend::<C, K>::default_path(),
+			w2n_client: n_client,
+			_phantom: &PhantomData,
+		})
+	}
+
+	fn default_path() -> Identifier {
+		// return the default parent wallet path, corresponding to the default account
+		// in the BIP32 spec. Parent is account 0 at level 2, child output identifiers
+		// are all at level 3
+		ExtKeychain::derive_key_id(2, 0, 0, 0, 0)
+	}
+
+	fn new_batch<'a>(&'a mut self, keychain: Option<K>) -> Result<Batch<'a, K>, Error> {
+		let max_reorg_depth = self.scan_config.max_reorg_depth;
+		let conn = self.conn.get_mut();
+		conn.execute_batch("BEGIN IMMEDIATE").map_err(db_error)?;
+		Ok(Batch {
+			conn,
+			keychain,
+			max_reorg_depth,
+			committed: Cell::new(false),
+		})
+	}
+}
+
+impl<'ck, C, K> WalletBackend<'ck, C, K> for SqliteBackend<'ck, C, K>
+where
+	C: NodeClient + 'ck,
+	K: Keychain + 'ck,
+{
+	fn get_data_file_dir(&self) -> &str {
+		&self.data_file_dir
+	}
+
+	fn set_keychain(
+		&mut self,
+		mut k: Box<K>,
+		mask: bool,
+		use_test_rng: bool,
+	) -> Result<Option<SecretKey>, Error> {
+		// store hash of master key, so it can be verified later after unmasking
+		let root_key = k.derive_key(0, &K::root_key_id(), SwitchCommitmentType::Regular)?;
+		let mut hasher = Blake2b::new(SECRET_KEY_SIZE);
+		hasher.update(&root_key.0[..]);
+		self.master_checksum = Box::new(Some(hasher.finalize()));
+
+		let mask_value = match mask {
+			true => {
+				// Random value that must be XORed against the stored wallet seed
+				// before it is used
+				let mask_value = match use_test_rng {
+					true => {
+						let mut test_rng = StepRng::new(1_234_567_890_u64, 1);
+						secp::key::SecretKey::new(&mut test_rng)
+					}
+					false => secp::key::SecretKey::new(&mut thread_rng()),
+				};
+				k.mask_master_key(&mask_value)?;
+				Some(mask_value)
+			}
+			false => None,
+		};
+
+		self.keychain = Some(*k);
+		Ok(mask_value)
+	}
+
+	fn close(&mut self) -> Result<(), Error> {
+		self.keychain = None;
+		self.view_key = None;
+		Ok(())
+	}
+
+	fn set_view_key(&mut self, view_key: ViewKey) {
+		self.view_key = Some(view_key);
+	}
+
+	fn view_key(&self) -> Option<ViewKey> {
+		self.view_key.clone()
+	}
+
+	fn set_scan_config(&mut self, config: ScanConfig) {
+		self.scan_config = config;
+	}
+
+	fn scan_config(&self) -> ScanConfig {
+		self.scan_config.clone()
+	}
+
+	fn keychain(&self, mask: Option<&SecretKey>) -> Result<K, Error> {
+		match self.keychain.as_ref() {
+			Some(k) => {
+				let mut k_masked = k.clone();
+				if let Some(m) = mask {
+					k_masked.mask_master_key(m)?;
+				}
+				// Check if master seed is what is expected (especially if it's been xored)
+				let root_key =
+					k_masked.derive_key(0, &K::root_key_id(), SwitchCommitmentType::Regular)?;
+				let mut hasher = Blake2b::new(SECRET_KEY_SIZE);
+				hasher.update(&root_key.0[..]);
+				if *self.master_checksum != Some(hasher.finalize()) {
+					error!("Supplied keychain mask is invalid");
+					return Err(ErrorKind::InvalidKeychainMask.into());
+				}
+				Ok(k_masked)
+			}
+			None => match self.view_key {
+				Some(_) => Err(ErrorKind::WatchOnlyWallet.into()),
+				None => Err(ErrorKind::KeychainDoesntExist.into()),
+			},
+		}
+	}
+
+	fn w2n_client(&mut self) -> &mut C {
+		&mut self.w2n_client
+	}
+
+	fn calc_commit_for_cache(
+		&mut self,
+		keychain_mask: Option<&SecretKey>,
+		amount: u64,
+		id: &Identifier,
+	) -> Result<Option<String>, Error> {
+		Ok(Some(util::to_hex(
+			&self
+				.keychain(keychain_mask)?
+				.commit(amount, &id, SwitchCommitmentType::Regular)?
+				.0,
+		)))
+	}
+
+	fn set_parent_key_id_by_name(&mut self, label: &str) -> Result<(), Error> {
+		let label = label.to_owned();
+		let res = self.acct_path_iter().find(|l| l.label == label);
+		if let Some(a) = res {
+			self.set_parent_key_id(a.path);
+			Ok(())
+		} else {
+			Err(ErrorKind::UnknownAccountLabel(label).into())
+		}
+	}
+
+	fn set_parent_key_id(&mut self, id: Identifier) {
+		self.parent_key_id = id;
+	}
+
+	fn parent_key_id(&mut self) -> Identifier {
+		self.parent_key_id.clone()
+	}
+
+	fn get(&self, id: &Identifier, mmr_index: &Option<u64>) -> Result<OutputData, Error> {
+		get_output(&self.conn.lock(), id, mmr_index)
+	}
+
+	fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = OutputData> + 'a> {
+		Box::new(outputs(&self.conn.lock()).unwrap().into_iter())
+	}
+
+	fn tx_log_iter<'a>(&'a self) -> Box<dyn Iterator<Item = TxLogEntry> + 'a> {
+		Box::new(tx_log_entries(&self.conn.lock()).unwrap().into_iter())
+	}
+
+	fn get_private_context(
+		&mut self,
+		keychain_mask: Option<&SecretKey>,
+		slate_id: &[u8],
+		participant_id: usize,
+	) -> Result<Context, Error> {
+		let (blind_xor_key, nonce_xor_key) =
+			private_ctx_xor_keys(&self.keychain(keychain_mask)?, slate_id)?;
+
+		let ctx: Option<Context> = query_record(
+			&self.conn.lock(),
+			"SELECT data FROM private_contexts WHERE slate_id = ?1 AND participant_id = ?2",
+			params![slate_id, participant_id as i64],
+		)?;
+		let mut ctx = ctx.ok_or_else(|| {
+			ErrorKind::Backend(format!(
+				"DB Not Found Error: Slate id: {:x?}",
+				slate_id.to_vec()
+			))
+		})?;
+
+		for i in 0..SECRET_KEY_SIZE {
+			ctx.sec_key.0[i] ^= blind_xor_key[i];
+			ctx.sec_nonce.0[i] ^= nonce_xor_key[i];
+		}
+
+		Ok(ctx)
+	}
+
+	fn acct_path_iter<'a>(&'a self) -> Box<dyn Iterator<Item = AcctPathMapping> + 'a> {
+		Box::new(acct_paths(&self.conn.lock()).unwrap().into_iter())
+	}
+
+	fn get_acct_path(&self, label: String) -> Result<Option<AcctPathMapping>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM accounts WHERE label = ?1",
+			params![label],
+		)
+	}
+
+	fn store_tx(&self, uuid: &str, tx: &Transaction) -> Result<(), Error> {
+		let tx_hex = util::to_hex(&ser::ser_vec(tx, ser::ProtocolVersion(1))?);
+		put_stored_tx(&self.conn.lock(), &format!("{}.mwctx", uuid), &tx_hex)
+	}
+
+	fn get_stored_tx(&self, entry: &TxLogEntry) -> Result<Option<Transaction>, Error> {
+		let name = match entry.stored_tx.as_ref() {
+			Some(n) => n,
+			None => return Ok(None),
+		};
+		let tx_hex: Option<String> = query_value(
+			&self.conn.lock(),
+			"SELECT tx FROM stored_txs WHERE name = ?1",
+			params![name],
+		)?;
+		match tx_hex {
+			Some(tx_hex) => Ok(Some(tx_from_hex(&tx_hex)?)),
+			None => Err(ErrorKind::StoredTransactionError(format!(
+				"Transaction {} is not found",
+				name
+			)))?,
+		}
+	}
+
+	// mwc need to suport extentions mwctx and grintx because 2.4 version has grintx, 3.0 mwctx
+	fn get_stored_tx_by_uuid(&self, uuid: &str) -> Result<Transaction, Error> {
+		let tx_hex: Option<String> = query_value(
+			&self.conn.lock(),
+			"SELECT tx FROM stored_txs WHERE name IN (?1, ?2) ORDER BY name DESC",
+			params![format!("{}.mwctx", uuid), format!("{}.grintx", uuid)],
+		)?;
+		match tx_hex {
+			Some(tx_hex) => tx_from_hex(&tx_hex),
+			None => Err(ErrorKind::StoredTransactionError(format!(
+				"Transaction {} is not found",
+				uuid
+			)))?,
+		}
+	}
+
+	fn load_stored_tx(&self, path: &str) -> Result<Transaction, Error> {
+		let content = fs::read_to_string(path)?;
+		tx_from_hex(&content)
+	}
+
+	fn store_tx_proof(&self, uuid: &str, proof: &TxProof) -> Result<(), Error> {
+		put_tx_proof(&self.conn.lock(), uuid, &to_json(proof)?)
+	}
+
+	fn get_stored_tx_proof(&self, uuid: &str) -> Result<TxProof, Error> {
+		let proof: Option<String> = query_value(
+			&self.conn.lock(),
+			"SELECT proof FROM tx_proofs WHERE slate_id = ?1",
+			params![uuid],
+		)?;
+		match proof {
+			Some(p) => Ok(serde_json::from_str(&p).map_err(|e| {
+				ErrorKind::TxProofGenericError(format!("Unable to Build TxProof from Json, {}", e))
+			})?),
+			None => Err(ErrorKind::TransactionHasNoProof(uuid.to_string()).into()),
+		}
+	}
+
+	fn batch<'a>(
+		&'a mut self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Box<dyn WalletOutputBatch<K> + 'a>, Error> {
+		let keychain = self.keychain(keychain_mask)?;
+		Ok(Box::new(self.new_batch(Some(keychain))?))
+	}
+
+	fn batch_no_mask<'a>(&'a mut self) -> Result<Box<dyn WalletOutputBatch<K> + 'a>, Error> {
+		Ok(Box::new(self.new_batch(None)?))
+	}
+
+	fn current_child_index<'a>(&mut self, parent_key_id: &Identifier) -> Result<u32, Error> {
+		let index = get_counter(
+			self.conn.get_mut(),
+			"child_indexes",
+			"child_index",
+			parent_key_id,
+		)?;
+		Ok(index as u32)
+	}
+
+	fn next_child<'a>(
+		&mut self,
+		keychain_mask: Option<&SecretKey>,
+		parent_key_id: Option<Identifier>,
+	) -> Result<Identifier, Error> {
+		let parent_key_id = parent_key_id.unwrap_or(self.parent_key_id.clone());
+		let mut deriv_idx = get_counter(
+			self.conn.get_mut(),
+			"child_indexes",
+			"child_index",
+			&self.parent_key_id,
+		)? as u32;
+		let mut return_path = self.parent_key_id.to_path();
+		return_path.depth += 1;
+		return_path.path[return_path.depth as usize - 1] = ChildNumber::from(deriv_idx);
+		deriv_idx += 1;
+		let mut batch = self.batch(keychain_mask)?;
+		batch.save_child_index(&parent_key_id, deriv_idx)?;
+		batch.commit()?;
+		Ok(Identifier::from_path(&return_path))
+	}
+
+	fn last_confirmed_height<'a>(&mut self) -> Result<u64, Error> {
+		get_counter(
+			self.conn.get_mut(),
+			"confirmed_heights",
+			"height",
+			&self.parent_key_id,
+		)
+	}
+
+	fn last_scanned_blocks<'a>(&mut self) -> Result<Vec<ScannedBlockInfo>, Error> {
+		let mut stmt = self
+			.conn
+			.get_mut()
+			.prepare_cached("SELECT height, hash FROM scanned_blocks ORDER BY height DESC")
+			.map_err(db_error)?;
+		let rows = stmt
+			.query_map(NO_PARAMS, |row| {
+				Ok(ScannedBlockInfo::new(
+					row.get::<_, i64>(0)? as u64,
+					row.get(1)?,
+				))
+			})
+			.map_err(db_error)?;
+		let mut blocks = vec![];
+		for b in rows {
+			blocks.push(b.map_err(db_error)?);
+		}
+
+		debug!("last_scanned_blocks: {:?}", blocks);
+
+		Ok(blocks)
+	}
+
+	fn get_outputs_by_commit(&self, commit: &str) -> Result<Vec<OutputData>, Error> {
+		query_records(
+			&self.conn.lock(),
+			"SELECT data FROM outputs WHERE commitment = ?1 ORDER BY key_id, mmr_index",
+			params![commit],
+		)
+	}
+
+	fn get_tx_log_by_slate_id(&self, slate_id: &Uuid) -> Result<Vec<TxLogEntry>, Error> {
+		query_records(
+			&self.conn.lock(),
+			"SELECT data FROM tx_log WHERE tx_slate_id = ?1 ORDER BY parent_key_id, id",
+			params![slate_id.to_string()],
+		)
+	}
+
+	fn get_tx_log_by_kernel_excess(
+		&self,
+		excess: &pedersen::Commitment,
+	) -> Result<Vec<TxLogEntry>, Error> {
+		query_records(
+			&self.conn.lock(),
+			"SELECT data FROM tx_log WHERE kernel_excess = ?1 ORDER BY parent_key_id, id",
+			params![util::to_hex(&excess.0)],
+		)
+	}
+
+	fn get_output_metadata(&self, commit: &str) -> Result<Option<OutputMetadata>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM output_metadata WHERE commitment = ?1",
+			params![commit],
+		)
+	}
+
+	fn output_metadata_iter<'a>(&'a self) -> Box<dyn Iterator<Item = OutputMetadata> + 'a> {
+		let records: Vec<OutputMetadata> = query_records(
+			&self.conn.lock(),
+			"SELECT data FROM output_metadata ORDER BY commitment",
+			NO_PARAMS,
+		)
+		.unwrap();
+		Box::new(records.into_iter())
+	}
+
+	fn get_multisig_account(&self, id: &Uuid) -> Result<Option<MultisigAccount>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM multisig_accounts WHERE id = ?1",
+			params![id.to_string()],
+		)
+	}
+
+	fn multisig_account_iter<'a>(&'a self) -> Box<dyn Iterator<Item = MultisigAccount> + 'a> {
+		let records: Vec<MultisigAccount> = query_records(
+			&self.conn.lock(),
+			"SELECT data FROM multisig_accounts ORDER BY id",
+			NO_PARAMS,
+		)
+		.unwrap();
+		Box::new(records.into_iter())
+	}
+
+	fn get_scheduled_payment(&self, id: &Uuid) -> Result<Option<ScheduledPayment>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM scheduled_payments WHERE id = ?1",
+			params![id.to_string()],
+		)
+	}
+
+	fn scheduled_payment_iter<'a>(&'a self) -> Box<dyn Iterator<Item = ScheduledPayment> + 'a> {
+		let records: Vec<ScheduledPayment> = query_records(
+			&self.conn.lock(),
+			"SELECT data FROM scheduled_payments ORDER BY id",
+			NO_PARAMS,
+		)
+		.unwrap();
+		Box::new(records.into_iter())
+	}
+
+	fn get_contact(&self, name: &str) -> Result<Option<Contact>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM contacts WHERE name = ?1",
+			params![name],
+		)
+	}
+
+	fn contact_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Contact> + 'a> {
+		let records: Vec<Contact> = query_records(
+			&self.conn.lock(),
+			"SELECT data FROM contacts ORDER BY name",
+			NO_PARAMS,
+		)
+		.unwrap();
+		Box::new(records.into_iter())
+	}
+
+	fn get_scan_checkpoint(&self) -> Result<Option<ScanCheckpoint>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM scan_checkpoint",
+			NO_PARAMS,
+		)
+	}
+
+	fn scan_checkpoint_outputs(&self) -> Result<Vec<OutputResult>, Error> {
+		query_records(
+			&self.conn.lock(),
+			"SELECT data FROM scan_outputs ORDER BY mmr_index",
+			NO_PARAMS,
+		)
+	}
+
+	fn get_event_state(&self) -> Result<Option<WalletEventState>, Error> {
+		query_record(&self.conn.lock(), "SELECT data FROM event_state", NO_PARAMS)
+	}
+
+	fn get_payment_request(&self, id: &Uuid) -> Result<Option<PaymentRequest>, Error> {
+		query_record(
+			&self.conn.lock(),
+			"SELECT data FROM payment_requests WHERE id = ?1",
+			params![id.to_string()],
+		)
+	}
+
+	fn payment_request_iter<'a>(&'a self) -> Box<dyn Iterator<Item = PaymentRequest> + 'a> {
+		let records: Vec<PaymentRequest> = query_records(
+			&self.conn.lock(),
+			"SELECT data FROM payment_requests ORDER BY id",
+			NO_PARAMS,
+		)
+		.unwrap();
+		Box::new(records.into_iter())
+	}
+}
+
+/// An atomic batch, the SQLite transaction is committed all at once or
+/// rolled back when the batch is dropped without the commit.
+pub struct Batch<'a, K>
+where
+	K: Keychain,
+{
+	conn: &'a Connection,
+	/// Keychain
+	keychain: Option<K>,
+	/// Scanned blocks within this depth are kept
+	max_reorg_depth: u64,
+	committed: Cell<bool>,
+}
+
+impl<'a, K> Drop for Batch<'a, K>
+where
+	K: Keychain,
+{
+	fn drop(&mut self) {
+		if !self.committed.get() {
+			if let Err(e) = self.conn.execute_batch("ROLLBACK") {
+				error!("Unable to roll back the wallet DB transaction, {}", e);
+			}
+		}
+	}
+}
+
+#[allow(missing_docs)]
+impl<'a, K> WalletOutputBatch<K> for Batch<'a, K>
+where
+	K: Keychain,
+{
+	fn keychain(&mut self) -> &mut K {
+		self.keychain.as_mut().unwrap()
+	}
+
+	fn save(&mut self, out: OutputData) -> Result<(), Error> {
+		put_output(self.conn, &out)
+	}
+
+	fn get(&self, id: &Identifier, mmr_index: &Option<u64>) -> Result<OutputData, Error> {
+		get_output(self.conn, id, mmr_index)
+	}
+
+	fn iter(&self) -> Box<dyn Iterator<Item = OutputData>> {
+		Box::new(outputs(self.conn).unwrap().into_iter())
+	}
+
+	fn delete(&mut self, id: &Identifier, mmr_index: &Option<u64>) -> Result<(), Error> {
+		delete_output(self.conn, id, mmr_index)
+	}
+
+	fn next_tx_log_id(&mut self, parent_key_id: &Identifier) -> Result<u32, Error> {
+		let last_tx_log_id = get_counter(self.conn, "tx_log_ids", "next_id", parent_key_id)?;
+		put_counter(
+			self.conn,
+			"tx_log_ids",
+			"next_id",
+			parent_key_id,
+			last_tx_log_id + 1,
+		)?;
+		Ok(last_tx_log_id as u32)
+	}
+
+	fn tx_log_iter(&self) -> Box<dyn Iterator<Item = TxLogEntry>> {
+		Box::new(tx_log_entries(self.conn).unwrap().into_iter())
+	}
+
+	fn save_last_confirmed_height(
+		&mut self,
+		parent_key_id: &Identifier,
+		height: u64,
+	) -> Result<(), Error> {
+		put_counter(
+			self.conn,
+			"confirmed_heights",
+			"height",
+			parent_key_id,
+			height,
+		)
+	}
+
+	fn save_last_scanned_blocks(
+		&mut self,
+		first_scanned_block_height: u64,
+		block_info: &Vec<ScannedBlockInfo>,
+	) -> Result<(), Error> {
+		debug_assert!(block_info.first().unwrap().height >= block_info.last().unwrap().height);
+
+		// Cleaning up the head blocks...
+		execute(
+			self.conn,
+			"DELETE FROM scanned_blocks WHERE height >= ?1",
+			params![first_scanned_block_height as i64],
+		)?;
+
+		// Inserting the new data
+		for bl_info in block_info {
+			put_scanned_block(self.conn, bl_info)?;
+		}
+
+		// Same thinning as with LMDB, the blocks within the max reorg depth are kept and below
+		// them the gap between the kept blocks doubles with the depth
+		let mut heights: Vec<u64> = {
+			let mut stmt = self
+				.conn
+				.prepare_cached("SELECT height FROM scanned_blocks ORDER BY height")
+				.map_err(db_error)?;
+			let rows = stmt
+				.query_map(NO_PARAMS, |row| row.get::<_, i64>(0))
+				.map_err(db_error)?;
+			let mut heights = vec![];
+			for h in rows {
+				heights.push(h.map_err(db_error)? as u64);
+			}
+			heights
+		};
+
+		let mut step = 4;
+		let mut start = heights.pop().unwrap_or(1);
+		let keep_height = start.saturating_sub(self.max_reorg_depth);
+
+		while let Some(h) = heights.pop() {
+			assert!(h < start);
+			if h >= keep_height {
+				start = h;
+			} else if start - h < step {
+				execute(
+					self.conn,
+					"DELETE FROM scanned_blocks WHERE height = ?1",
+					params![h as i64],
+				)?;
+			} else {
+				start = h;
+				step *= 2;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn save_child_index(&mut self, parent_id: &Identifier, child_n: u32) -> Result<(), Error> {
+		put_counter(
+			self.conn,
+			"child_indexes",
+			"child_index",
+			parent_id,
+			child_n as u64,
+		)
+	}
+
+	fn save_tx_log_entry(
+		&mut self,
+		tx_in: TxLogEntry,
+		parent_id: &Identifier,
+	) -> Result<(), Error> {
+		put_tx_log_entry(self.conn, &tx_in, parent_id)
+	}
+
+	fn rename_acct_path(
+		&mut self,
+		accounts: Vec<AcctPathMapping>,
+		old_name: &str,
+		new_name: &str,
+	) -> Result<(), Error> {
+		for acc in accounts {
+			if acc.label == old_name {
+				let mut nacc = acc.clone();
+				execute(
+					self.conn,
+					"DELETE FROM accounts WHERE label = ?1",
+					params![acc.label],
+				)?;
+				nacc.label = new_name.to_string();
+				put_acct_path(self.conn, &nacc)?;
+				break;
+			}
+		}
+		println!("rename acct from '{}' to '{}'", old_name, new_name);
+		Ok(())
+	}
+
+	fn save_acct_path(&mut self, mapping: AcctPathMapping) -> Result<(), Error> {
+		put_acct_path(self.conn, &mapping)
+	}
+
+	fn acct_path_iter(&self) -> Box<dyn Iterator<Item = AcctPathMapping>> {
+		Box::new(acct_paths(self.conn).unwrap().into_iter())
+	}
+
+	fn lock_output(&mut self, out: &mut OutputData) -> Result<(), Error> {
+		out.lock();
+		self.save(out.clone())
+	}
+
+	fn save_output_metadata(&mut self, metadata: &OutputMetadata) -> Result<(), Error> {
+		put_output_metadata(self.conn, metadata)
+	}
+
+	fn delete_output_metadata(&mut self, commit: &str) -> Result<(), Error> {
+		execute(
+			self.conn,
+			"DELETE FROM output_metadata WHERE commitment = ?1",
+			params![commit],
+		)?;
+		Ok(())
+	}
+
+	fn save_multisig_account(&mut self, account: &MultisigAccount) -> Result<(), Error> {
+		put_multisig_account(self.conn, account)
+	}
+
+	fn save_scheduled_payment(&mut self, payment: &ScheduledPayment) -> Result<(), Error> {
+		put_scheduled_payment(self.conn, payment)
+	}
+
+	fn delete_scheduled_payment(&mut self, id: &Uuid) -> Result<(), Error> {
+		execute(
+			self.conn,
+			"DELETE FROM scheduled_payments WHERE id = ?1",
+			params![id.to_string()],
+		)?;
+		Ok(())
+	}
+
+	fn save_contact(&mut self, contact: &Contact) -> Result<(), Error> {
+		put_contact(self.conn, contact)
+	}
+
+	fn delete_contact(&mut self, name: &str) -> Result<(), Error> {
+		execute(
+			self.conn,
+			"DELETE FROM contacts WHERE name = ?1",
+			params![name],
+		)?;
+		Ok(())
+	}
+
+	fn save_payment_request(&mut self, request: &PaymentRequest) -> Result<(), Error> {
+		put_payment_request(self.conn, request)
+	}
+
+	fn save_scan_checkpoint(&mut self, checkpoint: &ScanCheckpoint) -> Result<(), Error> {
+		execute(
+			self.conn,
+			"INSERT OR REPLACE INTO scan_checkpoint (id, data) VALUES (0, ?1)",
+			params![to_json(checkpoint)?],
+		)?;
+		Ok(())
+	}
+
+	fn save_scan_checkpoint_output(&mut self, output: &OutputResult) -> Result<(), Error> {
+		execute(
+			self.conn,
+			"INSERT OR REPLACE INTO scan_outputs (mmr_index, data) VALUES (?1, ?2)",
+			params![output.mmr_index as i64, to_json(output)?],
+		)?;
+		Ok(())
+	}
+
+	fn delete_scan_checkpoint(&mut self) -> Result<(), Error> {
+		execute(self.conn, "DELETE FROM scan_outputs", NO_PARAMS)?;
+		execute(self.conn, "DELETE FROM scan_checkpoint", NO_PARAMS)?;
+		Ok(())
+	}
+
+	fn save_event_state(&mut self, state: &WalletEventState) -> Result<(), Error> {
+		put_event_state(self.conn, state)
+	}
+
+	fn save_private_context(
+		&mut self,
+		slate_id: &[u8],
+		participant_id: usize,
+		ctx: &Context,
+	) -> Result<(), Error> {
+		let (blind_xor_key, nonce_xor_key) = private_ctx_xor_keys(self.keychain(), slate_id)?;
+
+		let mut s_ctx = ctx.clone();
+		for i in 0..SECRET_KEY_SIZE {
+			s_ctx.sec_key.0[i] ^= blind_xor_key[i];
+			s_ctx.sec_nonce.0[i] ^= nonce_xor_key[i];
+		}
+
+		put_private_context(self.conn, slate_id, participant_id, &s_ctx)
+	}
+
+	fn delete_private_context(
+		&mut self,
+		slate_id: &[u8],
+		participant_id: usize,
+	) -> Result<(), Error> {
+		let deleted = execute(
+			self.conn,
+			"DELETE FROM private_contexts WHERE slate_id = ?1 AND participant_id = ?2",
+			params![slate_id, participant_id as i64],
+		)?;
+		match deleted {
+			0 => Err(ErrorKind::Backend(format!(
+				"DB Not Found Error: Slate id: {:x?}",
+				slate_id.to_vec()
+			))
+			.into()),
+			_ => Ok(()),
+		}
+	}
+
+	fn commit(&self) -> Result<(), Error> {
+		self.conn.execute_batch("COMMIT").map_err(db_error)?;
+		self.committed.set(true);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::libwallet::{OutputStatus, TxLogEntryType};
+	use crate::test_framework::LocalWalletClient;
+	use std::sync::mpsc::channel;
+
+	type Backend<'a> = SqliteBackend<'a, LocalWalletClient, ExtKeychain>;
+
+	fn client() -> LocalWalletClient {
+		let (tx, _rx) = channel();
+		LocalWalletClient::new("wallet", tx)
+	}
+
+	fn output(n_child: u32, value: u64, commit: &str) -> OutputData {
+		OutputData {
+			root_key_id: ExtKeychain::derive_key_id(2, 0, 0, 0, 0),
+			key_id: ExtKeychain::derive_key_id(3, 0, 0, n_child, 0),
+			n_child,
+			commit: Some(commit.to_owned()),
+			mmr_index: Some(n_child as u64 + 10),
+			value,
+			status: OutputStatus::Unspent,
+			height: 5,
+			lock_height: 0,
+			is_coinbase: false,
+			tx_log_entry: Some(n_child),
+		}
+	}
+
+	fn next_tx_log_id(backend: &mut Backend, parent: &Identifier) -> u64 {
+		get_counter(backend.conn.get_mut(), "tx_log_ids", "next_id", parent).unwrap()
+	}
+
+	#[test]
+	fn sqlite_backend() {
+		let dir = "test_output/sqlite_backend";
+		let _ = fs::remove_dir_all(dir);
+		let keychain = ExtKeychain::from_random_seed(false).unwrap();
+		let mut backend: Backend = SqliteBackend::new(dir, client()).unwrap();
+		backend
+			.set_keychain(Box::new(keychain), false, true)
+			.unwrap();
+		let parent = backend.parent_key_id();
+		let slate_id = Uuid::new_v4();
+		let excess = pedersen::Commitment::from_vec(vec![9; 33]);
+
+		{
+			let mut batch = backend.batch(None).unwrap();
+			batch.save(output(1, 100, "08aa")).unwrap();
+			batch.save(output(2, 200, "08bb")).unwrap();
+			let id = batch.next_tx_log_id(&parent).unwrap();
+			assert_eq!(id, 0);
+			let mut tx = TxLogEntry::new(parent.clone(), TxLogEntryType::TxSent, id);
+			tx.tx_slate_id = Some(slate_id);
+			tx.kernel_excess = Some(excess);
+			tx.amount_debited = 300;
+			batch.save_tx_log_entry(tx, &parent).unwrap();
+			batch.save_child_index(&parent, 3).unwrap();
+			batch.save_last_confirmed_height(&parent, 7).unwrap();
+			batch.commit().unwrap();
+		}
+		assert_eq!(next_tx_log_id(&mut backend, &parent), 1);
+
+		// Dropped batch is rolled back
+		{
+			let mut batch = backend.batch(None).unwrap();
+			batch.save(output(3, 300, "08cc")).unwrap();
+			assert_eq!(batch.iter().count(), 3);
+		}
+		assert_eq!(backend.iter().count(), 2);
+
+		// Updates replace the record
+		let mut out = backend.get(&output(2, 0, "").key_id, &Some(12)).unwrap();
+		assert_eq!(out.value, 200);
+		{
+			let mut batch = backend.batch(None).unwrap();
+			batch.lock_output(&mut out).unwrap();
+			batch.commit().unwrap();
+		}
+		let found = backend.get_outputs_by_commit("08bb").unwrap();
+		assert_eq!(found.len(), 1);
+		assert_eq!(found[0].status, OutputStatus::Locked);
+		assert!(backend.get_outputs_by_commit("08").unwrap().is_empty());
+
+		let txs = backend.get_tx_log_by_slate_id(&slate_id).unwrap();
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].amount_debited, 300);
+		assert_eq!(
+			backend.get_tx_log_by_kernel_excess(&excess).unwrap().len(),
+			1
+		);
+		assert_eq!(backend.current_child_index(&parent).unwrap(), 3);
+		assert_eq!(backend.last_confirmed_height().unwrap(), 7);
+		let next = backend.next_child(None, None).unwrap();
+		assert_eq!(next.to_path().last_path_index(), 3);
+		assert_eq!(backend.current_child_index(&parent).unwrap(), 4);
+		assert_eq!(
+			backend
+				.get_acct_path("default".to_owned())
+				.unwrap()
+				.unwrap()
+				.path,
+			parent
+		);
+
+		// Private context is stored masked and read unmasked
+		let secp = secp::Secp256k1::new();
+		let ctx = Context::new(&secp, &parent, false, true, 0, 100, 0, None);
+		{
+			let mut batch = backend.batch(None).unwrap();
+			batch
+				.save_private_context(slate_id.as_bytes(), 0, &ctx)
+				.unwrap();
+			batch.commit().unwrap();
+		}
+		let stored: Context = query_record(
+			&backend.conn.lock(),
+			"SELECT data FROM private_contexts",
+			NO_PARAMS,
+		)
+		.unwrap()
+		.unwrap();
+		assert_ne!(stored.sec_key, ctx.sec_key);
+		let read = backend
+			.get_private_context(None, slate_id.as_bytes(), 0)
+			.unwrap();
+		assert_eq!(read.sec_key, ctx.sec_key);
+		assert_eq!(read.sec_nonce, ctx.sec_nonce);
+		{
+			let mut batch = backend.batch(None).unwrap();
+			batch
+				.delete_private_context(slate_id.as_bytes(), 0)
+				.unwrap();
+			assert!(batch
+				.delete_private_context(slate_id.as_bytes(), 0)
+				.is_err());
+			batch.commit().unwrap();
+		}
+
+		// Records are in plain tables
+		let unspent: Option<i64> = query_value(
+			&backend.conn.lock(),
+			"SELECT SUM(value) FROM outputs WHERE status = 'Unspent'",
+			NO_PARAMS,
+		)
+		.unwrap();
+		assert_eq!(unspent, Some(100));
+		let _ = fs::remove_dir_all(dir);
+	}
+
+	#[test]
+	fn sqlite_migration() {
+		let dir = "test_output/sqlite_migration";
+		let _ = fs::remove_dir_all(dir);
+		let keychain = ExtKeychain::from_random_seed(false).unwrap();
+		let slate_id = Uuid::new_v4();
+		let ctx;
+		{
+			let mut lmdb: LMDBBackend<LocalWalletClient, ExtKeychain> =
+				LMDBBackend::new(dir, client()).unwrap();
+			lmdb.set_keychain(Box::new(keychain.clone()), false, true)
+				.unwrap();
+			let parent = lmdb.parent_key_id();
+			let secp = secp::Secp256k1::new();
+			ctx = Context::new(&secp, &parent, false, false, 1, 100, 0, None);
+			let mut batch = lmdb.batch(None).unwrap();
+			batch.save(output(1, 100, "08aa")).unwrap();
+			let id = batch.next_tx_log_id(&parent).unwrap();
+			let mut tx = TxLogEntry::new(parent.clone(), TxLogEntryType::TxReceived, id);
+			tx.tx_slate_id = Some(slate_id);
+			batch.save_tx_log_entry(tx, &parent).unwrap();
+			batch.save_child_index(&parent, 2).unwrap();
+			batch
+				.save_private_context(slate_id.as_bytes(), 1, &ctx)
+				.unwrap();
+			batch
+				.save_acct_path(AcctPathMapping {
+					label: "savings".to_owned(),
+					path: ExtKeychain::derive_key_id(2, 1, 0, 0, 0),
+				})
+				.unwrap();
+			batch.commit().unwrap();
+		}
+		fs::write(
+			Path::new(dir)
+				.join(TX_SAVE_DIR)
+				.join(format!("{}.mwctx", slate_id)),
+			"00",
+		)
+		.unwrap();
+
+		let db_file = migrate_from_lmdb::<LocalWalletClient, ExtKeychain>(dir, client()).unwrap();
+		assert!(Path::new(&db_file).exists());
+		assert!(migrate_from_lmdb::<LocalWalletClient, ExtKeychain>(dir, client()).is_err());
+
+		let mut backend: Backend = SqliteBackend::new(dir, client()).unwrap();
+		backend
+			.set_keychain(Box::new(keychain), false, true)
+			.unwrap();
+		let parent = backend.parent_key_id();
+		assert_eq!(backend.iter().count(), 1);
+		assert_eq!(backend.get_tx_log_by_slate_id(&slate_id).unwrap().len(), 1);
+		assert_eq!(backend.current_child_index(&parent).unwrap(), 2);
+		assert_eq!(backend.acct_path_iter().count(), 2);
+		assert_eq!(next_tx_log_id(&mut backend, &parent), 1);
+		let read = backend
+			.get_private_context(None, slate_id.as_bytes(), 1)
+			.unwrap();
+		assert_eq!(read.sec_key, ctx.sec_key);
+		let stored: Option<String> = query_value(
+			&backend.conn.lock(),
+			"SELECT tx FROM stored_txs WHERE name = ?1",
+			params![format!("{}.mwctx", slate_id)],
+		)
+		.unwrap();
+		assert_eq!(stored, Some("00".to_owned()));
+		let _ = fs::remove_dir_all(dir);
+	}
+}
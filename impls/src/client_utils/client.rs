@@ -19,7 +19,7 @@ use crate::util::to_base64;
 use crossbeam_utils::thread::scope;
 use failure::{Backtrace, Context, Fail};
 use hyper::body;
-use hyper::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT, CONNECTION};
+use hyper::header::{HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT, CONNECTION};
 use hyper::{self, Body, Client as HyperClient, Request, Uri};
 use hyper_rustls;
 use hyper_timeout::TimeoutConnector;
@@ -255,6 +255,63 @@ impl Client {
 		Ok(())
 	}
 
+	/// Helper function to issue a HTTP POST request with already serialized JSON body
+	/// and additional headers. Response body is ignored, but unlike other helpers the
+	/// response code is checked, non success code is reported as an error.
+	pub fn post_raw_checked(
+		&self,
+		url: &str,
+		headers: &[(&str, String)],
+		body: String,
+	) -> Result<(), Error> {
+		let mut req = self.build_request_ex(url, "POST", None, None, Some(body))?;
+		for (name, value) in headers {
+			let name = HeaderName::from_bytes(name.as_bytes())
+				.map_err(|e| ErrorKind::Argument(format!("Invalid header {}, {}", name, e)))?;
+			let value = HeaderValue::from_str(value)
+				.map_err(|e| ErrorKind::Argument(format!("Invalid header value {}, {}", value, e)))?;
+			req.headers_mut().insert(name, value);
+		}
+
+		let task = async move {
+			let resp = if self.https_client.is_some() {
+				let client = self.https_client.iter().next().unwrap();
+				client.request(req).await
+			} else {
+				debug_assert!(self.socks_client.is_some());
+				self.socks_client.iter().next().unwrap().request(req).await
+			};
+			let resp =
+				resp.map_err(|e| ErrorKind::RequestError(format!("Cannot make request: {}", e)))?;
+			if !resp.status().is_success() {
+				return Err(ErrorKind::ResponseError(format!(
+					"Request to {} failed with response code {}",
+					url,
+					resp.status()
+				))
+				.into());
+			}
+			Ok(())
+		};
+
+		scope(|s| {
+			let handle = s.spawn(|_| {
+				let mut rt = Builder::new()
+					.basic_scheduler()
+					.enable_all()
+					.build()
+					.map_err(|e| {
+						ErrorKind::Internal(format!("can't create Tokio runtime, {}", e))
+					})?;
+				rt.block_on(task)
+			});
+			handle.join().unwrap_or_else(|_| {
+				Err(ErrorKind::Internal("Request thread panicked".to_string()).into())
+			})
+		})
+		.unwrap()
+	}
+
 	fn build_request(
 		&self,
 		url: &str,
@@ -0,0 +1,218 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Delivery of the wallet events to HTTP webhooks and JSON-lines files
+
+use crate::client_utils::Client;
+use crate::config::WalletConfig;
+use crate::error::{Error, ErrorKind};
+use crate::libwallet::{self, EventNotifier, EventSink, WalletEvent};
+use crate::util::{to_hex, Mutex};
+use ring::hmac;
+use std::cmp;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Header with the event name
+pub const EVENT_HEADER: &str = "X-MWC-Event";
+/// Header with the event id
+pub const EVENT_ID_HEADER: &str = "X-MWC-Event-Id";
+/// Header with the HMAC-SHA256 signature of the request body, 'sha256=<hex>'
+pub const SIGNATURE_HEADER: &str = "X-MWC-Signature";
+
+/// Default number of the webhook delivery retries
+pub const DEFAULT_WEBHOOK_RETRIES: u32 = 5;
+// Max delay between the delivery attempts
+const MAX_RETRY_DELAY_SECS: u64 = 60;
+
+/// HMAC-SHA256 signature of the webhook body, the value of SIGNATURE_HEADER
+pub fn webhook_signature(secret: &str, body: &[u8]) -> String {
+	let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+	let tag = hmac::sign(&key, body);
+	format!("sha256={}", to_hex(tag.as_ref()))
+}
+
+/// Posts the events as JSON to the URL. Failed deliveries are retried with exponential
+/// backoff; if all retries fail, the event stays in the wallet DB and the delivery is
+/// started again after the next wallet update.
+pub struct WebhookEventSink {
+	client: Client,
+	url: String,
+	secret: Option<String>,
+	retries: u32,
+}
+
+impl WebhookEventSink {
+	/// Create the sink. If secret is provided, every request is signed with it.
+	pub fn new(url: String, secret: Option<String>, retries: u32) -> Result<Self, Error> {
+		if !(url.starts_with("http://") || url.starts_with("https://")) {
+			return Err(ErrorKind::ArgumentError(format!(
+				"Invalid webhook url {}, expected http or https url",
+				url
+			))
+			.into());
+		}
+		let client = Client::new(false, None).map_err(|e| {
+			ErrorKind::GenericError(format!("Unable to create webhook client, {}", e))
+		})?;
+
+		Ok(WebhookEventSink {
+			client,
+			url,
+			secret,
+			retries,
+		})
+	}
+}
+
+impl EventSink for WebhookEventSink {
+	fn notify(&self, event: &WalletEvent) -> Result<(), libwallet::Error> {
+		let body = serde_json::to_string(event).map_err(|e| {
+			libwallet::ErrorKind::GenericError(format!(
+				"Unable to serialize wallet event {}, {}",
+				event.id, e
+			))
+		})?;
+		let mut headers = vec![
+			(EVENT_HEADER, event.name().to_string()),
+			(EVENT_ID_HEADER, event.id.to_string()),
+		];
+		if let Some(secret) = &self.secret {
+			headers.push((SIGNATURE_HEADER, webhook_signature(secret, body.as_bytes())));
+		}
+
+		let mut attempt = 0;
+		loop {
+			match self
+				.client
+				.post_raw_checked(&self.url, &headers, body.clone())
+			{
+				Ok(_) => return Ok(()),
+				Err(e) => {
+					if attempt >= self.retries {
+						return Err(libwallet::ErrorKind::GenericError(format!(
+							"Unable to deliver wallet event {} to {}, {}",
+							event.id, self.url, e
+						))
+						.into());
+					}
+					let delay = cmp::min(1u64 << cmp::min(attempt, 6), MAX_RETRY_DELAY_SECS);
+					warn!(
+						"Wallet event {} delivery to {} failed, retry in {} seconds. {}",
+						event.id, self.url, delay, e
+					);
+					thread::sleep(Duration::from_secs(delay));
+					attempt += 1;
+				}
+			}
+		}
+	}
+}
+
+/// Appends the events to a file, one JSON object per line
+pub struct JsonLinesEventSink {
+	file: Mutex<File>,
+}
+
+impl JsonLinesEventSink {
+	/// Open the file for appending, the file is created if it doesn't exist
+	pub fn new(path: &str) -> Result<Self, Error> {
+		let file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(path)
+			.map_err(|e| ErrorKind::IO(format!("Unable to open events file {}, {}", path, e)))?;
+		Ok(JsonLinesEventSink {
+			file: Mutex::new(file),
+		})
+	}
+}
+
+impl EventSink for JsonLinesEventSink {
+	fn notify(&self, event: &WalletEvent) -> Result<(), libwallet::Error> {
+		let line = serde_json::to_string(event).map_err(|e| {
+			libwallet::ErrorKind::GenericError(format!(
+				"Unable to serialize wallet event {}, {}",
+				event.id, e
+			))
+		})?;
+		let mut file = self.file.lock();
+		writeln!(file, "{}", line)
+			.and_then(|_| file.flush())
+			.map_err(|e| {
+				libwallet::ErrorKind::IO(format!(
+					"Unable to write wallet event {}, {}",
+					event.id, e
+				))
+			})?;
+		Ok(())
+	}
+}
+
+/// Delivers the events to several sinks. The event is offered to every sink; if any of
+/// them fails, the event is delivered again later, so the receivers should dedupe by the
+/// event id.
+pub struct MultiEventSink {
+	sinks: Vec<Arc<dyn EventSink>>,
+}
+
+impl MultiEventSink {
+	/// Create from the list of sinks
+	pub fn new(sinks: Vec<Arc<dyn EventSink>>) -> Self {
+		MultiEventSink { sinks }
+	}
+}
+
+impl EventSink for MultiEventSink {
+	fn notify(&self, event: &WalletEvent) -> Result<(), libwallet::Error> {
+		let mut res = Ok(());
+		for s in &self.sinks {
+			if let Err(e) = s.notify(event) {
+				res = Err(e);
+			}
+		}
+		res
+	}
+}
+
+/// Build the event notifier from the wallet config. Returns None if neither
+/// webhook nor events file are configured.
+pub fn create_event_notifier(config: &WalletConfig) -> Result<Option<EventNotifier>, Error> {
+	let mut sinks: Vec<Arc<dyn EventSink>> = vec![];
+	if let Some(url) = &config.event_webhook_url {
+		sinks.push(Arc::new(WebhookEventSink::new(
+			url.clone(),
+			config.event_webhook_secret.clone(),
+			config
+				.event_webhook_retries
+				.unwrap_or(DEFAULT_WEBHOOK_RETRIES),
+		)?));
+	}
+	if let Some(path) = &config.event_log_file {
+		sinks.push(Arc::new(JsonLinesEventSink::new(path)?));
+	}
+
+	let sink: Arc<dyn EventSink> = match sinks.len() {
+		0 => return Ok(None),
+		1 => sinks.remove(0),
+		_ => Arc::new(MultiEventSink::new(sinks)),
+	};
+	Ok(Some(EventNotifier::new(
+		sink,
+		config.event_confirmations.unwrap_or(1),
+	)))
+}
@@ -40,6 +40,7 @@ pub mod adapters;
 mod backends;
 mod client_utils;
 mod error;
+pub mod events;
 pub mod lifecycle;
 pub mod node_clients;
 pub mod test_framework;
@@ -54,10 +55,15 @@ pub use crate::adapters::{
 	SlateGetter, SlatePutter, SlateReceiver, SlateSender, Subscriber, SubscriptionHandler,
 	SwapMessageSender,
 };
-pub use crate::backends::{wallet_db_exists, LMDBBackend};
+pub use crate::backends::{
+	db_schema_version, pending_migrations, sqlite_db_exists, wallet_db_exists, LMDBBackend,
+};
+#[cfg(feature = "sqlite")]
+pub use crate::backends::{migrate_from_lmdb, SqliteBackend};
 pub use crate::error::{Error, ErrorKind};
+pub use crate::events::create_event_notifier;
 pub use crate::lifecycle::DefaultLCProvider;
-pub use crate::node_clients::HTTPNodeClient;
+pub use crate::node_clients::{HTTPNodeClient, MultiNodeClient};
 
 use crate::keychain::{ExtKeychain, Keychain};
 
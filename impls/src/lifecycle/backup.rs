@@ -0,0 +1,312 @@
+// Copyright 2019 The Grin Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Full wallet backup. Seed, database, saved transactions, transaction proofs and
+//! swap trades are packed into a single archive encrypted with the wallet password.
+//!
+//! Archive layout:
+//! magic (8) | format version (2) | salt (16) | nonce (12) | checksum (32) | encrypted payload
+//!
+//! Checksum is blake2b of the encrypted payload, it is validated before decryption, so a
+//! damaged archive is reported separately from a wrong password.
+
+use crate::backends::{with_db_write_lock, DB_DIR, SQLITE_DB_FILE, TX_SAVE_DIR};
+use crate::blake2::blake2b::blake2b;
+use crate::libwallet::proof::tx_proof::TX_PROOF_SAVE_DIR;
+use crate::libwallet::swap::trades::SWAP_DEAL_SAVE_DIR;
+use crate::lifecycle::seed::{SEED_FILE, VIEW_KEY_FILE};
+use crate::util::ZeroingString;
+use crate::{Error, ErrorKind};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use rand::{thread_rng, Rng};
+use ring::aead;
+use ring::pbkdf2;
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Write};
+use std::num::NonZeroU32;
+use std::path::{Component, Path};
+
+/// Current version of the backup archive format
+pub const BACKUP_FORMAT_VERSION: u16 = 1;
+
+const BACKUP_MAGIC: &[u8; 8] = b"MWCWBKUP";
+// Archive is expected to leave the host, so the key derivation is much stronger than for the seed file
+const BACKUP_PBKDF2_ROUNDS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const CHECKSUM_LEN: usize = 32;
+// LMDB recreates the lock file on open
+const LMDB_LOCK_FILE: &str = "lock.mdb";
+
+/// Wallet data that goes into the backup, relative to the wallet data directory
+const BACKUP_ITEMS: &[&str] = &[
+	SEED_FILE,
+	VIEW_KEY_FILE,
+	DB_DIR,
+	SQLITE_DB_FILE,
+	TX_SAVE_DIR,
+	TX_PROOF_SAVE_DIR,
+	SWAP_DEAL_SAVE_DIR,
+];
+
+/// Pack the wallet data from `data_file_dir` into the encrypted archive `backup_file`.
+/// Returns the number of files in the archive. The wallet must be closed, the files are
+/// read while the DB write transaction is held, so they are consistent with each other.
+pub fn create_backup(
+	data_file_dir: &str,
+	backup_file: &str,
+	password: ZeroingString,
+) -> Result<usize, Error> {
+	let mut files: Vec<(String, Vec<u8>)> = vec![];
+	with_db_write_lock(data_file_dir, || {
+		BACKUP_ITEMS
+			.iter()
+			.try_for_each(|item| collect_files(Path::new(data_file_dir), item, &mut files))
+	})
+	.map_err(|e| ErrorKind::IO(format!("Unable to lock wallet DB, {}", e)))??;
+
+	let mut payload: Vec<u8> = vec![];
+	payload
+		.write_u32::<BigEndian>(files.len() as u32)
+		.map_err(|e| ErrorKind::IO(format!("Unable to build backup archive, {}", e)))?;
+	for (name, data) in &files {
+		payload
+			.write_u16::<BigEndian>(name.len() as u16)
+			.and_then(|_| payload.write_all(name.as_bytes()))
+			.and_then(|_| payload.write_u64::<BigEndian>(data.len() as u64))
+			.and_then(|_| payload.write_all(data))
+			.map_err(|e| ErrorKind::IO(format!("Unable to build backup archive, {}", e)))?;
+	}
+
+	let salt: [u8; SALT_LEN] = thread_rng().gen();
+	let nonce: [u8; NONCE_LEN] = thread_rng().gen();
+	let mut header: Vec<u8> = BACKUP_MAGIC.to_vec();
+	header
+		.write_u16::<BigEndian>(BACKUP_FORMAT_VERSION)
+		.map_err(|e| ErrorKind::IO(format!("Unable to build backup archive, {}", e)))?;
+
+	let key = derive_key(&password, &salt)?;
+	key.seal_in_place_append_tag(
+		aead::Nonce::assume_unique_for_key(nonce),
+		aead::Aad::from(&header),
+		&mut payload,
+	)
+	.map_err(|e| ErrorKind::Encryption(format!("Unable to encrypt backup, {}", e)))?;
+	let checksum = blake2b(CHECKSUM_LEN, &[], &payload);
+
+	let mut file = File::create(backup_file)
+		.map_err(|e| ErrorKind::IO(format!("Unable to create file {}, {}", backup_file, e)))?;
+	file.write_all(&header)
+		.and_then(|_| file.write_all(&salt))
+		.and_then(|_| file.write_all(&nonce))
+		.and_then(|_| file.write_all(checksum.as_bytes()))
+		.and_then(|_| file.write_all(&payload))
+		.and_then(|_| file.sync_all())
+		.map_err(|e| ErrorKind::IO(format!("Unable to save data to {}, {}", backup_file, e)))?;
+
+	Ok(files.len())
+}
+
+/// Unpack the encrypted archive `backup_file` into `data_file_dir`.
+/// Returns the number of restored files.
+pub fn restore_backup(
+	data_file_dir: &str,
+	backup_file: &str,
+	password: ZeroingString,
+) -> Result<usize, Error> {
+	let mut archive = vec![];
+	File::open(backup_file)
+		.and_then(|mut f| f.read_to_end(&mut archive))
+		.map_err(|e| ErrorKind::IO(format!("Unable to read file {}, {}", backup_file, e)))?;
+
+	let header_len = BACKUP_MAGIC.len() + 2;
+	let payload_start = header_len + SALT_LEN + NONCE_LEN + CHECKSUM_LEN;
+	if archive.len() < payload_start || &archive[..BACKUP_MAGIC.len()] != BACKUP_MAGIC {
+		return Err(ErrorKind::Format(format!("{} is not a wallet backup", backup_file)).into());
+	}
+	let version = Cursor::new(&archive[BACKUP_MAGIC.len()..header_len])
+		.read_u16::<BigEndian>()
+		.map_err(|e| ErrorKind::Format(format!("Unable to read backup version, {}", e)))?;
+	if version > BACKUP_FORMAT_VERSION {
+		return Err(ErrorKind::Format(format!(
+			"Backup format version {} is not supported, please upgrade the wallet",
+			version
+		))
+		.into());
+	}
+
+	let (header, rest) = archive.split_at_mut(header_len);
+	let (salt, rest) = rest.split_at_mut(SALT_LEN);
+	let (nonce, rest) = rest.split_at_mut(NONCE_LEN);
+	let (checksum, payload) = rest.split_at_mut(CHECKSUM_LEN);
+
+	if blake2b(CHECKSUM_LEN, &[], payload).as_bytes() != &checksum[..] {
+		return Err(ErrorKind::Format(format!(
+			"Backup {} is corrupted, checksum doesn't match",
+			backup_file
+		))
+		.into());
+	}
+
+	let mut n = [0u8; NONCE_LEN];
+	n.copy_from_slice(nonce);
+	let key = derive_key(&password, salt)?;
+	let payload = key
+		.open_in_place(
+			aead::Nonce::assume_unique_for_key(n),
+			aead::Aad::from(&header[..]),
+			payload,
+		)
+		.map_err(|e| ErrorKind::Encryption(format!("Unable to decrypt backup, {}", e)))?;
+
+	let files = read_files(payload)?;
+	for (name, data) in &files {
+		let path = Path::new(data_file_dir).join(name);
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|e| {
+				ErrorKind::IO(format!("Unable to create directory {:?}, {}", dir, e))
+			})?;
+		}
+		File::create(&path)
+			.and_then(|mut f| f.write_all(data))
+			.map_err(|e| ErrorKind::IO(format!("Unable to restore file {:?}, {}", path, e)))?;
+	}
+	Ok(files.len())
+}
+
+fn derive_key(password: &ZeroingString, salt: &[u8]) -> Result<aead::LessSafeKey, Error> {
+	let mut key = [0; 32];
+	pbkdf2::derive(
+		pbkdf2::PBKDF2_HMAC_SHA512,
+		NonZeroU32::new(BACKUP_PBKDF2_ROUNDS).unwrap(),
+		salt,
+		password.as_bytes(),
+		&mut key,
+	);
+	let unbound_key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key)
+		.map_err(|e| ErrorKind::Encryption(format!("Unable to build backup key, {}", e)))?;
+	Ok(aead::LessSafeKey::new(unbound_key))
+}
+
+// Collect the file or all files from the directory. Names are relative to the data directory and
+// always use '/' so the archive can be restored on another platform.
+fn collect_files(
+	data_dir: &Path,
+	name: &str,
+	files: &mut Vec<(String, Vec<u8>)>,
+) -> Result<(), Error> {
+	let path = data_dir.join(name);
+	if path.is_dir() {
+		let entries = fs::read_dir(&path)
+			.map_err(|e| ErrorKind::IO(format!("Unable to read directory {:?}, {}", path, e)))?;
+		for entry in entries {
+			let entry = entry.map_err(|e| {
+				ErrorKind::IO(format!("Unable to read directory {:?}, {}", path, e))
+			})?;
+			let file_name = entry.file_name();
+			let file_name = file_name.to_str().ok_or(ErrorKind::IO(format!(
+				"Unable to backup file {:?}, unsupported name",
+				entry.path()
+			)))?;
+			if file_name == LMDB_LOCK_FILE {
+				continue;
+			}
+			collect_files(data_dir, &format!("{}/{}", name, file_name), files)?;
+		}
+	} else if path.is_file() {
+		let data = fs::read(&path)
+			.map_err(|e| ErrorKind::IO(format!("Unable to read file {:?}, {}", path, e)))?;
+		files.push((name.to_string(), data));
+	}
+	Ok(())
+}
+
+fn read_files(payload: &[u8]) -> Result<Vec<(String, Vec<u8>)>, Error> {
+	let format_err = |e| ErrorKind::Format(format!("Unable to read backup content, {}", e));
+	let mut reader = Cursor::new(payload);
+	let count = reader.read_u32::<BigEndian>().map_err(format_err)?;
+	let mut files = vec![];
+	for _ in 0..count {
+		let name_len = reader.read_u16::<BigEndian>().map_err(format_err)?;
+		let mut name = vec![0; name_len as usize];
+		reader.read_exact(&mut name).map_err(format_err)?;
+		let name = String::from_utf8(name)
+			.map_err(|e| ErrorKind::Format(format!("Invalid file name in backup, {}", e)))?;
+		// Don't let the archive write outside of the wallet data directory
+		let is_safe = Path::new(&name).components().all(|c| match c {
+			Component::Normal(_) => true,
+			_ => false,
+		});
+		if !is_safe {
+			return Err(ErrorKind::Format(format!("Invalid file name {} in backup", name)).into());
+		}
+		let data_len = reader.read_u64::<BigEndian>().map_err(format_err)?;
+		if data_len > payload.len() as u64 {
+			return Err(ErrorKind::Format(format!("Invalid size of {} in backup", name)).into());
+		}
+		let mut data = vec![0; data_len as usize];
+		reader.read_exact(&mut data).map_err(format_err)?;
+		files.push((name, data));
+	}
+	Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn backup_restore() {
+		let test_dir = "test_output/wallet_backup";
+		let _ = fs::remove_dir_all(test_dir);
+		let src = format!("{}/src", test_dir);
+		let dst = format!("{}/dst", test_dir);
+		let db_dir = format!("{}/{}", src, DB_DIR);
+		drop(crate::store::Store::new(&db_dir, None, Some(DB_DIR), None).unwrap());
+		let db_file = format!("{}/lmdb/data.mdb", DB_DIR);
+		fs::create_dir_all(format!("{}/{}", src, TX_SAVE_DIR)).unwrap();
+		fs::write(format!("{}/{}", src, SEED_FILE), b"seed").unwrap();
+		fs::write(format!("{}/{}/tx.mwctx", src, TX_SAVE_DIR), b"tx").unwrap();
+		fs::write(format!("{}/unrelated.txt", src), b"skip").unwrap();
+
+		let backup = format!("{}/wallet.backup", test_dir);
+		let pw = ZeroingString::from("password");
+		assert_eq!(create_backup(&src, &backup, pw.clone()).unwrap(), 3);
+
+		// Wrong password
+		assert!(restore_backup(&dst, &backup, ZeroingString::from("wrong")).is_err());
+
+		assert_eq!(restore_backup(&dst, &backup, pw.clone()).unwrap(), 3);
+		assert_eq!(fs::read(format!("{}/{}", dst, SEED_FILE)).unwrap(), b"seed");
+		assert_eq!(
+			fs::read(format!("{}/{}", dst, db_file)).unwrap(),
+			fs::read(format!("{}/{}", src, db_file)).unwrap()
+		);
+		assert!(Path::new(&format!("{}/{}/lmdb/{}", src, DB_DIR, LMDB_LOCK_FILE)).exists());
+		assert!(!Path::new(&format!("{}/{}/lmdb/{}", dst, DB_DIR, LMDB_LOCK_FILE)).exists());
+		assert!(!Path::new(&format!("{}/unrelated.txt", dst)).exists());
+
+		// Damaged archive is detected before decryption
+		let mut archive = fs::read(&backup).unwrap();
+		let last = archive.len() - 1;
+		archive[last] ^= 1;
+		fs::write(&backup, archive).unwrap();
+		match restore_backup(&dst, &backup, pw).unwrap_err().kind() {
+			ErrorKind::Format(msg) => assert!(msg.contains("corrupted")),
+			e => panic!("Unexpected error {}", e),
+		}
+
+		let _ = fs::remove_dir_all(test_dir);
+	}
+}
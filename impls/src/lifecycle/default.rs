@@ -14,13 +14,19 @@
 
 //! Default wallet lifecycle provider
 
+#[cfg(feature = "sqlite")]
+use crate::backends::{migrate_from_lmdb, SqliteBackend};
+use crate::backends::{pending_migrations, sqlite_db_exists, wallet_db_exists};
 use crate::config::{
 	config, GlobalWalletConfig, GlobalWalletConfigMembers, MQSConfig, TorConfig, WalletConfig,
-	GRIN_WALLET_DIR,
+	WalletDbBackend, GRIN_WALLET_DIR,
 };
 use crate::core::global;
 use crate::keychain::Keychain;
-use crate::libwallet::{Error, ErrorKind, NodeClient, WalletBackend, WalletLCProvider};
+use crate::libwallet::{
+	Error, ErrorKind, NodeClient, ScanConfig, ViewKey, WalletBackend, WalletLCProvider,
+};
+use crate::lifecycle::backup;
 use crate::lifecycle::seed::WalletSeed;
 use crate::util::secp::key::SecretKey;
 use crate::util::ZeroingString;
@@ -37,6 +43,12 @@ where
 	data_dir: String,
 	node_client: C,
 	backend: Option<Box<dyn WalletBackend<'a, C, K> + 'a>>,
+	/// Name of the opened wallet, None for the default one
+	backend_name: Option<String>,
+	/// DB backend of the wallets
+	db_backend: WalletDbBackend,
+	/// Update and scan settings of the wallets
+	scan_config: ScanConfig,
 }
 
 impl<'a, C, K> DefaultLCProvider<'a, C, K>
@@ -50,10 +62,78 @@ where
 			node_client,
 			data_dir: "default".to_owned(),
 			backend: None,
+			backend_name: None,
+			db_backend: WalletDbBackend::default(),
+			scan_config: ScanConfig::default(),
+		}
+	}
+
+	/// Wallet top level directory. Named wallets are stored in the sub directory
+	/// with the same name, the default (unnamed) wallet in the top level directory itself
+	fn wallet_dir(&self, name: Option<&str>) -> Result<PathBuf, Error> {
+		let mut dir = PathBuf::from(self.data_dir.clone());
+		if let Some(name) = name {
+			if name.is_empty()
+				|| name == "."
+				|| name == ".."
+				|| name.contains(|c| c == '/' || c == '\\')
+			{
+				return Err(ErrorKind::Lifecycle(format!("Invalid wallet name '{}'", name)).into());
+			}
+			dir.push(name);
+		}
+		Ok(dir)
+	}
+
+	/// Open or create the wallet DB with the configured backend. The wallet DB of the other
+	/// backend isn't picked up silently, the wallet would look empty.
+	fn open_backend(
+		&self,
+		data_dir_name: &str,
+	) -> Result<Box<dyn WalletBackend<'a, C, K> + 'a>, Error> {
+		match self.db_backend {
+			WalletDbBackend::Lmdb => {
+				if !wallet_db_exists(data_dir_name) && sqlite_db_exists(data_dir_name) {
+					return Err(ErrorKind::Lifecycle(format!(
+						"Wallet at {} has the SQLite DB, please set db_backend = \"sqlite\" in the config",
+						data_dir_name
+					))
+					.into());
+				}
+				let mut wallet: LMDBBackend<'a, C, K> =
+					LMDBBackend::new(data_dir_name, self.node_client.clone())?;
+				wallet.set_scan_config(self.scan_config.clone());
+				Ok(Box::new(wallet))
+			}
+			#[cfg(feature = "sqlite")]
+			WalletDbBackend::Sqlite => {
+				if !sqlite_db_exists(data_dir_name) && wallet_db_exists(data_dir_name) {
+					return Err(ErrorKind::Lifecycle(format!(
+						"Wallet at {} has the LMDB DB, please migrate it with 'migrate --to-sqlite' first",
+						data_dir_name
+					))
+					.into());
+				}
+				let mut wallet: SqliteBackend<'a, C, K> =
+					SqliteBackend::new(data_dir_name, self.node_client.clone())?;
+				wallet.set_scan_config(self.scan_config.clone());
+				Ok(Box::new(wallet))
+			}
+			#[cfg(not(feature = "sqlite"))]
+			WalletDbBackend::Sqlite => Err(sqlite_not_supported()),
 		}
 	}
 }
 
+#[cfg(not(feature = "sqlite"))]
+fn sqlite_not_supported() -> Error {
+	ErrorKind::Lifecycle(
+		"SQLite wallet DB is not supported, the wallet must be built with the 'sqlite' feature"
+			.to_string(),
+	)
+	.into()
+}
+
 impl<'a, C, K> WalletLCProvider<'a, C, K> for DefaultLCProvider<'a, C, K>
 where
 	C: NodeClient + 'a,
@@ -68,6 +148,19 @@ where
 		Ok(self.data_dir.to_owned())
 	}
 
+	fn set_db_backend(&mut self, backend: WalletDbBackend) -> Result<(), Error> {
+		self.db_backend = backend;
+		Ok(())
+	}
+
+	fn set_scan_config(&mut self, config: ScanConfig) -> Result<(), Error> {
+		if let Some(wallet) = self.backend.as_mut() {
+			wallet.set_scan_config(config.clone());
+		}
+		self.scan_config = config;
+		Ok(())
+	}
+
 	fn create_config(
 		&self,
 		chain_type: &global::ChainTypes,
@@ -184,14 +277,14 @@ where
 
 	fn create_wallet(
 		&mut self,
-		_name: Option<&str>,
+		name: Option<&str>,
 		mnemonic: Option<ZeroingString>,
 		mnemonic_length: usize,
 		password: ZeroingString,
 		test_mode: bool,
 		wallet_data_dir: Option<&str>,
 	) -> Result<(), Error> {
-		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		let mut data_dir_name = self.wallet_dir(name)?;
 		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
 		let data_dir_name = data_dir_name.to_str().unwrap();
 		let exists = WalletSeed::seed_file_exists(&data_dir_name);
@@ -216,15 +309,14 @@ where
 		})?;
 
 		info!("Wallet seed file created");
-		let mut wallet: LMDBBackend<'a, C, K> =
-			match LMDBBackend::new(&data_dir_name, self.node_client.clone()) {
-				Err(e) => {
-					let msg = format!("Error creating wallet: {}, Data Dir: {}", e, &data_dir_name);
-					error!("{}", msg);
-					return Err(ErrorKind::Lifecycle(msg).into());
-				}
-				Ok(d) => d,
-			};
+		let mut wallet = match self.open_backend(&data_dir_name) {
+			Err(e) => {
+				let msg = format!("Error creating wallet: {}, Data Dir: {}", e, &data_dir_name);
+				error!("{}", msg);
+				return Err(ErrorKind::Lifecycle(msg).into());
+			}
+			Ok(d) => d,
+		};
 		// Save init status of this wallet, to determine whether it needs a full UTXO scan
 		let batch = wallet.batch_no_mask()?;
 		batch.commit()?;
@@ -232,25 +324,71 @@ where
 		Ok(())
 	}
 
+	fn create_view_wallet(
+		&mut self,
+		name: Option<&str>,
+		view_key: &ViewKey,
+		password: ZeroingString,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error> {
+		let mut data_dir_name = self.wallet_dir(name)?;
+		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
+		let data_dir_name = data_dir_name.to_str().unwrap();
+		WalletSeed::init_view_key_file(&data_dir_name, view_key.as_bytes(), password).map_err(
+			|e| ErrorKind::Lifecycle(format!("Error creating wallet view key file, {}", e)),
+		)?;
+
+		info!("Wallet view key file created");
+		let mut wallet = match self.open_backend(&data_dir_name) {
+			Err(e) => {
+				let msg = format!("Error creating wallet: {}, Data Dir: {}", e, &data_dir_name);
+				error!("{}", msg);
+				return Err(ErrorKind::Lifecycle(msg).into());
+			}
+			Ok(d) => d,
+		};
+		let batch = wallet.batch_no_mask()?;
+		batch.commit()?;
+		info!(
+			"Watch-only wallet database backend created at {}",
+			data_dir_name
+		);
+		Ok(())
+	}
+
 	fn open_wallet(
 		&mut self,
-		_name: Option<&str>,
+		name: Option<&str>,
 		password: ZeroingString,
 		create_mask: bool,
 		use_test_rng: bool,
 		wallet_data_dir: Option<&str>,
 	) -> Result<Option<SecretKey>, Error> {
-		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		let mut data_dir_name = self.wallet_dir(name)?;
 		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
 		let data_dir_name = data_dir_name.to_str().unwrap();
-		let mut wallet: LMDBBackend<'a, C, K> =
-			match LMDBBackend::new(&data_dir_name, self.node_client.clone()) {
-				Err(e) => {
-					let msg = format!("Error opening wallet: {}, Data Dir: {}", e, &data_dir_name);
-					return Err(ErrorKind::Lifecycle(msg).into());
-				}
-				Ok(d) => d,
-			};
+		let mut wallet = match self.open_backend(&data_dir_name) {
+			Err(e) => {
+				let msg = format!("Error opening wallet: {}, Data Dir: {}", e, &data_dir_name);
+				return Err(ErrorKind::Lifecycle(msg).into());
+			}
+			Ok(d) => d,
+		};
+		// Watch-only wallet has no seed, only the view key
+		let seed_exists = WalletSeed::seed_file_exists(&data_dir_name).unwrap_or(false);
+		if !seed_exists && WalletSeed::view_key_file_exists(&data_dir_name) {
+			let view_key =
+				WalletSeed::view_key_from_file(&data_dir_name, password).map_err(|e| {
+					ErrorKind::Lifecycle(format!(
+						"Error opening wallet (is password correct?), {}",
+						e
+					))
+				})?;
+			wallet.set_view_key(ViewKey::from_bytes(&view_key)?);
+			self.backend = Some(wallet);
+			self.backend_name = name.map(|n| n.to_owned());
+			return Ok(None);
+		}
 		let wallet_seed = WalletSeed::from_file(&data_dir_name, password).map_err(|e| {
 			ErrorKind::Lifecycle(format!(
 				"Error opening wallet (is password correct?), {}",
@@ -262,39 +400,48 @@ where
 			.map_err(|e| ErrorKind::Lifecycle(format!("Error deriving keychain, {}", e)))?;
 
 		let mask = wallet.set_keychain(Box::new(keychain), create_mask, use_test_rng)?;
-		self.backend = Some(Box::new(wallet));
+		self.backend = Some(wallet);
+		self.backend_name = name.map(|n| n.to_owned());
 		Ok(mask)
 	}
 
-	fn close_wallet(&mut self, _name: Option<&str>) -> Result<(), Error> {
+	fn close_wallet(&mut self, name: Option<&str>) -> Result<(), Error> {
+		if self.backend.is_some() && self.backend_name.as_deref() != name {
+			return Err(ErrorKind::Lifecycle(format!(
+				"Wallet {} is not open",
+				name.unwrap_or("default")
+			))
+			.into());
+		}
 		if let Some(b) = self.backend.as_mut() {
 			b.close()?
 		}
 		self.backend = None;
+		self.backend_name = None;
 		Ok(())
 	}
 
 	fn wallet_exists(
 		&self,
-		_name: Option<&str>,
+		name: Option<&str>,
 		wallet_data_dir: Option<&str>,
 	) -> Result<bool, Error> {
-		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		let mut data_dir_name = self.wallet_dir(name)?;
 		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
 		let data_dir_name = data_dir_name.to_str().unwrap();
 		let res = WalletSeed::seed_file_exists(&data_dir_name).map_err(|e| {
 			ErrorKind::CallbackImpl(format!("Error checking for wallet existence, {}", e))
 		})?;
-		Ok(res)
+		Ok(res || WalletSeed::view_key_file_exists(&data_dir_name))
 	}
 
 	fn get_mnemonic(
 		&self,
-		_name: Option<&str>,
+		name: Option<&str>,
 		password: ZeroingString,
 		wallet_data_dir: Option<&str>,
 	) -> Result<ZeroingString, Error> {
-		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		let mut data_dir_name = self.wallet_dir(name)?;
 		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
 		let data_dir_name = data_dir_name.to_str().unwrap();
 		let wallet_seed = WalletSeed::from_file(&data_dir_name, password)
@@ -331,12 +478,12 @@ where
 
 	fn change_password(
 		&self,
-		_name: Option<&str>,
+		name: Option<&str>,
 		old: ZeroingString,
 		new: ZeroingString,
 		wallet_data_dir: Option<&str>,
 	) -> Result<(), Error> {
-		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		let mut data_dir_name = self.wallet_dir(name)?;
 		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
 		let data_dir_name = data_dir_name.to_str().unwrap();
 		// get seed for later check
@@ -396,8 +543,141 @@ where
 		Ok(())
 	}
 
-	fn delete_wallet(&self, _name: Option<&str>) -> Result<(), Error> {
-		let data_dir_name = PathBuf::from(self.data_dir.clone());
+	fn backup_wallet(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		backup_file: &str,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error> {
+		// Open DB can't be copied consistently
+		if self.backend.is_some() {
+			return Err(ErrorKind::Lifecycle(
+				"Wallet is open, please close it before the backup".to_string(),
+			)
+			.into());
+		}
+
+		let mut data_dir_name = self.wallet_dir(name)?;
+		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
+		let data_dir_name = data_dir_name.to_str().unwrap();
+
+		// Archive is encrypted with the wallet password, so it must be the right one
+		let checked = if WalletSeed::seed_file_exists(&data_dir_name).unwrap_or(false) {
+			WalletSeed::from_file(&data_dir_name, password.clone()).map(|_| ())
+		} else if WalletSeed::view_key_file_exists(&data_dir_name) {
+			WalletSeed::view_key_from_file(&data_dir_name, password.clone()).map(|_| ())
+		} else {
+			return Err(
+				ErrorKind::Lifecycle(format!("Wallet doesn't exist at {}", data_dir_name)).into(),
+			);
+		};
+		checked.map_err(|e| {
+			ErrorKind::Lifecycle(format!(
+				"Error opening wallet (is password correct?), {}",
+				e
+			))
+		})?;
+
+		let files = backup::create_backup(&data_dir_name, backup_file, password)
+			.map_err(|e| ErrorKind::Lifecycle(format!("Error creating wallet backup, {}", e)))?;
+		info!(
+			"Wallet backup with {} files created at {}",
+			files, backup_file
+		);
+		Ok(())
+	}
+
+	fn restore_wallet(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		backup_file: &str,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error> {
+		if self.wallet_exists(name, wallet_data_dir)? {
+			return Err(ErrorKind::Lifecycle(
+				"Wallet already exists, please restore the backup into a new location".to_string(),
+			)
+			.into());
+		}
+		let mut data_dir_name = self.wallet_dir(name)?;
+		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
+		let data_dir_name = data_dir_name.to_str().unwrap();
+
+		let files = backup::restore_backup(&data_dir_name, backup_file, password)
+			.map_err(|e| ErrorKind::Lifecycle(format!("Error restoring wallet backup, {}", e)))?;
+		info!(
+			"Wallet restored from backup {}, {} files written to {}",
+			backup_file, files, data_dir_name
+		);
+		Ok(())
+	}
+
+	fn migrate_wallet_db(
+		&self,
+		name: Option<&str>,
+		dry_run: bool,
+		wallet_data_dir: Option<&str>,
+	) -> Result<Vec<(u32, String)>, Error> {
+		// Opening the wallet runs the migrations, and the DB can't be opened twice.
+		// SQLite tables are created when the DB is opened.
+		if self.backend.is_some() || self.db_backend == WalletDbBackend::Sqlite {
+			return Ok(vec![]);
+		}
+		let mut data_dir_name = self.wallet_dir(name)?;
+		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
+		let data_dir_name = data_dir_name.to_str().unwrap();
+		if !wallet_db_exists(&data_dir_name) {
+			return Err(
+				ErrorKind::Lifecycle(format!("Wallet doesn't exist at {}", data_dir_name)).into(),
+			);
+		}
+
+		let pending = pending_migrations(&data_dir_name)?;
+		if !dry_run && !pending.is_empty() {
+			let _: LMDBBackend<'a, C, K> =
+				LMDBBackend::new(&data_dir_name, self.node_client.clone()).map_err(|e| {
+					ErrorKind::Lifecycle(format!("Error migrating wallet DB, {}", e))
+				})?;
+		}
+		Ok(pending)
+	}
+
+	fn migrate_wallet_db_to_sqlite(
+		&self,
+		name: Option<&str>,
+		wallet_data_dir: Option<&str>,
+	) -> Result<String, Error> {
+		if self.backend.is_some() {
+			return Err(ErrorKind::Lifecycle(
+				"Wallet DB can't be migrated while the wallet is open".to_string(),
+			)
+			.into());
+		}
+		let mut data_dir_name = self.wallet_dir(name)?;
+		data_dir_name.push(wallet_data_dir.unwrap_or(GRIN_WALLET_DIR));
+		let data_dir_name = data_dir_name.to_str().unwrap();
+		if !wallet_db_exists(&data_dir_name) {
+			return Err(
+				ErrorKind::Lifecycle(format!("Wallet doesn't exist at {}", data_dir_name)).into(),
+			);
+		}
+
+		#[cfg(feature = "sqlite")]
+		{
+			migrate_from_lmdb::<C, K>(&data_dir_name, self.node_client.clone()).map_err(|e| {
+				ErrorKind::Lifecycle(format!("Error migrating wallet DB to SQLite, {}", e)).into()
+			})
+		}
+		#[cfg(not(feature = "sqlite"))]
+		{
+			Err(sqlite_not_supported())
+		}
+	}
+
+	fn delete_wallet(&self, name: Option<&str>) -> Result<(), Error> {
+		let data_dir_name = self.wallet_dir(name)?;
 		let data_dir_path = data_dir_name.to_str().unwrap();
 		warn!("Removing all wallet data from: {}", data_dir_path);
 		fs::remove_dir_all(data_dir_name)
@@ -30,6 +30,7 @@ use crate::{Error, ErrorKind};
 use std::num::NonZeroU32;
 
 pub const SEED_FILE: &str = "wallet.seed";
+pub const VIEW_KEY_FILE: &str = "wallet.view";
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct WalletSeed(Vec<u8>);
@@ -224,19 +225,7 @@ impl WalletSeed {
 		}
 
 		if write_seed {
-			let enc_seed = EncryptedWalletSeed::from_seed(&seed, password)?;
-			let enc_seed_json = serde_json::to_string_pretty(&enc_seed).map_err(|e| {
-				ErrorKind::Format(format!(
-					"EncryptedWalletSeed to json conversion error, {}",
-					e
-				))
-			})?;
-			let mut file = File::create(seed_file_path).map_err(|e| {
-				ErrorKind::IO(format!("Unable to create file {}, {}", seed_file_path, e))
-			})?;
-			file.write_all(&enc_seed_json.as_bytes()).map_err(|e| {
-				ErrorKind::IO(format!("Unable to save data to {}, {}", seed_file_path, e))
-			})?;
+			seed.write_encrypted(seed_file_path, password)?;
 		}
 
 		if show_seed {
@@ -259,24 +248,7 @@ impl WalletSeed {
 		debug!("Using wallet seed file at: {}", seed_file_path);
 
 		if Path::new(seed_file_path).exists() {
-			let mut file = File::open(seed_file_path).map_err(|e| {
-				ErrorKind::IO(format!("Unable to open file {}, {}", seed_file_path, e))
-			})?;
-			let mut buffer = String::new();
-			file.read_to_string(&mut buffer).map_err(|e| {
-				ErrorKind::IO(format!(
-					"Unable to read from file {}, {}",
-					seed_file_path, e
-				))
-			})?;
-			let enc_seed: EncryptedWalletSeed = serde_json::from_str(&buffer).map_err(|e| {
-				ErrorKind::Format(format!(
-					"Json to EncryptedWalletSeed conversion error, {}",
-					e
-				))
-			})?;
-			let wallet_seed = enc_seed.decrypt(&password)?;
-			Ok(wallet_seed)
+			WalletSeed::read_encrypted(seed_file_path, password)
 		} else {
 			error!(
 				"wallet seed file {} could not be opened (mwc wallet init). \
@@ -287,6 +259,74 @@ impl WalletSeed {
 		}
 	}
 
+	pub fn view_key_file_exists(data_file_dir: &str) -> bool {
+		let view_key_file_path = &format!("{}{}{}", data_file_dir, MAIN_SEPARATOR, VIEW_KEY_FILE,);
+		Path::new(view_key_file_path).exists()
+	}
+
+	/// Watch-only wallet keeps its view key instead of the seed, encrypted the same way
+	pub fn init_view_key_file(
+		data_file_dir: &str,
+		view_key: &[u8],
+		password: util::ZeroingString,
+	) -> Result<(), Error> {
+		fs::create_dir_all(data_file_dir)
+			.map_err(|e| ErrorKind::IO(format!("Unable create dir {}, {}", data_file_dir, e)))?;
+
+		if WalletSeed::seed_file_exists(data_file_dir)?
+			|| WalletSeed::view_key_file_exists(data_file_dir)
+		{
+			return Err(ErrorKind::WalletSeedExists(format!(
+				"Wallet already exists at: {}",
+				data_file_dir
+			)))?;
+		}
+
+		let view_key_file_path = &format!("{}{}{}", data_file_dir, MAIN_SEPARATOR, VIEW_KEY_FILE,);
+		warn!("Generating wallet view key file at: {}", view_key_file_path);
+		WalletSeed::from_bytes(view_key).write_encrypted(view_key_file_path, password)
+	}
+
+	/// Read the view key of the watch-only wallet
+	pub fn view_key_from_file(
+		data_file_dir: &str,
+		password: util::ZeroingString,
+	) -> Result<Vec<u8>, Error> {
+		let view_key_file_path = &format!("{}{}{}", data_file_dir, MAIN_SEPARATOR, VIEW_KEY_FILE,);
+		let view_key = WalletSeed::read_encrypted(view_key_file_path, password)?;
+		Ok(view_key.0.clone())
+	}
+
+	fn write_encrypted(&self, file_path: &str, password: util::ZeroingString) -> Result<(), Error> {
+		let enc_seed = EncryptedWalletSeed::from_seed(self, password)?;
+		let enc_seed_json = serde_json::to_string_pretty(&enc_seed).map_err(|e| {
+			ErrorKind::Format(format!(
+				"EncryptedWalletSeed to json conversion error, {}",
+				e
+			))
+		})?;
+		let mut file = File::create(file_path)
+			.map_err(|e| ErrorKind::IO(format!("Unable to create file {}, {}", file_path, e)))?;
+		file.write_all(&enc_seed_json.as_bytes())
+			.map_err(|e| ErrorKind::IO(format!("Unable to save data to {}, {}", file_path, e)))?;
+		Ok(())
+	}
+
+	fn read_encrypted(file_path: &str, password: util::ZeroingString) -> Result<WalletSeed, Error> {
+		let mut file = File::open(file_path)
+			.map_err(|e| ErrorKind::IO(format!("Unable to open file {}, {}", file_path, e)))?;
+		let mut buffer = String::new();
+		file.read_to_string(&mut buffer)
+			.map_err(|e| ErrorKind::IO(format!("Unable to read from file {}, {}", file_path, e)))?;
+		let enc_seed: EncryptedWalletSeed = serde_json::from_str(&buffer).map_err(|e| {
+			ErrorKind::Format(format!(
+				"Json to EncryptedWalletSeed conversion error, {}",
+				e
+			))
+		})?;
+		enc_seed.decrypt(&password)
+	}
+
 	pub fn delete_seed_file(data_file_dir: &str) -> Result<(), Error> {
 		let seed_file_path = &format!("{}{}{}", data_file_dir, MAIN_SEPARATOR, SEED_FILE,);
 		if Path::new(seed_file_path).exists() {
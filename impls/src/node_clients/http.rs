@@ -379,6 +379,8 @@ impl NodeClient for HTTPNodeClient {
 		self.block_info.clean();
 	}
 
+	fn start_cycle(&self) {}
+
 	fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
 		if let Some(v) = self.node_version_info.as_ref() {
 			return Some(v.clone());
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub mod http;
+pub mod multi;
 mod resp_types;
 
 pub use self::http::HTTPNodeClient;
+pub use self::multi::MultiNodeClient;
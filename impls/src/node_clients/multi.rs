@@ -0,0 +1,640 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Node client that works with several nodes. Calls go to the healthiest node and fail over
+//! to the next one on errors. The node is pinned for the whole wallet update or scan, so the
+//! wallet doesn't mix the chains of the nodes that are at different heights. Kernel and output
+//! lookups can require agreement of several nodes.
+
+use crate::api;
+use crate::core::core::{Transaction, TxKernel};
+use crate::libwallet::{self, ErrorKind, HeaderInfo, NodeClient, NodeVersionInfo};
+use crate::util::secp::pedersen;
+use crate::HTTPNodeClient;
+use crossbeam_utils::thread::scope;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// How long the node ranking is used before the nodes are probed again
+const RANKING_VALID_TIME: Duration = Duration::from_secs(60);
+/// Node that is behind the best known tip by more blocks is considered lagging
+const NODE_LAG_BLOCKS: u64 = 2;
+
+// Node indexes, the healthiest first
+#[derive(Default)]
+struct Ranking {
+	order: Vec<usize>,
+	updated: Option<Instant>,
+	// node that answered first in the current cycle, it is used till the next one
+	pinned: Option<usize>,
+}
+
+/// Node client over a list of nodes. The first node is the configured check node,
+/// node_url and set_node_url refer to it.
+#[derive(Clone)]
+pub struct MultiNodeClient<C = HTTPNodeClient>
+where
+	C: NodeClient,
+{
+	nodes: Vec<C>,
+	// number of nodes that must return the same kernel or outputs
+	quorum: usize,
+	ranking: Arc<RwLock<Ranking>>,
+}
+
+impl MultiNodeClient<HTTPNodeClient> {
+	/// Create a client for the node urls. The first url is the primary node.
+	pub fn from_urls(
+		node_urls: &[String],
+		node_api_secret: Option<String>,
+		quorum: usize,
+	) -> Result<Self, libwallet::Error> {
+		let mut nodes = Vec::new();
+		for url in node_urls {
+			let node = HTTPNodeClient::new(url, node_api_secret.clone()).map_err(|e| {
+				ErrorKind::GenericError(format!("Unable to create a client for {}, {}", url, e))
+			})?;
+			nodes.push(node);
+		}
+		Self::new(nodes, quorum)
+	}
+}
+
+impl<C> MultiNodeClient<C>
+where
+	C: NodeClient,
+{
+	/// Create a client over the nodes. quorum is the number of nodes that must agree
+	/// on get_kernel and get_outputs_from_node results, 1 disables the check.
+	pub fn new(nodes: Vec<C>, quorum: usize) -> Result<Self, libwallet::Error> {
+		if nodes.is_empty() {
+			return Err(ErrorKind::GenericError("No nodes are configured".to_string()).into());
+		}
+		if quorum == 0 || quorum > nodes.len() {
+			return Err(ErrorKind::GenericError(format!(
+				"Node quorum {} is invalid, {} nodes are configured",
+				quorum,
+				nodes.len()
+			))
+			.into());
+		}
+		Ok(MultiNodeClient {
+			nodes,
+			quorum,
+			ranking: Arc::new(RwLock::new(Ranking::default())),
+		})
+	}
+
+	/// Node indexes in the order they should be called, the pinned node first
+	fn ranked(&self) -> Vec<usize> {
+		if self.nodes.len() == 1 {
+			return vec![0];
+		}
+		{
+			let ranking = self.ranking.read().unwrap();
+			if let Some(pinned) = ranking.pinned {
+				let mut order = vec![pinned];
+				order.extend(ranking.order.iter().filter(|i| **i != pinned));
+				return order;
+			}
+			if let Some(updated) = ranking.updated {
+				if updated.elapsed() < RANKING_VALID_TIME {
+					return ranking.order.clone();
+				}
+			}
+		}
+
+		let order = rank_nodes(&self.probe());
+		debug!(
+			"Node ranking: {:?}",
+			order
+				.iter()
+				.map(|i| self.nodes[*i].node_url())
+				.collect::<Vec<&str>>()
+		);
+		let mut ranking = self.ranking.write().unwrap();
+		ranking.order = order.clone();
+		ranking.updated = Some(Instant::now());
+		order
+	}
+
+	/// Tip height and latency in ms of every node, None if the node isn't reachable
+	fn probe(&self) -> Vec<Option<(u64, u128)>> {
+		scope(|s| {
+			let handles: Vec<_> = self
+				.nodes
+				.iter()
+				.map(|node| {
+					s.spawn(move |_| {
+						let start = Instant::now();
+						node.get_chain_tip()
+							.ok()
+							.map(|(height, _, _)| (height, start.elapsed().as_millis()))
+					})
+				})
+				.collect();
+			handles
+				.into_iter()
+				.map(|h| h.join().unwrap_or(None))
+				.collect()
+		})
+		.unwrap_or_else(|_| vec![None; self.nodes.len()])
+	}
+
+	/// Move the failed node to the end of the ranking. The failed pinned node is unpinned.
+	fn demote(&self, idx: usize) {
+		let mut ranking = self.ranking.write().unwrap();
+		ranking.order.retain(|i| *i != idx);
+		ranking.order.push(idx);
+		if ranking.pinned == Some(idx) {
+			ranking.pinned = None;
+		}
+	}
+
+	/// Pin the node for the rest of the cycle if no node is pinned yet
+	fn pin(&self, idx: usize) {
+		let mut ranking = self.ranking.write().unwrap();
+		if ranking.pinned.is_none() {
+			ranking.pinned = Some(idx);
+		}
+	}
+
+	/// Call the nodes in the ranking order until one of them succeeds. The node that
+	/// succeeds is pinned.
+	fn call<T, F>(&self, method: &str, f: F) -> Result<T, libwallet::Error>
+	where
+		F: Fn(&C) -> Result<T, libwallet::Error>,
+	{
+		let mut last_error = None;
+		for idx in self.ranked() {
+			match f(&self.nodes[idx]) {
+				Ok(res) => {
+					if self.nodes.len() > 1 {
+						self.pin(idx);
+					}
+					return Ok(res);
+				}
+				Err(e) => {
+					if self.nodes.len() > 1 {
+						warn!(
+							"Node {} failed on {}, {}",
+							self.nodes[idx].node_url(),
+							method,
+							e
+						);
+						self.demote(idx);
+					}
+					last_error = Some(e);
+				}
+			}
+		}
+		Err(last_error.unwrap_or_else(|| {
+			ErrorKind::ClientCallback(format!("No nodes available for {}", method)).into()
+		}))
+	}
+
+	/// Call the nodes in the ranking order until quorum of them return the same result
+	fn call_quorum<T, F>(&self, method: &str, f: F) -> Result<T, libwallet::Error>
+	where
+		T: PartialEq,
+		F: Fn(&C) -> Result<T, libwallet::Error>,
+	{
+		if self.quorum <= 1 {
+			return self.call(method, f);
+		}
+
+		// distinct results and number of nodes that returned them
+		let mut answers: Vec<(T, usize)> = Vec::new();
+		let mut failed = 0;
+		for idx in self.ranked() {
+			match f(&self.nodes[idx]) {
+				Ok(res) => {
+					let pos = match answers.iter().position(|(a, _)| *a == res) {
+						Some(pos) => {
+							answers[pos].1 += 1;
+							pos
+						}
+						None => {
+							answers.push((res, 1));
+							answers.len() - 1
+						}
+					};
+					if answers[pos].1 >= self.quorum {
+						if answers.len() > 1 {
+							warn!(
+								"Nodes returned different results for {}, using the one confirmed by {} nodes",
+								method, self.quorum
+							);
+						}
+						return Ok(answers.swap_remove(pos).0);
+					}
+				}
+				Err(e) => {
+					warn!(
+						"Node {} failed on {}, {}",
+						self.nodes[idx].node_url(),
+						method,
+						e
+					);
+					self.demote(idx);
+					failed += 1;
+				}
+			}
+		}
+
+		let agreed = answers.iter().map(|(_, n)| *n).max().unwrap_or(0);
+		Err(ErrorKind::ClientCallback(format!(
+			"Unable to get {} confirmed by {} nodes, at most {} nodes agree, {} nodes failed",
+			method, self.quorum, agreed, failed
+		))
+		.into())
+	}
+}
+
+/// Order nodes by health. Nodes at the best tip go first, the fastest first. Lagging nodes
+/// follow, the highest first, and the unreachable nodes are the last resort.
+fn rank_nodes(tips: &[Option<(u64, u128)>]) -> Vec<usize> {
+	let max_height = tips.iter().filter_map(|t| t.map(|(h, _)| h)).max();
+	let mut order: Vec<usize> = (0..tips.len()).collect();
+	order.sort_by_key(|i| match (tips[*i], max_height) {
+		(Some((height, latency)), Some(max_height)) if height + NODE_LAG_BLOCKS >= max_height => {
+			(0, 0, latency)
+		}
+		(Some((height, latency)), Some(max_height)) => (1, max_height - height, latency),
+		_ => (2, 0, 0),
+	});
+	order
+}
+
+impl<C> NodeClient for MultiNodeClient<C>
+where
+	C: NodeClient,
+{
+	fn node_url(&self) -> &str {
+		self.nodes[0].node_url()
+	}
+
+	fn set_node_url(&mut self, node_url: &str) {
+		if self.nodes[0].node_url() != node_url {
+			self.nodes[0].set_node_url(node_url);
+			let mut ranking = self.ranking.write().unwrap();
+			ranking.updated = None;
+			ranking.pinned = None;
+		}
+	}
+
+	fn node_api_secret(&self) -> Option<String> {
+		self.nodes[0].node_api_secret()
+	}
+
+	/// The secret is used for all nodes
+	fn set_node_api_secret(&mut self, node_api_secret: Option<String>) {
+		for node in &mut self.nodes {
+			node.set_node_api_secret(node_api_secret.clone());
+		}
+	}
+
+	fn reset_cache(&self) {
+		for node in &self.nodes {
+			node.reset_cache();
+		}
+	}
+
+	/// The node is chosen again by the ranking, the ranking is updated if it is outdated
+	fn start_cycle(&self) {
+		self.ranking.write().unwrap().pinned = None;
+		for node in &self.nodes {
+			node.start_cycle();
+		}
+	}
+
+	fn post_tx(&self, tx: &Transaction, fluff: bool) -> Result<(), libwallet::Error> {
+		self.call("post_tx", |node| node.post_tx(tx, fluff))
+	}
+
+	fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
+		for idx in self.ranked() {
+			if let Some(info) = self.nodes[idx].get_version_info() {
+				return Some(info);
+			}
+		}
+		None
+	}
+
+	fn get_chain_tip(&self) -> Result<(u64, String, u64), libwallet::Error> {
+		self.call("get_chain_tip", |node| node.get_chain_tip())
+	}
+
+	fn get_header_info(&self, height: u64) -> Result<HeaderInfo, libwallet::Error> {
+		self.call("get_header_info", |node| node.get_header_info(height))
+	}
+
+	fn get_connected_peer_info(
+		&self,
+	) -> Result<Vec<grin_p2p::types::PeerInfoDisplayLegacy>, libwallet::Error> {
+		self.call("get_connected_peer_info", |node| {
+			node.get_connected_peer_info()
+		})
+	}
+
+	fn get_kernel(
+		&self,
+		excess: &pedersen::Commitment,
+		min_height: Option<u64>,
+		max_height: Option<u64>,
+	) -> Result<Option<(TxKernel, u64, u64)>, libwallet::Error> {
+		self.call_quorum("get_kernel", |node| {
+			node.get_kernel(excess, min_height, max_height)
+		})
+	}
+
+	fn get_outputs_from_node(
+		&self,
+		wallet_outputs: &Vec<pedersen::Commitment>,
+	) -> Result<HashMap<pedersen::Commitment, (String, u64, u64)>, libwallet::Error> {
+		self.call_quorum("get_outputs_from_node", |node| {
+			node.get_outputs_from_node(wallet_outputs)
+		})
+	}
+
+	fn get_outputs_by_pmmr_index(
+		&self,
+		start_index: u64,
+		end_index: Option<u64>,
+		max_outputs: u64,
+	) -> Result<
+		(
+			u64,
+			u64,
+			Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+		),
+		libwallet::Error,
+	> {
+		self.call("get_outputs_by_pmmr_index", |node| {
+			node.get_outputs_by_pmmr_index(start_index, end_index, max_outputs)
+		})
+	}
+
+	fn height_range_to_pmmr_indices(
+		&self,
+		start_height: u64,
+		end_height: Option<u64>,
+	) -> Result<(u64, u64), libwallet::Error> {
+		self.call("height_range_to_pmmr_indices", |node| {
+			node.height_range_to_pmmr_indices(start_height, end_height)
+		})
+	}
+
+	fn get_blocks_by_height(
+		&self,
+		start_height: u64,
+		end_height: u64,
+		threads_number: usize,
+	) -> Result<Vec<api::BlockPrintable>, libwallet::Error> {
+		self.call("get_blocks_by_height", |node| {
+			node.get_blocks_by_height(start_height, end_height, threads_number)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util::secp::pedersen::Commitment;
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	#[derive(Clone)]
+	struct TestNodeClient {
+		url: String,
+		online: Arc<AtomicBool>,
+		height: u64,
+	}
+
+	impl TestNodeClient {
+		fn new(url: &str, online: bool, height: u64) -> Self {
+			TestNodeClient {
+				url: url.to_string(),
+				online: Arc::new(AtomicBool::new(online)),
+				height,
+			}
+		}
+
+		fn check_online(&self) -> Result<(), libwallet::Error> {
+			if !self.online.load(Ordering::Relaxed) {
+				return Err(ErrorKind::ClientCallback(format!("{} is down", self.url)).into());
+			}
+			Ok(())
+		}
+
+		fn not_supported(&self, call: &str) -> libwallet::Error {
+			ErrorKind::ClientCallback(format!("{} is not supported by {}", call, self.url)).into()
+		}
+	}
+
+	impl NodeClient for TestNodeClient {
+		fn node_url(&self) -> &str {
+			&self.url
+		}
+		fn set_node_url(&mut self, node_url: &str) {
+			self.url = node_url.to_string();
+		}
+		fn node_api_secret(&self) -> Option<String> {
+			None
+		}
+		fn set_node_api_secret(&mut self, _node_api_secret: Option<String>) {}
+		fn reset_cache(&self) {}
+		fn start_cycle(&self) {}
+		fn post_tx(&self, _tx: &Transaction, _fluff: bool) -> Result<(), libwallet::Error> {
+			self.check_online()
+		}
+		fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
+			None
+		}
+		fn get_chain_tip(&self) -> Result<(u64, String, u64), libwallet::Error> {
+			self.check_online()?;
+			Ok((self.height, self.url.clone(), self.height))
+		}
+		fn get_header_info(&self, _height: u64) -> Result<HeaderInfo, libwallet::Error> {
+			Err(self.not_supported("get_header_info"))
+		}
+		fn get_connected_peer_info(
+			&self,
+		) -> Result<Vec<grin_p2p::types::PeerInfoDisplayLegacy>, libwallet::Error> {
+			Err(self.not_supported("get_connected_peer_info"))
+		}
+		fn get_kernel(
+			&self,
+			_excess: &pedersen::Commitment,
+			_min_height: Option<u64>,
+			_max_height: Option<u64>,
+		) -> Result<Option<(TxKernel, u64, u64)>, libwallet::Error> {
+			self.check_online()?;
+			Ok(None)
+		}
+		// Every node reports the output at its own tip height
+		fn get_outputs_from_node(
+			&self,
+			wallet_outputs: &Vec<pedersen::Commitment>,
+		) -> Result<HashMap<pedersen::Commitment, (String, u64, u64)>, libwallet::Error> {
+			self.check_online()?;
+			Ok(wallet_outputs
+				.iter()
+				.map(|c| (*c, (String::new(), self.height, 1)))
+				.collect())
+		}
+		fn get_outputs_by_pmmr_index(
+			&self,
+			_start_index: u64,
+			_end_index: Option<u64>,
+			_max_outputs: u64,
+		) -> Result<
+			(
+				u64,
+				u64,
+				Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+			),
+			libwallet::Error,
+		> {
+			Err(self.not_supported("get_outputs_by_pmmr_index"))
+		}
+		fn height_range_to_pmmr_indices(
+			&self,
+			_start_height: u64,
+			_end_height: Option<u64>,
+		) -> Result<(u64, u64), libwallet::Error> {
+			Err(self.not_supported("height_range_to_pmmr_indices"))
+		}
+		fn get_blocks_by_height(
+			&self,
+			_start_height: u64,
+			_end_height: u64,
+			_threads_number: usize,
+		) -> Result<Vec<api::BlockPrintable>, libwallet::Error> {
+			Err(self.not_supported("get_blocks_by_height"))
+		}
+	}
+
+	#[test]
+	fn node_ranking() {
+		let tips = [
+			Some((100, 50)),
+			None,
+			Some((90, 5)),
+			Some((101, 200)),
+			Some((99, 10)),
+		];
+		assert_eq!(rank_nodes(&tips), vec![4, 0, 3, 2, 1]);
+		assert_eq!(rank_nodes(&[None, None]), vec![0, 1]);
+	}
+
+	#[test]
+	fn node_failover() {
+		assert!(MultiNodeClient::<TestNodeClient>::new(vec![], 1).is_err());
+		let nodes = vec![
+			TestNodeClient::new("node1", false, 100),
+			TestNodeClient::new("node2", true, 90),
+			TestNodeClient::new("node3", true, 100),
+		];
+		assert!(MultiNodeClient::new(nodes.clone(), 4).is_err());
+
+		let client = MultiNodeClient::new(nodes.clone(), 1).unwrap();
+		assert_eq!(client.node_url(), "node1");
+		// node3 is in sync, node2 lags, node1 is down
+		assert_eq!(client.ranked(), vec![2, 1, 0]);
+		assert_eq!(client.get_chain_tip().unwrap().1, "node3");
+
+		// All nodes are down
+		let nodes = vec![
+			TestNodeClient::new("node1", false, 100),
+			TestNodeClient::new("node2", false, 100),
+		];
+		let client = MultiNodeClient::new(nodes, 1).unwrap();
+		assert!(client.get_chain_tip().is_err());
+		assert!(client.get_kernel(&commit(), None, None).is_err());
+	}
+
+	#[test]
+	fn node_pinning() {
+		let nodes = vec![
+			TestNodeClient::new("node1", true, 100),
+			TestNodeClient::new("node2", true, 100),
+			TestNodeClient::new("node3", true, 99),
+		];
+		let client = MultiNodeClient::new(nodes.clone(), 1).unwrap();
+		client.start_cycle();
+		let pinned = client.get_chain_tip().unwrap().1;
+
+		// The ranking changes during the cycle, the pinned node is still used
+		{
+			let mut ranking = client.ranking.write().unwrap();
+			let first = ranking.order.remove(0);
+			ranking.order.push(first);
+		}
+		assert_eq!(client.get_chain_tip().unwrap().1, pinned);
+		assert_eq!(
+			client.ranked()[0],
+			client.ranking.read().unwrap().pinned.unwrap()
+		);
+
+		// The next cycle uses the ranking
+		client.start_cycle();
+		let next = client.get_chain_tip().unwrap().1;
+		assert_ne!(next, pinned);
+		assert_eq!(client.get_chain_tip().unwrap().1, next);
+
+		// The pinned node fails, the call fails over and the new node is pinned
+		let idx = nodes.iter().position(|n| n.url == next).unwrap();
+		nodes[idx].online.store(false, Ordering::Relaxed);
+		let failover = client.get_chain_tip().unwrap().1;
+		assert_ne!(failover, next);
+		nodes[idx].online.store(true, Ordering::Relaxed);
+		assert_eq!(client.get_chain_tip().unwrap().1, failover);
+	}
+
+	#[test]
+	fn node_quorum() {
+		let nodes = vec![
+			TestNodeClient::new("node1", true, 100),
+			TestNodeClient::new("node2", true, 101),
+			TestNodeClient::new("node3", false, 100),
+			TestNodeClient::new("node4", true, 100),
+		];
+		let commits = vec![commit()];
+
+		// node1 and node4 agree
+		let client = MultiNodeClient::new(nodes.clone(), 2).unwrap();
+		let outputs = client.get_outputs_from_node(&commits).unwrap();
+		assert_eq!(outputs[&commits[0]].1, 100);
+		assert_eq!(client.get_kernel(&commits[0], None, None).unwrap(), None);
+
+		// Only two of the online nodes agree
+		let client = MultiNodeClient::new(nodes.clone(), 3).unwrap();
+		assert!(client.get_outputs_from_node(&commits).is_err());
+		assert_eq!(client.get_kernel(&commits[0], None, None).unwrap(), None);
+
+		// Without quorum the first node answers
+		let client = MultiNodeClient::new(nodes, 1).unwrap();
+		assert_eq!(client.get_outputs_from_node(&commits).unwrap().len(), 1);
+	}
+
+	fn commit() -> Commitment {
+		Commitment::from_vec(
+			crate::util::from_hex(
+				"08a30bc4893f169098cab8291d699741853553f897f202fcdea2ca3d9c187551ab",
+			)
+			.unwrap(),
+		)
+	}
+}
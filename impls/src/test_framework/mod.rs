@@ -16,7 +16,9 @@ use crate::api;
 use crate::chain;
 use crate::chain::Chain;
 use crate::core;
-use crate::core::core::{Output, OutputFeatures, OutputIdentifier, Transaction, TxKernel};
+use crate::core::core::{
+	BlockHeader, Output, OutputFeatures, OutputIdentifier, Transaction, TxKernel,
+};
 use crate::core::{consensus, global, pow};
 use crate::keychain;
 use crate::libwallet;
@@ -34,7 +36,9 @@ use std::thread;
 
 mod testclient;
 
-pub use self::{testclient::LocalWalletClient, testclient::WalletProxy};
+pub use self::{
+	testclient::CountingNodeClient, testclient::LocalWalletClient, testclient::WalletProxy,
+};
 
 /// Get an output from the chain locally and present it back as an API output
 fn get_output_local(chain: &chain::Chain, commit: &pedersen::Commitment) -> Option<api::Output> {
@@ -131,10 +135,29 @@ pub fn add_block_with_reward(
 	reward_kernel: TxKernel,
 ) {
 	let prev = chain.head_header().unwrap();
+	add_block_with_reward_on(
+		chain,
+		&prev,
+		txs,
+		reward_output,
+		reward_kernel,
+		chain::Options::MINE,
+	);
+}
+
+// Builds the block on top of prev, mines and processes it
+fn add_block_with_reward_on(
+	chain: &Chain,
+	prev: &BlockHeader,
+	txs: Vec<&Transaction>,
+	reward_output: Output,
+	reward_kernel: TxKernel,
+	opts: chain::Options,
+) -> BlockHeader {
 	let next_header_info = consensus::next_difficulty(1, chain.difficulty_iter().unwrap());
 	let txs_cloned: Vec<Transaction> = txs.into_iter().cloned().collect();
 	let mut b = core::core::Block::new(
-		&prev,
+		prev,
 		&txs_cloned,
 		next_header_info.clone().difficulty,
 		(reward_output, reward_kernel),
@@ -150,8 +173,10 @@ pub fn add_block_with_reward(
 		global::min_edge_bits(),
 	)
 	.unwrap();
-	chain.process_block(b, chain::Options::MINE).unwrap();
+	let header = b.header.clone();
+	chain.process_block(b, opts).unwrap();
 	chain.validate(false).unwrap();
+	header
 }
 
 /// adds a reward output to a wallet, includes that reward in a block, mines
@@ -186,6 +211,40 @@ where
 	Ok(())
 }
 
+/// Mines a block with a reward to the wallet on top of the given header. It is a fork if the
+/// header is not the chain head, the fork becomes the chain when it gets longer. The difficulty
+/// of the fork blocks is not validated. Return the header of the new block.
+pub fn award_fork_block_to_wallet<'a, L, C, K>(
+	chain: &Chain,
+	prev: &BlockHeader,
+	wallet: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K> + 'a>>>,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<BlockHeader, libwallet::Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: keychain::Keychain + 'a,
+{
+	let block_fees = BlockFees {
+		fees: 0,
+		key_id: None,
+		height: prev.height + 1,
+	};
+	let coinbase_tx = {
+		let mut w_lock = wallet.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		foreign::build_coinbase(&mut **w, keychain_mask, &block_fees, false)?
+	};
+	Ok(add_block_with_reward_on(
+		chain,
+		prev,
+		vec![],
+		coinbase_tx.output,
+		coinbase_tx.kernel,
+		chain::Options::SKIP_POW,
+	))
+}
+
 /// Award a blocks to a wallet directly
 pub fn award_blocks_to_wallet<'a, L, C, K>(
 	chain: &Chain,
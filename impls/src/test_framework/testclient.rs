@@ -477,6 +477,7 @@ impl NodeClient for LocalWalletClient {
 	fn set_node_url(&mut self, _node_url: &str) {}
 	fn set_node_api_secret(&mut self, _node_api_secret: Option<String>) {}
 	fn reset_cache(&self) {}
+	fn start_cycle(&self) {}
 	fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
 		None
 	}
@@ -682,6 +683,9 @@ impl NodeClient for LocalWalletClient {
 			method: "get_outputs_by_pmmr_index".to_owned(),
 			body: query_str,
 		};
+		// Locking rx before the request, so the concurrent requests of the UTXO scan
+		// don't take the responses of each other
+		let r = self.rx.lock();
 		{
 			let p = self.proxy_tx.lock();
 			p.send(m).map_err(|e| {
@@ -692,7 +696,6 @@ impl NodeClient for LocalWalletClient {
 			})?;
 		}
 
-		let r = self.rx.lock();
 		let m = r.recv().unwrap();
 		let o: api::OutputListing = serde_json::from_str(&m.body).unwrap();
 
@@ -776,6 +779,138 @@ impl NodeClient for LocalWalletClient {
 		Ok(o)
 	}
 }
+/// Node client that counts the calls of the wrapped client, so the tests can check
+/// what the wallet asks the node about
+#[derive(Clone)]
+pub struct CountingNodeClient<C>
+where
+	C: NodeClient,
+{
+	/// wrapped client
+	pub client: C,
+	/// number of calls by the method name
+	calls: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl<C> CountingNodeClient<C>
+where
+	C: NodeClient,
+{
+	/// new
+	pub fn new(client: C) -> Self {
+		CountingNodeClient {
+			client,
+			calls: Arc::new(Mutex::new(HashMap::new())),
+		}
+	}
+
+	/// Number of the calls of the method
+	pub fn calls(&self, method: &str) -> usize {
+		self.calls.lock().get(method).cloned().unwrap_or(0)
+	}
+
+	fn count(&self, method: &str) {
+		*self.calls.lock().entry(method.to_owned()).or_insert(0) += 1;
+	}
+}
+
+impl<C> NodeClient for CountingNodeClient<C>
+where
+	C: NodeClient,
+{
+	fn node_url(&self) -> &str {
+		self.client.node_url()
+	}
+	fn node_api_secret(&self) -> Option<String> {
+		self.client.node_api_secret()
+	}
+	fn set_node_url(&mut self, node_url: &str) {
+		self.client.set_node_url(node_url)
+	}
+	fn set_node_api_secret(&mut self, node_api_secret: Option<String>) {
+		self.client.set_node_api_secret(node_api_secret)
+	}
+	fn reset_cache(&self) {
+		self.client.reset_cache()
+	}
+	fn start_cycle(&self) {
+		self.client.start_cycle()
+	}
+	fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
+		self.client.get_version_info()
+	}
+	fn post_tx(&self, tx: &Transaction, fluff: bool) -> Result<(), libwallet::Error> {
+		self.count("post_tx");
+		self.client.post_tx(tx, fluff)
+	}
+	fn get_chain_tip(&self) -> Result<(u64, String, u64), libwallet::Error> {
+		self.count("get_chain_tip");
+		self.client.get_chain_tip()
+	}
+	fn get_header_info(&self, height: u64) -> Result<HeaderInfo, libwallet::Error> {
+		self.count("get_header_info");
+		self.client.get_header_info(height)
+	}
+	fn get_connected_peer_info(
+		&self,
+	) -> Result<Vec<grin_p2p::types::PeerInfoDisplayLegacy>, libwallet::Error> {
+		self.count("get_connected_peer_info");
+		self.client.get_connected_peer_info()
+	}
+	fn get_outputs_from_node(
+		&self,
+		wallet_outputs: &Vec<pedersen::Commitment>,
+	) -> Result<HashMap<pedersen::Commitment, (String, u64, u64)>, libwallet::Error> {
+		self.count("get_outputs_from_node");
+		self.client.get_outputs_from_node(wallet_outputs)
+	}
+	fn get_kernel(
+		&self,
+		excess: &pedersen::Commitment,
+		min_height: Option<u64>,
+		max_height: Option<u64>,
+	) -> Result<Option<(TxKernel, u64, u64)>, libwallet::Error> {
+		self.count("get_kernel");
+		self.client.get_kernel(excess, min_height, max_height)
+	}
+	fn get_outputs_by_pmmr_index(
+		&self,
+		start_height: u64,
+		end_height: Option<u64>,
+		max_outputs: u64,
+	) -> Result<
+		(
+			u64,
+			u64,
+			Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+		),
+		libwallet::Error,
+	> {
+		self.count("get_outputs_by_pmmr_index");
+		self.client
+			.get_outputs_by_pmmr_index(start_height, end_height, max_outputs)
+	}
+	fn height_range_to_pmmr_indices(
+		&self,
+		start_height: u64,
+		end_height: Option<u64>,
+	) -> Result<(u64, u64), libwallet::Error> {
+		self.count("height_range_to_pmmr_indices");
+		self.client
+			.height_range_to_pmmr_indices(start_height, end_height)
+	}
+	fn get_blocks_by_height(
+		&self,
+		start_height: u64,
+		end_height: u64,
+		threads_number: usize,
+	) -> Result<Vec<api::BlockPrintable>, libwallet::Error> {
+		self.count("get_blocks_by_height");
+		self.client
+			.get_blocks_by_height(start_height, end_height, threads_number)
+	}
+}
+
 unsafe impl<'a, L, C, K> Send for WalletProxy<'a, L, C, K>
 where
 	L: WalletLCProvider<'a, C, K>,
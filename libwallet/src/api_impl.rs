@@ -23,6 +23,12 @@
 
 pub mod foreign;
 pub mod owner;
+pub mod owner_contacts;
+pub mod owner_events;
+pub mod owner_multisig;
+pub mod owner_payment_requests;
+pub mod owner_schedule;
 pub mod owner_swap;
 pub mod owner_updater;
+pub mod owner_utxo;
 pub mod types;
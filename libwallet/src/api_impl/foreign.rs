@@ -14,7 +14,7 @@
 
 //! Generic implementation of owner API functions
 use crate::api_impl::owner::check_ttl;
-use crate::api_impl::owner_swap;
+use crate::api_impl::{owner_payment_requests, owner_swap};
 use crate::grin_keychain::Keychain;
 use crate::grin_util::secp::key::SecretKey;
 use crate::grin_util::Mutex;
@@ -161,6 +161,7 @@ where
 	debug!("foreign just received_tx just got slate = {:?}", slate);
 	let mut ret_slate = slate.clone();
 	check_ttl(w, &ret_slate, refresh_from_node)?;
+	owner_payment_requests::verify_payment(&mut *w, &ret_slate)?;
 
 	let mut dest_acct_name = dest_acct_name.map(|s| s.to_string());
 	if dest_acct_name.is_none() {
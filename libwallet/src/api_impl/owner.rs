@@ -18,9 +18,11 @@ use uuid::Uuid;
 
 use crate::grin_core::core::hash::Hashed;
 use crate::grin_core::core::Transaction;
+use crate::grin_core::libtx::tx_fee;
 use crate::grin_util::secp::key::SecretKey;
 use crate::grin_util::Mutex;
 
+use crate::api_impl::owner_payment_requests;
 use crate::api_impl::owner_updater::StatusMessage;
 use crate::grin_keychain::{Identifier, Keychain};
 use crate::grin_util::secp::key::PublicKey;
@@ -28,17 +30,20 @@ use crate::grin_util::secp::key::PublicKey;
 use crate::internal::{keys, scan, selection, tx, updater};
 use crate::slate::{PaymentInfo, Slate};
 use crate::types::{
-	AcctPathMapping, Context, NodeClient, OutputData, TxLogEntry, WalletBackend, WalletInfo,
+	AcctPathMapping, BatchPayoutEntry, Context, NodeClient, OutputData, OutputMetadata,
+	ScanCheckpoint, TxLogEntry, ViewKey, WalletBackend, WalletInfo,
 };
 use crate::{
-	wallet_lock, InitTxArgs, IssueInvoiceTxArgs, NodeHeightResult, OutputCommitMapping,
-	PaymentProof, ScannedBlockInfo, TxLogEntryType, WalletInst, WalletLCProvider,
+	wallet_lock, BatchPayout, CoinSelectionStrategy, FeeEstimate, InitTxArgs, IssueInvoiceTxArgs,
+	NodeHeightResult, OutputCommitMapping, PaymentProof, ScannedBlockInfo, TxExportArgs,
+	TxExportFormat, TxExportRecord, TxLogEntryType, TxQueryArgs, WalletInst, WalletLCProvider,
 };
 use crate::{Error, ErrorKind};
 
 use crate::proof::tx_proof::{pop_proof_for_slate, TxProof};
 use ed25519_dalek::PublicKey as DalekPublicKey;
 use std::cmp;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::Write;
 use std::sync::mpsc::Sender;
@@ -83,6 +88,83 @@ where
 	w.set_parent_key_id_by_name(label)
 }
 
+/// Apply a change to the user metadata of the output. Empty metadata is deleted.
+fn update_output_metadata<'a, T: ?Sized, C, K, F>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	commit: &str,
+	update: F,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+	F: FnOnce(&mut OutputMetadata),
+{
+	if w.get_outputs_by_commit(commit)?.is_empty() {
+		return Err(ErrorKind::UnknownOutput(commit.to_string()).into());
+	}
+
+	let mut metadata = w
+		.get_output_metadata(commit)?
+		.unwrap_or_else(|| OutputMetadata::new(commit.to_string()));
+	update(&mut metadata);
+
+	let mut batch = w.batch(keychain_mask)?;
+	if metadata.is_empty() {
+		batch.delete_output_metadata(commit)?;
+	} else {
+		batch.save_output_metadata(&metadata)?;
+	}
+	batch.commit()?;
+	Ok(())
+}
+
+/// Set (or clear with None) the label of the output
+pub fn set_output_label<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	commit: &str,
+	label: Option<String>,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	update_output_metadata(w, keychain_mask, commit, |m| m.label = label)
+}
+
+/// Set (or clear with None) the note of the output
+pub fn set_output_note<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	commit: &str,
+	note: Option<String>,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	update_output_metadata(w, keychain_mask, commit, |m| m.note = note)
+}
+
+/// Freeze or unfreeze the output. Frozen outputs are never selected for spending.
+pub fn set_output_frozen<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	commit: &str,
+	frozen: bool,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	update_output_metadata(w, keychain_mask, commit, |m| m.frozen = frozen)
+}
+
 /// Retrieve the MQS address for the wallet
 pub fn get_mqs_address<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -99,6 +181,23 @@ where
 	Ok(pub_key)
 }
 
+/// Retrieve the view key that allows to build a watch-only copy of the wallet
+pub fn get_view_key<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<ViewKey, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	match w.view_key() {
+		Some(view_key) => Ok(view_key),
+		None => Ok(ViewKey::from_keychain(&w.keychain(keychain_mask)?)),
+	}
+}
+
 /// Retrieve TOR or public wallet address
 pub fn get_wallet_public_address<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -222,6 +321,101 @@ where
 	Ok((validated, txs))
 }
 
+/// Retrieve the transaction log entries of the active account that match the query
+pub fn query_txs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	refresh_from_node: bool,
+	query: &TxQueryArgs,
+) -> Result<(bool, Vec<TxLogEntry>), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut validated = false;
+	if refresh_from_node {
+		validated =
+			perform_refresh_from_node(wallet_inst.clone(), keychain_mask, status_send_channel)?;
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let parent_key_id = w.parent_key_id();
+	let txs = updater::query_txs(&mut **w, Some(&parent_key_id), query)?;
+	Ok((validated, txs))
+}
+
+/// Export the transaction log as CSV or JSON text
+pub fn export_txs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	refresh_from_node: bool,
+	args: &TxExportArgs,
+) -> Result<(bool, String), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut validated = false;
+	if refresh_from_node {
+		validated =
+			perform_refresh_from_node(wallet_inst.clone(), keychain_mask, status_send_channel)?;
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let accounts = keys::accounts(&mut **w)?;
+	let parent_key_id = match &args.account {
+		Some(label) => Some(
+			accounts
+				.iter()
+				.find(|a| a.label == *label)
+				.ok_or_else(|| ErrorKind::UnknownAccountLabel(label.clone()))?
+				.path
+				.clone(),
+		),
+		None => None,
+	};
+
+	let txs = updater::retrieve_txs(
+		&mut **w,
+		keychain_mask,
+		None,
+		None,
+		parent_key_id.as_ref(),
+		false,
+		None,
+		None,
+	)?;
+
+	let records: Vec<TxExportRecord> = txs
+		.iter()
+		.filter(|tx| args.matches(tx))
+		.map(|tx| {
+			let account = accounts
+				.iter()
+				.find(|a| a.path == tx.parent_key_id)
+				.map(|a| a.label.clone())
+				.unwrap_or_else(|| tx.parent_key_id.to_bip_32_string());
+			TxExportRecord::from_tx_log_entry(tx, &account)
+		})
+		.collect();
+
+	let res = match args.format {
+		TxExportFormat::Csv => {
+			let mut lines = vec![TxExportRecord::csv_header()];
+			lines.extend(records.iter().map(|r| r.to_csv_row()));
+			lines.join("\n") + "\n"
+		}
+		TxExportFormat::Json => serde_json::to_string_pretty(&records).map_err(|e| {
+			ErrorKind::GenericError(format!("Unable to serialize transactions, {}", e))
+		})?,
+	};
+	Ok((validated, res))
+}
+
 /// Retrieve summary info
 pub fn retrieve_summary_info<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -377,7 +571,8 @@ where
 	let uuid = txs[0].tx_slate_id.ok_or_else(|| {
 		ErrorKind::GenericError(format!("Unable to find slateId for txId, {}", tx_id))
 	})?;
-	let proof = TxProof::get_stored_tx_proof(w.get_data_file_dir(), &uuid.to_string())
+	let proof = w
+		.get_stored_tx_proof(&uuid.to_string())
 		.map_err(|e| ErrorKind::TransactionHasNoProof(format!("{}", e)))?;
 	return Ok(proof);
 }
@@ -407,6 +602,15 @@ where
 		None => w.parent_key_id(),
 	};
 
+	// Sweep is a normal send of the selected outputs, with the amount that leaves nothing for change
+	let sweep_args;
+	let args = if args.sweep.unwrap_or(false) {
+		sweep_args = sweep_init_args(&mut *w, args, &parent_key_id, routputs)?;
+		&sweep_args
+	} else {
+		args
+	};
+
 	let message = match &args.message {
 		Some(m) => {
 			let mut m = m.clone();
@@ -437,7 +641,7 @@ where
 			args.minimum_confirmations,
 			args.max_outputs as usize,
 			args.num_change_outputs as usize,
-			args.selection_strategy_is_use_all,
+			args.coin_selection_strategy(),
 			&parent_key_id,
 			&args.outputs,
 			routputs,
@@ -449,6 +653,34 @@ where
 		return Ok(slate);
 	}
 
+	// The payee matches the payment with its request by the slate id
+	if let Some(id) = args.payment_request_id {
+		if !w.get_tx_log_by_slate_id(&id)?.is_empty() {
+			return Err(ErrorKind::GenericError(format!(
+				"Payment request {} is already paid by this wallet",
+				id
+			))
+			.into());
+		}
+		slate.id = id;
+	}
+	if let Some(id) = args.slate_id {
+		if args.payment_request_id.is_some() {
+			return Err(ErrorKind::GenericError(
+				"Slate id can't be set for the payment request transaction".to_string(),
+			)
+			.into());
+		}
+		if !w.get_tx_log_by_slate_id(&id)?.is_empty() {
+			return Err(ErrorKind::GenericError(format!(
+				"Transaction with slate id {} already exists",
+				id
+			))
+			.into());
+		}
+		slate.id = id;
+	}
+
 	// Updating height because it is lookup height for the kernel
 	slate.height = w.w2n_client().get_chain_tip()?.0;
 	let h = slate.height;
@@ -479,7 +711,7 @@ where
 			args.minimum_confirmations,
 			args.max_outputs as usize,
 			args.num_change_outputs as usize,
-			args.selection_strategy_is_use_all,
+			args.coin_selection_strategy(),
 			&parent_key_id,
 			0,
 			message,
@@ -543,6 +775,142 @@ where
 	Ok(slate)
 }
 
+// Arguments for sending everything from the account. The largest eligible outputs
+// are selected explicitly and the amount is their value minus the fee.
+fn sweep_init_args<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	args: &InitTxArgs,
+	parent_key_id: &Identifier,
+	routputs: usize,
+) -> Result<InitTxArgs, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if args.late_lock.unwrap_or(false) {
+		return Err(ErrorKind::GenericError(
+			"Sweep can't be used with late lock, the amount depends on the inputs".to_string(),
+		)
+		.into());
+	}
+
+	let current_height = w.w2n_client().get_chain_tip()?.0;
+	let (coins, amount, _fee) = selection::max_send_amount(
+		&mut *w,
+		current_height,
+		args.minimum_confirmations,
+		args.max_outputs as usize,
+		parent_key_id,
+		&args.outputs,
+		routputs,
+		args.exclude_change_outputs.unwrap_or(false),
+		args.minimum_confirmations_change_outputs,
+	);
+	if coins.is_empty() {
+		return Err(ErrorKind::GenericError(
+			"No outputs to sweep, or their value doesn't cover the transaction fee".to_string(),
+		)
+		.into());
+	}
+
+	Ok(InitTxArgs {
+		amount,
+		num_change_outputs: 0,
+		selection_strategy_is_use_all: true,
+		selection_strategy: Some(CoinSelectionStrategy::All),
+		outputs: Some(coins.iter().filter_map(|c| c.commit.clone()).collect()),
+		sweep: Some(false),
+		..args.clone()
+	})
+}
+
+/// Estimate the fee of a send transaction. Outputs are selected the same way as
+/// init_send_tx does, but no slate is created and nothing is locked or saved.
+/// If args.amount is zero or args.sweep is set, the estimate is done for the maximum amount
+/// that can be sent. Caller is responsible for wallet refresh
+pub fn estimate_fee<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	args: &InitTxArgs,
+	routputs: usize, // Number of resulting outputs. Normally it is 1
+) -> Result<FeeEstimate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = match &args.src_acct_name {
+		Some(d) => {
+			let pm = w.get_acct_path(d.clone())?;
+			match pm {
+				Some(p) => p.path,
+				None => w.parent_key_id(),
+			}
+		}
+		None => w.parent_key_id(),
+	};
+
+	let current_height = w.w2n_client().get_chain_tip()?.0;
+
+	let (max_inputs, max_amount, max_fee) = selection::max_send_amount(
+		&mut *w,
+		current_height,
+		args.minimum_confirmations,
+		args.max_outputs as usize,
+		&parent_key_id,
+		&args.outputs,
+		routputs,
+		args.exclude_change_outputs.unwrap_or(false),
+		args.minimum_confirmations_change_outputs,
+	);
+
+	if args.amount == 0 || args.sweep.unwrap_or(false) {
+		return Ok(FeeEstimate {
+			amount: max_amount,
+			fee: max_fee,
+			total: max_amount + max_fee,
+			change: 0,
+			num_change_outputs: 0,
+			weight: tx_fee(max_inputs.len(), routputs, 1, Some(1)),
+			inputs: max_inputs,
+			max_amount,
+		});
+	}
+
+	let (coins, total, amount, fee) = selection::select_coins_and_fee(
+		&mut *w,
+		args.amount,
+		current_height,
+		args.minimum_confirmations,
+		args.max_outputs as usize,
+		args.num_change_outputs as usize,
+		args.coin_selection_strategy(),
+		&parent_key_id,
+		&args.outputs,
+		routputs,
+		args.exclude_change_outputs.unwrap_or(false),
+		args.minimum_confirmations_change_outputs,
+	)?;
+
+	let change = total - amount - fee;
+	let num_change_outputs = if change == 0 {
+		0
+	} else {
+		args.num_change_outputs as usize
+	};
+
+	Ok(FeeEstimate {
+		amount,
+		fee,
+		total,
+		change,
+		num_change_outputs,
+		weight: tx_fee(coins.len(), routputs + num_change_outputs, 1, Some(1)),
+		inputs: coins,
+		max_amount,
+	})
+}
+
 /// Initiate a transaction as the recipient (invoicing)
 pub fn issue_invoice_tx<'a, T: ?Sized, C, K>(
 	w: &mut T,
@@ -567,7 +935,36 @@ where
 		None => w.parent_key_id(),
 	};
 
-	let message = match &args.message {
+	// Invoice for the payment request, the amount and the memo are taken from the request
+	let request = match &args.payment_request_id {
+		Some(id) => {
+			owner_payment_requests::update_payment_requests(&mut *w, keychain_mask)?;
+			let request = owner_payment_requests::get_open_payment_request(&mut *w, id)?;
+			if !w.get_tx_log_by_slate_id(id)?.is_empty() {
+				return Err(ErrorKind::GenericError(format!(
+					"Transaction for the payment request {} already exists",
+					id
+				))
+				.into());
+			}
+			if args.amount != 0 && args.amount != request.amount {
+				return Err(ErrorKind::GenericError(format!(
+					"Invoice amount {} doesn't match the payment request amount {}",
+					args.amount, request.amount
+				))
+				.into());
+			}
+			Some(request)
+		}
+		None => None,
+	};
+	let amount = request.as_ref().map(|r| r.amount).unwrap_or(args.amount);
+
+	let message = match args
+		.message
+		.as_ref()
+		.or(request.as_ref().and_then(|r| r.memo.as_ref()))
+	{
 		Some(m) => {
 			let mut m = m.clone();
 			m.truncate(USER_MESSAGE_MAX_LEN);
@@ -577,7 +974,10 @@ where
 	};
 
 	let compact_slate = args.slatepack_recipient.is_some();
-	let mut slate = tx::new_tx_slate(&mut *w, args.amount, 2, use_test_rng, None, compact_slate)?;
+	let mut slate = tx::new_tx_slate(&mut *w, amount, 2, use_test_rng, None, compact_slate)?;
+	if let Some(r) = &request {
+		slate.id = r.id;
+	}
 	let chain_tip = slate.height; // it is fresh slate, height is a tip
 	let context = tx::add_output_to_slate(
 		&mut *w,
@@ -679,7 +1079,7 @@ where
 		args.minimum_confirmations,
 		args.max_outputs as usize,
 		args.num_change_outputs as usize,
-		args.selection_strategy_is_use_all,
+		args.coin_selection_strategy(),
 		&parent_key_id,
 		1, // Participant id 1 for mwc713 compatibility
 		message,
@@ -835,7 +1235,7 @@ where
 			args.minimum_confirmations,
 			args.max_outputs as usize,
 			args.num_change_outputs as usize,
-			args.selection_strategy_is_use_all,
+			args.coin_selection_strategy(),
 			parent_key_id.clone(),
 			0,
 			use_test_rng,
@@ -897,12 +1297,285 @@ where
 			proof.outputs.push(output.clone());
 		}
 
-		proof.store_tx_proof(w.get_data_file_dir(), &slate.id.to_string())?;
+		w.store_tx_proof(&slate.id.to_string(), &proof)?;
 	};
 
 	Ok((sl, context))
 }
 
+/// Initiate a batch payout as sender. Every recipient gets its own slate, the slates are
+/// exchanged with the recipients as the regular send slates and finalized together by
+/// finalize_batch_tx into a single transaction. The first slate carries the inputs and
+/// change, the fee is shared by the kernels.
+/// Outputs are locked here, the batch is recorded as a single sent transaction.
+/// Caller is responsible for wallet refresh
+pub fn init_batch_send_tx<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &InitTxArgs,
+	payouts: &[BatchPayout],
+	use_test_rng: bool,
+) -> Result<Vec<Slate>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if payouts.is_empty() {
+		return Err(ErrorKind::GenericError(
+			"Batch payout needs at least one recipient".to_string(),
+		)
+		.into());
+	}
+	if payouts.iter().any(|p| p.amount == 0) {
+		return Err(
+			ErrorKind::GenericError("Batch payout amount can't be zero".to_string()).into(),
+		);
+	}
+	if args.late_lock.unwrap_or(false)
+		|| args.estimate_only.unwrap_or(false)
+		|| args.sweep.unwrap_or(false)
+		|| args.payment_proof_recipient_address.is_some()
+	{
+		return Err(ErrorKind::GenericError(
+			"Lock later, sweep, estimates and payment proofs are not supported by batch payouts"
+				.to_string(),
+		)
+		.into());
+	}
+
+	let parent_key_id = match &args.src_acct_name {
+		Some(d) => {
+			let pm = w.get_acct_path(d.clone())?;
+			match pm {
+				Some(p) => p.path,
+				None => w.parent_key_id(),
+			}
+		}
+		None => w.parent_key_id(),
+	};
+
+	let message = match &args.message {
+		Some(m) => {
+			let mut m = m.clone();
+			m.truncate(USER_MESSAGE_MAX_LEN);
+			Some(m)
+		}
+		None => None,
+	};
+
+	let compact_slate =
+		args.slatepack_recipient.is_some() || args.target_slate_version.clone().unwrap_or(0) >= 4;
+
+	let mut slates = vec![];
+	for p in payouts {
+		slates.push(tx::new_tx_slate(
+			&mut *w,
+			p.amount,
+			2,
+			use_test_rng,
+			args.ttl_blocks,
+			compact_slate,
+		)?);
+	}
+
+	let keychain = w.keychain(keychain_mask)?;
+	let mut contexts = selection::build_batch_send_tx(
+		&mut *w,
+		&keychain,
+		keychain_mask,
+		&mut slates,
+		args.minimum_confirmations,
+		args.max_outputs as usize,
+		args.num_change_outputs as usize,
+		args.coin_selection_strategy(),
+		parent_key_id,
+		use_test_rng,
+		&args.outputs,
+		args.exclude_change_outputs.unwrap_or(false),
+		args.minimum_confirmations_change_outputs,
+		message.clone(),
+	)?;
+
+	for (slate, context) in slates.iter_mut().zip(contexts.iter_mut()) {
+		slate.fill_round_1(
+			&keychain,
+			&mut context.sec_key,
+			&context.sec_nonce,
+			0,
+			message.clone(),
+			use_test_rng,
+		)?;
+		context.initial_sec_key = context.sec_key.clone();
+
+		// mwc713 payment proof support.
+		context.input_commits = slate.tx.inputs_committed();
+		for output in slate.tx.outputs() {
+			context.output_commits.push(output.commitment());
+		}
+	}
+
+	// The first slate has all inputs and change, locking them records the transaction.
+	// The aggsig contexts (needed when the slates come back) and the payouts are saved
+	// in the same DB batch, so the batch is either recorded completely or not at all.
+	let mut lock_slate = slates[0].clone();
+	if lock_slate.compact_slate {
+		selection::repopulate_tx(
+			&mut *w,
+			keychain_mask,
+			&mut lock_slate,
+			&contexts[0],
+			true,
+			use_test_rng,
+		)?;
+	}
+	let height = w.w2n_client().get_chain_tip()?.0;
+	selection::lock_tx_context_with(
+		&mut *w,
+		keychain_mask,
+		&lock_slate,
+		height,
+		&contexts[0],
+		None,
+		None,
+		|batch, tx_entry| {
+			for (slate, context) in slates.iter().zip(contexts.iter()) {
+				batch.save_private_context(slate.id.as_bytes(), 0, context)?;
+			}
+			tx_entry.fee = Some(slates.iter().map(|s| s.fee).sum());
+			tx_entry.batch_payouts = Some(
+				slates
+					.iter()
+					.zip(payouts)
+					.map(|(s, p)| BatchPayoutEntry {
+						slate_id: s.id,
+						address: p.address.clone(),
+						amount: s.amount,
+						fee: s.fee,
+						kernel_excess: None,
+					})
+					.collect(),
+			);
+			Ok(())
+		},
+	)?;
+
+	Ok(slates)
+}
+
+/// Finalize a batch payout. Every slate has to come back from its recipient. The kernels
+/// are signed one by one and aggregated with all inputs and outputs into a single
+/// transaction. The transaction is stored and returned, ready for posting.
+pub fn finalize_batch_tx<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slates: &[Slate],
+	refresh_from_node: bool,
+	use_test_rng: bool,
+) -> Result<Transaction, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let slate_id = match slates.first() {
+		Some(s) => s.id,
+		None => {
+			return Err(
+				ErrorKind::GenericError("Batch payout doesn't have any slates".to_string()).into(),
+			)
+		}
+	};
+	check_ttl(w, &slates[0], refresh_from_node)?;
+
+	// The entry is indexed by the first slate of the batch, the slates can come back in any order
+	let mut found = vec![];
+	for slate in slates {
+		found.extend(w.get_tx_log_by_slate_id(&slate.id)?);
+	}
+	let mut tx_entry = found
+		.into_iter()
+		.find(|t| {
+			t.tx_type == TxLogEntryType::TxSent
+				&& t.batch_payouts
+					.as_ref()
+					.map(|b| b.iter().any(|p| p.slate_id == slate_id))
+					.unwrap_or(false)
+		})
+		.ok_or(ErrorKind::TransactionDoesntExist(slate_id.to_string()))?;
+	let mut payouts = tx_entry.batch_payouts.take().unwrap_or(vec![]);
+	if payouts.len() != slates.len() {
+		return Err(ErrorKind::GenericError(format!(
+			"Batch payout has {} recipients, but {} slates are provided",
+			payouts.len(),
+			slates.len()
+		))
+		.into());
+	}
+
+	let keychain = w.keychain(keychain_mask)?;
+
+	// Slates are finalized in the batch order, so the first one has the inputs
+	let mut batch_slates = vec![];
+	for p in payouts.iter_mut() {
+		let mut sl = slates
+			.iter()
+			.find(|s| s.id == p.slate_id)
+			.ok_or(ErrorKind::GenericError(format!(
+				"Slate {} of the batch payout is missing",
+				p.slate_id
+			)))?
+			.clone();
+		let context = w.get_private_context(keychain_mask, sl.id.as_bytes(), 0)?;
+
+		if sl.compact_slate {
+			// Add our contribution to the offset
+			sl.adjust_offset(&keychain, &context)?;
+
+			selection::repopulate_tx(
+				&mut *w,
+				keychain_mask,
+				&mut sl,
+				&context,
+				true,
+				use_test_rng,
+			)?;
+		}
+
+		if sl.amount != p.amount || sl.fee != p.fee {
+			return Err(ErrorKind::SlateValidation(format!(
+				"amount or fee mismatch for slate {}",
+				sl.id
+			))
+			.into());
+		}
+
+		sl.fill_round_2(keychain.secp(), &context.sec_key, &context.sec_nonce, 0)?;
+		sl.finalize_batch_kernel(&keychain)?;
+		p.kernel_excess = Some(sl.tx.kernels()[0].excess);
+		batch_slates.push(sl);
+	}
+
+	let final_tx = Slate::aggregate_batch(&keychain, &batch_slates)?;
+
+	w.store_tx(&format!("{}", payouts[0].slate_id), &final_tx)?;
+
+	// Any of the kernels will do for the lookup, they are all in the same transaction
+	tx_entry.kernel_excess = payouts[0].kernel_excess.clone();
+	tx_entry.batch_payouts = Some(payouts);
+	{
+		let parent_key_id = tx_entry.parent_key_id.clone();
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_tx_log_entry(tx_entry, &parent_key_id)?;
+		for sl in &batch_slates {
+			batch.delete_private_context(sl.id.as_bytes(), 0)?;
+		}
+		batch.commit()?;
+	}
+
+	Ok(final_tx)
+}
+
 /// cancel tx
 pub fn cancel_tx<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -993,23 +1666,29 @@ where
 	{
 		wallet_lock!(wallet_inst, w);
 		w.w2n_client().reset_cache();
+		w.w2n_client().start_cycle();
 	}
 
 	// Checking from what point we should start scanning
-	let (tip_height, tip_hash, last_scanned_block, has_reorg) = get_last_detect_last_scanned_block(
-		wallet_inst.clone(),
-		keychain_mask,
-		status_send_channel,
-	)?;
+	let (tip_height, tip_hash, last_scanned_block, reorg_depth) =
+		get_last_detect_last_scanned_block(
+			wallet_inst.clone(),
+			keychain_mask,
+			status_send_channel,
+		)?;
 
 	if tip_height == 0 {
 		return Err(ErrorKind::NodeNotReady)?;
 	}
 
-	if has_reorg {
-		info!(
-			"Wallet update will do full outputs checking because since last update reorg happend"
-		);
+	if reorg_depth > 0 {
+		rollback_reorg(
+			wallet_inst.clone(),
+			keychain_mask,
+			last_scanned_block.height,
+			reorg_depth,
+			status_send_channel,
+		)?;
 	}
 
 	debug!(
@@ -1022,9 +1701,108 @@ where
 		None => 1,
 	};
 
+	let view_key = {
+		wallet_lock!(wallet_inst, w);
+		w.view_key()
+	};
+
+	// Scan progress is saved with the checkpoints, so the interrupted scan can be resumed.
+	// The new scan replaces the checkpoint of the previous one.
+	if view_key.is_none() {
+		wallet_lock!(wallet_inst, w);
+		let mut batch = w.batch(keychain_mask)?;
+		batch.delete_scan_checkpoint()?;
+		batch.save_scan_checkpoint(&ScanCheckpoint::new(
+			start_height,
+			tip_height,
+			tip_hash.clone(),
+			delete_unconfirmed,
+		))?;
+		batch.commit()?;
+	}
+
+	scan_range(
+		wallet_inst,
+		keychain_mask,
+		view_key,
+		start_height,
+		tip_height,
+		tip_hash,
+		delete_unconfirmed,
+		status_send_channel,
+		do_full_outputs_refresh,
+	)
+}
+
+/// Continue the interrupted scan from its last checkpoint. The result is the same as
+/// if the scan wasn't interrupted, the wallet is updated up to the height where the
+/// interrupted scan was supposed to finish.
+pub fn resume_scan<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (checkpoint, end_block) = {
+		wallet_lock!(wallet_inst, w);
+		w.w2n_client().reset_cache();
+		w.w2n_client().start_cycle();
+		let checkpoint = w.get_scan_checkpoint()?.ok_or(ErrorKind::GenericError(
+			"There is no interrupted scan to resume".to_string(),
+		))?;
+		let end_block = w.w2n_client().get_header_info(checkpoint.end_height)?;
+		(checkpoint, end_block)
+	};
+
+	// Outputs found before the checkpoint are valid only for the same chain
+	if end_block.hash != checkpoint.end_block_hash {
+		return Err(ErrorKind::GenericError(format!(
+			"Chain was changed at height {} since the scan was interrupted, please start a new scan",
+			checkpoint.end_height
+		)))?;
+	}
+
+	debug!(
+		"Resuming the scan of heights {} - {} from PMMR index {:?}",
+		checkpoint.start_height, checkpoint.end_height, checkpoint.next_pmmr_index
+	);
+
+	scan_range(
+		wallet_inst,
+		keychain_mask,
+		None,
+		checkpoint.start_height,
+		checkpoint.end_height,
+		checkpoint.end_block_hash,
+		checkpoint.delete_unconfirmed,
+		status_send_channel,
+		true,
+	)
+}
+
+// Scan the heights range and save the scanned blocks
+fn scan_range<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	view_key: Option<ViewKey>,
+	start_height: u64,
+	tip_height: u64,
+	tip_hash: String,
+	delete_unconfirmed: bool,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	do_full_outputs_refresh: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
 	// First we need to get the hashes for heights... Reason, if block chain will be changed during scan, we will detect that naturally with next wallet_update.
-	let mut blocks: Vec<ScannedBlockInfo> =
-		vec![ScannedBlockInfo::new(tip_height, tip_hash.clone())];
+	let mut blocks: Vec<ScannedBlockInfo> = vec![ScannedBlockInfo::new(tip_height, tip_hash)];
 	{
 		wallet_lock!(wallet_inst, w);
 
@@ -1038,19 +1816,32 @@ where
 		// adding last_scanned_block.height not needed
 	}
 
-	scan::scan(
-		wallet_inst.clone(),
-		keychain_mask,
-		delete_unconfirmed,
-		start_height,
-		tip_height,
-		status_send_channel,
-		true,
-		do_full_outputs_refresh,
-	)?;
+	match &view_key {
+		Some(view_key) => scan::view_wallet_scan(
+			wallet_inst.clone(),
+			view_key,
+			start_height,
+			tip_height,
+			status_send_channel,
+			true,
+		)?,
+		None => scan::scan(
+			wallet_inst.clone(),
+			keychain_mask,
+			delete_unconfirmed,
+			start_height,
+			tip_height,
+			status_send_channel,
+			true,
+			do_full_outputs_refresh,
+		)?,
+	}
 
 	wallet_lock!(wallet_inst, w);
-	let mut batch = w.batch(keychain_mask)?;
+	let mut batch = match view_key {
+		Some(_) => w.batch_no_mask()?,
+		None => w.batch(keychain_mask)?,
+	};
 	batch.save_last_scanned_blocks(start_height, &blocks)?;
 	batch.commit()?;
 
@@ -1175,14 +1966,17 @@ where
 }
 
 // Checking if node head is fine and we can perform the scanning
-// Result: (tip_height: u64, tip_hash:String, first_block_to_scan_from: ScannedBlockInfo, is_reorg: bool)
-// is_reorg true if new need go back by the chain to perform scanning
+// Result: (tip_height: u64, tip_hash:String, first_block_to_scan_from: ScannedBlockInfo, reorg_depth: u64)
+// reorg_depth is non zero if new need go back by the chain to perform scanning. It is the distance
+// from the last scanned head to the highest scanned block that is still at the chain (fork point).
+// The scanned blocks within the max reorg depth are all kept, so it is the fork point of the chain
+// up to the wallet update interval.
 // Note: In case of error return tip 0!!!
 fn get_last_detect_last_scanned_block<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
 	status_send_channel: &Option<Sender<StatusMessage>>,
-) -> Result<(u64, String, ScannedBlockInfo, bool), Error>
+) -> Result<(u64, String, ScannedBlockInfo, u64), Error>
 where
 	L: WalletLCProvider<'a, C, K>,
 	C: NodeClient + 'a,
@@ -1194,7 +1988,10 @@ where
 	// In this case better to inform user and do nothing. Sync is useless in any case.
 
 	// Checking if keychain mask correct. Issue that sometimes update_wallet_state doesn't need it and it is a security problem
-	let _ = w.batch(keychain_mask)?;
+	// Watch-only wallet has no keychain to check
+	if w.view_key().is_none() {
+		let _ = w.batch(keychain_mask)?;
+	}
 
 	let (tip_height, tip_hash, _) = match w.w2n_client().get_chain_tip() {
 		Ok(t) => t,
@@ -1204,7 +2001,7 @@ where
 					"Unable to contact mwc-node".to_owned(),
 				));
 			}
-			return Ok((0, String::new(), ScannedBlockInfo::empty(), false));
+			return Ok((0, String::new(), ScannedBlockInfo::empty(), 0));
 		}
 	};
 
@@ -1218,24 +2015,72 @@ where
 				String::from("Wallet Update is skipped, please wait for sync on node to complete or fork to resolve.")
 			));
 		}
-		return Ok((0, String::new(), ScannedBlockInfo::empty(), false));
+		return Ok((0, String::new(), ScannedBlockInfo::empty(), 0));
 	}
 
-	let mut last_scanned_block = ScannedBlockInfo::empty();
 	let head_height = blocks.first().map(|b| b.height).unwrap_or(0);
-	for bl in blocks {
-		// check if that block is not changed
-		if let Ok(hdr_info) = w.w2n_client().get_header_info(bl.height) {
-			if hdr_info.hash == bl.hash {
-				last_scanned_block = bl;
-				break;
+	let client = w.w2n_client().clone();
+	// check if that block is not changed
+	let at_chain = |bl: &ScannedBlockInfo| {
+		client
+			.get_header_info(bl.height)
+			.map(|hdr_info| hdr_info.hash == bl.hash)
+			.unwrap_or(false)
+	};
+
+	// The stored blocks below the fork point are at the chain and the ones above it are not,
+	// so the fork point is found by the binary search. Normally the head is at the chain.
+	let mut low = 0;
+	if blocks.first().map(|bl| !at_chain(bl)).unwrap_or(false) {
+		low = 1;
+		let mut high = blocks.len();
+		while low < high {
+			let mid = (low + high) / 2;
+			if at_chain(&blocks[mid]) {
+				high = mid;
+			} else {
+				low = mid + 1;
 			}
 		}
 	}
+	let last_scanned_block = blocks
+		.get(low)
+		.cloned()
+		.unwrap_or(ScannedBlockInfo::empty());
+
+	let reorg_depth = head_height - last_scanned_block.height;
+
+	Ok((tip_height, tip_hash, last_scanned_block, reorg_depth))
+}
+
+// Revert the wallet confirmations above the fork point and report the reorg
+fn rollback_reorg<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	fork_height: u64,
+	reorg_depth: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	w.w2n_client().reset_cache(); // let's reset cach to be safe
 
-	let has_reorg = last_scanned_block.height != head_height;
+	let (txs_num, outputs_num) =
+		scan::rollback_confirmations(&mut **w, keychain_mask, fork_height)?;
 
-	Ok((tip_height, tip_hash, last_scanned_block, has_reorg))
+	let msg = format!(
+		"Chain reorg of {} blocks is detected at height {}. Confirmations of {} transactions and {} outputs are reverted",
+		reorg_depth, fork_height, txs_num, outputs_num
+	);
+	info!("{}", msg);
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::Reorg(fork_height, msg));
+	}
+	Ok(())
 }
 
 /// Experimental, wrap the entire definition of how a wallet's state is updated
@@ -1249,21 +2094,49 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
+	// All node calls of the update go to the same node, so a lagging node can't
+	// look like a reorg
+	{
+		wallet_lock!(wallet_inst, w);
+		w.w2n_client().start_cycle();
+	}
+
 	// Checking from what point we should start scanning
-	let (tip_height, tip_hash, last_scanned_block, has_reorg) = get_last_detect_last_scanned_block(
-		wallet_inst.clone(),
-		keychain_mask,
-		status_send_channel,
-	)?;
+	let (tip_height, tip_hash, last_scanned_block, reorg_depth) =
+		get_last_detect_last_scanned_block(
+			wallet_inst.clone(),
+			keychain_mask,
+			status_send_channel,
+		)?;
 
 	if tip_height == 0 {
 		return Ok(false);
 	}
 
+	let has_reorg = reorg_depth > 0;
 	if has_reorg {
-		wallet_lock!(wallet_inst, w);
-		w.w2n_client().reset_cache(); // let's reset cach to be safe
+		// Too deep reorg is more likely the node problem (syncing, wrong fork), it is not reverted automatically
+		let max_reorg_depth = {
+			wallet_lock!(wallet_inst, w);
+			w.scan_config().max_reorg_depth
+		};
+		if reorg_depth > max_reorg_depth {
+			if let Some(ref s) = status_send_channel {
+				let _ = s.send(StatusMessage::Warning(format!(
+					"Wallet Update is skipped, chain reorg of {} blocks is deeper than {} blocks. Please check your node and run the scan if the chain is correct.",
+					reorg_depth, max_reorg_depth
+				)));
+			}
+			return Ok(false);
+		}
 
+		rollback_reorg(
+			wallet_inst.clone(),
+			keychain_mask,
+			last_scanned_block.height,
+			reorg_depth,
+			status_send_channel,
+		)?;
 		info!(
 			"Wallet update will do full outputs checking because since last update reorg happend"
 		);
@@ -1303,19 +2176,40 @@ where
 			blocks.push(ScannedBlockInfo::new(h, hdr.hash));
 			step *= 2;
 		}
-		// adding last_scanned_block.height not needed
+		// Last scanned block is checked at the chain already. It is kept, so the heads of the
+		// updates within the max reorg depth are all stored and locate the fork point.
+		if last_scanned_block.height > 0 {
+			blocks.push(last_scanned_block.clone());
+		}
 	}
 
-	scan::scan(
-		wallet_inst.clone(),
-		keychain_mask,
-		false,
-		last_scanned_block.height,
-		tip_height,
-		status_send_channel,
-		show_progress,
-		has_reorg,
-	)?;
+	let view_key = {
+		wallet_lock!(wallet_inst, w);
+		w.view_key()
+	};
+
+	match &view_key {
+		// Confirmations above the fork point are already reverted, the outputs found
+		// again are restored by the scan from the fork point
+		Some(view_key) => scan::view_wallet_scan(
+			wallet_inst.clone(),
+			view_key,
+			last_scanned_block.height,
+			tip_height,
+			status_send_channel,
+			show_progress,
+		)?,
+		None => scan::scan(
+			wallet_inst.clone(),
+			keychain_mask,
+			false,
+			last_scanned_block.height,
+			tip_height,
+			status_send_channel,
+			show_progress,
+			has_reorg,
+		)?,
+	}
 
 	// Checking if tip was changed. In this case we need to retry. Retry will be handles naturally optimal
 	let mut tip_was_changed = false;
@@ -1325,7 +2219,10 @@ where
 		if let Ok((after_tip_height, after_tip_hash, _)) = w.w2n_client().get_chain_tip() {
 			// Since we are still online, we can save the scan status
 			{
-				let mut batch = w.batch(keychain_mask)?;
+				let mut batch = match view_key {
+					Some(_) => w.batch_no_mask()?,
+					None => w.batch(keychain_mask)?,
+				};
 				batch.save_last_scanned_blocks(last_scanned_block.height, &blocks)?;
 				batch.commit()?;
 			}
@@ -1388,13 +2285,40 @@ where
 	let sender_pubkey = proof.sender_address.clone().public_key;
 	let msg = tx::payment_proof_message(proof.amount, &proof.excess, sender_pubkey)?;
 
-	let (client, keychain) = {
+	let (client, keychain, private_update, kernel_height) = {
 		wallet_lock!(wallet_inst, w);
-		(w.w2n_client().clone(), w.keychain(keychain_mask)?)
+		// Height of the kernel if the proof is for the confirmed transaction of this wallet
+		let kernel_height = w
+			.get_tx_log_by_kernel_excess(&proof.excess)?
+			.into_iter()
+			.find(|t| t.confirmed)
+			.map(|t| t.output_height);
+		(
+			w.w2n_client().clone(),
+			w.keychain(keychain_mask)?,
+			w.scan_config().private_update,
+			kernel_height,
+		)
 	};
 
-	// Check kernel exists
-	match client.get_kernel(&proof.excess, None, None) {
+	// Check kernel exists. In the private update mode the kernel is never sent to the node,
+	// only the block where this wallet has seen it is checked.
+	let kernel = if private_update {
+		let height = kernel_height.ok_or(ErrorKind::PaymentProof(
+			"Kernel of the transaction that isn't confirmed in this wallet can't be checked in the private update mode"
+				.to_string(),
+		))?;
+		let excess = crate::grin_util::to_hex(&proof.excess.0);
+		let mut kernels = HashSet::new();
+		kernels.insert(excess.clone());
+		scan::get_kernels_from_chain(&client, &kernels, height, height, &None, false)
+			.map(|found| found.get(&excess).map(|_| ()))
+	} else {
+		client
+			.get_kernel(&proof.excess, None, None)
+			.map(|kernel| kernel.map(|_| ()))
+	};
+	match kernel {
 		Err(e) => {
 			return Err(ErrorKind::PaymentProof(format!(
 				"Error retrieving kernel from chain: {}",
@@ -0,0 +1,208 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Address book. Contacts are stored in the wallet DB by name.
+
+use crate::grin_keychain::Keychain;
+use crate::grin_util::secp::key::SecretKey;
+
+use crate::proof::proofaddress::{self, ProvableAddress};
+use crate::types::{Contact, NodeClient, WalletBackend};
+use crate::{Error, ErrorKind};
+
+// Max length of the contact name. Addresses are longer, so a name is never mixed up with
+// an address.
+const MAX_NAME_LEN: usize = 32;
+
+/// Check if the string can be a contact name: up to 32 letters, digits, '_' or '-', starting
+/// with a letter. Urls, file names, Tor and MQS addresses are never valid names.
+pub fn is_contact_name(name: &str) -> bool {
+	name.len() <= MAX_NAME_LEN
+		&& name
+			.chars()
+			.next()
+			.map(|c| c.is_ascii_alphabetic())
+			.unwrap_or(false)
+		&& name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Add a new contact or update the existing one with the same name. If the proof address is
+/// not specified, it is taken from the Tor or MQS address.
+pub fn save_contact<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	contact: &Contact,
+) -> Result<Contact, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if !is_contact_name(&contact.name) {
+		return Err(ErrorKind::GenericError(format!(
+			"Invalid contact name '{}', expected up to {} letters, digits, '_' or '-', starting with a letter",
+			contact.name, MAX_NAME_LEN
+		))
+		.into());
+	}
+	let address = contact.address.trim().to_string();
+	if address.is_empty() || address.contains(char::is_whitespace) {
+		return Err(ErrorKind::GenericError(format!(
+			"Invalid address '{}' of contact {}",
+			contact.address, contact.name
+		))
+		.into());
+	}
+	match contact.method.as_str() {
+		"http" => {
+			if !address.starts_with("http://")
+				&& !address.starts_with("https://")
+				&& tor_address(&address).is_none()
+			{
+				return Err(ErrorKind::GenericError(format!(
+					"HTTP address should start with http:// or https://, or be a Tor address: {}",
+					address
+				))
+				.into());
+			}
+		}
+		"mwcmqs" => (),
+		m => {
+			return Err(ErrorKind::GenericError(format!(
+				"Unsupported contact method {}, expected http or mwcmqs",
+				m
+			))
+			.into())
+		}
+	}
+
+	let proof_address = match &contact.proof_address {
+		Some(a) => Some(a.clone()),
+		None => match contact.method.as_str() {
+			"http" => tor_address(&address),
+			_ => mqs_address(&address),
+		},
+	};
+
+	let contact = Contact {
+		name: contact.name.clone(),
+		address,
+		method: contact.method.clone(),
+		proof_address,
+		apisecret: contact.apisecret.clone(),
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_contact(&contact)?;
+	batch.commit()?;
+	Ok(contact)
+}
+
+/// All contacts, ordered by name
+pub fn contacts<'a, T: ?Sized, C, K>(w: &T) -> Result<Vec<Contact>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut contacts: Vec<Contact> = w.contact_iter().collect();
+	contacts.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(contacts)
+}
+
+/// Get a contact by name
+pub fn get_contact<'a, T: ?Sized, C, K>(w: &T, name: &str) -> Result<Contact, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	w.get_contact(name)?
+		.ok_or(ErrorKind::GenericError(format!("Contact {} is not found", name)).into())
+}
+
+/// Delete a contact
+pub fn delete_contact<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	name: &str,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	get_contact(&*w, name)?;
+	let mut batch = w.batch(keychain_mask)?;
+	batch.delete_contact(name)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Find the contact of the transaction address. The address is matched with the contact
+/// address, ignoring the url scheme and the '.onion' suffix, or with the proof address.
+pub fn contact_for_address<'c>(contacts: &'c [Contact], address: &str) -> Option<&'c Contact> {
+	let address = normalize_address(address);
+	if address.is_empty() {
+		return None;
+	}
+	contacts.iter().find(|c| {
+		normalize_address(&c.address) == address
+			|| c.proof_address
+				.as_ref()
+				.map(|p| p.public_key == address)
+				.unwrap_or(false)
+	})
+}
+
+// Address without the url scheme, MQS domain and the '.onion' suffix
+fn normalize_address(address: &str) -> String {
+	let mut address = address.trim().trim_end_matches('/');
+	for prefix in &["http://", "https://", "mwcmqs://"] {
+		if let Some(p) = address.get(..prefix.len()) {
+			if p.eq_ignore_ascii_case(prefix) {
+				address = &address[prefix.len()..];
+			}
+		}
+	}
+	if let Some(i) = address.find('@') {
+		address = &address[..i];
+	}
+	let suffix = ".onion";
+	if address.len() > suffix.len() {
+		let i = address.len() - suffix.len();
+		if let Some(s) = address.get(i..) {
+			if s.eq_ignore_ascii_case(suffix) {
+				address = &address[..i];
+			}
+		}
+	}
+	address.to_string()
+}
+
+// Proof address of the Tor address
+fn tor_address(address: &str) -> Option<ProvableAddress> {
+	let pk = proofaddress::address_to_pubkey(address.to_string());
+	ProvableAddress::from_str(&pk)
+		.ok()
+		.filter(|a| a.tor_public_key().is_ok())
+}
+
+// Proof address of the MQS address
+fn mqs_address(address: &str) -> Option<ProvableAddress> {
+	ProvableAddress::from_str(&normalize_address(address))
+		.ok()
+		.filter(|a| a.public_key().is_ok())
+}
@@ -0,0 +1,507 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wallet events. The updater thread compares the wallet state after every update
+//! with the previously reported one and reports the changes to the event sink. The reported
+//! state and the undelivered events are stored in the wallet DB, so nothing is lost when
+//! the wallet is restarted or the sink is not available.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::grin_core::libtx::secp_ser;
+use crate::grin_core::ser;
+use crate::grin_keychain::{Identifier, Keychain};
+use crate::grin_util::secp::key::SecretKey;
+use crate::grin_util::Mutex;
+
+use crate::api_impl::owner_swap;
+use crate::swap::fsm::state::StateId;
+use crate::swap::trades;
+use crate::types::{NodeClient, TxLogEntry, TxLogEntryType};
+use crate::Error;
+use crate::{WalletInst, WalletLCProvider};
+
+/// Transaction data that is reported with the transaction events
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TxEventInfo {
+	/// Local id of the transaction
+	pub id: u32,
+	/// Slate id
+	pub tx_slate_id: Option<Uuid>,
+	/// Transaction type
+	pub tx_type: TxLogEntryType,
+	/// Address of the other party
+	pub address: Option<String>,
+	/// Amount credited to the wallet
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount_credited: u64,
+	/// Amount debited from the wallet
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount_debited: u64,
+	/// Fee
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	pub fee: Option<u64>,
+	/// Height of the block with the transaction, 0 if not confirmed
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub output_height: u64,
+}
+
+impl From<&TxLogEntry> for TxEventInfo {
+	fn from(tx: &TxLogEntry) -> Self {
+		TxEventInfo {
+			id: tx.id,
+			tx_slate_id: tx.tx_slate_id.clone(),
+			tx_type: tx.tx_type.clone(),
+			address: tx.address.clone(),
+			amount_credited: tx.amount_credited,
+			amount_debited: tx.amount_debited,
+			fee: tx.fee,
+			output_height: tx.output_height,
+		}
+	}
+}
+
+/// Types of the wallet events
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum WalletEventType {
+	/// New incoming transaction was received
+	TxReceived {
+		/// Transaction
+		tx: TxEventInfo,
+	},
+	/// Transaction reached the configured number of confirmations
+	TxConfirmed {
+		/// Transaction
+		tx: TxEventInfo,
+		/// Number of confirmations
+		confirmations: u64,
+	},
+	/// Transaction was cancelled
+	TxCancelled {
+		/// Transaction
+		tx: TxEventInfo,
+	},
+	/// Transaction was cancelled because its TTL expired
+	TxTtlExpired {
+		/// Transaction
+		tx: TxEventInfo,
+	},
+	/// Swap trade moved to a new state
+	SwapStateChanged {
+		/// Swap trade id
+		swap_id: String,
+		/// Previous state, None for a new trade
+		previous: Option<StateId>,
+		/// Current state
+		state: StateId,
+	},
+	/// Wallet update finished
+	ScanFinished {
+		/// Height the wallet is updated to
+		height: u64,
+	},
+}
+
+/// Wallet event that is delivered to the event sinks
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WalletEvent {
+	/// Unique id of the event, receiver can use it to drop duplicates
+	pub id: Uuid,
+	/// Time when the event was detected
+	pub timestamp: DateTime<Utc>,
+	/// Event data
+	#[serde(flatten)]
+	pub event: WalletEventType,
+}
+
+impl WalletEvent {
+	/// Create a new event with a random id
+	pub fn new(event: WalletEventType) -> Self {
+		WalletEvent {
+			id: Uuid::new_v4(),
+			timestamp: Utc::now(),
+			event,
+		}
+	}
+
+	/// Event name, the same as the 'type' field of the serialized event
+	pub fn name(&self) -> &'static str {
+		match self.event {
+			WalletEventType::TxReceived { .. } => "TxReceived",
+			WalletEventType::TxConfirmed { .. } => "TxConfirmed",
+			WalletEventType::TxCancelled { .. } => "TxCancelled",
+			WalletEventType::TxTtlExpired { .. } => "TxTtlExpired",
+			WalletEventType::SwapStateChanged { .. } => "SwapStateChanged",
+			WalletEventType::ScanFinished { .. } => "ScanFinished",
+		}
+	}
+}
+
+/// Receiver of the wallet events. Called from the updater thread in the detection order.
+pub trait EventSink: Send + Sync {
+	/// Deliver the event. If the delivery fails, the event stays in the wallet DB and
+	/// is delivered again after the next update.
+	fn notify(&self, event: &WalletEvent) -> Result<(), Error>;
+}
+
+/// Event sink with the settings for the event detection
+#[derive(Clone)]
+pub struct EventNotifier {
+	/// Where to deliver the events
+	pub sink: Arc<dyn EventSink>,
+	/// Number of confirmations for TxConfirmed event
+	pub confirmations: u64,
+}
+
+impl EventNotifier {
+	/// Create a new notifier. Zero confirmations are treated as one.
+	pub fn new(sink: Arc<dyn EventSink>, confirmations: u64) -> Self {
+		EventNotifier {
+			sink,
+			confirmations: std::cmp::max(confirmations, 1),
+		}
+	}
+}
+
+/// Reported state of a transaction
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TxEventState {
+	/// Account of the transaction, transaction ids are unique per account only
+	pub parent_key_id: Identifier,
+	/// Local id of the transaction
+	pub id: u32,
+	/// Whether the cancellation was reported
+	pub cancelled: bool,
+	/// Whether TxConfirmed was reported
+	pub confirmed_reported: bool,
+}
+
+/// Wallet state that was reported to the event sink, stored in the wallet DB. It is
+/// compared with the wallet after every update, including the first one after the start.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct WalletEventState {
+	/// Reported transactions, None until the first update. The first update is the
+	/// baseline and reports nothing.
+	pub txs: Option<Vec<TxEventState>>,
+	/// Reported swap trade states, None until the swap trades are read the first time
+	pub swaps: Option<Vec<(String, StateId)>>,
+	/// Height of the last ScanFinished event
+	pub height: u64,
+	/// Detected events that are not delivered yet, in the detection order
+	pub pending: Vec<WalletEvent>,
+}
+
+impl ser::Writeable for WalletEventState {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("WalletEventState to json conversion failed, {}", e))
+		})?;
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for WalletEventState {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<WalletEventState, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to WalletEventState conversion failed, {}", e))
+		})
+	}
+}
+
+// What we know about a transaction from the previous update
+struct TxState {
+	cancelled: bool,
+	confirmed_reported: bool,
+}
+
+/// Compares the wallet with the reported state, so the changes can be detected
+pub struct EventDetector {
+	confirmations: u64,
+	// None until the first update, the first update is the baseline and reports nothing
+	txs: Option<HashMap<(Identifier, u32), TxState>>,
+	swaps: Option<HashMap<String, StateId>>,
+	height: u64,
+}
+
+impl EventDetector {
+	/// Create a new detector that reports TxConfirmed at 'confirmations'
+	pub fn new(confirmations: u64) -> Self {
+		EventDetector {
+			confirmations: std::cmp::max(confirmations, 1),
+			txs: None,
+			swaps: None,
+			height: 0,
+		}
+	}
+
+	/// Create a detector that continues from the reported state
+	pub fn from_state(confirmations: u64, state: &WalletEventState) -> Self {
+		let mut detector = EventDetector::new(confirmations);
+		detector.txs = state.txs.as_ref().map(|txs| {
+			txs.iter()
+				.map(|t| {
+					(
+						(t.parent_key_id.clone(), t.id),
+						TxState {
+							cancelled: t.cancelled,
+							confirmed_reported: t.confirmed_reported,
+						},
+					)
+				})
+				.collect()
+		});
+		detector.swaps = state.swaps.as_ref().map(|s| s.iter().cloned().collect());
+		detector.height = state.height;
+		detector
+	}
+
+	/// Reported state of the detector, without the pending events
+	pub fn to_state(&self) -> WalletEventState {
+		let txs = self.txs.as_ref().map(|txs| {
+			let mut txs: Vec<TxEventState> = txs
+				.iter()
+				.map(|((parent_key_id, id), t)| TxEventState {
+					parent_key_id: parent_key_id.clone(),
+					id: *id,
+					cancelled: t.cancelled,
+					confirmed_reported: t.confirmed_reported,
+				})
+				.collect();
+			txs.sort_by_key(|t| (t.parent_key_id.to_hex(), t.id));
+			txs
+		});
+		let swaps = self.swaps.as_ref().map(|swaps| {
+			let mut swaps: Vec<(String, StateId)> = swaps
+				.iter()
+				.map(|(id, state)| (id.clone(), state.clone()))
+				.collect();
+			swaps.sort_by(|a, b| a.0.cmp(&b.0));
+			swaps
+		});
+		WalletEventState {
+			txs,
+			swaps,
+			height: self.height,
+			pending: vec![],
+		}
+	}
+
+	/// Compare transactions with the previous state and return the events
+	pub fn process_txs(&mut self, txs: &[TxLogEntry], height: u64) -> Vec<WalletEventType> {
+		let mut events = Vec::new();
+		let baseline = self.txs.is_none();
+		let known = self.txs.get_or_insert_with(HashMap::new);
+
+		for tx in txs {
+			let confirmations = if !tx.confirmed {
+				0
+			} else if tx.output_height > 0 && height >= tx.output_height {
+				height - tx.output_height + 1
+			} else {
+				1
+			};
+			let confirmed_reached = confirmations >= self.confirmations;
+
+			// Transaction ids are unique per account only
+			let key = (tx.parent_key_id.clone(), tx.id);
+			match known.get_mut(&key) {
+				None => {
+					if !baseline && tx.tx_type == TxLogEntryType::TxReceived {
+						events.push(WalletEventType::TxReceived { tx: tx.into() });
+					}
+					let cancelled = tx.is_cancelled();
+					if !baseline && cancelled {
+						events.push(cancel_event(tx, height));
+					}
+					if !baseline && confirmed_reached {
+						events.push(WalletEventType::TxConfirmed {
+							tx: tx.into(),
+							confirmations,
+						});
+					}
+					known.insert(
+						key,
+						TxState {
+							cancelled,
+							confirmed_reported: confirmed_reached,
+						},
+					);
+				}
+				Some(state) => {
+					if !state.cancelled && tx.is_cancelled() {
+						state.cancelled = true;
+						events.push(cancel_event(tx, height));
+					}
+					if !state.confirmed_reported && confirmed_reached {
+						state.confirmed_reported = true;
+						events.push(WalletEventType::TxConfirmed {
+							tx: tx.into(),
+							confirmations,
+						});
+					}
+				}
+			}
+		}
+		events
+	}
+
+	/// Compare swap trade states with the previous ones and return the events
+	pub fn process_swaps(&mut self, swaps: Vec<(String, StateId)>) -> Vec<WalletEventType> {
+		let mut events = Vec::new();
+		let baseline = self.swaps.is_none();
+		let known = self.swaps.get_or_insert_with(HashMap::new);
+		for (swap_id, state) in swaps {
+			let previous = known.insert(swap_id.clone(), state.clone());
+			if !baseline && previous.as_ref() != Some(&state) {
+				events.push(WalletEventType::SwapStateChanged {
+					swap_id,
+					previous,
+					state,
+				});
+			}
+		}
+		events
+	}
+
+	/// Report ScanFinished if the wallet was updated to a new height
+	pub fn process_height(&mut self, height: u64) -> Option<WalletEventType> {
+		if height == self.height {
+			return None;
+		}
+		self.height = height;
+		Some(WalletEventType::ScanFinished { height })
+	}
+}
+
+fn cancel_event(tx: &TxLogEntry, height: u64) -> WalletEventType {
+	match tx.ttl_cutoff_height {
+		Some(h) if height >= h => WalletEventType::TxTtlExpired { tx: tx.into() },
+		_ => WalletEventType::TxCancelled { tx: tx.into() },
+	}
+}
+
+/// Read the wallet state after an update and compare it with the reported state from the
+/// wallet DB. New events are added to the pending events, the new state is saved.
+/// The first call for the wallet only records the state, except ScanFinished.
+pub fn detect_events<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	confirmations: u64,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (txs, height, view_only, state) = {
+		wallet_lock!(wallet_inst, w);
+		let txs: Vec<TxLogEntry> = w.tx_log_iter().collect();
+		let state = w.get_event_state()?.unwrap_or_default();
+		(
+			txs,
+			w.last_confirmed_height()?,
+			w.view_key().is_some(),
+			state,
+		)
+	};
+
+	let mut detector = EventDetector::from_state(confirmations, &state);
+	let mut events = detector.process_txs(&txs, height);
+
+	// Swap trades are encrypted with the wallet keys, the watch only wallet can't read them
+	if !view_only && trades::is_swap_trade_backend_initialized() {
+		match owner_swap::swap_list(wallet_inst.clone(), keychain_mask, false) {
+			Ok(swaps) => events.extend(
+				detector.process_swaps(swaps.into_iter().map(|s| (s.swap_id, s.state)).collect()),
+			),
+			Err(e) => warn!("Unable to read swap trades for the events, {}", e),
+		}
+	}
+
+	events.extend(detector.process_height(height));
+
+	let mut new_state = detector.to_state();
+	new_state.pending = state.pending;
+	new_state
+		.pending
+		.extend(events.into_iter().map(WalletEvent::new));
+	if new_state == state {
+		return Ok(());
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let mut batch = if view_only {
+		w.batch_no_mask()?
+	} else {
+		w.batch(keychain_mask)?
+	};
+	batch.save_event_state(&new_state)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Deliver the pending events to the sink in the detection order. Delivery stops at the
+/// first failure, the rest of the events stay in the wallet DB for the next call.
+/// Returns the number of the delivered events.
+pub fn deliver_events<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	sink: &dyn EventSink,
+) -> Result<usize, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let pending = {
+		wallet_lock!(wallet_inst, w);
+		match w.get_event_state()? {
+			Some(state) => state.pending,
+			None => return Ok(0),
+		}
+	};
+
+	// The sink might be slow, the wallet is not locked during the delivery
+	let mut delivered = Vec::new();
+	for event in &pending {
+		if let Err(e) = sink.notify(event) {
+			warn!(
+				"Unable to deliver wallet event {}, it will be retried after the next update. {}",
+				event.id, e
+			);
+			break;
+		}
+		delivered.push(event.id);
+	}
+	if delivered.is_empty() {
+		return Ok(0);
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let mut state = w.get_event_state()?.unwrap_or_default();
+	state.pending.retain(|e| !delivered.contains(&e.id));
+	let mut batch = match w.view_key() {
+		Some(_) => w.batch_no_mask()?,
+		None => w.batch(keychain_mask)?,
+	};
+	batch.save_event_state(&state)?;
+	batch.commit()?;
+	Ok(delivered.len())
+}
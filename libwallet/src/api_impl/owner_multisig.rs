@@ -0,0 +1,1019 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generic implementation of owner API multisig account functions
+
+use uuid::Uuid;
+
+use crate::grin_core::core::Transaction;
+use crate::grin_core::libtx::proof::ProofBuilder;
+use crate::grin_core::libtx::tx_fee;
+use crate::grin_keychain::{BlindSum, BlindingFactor, Identifier, Keychain, SwitchCommitmentType};
+use crate::grin_util::secp::key::SecretKey;
+use crate::grin_util::secp::pedersen::Commitment;
+use rand::thread_rng;
+
+use crate::internal::{multisig, scan, selection, tx};
+use crate::slate::Slate;
+use crate::types::{
+	Context, MultisigAccount, MultisigLocal, MultisigSpend, MultisigStatus, MultisigTxInfo,
+	NodeClient, TxLogEntry, TxLogEntryType, WalletBackend,
+};
+use crate::{Error, ErrorKind, InitTxArgs};
+
+/// Create a new multisig account. This wallet is the first co-signer, it creates the funding
+/// transaction for the shared output of `args.amount`. `contributions` are the amounts that
+/// every co-signer pays, in the order of joining. If there are none, this wallet pays for
+/// all of the shared output. Returns the account to pass to the next co-signer.
+pub fn multisig_create<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &InitTxArgs,
+	num_participants: usize,
+	threshold: usize,
+	contributions: Option<Vec<u64>>,
+) -> Result<MultisigAccount, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if num_participants < 2 || num_participants > multisig::MAX_PARTICIPANTS {
+		return Err(ErrorKind::MultisigError(format!(
+			"Multisig account needs from 2 to {} co-signers",
+			multisig::MAX_PARTICIPANTS
+		))
+		.into());
+	}
+	if threshold < 2 || threshold > num_participants {
+		return Err(ErrorKind::MultisigError(format!(
+			"Threshold {} is out of range, expected from 2 to {}",
+			threshold, num_participants
+		))
+		.into());
+	}
+	if args.amount == 0 {
+		return Err(ErrorKind::MultisigError("Shared output amount is zero".to_string()).into());
+	}
+	let contributions = contributions.unwrap_or_default();
+	if !contributions.is_empty() {
+		let total = contributions
+			.iter()
+			.try_fold(0u64, |sum, c| sum.checked_add(*c));
+		if contributions.len() != num_participants || total != Some(args.amount) {
+			return Err(ErrorKind::MultisigError(format!(
+				"Contributions of {} co-signers have to sum up to the shared output amount {}",
+				num_participants, args.amount
+			))
+			.into());
+		}
+	}
+
+	let parent_key_id = source_account(&*w, Some(args))?;
+
+	// Funding transaction is a regular send of our part, except that every co-signer signs it
+	let slate = tx::new_tx_slate(
+		&mut *w,
+		contributions.first().cloned().unwrap_or(args.amount),
+		num_participants,
+		false,
+		args.ttl_blocks,
+		false,
+	)?;
+	let mut account = MultisigAccount {
+		id: Uuid::new_v4(),
+		num_participants,
+		threshold,
+		amount: args.amount,
+		common_nonce: SecretKey::new(&mut thread_rng()),
+		contributions,
+		participants: vec![],
+		funding: slate,
+		commit: None,
+		spend: None,
+		status: MultisigStatus::Joining,
+		local: None,
+	};
+
+	let keychain = w.keychain(keychain_mask)?;
+	let mut context = selection::build_send_tx(
+		&mut *w,
+		&keychain,
+		keychain_mask,
+		&mut account.funding,
+		args.minimum_confirmations,
+		args.max_outputs as usize,
+		args.num_change_outputs as usize,
+		args.coin_selection_strategy(),
+		parent_key_id.clone(),
+		0,
+		false,
+		true,
+		&args.outputs,
+		1,
+		args.exclude_change_outputs.unwrap_or(false),
+		args.minimum_confirmations_change_outputs,
+		None,
+	)?;
+	account.funding.amount = account.amount;
+
+	let secret = join(&mut *w, keychain_mask, &mut account, &parent_key_id)?;
+
+	// Our part of the shared output blinding factor is signed together with the inputs and change
+	context.sec_key = keychain
+		.blind_sum(
+			&BlindSum::new()
+				.add_blinding_factor(BlindingFactor::from_secret_key(context.sec_key.clone()))
+				.add_blinding_factor(BlindingFactor::from_secret_key(secret)),
+		)?
+		.secret_key()?;
+	account.funding.fill_round_1(
+		&keychain,
+		&mut context.sec_key,
+		&context.sec_nonce,
+		0,
+		None,
+		false,
+	)?;
+	context.initial_sec_key = context.sec_key.clone();
+	context.input_commits = account.funding.tx.inputs_committed();
+	for output in account.funding.tx.outputs() {
+		context.output_commits.push(output.commitment());
+	}
+
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_private_context(account.funding.id.as_bytes(), 0, &context)?;
+		batch.commit()?;
+	}
+	lock_funding(&mut *w, keychain_mask, &account, &context)?;
+
+	Ok(account.export())
+}
+
+/// Process the account that came from another co-signer. Joins the account if this wallet
+/// isn't a co-signer yet, then does every step that is due: secret shares, shared output
+/// bulletproof rounds and transaction signatures. Returns the account to pass on, and the
+/// transaction if this wallet finalized it. The transaction still needs to be posted.
+/// `args` select the inputs that pay our contribution to the shared output when joining,
+/// the defaults are used if there are none.
+pub fn multisig_process<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account: &MultisigAccount,
+	args: Option<&InitTxArgs>,
+) -> Result<(MultisigAccount, Option<Transaction>), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let keychain = w.keychain(keychain_mask)?;
+	multisig::verify_participants(keychain.secp(), account)?;
+
+	let mut account = match w.get_multisig_account(&account.id)? {
+		Some(stored) => multisig::merge(&stored, account)?,
+		None => {
+			if account.status != MultisigStatus::Joining {
+				return Err(ErrorKind::MultisigError(format!(
+					"All co-signers already joined account {}",
+					account.id
+				))
+				.into());
+			}
+			let mut account = account.export();
+			let parent_key_id = source_account(&*w, args)?;
+			let secret = join(&mut *w, keychain_mask, &mut account, &parent_key_id)?;
+			let index = account.participants.len() - 1;
+			let contribution = account.contributions.get(index).cloned().unwrap_or(0);
+			let mut context = if contribution > 0 {
+				let default_args = InitTxArgs::default();
+				add_contribution(
+					&mut *w,
+					keychain_mask,
+					&mut account,
+					secret,
+					args.unwrap_or(&default_args),
+					&parent_key_id,
+				)?
+			} else {
+				Context::with_excess(
+					keychain.secp(),
+					secret,
+					&parent_key_id,
+					false,
+					index,
+					account.funding.amount,
+					account.funding.fee,
+					None,
+				)
+			};
+			account.funding.fill_round_1(
+				&keychain,
+				&mut context.sec_key,
+				&context.sec_nonce,
+				index,
+				None,
+				false,
+			)?;
+			context.initial_sec_key = context.sec_key.clone();
+			{
+				let mut batch = w.batch(keychain_mask)?;
+				batch.save_private_context(account.funding.id.as_bytes(), index, &context)?;
+				batch.commit()?;
+			}
+			if contribution > 0 {
+				lock_funding(&mut *w, keychain_mask, &account, &context)?;
+			}
+			account
+		}
+	};
+
+	let tx = match account.status {
+		MultisigStatus::Joining | MultisigStatus::Funding => {
+			process_funding(&mut *w, keychain_mask, &mut account)?
+		}
+		MultisigStatus::Spending => process_spending(&mut *w, keychain_mask, &mut account)?,
+		MultisigStatus::Funded | MultisigStatus::Spent => None,
+	};
+
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_multisig_account(&account)?;
+		batch.commit()?;
+	}
+	Ok((account.export(), tx))
+}
+
+/// Start spending the shared output. `signers` are the co-signers that sign the spending
+/// transaction, this wallet co-signer has to be one of them. If there is no recipient
+/// `address`, the shared output minus the transaction fee goes to this wallet. Otherwise
+/// the recipient gets `amount`, all of the shared output minus the fee if it is None, and
+/// the change goes to this wallet. Returns the account to pass to the other signers.
+pub fn multisig_spend<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account_id: &Uuid,
+	signers: &[usize],
+	amount: Option<u64>,
+	address: Option<String>,
+) -> Result<MultisigAccount, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut account = get_account(&*w, account_id)?;
+	let local = local_co_signer(&account)?;
+	let commit = match account.commit.clone() {
+		Some(c) if account.status >= MultisigStatus::Funding => c,
+		_ => {
+			return Err(ErrorKind::MultisigError(format!(
+				"Account {} isn't funded yet",
+				account.id
+			))
+			.into())
+		}
+	};
+	if account.spend.is_some() {
+		return Err(ErrorKind::MultisigError(format!(
+			"Account {} is already spent or being spent",
+			account.id
+		))
+		.into());
+	}
+	let client = w.w2n_client().clone();
+	let scan_config = w.scan_config();
+	let on_chain = if scan_config.private_update {
+		// The shared output can't be older than the funding slate
+		scan::get_outputs_from_chain(
+			&client,
+			&[(commit.clone(), account.funding.height)],
+			scan_config.threads,
+			&None,
+			false,
+		)?
+	} else {
+		client.get_outputs_from_node(&vec![commit.clone()])?
+	};
+	if on_chain.is_empty() {
+		return Err(ErrorKind::MultisigError(format!(
+			"Shared output of account {} isn't on the chain",
+			account.id
+		))
+		.into());
+	}
+
+	// We initiate the spending, so we are the first signer
+	let mut others: Vec<usize> = signers
+		.iter()
+		.cloned()
+		.filter(|s| *s != local.index)
+		.collect();
+	others.sort();
+	others.dedup();
+	let mut signers = vec![local.index];
+	signers.extend(others);
+	if signers.len() != account.threshold || signers.iter().any(|s| *s >= account.num_participants)
+	{
+		return Err(ErrorKind::MultisigError(format!(
+			"Spending needs {} different co-signers from 0 to {}, including this wallet co-signer {}",
+			account.threshold,
+			account.num_participants - 1,
+			local.index
+		))
+		.into());
+	}
+
+	let (amount, change, fee) = spend_amounts(&account, amount, address.is_some())?;
+
+	let keychain = w.keychain(keychain_mask)?;
+	let secret = co_signer_secret(&keychain, &local)?;
+	let (builder, _, _) = multisig::shared_output(&account, local.index, &secret)?;
+
+	let num_participants = match address {
+		Some(_) => signers.len() + 1,
+		None => signers.len(),
+	};
+	let mut slate = tx::new_tx_slate(&mut *w, amount, num_participants, false, None, false)?;
+	slate.fee = fee;
+	slate.tx = slate.tx.with_input(builder.as_input(keychain.secp())?);
+	slate.update_kernel();
+	// Offset is generated upfront, it is recorded with the received output
+	slate.tx.offset = BlindingFactor::from_secret_key(SecretKey::new(&mut thread_rng()));
+
+	// Our output is the whole amount, or the change if the funds go to the recipient
+	let own_amount = match address {
+		Some(_) => change,
+		None => amount,
+	};
+	let mut blind_sum = BlindSum::new();
+	let mut tx_entry = None;
+	let mut context = if own_amount > 0 {
+		let height = slate.height;
+		slate.amount = own_amount;
+		let (_, context, t) = selection::build_recipient_output(
+			&mut *w,
+			keychain_mask,
+			&mut slate,
+			height,
+			None,
+			local.parent_key_id.clone(),
+			0,
+			None,
+			None,
+			false,
+			true,
+			1,
+			None,
+		)?;
+		slate.amount = amount;
+		blind_sum =
+			blind_sum.add_blinding_factor(BlindingFactor::from_secret_key(context.sec_key.clone()));
+		tx_entry = Some(t);
+		context
+	} else {
+		// Nothing to receive, only the nonce of the new context is used
+		Context::new(
+			keychain.secp(),
+			&local.parent_key_id,
+			false,
+			true,
+			0,
+			amount,
+			fee,
+			None,
+		)
+	};
+
+	let key = multisig::signer_key(&keychain, &account, local.index, &secret, &signers)?;
+	context.sec_key = keychain
+		.blind_sum(
+			&blind_sum
+				.sub_blinding_factor(BlindingFactor::from_secret_key(key))
+				.sub_blinding_factor(slate.tx.offset.clone()),
+		)?
+		.secret_key()?;
+	slate.fill_round_1(
+		&keychain,
+		&mut context.sec_key,
+		&context.sec_nonce,
+		0,
+		None,
+		false,
+	)?;
+
+	let slate_id = slate.id;
+	account.spend = Some(MultisigSpend {
+		signers,
+		address,
+		slate,
+	});
+	account.status = MultisigStatus::Spending;
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_private_context(slate_id.as_bytes(), 0, &context)?;
+		if let Some(mut t) = tx_entry {
+			t.multisig = Some(MultisigTxInfo {
+				account_id: account.id,
+				funding: false,
+				amount: account.amount,
+				commit: Some(commit),
+			});
+			batch.save_tx_log_entry(t, &local.parent_key_id)?;
+		}
+		batch.save_multisig_account(&account)?;
+		batch.commit()?;
+	}
+
+	Ok(account.export())
+}
+
+/// Slate for the recipient of the spending. It is ready once every signer added its nonce,
+/// the recipient receives it as a regular send slate. The response goes to
+/// `multisig_spend_response`.
+pub fn multisig_spend_slate<'a, T: ?Sized, C, K>(w: &T, account_id: &Uuid) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let account = get_account(&*w, account_id)?;
+	let spend = recipient_spend(&account)?;
+	let ready = account.status == MultisigStatus::Spending
+		&& spend.slate.participant_data.len() == spend.signers.len()
+		&& (0..spend.signers.len()).all(|pos| {
+			spend
+				.slate
+				.participant_with_id(spend.participant_id(pos))
+				.is_some()
+		});
+	if !ready {
+		return Err(ErrorKind::MultisigError(format!(
+			"Spending of account {} isn't ready for the recipient",
+			account.id
+		))
+		.into());
+	}
+	Ok(spend.slate.clone())
+}
+
+/// Apply the response of the spending recipient, then sign the spending transaction if this
+/// wallet co-signer is one of the signers. Returns the account to pass to the other signers,
+/// and the transaction if this wallet finalized it.
+pub fn multisig_spend_response<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &Slate,
+) -> Result<(MultisigAccount, Option<Transaction>), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut account = w
+		.multisig_account_iter()
+		.find(|a| a.spend.as_ref().map(|s| s.slate.id) == Some(slate.id))
+		.ok_or(ErrorKind::MultisigError(format!(
+			"Spending transaction {} is not found",
+			slate.id
+		)))?;
+	let mut spend = recipient_spend(&account)?.clone();
+
+	if spend
+		.slate
+		.participant_with_id(MultisigSpend::RECIPIENT_ID)
+		.is_none()
+	{
+		// Only the recipient output and signature are taken from the response
+		let ours: Vec<Commitment> = spend
+			.slate
+			.tx
+			.outputs()
+			.iter()
+			.map(|o| o.commitment())
+			.collect();
+		let outputs: Vec<_> = slate
+			.tx
+			.outputs()
+			.iter()
+			.filter(|o| !ours.contains(&o.commitment()))
+			.cloned()
+			.collect();
+		let recipient = match slate.participant_with_id(MultisigSpend::RECIPIENT_ID) {
+			Some(p)
+				if p.is_complete()
+					&& outputs.len() == 1
+					&& slate.amount == spend.slate.amount
+					&& slate.fee == spend.slate.fee =>
+			{
+				p
+			}
+			_ => {
+				return Err(ErrorKind::MultisigError(format!(
+					"Recipient response for account {} is not valid",
+					account.id
+				))
+				.into())
+			}
+		};
+		spend.slate.tx = spend.slate.tx.with_output(outputs[0].clone());
+		spend.slate.participant_data.push(recipient);
+		account.spend = Some(spend);
+	}
+
+	let tx = process_spending(&mut *w, keychain_mask, &mut account)?;
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_multisig_account(&account)?;
+		batch.commit()?;
+	}
+	Ok((account.export(), tx))
+}
+
+/// Multisig accounts of this wallet
+pub fn multisig_accounts<'a, T: ?Sized, C, K>(w: &T) -> Result<Vec<MultisigAccount>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	Ok(w.multisig_account_iter().collect())
+}
+
+/// Multisig account of this wallet
+pub fn get_account<'a, T: ?Sized, C, K>(w: &T, account_id: &Uuid) -> Result<MultisigAccount, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	w.get_multisig_account(account_id)?.ok_or(
+		ErrorKind::MultisigError(format!("Multisig account {} is not found", account_id)).into(),
+	)
+}
+
+/// Adds this wallet as the next co-signer of the account, returns the co-signer secret
+fn join<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account: &mut MultisigAccount,
+	parent_key_id: &Identifier,
+) -> Result<SecretKey, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if account.participants.len() >= account.num_participants {
+		return Err(ErrorKind::MultisigError(format!(
+			"All co-signers already joined account {}",
+			account.id
+		))
+		.into());
+	}
+	let local = MultisigLocal {
+		index: account.participants.len(),
+		key_id: w.next_child(keychain_mask, Some(parent_key_id.clone()))?,
+		parent_key_id: parent_key_id.clone(),
+	};
+	let keychain = w.keychain(keychain_mask)?;
+	let secret = co_signer_secret(&keychain, &local)?;
+	let participant = multisig::new_participant(keychain.secp(), account, &local, &secret)?;
+	account.participants.push(participant);
+	account.local = Some(local);
+	Ok(secret)
+}
+
+fn co_signer_secret<K: Keychain>(keychain: &K, local: &MultisigLocal) -> Result<SecretKey, Error> {
+	Ok(keychain.derive_key(0, &local.key_id, SwitchCommitmentType::None)?)
+}
+
+fn local_co_signer(account: &MultisigAccount) -> Result<MultisigLocal, Error> {
+	account.local.clone().ok_or(
+		ErrorKind::MultisigError(format!(
+			"Account {} doesn't have the wallet co-signer",
+			account.id
+		))
+		.into(),
+	)
+}
+
+fn recipient_spend(account: &MultisigAccount) -> Result<&MultisigSpend, Error> {
+	match &account.spend {
+		Some(spend) if spend.address.is_some() => Ok(spend),
+		_ => Err(ErrorKind::MultisigError(format!(
+			"Account {} isn't being spent to a recipient",
+			account.id
+		))
+		.into()),
+	}
+}
+
+/// Wallet account that pays for the funding or receives the spending
+fn source_account<'a, T: ?Sized, C, K>(
+	w: &T,
+	args: Option<&InitTxArgs>,
+) -> Result<Identifier, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	match args.and_then(|a| a.src_acct_name.clone()) {
+		Some(d) => match w.get_acct_path(d)? {
+			Some(p) => Ok(p.path),
+			None => Ok(w.parent_key_id()),
+		},
+		None => Ok(w.parent_key_id()),
+	}
+}
+
+/// Amount for the recipient, the change and the fee of the spending transaction
+fn spend_amounts(
+	account: &MultisigAccount,
+	amount: Option<u64>,
+	to_recipient: bool,
+) -> Result<(u64, u64, u64), Error> {
+	let fee = tx_fee(1, 1, 1, None);
+	let whole = account.amount.saturating_sub(fee);
+	match amount {
+		None | Some(_) if whole == 0 => Err(ErrorKind::MultisigError(format!(
+			"Shared output amount {} doesn't cover the fee {}",
+			account.amount, fee
+		))
+		.into()),
+		None => Ok((whole, 0, fee)),
+		Some(a) if a == whole => Ok((a, 0, fee)),
+		Some(_) if !to_recipient => Err(ErrorKind::MultisigError(
+			"Spending to this wallet takes all of the shared output".to_string(),
+		)
+		.into()),
+		Some(a) => {
+			// The change output adds to the fee
+			let fee = tx_fee(1, 2, 1, None);
+			if a == 0 || a.saturating_add(fee) >= account.amount {
+				return Err(ErrorKind::MultisigError(format!(
+					"Shared output amount {} doesn't cover {} and the fee {}",
+					account.amount, a, fee
+				))
+				.into());
+			}
+			Ok((a, account.amount - a - fee, fee))
+		}
+	}
+}
+
+/// Add inputs and change of this wallet co-signer to the funding transaction. The co-signer
+/// pays the fee for them, as for its own transaction. Returns the context to sign with.
+fn add_contribution<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account: &mut MultisigAccount,
+	secret: SecretKey,
+	args: &InitTxArgs,
+	parent_key_id: &Identifier,
+) -> Result<Context, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let index = account.participants.len() - 1;
+	let contribution = account.contributions[index];
+	let keychain = w.keychain(keychain_mask)?;
+	let slate = &mut account.funding;
+
+	let (elems, inputs, change_amounts_derivations, fee) = selection::select_send_tx(
+		&mut *w,
+		keychain_mask,
+		contribution,
+		slate.height,
+		args.minimum_confirmations,
+		args.max_outputs as usize,
+		args.num_change_outputs as usize,
+		args.coin_selection_strategy(),
+		parent_key_id,
+		&args.outputs,
+		1,
+		args.exclude_change_outputs.unwrap_or(false),
+		args.minimum_confirmations_change_outputs,
+		true, // Legacy value is true
+	)?;
+	let inputs_before = slate.tx.inputs_committed();
+	let outputs_before: Vec<Commitment> =
+		slate.tx.outputs().iter().map(|o| o.commitment()).collect();
+	slate.fee += fee;
+	let blinding =
+		slate.add_transaction_elements(&keychain, &ProofBuilder::new(&keychain), elems)?;
+
+	let sec_key = keychain
+		.blind_sum(
+			&BlindSum::new()
+				.add_blinding_factor(blinding)
+				.add_blinding_factor(BlindingFactor::from_secret_key(secret)),
+		)?
+		.secret_key()?;
+	let mut context = Context::with_excess(
+		keychain.secp(),
+		sec_key,
+		parent_key_id,
+		false,
+		index,
+		contribution,
+		fee,
+		None,
+	);
+	for input in inputs {
+		context.add_input(&input.key_id, &input.mmr_index, input.value);
+	}
+	for (change_amount, id, mmr_index) in &change_amounts_derivations {
+		context.add_output(&id, &mmr_index, *change_amount);
+	}
+	context.input_commits = slate
+		.tx
+		.inputs_committed()
+		.into_iter()
+		.filter(|c| !inputs_before.contains(c))
+		.collect();
+	context.output_commits = slate
+		.tx
+		.outputs()
+		.iter()
+		.map(|o| o.commitment())
+		.filter(|c| !outputs_before.contains(c))
+		.collect();
+	Ok(context)
+}
+
+/// Lock our funding inputs, the funding transaction is recorded as a regular send
+fn lock_funding<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account: &MultisigAccount,
+	context: &Context,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	selection::lock_tx_context_with(
+		&mut *w,
+		keychain_mask,
+		&account.funding,
+		account.funding.height,
+		context,
+		None,
+		None,
+		|batch, t| {
+			t.multisig = Some(MultisigTxInfo {
+				account_id: account.id,
+				funding: true,
+				amount: account.amount,
+				commit: None,
+			});
+			batch.save_multisig_account(account)?;
+			Ok(())
+		},
+	)
+}
+
+fn process_funding<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account: &mut MultisigAccount,
+) -> Result<Option<Transaction>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let n = account.num_participants;
+	if account.participants.len() < n {
+		return Ok(None);
+	}
+	account.status = MultisigStatus::Funding;
+
+	let local = local_co_signer(account)?;
+	let me = local.index;
+	let keychain = w.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let secret = co_signer_secret(&keychain, &local)?;
+
+	let (mut builder, mut round_1, mut round_2) = multisig::shared_output(account, me, &secret)?;
+	account.commit = Some(builder.commit(secp)?);
+
+	if account.threshold < n && account.participants[me].commitments.is_empty() {
+		multisig::issue_shares(secp, account, me, &secret)?;
+	}
+	if !round_1[me] {
+		builder.round_1(secp, &secret)?;
+		account.participants[me].bulletproof = builder.export()?;
+		round_1[me] = true;
+	}
+
+	// Nothing is signed until we can spend our part of the shared output
+	if !multisig::shares_ready(secp, account, me, &secret)? {
+		return Ok(None);
+	}
+	if round_1.iter().all(|r| *r) && !round_2[me] {
+		builder.round_2(secp, &secret)?;
+		account.participants[me].bulletproof = builder.export()?;
+		round_2[me] = true;
+	}
+	let signed = account
+		.funding
+		.participant_with_id(me)
+		.map(|p| p.part_sig.is_some())
+		.unwrap_or(false);
+	if account.funding.participant_data.len() == n && !signed {
+		let context = w.get_private_context(keychain_mask, account.funding.id.as_bytes(), me)?;
+		account
+			.funding
+			.fill_round_2(secp, &context.sec_key, &context.sec_nonce, me)?;
+
+		// Every co-signer excess is in, so the kernel excess is final. Our part of the
+		// funding is confirmed by it.
+		if let Some(mut tx_entry) = funding_tx_entry(&*w, account)? {
+			tx_entry.kernel_excess = Some(account.funding.calc_excess(Some(&keychain))?);
+			if let Some(m) = tx_entry.multisig.as_mut() {
+				m.commit = account.commit.clone();
+			}
+			let mut batch = w.batch(keychain_mask)?;
+			batch.save_tx_log_entry(tx_entry, &local.parent_key_id)?;
+			batch.commit()?;
+		}
+	}
+
+	// The funding co-signer finalizes once the others are done
+	let all_signed = account.funding.participant_data.len() == n
+		&& account
+			.funding
+			.participant_data
+			.iter()
+			.all(|p| p.is_complete());
+	if me != 0 || !all_signed || !round_2.iter().all(|r| *r) {
+		return Ok(None);
+	}
+
+	let mut slate = account.funding.clone();
+	slate.tx = slate.tx.with_output(builder.as_output(secp, &secret)?);
+	slate.finalize(&keychain)?;
+	w.store_tx(&format!("{}", slate.id), &slate.tx)?;
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.delete_private_context(slate.id.as_bytes(), 0)?;
+		batch.commit()?;
+	}
+
+	let tx = slate.tx.clone();
+	account.funding = slate;
+	account.status = MultisigStatus::Funded;
+	Ok(Some(tx))
+}
+
+fn process_spending<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	account: &mut MultisigAccount,
+) -> Result<Option<Transaction>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let local = local_co_signer(account)?;
+	let mut spend = account
+		.spend
+		.clone()
+		.ok_or(ErrorKind::MultisigError(format!(
+			"Account {} doesn't have the spending transaction",
+			account.id
+		)))?;
+	let pos = match spend.signers.iter().position(|s| *s == local.index) {
+		Some(p) => p,
+		None => return Ok(None),
+	};
+	let id = spend.participant_id(pos);
+	let keychain = w.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+
+	if spend.slate.participant_with_id(id).is_none() {
+		// Check what we sign: the shared output goes to the initiator or to the recipient
+		// with the change to the initiator, minus the fee
+		let (_, change, fee) =
+			spend_amounts(account, Some(spend.slate.amount), spend.address.is_some())?;
+		let (outputs, num_participants) = match spend.address {
+			Some(_) => ((change > 0) as usize, spend.signers.len() + 1),
+			None => (1, spend.signers.len()),
+		};
+		let inputs = spend.slate.tx.inputs_committed();
+		if inputs.len() != 1
+			|| Some(inputs[0]) != account.commit
+			|| spend.slate.tx.outputs().len() != outputs
+			|| spend.slate.fee != fee
+			|| spend.slate.num_participants != num_participants
+		{
+			return Err(ErrorKind::MultisigError(format!(
+				"Spending transaction of account {} is not valid",
+				account.id
+			))
+			.into());
+		}
+
+		let secret = co_signer_secret(&keychain, &local)?;
+		let key = multisig::signer_key(&keychain, account, local.index, &secret, &spend.signers)?;
+		let mut context = Context::with_excess(
+			secp,
+			multisig::negate(&keychain, &key)?,
+			&local.parent_key_id,
+			false,
+			id,
+			spend.slate.amount,
+			spend.slate.fee,
+			None,
+		);
+		spend.slate.fill_round_1(
+			&keychain,
+			&mut context.sec_key,
+			&context.sec_nonce,
+			id,
+			None,
+			false,
+		)?;
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_private_context(spend.slate.id.as_bytes(), id, &context)?;
+		batch.commit()?;
+	}
+
+	let n = spend.slate.num_participants;
+	let signed = spend
+		.slate
+		.participant_with_id(id)
+		.map(|p| p.part_sig.is_some())
+		.unwrap_or(false);
+	if spend.slate.participant_data.len() == n && !signed {
+		let context = w.get_private_context(keychain_mask, spend.slate.id.as_bytes(), id)?;
+		spend
+			.slate
+			.fill_round_2(secp, &context.sec_key, &context.sec_nonce, id)?;
+	}
+
+	let all_signed = spend.slate.participant_data.len() == n
+		&& spend.slate.participant_data.iter().all(|p| p.is_complete());
+	if pos != 0 || !all_signed {
+		account.spend = Some(spend);
+		return Ok(None);
+	}
+
+	// We initiated the spending, the received output or the change is ours
+	spend.slate.finalize(&keychain)?;
+	let slate_id = spend.slate.id;
+	w.store_tx(&format!("{}", slate_id), &spend.slate.tx)?;
+	let tx_entry = w
+		.get_tx_log_by_slate_id(&slate_id)?
+		.into_iter()
+		.find(|t| t.tx_type == TxLogEntryType::TxReceived);
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		if let Some(mut tx_entry) = tx_entry {
+			tx_entry.kernel_excess = Some(spend.slate.tx.kernels()[0].excess);
+			tx_entry.stored_tx = Some(format!("{}.mwctx", slate_id));
+			batch.save_tx_log_entry(tx_entry, &local.parent_key_id)?;
+		}
+		batch.delete_private_context(slate_id.as_bytes(), 0)?;
+		batch.commit()?;
+	}
+
+	let tx = spend.slate.tx.clone();
+	account.spend = Some(spend);
+	account.status = MultisigStatus::Spent;
+	Ok(Some(tx))
+}
+
+/// Funding transaction of this wallet, if it pays a part of the shared output
+fn funding_tx_entry<'a, T: ?Sized, C, K>(
+	w: &T,
+	account: &MultisigAccount,
+) -> Result<Option<TxLogEntry>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	Ok(w.get_tx_log_by_slate_id(&account.funding.id)?
+		.into_iter()
+		.find(|t| t.tx_type == TxLogEntryType::TxSent))
+}
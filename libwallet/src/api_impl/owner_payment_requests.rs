@@ -0,0 +1,475 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Payment requests. A request is shared as a 'mwc:' URI and tracked until it is paid, expired
+//! or cancelled. The paying transaction, sent or invoiced, has the request id as the slate id.
+
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::grin_keychain::Keychain;
+use crate::grin_util::secp::key::SecretKey;
+
+use crate::proof::proofaddress::{self, ProofAddressType, ProvableAddress};
+use crate::slate::Slate;
+use crate::types::{
+	NodeClient, PaymentRequest, PaymentRequestStatus, TxLogEntry, TxLogEntryType, WalletBackend,
+};
+use crate::{Error, ErrorKind, PaymentRequestArgs};
+
+const URI_SCHEME: &str = "mwc:";
+// The memo is the transaction message, the receiving wallet keeps up to 256 symbols of it
+const MAX_MEMO_LEN: usize = 256;
+const NANO_PER_MWC: u64 = 1_000_000_000;
+
+/// Create and store a new payment request
+pub fn create_payment_request<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &PaymentRequestArgs,
+) -> Result<PaymentRequest, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if args.amount == 0 {
+		return Err(
+			ErrorKind::GenericError("Payment request amount can't be zero".to_string()).into(),
+		);
+	}
+	let address = args.address.trim().to_string();
+	if address.is_empty() || address.contains(char::is_whitespace) {
+		return Err(ErrorKind::GenericError(format!(
+			"Invalid payment request address '{}'",
+			args.address
+		))
+		.into());
+	}
+	if let Some(memo) = &args.memo {
+		if memo.len() > MAX_MEMO_LEN {
+			return Err(ErrorKind::GenericError(format!(
+				"Payment request memo is longer than {} symbols",
+				MAX_MEMO_LEN
+			))
+			.into());
+		}
+	}
+	let now = Utc::now();
+	if let Some(t) = args.expiry_time {
+		if t <= now {
+			return Err(ErrorKind::GenericError(format!(
+				"Payment request expiry time {} is in the past",
+				t
+			))
+			.into());
+		}
+	}
+
+	// Proof address of the same type as the payee address, the payer checks it against the address
+	let proof_address = if args.require_proof {
+		let keychain = w.keychain(keychain_mask)?;
+		let addr_type = if is_mqs_address(&address) {
+			ProofAddressType::MQS
+		} else {
+			ProofAddressType::Onion
+		};
+		Some(proofaddress::payment_proof_address(&keychain, addr_type)?)
+	} else {
+		None
+	};
+
+	let height = w.last_confirmed_height()?;
+	let request = PaymentRequest {
+		id: Uuid::new_v4(),
+		amount: args.amount,
+		address,
+		memo: args.memo.clone(),
+		expiry_height: args.expiry_blocks.map(|b| height + b),
+		expiry_time: args.expiry_time,
+		proof_address,
+		created: now,
+		status: PaymentRequestStatus::Open,
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_payment_request(&request)?;
+	batch.commit()?;
+	Ok(request)
+}
+
+/// All payment requests with the updated status, oldest first
+pub fn payment_requests<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<Vec<PaymentRequest>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	update_payment_requests(&mut *w, keychain_mask)?;
+	let mut requests: Vec<PaymentRequest> = w.payment_request_iter().collect();
+	requests.sort_by_key(|r| r.created);
+	Ok(requests)
+}
+
+/// Get a payment request by id
+pub fn get_payment_request<'a, T: ?Sized, C, K>(w: &T, id: &Uuid) -> Result<PaymentRequest, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	w.get_payment_request(id)?
+		.ok_or(ErrorKind::GenericError(format!("Payment request {} is not found", id)).into())
+}
+
+/// Cancel the open payment request. Payments for it are not accepted any more.
+pub fn cancel_payment_request<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: &Uuid,
+) -> Result<PaymentRequest, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	update_payment_requests(&mut *w, keychain_mask)?;
+	let mut request = get_payment_request(&*w, id)?;
+	if request.status != PaymentRequestStatus::Open {
+		return Err(ErrorKind::GenericError(format!(
+			"Payment request {} is {}, only open requests can be cancelled",
+			id, request.status
+		))
+		.into());
+	}
+	request.status = PaymentRequestStatus::Cancelled;
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_payment_request(&request)?;
+	batch.commit()?;
+	Ok(request)
+}
+
+/// Update the status of the open requests. A request is paid when its transaction is
+/// confirmed. It expires if it has no transaction in progress after the expiry height or time.
+pub fn update_payment_requests<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let open: Vec<PaymentRequest> = w
+		.payment_request_iter()
+		.filter(|r| r.status == PaymentRequestStatus::Open)
+		.collect();
+	if open.is_empty() {
+		return Ok(());
+	}
+
+	let height = w.last_confirmed_height()?;
+	let now = Utc::now();
+
+	let mut updated = vec![];
+	for mut request in open {
+		let request_txs: Vec<TxLogEntry> = w
+			.get_tx_log_by_slate_id(&request.id)?
+			.into_iter()
+			.filter(|t| t.tx_type == TxLogEntryType::TxReceived)
+			.collect();
+		let status = if request_txs.iter().any(|t| t.confirmed) {
+			PaymentRequestStatus::Paid
+		} else if request_txs.is_empty() && request.is_expired(height, now) {
+			PaymentRequestStatus::Expired
+		} else {
+			continue;
+		};
+		request.status = status;
+		updated.push(request);
+	}
+
+	if !updated.is_empty() {
+		let mut batch = w.batch(keychain_mask)?;
+		for request in &updated {
+			batch.save_payment_request(request)?;
+		}
+		batch.commit()?;
+	}
+	Ok(())
+}
+
+/// Get the payment request that can be paid: it must be open and not expired
+pub fn get_open_payment_request<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	id: &Uuid,
+) -> Result<PaymentRequest, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let request = get_payment_request(&*w, id)?;
+	if request.status != PaymentRequestStatus::Open {
+		return Err(ErrorKind::GenericError(format!(
+			"Payment request {} is {}",
+			request.id, request.status
+		))
+		.into());
+	}
+	let height = w.last_confirmed_height()?;
+	if request.is_expired(height, Utc::now()) {
+		return Err(
+			ErrorKind::GenericError(format!("Payment request {} is expired", request.id)).into(),
+		);
+	}
+	Ok(request)
+}
+
+/// Check the slate that pays a payment request of this wallet. Slates of the unknown ids are not
+/// payments for requests and pass. The request must be open and not expired, the amount must be
+/// at least the requested one, and the payment proof must be requested if the request needs it.
+pub fn verify_payment<'a, T: ?Sized, C, K>(w: &mut T, slate: &Slate) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if w.get_payment_request(&slate.id)?.is_none() {
+		return Ok(());
+	}
+	let request = get_open_payment_request(&mut *w, &slate.id)?;
+	if slate.amount < request.amount {
+		return Err(ErrorKind::GenericError(format!(
+			"Payment request {} is for {} nano MWC, the transaction has {}",
+			request.id, request.amount, slate.amount
+		))
+		.into());
+	}
+	if let Some(proof_address) = &request.proof_address {
+		let proof_requested = slate
+			.payment_proof
+			.as_ref()
+			.map(|p| p.receiver_address.public_key == proof_address.public_key)
+			.unwrap_or(false);
+		if !proof_requested {
+			return Err(ErrorKind::GenericError(format!(
+				"Payment request {} requires a payment proof for {}",
+				request.id, proof_address.public_key
+			))
+			.into());
+		}
+	}
+	Ok(())
+}
+
+/// Encode the request as a URI, for example
+/// 'mwc:http://host:3415?amount=1.5&id=<uuid>&memo=Order%2042&expiry_height=1000&proof=<address>'
+pub fn payment_request_uri(request: &PaymentRequest) -> String {
+	let mut uri = format!(
+		"{}{}?amount={}&id={}",
+		URI_SCHEME,
+		encode(&request.address),
+		format_amount(request.amount),
+		request.id
+	);
+	if let Some(memo) = &request.memo {
+		uri.push_str(&format!("&memo={}", encode(memo)));
+	}
+	if let Some(h) = request.expiry_height {
+		uri.push_str(&format!("&expiry_height={}", h));
+	}
+	if let Some(t) = request.expiry_time {
+		uri.push_str(&format!("&expiry_time={}", t.timestamp()));
+	}
+	if let Some(a) = &request.proof_address {
+		uri.push_str(&format!("&proof={}", a.public_key));
+	}
+	uri
+}
+
+/// Decode the request URI. Unknown parameters are ignored, so the newer requests can be read.
+pub fn parse_payment_request_uri(uri: &str) -> Result<PaymentRequest, Error> {
+	let err = |msg: &str| -> Error {
+		ErrorKind::GenericError(format!("Invalid payment request '{}', {}", uri, msg)).into()
+	};
+
+	let uri = uri.trim();
+	let body = match uri.get(..URI_SCHEME.len()) {
+		Some(s) if s.eq_ignore_ascii_case(URI_SCHEME) => &uri[URI_SCHEME.len()..],
+		_ => return Err(err(&format!("expected '{}' scheme", URI_SCHEME))),
+	};
+	let (address, query) = match body.find('?') {
+		Some(i) => (&body[..i], &body[i + 1..]),
+		None => (body, ""),
+	};
+	let address = decode(address).ok_or(err("invalid address encoding"))?;
+	if address.is_empty() {
+		return Err(err("address is missing"));
+	}
+
+	let mut amount = None;
+	let mut id = None;
+	let mut memo = None;
+	let mut expiry_height = None;
+	let mut expiry_time = None;
+	let mut proof_address = None;
+	for param in query.split('&').filter(|p| !p.is_empty()) {
+		let (key, value) = match param.find('=') {
+			Some(i) => (&param[..i], &param[i + 1..]),
+			None => (param, ""),
+		};
+		let value = decode(value).ok_or(err(&format!("invalid {} encoding", key)))?;
+		match key {
+			"amount" => amount = Some(parse_amount(&value).ok_or(err("invalid amount"))?),
+			"id" => id = Some(Uuid::parse_str(&value).map_err(|_| err("invalid id"))?),
+			"memo" => memo = Some(value),
+			"expiry_height" => {
+				expiry_height = Some(
+					value
+						.parse::<u64>()
+						.map_err(|_| err("invalid expiry_height"))?,
+				)
+			}
+			"expiry_time" => {
+				let secs = value
+					.parse::<i64>()
+					.map_err(|_| err("invalid expiry_time"))?;
+				expiry_time = Some(
+					Utc.timestamp_opt(secs, 0)
+						.single()
+						.ok_or(err("invalid expiry_time"))?,
+				);
+			}
+			"proof" => {
+				proof_address = Some(
+					ProvableAddress::from_str(&value)
+						.map_err(|e| err(&format!("invalid proof address, {}", e)))?,
+				)
+			}
+			_ => (),
+		}
+	}
+
+	let amount = amount.ok_or(err("amount is missing"))?;
+	if amount == 0 {
+		return Err(err("amount can't be zero"));
+	}
+	Ok(PaymentRequest {
+		id: id.ok_or(err("id is missing"))?,
+		amount,
+		address,
+		memo,
+		expiry_height,
+		expiry_time,
+		proof_address,
+		created: Utc::now(),
+		status: PaymentRequestStatus::Open,
+	})
+}
+
+/// Send method for the request address: 'mwcmqs' for MQS addresses, 'http' for the rest
+pub fn payment_request_method(request: &PaymentRequest) -> &'static str {
+	if is_mqs_address(&request.address) {
+		"mwcmqs"
+	} else {
+		"http"
+	}
+}
+
+// MQS address, with optional scheme and domain
+fn is_mqs_address(address: &str) -> bool {
+	let address = address.trim_start_matches("mwcmqs://");
+	let key = match address.find('@') {
+		Some(i) => &address[..i],
+		None => address,
+	};
+	ProvableAddress::from_str(key)
+		.map(|a| a.public_key().is_ok())
+		.unwrap_or(false)
+}
+
+// Exact decimal representation of nano MWC, without the trailing zeros
+fn format_amount(amount: u64) -> String {
+	let fraction = amount % NANO_PER_MWC;
+	if fraction == 0 {
+		return format!("{}", amount / NANO_PER_MWC);
+	}
+	let fraction = format!("{:09}", fraction);
+	format!(
+		"{}.{}",
+		amount / NANO_PER_MWC,
+		fraction.trim_end_matches('0')
+	)
+}
+
+fn parse_amount(s: &str) -> Option<u64> {
+	let (int, fraction) = match s.find('.') {
+		Some(i) => (&s[..i], &s[i + 1..]),
+		None => (s, ""),
+	};
+	if int.is_empty() || fraction.len() > 9 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+		return None;
+	}
+	let int = int.parse::<u64>().ok()?;
+	let fraction = format!("{:0<9}", fraction).parse::<u64>().ok()?;
+	int.checked_mul(NANO_PER_MWC)?.checked_add(fraction)
+}
+
+// Percent encoding of everything except the unreserved and the address symbols
+fn encode(s: &str) -> String {
+	let mut res = String::new();
+	for b in s.bytes() {
+		match b {
+			b'A'..=b'Z'
+			| b'a'..=b'z'
+			| b'0'..=b'9'
+			| b'-'
+			| b'.'
+			| b'_'
+			| b'~'
+			| b':'
+			| b'/'
+			| b'@' => res.push(b as char),
+			_ => res.push_str(&format!("%{:02X}", b)),
+		}
+	}
+	res
+}
+
+fn decode(s: &str) -> Option<String> {
+	let bytes = s.as_bytes();
+	let mut res = vec![];
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'%' => {
+				let hex = s.get(i + 1..i + 3)?;
+				res.push(u8::from_str_radix(hex, 16).ok()?);
+				i += 3;
+			}
+			b'+' => {
+				res.push(b' ');
+				i += 1;
+			}
+			b => {
+				res.push(b);
+				i += 1;
+			}
+		}
+	}
+	String::from_utf8(res).ok()
+}
@@ -0,0 +1,485 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduled and recurring payments. Payments are stored in the wallet DB and sent by
+//! the updater thread when they are due. Failed sends are retried with exponential backoff.
+//! The slate id of the send is stored before the send, so a payment that failed after its
+//! transaction was finalized is not sent again.
+
+use std::cmp;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::grin_core::core::Transaction;
+use crate::grin_keychain::Keychain;
+use crate::grin_util::secp::key::SecretKey;
+use crate::grin_util::Mutex;
+
+use crate::internal::tx;
+use crate::slate::Slate;
+use crate::types::{
+	NodeClient, ScheduledPayment, ScheduledPaymentRun, ScheduledPaymentStatus, TxLogEntryType,
+	WalletBackend,
+};
+use crate::{
+	Error, ErrorKind, InitTxArgs, InitTxSendArgs, SchedulePaymentArgs, WalletInst, WalletLCProvider,
+};
+
+// Delay before the first retry, doubled with every next one
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+// Max delay between the retries
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+// Number of the send attempts that are kept in the payment history
+const MAX_HISTORY_LEN: usize = 20;
+
+/// Sends the scheduled payments. Implemented by the owner API with the configured sender,
+/// called from the updater thread.
+pub trait PaymentExecutor: Send + Sync {
+	/// Init, send, finalize and post the transaction. The transaction must be created with
+	/// the slate id from args.
+	fn send(&self, keychain_mask: Option<&SecretKey>, args: &InitTxArgs) -> Result<Slate, Error>;
+}
+
+/// Add a new scheduled payment
+pub fn schedule_payment<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &SchedulePaymentArgs,
+) -> Result<ScheduledPayment, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if args.amount == 0 {
+		return Err(ErrorKind::GenericError("Payment amount can't be zero".to_string()).into());
+	}
+	if args.minimum_confirmations < 1 {
+		return Err(ErrorKind::ClientCallback(
+			"Minimum_confirmations can not be smaller than 1".to_owned(),
+		)
+		.into());
+	}
+	match args.method.as_str() {
+		"http" | "mwcmqs" => (),
+		m => {
+			return Err(ErrorKind::GenericError(format!(
+				"Unsupported payment method {}, expected http or mwcmqs",
+				m
+			))
+			.into())
+		}
+	}
+	if args.interval_secs == Some(0) {
+		return Err(ErrorKind::GenericError("Payment interval can't be zero".to_string()).into());
+	}
+	if let Some(name) = &args.src_acct_name {
+		if w.get_acct_path(name.clone())?.is_none() {
+			return Err(ErrorKind::UnknownAccountLabel(name.clone()).into());
+		}
+	}
+
+	let payment = ScheduledPayment {
+		id: Uuid::new_v4(),
+		src_acct_name: args.src_acct_name.clone(),
+		amount: args.amount,
+		minimum_confirmations: args.minimum_confirmations,
+		message: args.message.clone(),
+		method: args.method.clone(),
+		dest: args.dest.clone(),
+		apisecret: args.apisecret.clone(),
+		fluff: args.fluff,
+		interval_secs: args.interval_secs,
+		next_run: args.start_time.unwrap_or_else(Utc::now),
+		retry_at: None,
+		retries: 0,
+		max_retries: args.max_retries,
+		status: ScheduledPaymentStatus::Active,
+		history: vec![],
+		in_flight: None,
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_scheduled_payment(&payment)?;
+	batch.commit()?;
+	Ok(payment)
+}
+
+/// All scheduled payments, ordered by the next run time
+pub fn scheduled_payments<'a, T: ?Sized, C, K>(w: &T) -> Result<Vec<ScheduledPayment>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut payments: Vec<ScheduledPayment> = w.scheduled_payment_iter().collect();
+	payments.sort_by_key(|p| p.next_run);
+	Ok(payments)
+}
+
+/// Get a scheduled payment by id
+pub fn get_scheduled_payment<'a, T: ?Sized, C, K>(
+	w: &T,
+	id: &Uuid,
+) -> Result<ScheduledPayment, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	w.get_scheduled_payment(id)?
+		.ok_or(ErrorKind::GenericError(format!("Scheduled payment {} is not found", id)).into())
+}
+
+/// Delete a scheduled payment. Already sent transactions are not affected.
+pub fn delete_scheduled_payment<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: &Uuid,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	get_scheduled_payment(&*w, id)?;
+	let mut batch = w.batch(keychain_mask)?;
+	batch.delete_scheduled_payment(id)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Pause or resume a scheduled payment. A resumed recurring payment continues from its
+/// next interval, the payments that were missed during the pause are not sent.
+pub fn set_scheduled_payment_active<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: &Uuid,
+	active: bool,
+) -> Result<ScheduledPayment, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut payment = get_scheduled_payment(&*w, id)?;
+	match (&payment.status, active) {
+		(ScheduledPaymentStatus::Active, false) => {
+			payment.status = ScheduledPaymentStatus::Paused;
+			payment.retry_at = None;
+			payment.retries = 0;
+		}
+		(ScheduledPaymentStatus::Paused, true) => {
+			payment.status = ScheduledPaymentStatus::Active;
+			if let Some(interval) = payment.interval_secs {
+				payment.next_run = next_interval(payment.next_run, interval, Utc::now());
+			}
+		}
+		(ScheduledPaymentStatus::Active, true) | (ScheduledPaymentStatus::Paused, false) => (),
+		(status, _) => {
+			return Err(ErrorKind::GenericError(format!(
+				"Scheduled payment {} is {}, it can't be changed",
+				id, status
+			))
+			.into())
+		}
+	}
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_scheduled_payment(&payment)?;
+	batch.commit()?;
+	Ok(payment)
+}
+
+/// Active payments that should be sent at 'now'
+pub fn due_payments<'a, T: ?Sized, C, K>(
+	w: &T,
+	now: DateTime<Utc>,
+) -> Result<Vec<ScheduledPayment>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	Ok(scheduled_payments(w)?
+		.into_iter()
+		.filter(|p| p.due_time().map(|t| t <= now).unwrap_or(false))
+		.collect())
+}
+
+/// Transaction arguments for the payment with the given slate id, the transaction is
+/// finalized and posted
+pub fn payment_tx_args(payment: &ScheduledPayment, slate_id: Uuid) -> InitTxArgs {
+	InitTxArgs {
+		src_acct_name: payment.src_acct_name.clone(),
+		amount: payment.amount,
+		minimum_confirmations: payment.minimum_confirmations,
+		message: payment.message.clone(),
+		address: Some(payment.dest.clone()),
+		send_args: Some(InitTxSendArgs {
+			method: payment.method.clone(),
+			dest: payment.dest.clone(),
+			apisecret: payment.apisecret.clone(),
+			finalize: true,
+			post_tx: true,
+			fluff: payment.fluff,
+		}),
+		slate_id: Some(slate_id),
+		..Default::default()
+	}
+}
+
+/// Record the send attempt and clear the send in progress. On success the payment moves to
+/// the next interval, or is completed if it is a one time payment. A failed send is retried
+/// with exponential backoff until max_retries is reached, then the payment is skipped till
+/// the next interval, or marked as failed if it is a one time payment.
+pub fn record_payment_result<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: &Uuid,
+	now: DateTime<Utc>,
+	result: Result<Uuid, String>,
+) -> Result<ScheduledPayment, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut payment = get_scheduled_payment(&*w, id)?;
+	payment.in_flight = None;
+
+	let (tx_slate_id, error) = match result {
+		Ok(slate_id) => (Some(slate_id), None),
+		Err(e) => (None, Some(e)),
+	};
+	payment.history.push(ScheduledPaymentRun {
+		time: now,
+		retry: payment.retries,
+		tx_slate_id,
+		error: error.clone(),
+	});
+	if payment.history.len() > MAX_HISTORY_LEN {
+		let extra = payment.history.len() - MAX_HISTORY_LEN;
+		payment.history.drain(..extra);
+	}
+
+	// Paused or deleted while the payment was sent
+	if payment.status == ScheduledPaymentStatus::Active {
+		let failed = error.is_some();
+		if failed && payment.retries < payment.max_retries {
+			payment.retries += 1;
+			payment.retry_at = Some(now + Duration::seconds(retry_delay(payment.retries)));
+		} else {
+			payment.retries = 0;
+			payment.retry_at = None;
+			match payment.interval_secs {
+				Some(interval) => payment.next_run = next_interval(payment.next_run, interval, now),
+				None => {
+					payment.status = match failed {
+						true => ScheduledPaymentStatus::Failed,
+						false => ScheduledPaymentStatus::Completed,
+					}
+				}
+			}
+		}
+	}
+
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_scheduled_payment(&payment)?;
+	batch.commit()?;
+	Ok(payment)
+}
+
+// Transaction of the send in progress
+enum InFlightTx {
+	// Confirmed, the payment is done
+	Confirmed,
+	// Finalized, but not confirmed yet. It might be posted already, the payment waits for it.
+	Finalized(Transaction),
+	// Not created, cancelled or not finalized, the payment can be sent again
+	NotSent,
+}
+
+// Check the transaction of the send in progress. A transaction that is not finalized can't
+// be posted, it is cancelled to unlock its outputs.
+fn check_in_flight<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate_id: &Uuid,
+) -> Result<InFlightTx, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let tx_entry = w.get_tx_log_by_slate_id(slate_id)?.into_iter().find(|t| {
+		matches!(
+			t.tx_type,
+			TxLogEntryType::TxSent | TxLogEntryType::TxSentCancelled
+		)
+	});
+	let tx_entry = match tx_entry {
+		Some(t) => t,
+		None => return Ok(InFlightTx::NotSent),
+	};
+	if tx_entry.confirmed {
+		return Ok(InFlightTx::Confirmed);
+	}
+	if tx_entry.is_cancelled() {
+		return Ok(InFlightTx::NotSent);
+	}
+
+	let final_tx = w.get_stored_tx(&tx_entry)?.filter(|t| {
+		t.kernels()
+			.first()
+			.map(|k| k.verify().is_ok())
+			.unwrap_or(false)
+	});
+	match final_tx {
+		Some(final_tx) => Ok(InFlightTx::Finalized(final_tx)),
+		None => {
+			tx::cancel_tx(
+				w,
+				keychain_mask,
+				&tx_entry.parent_key_id,
+				Some(tx_entry.id),
+				None,
+			)?;
+			Ok(InFlightTx::NotSent)
+		}
+	}
+}
+
+/// Send the due payments. Called by the updater thread after the wallet is updated.
+/// The wallet is not locked while the slate is sent. If the previous send of the payment
+/// was interrupted, its transaction is checked first, and the payment is sent again only
+/// if that transaction can't reach the chain.
+pub fn run_due_payments<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	executor: &dyn PaymentExecutor,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let payments = {
+		wallet_lock!(wallet_inst, w);
+		due_payments(&**w, Utc::now())?
+	};
+
+	for payment in payments {
+		let mut send_error = None;
+		let slate_id = match payment.in_flight {
+			Some(slate_id) => slate_id,
+			None => {
+				let slate_id = Uuid::new_v4();
+				{
+					wallet_lock!(wallet_inst, w);
+					let mut p = get_scheduled_payment(&**w, &payment.id)?;
+					p.in_flight = Some(slate_id);
+					let mut batch = w.batch(keychain_mask)?;
+					batch.save_scheduled_payment(&p)?;
+					batch.commit()?;
+				}
+
+				match executor.send(keychain_mask, &payment_tx_args(&payment, slate_id)) {
+					Ok(slate) => {
+						info!(
+							"Scheduled payment {} to {} is sent, slate {}",
+							payment.id, payment.dest, slate.id
+						);
+						wallet_lock!(wallet_inst, w);
+						record_payment_result(
+							&mut **w,
+							keychain_mask,
+							&payment.id,
+							Utc::now(),
+							Ok(slate.id),
+						)?;
+						continue;
+					}
+					Err(e) => {
+						warn!(
+							"Scheduled payment {} to {} is failed, {}",
+							payment.id, payment.dest, e
+						);
+						send_error = Some(format!("{}", e));
+						slate_id
+					}
+				}
+			}
+		};
+
+		let (in_flight, client) = {
+			wallet_lock!(wallet_inst, w);
+			let in_flight = check_in_flight(&mut **w, keychain_mask, &slate_id);
+			(in_flight, w.w2n_client().clone())
+		};
+		let result = match in_flight {
+			Ok(InFlightTx::Confirmed) => Ok(slate_id),
+			Ok(InFlightTx::Finalized(final_tx)) => {
+				// Posting the same transaction again can't pay twice
+				if let Err(e) = client.post_tx(&final_tx, payment.fluff) {
+					warn!("Unable to post the transaction {}, {}", slate_id, e);
+				}
+				info!(
+					"Scheduled payment {} waits for the transaction {}",
+					payment.id, slate_id
+				);
+				continue;
+			}
+			Ok(InFlightTx::NotSent) => Err(send_error.unwrap_or_else(|| {
+				format!("Send of the transaction {} was interrupted", slate_id)
+			})),
+			Err(e) => {
+				warn!(
+					"Unable to check the transaction {} of the scheduled payment {}, {}",
+					slate_id, payment.id, e
+				);
+				continue;
+			}
+		};
+
+		wallet_lock!(wallet_inst, w);
+		let payment =
+			record_payment_result(&mut **w, keychain_mask, &payment.id, Utc::now(), result)?;
+		if let Some(retry_at) = payment.retry_at {
+			info!(
+				"Scheduled payment {} will be retried at {}",
+				payment.id, retry_at
+			);
+		}
+	}
+	Ok(())
+}
+
+// Exponential backoff, the retry number starts from 1
+fn retry_delay(retry: u32) -> i64 {
+	cmp::min(
+		RETRY_BASE_DELAY_SECS << cmp::min(retry.saturating_sub(1), 16),
+		MAX_RETRY_DELAY_SECS,
+	)
+}
+
+// First run after 'now' that is on the interval grid of 'next_run'
+fn next_interval(next_run: DateTime<Utc>, interval_secs: u64, now: DateTime<Utc>) -> DateTime<Utc> {
+	let interval = cmp::max(interval_secs, 1) as i64;
+	let periods = match now >= next_run {
+		true => (now - next_run).num_seconds() / interval + 1,
+		false => 0,
+	};
+	next_run + Duration::seconds(periods * interval)
+}
@@ -28,8 +28,8 @@ use crate::swap::{trades, BuyApi, Context, SwapApi};
 use crate::types::NodeClient;
 use crate::{get_receive_account, Error};
 use crate::{
-	wallet_lock, OutputData, OutputStatus, Slate, SwapStartArgs, TxLogEntry, TxLogEntryType,
-	WalletBackend, WalletInst, WalletLCProvider,
+	wallet_lock, CoinSelectionStrategy, OutputData, OutputStatus, ScanConfig, Slate, SwapStartArgs,
+	TxLogEntry, TxLogEntryType, WalletBackend, WalletInst, WalletLCProvider,
 };
 use grin_core::core;
 use grin_keychain::ExtKeychainPath;
@@ -49,6 +49,18 @@ fn get_swap_storage_key<K: Keychain>(keychain: &K) -> Result<SecretKey, Error> {
 	)?)
 }
 
+/// Swap trades track the lock, redeem and refund transactions by requesting their outputs
+/// and kernels from the node, that is not allowed when the wallet updates privately.
+fn check_private_update(scan_config: &ScanConfig) -> Result<(), Error> {
+	if scan_config.private_update {
+		return Err(ErrorKind::Generic(
+			"Swap trades are not supported in private update mode".to_string(),
+		)
+		.into());
+	}
+	Ok(())
+}
+
 /// Start swap trade process. Return SwapID that can be used to check the status or perform further action.
 pub fn swap_start<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -82,6 +94,7 @@ where
 		.collect();
 
 	wallet_lock!(wallet_inst, w);
+	check_private_update(&w.scan_config())?;
 	let node_client = w.w2n_client().clone();
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
@@ -145,7 +158,7 @@ where
 		params.minimum_confirmations.unwrap_or(10),
 		500,
 		1,
-		false,
+		CoinSelectionStrategy::Smallest,
 		&parent_key_id,
 		&Some(outputs), // outputs to include into the transaction
 		1,              // Number of resulting outputs. Normally it is 1
@@ -220,6 +233,7 @@ where
 }
 
 /// Respond from swap_list API. Respond is very specific, that is why it has special structure
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SwapListInfo {
 	/// Swap id
 	pub swap_id: String,
@@ -266,7 +280,8 @@ where
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
 
-	let mut do_check = do_check;
+	// Node checks of the trades are skipped, the swap transactions are requested by commit
+	let mut do_check = do_check && !w.scan_config().private_update;
 
 	for sw_id in &swap_id {
 		let swap_lock = trades::get_swap_lock(sw_id);
@@ -394,6 +409,7 @@ where
 	K: Keychain + 'a,
 {
 	wallet_lock!(wallet_inst, w);
+	check_private_update(&w.scan_config())?;
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
 	let node_client = w.w2n_client();
@@ -577,6 +593,7 @@ where
 	K: Keychain + 'a,
 {
 	wallet_lock!(wallet_inst, w);
+	check_private_update(&w.scan_config())?;
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
 	let swap_lock = trades::get_swap_lock(&"export".to_string());
@@ -725,6 +742,7 @@ where
 	K: Keychain + 'a,
 {
 	wallet_lock!(wallet_inst, w);
+	check_private_update(&w.scan_config())?;
 	let node_client = w.w2n_client().clone();
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
@@ -780,6 +798,7 @@ where
 	K: Keychain + 'a,
 {
 	wallet_lock!(wallet_inst, w);
+	check_private_update(&w.scan_config())?;
 	let node_client = w.w2n_client().clone();
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
@@ -925,10 +944,7 @@ where
 			wallet_lock!(wallet_inst, w);
 			// Checking if transaction is already created.
 			let kernel = &swap.lock_slate.tx.body.kernels[0].excess;
-			if w.tx_log_iter()
-				.filter(|tx| tx.kernel_excess.filter(|c| c == kernel).is_some())
-				.count() == 0
-			{
+			if w.get_tx_log_by_kernel_excess(kernel)?.is_empty() {
 				// Transaction doesn't exist, let's create it and lock the outputs.
 				let seller_context = context.unwrap_seller()?;
 				let slate_context = crate::types::Context::from_send_slate(
@@ -969,10 +985,7 @@ where
 
 			// Checking if this transaction already exist
 			let kernel = &swap.redeem_slate.tx.body.kernels[0].excess;
-			if w.tx_log_iter()
-				.filter(|tx| tx.kernel_excess.filter(|c| c == kernel).is_some())
-				.count() == 0
-			{
+			if w.get_tx_log_by_kernel_excess(kernel)?.is_empty() {
 				// Creating receive transaction from the slate
 				let buyer_context = context.unwrap_buyer()?;
 				create_receive_tx_record(
@@ -991,10 +1004,7 @@ where
 			wallet_lock!(wallet_inst, w);
 
 			let kernel = &swap.refund_slate.tx.body.kernels[0].excess;
-			if w.tx_log_iter()
-				.filter(|tx| tx.kernel_excess.filter(|c| c == kernel).is_some())
-				.count() == 0
-			{
+			if w.get_tx_log_by_kernel_excess(kernel)?.is_empty() {
 				// For MWC transaction we can create a record in the wallet.
 				let seller_context = context.unwrap_seller()?;
 				create_receive_tx_record(
@@ -1051,6 +1061,7 @@ where
 {
 	let (node_client, keychain) = {
 		wallet_lock!(wallet_inst, w);
+		check_private_update(&w.scan_config())?;
 		let node_client = w.w2n_client().clone();
 		let keychain = w.keychain(keychain_mask)?;
 		(node_client, keychain)
@@ -1176,16 +1187,30 @@ where
 		))
 	})?;
 
+	swap_create_from_offer_message(wallet_inst, keychain_mask, &contents)
+}
+
+/// Create Swap record from the income offer message content
+pub fn swap_create_from_offer_message<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	contents: &str,
+) -> Result<String, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
 	// processing the message with a regular API.
 	// but first let's check if the message type matching expected
-	let message = Message::from_json(&contents)?;
+	let message = Message::from_json(contents)?;
 	if !message.is_offer() {
 		return Err(
 			ErrorKind::Generic("Expected offer message, get different one".to_string()).into(),
 		);
 	}
 
-	swap_income_message(wallet_inst, keychain_mask, &contents, None)?;
+	swap_income_message(wallet_inst, keychain_mask, contents, None)?;
 	Ok(message.id.to_string())
 }
 
@@ -1210,6 +1235,7 @@ where
 	debug!("Get swap message {:?}", message);
 
 	wallet_lock!(wallet_inst, w);
+	check_private_update(&w.scan_config())?;
 	let node_client = w.w2n_client().clone();
 	let keychain = w.keychain(keychain_mask)?;
 	let skey = get_swap_storage_key(&keychain)?;
@@ -24,6 +24,8 @@ use crate::grin_util::secp::key::SecretKey;
 use crate::grin_util::Mutex;
 
 use crate::api_impl::owner;
+use crate::api_impl::owner_events::{self, EventNotifier};
+use crate::api_impl::owner_schedule::{self, PaymentExecutor};
 use crate::types::NodeClient;
 use crate::Error;
 use crate::{WalletInst, WalletLCProvider};
@@ -48,6 +50,8 @@ pub enum StatusMessage {
 	Warning(String),
 	/// Generic info message
 	Info(String),
+	/// Chain reorg is detected, the confirmations above the fork height are reverted
+	Reorg(u64, String),
 }
 
 /// Helper function that starts a simple log thread for updater messages
@@ -90,6 +94,7 @@ pub fn start_updater_log_thread(
 					}
 					StatusMessage::Warning(s) => warn!("{}", s),
 					StatusMessage::Info(s) => info!("{}", s),
+					StatusMessage::Reorg(_fork_height, s) => warn!("{}", s),
 				}
 			}
 			if !running {
@@ -133,6 +138,7 @@ pub fn start_updater_console_thread(
 						}
 						StatusMessage::Warning(s) => println!("Warning: {}", s),
 						StatusMessage::Info(s) => println!("Info: {}", s),
+						StatusMessage::Reorg(_fork_height, s) => println!("Warning: {}", s),
 					}
 				}
 				if !running {
@@ -176,12 +182,17 @@ where
 		}
 	}
 
-	/// Start the updater at the given frequency
+	/// Start the updater at the given frequency. If event_notifier is provided, wallet
+	/// events are detected after every update and delivered to its sink. Events the
+	/// sink fails to take stay in the wallet DB and are retried on the next cycle. If
+	/// payment_executor is provided, the due scheduled payments are sent after every update.
 	pub fn run(
 		&self,
 		frequency: Duration,
 		keychain_mask: Option<SecretKey>,
 		status_send_channel: &Option<Sender<StatusMessage>>,
+		event_notifier: Option<EventNotifier>,
+		payment_executor: Option<Arc<dyn PaymentExecutor>>,
 	) -> Result<(), Error> {
 		self.is_running.store(true, Ordering::Relaxed);
 		loop {
@@ -192,11 +203,40 @@ where
 			};
 			if wallet_opened {
 				// Business goes here
-				owner::update_wallet_state(
+				let updated = owner::update_wallet_state(
 					self.wallet_inst.clone(),
 					(&keychain_mask).as_ref(),
 					status_send_channel,
 				)?;
+
+				if let Some(notifier) = &event_notifier {
+					if updated {
+						if let Err(e) = owner_events::detect_events(
+							self.wallet_inst.clone(),
+							(&keychain_mask).as_ref(),
+							notifier.confirmations,
+						) {
+							warn!("Unable to detect wallet events, {}", e);
+						}
+					}
+					if let Err(e) = owner_events::deliver_events(
+						self.wallet_inst.clone(),
+						(&keychain_mask).as_ref(),
+						notifier.sink.as_ref(),
+					) {
+						warn!("Unable to deliver wallet events, {}", e);
+					}
+				}
+
+				if let (true, Some(executor)) = (updated, &payment_executor) {
+					if let Err(e) = owner_schedule::run_due_payments(
+						self.wallet_inst.clone(),
+						(&keychain_mask).as_ref(),
+						executor.as_ref(),
+					) {
+						warn!("Unable to send the scheduled payments, {}", e);
+					}
+				}
 			}
 
 			let sec = frequency.as_secs();
@@ -0,0 +1,322 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consolidation and splitting of the wallet outputs. Both are done as self sends:
+//! the wallet builds the transaction, receives it into the same account, finalizes
+//! and posts it.
+
+use std::cmp;
+
+use crate::grin_core::consensus;
+use crate::grin_core::core::amount_to_hr_string;
+use crate::grin_core::global;
+use crate::grin_core::libtx::tx_fee;
+use crate::grin_keychain::{Identifier, Keychain};
+use crate::grin_util::secp::key::SecretKey;
+
+use crate::api_impl::{foreign, owner};
+use crate::internal::selection;
+use crate::slate::Slate;
+use crate::types::{NodeClient, WalletBackend};
+use crate::{
+	CoinSelectionStrategy, ConsolidateArgs, Error, ErrorKind, InitTxArgs, SplitArgs, UtxoTx,
+};
+
+// Address that is recorded for the self send transactions
+const SELF_ADDRESS: &str = "self";
+
+/// Plan the consolidation. The smallest spendable outputs are merged into one output,
+/// the outputs are divided into transactions that fit into a block.
+pub fn consolidate_plan<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	args: &ConsolidateArgs,
+) -> Result<Vec<UtxoTx>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let max_inputs = cmp::min(args.max_inputs, max_block_inputs());
+	if max_inputs < 2 {
+		return Err(ErrorKind::GenericError(
+			"Consolidation needs at least 2 inputs per transaction".to_string(),
+		)
+		.into());
+	}
+
+	let (parent_key_id, _) = account(w, &args.src_acct_name)?;
+	let height = w.w2n_client().get_chain_tip()?.0;
+	let mut eligible = selection::eligible_outputs(
+		&mut *w,
+		height,
+		args.minimum_confirmations,
+		&parent_key_id,
+		&None,
+		false,
+		1,
+	);
+	eligible.sort_by_key(|o| o.value);
+	if let Some(n) = args.num_outputs {
+		eligible.truncate(n);
+	}
+	if eligible.len() < 2 {
+		return Err(ErrorKind::GenericError(format!(
+			"Found {} spendable outputs, at least 2 are needed for consolidation",
+			eligible.len()
+		))
+		.into());
+	}
+
+	// Transactions of a similar size, so the last one doesn't end up with a single input
+	let num_txs = (eligible.len() + max_inputs - 1) / max_inputs;
+	let tx_inputs = (eligible.len() + num_txs - 1) / num_txs;
+
+	let mut txs = vec![];
+	for inputs in eligible.chunks(tx_inputs) {
+		let total: u64 = inputs.iter().map(|o| o.value).sum();
+		let fee = tx_fee(inputs.len(), 1, 1, None);
+		if total <= fee {
+			debug!(
+				"Skipping consolidation of {} outputs, their value {} doesn't cover the fee",
+				inputs.len(),
+				total
+			);
+			continue;
+		}
+		txs.push(UtxoTx {
+			inputs: inputs.to_vec(),
+			outputs: vec![total - fee],
+			fee,
+			tx_slate_id: None,
+		});
+	}
+	if txs.is_empty() {
+		return Err(ErrorKind::GenericError(
+			"Value of the outputs doesn't cover the consolidation fee".to_string(),
+		)
+		.into());
+	}
+	Ok(txs)
+}
+
+/// Merge the smallest outputs of the account. If preview is true, only the planned
+/// transactions are returned, otherwise the transactions are created and posted.
+pub fn consolidate_outputs<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &ConsolidateArgs,
+	preview: bool,
+) -> Result<Vec<UtxoTx>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut txs = consolidate_plan(&mut *w, args)?;
+	if preview {
+		return Ok(txs);
+	}
+
+	for tx in txs.iter_mut() {
+		// Sweep of the planned inputs, the fee is paid from the amount
+		let init_args = InitTxArgs {
+			src_acct_name: args.src_acct_name.clone(),
+			minimum_confirmations: args.minimum_confirmations,
+			max_outputs: tx.inputs.len() as u32 + 1,
+			num_change_outputs: 0,
+			outputs: Some(tx.inputs.iter().filter_map(|o| o.commit.clone()).collect()),
+			sweep: Some(true),
+			..Default::default()
+		};
+		let slate = self_send(&mut *w, keychain_mask, &init_args, 1, None, args.fluff)?;
+		tx.outputs = vec![slate.amount];
+		tx.fee = slate.fee;
+		tx.tx_slate_id = Some(slate.id);
+	}
+	Ok(txs)
+}
+
+/// Plan the split of the output into the outputs of the requested amounts. The rest
+/// of the value, minus the fee, goes to a change output.
+pub fn split_plan<'a, T: ?Sized, C, K>(w: &mut T, args: &SplitArgs) -> Result<UtxoTx, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if args.amounts.is_empty() || args.amounts.iter().any(|a| *a == 0) {
+		return Err(
+			ErrorKind::GenericError("Split needs a list of non zero amounts".to_string()).into(),
+		);
+	}
+
+	let (parent_key_id, _) = account(w, &args.src_acct_name)?;
+	let height = w.w2n_client().get_chain_tip()?.0;
+	let output = selection::eligible_outputs(
+		&mut *w,
+		height,
+		args.minimum_confirmations,
+		&parent_key_id,
+		&Some(vec![args.output.clone()]),
+		false,
+		1,
+	)
+	.into_iter()
+	.next()
+	.ok_or(ErrorKind::GenericError(format!(
+		"Output {} is not found at the account or is not spendable",
+		args.output
+	)))?;
+
+	// The same fee as the coin selection calculates: no change if the value matches exactly
+	let amount: u64 = args.amounts.iter().sum();
+	let num_outputs = args.amounts.len();
+	let mut outputs = args.amounts.clone();
+	let mut fee = tx_fee(1, num_outputs, 1, None);
+	if output.value != amount + fee {
+		fee = tx_fee(1, num_outputs + 1, 1, None);
+		if output.value < amount + fee {
+			return Err(ErrorKind::NotEnoughFunds {
+				available: output.value,
+				available_disp: amount_to_hr_string(output.value, true),
+				needed: amount + fee,
+				needed_disp: amount_to_hr_string(amount + fee, true),
+			}
+			.into());
+		}
+		outputs.push(output.value - amount - fee);
+	}
+
+	Ok(UtxoTx {
+		inputs: vec![output],
+		outputs,
+		fee,
+		tx_slate_id: None,
+	})
+}
+
+/// Split the output into the outputs of the requested amounts. If preview is true, only
+/// the planned transaction is returned, otherwise the transaction is created and posted.
+pub fn split_output<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &SplitArgs,
+	preview: bool,
+) -> Result<UtxoTx, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut tx = split_plan(&mut *w, args)?;
+	if preview {
+		return Ok(tx);
+	}
+
+	// Receiver builds the outputs of the requested amounts, sender gets the change
+	let init_args = InitTxArgs {
+		src_acct_name: args.src_acct_name.clone(),
+		amount: args.amounts.iter().sum(),
+		minimum_confirmations: args.minimum_confirmations,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: true,
+		selection_strategy: Some(CoinSelectionStrategy::All),
+		outputs: Some(vec![args.output.clone()]),
+		..Default::default()
+	};
+	let slate = self_send(
+		&mut *w,
+		keychain_mask,
+		&init_args,
+		args.amounts.len(),
+		Some(args.amounts.clone()),
+		args.fluff,
+	)?;
+	tx.fee = slate.fee;
+	tx.tx_slate_id = Some(slate.id);
+	Ok(tx)
+}
+
+// Send to the same account: build, receive, lock, finalize and post
+fn self_send<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &InitTxArgs,
+	routputs: usize,
+	output_amounts: Option<Vec<u64>>,
+	fluff: bool,
+) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (_, account_name) = account(w, &args.src_acct_name)?;
+	let address = Some(SELF_ADDRESS.to_string());
+
+	let mut slate = owner::init_send_tx(&mut *w, keychain_mask, args, false, routputs)?;
+	slate = foreign::receive_tx(
+		&mut *w,
+		keychain_mask,
+		&slate,
+		address.clone(),
+		None,
+		output_amounts,
+		Some(&account_name),
+		None,
+		false,
+		false,
+	)?;
+	owner::tx_lock_outputs(&mut *w, keychain_mask, &slate, address, 0, false)?;
+	let (slate, _) = owner::finalize_tx(&mut *w, keychain_mask, &slate, false, false)?;
+
+	let client = w.w2n_client().clone();
+	owner::post_tx(&client, &slate.tx, fluff)?;
+	Ok(slate)
+}
+
+// Path and name of the account, the active one if the name is not specified
+fn account<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	acct_name: &Option<String>,
+) -> Result<(Identifier, String), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = match acct_name {
+		Some(name) => match w.get_acct_path(name.clone())? {
+			Some(p) => p.path,
+			None => return Err(ErrorKind::UnknownAccountLabel(name.clone()).into()),
+		},
+		None => w.parent_key_id(),
+	};
+	let name = w
+		.acct_path_iter()
+		.find(|a| a.path == parent_key_id)
+		.map(|a| a.label)
+		.ok_or(ErrorKind::GenericError(format!(
+			"Account {} is not found",
+			parent_key_id.to_bip_32_string()
+		)))?;
+	Ok((parent_key_id, name))
+}
+
+// Largest number of inputs of a transaction with a single output and kernel that
+// still fits into a block together with the coinbase
+fn max_block_inputs() -> usize {
+	let reserved = 2 * (consensus::BLOCK_OUTPUT_WEIGHT + consensus::BLOCK_KERNEL_WEIGHT);
+	(global::max_block_weight().saturating_sub(reserved) / consensus::BLOCK_INPUT_WEIGHT) as usize
+}
@@ -20,7 +20,12 @@ use crate::grin_util::secp::pedersen;
 use crate::proof::proofaddress;
 use crate::proof::proofaddress::ProvableAddress;
 use crate::slate_versions::SlateVersion;
-use crate::types::OutputData;
+use crate::types::{OutputData, OutputMetadata, TxLogEntry, TxLogEntryType};
+use crate::ErrorKind;
+use chrono::prelude::*;
+use std::convert::TryFrom;
+use std::fmt;
+use uuid::Uuid;
 
 /// Send TX API Args
 // TODO: This is here to ensure the legacy V1 API remains intact
@@ -84,6 +89,10 @@ pub struct InitTxArgs {
 	/// value outputs.
 	#[serde(default = "InitTxArgs::default_selection_strategy_is_use_all")]
 	pub selection_strategy_is_use_all: bool,
+	/// Coin selection strategy. If set, it takes precedence over `selection_strategy_is_use_all`.
+	/// Possible values: `all`, `smallest`, `branch_and_bound`, `largest_first`, `oldest_first`, `privacy`.
+	#[serde(default)]
+	pub selection_strategy: Option<CoinSelectionStrategy>,
 	/// An optional participant message to include alongside the sender's public
 	/// ParticipantData within the slate. This message will include a signature created with the
 	/// sender's private excess value, and will be publically verifiable. Note this message is for
@@ -141,6 +150,19 @@ pub struct InitTxArgs {
 	/// because we don't want outputs to be reserved for a long time.
 	#[serde(default)]
 	pub late_lock: Option<bool>,
+	/// If true, send everything: the eligible outputs of the account (or the `outputs`, if set)
+	/// are spent without a change output and the fee is deducted from the sent amount. The
+	/// `amount` value is ignored. Up to `max_outputs` largest outputs are spent.
+	#[serde(default)]
+	pub sweep: Option<bool>,
+	/// Id of the paid payment request. It is used as the slate id, so the payee can match
+	/// the transaction with the request.
+	#[serde(default)]
+	pub payment_request_id: Option<Uuid>,
+	/// Slate id of the new transaction, so the caller can find the transaction even if the
+	/// call fails. Init fails if the wallet already has a transaction with this slate id.
+	#[serde(default)]
+	pub slate_id: Option<Uuid>,
 }
 
 /// Send TX API Args, for convenience functionality that inits the transaction and sends
@@ -174,6 +196,7 @@ impl Default for InitTxArgs {
 			max_outputs: 500,
 			num_change_outputs: 1,
 			selection_strategy_is_use_all: true,
+			selection_strategy: None,
 			message: None,
 			target_slate_version: None,
 			ttl_blocks: None,
@@ -186,6 +209,9 @@ impl Default for InitTxArgs {
 			late_lock: Some(false),
 			outputs: None,
 			slatepack_recipient: None,
+			sweep: Some(false),
+			payment_request_id: None,
+			slate_id: None,
 		}
 	}
 }
@@ -206,6 +232,477 @@ impl InitTxArgs {
 	fn default_selection_strategy_is_use_all() -> bool {
 		false
 	}
+
+	/// Coin selection strategy to use for this transaction. Falls back to the legacy
+	/// `selection_strategy_is_use_all` flag if no strategy was set explicitly.
+	pub fn coin_selection_strategy(&self) -> CoinSelectionStrategy {
+		match self.selection_strategy {
+			Some(s) => s,
+			None => CoinSelectionStrategy::from_use_all(self.selection_strategy_is_use_all),
+		}
+	}
+}
+
+/// Strategy for selecting the outputs that will be spent as transaction inputs
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinSelectionStrategy {
+	/// Spend as many outputs as possible, up to `max_outputs`. Reduces the UTXO set,
+	/// but usually produces a large change output.
+	All,
+	/// Spend the smallest outputs first, only as many as needed to cover the amount.
+	Smallest,
+	/// Search for a set of outputs that covers amount and fee exactly, so no change output
+	/// is needed. Falls back to `Smallest` if no such set exists.
+	BranchAndBound,
+	/// Spend the largest outputs first. Produces transactions with the fewest inputs.
+	LargestFirst,
+	/// Spend the oldest outputs first.
+	OldestFirst,
+	/// Avoid mixing outputs that were received with different transactions. Outputs from
+	/// a single transaction are preferred; if none can cover the amount, as few
+	/// transactions as possible are combined.
+	Privacy,
+}
+
+impl CoinSelectionStrategy {
+	/// All known strategies, in the order they are reported by the estimation
+	pub fn all_strategies() -> Vec<CoinSelectionStrategy> {
+		vec![
+			CoinSelectionStrategy::Smallest,
+			CoinSelectionStrategy::All,
+			CoinSelectionStrategy::BranchAndBound,
+			CoinSelectionStrategy::LargestFirst,
+			CoinSelectionStrategy::OldestFirst,
+			CoinSelectionStrategy::Privacy,
+		]
+	}
+
+	/// Strategy that matches the legacy `selection_strategy_is_use_all` flag
+	pub fn from_use_all(use_all: bool) -> CoinSelectionStrategy {
+		if use_all {
+			CoinSelectionStrategy::All
+		} else {
+			CoinSelectionStrategy::Smallest
+		}
+	}
+
+	/// Value for the legacy `selection_strategy_is_use_all` flag
+	pub fn is_use_all(&self) -> bool {
+		*self == CoinSelectionStrategy::All
+	}
+}
+
+impl fmt::Display for CoinSelectionStrategy {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			CoinSelectionStrategy::All => write!(f, "all"),
+			CoinSelectionStrategy::Smallest => write!(f, "smallest"),
+			CoinSelectionStrategy::BranchAndBound => write!(f, "branch_and_bound"),
+			CoinSelectionStrategy::LargestFirst => write!(f, "largest_first"),
+			CoinSelectionStrategy::OldestFirst => write!(f, "oldest_first"),
+			CoinSelectionStrategy::Privacy => write!(f, "privacy"),
+		}
+	}
+}
+
+impl TryFrom<&str> for CoinSelectionStrategy {
+	type Error = ErrorKind;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value.to_lowercase().replace("-", "_").as_str() {
+			"all" => Ok(CoinSelectionStrategy::All),
+			"smallest" => Ok(CoinSelectionStrategy::Smallest),
+			"branch_and_bound" | "bnb" => Ok(CoinSelectionStrategy::BranchAndBound),
+			"largest_first" | "largest" => Ok(CoinSelectionStrategy::LargestFirst),
+			"oldest_first" | "oldest" => Ok(CoinSelectionStrategy::OldestFirst),
+			"privacy" => Ok(CoinSelectionStrategy::Privacy),
+			_ => Err(ErrorKind::GenericError(format!(
+				"Unknown coin selection strategy '{}'",
+				value
+			))),
+		}
+	}
+}
+
+/// Output format of the transaction log export
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxExportFormat {
+	/// Comma separated values, the first line is a header
+	Csv,
+	/// JSON array of records
+	Json,
+}
+
+impl Default for TxExportFormat {
+	fn default() -> Self {
+		TxExportFormat::Csv
+	}
+}
+
+impl TryFrom<&str> for TxExportFormat {
+	type Error = ErrorKind;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value.to_lowercase().as_str() {
+			"csv" => Ok(TxExportFormat::Csv),
+			"json" => Ok(TxExportFormat::Json),
+			_ => Err(ErrorKind::GenericError(format!(
+				"Unknown export format '{}'",
+				value
+			))),
+		}
+	}
+}
+
+/// Transaction log export parameters. All filters are optional, an empty
+/// set of filters exports the whole transaction log
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct TxExportArgs {
+	/// Account name to export. If None, transactions from all accounts are exported
+	#[serde(default)]
+	pub account: Option<String>,
+	/// Export only transactions created at or after this time
+	#[serde(default)]
+	pub start_date: Option<DateTime<Utc>>,
+	/// Export only transactions created at or before this time
+	#[serde(default)]
+	pub end_date: Option<DateTime<Utc>>,
+	/// Export only transactions confirmed at or above this height.
+	/// Unconfirmed transactions are skipped when a height filter is set
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub start_height: Option<u64>,
+	/// Export only transactions confirmed at or below this height
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub end_height: Option<u64>,
+	/// Output format
+	#[serde(default)]
+	pub format: TxExportFormat,
+}
+
+impl TxExportArgs {
+	/// Check if the transaction log entry passes the date and height filters
+	pub fn matches(&self, tx: &TxLogEntry) -> bool {
+		if self.start_date.map_or(false, |d| tx.creation_ts < d)
+			|| self.end_date.map_or(false, |d| tx.creation_ts > d)
+		{
+			return false;
+		}
+		if self.start_height.is_some() || self.end_height.is_some() {
+			if !tx.confirmed {
+				return false;
+			}
+			if self.start_height.map_or(false, |h| tx.output_height < h)
+				|| self.end_height.map_or(false, |h| tx.output_height > h)
+			{
+				return false;
+			}
+		}
+		true
+	}
+}
+
+/// Field to sort the transaction log query results by
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TxQuerySortField {
+	/// Local transaction log id
+	Id,
+	/// Time the transaction was created
+	CreationTime,
+	/// Time the transaction was confirmed. Unconfirmed transactions are treated as the newest
+	ConfirmationTime,
+	/// Height of the confirmed outputs. Unconfirmed transactions are treated as the newest
+	Height,
+	/// Net amount of the transaction, see [`TxQueryArgs::amount`](struct.TxQueryArgs.html#method.amount)
+	Amount,
+}
+
+impl Default for TxQuerySortField {
+	fn default() -> Self {
+		TxQuerySortField::CreationTime
+	}
+}
+
+impl TryFrom<&str> for TxQuerySortField {
+	type Error = ErrorKind;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value.to_lowercase().as_str() {
+			"id" => Ok(TxQuerySortField::Id),
+			"creation_time" | "created" => Ok(TxQuerySortField::CreationTime),
+			"confirmation_time" | "confirmed" => Ok(TxQuerySortField::ConfirmationTime),
+			"height" => Ok(TxQuerySortField::Height),
+			"amount" => Ok(TxQuerySortField::Amount),
+			_ => Err(ErrorKind::GenericError(format!(
+				"Unknown sort field '{}'",
+				value
+			))),
+		}
+	}
+}
+
+/// Sort order of the transaction log query results
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxQuerySortOrder {
+	/// Ascending
+	Asc,
+	/// Descending
+	Desc,
+}
+
+impl Default for TxQuerySortOrder {
+	fn default() -> Self {
+		TxQuerySortOrder::Asc
+	}
+}
+
+/// Transaction log query parameters. All filters are optional, an empty query returns
+/// the same transactions as `retrieve_txs`, ordered by the creation time
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct TxQueryArgs {
+	/// Return only transactions of these types
+	#[serde(default)]
+	pub tx_types: Option<Vec<TxLogEntryType>>,
+	/// Return only confirmed (true) or only unconfirmed (false) transactions
+	#[serde(default)]
+	pub confirmed: Option<bool>,
+	/// Return only cancelled (true) or only not cancelled (false) transactions
+	#[serde(default)]
+	pub cancelled: Option<bool>,
+	/// Return only transactions created at or after this time
+	#[serde(default)]
+	pub start_date: Option<DateTime<Utc>>,
+	/// Return only transactions created at or before this time
+	#[serde(default)]
+	pub end_date: Option<DateTime<Utc>>,
+	/// Return only transactions confirmed at or above this height.
+	/// Unconfirmed transactions are skipped when a height filter is set
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub start_height: Option<u64>,
+	/// Return only transactions confirmed at or below this height
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub end_height: Option<u64>,
+	/// Minimum net amount of the transaction in nanoMWC
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub min_amount: Option<u64>,
+	/// Maximum net amount of the transaction in nanoMWC
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub max_amount: Option<u64>,
+	/// Return only transactions with the counterparty address containing this string,
+	/// case insensitive
+	#[serde(default)]
+	pub address: Option<String>,
+	/// Field to sort by
+	#[serde(default)]
+	pub sort_field: TxQuerySortField,
+	/// Sort order
+	#[serde(default)]
+	pub sort_order: TxQuerySortOrder,
+	/// Number of the matching transactions to skip
+	#[serde(default)]
+	pub offset: Option<u32>,
+	/// Max number of transactions to return
+	#[serde(default)]
+	pub limit: Option<u32>,
+}
+
+impl TxQueryArgs {
+	/// Net amount of the transaction, the absolute value of the wallet balance change.
+	/// For sent transactions it includes the fee.
+	pub fn amount(tx: &TxLogEntry) -> u64 {
+		if tx.amount_credited > tx.amount_debited {
+			tx.amount_credited - tx.amount_debited
+		} else {
+			tx.amount_debited - tx.amount_credited
+		}
+	}
+
+	/// Check if the transaction log entry passes all filters of the query
+	pub fn matches(&self, tx: &TxLogEntry) -> bool {
+		if let Some(types) = &self.tx_types {
+			if !types.contains(&tx.tx_type) {
+				return false;
+			}
+		}
+		if self.confirmed.map_or(false, |c| tx.confirmed != c) {
+			return false;
+		}
+		let cancelled = tx.tx_type == TxLogEntryType::TxReceivedCancelled
+			|| tx.tx_type == TxLogEntryType::TxSentCancelled;
+		if self.cancelled.map_or(false, |c| cancelled != c) {
+			return false;
+		}
+		if self.start_date.map_or(false, |d| tx.creation_ts < d)
+			|| self.end_date.map_or(false, |d| tx.creation_ts > d)
+		{
+			return false;
+		}
+		if self.start_height.is_some() || self.end_height.is_some() {
+			if !tx.confirmed {
+				return false;
+			}
+			if self.start_height.map_or(false, |h| tx.output_height < h)
+				|| self.end_height.map_or(false, |h| tx.output_height > h)
+			{
+				return false;
+			}
+		}
+		let amount = Self::amount(tx);
+		if self.min_amount.map_or(false, |a| amount < a)
+			|| self.max_amount.map_or(false, |a| amount > a)
+		{
+			return false;
+		}
+		if let Some(address) = &self.address {
+			let address = address.to_lowercase();
+			match &tx.address {
+				Some(a) if a.to_lowercase().contains(&address) => (),
+				_ => return false,
+			}
+		}
+		true
+	}
+
+	/// Sort the transactions and apply the offset and the limit
+	pub fn sort_and_paginate(&self, txs: &mut Vec<TxLogEntry>) {
+		match self.sort_field {
+			TxQuerySortField::Id => txs.sort_by_key(|tx| tx.id),
+			TxQuerySortField::CreationTime => txs.sort_by_key(|tx| tx.creation_ts),
+			TxQuerySortField::ConfirmationTime => {
+				txs.sort_by_key(|tx| (tx.confirmation_ts.is_none(), tx.confirmation_ts))
+			}
+			TxQuerySortField::Height => txs.sort_by_key(|tx| (!tx.confirmed, tx.output_height)),
+			TxQuerySortField::Amount => txs.sort_by_key(|tx| Self::amount(tx)),
+		}
+		if self.sort_order == TxQuerySortOrder::Desc {
+			txs.reverse();
+		}
+		let offset = self.offset.unwrap_or(0) as usize;
+		if offset > 0 {
+			txs.drain(..offset.min(txs.len()));
+		}
+		if let Some(limit) = self.limit {
+			txs.truncate(limit as usize);
+		}
+	}
+}
+
+/// Single record of the transaction log export. Amounts are in nanoMWC
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TxExportRecord {
+	/// Account label
+	pub account: String,
+	/// Local transaction log id
+	pub id: u32,
+	/// Slate transaction id
+	pub tx_slate_id: Option<String>,
+	/// Transaction type
+	pub tx_type: TxLogEntryType,
+	/// Time this tx entry was created
+	pub creation_ts: DateTime<Utc>,
+	/// Whether the transaction is confirmed
+	pub confirmed: bool,
+	/// Time this tx was confirmed
+	pub confirmation_ts: Option<DateTime<Utc>>,
+	/// Height of the confirmed outputs
+	pub height: Option<u64>,
+	/// Amount credited via this transaction
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount_credited: u64,
+	/// Amount debited via this transaction
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount_debited: u64,
+	/// Fee
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	pub fee: Option<u64>,
+	/// Address of the other party
+	pub address: Option<String>,
+	/// Kernel excess, hex encoded
+	pub kernel_excess: Option<String>,
+	/// Payment proof status: 'none', 'pending' (receiver signature is missing) or 'signed'
+	pub payment_proof: String,
+}
+
+impl TxExportRecord {
+	/// Build the export record from the transaction log entry
+	pub fn from_tx_log_entry(tx: &TxLogEntry, account: &str) -> Self {
+		let payment_proof = match &tx.payment_proof {
+			None => "none",
+			Some(p) if p.receiver_signature.is_none() => "pending",
+			Some(_) => "signed",
+		};
+		TxExportRecord {
+			account: account.to_string(),
+			id: tx.id,
+			tx_slate_id: tx.tx_slate_id.map(|id| id.to_string()),
+			tx_type: tx.tx_type.clone(),
+			creation_ts: tx.creation_ts,
+			confirmed: tx.confirmed,
+			confirmation_ts: tx.confirmation_ts,
+			height: if tx.confirmed {
+				Some(tx.output_height)
+			} else {
+				None
+			},
+			amount_credited: tx.amount_credited,
+			amount_debited: tx.amount_debited,
+			fee: tx.fee,
+			address: tx.address.clone(),
+			kernel_excess: tx.kernel_excess.map(|e| crate::grin_util::to_hex(&e.0)),
+			payment_proof: payment_proof.to_string(),
+		}
+	}
+
+	/// CSV header, matches the columns of `to_csv_row`
+	pub fn csv_header() -> String {
+		"account,id,tx_slate_id,tx_type,creation_ts,confirmed,confirmation_ts,height,\
+		 amount_credited,amount_debited,fee,address,kernel_excess,payment_proof"
+			.to_string()
+	}
+
+	/// Format the record as a single CSV line
+	pub fn to_csv_row(&self) -> String {
+		let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+		let fields = [
+			self.account.clone(),
+			self.id.to_string(),
+			opt(&self.tx_slate_id),
+			format!("{:?}", self.tx_type),
+			self.creation_ts.to_rfc3339(),
+			self.confirmed.to_string(),
+			opt(&self.confirmation_ts.map(|t| t.to_rfc3339())),
+			opt(&self.height.map(|h| h.to_string())),
+			self.amount_credited.to_string(),
+			self.amount_debited.to_string(),
+			opt(&self.fee.map(|f| f.to_string())),
+			opt(&self.address),
+			opt(&self.kernel_excess),
+			self.payment_proof.clone(),
+		];
+		fields
+			.iter()
+			.map(|f| csv_escape(f))
+			.collect::<Vec<String>>()
+			.join(",")
+	}
+}
+
+/// Quote CSV field if it contains a separator, quote or line break
+fn csv_escape(field: &str) -> String {
+	if field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+		format!("\"{}\"", field.replace("\"", "\"\""))
+	} else {
+		field.to_string()
+	}
 }
 
 impl InitTxSendArgs {
@@ -220,6 +717,194 @@ impl InitTxSendArgs {
 	}
 }
 
+/// Recipient of a batch payout, see
+/// [`init_batch_send_tx`](../grin_wallet_api/owner/struct.Owner.html#method.init_batch_send_tx)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BatchPayout {
+	/// The amount to pay to the recipient in nanogrins. (`1 G = 1_000_000_000nG`)
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Recipient address, recorded in the transaction log
+	#[serde(default)]
+	pub address: Option<String>,
+}
+
+/// Fee estimate of a send transaction, see
+/// [`estimate_fee`](../grin_wallet_api/owner/struct.Owner.html#method.estimate_fee)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FeeEstimate {
+	/// The amount to send in nanogrins. (`1 G = 1_000_000_000nG`)
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Transaction fee
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub fee: u64,
+	/// Total value of the inputs
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total: u64,
+	/// Change that returns to the wallet
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub change: u64,
+	/// Number of change outputs that will be created
+	pub num_change_outputs: usize,
+	/// Transaction weight. The fee is the weight multiplied by the base fee
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub weight: u64,
+	/// Outputs that will be spent by the transaction
+	pub inputs: Vec<OutputData>,
+	/// Maximum amount that can be sent with the same arguments. The largest outputs
+	/// (up to max_outputs) are spent and no change output is created.
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub max_amount: u64,
+}
+
+/// Arguments for merging the smallest outputs into one, see
+/// [`consolidate_outputs`](../grin_wallet_api/owner/struct.Owner.html#method.consolidate_outputs)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ConsolidateArgs {
+	/// Account to consolidate. If None, the active account is used
+	#[serde(default)]
+	pub src_acct_name: Option<String>,
+	/// The minimum number of confirmations an output should have to be merged
+	#[serde(with = "secp_ser::string_or_u64")]
+	#[serde(default = "ConsolidateArgs::default_minimum_confirmations")]
+	pub minimum_confirmations: u64,
+	/// Number of the smallest outputs to merge. If None, all spendable outputs are merged
+	#[serde(default)]
+	pub num_outputs: Option<usize>,
+	/// Maximum number of inputs in a single transaction. Larger sets are merged by several
+	/// transactions, one output per transaction. Also limited by the block weight.
+	#[serde(default = "ConsolidateArgs::default_max_inputs")]
+	pub max_inputs: usize,
+	/// Whether to use dandelion when posting. If false, skip the dandelion relay
+	#[serde(default)]
+	pub fluff: bool,
+}
+
+impl Default for ConsolidateArgs {
+	fn default() -> ConsolidateArgs {
+		ConsolidateArgs {
+			src_acct_name: None,
+			minimum_confirmations: ConsolidateArgs::default_minimum_confirmations(),
+			num_outputs: None,
+			max_inputs: ConsolidateArgs::default_max_inputs(),
+			fluff: false,
+		}
+	}
+}
+
+impl ConsolidateArgs {
+	fn default_minimum_confirmations() -> u64 {
+		10
+	}
+	fn default_max_inputs() -> usize {
+		500
+	}
+}
+
+/// Arguments for splitting an output into several outputs, see
+/// [`split_output`](../grin_wallet_api/owner/struct.Owner.html#method.split_output)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SplitArgs {
+	/// Account of the output. If None, the active account is used
+	#[serde(default)]
+	pub src_acct_name: Option<String>,
+	/// The minimum number of confirmations the output should have
+	#[serde(with = "secp_ser::string_or_u64")]
+	#[serde(default = "ConsolidateArgs::default_minimum_confirmations")]
+	pub minimum_confirmations: u64,
+	/// Commitment of the output to split
+	pub output: String,
+	/// Amounts of the new outputs in nano MWC. The rest of the output value, minus the fee,
+	/// goes to a change output.
+	pub amounts: Vec<u64>,
+	/// Whether to use dandelion when posting. If false, skip the dandelion relay
+	#[serde(default)]
+	pub fluff: bool,
+}
+
+/// Arguments for a scheduled payment, see
+/// [`schedule_payment`](../grin_wallet_api/owner/struct.Owner.html#method.schedule_payment)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SchedulePaymentArgs {
+	/// Account to send from. If None, the active account is used
+	#[serde(default)]
+	pub src_acct_name: Option<String>,
+	/// Amount to send in nano MWC
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// The minimum number of confirmations an output should have to be spent
+	#[serde(with = "secp_ser::string_or_u64")]
+	#[serde(default = "ConsolidateArgs::default_minimum_confirmations")]
+	pub minimum_confirmations: u64,
+	/// Transaction message
+	#[serde(default)]
+	pub message: Option<String>,
+	/// Send method, 'http' or 'mwcmqs'
+	pub method: String,
+	/// Destination address
+	pub dest: String,
+	/// Receiver wallet api secret, applicable to http only
+	#[serde(default)]
+	pub apisecret: Option<String>,
+	/// Whether to use dandelion when posting. If false, skip the dandelion relay
+	#[serde(default)]
+	pub fluff: bool,
+	/// Time of the first payment. If None, the payment is sent at the next wallet update
+	#[serde(default)]
+	pub start_time: Option<DateTime<Utc>>,
+	/// Interval between the payments in seconds. If None, the payment is sent once
+	#[serde(default)]
+	pub interval_secs: Option<u64>,
+	/// Number of retries of a failed send before the payment is given up
+	#[serde(default = "SchedulePaymentArgs::default_max_retries")]
+	pub max_retries: u32,
+}
+
+impl SchedulePaymentArgs {
+	fn default_max_retries() -> u32 {
+		5
+	}
+}
+
+/// Arguments for a payment request, see
+/// [`create_payment_request`](../grin_wallet_api/owner/struct.Owner.html#method.create_payment_request)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PaymentRequestArgs {
+	/// Requested amount in nano MWC
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Address of this wallet to send the payment to: http(s) url, Tor or MQS address
+	pub address: String,
+	/// Payment description
+	#[serde(default)]
+	pub memo: Option<String>,
+	/// Number of blocks from the current height while the request can be paid. No limit if None
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub expiry_blocks: Option<u64>,
+	/// Time until the request can be paid. No limit if None
+	#[serde(default)]
+	pub expiry_time: Option<DateTime<Utc>>,
+	/// Whether the payer must request a payment proof
+	#[serde(default)]
+	pub require_proof: bool,
+}
+
+/// Self send transaction that consolidates or splits the outputs
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UtxoTx {
+	/// Outputs that are spent
+	pub inputs: Vec<OutputData>,
+	/// Amounts of the created outputs, including change
+	pub outputs: Vec<u64>,
+	/// Transaction fee
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub fee: u64,
+	/// Slate id of the transaction. None for a preview
+	pub tx_slate_id: Option<Uuid>,
+}
+
 /// V2 Issue Invoice Tx Args
 #[derive(Clone, Serialize, Deserialize)]
 pub struct IssueInvoiceTxArgs {
@@ -245,6 +930,10 @@ pub struct IssueInvoiceTxArgs {
 	/// Slatepack recipient. If defined will send as a slatepack. Otherwise as not encrypted. Will be ignored for MQS
 	/// ProvableAddress has to be tor (DalekPublicKey) address
 	pub slatepack_recipient: Option<ProvableAddress>,
+	/// Payment request that the invoice is issued for. The request id is used as the slate id,
+	/// the amount and the message are taken from the request if not specified.
+	#[serde(default)]
+	pub payment_request_id: Option<Uuid>,
 }
 
 impl Default for IssueInvoiceTxArgs {
@@ -256,6 +945,7 @@ impl Default for IssueInvoiceTxArgs {
 			target_slate_version: None,
 			address: None,
 			slatepack_recipient: None,
+			payment_request_id: None,
 		}
 	}
 }
@@ -291,6 +981,9 @@ pub struct OutputCommitMapping {
 		deserialize_with = "secp_ser::commitment_from_hex"
 	)]
 	pub commit: pedersen::Commitment,
+	/// User defined label, note and frozen flag, if any were set
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub metadata: Option<OutputMetadata>,
 }
 
 /// Node height result
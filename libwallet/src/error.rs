@@ -20,6 +20,7 @@ use crate::grin_keychain;
 use crate::grin_store;
 use crate::grin_util::secp;
 use crate::swap::error::ErrorKind as SwapErrorKind;
+use crate::swap::multisig::ErrorKind as MultisigErrorKind;
 use crate::util;
 use failure::{Backtrace, Context, Fail};
 use std::env;
@@ -206,6 +207,10 @@ pub enum ErrorKind {
 	#[fail(display = "Unknown Account Label '{}'", _0)]
 	UnknownAccountLabel(String),
 
+	/// Reference output commitment that doesn't belong to the wallet
+	#[fail(display = "Unknown output commitment '{}'", _0)]
+	UnknownOutput(String),
+
 	/// Error from summing commitments via committed trait.
 	#[fail(display = "Committed Error, {}", _0)]
 	Committed(committed::Error),
@@ -351,6 +356,18 @@ pub enum ErrorKind {
 	/// Slatepack Encoding Error
 	#[fail(display = "Slatepack encode error, {}", _0)]
 	SlatepackEncodeError(String),
+
+	/// Multisig account error
+	#[fail(display = "Multisig error, {}", _0)]
+	MultisigError(String),
+
+	/// Watch-only wallet doesn't have the keys
+	#[fail(display = "Watch-only wallet can't sign or spend")]
+	WatchOnlyWallet,
+
+	/// View key error
+	#[fail(display = "View key error, {}", _0)]
+	ViewKeyError(String),
 }
 
 impl Display for Error {
@@ -481,3 +498,9 @@ impl From<SwapErrorKind> for Error {
 		Error::from(ErrorKind::SwapError(format!("{}", error)))
 	}
 }
+
+impl From<MultisigErrorKind> for Error {
+	fn from(error: MultisigErrorKind) -> Error {
+		Error::from(ErrorKind::MultisigError(format!("{}", error)))
+	}
+}
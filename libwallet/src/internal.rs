@@ -22,6 +22,7 @@
 #![warn(missing_docs)]
 
 pub mod keys;
+pub mod multisig;
 pub mod scan;
 pub mod selection;
 pub mod tx;
@@ -0,0 +1,504 @@
+// Copyright 2020 The MWC Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multisig account internals. The shared output blinding factor is the sum of the
+//! co-signer secrets. For threshold accounts every co-signer splits its secret into
+//! verifiable shares (Feldman VSS), so any `threshold` co-signers can sign the spending.
+
+use crate::blake2::blake2b::blake2b;
+use crate::error::{Error, ErrorKind};
+use crate::grin_core::libtx::aggsig;
+use crate::grin_keychain::{BlindSum, BlindingFactor, Keychain};
+use crate::grin_util::secp::key::{PublicKey, SecretKey};
+use crate::grin_util::secp::pedersen::Commitment;
+use crate::grin_util::secp::{self, Secp256k1};
+use crate::grin_util::{from_hex, to_hex};
+use crate::slate::Slate;
+use crate::swap::multisig::Builder;
+use crate::types::{MultisigAccount, MultisigLocal, MultisigParticipant};
+use uuid::Uuid;
+
+/// Maximum number of co-signers of the multisig account
+pub const MAX_PARTICIPANTS: usize = 10;
+
+/// secp256k1 group order minus 2, the exponent of the modular inverse
+const ORDER_MINUS_2: [u8; 32] = [
+	0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+	0xBA, 0xAE, 0xDC, 0xE6, 0xAF, 0x48, 0xA0, 0x3B, 0xBF, 0xD2, 0x5E, 0x8C, 0xD0, 0x36, 0x41, 0x3F,
+];
+
+/// Shared output builder with the data of every joined co-signer. Returns the builder
+/// and the flags of the bulletproof rounds that every co-signer completed.
+pub fn shared_output(
+	account: &MultisigAccount,
+	index: usize,
+	secret: &SecretKey,
+) -> Result<(Builder, Vec<bool>, Vec<bool>), Error> {
+	let mut nonce_data = account.id.as_bytes().to_vec();
+	nonce_data.extend_from_slice(b"bulletproof");
+	let mut builder = Builder::new(
+		account.num_participants,
+		account.amount,
+		false,
+		index,
+		hash_key(secret, &nonce_data)?,
+		Some(account.common_nonce.clone()),
+	);
+	for (i, p) in account.participants.iter().enumerate() {
+		builder.import_participant(i, &p.bulletproof)?;
+	}
+
+	let mut round_1 = vec![false; account.num_participants];
+	let mut round_2 = vec![false; account.num_participants];
+	if account.participants.len() == account.num_participants {
+		for (i, p) in account.participants.iter().enumerate() {
+			round_1[i] = builder.round_1_participant(i, &p.bulletproof).is_ok();
+			round_2[i] = builder.round_2_participant(i, &p.bulletproof).is_ok();
+		}
+	}
+	Ok((builder, round_1, round_2))
+}
+
+/// New co-signer data. The key proof prevents a co-signer from choosing its key
+/// based on the keys of the others.
+pub fn new_participant(
+	secp: &Secp256k1,
+	account: &MultisigAccount,
+	local: &MultisigLocal,
+	secret: &SecretKey,
+) -> Result<MultisigParticipant, Error> {
+	if local.index != account.participants.len() {
+		return Err(ErrorKind::MultisigError(format!(
+			"Co-signer {} can't join account {}, {} co-signers joined",
+			local.index,
+			account.id,
+			account.participants.len()
+		))
+		.into());
+	}
+	let public_key = PublicKey::from_secret_key(secp, secret)?;
+	let msg = key_proof_msg(&account.id, &public_key)?;
+	let key_proof = aggsig::sign_single(secp, &msg, secret, None, Some(&public_key))?;
+
+	let (mut builder, _, _) = shared_output(account, local.index, secret)?;
+	builder.create_participant(secp, secret)?;
+
+	Ok(MultisigParticipant {
+		public_key,
+		key_proof,
+		bulletproof: builder.export()?,
+		commitments: vec![],
+		shares: vec![],
+	})
+}
+
+/// Check the key proofs and that the shared output is built from the co-signer keys
+pub fn verify_participants(secp: &Secp256k1, account: &MultisigAccount) -> Result<(), Error> {
+	for (i, p) in account.participants.iter().enumerate() {
+		let msg = key_proof_msg(&account.id, &p.public_key)?;
+		if !aggsig::verify_single(
+			secp,
+			&p.key_proof,
+			&msg,
+			None,
+			&p.public_key,
+			Some(&p.public_key),
+			false,
+		) {
+			return Err(
+				ErrorKind::MultisigError(format!("Invalid key proof of co-signer {}", i)).into(),
+			);
+		}
+		if p.bulletproof.partial_commitment != Some(Commitment::from_pubkey(&p.public_key)?) {
+			return Err(ErrorKind::MultisigError(format!(
+				"Shared output commitment of co-signer {} doesn't match its key",
+				i
+			))
+			.into());
+		}
+	}
+	Ok(())
+}
+
+/// Update the stored account with the data from the other co-signers. Data of this wallet
+/// co-signer is always taken from the stored account, so an outdated copy can't roll it back.
+pub fn merge(stored: &MultisigAccount, update: &MultisigAccount) -> Result<MultisigAccount, Error> {
+	let local = stored
+		.local
+		.clone()
+		.ok_or(ErrorKind::MultisigError(format!(
+			"Account {} doesn't have the wallet co-signer",
+			stored.id
+		)))?;
+	if stored.id != update.id
+		|| stored.num_participants != update.num_participants
+		|| stored.threshold != update.threshold
+		|| stored.amount != update.amount
+		|| stored.contributions != update.contributions
+		|| stored.common_nonce != update.common_nonce
+		|| stored.funding.id != update.funding.id
+		|| stored.participants.len() > update.participants.len()
+	{
+		return Err(ErrorKind::MultisigError(format!(
+			"Account {} doesn't match the stored one",
+			stored.id
+		))
+		.into());
+	}
+	for (i, p) in stored.participants.iter().enumerate() {
+		if update.participants[i].public_key != p.public_key {
+			return Err(
+				ErrorKind::MultisigError(format!("Key of co-signer {} was changed", i)).into(),
+			);
+		}
+	}
+
+	let mut account = update.clone();
+	account.participants[local.index] = stored.participants[local.index].clone();
+	merge_slate(&stored.funding, &mut account.funding, local.index);
+	account.commit = account.commit.or(stored.commit.clone());
+	account.status = std::cmp::max(stored.status, update.status);
+	account.spend = match (&stored.spend, &update.spend) {
+		(None, _) => update.spend.clone(),
+		(Some(s), None) => Some(s.clone()),
+		(Some(s), Some(u)) => {
+			if s.slate.id != u.slate.id {
+				return Err(ErrorKind::MultisigError(format!(
+					"Another spending of account {} is in progress",
+					stored.id
+				))
+				.into());
+			}
+			let mut spend = u.clone();
+			spend.address = s.address.clone();
+			if let Some(pos) = s.signers.iter().position(|i| *i == local.index) {
+				merge_slate(&s.slate, &mut spend.slate, s.participant_id(pos));
+			}
+			Some(spend)
+		}
+	};
+	account.local = Some(local);
+	Ok(account)
+}
+
+/// Keep our slate participant data, the update might not have it yet
+fn merge_slate(stored: &Slate, update: &mut Slate, participant_id: usize) {
+	if let Some(ours) = stored.participant_with_id(participant_id) {
+		match update
+			.participant_data
+			.iter_mut()
+			.find(|p| p.id == participant_id as u64)
+		{
+			Some(p) => {
+				if p.part_sig.is_none() {
+					*p = ours;
+				}
+			}
+			None => update.participant_data.push(ours),
+		}
+	}
+}
+
+/// Issue the secret shares for the other co-signers. Share of the co-signer `j` is the value
+/// of our polynomial at `j + 1`, the polynomial at zero is our secret.
+pub fn issue_shares(
+	secp: &Secp256k1,
+	account: &mut MultisigAccount,
+	index: usize,
+	secret: &SecretKey,
+) -> Result<(), Error> {
+	let coefficients = polynomial(secret, &account.id, account.threshold)?;
+	let mut commitments = vec![];
+	for c in &coefficients {
+		let pk = PublicKey::from_secret_key(secp, c)?;
+		commitments.push(to_hex(&pk.serialize_vec(true)[..]));
+	}
+	let mut shares = vec![];
+	for (j, p) in account.participants.iter().enumerate() {
+		if j == index {
+			shares.push(None);
+			continue;
+		}
+		let share = evaluate(&coefficients, j as u64 + 1)?;
+		let key = share_key(secp, secret, &p.public_key, &account.id, index, j)?;
+		shares.push(Some(to_hex(&xor(&share.0, &key))));
+	}
+	account.participants[index].commitments = commitments;
+	account.participants[index].shares = shares;
+	Ok(())
+}
+
+/// True if every other co-signer issued a valid share for us. Invalid share is an error,
+/// the shared output wouldn't be spendable with it.
+pub fn shares_ready(
+	secp: &Secp256k1,
+	account: &MultisigAccount,
+	index: usize,
+	secret: &SecretKey,
+) -> Result<bool, Error> {
+	if account.threshold == account.num_participants {
+		return Ok(true);
+	}
+	for (i, p) in account.participants.iter().enumerate() {
+		if i == index {
+			continue;
+		}
+		if p.commitments.is_empty() {
+			return Ok(false);
+		}
+		received_share(secp, account, i, index, secret)?;
+	}
+	Ok(true)
+}
+
+/// Key of the co-signer for signing the spending transaction. Keys of the signers add up
+/// to the shared output blinding factor.
+pub fn signer_key<K: Keychain>(
+	keychain: &K,
+	account: &MultisigAccount,
+	index: usize,
+	secret: &SecretKey,
+	signers: &[usize],
+) -> Result<SecretKey, Error> {
+	if account.threshold == account.num_participants {
+		return Ok(secret.clone());
+	}
+
+	let secp = keychain.secp();
+	let mut share = evaluate(
+		&polynomial(secret, &account.id, account.threshold)?,
+		index as u64 + 1,
+	)?;
+	for i in 0..account.participants.len() {
+		if i != index {
+			share.add_assign(&received_share(secp, account, i, index, secret)?)?;
+		}
+	}
+
+	lagrange(keychain, share, signers, index)
+}
+
+/// Share multiplied by its Lagrange coefficient at zero. Shares of the signers
+/// add up to the secret after that.
+fn lagrange<K: Keychain>(
+	keychain: &K,
+	mut share: SecretKey,
+	signers: &[usize],
+	index: usize,
+) -> Result<SecretKey, Error> {
+	let mut numerator: u64 = 1;
+	let mut denominator: i64 = 1;
+	for s in signers.iter().filter(|s| **s != index) {
+		numerator *= *s as u64 + 1;
+		denominator *= *s as i64 - index as i64;
+	}
+	let mut coefficient = scalar(numerator)?;
+	coefficient.mul_assign(&inverse(&scalar(denominator.abs() as u64)?)?)?;
+	share.mul_assign(&coefficient)?;
+	if denominator < 0 {
+		share = negate(keychain, &share)?;
+	}
+	Ok(share)
+}
+
+/// Negated key, `-k mod n`
+pub fn negate<K: Keychain>(keychain: &K, key: &SecretKey) -> Result<SecretKey, Error> {
+	let sum = keychain.blind_sum(
+		&BlindSum::new().sub_blinding_factor(BlindingFactor::from_secret_key(key.clone())),
+	)?;
+	Ok(sum.secret_key()?)
+}
+
+/// Share that the co-signer `from` issued for the co-signer `to`, checked against the
+/// polynomial commitments
+fn received_share(
+	secp: &Secp256k1,
+	account: &MultisigAccount,
+	from: usize,
+	to: usize,
+	secret: &SecretKey,
+) -> Result<SecretKey, Error> {
+	let invalid_share = || -> Error {
+		ErrorKind::MultisigError(format!(
+			"Co-signer {} didn't issue a valid secret share for co-signer {}",
+			from, to
+		))
+		.into()
+	};
+
+	let p = &account.participants[from];
+	let encrypted = p
+		.shares
+		.get(to)
+		.cloned()
+		.flatten()
+		.ok_or_else(invalid_share)?;
+	let encrypted = from_hex(&encrypted).map_err(|_| invalid_share())?;
+	let key = share_key(secp, secret, &p.public_key, &account.id, from, to)?;
+	if encrypted.len() != key.len() {
+		return Err(invalid_share());
+	}
+	let share = SecretKey::from_slice(&xor(&encrypted, &key)).map_err(|_| invalid_share())?;
+
+	let mut commitments = vec![];
+	for c in &p.commitments {
+		let c = from_hex(c).map_err(|_| invalid_share())?;
+		commitments.push(PublicKey::from_slice(&c).map_err(|_| invalid_share())?);
+	}
+	if commitments.len() != account.threshold || commitments[0] != p.public_key {
+		return Err(invalid_share());
+	}
+
+	// share * G has to match the commited polynomial at `to + 1`
+	let x = to as u64 + 1;
+	let mut power: u64 = 1;
+	let mut terms = vec![];
+	for c in commitments {
+		let mut term = c;
+		if power > 1 {
+			term.mul_assign(secp, &scalar(power)?)?;
+		}
+		terms.push(term);
+		power *= x;
+	}
+	let expected = PublicKey::from_combination(terms.iter().collect())?;
+	if expected != PublicKey::from_secret_key(secp, &share)? {
+		return Err(invalid_share());
+	}
+	Ok(share)
+}
+
+/// Polynomial of the degree `threshold - 1`, its value at zero is the secret
+fn polynomial(secret: &SecretKey, id: &Uuid, threshold: usize) -> Result<Vec<SecretKey>, Error> {
+	let mut coefficients = vec![secret.clone()];
+	for k in 1..threshold {
+		let mut data = id.as_bytes().to_vec();
+		data.push(k as u8);
+		coefficients.push(hash_key(secret, &data)?);
+	}
+	Ok(coefficients)
+}
+
+fn evaluate(coefficients: &[SecretKey], x: u64) -> Result<SecretKey, Error> {
+	let x = scalar(x)?;
+	let mut res = coefficients[coefficients.len() - 1].clone();
+	for c in coefficients.iter().rev().skip(1) {
+		res.mul_assign(&x)?;
+		res.add_assign(c)?;
+	}
+	Ok(res)
+}
+
+/// Modular inverse, `a^(n-2) mod n`
+fn inverse(a: &SecretKey) -> Result<SecretKey, Error> {
+	let mut res = scalar(1)?;
+	for byte in ORDER_MINUS_2.iter() {
+		for bit in (0..8).rev() {
+			let square = res.clone();
+			res.mul_assign(&square)?;
+			if (byte >> bit) & 1 == 1 {
+				res.mul_assign(a)?;
+			}
+		}
+	}
+	Ok(res)
+}
+
+fn scalar(n: u64) -> Result<SecretKey, Error> {
+	let mut bytes = [0; 32];
+	bytes[24..].copy_from_slice(&n.to_be_bytes());
+	Ok(SecretKey::from_slice(&bytes)?)
+}
+
+fn hash_key(secret: &SecretKey, data: &[u8]) -> Result<SecretKey, Error> {
+	Ok(SecretKey::from_slice(
+		blake2b(32, &secret.0, data).as_bytes(),
+	)?)
+}
+
+fn key_proof_msg(id: &Uuid, public_key: &PublicKey) -> Result<secp::Message, Error> {
+	let mut data = id.as_bytes().to_vec();
+	data.extend_from_slice(&public_key.serialize_vec(true)[..]);
+	let hash = blake2b(secp::constants::MESSAGE_SIZE, &[], &data);
+	Ok(secp::Message::from_slice(hash.as_bytes())?)
+}
+
+/// Encryption key of the share, both co-signers can build it with the Diffie-Hellman
+fn share_key(
+	secp: &Secp256k1,
+	secret: &SecretKey,
+	other: &PublicKey,
+	id: &Uuid,
+	from: usize,
+	to: usize,
+) -> Result<Vec<u8>, Error> {
+	let mut point = other.clone();
+	point.mul_assign(secp, secret)?;
+	let mut data = point.serialize_vec(true)[..].to_vec();
+	data.extend_from_slice(id.as_bytes());
+	data.push(from as u8);
+	data.push(to as u8);
+	Ok(blake2b(32, &[], &data).as_bytes().to_vec())
+}
+
+fn xor(data: &[u8], key: &[u8]) -> Vec<u8> {
+	data.iter().zip(key.iter()).map(|(d, k)| d ^ k).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::grin_keychain::ExtKeychain;
+	use rand::thread_rng;
+
+	#[test]
+	fn test_inverse() {
+		let a = scalar(7).unwrap();
+		let mut res = inverse(&a).unwrap();
+		res.mul_assign(&a).unwrap();
+		assert_eq!(res, scalar(1).unwrap());
+	}
+
+	#[test]
+	fn test_threshold_keys() {
+		let keychain = ExtKeychain::from_random_seed(false).unwrap();
+		let secp = keychain.secp();
+		let id = Uuid::new_v4();
+		let secrets: Vec<SecretKey> = (0..3).map(|_| SecretKey::new(&mut thread_rng())).collect();
+		let total = secrets.iter().fold(BlindSum::new(), |sum, k| {
+			sum.add_blinding_factor(BlindingFactor::from_secret_key(k.clone()))
+		});
+		let total = keychain.blind_sum(&total).unwrap().secret_key().unwrap();
+
+		// Any two co-signers of 2-of-3 can rebuild the shared blinding factor
+		for signers in &[vec![0, 1], vec![0, 2], vec![2, 1]] {
+			let mut keys = vec![];
+			for i in signers {
+				let x = *i as u64 + 1;
+				let mut share = evaluate(&polynomial(&secrets[0], &id, 2).unwrap(), x).unwrap();
+				for s in &secrets[1..] {
+					share
+						.add_assign(&evaluate(&polynomial(s, &id, 2).unwrap(), x).unwrap())
+						.unwrap();
+				}
+				keys.push(lagrange(&keychain, share, signers, *i).unwrap());
+			}
+			keys[0].add_assign(&keys[1]).unwrap();
+			assert_eq!(
+				PublicKey::from_secret_key(secp, &keys[0]).unwrap(),
+				PublicKey::from_secret_key(secp, &total).unwrap()
+			);
+		}
+	}
+}
@@ -19,8 +19,8 @@ use crate::api_impl::owner_updater::StatusMessage;
 use crate::api_impl::types::InitTxArgs;
 use crate::grin_core::consensus::{valid_header_version, WEEK_HEIGHT};
 use crate::grin_core::core::HeaderVersion;
-use crate::grin_core::global;
-use crate::grin_core::libtx::{proof, tx_fee};
+use crate::grin_core::libtx::{proof, secp_ser, tx_fee};
+use crate::grin_core::{global, ser};
 use crate::grin_keychain::{Identifier, Keychain, SwitchCommitmentType};
 use crate::grin_util::secp::key::SecretKey;
 use crate::grin_util::secp::pedersen;
@@ -30,13 +30,15 @@ use crate::internal::keys;
 use crate::internal::tx;
 use crate::types::*;
 use crate::{wallet_lock, Error, ErrorKind};
+use crossbeam_utils::thread::scope;
 use grin_core::core::Transaction;
-use grin_util::secp::Secp256k1;
+use grin_util::secp::{ContextFlag, Secp256k1};
 use grin_wallet_util::grin_core::core::Committed;
 use grin_wallet_util::grin_util as util;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -53,10 +55,21 @@ const OUTPUT_TO_BLOCK: usize = 100;
 // of the node load. 4 is a reasonable number
 const SYNC_BLOCKS_THREADS: usize = 4;
 
+// Max number of outputs in a single request to the node
+const OUTPUTS_BATCH_SIZE: u64 = 1000;
+
+// PMMR range of the single scanning task. Every output takes about 2 PMMR positions, so
+// a task normally needs a single request to the node
+const SCAN_CHUNK_SIZE: u64 = OUTPUTS_BATCH_SIZE * 2;
+
 /// Utility struct for return values from below
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputResult {
 	///
+	#[serde(
+		serialize_with = "secp_ser::as_hex",
+		deserialize_with = "secp_ser::commitment_from_hex"
+	)]
 	pub commit: pedersen::Commitment,
 	///
 	pub key_id: Identifier,
@@ -98,6 +111,24 @@ impl OutputResult {
 	}
 }
 
+impl ser::Writeable for OutputResult {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("OutputResult to json conversion failed, {}", e))
+		})?;
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for OutputResult {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<OutputResult, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to OutputResult conversion failed, {}", e))
+		})
+	}
+}
+
 #[derive(Debug, Clone)]
 /// Collect stats in case we want to just output a single tx log entry
 /// for restored non-coinbase outputs
@@ -182,12 +213,58 @@ where
 	Ok(wallet_outputs)
 }
 
-/// Scanning chain for the outputs. Shared with mwc713
+/// Identify outputs that belong to the watch-only wallet. Only the view key is available,
+/// so the legacy (pre HF) proofs can't be rewound.
+fn identify_view_outputs(
+	view_key: &ViewKey,
+	outputs: Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+) -> Result<Vec<OutputResult>, Error> {
+	// Own context, the global instance lock would serialize the scanning threads
+	let secp = Secp256k1::with_caps(ContextFlag::Commit);
+	let mut wallet_outputs: Vec<OutputResult> = Vec::new();
+
+	for (commit, proof, is_coinbase, height, mmr_index) in outputs {
+		let (amount, key_id, switch) = match view_key.rewind(&secp, &commit, &proof)? {
+			Some(i) => i,
+			None => continue,
+		};
+
+		let lock_height = if is_coinbase {
+			height + global::coinbase_maturity()
+		} else {
+			height
+		};
+
+		debug!(
+			"View output found: {:?}, amount: {:?}, key_id: {:?}, mmr_index: {},",
+			commit, amount, key_id, mmr_index
+		);
+
+		if switch != SwitchCommitmentType::Regular {
+			warn!("Unexpected switch commitment type {:?}", switch);
+		}
+
+		wallet_outputs.push(OutputResult {
+			commit,
+			key_id: key_id.clone(),
+			n_child: key_id.to_path().last_path_index(),
+			value: amount,
+			height,
+			lock_height,
+			is_coinbase,
+			mmr_index,
+		});
+	}
+	Ok(wallet_outputs)
+}
+
+/// Scanning chain for the outputs with the given number of threads. Shared with mwc713
 pub fn collect_chain_outputs<'a, C, K>(
 	keychain: &K,
 	client: C,
 	start_index: u64,
 	end_index: Option<u64>,
+	threads: usize,
 	status_send_channel: &Option<Sender<StatusMessage>>,
 	show_progress: bool,
 ) -> Result<Vec<OutputResult>, Error>
@@ -195,38 +272,462 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	let batch_size = 1000;
-	let start_index_stat = start_index;
-	let mut start_index = start_index;
-	let mut result_vec: Vec<OutputResult> = vec![];
-	loop {
-		let (highest_index, last_retrieved_index, outputs) =
-			client.get_outputs_by_pmmr_index(start_index, end_index, batch_size)?;
+	traverse_chain_outputs(
+		client,
+		start_index,
+		end_index,
+		threads,
+		status_send_channel,
+		show_progress,
+		|outputs| identify_utxo_outputs(keychain, outputs),
+		|_, _| Ok(()),
+	)
+}
+
+// Scanning chain for the outputs with the progress checkpoints. The checkpoint of the scan must
+// be created by the caller, otherwise it is a plain scan. An interrupted scan is continued
+// from the checkpoint with the outputs that were found before.
+fn collect_chain_outputs_checkpointed<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	start_height: u64,
+	end_height: u64,
+	pmmr_range: (u64, u64),
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+) -> Result<Vec<OutputResult>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let client = wallet.w2n_client().clone();
+	let keychain = wallet.keychain(keychain_mask)?;
+	let threads = wallet.scan_config().threads;
+
+	let mut checkpoint = match wallet.get_scan_checkpoint()? {
+		Some(c) if c.is_scan_of(start_height, end_height) => c,
+		_ => {
+			return collect_chain_outputs(
+				&keychain,
+				client,
+				pmmr_range.0,
+				Some(pmmr_range.1),
+				threads,
+				status_send_channel,
+				show_progress,
+			)
+		}
+	};
+
+	let (start_index, mut chain_outs) = match checkpoint.next_pmmr_index {
+		Some(next_index) => {
+			let found = wallet.scan_checkpoint_outputs()?;
+			if let Some(ref s) = status_send_channel {
+				let _ = s.send(StatusMessage::Scanning(
+					show_progress,
+					format!(
+						"Resuming the scan from PMMR index {}, {} outputs are found before",
+						next_index,
+						found.len()
+					),
+					0,
+				));
+			}
+			(next_index, found)
+		}
+		None => (pmmr_range.0, vec![]),
+	};
 
-		let range = highest_index as f64 - start_index_stat as f64;
-		let progress = last_retrieved_index as f64 - start_index_stat as f64;
-		let perc_complete = cmp::min(((progress / range) * 100.0) as u8, 99);
+	if start_index <= pmmr_range.1 {
+		chain_outs.extend(traverse_chain_outputs(
+			client,
+			start_index,
+			Some(pmmr_range.1),
+			threads,
+			status_send_channel,
+			show_progress,
+			|outputs| identify_utxo_outputs(&keychain, outputs),
+			|last_index, found| {
+				let mut batch = wallet.batch(keychain_mask)?;
+				for output in found {
+					batch.save_scan_checkpoint_output(output)?;
+				}
+				checkpoint.next_pmmr_index = Some(last_index + 1);
+				batch.save_scan_checkpoint(&checkpoint)?;
+				batch.commit()
+			},
+		)?);
+	}
+	Ok(chain_outs)
+}
 
-		let msg = format!(
-			"Checking {} outputs, up to index {}. (Highest index: {})",
-			outputs.len(),
+// Walk the UTXO set in PMMR index order, identify function selects the outputs that belong to the wallet.
+// on_batch is called with the last scanned index and the outputs found in every batch.
+// The PMMR range is split into chunks. The chunks are requested from the node and rewound by
+// the pools of threads concurrently, the results are reported in the PMMR index order.
+fn traverse_chain_outputs<'a, C, R, F, P>(
+	client: C,
+	start_index: u64,
+	end_index: Option<u64>,
+	threads: usize,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+	identify: F,
+	mut on_batch: P,
+) -> Result<Vec<R>, Error>
+where
+	C: NodeClient + 'a,
+	R: Send,
+	F: Fn(
+			Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+		) -> Result<Vec<R>, Error>
+		+ Sync,
+	P: FnMut(u64, &[R]) -> Result<(), Error>,
+{
+	let highest_index = match end_index {
+		Some(index) => index,
+		None => client.get_outputs_by_pmmr_index(start_index, None, 1)?.0,
+	};
+	if highest_index < start_index {
+		return Ok(vec![]);
+	}
+
+	let threads = cmp::max(threads, 1);
+	let fetch_threads = cmp::min(threads, SYNC_BLOCKS_THREADS);
+
+	// Small ranges are spread between the fetch threads as well
+	let range = highest_index - start_index + 1;
+	let chunk_size = cmp::max(
+		1,
+		cmp::min(
+			SCAN_CHUNK_SIZE,
+			(range + fetch_threads as u64 - 1) / fetch_threads as u64,
+		),
+	);
+	let mut chunks: Vec<(u64, u64)> = vec![];
+	let mut chunk_start = start_index;
+	while chunk_start <= highest_index {
+		let chunk_end = cmp::min(chunk_start + chunk_size - 1, highest_index);
+		chunks.push((chunk_start, chunk_end));
+		chunk_start = chunk_end + 1;
+	}
+
+	let next_chunk = AtomicUsize::new(0);
+	let abort = AtomicBool::new(false);
+	// Bounded, so the fetched outputs are not piling up in memory if the rewinds are slower
+	let (fetched_tx, fetched_rx) = mpsc::sync_channel(threads);
+	let fetched_rx = Arc::new(Mutex::new(fetched_rx));
+	let (result_tx, result_rx) = mpsc::channel();
+
+	let res = scope(|s| {
+		for _ in 0..fetch_threads {
+			let client = client.clone();
+			let fetched_tx = fetched_tx.clone();
+			let (chunks, next_chunk, abort) = (&chunks, &next_chunk, &abort);
+			s.spawn(move |_| {
+				while !abort.load(Ordering::Relaxed) {
+					let idx = next_chunk.fetch_add(1, Ordering::Relaxed);
+					if idx >= chunks.len() {
+						break;
+					}
+					let (first, last) = chunks[idx];
+					if fetched_tx
+						.send((idx, fetch_outputs_range(&client, first, last)))
+						.is_err()
+					{
+						break;
+					}
+				}
+			});
+		}
+		drop(fetched_tx);
+
+		for _ in 0..threads {
+			let fetched_rx = fetched_rx.clone();
+			let result_tx = result_tx.clone();
+			let identify = &identify;
+			s.spawn(move |_| loop {
+				let fetched = fetched_rx.lock().recv();
+				let (idx, outputs) = match fetched {
+					Ok(f) => f,
+					Err(_) => break,
+				};
+				let found = outputs.and_then(|outputs| {
+					let outputs_num = outputs.len();
+					identify(outputs).map(|found| (outputs_num, found))
+				});
+				if result_tx.send((idx, found)).is_err() {
+					break;
+				}
+			});
+		}
+		drop(fetched_rx);
+		drop(result_tx);
+
+		let res = collect_ordered_outputs(
+			result_rx,
+			&chunks,
+			start_index,
 			highest_index,
-			last_retrieved_index,
+			status_send_channel,
+			show_progress,
+			&mut on_batch,
 		);
-		if let Some(ref s) = status_send_channel {
-			let _ = s.send(StatusMessage::Scanning(show_progress, msg, perc_complete));
+		if res.is_err() {
+			abort.store(true, Ordering::Relaxed);
 		}
+		res
+	});
 
-		result_vec.append(&mut identify_utxo_outputs(keychain, outputs)?);
+	res.map_err(|_| ErrorKind::GenericError("UTXO scanning thread is crashed".to_string()))?
+}
 
+// Request the outputs of the PMMR range from the node
+fn fetch_outputs_range<'a, C>(
+	client: &C,
+	start_index: u64,
+	end_index: u64,
+) -> Result<Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>, Error>
+where
+	C: NodeClient + 'a,
+{
+	let mut start_index = start_index;
+	let mut result = vec![];
+	loop {
+		let (highest_index, last_retrieved_index, mut outputs) =
+			client.get_outputs_by_pmmr_index(start_index, Some(end_index), OUTPUTS_BATCH_SIZE)?;
+		result.append(&mut outputs);
 		if highest_index <= last_retrieved_index {
 			break;
 		}
 		start_index = last_retrieved_index + 1;
 	}
+	Ok(result)
+}
+
+// Receive the outputs found in the chunks and report them in the chunks order. The
+// receiver is dropped on return, so the scanning threads are stopping after an error.
+fn collect_ordered_outputs<R, P>(
+	result_rx: Receiver<(usize, Result<(usize, Vec<R>), Error>)>,
+	chunks: &[(u64, u64)],
+	start_index: u64,
+	highest_index: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+	on_batch: &mut P,
+) -> Result<Vec<R>, Error>
+where
+	P: FnMut(u64, &[R]) -> Result<(), Error>,
+{
+	let mut result_vec: Vec<R> = vec![];
+	let mut pending: HashMap<usize, (usize, Vec<R>)> = HashMap::new();
+	let mut next_chunk = 0;
+	while next_chunk < chunks.len() {
+		let (idx, found) = result_rx.recv().map_err(|_| {
+			ErrorKind::GenericError("UTXO scanning threads are stopped unexpectedly".to_string())
+		})?;
+		pending.insert(idx, found?);
+
+		while let Some((outputs_num, mut found)) = pending.remove(&next_chunk) {
+			let last_index = chunks[next_chunk].1;
+
+			let range = highest_index as f64 - start_index as f64 + 1.0;
+			let progress = last_index as f64 - start_index as f64 + 1.0;
+			let perc_complete = cmp::min(((progress / range) * 100.0) as u8, 99);
+
+			let msg = format!(
+				"Checking {} outputs, up to index {}. (Highest index: {})",
+				outputs_num, last_index, highest_index,
+			);
+			if let Some(ref s) = status_send_channel {
+				let _ = s.send(StatusMessage::Scanning(show_progress, msg, perc_complete));
+			}
+
+			on_batch(last_index, &found)?;
+			result_vec.append(&mut found);
+			next_chunk += 1;
+		}
+	}
 	Ok(result_vec)
 }
 
+// Private replacement of get_outputs_from_node. The UTXO set is downloaded by the PMMR ranges
+// starting from the lowest height of the outputs and the commits are matched locally.
+// outputs - commits with the heights where they are expected to be.
+pub(crate) fn get_outputs_from_chain<'a, C>(
+	client: &C,
+	outputs: &[(pedersen::Commitment, u64)],
+	threads: usize,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+) -> Result<HashMap<pedersen::Commitment, (String, u64, u64)>, Error>
+where
+	C: NodeClient + 'a,
+{
+	if outputs.is_empty() {
+		return Ok(HashMap::new());
+	}
+
+	let start_height = cmp::max(1, outputs.iter().map(|o| o.1).min().unwrap_or(1));
+	let wanted: HashSet<pedersen::Commitment> = outputs.iter().map(|o| o.0.clone()).collect();
+
+	let (start_index, _) = client.height_range_to_pmmr_indices(start_height, None)?;
+	let found = traverse_chain_outputs(
+		client.clone(),
+		start_index,
+		None,
+		threads,
+		status_send_channel,
+		show_progress,
+		|outputs| {
+			Ok(outputs
+				.into_iter()
+				.filter(|o| wanted.contains(&o.0))
+				.map(|(commit, _, _, height, mmr_index)| (commit, height, mmr_index))
+				.collect())
+		},
+		|_, _| Ok(()),
+	)?;
+
+	Ok(found
+		.into_iter()
+		.map(|(commit, height, mmr_index)| (commit, (util::to_hex(&commit.0), height, mmr_index)))
+		.collect())
+}
+
+// Private replacement of get_kernel. The blocks are downloaded by the height and the kernels are
+// matched locally. Return the heights of the found kernels.
+pub(crate) fn get_kernels_from_chain<'a, C>(
+	client: &C,
+	kernels: &HashSet<String>,
+	start_height: u64,
+	end_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+) -> Result<HashMap<String, u64>, Error>
+where
+	C: NodeClient + 'a,
+{
+	let mut result: HashMap<String, u64> = HashMap::new();
+	if kernels.is_empty() {
+		return Ok(result);
+	}
+	traverse_blocks(
+		client,
+		"kernels",
+		start_height,
+		end_height,
+		status_send_channel,
+		show_progress,
+		|b| {
+			for tx_kernel in b.kernels {
+				if kernels.contains(&tx_kernel.excess) {
+					result.insert(tx_kernel.excess, b.header.height);
+				}
+			}
+		},
+	)?;
+	Ok(result)
+}
+
+// Heights of the blocks that spend the commits. The blocks are downloaded by the height and
+// the inputs are matched locally, like for the kernels.
+fn get_spend_heights_from_chain<'a, C>(
+	client: &C,
+	commits: &HashSet<String>,
+	start_height: u64,
+	end_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+) -> Result<HashMap<String, u64>, Error>
+where
+	C: NodeClient + 'a,
+{
+	let mut result: HashMap<String, u64> = HashMap::new();
+	if commits.is_empty() {
+		return Ok(result);
+	}
+	traverse_blocks(
+		client,
+		"inputs",
+		start_height,
+		end_height,
+		status_send_channel,
+		show_progress,
+		|b| {
+			for input in b.inputs {
+				if commits.contains(&input) {
+					result.insert(input, b.header.height);
+				}
+			}
+		},
+	)?;
+	Ok(result)
+}
+
+// Download the blocks of the height range in batches and pass them to `f`
+fn traverse_blocks<'a, C, F>(
+	client: &C,
+	what: &str,
+	start_height: u64,
+	end_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+	mut f: F,
+) -> Result<(), Error>
+where
+	C: NodeClient + 'a,
+	F: FnMut(grin_api::BlockPrintable),
+{
+	let mut cur_height = cmp::max(1, start_height);
+	while cur_height <= end_height {
+		let next_h = cmp::min(
+			end_height,
+			cur_height + (SYNC_BLOCKS_THREADS * SYNC_BLOCKS_THREADS - 1) as u64,
+		);
+
+		if let Some(ref s) = status_send_channel {
+			let msg = format!(
+				"Checking {} of {} blocks, Height: {} - {}",
+				what,
+				next_h - cur_height + 1,
+				cur_height,
+				next_h,
+			);
+			let perc_complete = (next_h - start_height) * 99 / (end_height - start_height + 1);
+			let _ = s.send(StatusMessage::Scanning(
+				show_progress,
+				msg,
+				perc_complete as u8,
+			));
+		}
+
+		for b in client.get_blocks_by_height(cur_height, next_h, SYNC_BLOCKS_THREADS)? {
+			f(b);
+		}
+		cur_height = next_h + 1;
+	}
+	Ok(())
+}
+
+// Commits with the heights of the wallet outputs, get_outputs_from_chain needs them
+fn with_output_heights(
+	commits: &[pedersen::Commitment],
+	outputs: &HashMap<String, WalletOutputInfo>,
+) -> Vec<(pedersen::Commitment, u64)> {
+	commits
+		.iter()
+		.map(|commit| {
+			let height = outputs
+				.get(&util::to_hex(&commit.0))
+				.map(|out| out.output.height)
+				.unwrap_or(1);
+			(commit.clone(), height)
+		})
+		.collect()
+}
+
 /// Respore missing outputs. Shared with mwc713
 fn restore_missing_output<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -716,17 +1217,17 @@ where
 		debug!("get_wallet_and_chain_data using check whatever needed strategy");
 		// Full data update.
 		let client = w.w2n_client().clone();
-		let keychain = w.keychain(keychain_mask)?.clone();
 
 		// Retrieve the actual PMMR index range we're looking for
 		let pmmr_range = client.height_range_to_pmmr_indices(start_height, Some(end_height))?;
 
 		// Getting outputs that are published on the chain.
-		chain_outs = collect_chain_outputs(
-			&keychain,
-			client,
-			pmmr_range.0,
-			Some(pmmr_range.1),
+		chain_outs = collect_chain_outputs_checkpointed(
+			&mut **w,
+			keychain_mask,
+			start_height,
+			end_height,
+			pmmr_range,
 			status_send_channel,
 			show_progress,
 		)?;
@@ -752,7 +1253,11 @@ where
 
 		// Validate kernels from transaction. Kernel are a source of truth
 		let client = w.w2n_client().clone();
-		for tx in transactions.values_mut() {
+		let scan_config = w.scan_config();
+
+		// Transactions with kernels to validate: kernel and lookup min height
+		let mut kernels_to_check: HashMap<String, (pedersen::Commitment, u64)> = HashMap::new();
+		for tx in transactions.values() {
 			if !(tx.tx_log.confirmed || tx.tx_log.is_cancelled())
 				|| tx.tx_log.output_height >= start_height
 			{
@@ -766,28 +1271,77 @@ where
 				if let Some(kernel) = &tx.tx_log.kernel_excess {
 					// Note!!!! Test framework doesn't support None for params. So assuming that value must be provided
 					let start_height = cmp::max(start_height, 1); // API to tests don't support 0 or smaller
-					let res = client.get_kernel(
-						&kernel,
-						Some(cmp::min(
+					let min_height = if scan_config.private_update {
+						// Every block is downloaded, so searching from the height where the kernel
+						// can be only, not from the scan start. The blocks below the scan start are
+						// checked by the previous updates, they are looked again for the reorgs that
+						// the update reverts only.
+						let output_height = match tx.tx_log.output_height {
+							0 => start_height,
+							h => h,
+						};
+						cmp::max(
+							cmp::max(1, start_height.saturating_sub(scan_config.max_reorg_depth)),
+							tx.tx_log.kernel_lookup_min_height.unwrap_or(output_height),
+						)
+					} else {
+						cmp::min(
 							start_height, // 1 is min supported value by API
 							cmp::max(
 								1,
 								tx.tx_log.kernel_lookup_min_height.unwrap_or(start_height),
 							),
-						)),
-						Some(end_height),
-					)?;
-
-					match res {
-						Some((txkernel, height, _mmr_index)) => {
-							tx.kernel_validation = Some(true);
-							assert!(txkernel.excess == *kernel);
-							tx.tx_log.output_height = height; // Height must come from kernel and will match heights of outputs
-							tx.updated = true;
-						}
-						None => tx.kernel_validation = Some(false),
-					}
+						)
+					};
+					kernels_to_check.insert(tx.tx_uuid.clone(), (kernel.clone(), min_height));
+				}
+			}
+		}
+
+		let chain_kernels = if scan_config.private_update {
+			let kernels: HashSet<String> = kernels_to_check
+				.values()
+				.map(|(kernel, _)| util::to_hex(&kernel.0))
+				.collect();
+			let min_height = kernels_to_check
+				.values()
+				.map(|(_, min_height)| *min_height)
+				.min()
+				.unwrap_or(end_height);
+			Some(get_kernels_from_chain(
+				&client,
+				&kernels,
+				min_height,
+				end_height,
+				status_send_channel,
+				show_progress,
+			)?)
+		} else {
+			None
+		};
+
+		for (tx_uuid, (kernel, min_height)) in kernels_to_check {
+			let kernel_height = match &chain_kernels {
+				Some(chain_kernels) => chain_kernels
+					.get(&util::to_hex(&kernel.0))
+					.filter(|height| **height >= min_height)
+					.cloned(),
+				None => client
+					.get_kernel(&kernel, Some(min_height), Some(end_height))?
+					.map(|(txkernel, height, _mmr_index)| {
+						assert!(txkernel.excess == kernel);
+						height
+					}),
+			};
+
+			let tx = transactions.get_mut(&tx_uuid).unwrap();
+			match kernel_height {
+				Some(height) => {
+					tx.kernel_validation = Some(true);
+					tx.tx_log.output_height = height; // Height must come from kernel and will match heights of outputs
+					tx.updated = true;
 				}
+				None => tx.kernel_validation = Some(false),
 			}
 		}
 
@@ -806,7 +1360,15 @@ where
 
 		let mut commits: HashMap<pedersen::Commitment, (String, u64, u64)> = HashMap::new();
 
-		if wallet_outputs_to_check.len() > 100 {
+		if scan_config.private_update {
+			commits = get_outputs_from_chain(
+				&client,
+				&with_output_heights(&wallet_outputs_to_check, &outputs),
+				scan_config.threads,
+				status_send_channel,
+				show_progress,
+			)?;
+		} else if wallet_outputs_to_check.len() > 100 {
 			if let Some(ref s) = status_send_channel {
 				let _ = s.send(StatusMessage::Warning(format!("You have {} active outputs, it is a large number, validation will take time. Please wait...", wallet_outputs_to_check.len()) ));
 			}
@@ -897,10 +1459,21 @@ where
 			.collect();
 
 		let client = w.w2n_client().clone();
+		let scan_config = w.scan_config();
 
 		// Node will return back only Commits that are exist now.
 		let active_commits: HashMap<pedersen::Commitment, (String, u64, u64)> =
-			client.get_outputs_from_node(&wallet_outputs_to_check)?;
+			if scan_config.private_update {
+				get_outputs_from_chain(
+					&client,
+					&with_output_heights(&wallet_outputs_to_check, &outputs),
+					scan_config.threads,
+					status_send_channel,
+					show_progress,
+				)?
+			} else {
+				client.get_outputs_from_node(&wallet_outputs_to_check)?
+			};
 
 		for (active_commit, _, _) in active_commits.values() {
 			let output = outputs
@@ -918,6 +1491,315 @@ where
 	Ok((outputs, chain_outs, transactions, last_output))
 }
 
+/// Revert the confirmations above the fork height after the chain reorg. Transactions become
+/// unconfirmed, their outputs are unconfirmed and their inputs are locked again. The following
+/// scan confirms them again if they are still at the chain.
+/// Return the numbers of the reverted transactions and outputs.
+pub fn rollback_confirmations<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	fork_height: u64,
+) -> Result<(usize, usize), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	// Selected by the confirmation height, the slate id and kernel indexes don't cover it
+	let txs: Vec<TxLogEntry> = wallet
+		.tx_log_iter()
+		.filter(|tx| tx.confirmed && tx.output_height > fork_height)
+		.collect();
+	let input_commits: HashSet<String> = txs
+		.iter()
+		.flat_map(|tx| tx.input_commits.iter().map(|c| util::to_hex(&c.0)))
+		.collect();
+
+	let outputs: Vec<OutputData> = wallet
+		.iter()
+		.filter(|o| match o.status {
+			OutputStatus::Unspent => o.height > fork_height,
+			OutputStatus::Spent => o
+				.commit
+				.as_ref()
+				.map(|c| input_commits.contains(c))
+				.unwrap_or(false),
+			_ => false,
+		})
+		.collect();
+
+	let res = (txs.len(), outputs.len());
+	if res == (0, 0) {
+		return Ok(res);
+	}
+
+	let mut batch = match wallet.view_key() {
+		Some(_) => wallet.batch_no_mask()?,
+		None => wallet.batch(keychain_mask)?,
+	};
+	for mut tx in txs {
+		tx.confirmed = false;
+		tx.confirmation_ts = None;
+		let parent_key_id = tx.parent_key_id.clone();
+		batch.save_tx_log_entry(tx, &parent_key_id)?;
+	}
+	for mut output in outputs {
+		output.status = match output.status {
+			OutputStatus::Unspent => OutputStatus::Unconfirmed,
+			_ => OutputStatus::Locked,
+		};
+		batch.save(output)?;
+	}
+	batch.commit()?;
+	Ok(res)
+}
+
+/// Update watch-only wallet contents against the chain. There is no keychain, the outputs
+/// are identified with the view key. Unspent outputs that left the UTXO set are marked as spent
+/// and the spend is recorded as a sent transaction at the height of the spending block. Outputs
+/// of the watch-only wallet always have the commit, so no key derivation is needed. Outputs of
+/// the accounts that the wallet doesn't know yet get the account mapping, like with the restore.
+pub fn view_wallet_scan<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	view_key: &ViewKey,
+	start_height: u64,
+	tip_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	show_progress: bool,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::Scanning(
+			show_progress,
+			"Starting UTXO scan with view key".to_owned(),
+			0,
+		));
+	}
+
+	let (client, scan_config) = {
+		wallet_lock!(wallet_inst, w);
+		(w.w2n_client().clone(), w.scan_config())
+	};
+
+	let (start_index, _) = client.height_range_to_pmmr_indices(start_height, None)?;
+	let chain_outs = traverse_chain_outputs(
+		client.clone(),
+		start_index,
+		None,
+		scan_config.threads,
+		status_send_channel,
+		show_progress,
+		|outputs| identify_view_outputs(view_key, outputs),
+		|_, _| Ok(()),
+	)?;
+
+	wallet_lock!(wallet_inst, w);
+
+	let outputs: HashMap<String, OutputData> = w
+		.iter()
+		.filter(|o| o.commit.is_some())
+		.map(|o| (o.commit.clone().unwrap(), o))
+		.collect();
+
+	// Receives that were reverted by the chain reorg, by the tx log id. The view wallet entries
+	// have neither slate id nor kernel, so they can't be found by the tx log indexes.
+	let reverted_receives: HashMap<u32, TxLogEntry> = w
+		.tx_log_iter()
+		.filter(|t| {
+			(t.tx_type == TxLogEntryType::TxReceived
+				|| t.tx_type == TxLogEntryType::ConfirmedCoinbase)
+				&& !t.confirmed
+		})
+		.map(|t| (t.id, t))
+		.collect();
+	// Spends that were reverted by the chain reorg, by the spent output commit
+	let reverted_spends: HashMap<pedersen::Commitment, TxLogEntry> = w
+		.tx_log_iter()
+		.filter(|t| t.tx_type == TxLogEntryType::TxSent && !t.confirmed)
+		.filter_map(|t| t.input_commits.first().cloned().map(|c| (c, t)))
+		.collect();
+
+	// Outputs that are still at the chain. Locked outputs are the spends reverted by reorg
+	let unspent: Vec<pedersen::Commitment> = outputs
+		.values()
+		.filter(|o| o.status == OutputStatus::Unspent || o.status == OutputStatus::Locked)
+		.filter_map(|o| util::from_hex(o.commit.as_ref().unwrap()).ok())
+		.map(|c| pedersen::Commitment::from_vec(c))
+		.collect();
+	let at_chain = if scan_config.private_update {
+		let unspent: Vec<(pedersen::Commitment, u64)> = unspent
+			.iter()
+			.cloned()
+			.map(|commit| {
+				let height = outputs
+					.get(&util::to_hex(&commit.0))
+					.map(|o| o.height)
+					.unwrap_or(1);
+				(commit, height)
+			})
+			.collect();
+		get_outputs_from_chain(
+			&client,
+			&unspent,
+			scan_config.threads,
+			status_send_channel,
+			show_progress,
+		)?
+	} else {
+		client.get_outputs_from_node(&unspent)?
+	};
+
+	// Outputs that left the UTXO set are spent after the last update. The spend heights are
+	// found at the blocks from the oldest of them to the tip.
+	let spent: Vec<&OutputData> = unspent
+		.iter()
+		.filter(|commit| !at_chain.contains_key(commit))
+		.filter_map(|commit| outputs.get(&util::to_hex(&commit.0)))
+		.collect();
+	let spend_heights = match spent.iter().map(|o| o.height).min() {
+		Some(min_height) => get_spend_heights_from_chain(
+			&client,
+			&spent.iter().filter_map(|o| o.commit.clone()).collect(),
+			cmp::max(min_height, start_height),
+			tip_height,
+			status_send_channel,
+			show_progress,
+		)?,
+		None => HashMap::new(),
+	};
+
+	let mut accounts: Vec<Identifier> = w.acct_path_iter().map(|m| m.path).collect();
+	let mut batch = w.batch_no_mask()?;
+
+	for output in chain_outs {
+		let commit = util::to_hex(&output.commit.0);
+		if let Some(o) = outputs.get(&commit) {
+			// Reorg might revert or mark it as spent, now it is back
+			if o.status != OutputStatus::Unspent {
+				let mut o = o.clone();
+				o.status = OutputStatus::Unspent;
+				o.height = output.height;
+				if let Some(mut t) = o
+					.tx_log_entry
+					.and_then(|id| reverted_receives.get(&id).cloned())
+				{
+					t.confirmed = true;
+					t.output_height = output.height;
+					if let Ok(hdr_info) = client.get_header_info(output.height) {
+						t.update_confirmation_ts(hdr_info.confirmed_time);
+					}
+					let parent_key_id = t.parent_key_id.clone();
+					batch.save_tx_log_entry(t, &parent_key_id)?;
+				}
+				batch.save(o)?;
+			}
+			continue;
+		}
+
+		// Outputs of the other accounts get the account mapping, the same way as with the restore
+		let parent_key_id = output.key_id.parent_path();
+		if !accounts.contains(&parent_key_id) {
+			batch.save_acct_path(AcctPathMapping {
+				label: format!("account_{}", accounts.len()),
+				path: parent_key_id.clone(),
+			})?;
+			accounts.push(parent_key_id.clone());
+		}
+		let log_id = batch.next_tx_log_id(&parent_key_id)?;
+		let entry_type = match output.is_coinbase {
+			true => TxLogEntryType::ConfirmedCoinbase,
+			false => TxLogEntryType::TxReceived,
+		};
+		let mut t = TxLogEntry::new(parent_key_id.clone(), entry_type, log_id);
+		t.confirmed = true;
+		t.output_height = output.height;
+		t.amount_credited = output.value;
+		t.num_outputs = 1;
+		t.output_commits = vec![output.commit.clone()];
+		if let Ok(hdr_info) = client.get_header_info(t.output_height) {
+			t.update_confirmation_ts(hdr_info.confirmed_time);
+		}
+		batch.save_tx_log_entry(t, &parent_key_id)?;
+
+		batch.save(OutputData {
+			root_key_id: parent_key_id,
+			key_id: output.key_id,
+			n_child: output.n_child,
+			mmr_index: Some(output.mmr_index),
+			commit: Some(commit),
+			value: output.value,
+			status: OutputStatus::Unspent,
+			height: output.height,
+			lock_height: output.lock_height,
+			is_coinbase: output.is_coinbase,
+			tx_log_entry: Some(log_id),
+		})?;
+	}
+
+	for commit in unspent {
+		let mut o = outputs.get(&util::to_hex(&commit.0)).unwrap().clone();
+		let reverted_spend = reverted_spends.get(&commit).cloned();
+		if at_chain.contains_key(&commit) {
+			// The spend is gone with the reorg
+			if o.status == OutputStatus::Locked {
+				o.status = OutputStatus::Unspent;
+				batch.save(o)?;
+				if let Some(mut t) = reverted_spend {
+					t.tx_type = TxLogEntryType::TxSentCancelled;
+					let parent_key_id = t.parent_key_id.clone();
+					batch.save_tx_log_entry(t, &parent_key_id)?;
+				}
+			}
+			continue;
+		}
+
+		// Spending block isn't found if the output is spent before the scanned range
+		let spend_height = spend_heights
+			.get(&util::to_hex(&commit.0))
+			.cloned()
+			.unwrap_or(cmp::max(o.height, start_height));
+		let mut t = match reverted_spend {
+			Some(t) => t,
+			None => {
+				let log_id = batch.next_tx_log_id(&o.root_key_id)?;
+				let mut t = TxLogEntry::new(o.root_key_id.clone(), TxLogEntryType::TxSent, log_id);
+				t.amount_debited = o.value;
+				t.num_inputs = 1;
+				t.input_commits = vec![commit.clone()];
+				t
+			}
+		};
+		t.confirmed = true;
+		t.output_height = spend_height;
+		if let Ok(hdr_info) = client.get_header_info(spend_height) {
+			t.update_confirmation_ts(hdr_info.confirmed_time);
+		}
+		let parent_key_id = t.parent_key_id.clone();
+		batch.save_tx_log_entry(t, &parent_key_id)?;
+
+		o.status = OutputStatus::Spent;
+		batch.save(o)?;
+	}
+
+	for par_id in &accounts {
+		batch.save_last_confirmed_height(par_id, tip_height)?;
+	}
+	batch.commit()?;
+
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::ScanningComplete(
+			show_progress,
+			"Scanning Complete".to_owned(),
+		));
+	}
+
+	Ok(())
+}
+
 /// Check / repair wallet contents by scanning against chain
 /// assume wallet contents have been freshly updated with contents
 /// of latest block
@@ -1080,11 +1962,18 @@ where
 		wallet_lock!(wallet_inst, w);
 
 		let accounts: Vec<Identifier> = w.acct_path_iter().map(|m| m.path).collect();
+		let checkpoint = w.get_scan_checkpoint()?;
 		let mut batch = w.batch(keychain_mask)?;
 
 		for par_id in &accounts {
 			batch.save_last_confirmed_height(par_id, tip_height)?;
 		}
+		// The scan is done, there is nothing to resume
+		if let Some(checkpoint) = checkpoint {
+			if checkpoint.is_scan_of(start_height, tip_height) {
+				batch.delete_scan_checkpoint()?;
+			}
+		}
 		batch.commit()?;
 	}
 
@@ -14,6 +14,7 @@
 
 //! Selection of inputs for building transactions
 
+use crate::api_impl::types::CoinSelectionStrategy;
 use crate::error::{Error, ErrorKind};
 use crate::grin_core::core::amount_to_hr_string;
 use crate::grin_core::libtx::{
@@ -21,7 +22,7 @@ use crate::grin_core::libtx::{
 	proof::{ProofBuild, ProofBuilder},
 	tx_fee,
 };
-use crate::grin_keychain::{Identifier, Keychain};
+use crate::grin_keychain::{BlindSum, BlindingFactor, Identifier, Keychain};
 use crate::grin_util::secp::key::SecretKey;
 use crate::grin_util::secp::pedersen::Commitment;
 use crate::internal::keys;
@@ -29,7 +30,9 @@ use crate::proof::proofaddress;
 use crate::slate::Slate;
 use crate::types::*;
 use grin_wallet_util::grin_util as util;
-use std::collections::HashMap;
+use rand::thread_rng;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 
 /// Initialize a transaction on the sender side, returns a corresponding
 /// libwallet transaction slate with the appropriate inputs selected,
@@ -44,7 +47,7 @@ pub fn build_send_tx<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	change_outputs: usize,
-	selection_strategy_is_use_all: bool,
+	selection_strategy: CoinSelectionStrategy,
 	parent_key_id: Identifier,
 	participant_id: usize,
 	use_test_nonce: bool,
@@ -68,7 +71,7 @@ where
 		minimum_confirmations,
 		max_outputs,
 		change_outputs,
-		selection_strategy_is_use_all,
+		selection_strategy,
 		&parent_key_id,
 		outputs,
 		routputs,
@@ -128,6 +131,135 @@ where
 	Ok(context)
 }
 
+/// Initialize a batch payout on the sender side. The slates are built the same way as
+/// by build_send_tx, but only the first slate gets the selected inputs and change outputs.
+/// Every slate has its own kernel that pays a share of the transaction fee.
+/// Returns the private contexts in the order of the slates.
+pub fn build_batch_send_tx<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain: &K,
+	keychain_mask: Option<&SecretKey>,
+	slates: &mut Vec<Slate>,
+	minimum_confirmations: u64,
+	max_outputs: usize,
+	change_outputs: usize,
+	selection_strategy: CoinSelectionStrategy,
+	parent_key_id: Identifier,
+	use_test_nonce: bool,
+	outputs: &Option<Vec<String>>, // outputs to include into the transaction
+	exclude_change_outputs: bool,
+	change_output_minimum_confirmations: u64,
+	message: Option<String>,
+) -> Result<Vec<Context>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let num_slates = slates.len();
+	if num_slates == 0 {
+		return Err(ErrorKind::GenericError(
+			"Batch payout needs at least one recipient".to_string(),
+		))?;
+	}
+	let amount: u64 = slates.iter().map(|s| s.amount).sum();
+	let height = slates[0].height;
+
+	// Coins selection accounts for a single kernel, the rest of kernels are added to the amount
+	let extra_kernels_fee = tx_fee(0, 0, num_slates, None) - tx_fee(0, 0, 1, None);
+	let (coins, _total, _amount, fee) = select_coins_and_fee(
+		wallet,
+		amount + extra_kernels_fee,
+		height,
+		minimum_confirmations,
+		max_outputs,
+		change_outputs,
+		selection_strategy,
+		&parent_key_id,
+		outputs,
+		num_slates, // every recipient has a single output
+		exclude_change_outputs,
+		change_output_minimum_confirmations,
+	)?;
+	let fee = fee + extra_kernels_fee;
+
+	let (elems, change_amounts_derivations) = inputs_and_change(
+		&coins,
+		wallet,
+		keychain_mask,
+		amount,
+		fee,
+		change_outputs,
+		true, // Legacy value is true
+	)?;
+
+	// The fee is shared by the kernels, the first one pays the remainder
+	let fee_share = fee / num_slates as u64;
+	for (i, slate) in slates.iter_mut().enumerate() {
+		slate.fee = match i {
+			0 => fee - fee_share * (num_slates as u64 - 1),
+			_ => fee_share,
+		};
+		slate.update_kernel();
+	}
+
+	let blinding =
+		slates[0].add_transaction_elements(keychain, &ProofBuilder::new(keychain), elems)?;
+
+	// Legacy model needs the excess of every kernel. The blinding of inputs and change
+	// is split between the kernels with the random keys.
+	let mut excesses: Vec<SecretKey> = vec![];
+	if !slates[0].compact_slate {
+		let mut sum = BlindSum::new().add_blinding_factor(blinding);
+		for _ in 1..num_slates {
+			let key = SecretKey::new(&mut thread_rng());
+			sum = sum.sub_blinding_factor(BlindingFactor::from_secret_key(key.clone()));
+			excesses.push(key);
+		}
+		excesses.insert(0, keychain.blind_sum(&sum)?.secret_key()?);
+	}
+
+	let mut contexts = vec![];
+	for (i, slate) in slates.iter().enumerate() {
+		let mut context = if slate.compact_slate {
+			Context::new(
+				keychain.secp(),
+				&parent_key_id,
+				use_test_nonce,
+				true,
+				0,
+				slate.amount,
+				slate.fee,
+				message.clone(),
+			)
+		} else {
+			// Legacy part
+			Context::with_excess(
+				keychain.secp(),
+				excesses[i].clone(),
+				&parent_key_id,
+				use_test_nonce,
+				0,
+				slate.amount,
+				slate.fee,
+				message.clone(),
+			)
+		};
+
+		if i == 0 {
+			for input in &coins {
+				context.add_input(&input.key_id, &input.mmr_index, input.value);
+			}
+			for (change_amount, id, mmr_index) in &change_amounts_derivations {
+				context.add_output(&id, &mmr_index, *change_amount);
+			}
+		}
+		contexts.push(context);
+	}
+
+	Ok(contexts)
+}
+
 /// Locks all corresponding outputs in the context, creates
 /// change outputs and tx log entry
 pub fn lock_tx_context<'a, T: ?Sized, C, K>(
@@ -143,6 +275,36 @@ where
 	T: WalletBackend<'a, C, K>,
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
+{
+	lock_tx_context_with(
+		wallet,
+		keychain_mask,
+		slate,
+		current_height,
+		context,
+		address,
+		excess_override,
+		|_, _| Ok(()),
+	)
+}
+
+/// Same as lock_tx_context. Before the tx log entry is saved, 'update' can change it and
+/// write more data, everything is committed together in a single DB batch.
+pub fn lock_tx_context_with<'a, T: ?Sized, C, K, F>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &Slate,
+	current_height: u64,
+	context: &Context,
+	address: Option<String>,
+	excess_override: Option<Commitment>,
+	update: F,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+	F: FnOnce(&mut dyn WalletOutputBatch<K>, &mut TxLogEntry) -> Result<(), Error>,
 {
 	let mut output_commits: HashMap<Identifier, (Option<String>, u64)> = HashMap::new();
 	// Store cached commits before locking wallet
@@ -168,21 +330,14 @@ where
 		let slate_id = slate.id;
 		let height = current_height;
 		let parent_key_id = context.parent_key_id.clone();
-		let mut batch = wallet.batch(keychain_mask)?;
 
 		// Check if such transaction already exist. It is very possible for lock after case.
-		let found_tx = batch
-			.tx_log_iter()
-			.filter(|tx_entry| {
-				if tx_entry.tx_type != TxLogEntryType::TxSent {
-					return false;
-				}
-				match tx_entry.tx_slate_id {
-					None => false,
-					Some(uuid) => uuid == slate_id,
-				}
-			})
-			.next();
+		let found_tx = wallet
+			.get_tx_log_by_slate_id(&slate_id)?
+			.into_iter()
+			.find(|tx_entry| tx_entry.tx_type == TxLogEntryType::TxSent);
+
+		let mut batch = wallet.batch(keychain_mask)?;
 
 		let mut t = match found_tx {
 			Some(tx) => tx,
@@ -278,6 +433,7 @@ where
 				tx_log_entry: Some(t.id),
 			})?;
 		}
+		update(&mut *batch, &mut t)?;
 		batch.save_tx_log_entry(t.clone(), &parent_key_id)?;
 		batch.commit()?;
 		t
@@ -482,7 +638,7 @@ pub fn select_send_tx<'a, T: ?Sized, C, K, B>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	change_outputs: usize,
-	selection_strategy_is_use_all: bool,
+	selection_strategy: CoinSelectionStrategy,
 	parent_key_id: &Identifier,
 	outputs: &Option<Vec<String>>, // outputs to include into the transaction
 	routputs: usize,               // Number of resulting outputs. Normally it is 1
@@ -511,7 +667,7 @@ where
 		minimum_confirmations,
 		max_outputs,
 		change_outputs,
-		selection_strategy_is_use_all,
+		selection_strategy,
 		&parent_key_id,
 		outputs,  // outputs to include into the transaction
 		routputs, // Number of resulting outputs. Normally it is 1
@@ -541,7 +697,7 @@ pub fn select_coins_and_fee<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	change_outputs: usize,
-	selection_strategy_is_use_all: bool,
+	selection_strategy: CoinSelectionStrategy,
 	parent_key_id: &Identifier,
 	outputs: &Option<Vec<String>>, // outputs to include into the transaction
 	routputs: usize,               // Number of resulting outputs. Normally it is 1
@@ -561,6 +717,35 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
+	if selection_strategy == CoinSelectionStrategy::BranchAndBound {
+		let eligible = eligible_outputs(
+			wallet,
+			current_height,
+			minimum_confirmations,
+			parent_key_id,
+			outputs,
+			exclude_change_outputs,
+			change_output_minimum_confirmations,
+		);
+		if let Some(coins) = select_exact_match(
+			amount,
+			routputs,
+			change_outputs,
+			max_outputs.saturating_sub(routputs),
+			&eligible,
+		) {
+			// No change output, so everything above the amount goes to the fee.
+			// The excess is never larger than the cost of the change output we are not creating.
+			let total: u64 = coins.iter().map(|c| c.value).sum();
+			let fee = total - amount;
+			return Ok((coins, total, amount, fee));
+		}
+		debug!(
+			"No exact match found for amount {}, using smallest outputs instead",
+			amount
+		);
+	}
+
 	// select some spendable coins from the wallet
 	let (_, mut coins) = select_coins(
 		wallet,
@@ -568,7 +753,7 @@ where
 		current_height,
 		minimum_confirmations,
 		max_outputs.saturating_sub(routputs + change_outputs), // Exclude number sof outpus
-		selection_strategy_is_use_all,
+		selection_strategy,
 		parent_key_id,
 		outputs, // outputs to include into the transaction
 		exclude_change_outputs,
@@ -616,7 +801,7 @@ where
 				current_height,
 				minimum_confirmations,
 				max_outputs,
-				selection_strategy_is_use_all,
+				selection_strategy,
 				parent_key_id,
 				outputs,
 				exclude_change_outputs,
@@ -646,6 +831,48 @@ where
 	Ok((coins, total, amount, fee))
 }
 
+/// Maximum amount that can be sent with a single transaction. The largest eligible outputs,
+/// up to max_outputs, are spent and no change output is created.
+pub fn max_send_amount<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	current_height: u64,
+	minimum_confirmations: u64,
+	max_outputs: usize,
+	parent_key_id: &Identifier,
+	outputs: &Option<Vec<String>>, // outputs to include into the transaction
+	routputs: usize,               // Number of resulting outputs. Normally it is 1
+	exclude_change_outputs: bool,
+	change_output_minimum_confirmations: u64,
+) -> (
+	Vec<OutputData>,
+	u64, // amount
+	u64, // fee
+)
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut coins = eligible_outputs(
+		wallet,
+		current_height,
+		minimum_confirmations,
+		parent_key_id,
+		outputs,
+		exclude_change_outputs,
+		change_output_minimum_confirmations,
+	);
+	coins.sort_by_key(|out| Reverse(out.value));
+	coins.truncate(max_outputs.saturating_sub(routputs));
+
+	let total: u64 = coins.iter().map(|c| c.value).sum();
+	let fee = tx_fee(coins.len(), routputs, 1, None);
+	if total <= fee {
+		return (vec![], 0, 0);
+	}
+	(coins, total - fee, fee)
+}
+
 /// Selects inputs and change for a transaction
 pub fn inputs_and_change<'a, T: ?Sized, C, K, B>(
 	coins: &[OutputData],
@@ -721,11 +948,11 @@ where
 }
 
 /// Select spendable coins from a wallet.
-/// Default strategy is to spend the maximum number of outputs (up to
-/// max_outputs). Alternative strategy is to spend smallest outputs first
-/// but only as many as necessary. When we introduce additional strategies
-/// we should pass something other than a bool in.
-/// TODO: Possibly move this into another trait to be owned by a wallet?
+/// The order in which outputs are considered depends on the selection strategy
+/// (smallest, largest or oldest first). `All` spends the maximum number of
+/// outputs (up to max_outputs), the others only as many as necessary.
+/// `Privacy` tries to keep the outputs from a single received transaction.
+/// `BranchAndBound` is handled by select_coins_and_fee, here it behaves like `Smallest`.
 
 pub fn select_coins<'a, T: ?Sized, C, K>(
 	wallet: &mut T,
@@ -733,7 +960,7 @@ pub fn select_coins<'a, T: ?Sized, C, K>(
 	current_height: u64,
 	minimum_confirmations: u64,
 	max_outputs: usize,
-	select_all: bool,
+	selection_strategy: CoinSelectionStrategy,
 	parent_key_id: &Identifier,
 	outputs: &Option<Vec<String>>, // outputs to include into the transaction
 	exclude_change_outputs: bool,
@@ -745,61 +972,30 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	let mut change_outputs: HashMap<String, u32> = HashMap::new();
-	if exclude_change_outputs {
-		let txs: Vec<TxLogEntry> = wallet
-			.tx_log_iter()
-			.filter(|tx_entry| tx_entry.tx_type == TxLogEntryType::TxSent && tx_entry.confirmed)
-			.collect();
-
-		for tx in &txs {
-			for o in &tx.output_commits {
-				let commit = format!("{}", util::to_hex(&o.0));
-				change_outputs.insert(commit, 1);
-			}
-		}
-	}
-	debug!(
-		"exclude_change_outputs = {}, change_output_minimum_confirmations = {}",
-		exclude_change_outputs, change_output_minimum_confirmations
+	let mut eligible = eligible_outputs(
+		wallet,
+		current_height,
+		minimum_confirmations,
+		parent_key_id,
+		outputs,
+		exclude_change_outputs,
+		change_output_minimum_confirmations,
 	);
-	// first find all eligible outputs based on number of confirmations
-	let mut eligible = wallet
-		.iter()
-		.filter(|out| {
-			if out.commit.is_some() && change_outputs.contains_key(out.commit.as_ref().unwrap()) {
-				out.root_key_id == *parent_key_id
-					&& out.eligible_to_spend(current_height, change_output_minimum_confirmations)
-			} else {
-				out.root_key_id == *parent_key_id
-					&& out.eligible_to_spend(current_height, minimum_confirmations)
-			}
-		})
-		.collect::<Vec<OutputData>>();
 
-	match outputs {
-		// User specify outputs to use. It is caller responsibility to make sure  that anount is enough.
-		// we are not adding more outputs to satisfy amount.
-		Some(outputs) => {
-			eligible = eligible
-				.into_iter()
-				.filter(|out| {
-					if out.commit.is_some() {
-						let commit_str = out.commit.clone().unwrap();
-						outputs.contains(&commit_str)
-					} else {
-						false
-					}
-				})
-				.collect::<Vec<OutputData>>();
+	let max_available = eligible.len();
+
+	if selection_strategy == CoinSelectionStrategy::Privacy {
+		if let Some(outputs) = select_from_single_tx(amount, max_outputs, &eligible) {
+			return (max_available, outputs);
 		}
-		None => (),
+		debug!(
+			"Amount {} can't be covered by outputs from a single transaction",
+			amount
+		);
 	}
 
-	let max_available = eligible.len();
-
-	// sort eligible outputs by increasing value
-	eligible.sort_by_key(|out| out.value);
+	sort_for_strategy(&mut eligible, selection_strategy);
+	let select_all = selection_strategy.is_use_all();
 
 	// use a sliding window to identify potential sets of possible outputs to spend
 	// Case of amount > total amount of max_outputs(500):
@@ -817,7 +1013,7 @@ where
 			}
 		}
 		// Not exist in any window of which total amount >= amount.
-		// Then take coins from the smallest one up to the total amount of selected
+		// Then take coins in the strategy order up to the total amount of selected
 		// coins = the amount.
 		if let Some(outputs) = select_from(amount, false, eligible.clone()) {
 			debug!(
@@ -833,7 +1029,7 @@ where
 	// we failed to find a suitable set of outputs to spend,
 	// so return the largest amount we can so we can provide guidance on what is
 	// possible
-	eligible.reverse();
+	eligible.sort_by_key(|out| Reverse(out.value));
 	(
 		max_available,
 		eligible.iter().take(max_outputs).cloned().collect(),
@@ -864,6 +1060,269 @@ fn select_from(amount: u64, select_all: bool, outputs: Vec<OutputData>) -> Optio
 	}
 }
 
+/// Outputs that are eligible to spend, based on number of confirmations
+/// and the list of outputs requested by the user.
+pub fn eligible_outputs<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	current_height: u64,
+	minimum_confirmations: u64,
+	parent_key_id: &Identifier,
+	outputs: &Option<Vec<String>>, // outputs to include into the transaction
+	exclude_change_outputs: bool,
+	change_output_minimum_confirmations: u64,
+) -> Vec<OutputData>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut change_outputs: HashMap<String, u32> = HashMap::new();
+	if exclude_change_outputs {
+		let txs: Vec<TxLogEntry> = wallet
+			.tx_log_iter()
+			.filter(|tx_entry| tx_entry.tx_type == TxLogEntryType::TxSent && tx_entry.confirmed)
+			.collect();
+
+		for tx in &txs {
+			for o in &tx.output_commits {
+				let commit = format!("{}", util::to_hex(&o.0));
+				change_outputs.insert(commit, 1);
+			}
+		}
+	}
+	debug!(
+		"exclude_change_outputs = {}, change_output_minimum_confirmations = {}",
+		exclude_change_outputs, change_output_minimum_confirmations
+	);
+	// frozen outputs are never spent, even if requested explicitly
+	let frozen: HashSet<String> = wallet
+		.output_metadata_iter()
+		.filter(|m| m.frozen)
+		.map(|m| m.commit)
+		.collect();
+
+	// first find all eligible outputs based on number of confirmations
+	let mut eligible = wallet
+		.iter()
+		.filter(|out| match &out.commit {
+			Some(c) => !frozen.contains(c),
+			None => true,
+		})
+		.filter(|out| {
+			if out.commit.is_some() && change_outputs.contains_key(out.commit.as_ref().unwrap()) {
+				out.root_key_id == *parent_key_id
+					&& out.eligible_to_spend(current_height, change_output_minimum_confirmations)
+			} else {
+				out.root_key_id == *parent_key_id
+					&& out.eligible_to_spend(current_height, minimum_confirmations)
+			}
+		})
+		.collect::<Vec<OutputData>>();
+
+	match outputs {
+		// User specify outputs to use. It is caller responsibility to make sure  that anount is enough.
+		// we are not adding more outputs to satisfy amount.
+		Some(outputs) => {
+			eligible = eligible
+				.into_iter()
+				.filter(|out| {
+					if out.commit.is_some() {
+						let commit_str = out.commit.clone().unwrap();
+						outputs.contains(&commit_str)
+					} else {
+						false
+					}
+				})
+				.collect::<Vec<OutputData>>();
+		}
+		None => (),
+	}
+	eligible
+}
+
+/// Order eligible outputs in the way the strategy wants to consume them
+fn sort_for_strategy(eligible: &mut Vec<OutputData>, selection_strategy: CoinSelectionStrategy) {
+	match selection_strategy {
+		CoinSelectionStrategy::All
+		| CoinSelectionStrategy::Smallest
+		| CoinSelectionStrategy::BranchAndBound => {
+			// sort eligible outputs by increasing value
+			eligible.sort_by_key(|out| out.value);
+		}
+		CoinSelectionStrategy::LargestFirst => {
+			eligible.sort_by_key(|out| Reverse(out.value));
+		}
+		CoinSelectionStrategy::OldestFirst => {
+			eligible.sort_by_key(|out| (out.height, out.value));
+		}
+		CoinSelectionStrategy::Privacy => {
+			// Keep outputs from the same transaction together, the transactions with the
+			// largest total go first, so as few transactions as possible get mixed.
+			let mut groups = group_by_tx(eligible.drain(..).collect());
+			groups.sort_by_key(|g| Reverse(group_total(g)));
+			for group in groups {
+				eligible.extend(group);
+			}
+		}
+	}
+}
+
+/// Group outputs by the transaction that created them. Every group is sorted by
+/// increasing value. Outputs without transaction are treated as separate groups.
+fn group_by_tx(outputs: Vec<OutputData>) -> Vec<Vec<OutputData>> {
+	// tx log id => index in groups
+	let mut by_tx: HashMap<u32, usize> = HashMap::new();
+	let mut groups: Vec<Vec<OutputData>> = Vec::new();
+	for out in outputs {
+		let group_idx = out
+			.tx_log_entry
+			.and_then(|tx_id| by_tx.get(&tx_id).cloned());
+		match group_idx {
+			Some(idx) => groups[idx].push(out),
+			None => {
+				if let Some(tx_id) = out.tx_log_entry {
+					by_tx.insert(tx_id, groups.len());
+				}
+				groups.push(vec![out]);
+			}
+		}
+	}
+	for group in &mut groups {
+		group.sort_by_key(|out| out.value);
+	}
+	groups
+}
+
+fn group_total(group: &[OutputData]) -> u64 {
+	group.iter().map(|out| out.value).sum()
+}
+
+/// Select outputs that were all received with a single transaction. If several transactions
+/// can cover the amount, the one that needs the least outputs (and then produces the smallest
+/// change) wins.
+fn select_from_single_tx(
+	amount: u64,
+	max_outputs: usize,
+	eligible: &[OutputData],
+) -> Option<Vec<OutputData>> {
+	let mut best: Option<Vec<OutputData>> = None;
+	for group in group_by_tx(eligible.to_vec()) {
+		let candidate = match select_from(amount, false, group) {
+			Some(c) => c,
+			None => continue,
+		};
+		if candidate.len() > max_outputs {
+			continue;
+		}
+		let better = match &best {
+			None => true,
+			Some(b) => {
+				candidate.len() < b.len()
+					|| (candidate.len() == b.len() && group_total(&candidate) < group_total(b))
+			}
+		};
+		if better {
+			best = Some(candidate);
+		}
+	}
+	best
+}
+
+/// Limit for the branch and bound search, to keep wallets with many outputs responsive
+const BNB_MAX_TRIES: usize = 100_000;
+
+/// Branch and bound search for a set of outputs that covers the amount and the fee without
+/// a change output. Sum of the selected outputs is expected to be in the range
+/// [amount + fee without change, amount + fee with change], so the excess is never more than
+/// what the change output would cost.
+fn select_exact_match(
+	amount: u64,
+	routputs: usize,
+	change_outputs: usize,
+	max_inputs: usize,
+	eligible: &[OutputData],
+) -> Option<Vec<OutputData>> {
+	let mut sorted = eligible.to_vec();
+	sorted.sort_by_key(|out| Reverse(out.value));
+	let values: Vec<u64> = sorted.iter().map(|out| out.value).collect();
+
+	// remaining[i] is the sum of all values starting from i
+	let mut remaining = vec![0u64; values.len() + 1];
+	for i in (0..values.len()).rev() {
+		remaining[i] = remaining[i + 1] + values[i];
+	}
+
+	let mut search = ExactMatchSearch {
+		values: &values,
+		remaining: &remaining,
+		amount,
+		routputs,
+		change_outputs: change_outputs.max(1),
+		max_inputs,
+		tries: 0,
+		selected: vec![],
+	};
+
+	if search.run(0, 0) {
+		Some(search.selected.iter().map(|i| sorted[*i].clone()).collect())
+	} else {
+		None
+	}
+}
+
+struct ExactMatchSearch<'a> {
+	values: &'a [u64],
+	remaining: &'a [u64],
+	amount: u64,
+	routputs: usize,
+	change_outputs: usize,
+	max_inputs: usize,
+	tries: usize,
+	selected: Vec<usize>,
+}
+
+impl<'a> ExactMatchSearch<'a> {
+	fn run(&mut self, index: usize, sum: u64) -> bool {
+		self.tries += 1;
+		if self.tries > BNB_MAX_TRIES {
+			return false;
+		}
+
+		if !self.selected.is_empty() {
+			let inputs = self.selected.len();
+			let lower = self.amount + tx_fee(inputs, self.routputs, 1, None);
+			let upper = self.amount + tx_fee(inputs, self.routputs + self.change_outputs, 1, None);
+			if (lower..=upper).contains(&sum) {
+				return true;
+			}
+			if sum > upper {
+				// values are sorted descending, taking more only goes further away
+				return false;
+			}
+		}
+
+		if index >= self.values.len() || self.selected.len() >= self.max_inputs {
+			return false;
+		}
+
+		// Even all the rest of the outputs are not enough
+		let min_needed = self.amount + tx_fee(self.selected.len() + 1, self.routputs, 1, None);
+		if sum + self.remaining[index] < min_needed {
+			return false;
+		}
+
+		// Branch with the output at index included
+		self.selected.push(index);
+		if self.run(index + 1, sum + self.values[index]) {
+			return true;
+		}
+		self.selected.pop();
+
+		// Branch with the output skipped
+		self.run(index + 1, sum)
+	}
+}
+
 /// Repopulates output in the slate's tranacstion
 /// with outputs from the stored context
 /// change outputs and tx log entry
@@ -32,7 +32,7 @@ use crate::proof::tx_proof::{push_proof_for_slate, TxProof};
 use crate::signature::Signature as otherSignature;
 use crate::slate::Slate;
 use crate::types::{Context, NodeClient, StoredProofInfo, TxLogEntryType, WalletBackend};
-use crate::InitTxArgs;
+use crate::{CoinSelectionStrategy, InitTxArgs};
 use crate::{Error, ErrorKind};
 use ed25519_dalek::Keypair as DalekKeypair;
 use ed25519_dalek::PublicKey as DalekPublicKey;
@@ -104,7 +104,7 @@ pub fn estimate_send_tx<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	num_change_outputs: usize,
-	selection_strategy_is_use_all: bool,
+	selection_strategy: CoinSelectionStrategy,
 	parent_key_id: &Identifier,
 	outputs: &Option<Vec<String>>, // outputs to include into the transaction
 	routputs: usize,               // Number of resulting outputs. Normally it is 1
@@ -140,7 +140,7 @@ where
 		minimum_confirmations,
 		max_outputs,
 		num_change_outputs,
-		selection_strategy_is_use_all,
+		selection_strategy,
 		parent_key_id,
 		outputs,
 		routputs,
@@ -159,7 +159,7 @@ pub fn add_inputs_to_slate<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	num_change_outputs: usize,
-	selection_strategy_is_use_all: bool,
+	selection_strategy: CoinSelectionStrategy,
 	parent_key_id: &Identifier,
 	participant_id: usize,
 	message: Option<String>,
@@ -192,7 +192,7 @@ where
 		minimum_confirmations,
 		max_outputs,
 		num_change_outputs,
-		selection_strategy_is_use_all,
+		selection_strategy,
 		parent_key_id.clone(),
 		participant_id,
 		use_test_rng,
@@ -331,7 +331,7 @@ where
 		init_tx_args.minimum_confirmations,
 		init_tx_args.max_outputs as usize,
 		init_tx_args.num_change_outputs as usize,
-		init_tx_args.selection_strategy_is_use_all,
+		init_tx_args.coin_selection_strategy(),
 		&parent_key_id,
 		&init_tx_args.outputs,
 		1,
@@ -32,7 +32,7 @@ use crate::internal::keys;
 use crate::types::{
 	NodeClient, OutputData, OutputStatus, TxLogEntry, TxLogEntryType, WalletBackend, WalletInfo,
 };
-use crate::{BlockFees, CbData, OutputCommitMapping};
+use crate::{BlockFees, CbData, OutputCommitMapping, TxQueryArgs};
 
 /// Retrieve all of the outputs (doesn't attempt to update from node)
 pub fn retrieve_outputs<'a, T: ?Sized, C, K>(
@@ -81,7 +81,11 @@ where
 		.collect();
 
 	outputs.sort_by_key(|out| out.n_child);
-	let keychain = wallet.keychain(keychain_mask)?;
+	// Watch-only wallet has no keychain, its outputs always have the commit
+	let keychain = match wallet.view_key() {
+		Some(_) => None,
+		None => Some(wallet.keychain(keychain_mask)?),
+	};
 
 	// Key: tx_log id;  Value: true if active, false if cancelled
 	let tx_log_is_active: HashMap<u32, bool> = wallet
@@ -106,12 +110,16 @@ where
 			Some(c) => pedersen::Commitment::from_vec(util::from_hex(&c).map_err(|e| {
 				ErrorKind::GenericError(format!("Unable to parse HEX commit {}, {}", c, e))
 			})?),
-			None => keychain // TODO: proper support for different switch commitment schemes
+			None => keychain
+				.as_ref()
+				.ok_or(ErrorKind::WatchOnlyWallet)? // TODO: proper support for different switch commitment schemes
 				.commit(out.value, &out.key_id, SwitchCommitmentType::Regular)?,
 		};
+		let metadata = wallet.get_output_metadata(&util::to_hex(&commit.0))?;
 		res.push(OutputCommitMapping {
 			output: out,
 			commit,
+			metadata,
 		});
 	}
 
@@ -155,8 +163,12 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	let mut txs: Vec<TxLogEntry> = wallet
-		.tx_log_iter()
+	// The slate id index has the entries of the slate, no need to go through the whole log
+	let source: Box<dyn Iterator<Item = TxLogEntry> + '_> = match tx_slate_id {
+		Some(t) => Box::new(wallet.get_tx_log_by_slate_id(&t)?.into_iter()),
+		None => wallet.tx_log_iter(),
+	};
+	let mut txs: Vec<TxLogEntry> = source
 		.filter(|tx_entry| {
 			let f_pk = match parent_key_id {
 				Some(k) => tx_entry.parent_key_id == *k,
@@ -166,10 +178,6 @@ where
 				Some(i) => tx_entry.id == i,
 				None => true,
 			};
-			let f_txs = match tx_slate_id {
-				Some(t) => tx_entry.tx_slate_id == Some(t),
-				None => true,
-			};
 			let f_outstanding = match outstanding_only {
 				true => {
 					!tx_entry.confirmed
@@ -182,7 +190,7 @@ where
 			let non_confirmed_coinbase =
 				!tx_entry.confirmed && (tx_entry.tx_type == TxLogEntryType::ConfirmedCoinbase);
 
-			f_pk && f_tx_id && f_txs && f_outstanding && !non_confirmed_coinbase
+			f_pk && f_tx_id && f_outstanding && !non_confirmed_coinbase
 		})
 		.collect();
 
@@ -213,6 +221,37 @@ where
 	}
 }
 
+/// Retrieve the transaction entries that match the query, sorted and paginated.
+/// If `parent_key_id` is set, only return entries from that key
+pub fn query_txs<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	parent_key_id: Option<&Identifier>,
+	query: &TxQueryArgs,
+) -> Result<Vec<TxLogEntry>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut txs: Vec<TxLogEntry> = wallet
+		.tx_log_iter()
+		.filter(|tx_entry| {
+			let f_pk = match parent_key_id {
+				Some(k) => tx_entry.parent_key_id == *k,
+				None => true,
+			};
+			// Unconfirmed coinbase transactions are hidden the same way as by retrieve_txs
+			let non_confirmed_coinbase =
+				!tx_entry.confirmed && (tx_entry.tx_type == TxLogEntryType::ConfirmedCoinbase);
+
+			f_pk && !non_confirmed_coinbase && query.matches(tx_entry)
+		})
+		.collect();
+
+	query.sort_and_paginate(&mut txs);
+	Ok(txs)
+}
+
 /// Cancel transaction and associated outputs
 pub fn cancel_tx_and_outputs<'a, T: ?Sized, C, K>(
 	wallet: &mut T,
@@ -226,8 +265,25 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
+	// Every slate of the batch payout has its own context, the recipients will never
+	// finish them now
+	let batch_contexts: Vec<Uuid> = tx
+		.batch_payouts
+		.iter()
+		.flatten()
+		.map(|p| p.slate_id)
+		.filter(|id| {
+			wallet
+				.get_private_context(keychain_mask, id.as_bytes(), 0)
+				.is_ok()
+		})
+		.collect();
+
 	let mut batch = wallet.batch(keychain_mask)?;
 
+	for slate_id in &batch_contexts {
+		batch.delete_private_context(slate_id.as_bytes(), 0)?;
+	}
 	for mut o in outputs {
 		// unlock locked outputs
 		//if o.status == OutputStatus::Unconfirmed {   WMC don't delete outputs, we want to keep them mapped to cancelled trasactions
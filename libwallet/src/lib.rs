@@ -80,20 +80,36 @@ pub use crate::slate_versions::{
 };
 pub use api_impl::foreign;
 pub use api_impl::owner;
+pub use api_impl::owner_contacts;
+pub use api_impl::owner_events::{
+	EventNotifier, EventSink, TxEventInfo, TxEventState, WalletEvent, WalletEventState,
+	WalletEventType,
+};
+pub use api_impl::owner_multisig;
+pub use api_impl::owner_payment_requests;
+pub use api_impl::owner_schedule::{self, PaymentExecutor};
 pub use api_impl::owner_swap;
 pub use api_impl::owner_updater::StatusMessage;
+pub use api_impl::owner_utxo;
 pub use api_impl::types::{
-	BlockFees, InitTxArgs, InitTxSendArgs, IssueInvoiceTxArgs, NodeHeightResult,
-	OutputCommitMapping, PaymentProof, SendTXArgs, SwapStartArgs, VersionInfo,
+	BatchPayout, BlockFees, CoinSelectionStrategy, ConsolidateArgs, FeeEstimate, InitTxArgs,
+	InitTxSendArgs, IssueInvoiceTxArgs, NodeHeightResult, OutputCommitMapping, PaymentProof,
+	PaymentRequestArgs, SchedulePaymentArgs, SendTXArgs, SplitArgs, SwapStartArgs, TxExportArgs,
+	TxExportFormat, TxExportRecord, TxQueryArgs, TxQuerySortField, TxQuerySortOrder, UtxoTx,
+	VersionInfo,
 };
 pub use internal::scan::scan;
 pub use proof::tx_proof::TxProof;
 pub use proof::tx_proof::{proof_ok, verify_tx_proof_wrapper};
 pub use slate_versions::ser as dalek_ser;
 pub use types::{
-	AcctPathMapping, BlockIdentifier, CbData, Context, HeaderInfo, NodeClient, NodeVersionInfo,
-	OutputData, OutputStatus, ScannedBlockInfo, StoredProofInfo, TxLogEntry, TxLogEntryType,
-	WalletBackend, WalletInfo, WalletInst, WalletLCProvider, WalletOutputBatch,
+	AcctPathMapping, BatchPayoutEntry, BlockIdentifier, CbData, Contact, Context, HeaderInfo,
+	MultisigAccount, MultisigLocal, MultisigParticipant, MultisigSpend, MultisigStatus,
+	MultisigTxInfo, NodeClient, NodeVersionInfo, OutputData, OutputMetadata, OutputStatus,
+	PaymentRequest, PaymentRequestStatus, ScanCheckpoint, ScanConfig, ScannedBlockInfo,
+	ScheduledPayment, ScheduledPaymentRun, ScheduledPaymentStatus, StoredProofInfo, TxLogEntry,
+	TxLogEntryType, ViewKey, WalletBackend, WalletInfo, WalletInst, WalletLCProvider,
+	WalletOutputBatch,
 };
 
 pub use api_impl::foreign::{get_receive_account, set_receive_account};
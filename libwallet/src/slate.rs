@@ -903,7 +903,9 @@ impl Slate {
 	where
 		K: Keychain,
 	{
-		if self.compact_slate {
+		// Once every participant added the public excess, the sum is the final excess.
+		// Batch payout slates need that, they are not balanced until aggregated.
+		if self.compact_slate || self.participant_data.len() >= self.num_participants {
 			let sum = self.pub_blind_sum()?;
 			Ok(Commitment::from_pubkey(&sum)?)
 		} else {
@@ -925,6 +927,64 @@ impl Slate {
 		}
 	}
 
+	/// Signs the kernel of the batch payout slate. Unlike `finalize` the transaction is not
+	/// validated, it is balanced only when aggregated with the rest of the batch.
+	pub fn finalize_batch_kernel<K>(&mut self, keychain: &K) -> Result<(), Error>
+	where
+		K: Keychain,
+	{
+		let final_sig = self.finalize_signature(keychain.secp())?;
+		let final_excess = self.calc_excess(Some(keychain))?;
+
+		if self.tx.kernels().len() != 1 {
+			return Err(ErrorKind::SlateValidation(format!(
+				"Batch slate {} expected to have a single kernel",
+				self.id
+			))
+			.into());
+		}
+		self.tx.body.kernels[0].excess = final_excess;
+		self.tx.body.kernels[0].excess_sig = final_sig;
+		self.tx.kernels()[0].verify()?;
+		Ok(())
+	}
+
+	/// Aggregates the finalized batch payout slates into a single transaction.
+	/// Only the first slate is expected to have inputs, the offsets are summed up.
+	pub fn aggregate_batch<K>(keychain: &K, slates: &[Slate]) -> Result<Transaction, Error>
+	where
+		K: Keychain,
+	{
+		let first = slates.first().ok_or(ErrorKind::GenericError(
+			"Batch payout doesn't have any slates".to_string(),
+		))?;
+		let mut final_tx = first.tx.clone();
+		let mut offset = BlindSum::new().add_blinding_factor(first.tx.offset.clone());
+		for slate in &slates[1..] {
+			if !slate.tx.body.inputs.is_empty() {
+				return Err(ErrorKind::SlateValidation(format!(
+					"Batch slate {} is not expected to have inputs",
+					slate.id
+				))
+				.into());
+			}
+			for output in slate.tx.outputs() {
+				final_tx = final_tx.with_output(output.clone());
+			}
+			for kernel in slate.tx.kernels() {
+				final_tx = final_tx.with_kernel(kernel.clone());
+			}
+			offset = offset.add_blinding_factor(slate.tx.offset.clone());
+		}
+		final_tx.offset = keychain.blind_sum(&offset)?;
+
+		// confirm the aggregated transaction balances, including the kernel sums
+		debug!("Validating aggregated batch transaction");
+		let verifier_cache = Arc::new(RwLock::new(LruVerifierCache::new()));
+		final_tx.validate(Weighting::AsTransaction, verifier_cache)?;
+		Ok(final_tx)
+	}
+
 	/// builds a final transaction after the aggregated sig exchange
 	fn finalize_transaction<K>(
 		&mut self,
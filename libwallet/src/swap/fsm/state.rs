@@ -299,7 +299,7 @@ pub enum Input {
 }
 
 /// Respond result
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct StateProcessRespond {
 	/// next state (new current state)
 	pub next_state_id: StateId,
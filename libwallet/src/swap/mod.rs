@@ -323,6 +323,7 @@ mod tests {
 		fn reset_cache(&self) {
 			unimplemented!()
 		}
+		fn start_cycle(&self) {}
 		fn post_tx(&self, tx: &Transaction, _fluff: bool) -> Result<(), crate::Error> {
 			tx.validate(
 				Weighting::AsTransaction,
@@ -91,6 +91,11 @@ pub fn get_electrumx_uri(
 	Ok((uri1, uri2))
 }
 
+/// Check if the swap deals storage was initialized by init_swap_trade_backend
+pub fn is_swap_trade_backend_initialized() -> bool {
+	TRADE_DEALS_PATH.read().is_some()
+}
+
 /// List available swap trades.
 pub fn list_swap_trades() -> Result<Vec<String>, ErrorKind> {
 	let mut result: Vec<String> = Vec::new();
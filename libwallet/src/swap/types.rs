@@ -1065,6 +1065,7 @@ impl fmt::Display for Action {
 
 /// Status of the transactions that can be published.
 ///  None for confirmations - Unable to verify, probably Transaction data is not here.
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SwapTransactionsConfirmations {
 	/// MWC node tip
 	pub mwc_tip: u64,
@@ -15,22 +15,28 @@
 //! Types and traits that should be provided by a wallet
 //! implementation
 
-use crate::config::{MQSConfig, TorConfig, WalletConfig};
+use crate::blake2::blake2b::blake2b;
+use crate::config::{MQSConfig, TorConfig, WalletConfig, WalletDbBackend};
 use crate::error::{Error, ErrorKind};
 use crate::grin_core::core::hash::Hash;
 use crate::grin_core::core::{Output, Transaction, TxKernel};
 use crate::grin_core::libtx::{aggsig, secp_ser};
 use crate::grin_core::{global, ser};
-use crate::grin_keychain::{Identifier, Keychain};
+use crate::grin_keychain::{Identifier, Keychain, SwitchCommitmentType};
 use crate::grin_util::logger::LoggingConfig;
 use crate::grin_util::secp::key::{PublicKey, SecretKey, ZERO_KEY};
 use crate::grin_util::secp::pedersen::Commitment;
 use crate::grin_util::secp::{self, pedersen, Secp256k1};
 use crate::grin_util::ZeroingString;
-use crate::proof::proofaddress::ProvableAddress;
+use crate::internal::scan::OutputResult;
+use crate::proof::proofaddress::{self, ProvableAddress};
+use crate::proof::tx_proof::TxProof;
 use crate::slate::ParticipantMessages;
+use crate::swap::multisig::ParticipantData as MultisigParticipantData;
+use crate::swap::ser::{seckey_from_hex, seckey_to_hex, slate_deser};
 use crate::InitTxArgs;
 use crate::Slate;
+use crate::WalletEventState;
 use chrono::prelude::*;
 use grin_util::ToHex;
 use grin_wallet_util::grin_core::core::Committed;
@@ -67,6 +73,13 @@ where
 	/// default is assumed to be ~/.grin/main/wallet_data (or floonet equivalent)
 	fn get_top_level_directory(&self) -> Result<String, Error>;
 
+	/// Sets the DB backend of the wallets created or opened after the call, default is LMDB
+	fn set_db_backend(&mut self, backend: WalletDbBackend) -> Result<(), Error>;
+
+	/// Sets the update and scan settings of the open wallet and of the wallets created or
+	/// opened after the call
+	fn set_scan_config(&mut self, config: ScanConfig) -> Result<(), Error>;
+
 	/// Output a grin-wallet.toml file into the current top-level system wallet directory
 	fn create_config(
 		&self,
@@ -89,6 +102,15 @@ where
 		wallet_data_dir: Option<&str>,
 	) -> Result<(), Error>;
 
+	/// Create a watch-only wallet that keeps the view key instead of the seed
+	fn create_view_wallet(
+		&mut self,
+		name: Option<&str>,
+		view_key: &ViewKey,
+		password: ZeroingString,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error>;
+
 	///
 	fn open_wallet(
 		&mut self,
@@ -138,6 +160,45 @@ where
 		wallet_data_dir: Option<&str>,
 	) -> Result<(), Error>;
 
+	/// Packs the wallet seed, database, saved transactions, transaction proofs and swap trades
+	/// into a single archive encrypted with the wallet password
+	fn backup_wallet(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		backup_file: &str,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error>;
+
+	/// Restores the wallet data from the archive created by `backup_wallet`.
+	/// The wallet must not exist.
+	fn restore_wallet(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		backup_file: &str,
+		wallet_data_dir: Option<&str>,
+	) -> Result<(), Error>;
+
+	/// Migrates the wallet DB to the current schema version. Returns the applied migrations
+	/// as (schema version, description), with `dry_run` only reports the pending ones.
+	/// The DB of the opened wallet is already migrated.
+	fn migrate_wallet_db(
+		&self,
+		name: Option<&str>,
+		dry_run: bool,
+		wallet_data_dir: Option<&str>,
+	) -> Result<Vec<(u32, String)>, Error>;
+
+	/// Copies the LMDB wallet DB, saved transactions and payment proofs into a new SQLite DB.
+	/// The LMDB data is kept, the wallet uses SQLite once the DB backend is switched in the
+	/// config. Returns the path of the SQLite DB.
+	fn migrate_wallet_db_to_sqlite(
+		&self,
+		name: Option<&str>,
+		wallet_data_dir: Option<&str>,
+	) -> Result<String, Error>;
+
 	/// deletes wallet
 	fn delete_wallet(&self, name: Option<&str>) -> Result<(), Error>;
 
@@ -175,6 +236,19 @@ where
 	/// Can optionally take a mask value
 	fn keychain(&self, mask: Option<&SecretKey>) -> Result<K, Error>;
 
+	/// Set the view key of the watch-only wallet. Such wallet has no keychain, it can only
+	/// find its outputs at the chain
+	fn set_view_key(&mut self, view_key: ViewKey);
+
+	/// View key if this is a watch-only wallet
+	fn view_key(&self) -> Option<ViewKey>;
+
+	/// Set the update and scan settings of the wallet
+	fn set_scan_config(&mut self, config: ScanConfig);
+
+	/// Update and scan settings of the wallet
+	fn scan_config(&self) -> ScanConfig;
+
 	/// Return the client being used to communicate with the node
 	fn w2n_client(&mut self) -> &mut C;
 
@@ -231,6 +305,12 @@ where
 	/// Load a txn from specified file
 	fn load_stored_tx(&self, path: &str) -> Result<Transaction, Error>;
 
+	/// Stores the payment proof of the transaction with the slate id
+	fn store_tx_proof(&self, uuid: &str, proof: &TxProof) -> Result<(), Error>;
+
+	/// Retrieves the stored payment proof of the transaction with the slate id
+	fn get_stored_tx_proof(&self, uuid: &str) -> Result<TxProof, Error>;
+
 	/// Create a new write batch to update or remove output data
 	fn batch<'a>(
 		&'a mut self,
@@ -255,6 +335,55 @@ where
 
 	/// last block scanned during scan or restore
 	fn last_scanned_blocks(&mut self) -> Result<Vec<ScannedBlockInfo>, Error>;
+
+	/// Progress of the last scan, it is deleted when the scan is finished
+	fn get_scan_checkpoint(&self) -> Result<Option<ScanCheckpoint>, Error>;
+
+	/// Outputs found by the last scan before its checkpoint, ordered by the PMMR index
+	fn scan_checkpoint_outputs(&self) -> Result<Vec<OutputResult>, Error>;
+
+	/// Wallet state that was reported to the event sink, with the undelivered events
+	fn get_event_state(&self) -> Result<Option<WalletEventState>, Error>;
+
+	/// Outputs with the commit, found by the commit index
+	fn get_outputs_by_commit(&self, commit: &str) -> Result<Vec<OutputData>, Error>;
+
+	/// Transaction log entries of the slate, found by the slate id index. Self sends have
+	/// two entries, one per account.
+	fn get_tx_log_by_slate_id(&self, slate_id: &Uuid) -> Result<Vec<TxLogEntry>, Error>;
+
+	/// Transaction log entries with the kernel excess, found by the kernel index
+	fn get_tx_log_by_kernel_excess(&self, excess: &Commitment) -> Result<Vec<TxLogEntry>, Error>;
+
+	/// Gets user metadata (label, note, frozen flag) for an output commit
+	fn get_output_metadata(&self, commit: &str) -> Result<Option<OutputMetadata>, Error>;
+
+	/// Iterate over all stored output metadata
+	fn output_metadata_iter<'a>(&'a self) -> Box<dyn Iterator<Item = OutputMetadata> + 'a>;
+
+	/// Gets a multisig account by id
+	fn get_multisig_account(&self, id: &Uuid) -> Result<Option<MultisigAccount>, Error>;
+
+	/// Iterate over all multisig accounts
+	fn multisig_account_iter<'a>(&'a self) -> Box<dyn Iterator<Item = MultisigAccount> + 'a>;
+
+	/// Gets a scheduled payment by id
+	fn get_scheduled_payment(&self, id: &Uuid) -> Result<Option<ScheduledPayment>, Error>;
+
+	/// Iterate over all scheduled payments
+	fn scheduled_payment_iter<'a>(&'a self) -> Box<dyn Iterator<Item = ScheduledPayment> + 'a>;
+
+	/// Gets an address book contact by name
+	fn get_contact(&self, name: &str) -> Result<Option<Contact>, Error>;
+
+	/// Iterate over the address book
+	fn contact_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Contact> + 'a>;
+
+	/// Gets a payment request by id
+	fn get_payment_request(&self, id: &Uuid) -> Result<Option<PaymentRequest>, Error>;
+
+	/// Iterate over all payment requests
+	fn payment_request_iter<'a>(&'a self) -> Box<dyn Iterator<Item = PaymentRequest> + 'a>;
 }
 
 /// Batch trait to update the output data backend atomically. Trying to use a
@@ -298,6 +427,18 @@ where
 		block: &Vec<ScannedBlockInfo>,
 	) -> Result<(), Error>;
 
+	/// Save the scan progress
+	fn save_scan_checkpoint(&mut self, checkpoint: &ScanCheckpoint) -> Result<(), Error>;
+
+	/// Add an output found by the scan to its checkpoint
+	fn save_scan_checkpoint_output(&mut self, output: &OutputResult) -> Result<(), Error>;
+
+	/// Delete the scan checkpoint together with its outputs
+	fn delete_scan_checkpoint(&mut self) -> Result<(), Error>;
+
+	/// Save the wallet state that was reported to the event sink
+	fn save_event_state(&mut self, state: &WalletEventState) -> Result<(), Error>;
+
 	/// get next tx log entry for the parent
 	fn next_tx_log_id(&mut self, parent_key_id: &Identifier) -> Result<u32, Error>;
 
@@ -324,6 +465,30 @@ where
 	/// Save an output as locked in the backend
 	fn lock_output(&mut self, out: &mut OutputData) -> Result<(), Error>;
 
+	/// Save user metadata for an output
+	fn save_output_metadata(&mut self, metadata: &OutputMetadata) -> Result<(), Error>;
+
+	/// Delete user metadata for an output commit
+	fn delete_output_metadata(&mut self, commit: &str) -> Result<(), Error>;
+
+	/// Add or update a multisig account
+	fn save_multisig_account(&mut self, account: &MultisigAccount) -> Result<(), Error>;
+
+	/// Add or update a scheduled payment
+	fn save_scheduled_payment(&mut self, payment: &ScheduledPayment) -> Result<(), Error>;
+
+	/// Delete a scheduled payment
+	fn delete_scheduled_payment(&mut self, id: &Uuid) -> Result<(), Error>;
+
+	/// Add or update an address book contact
+	fn save_contact(&mut self, contact: &Contact) -> Result<(), Error>;
+
+	/// Delete an address book contact
+	fn delete_contact(&mut self, name: &str) -> Result<(), Error>;
+
+	/// Add or update a payment request
+	fn save_payment_request(&mut self, request: &PaymentRequest) -> Result<(), Error>;
+
 	/// Saves the private context associated with a slate id
 	fn save_private_context(
 		&mut self,
@@ -361,6 +526,10 @@ pub trait NodeClient: Send + Sync + Clone {
 	/// Reset cache data
 	fn reset_cache(&self);
 
+	/// Start of a wallet update or scan. A client that works with several nodes uses the
+	/// same node until the next start, unless that node fails.
+	fn start_cycle(&self);
+
 	/// Posts a transaction to a grin node
 	fn post_tx(&self, tx: &Transaction, fluff: bool) -> Result<(), Error>;
 
@@ -992,6 +1161,12 @@ pub struct TxLogEntry {
 	/// Output commits as Strings, defined for send & recieve
 	#[serde(default = "TxLogEntry::default_commits")]
 	pub output_commits: Vec<pedersen::Commitment>,
+	/// Recipients of a batch payout, None for the regular transactions
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub batch_payouts: Option<Vec<BatchPayoutEntry>>,
+	/// Multisig account this transaction funds or spends
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub multisig: Option<MultisigTxInfo>,
 }
 
 impl ser::Writeable for TxLogEntry {
@@ -1045,6 +1220,8 @@ impl TxLogEntry {
 			payment_proof: None,
 			input_commits: vec![],
 			output_commits: vec![],
+			batch_payouts: None,
+			multisig: None,
 		}
 	}
 
@@ -1098,6 +1275,8 @@ impl TxLogEntry {
 			payment_proof,
 			input_commits,
 			output_commits,
+			batch_payouts: None,
+			multisig: None,
 		}
 	}
 
@@ -1161,6 +1340,44 @@ impl TxLogEntry {
 	}
 }
 
+/// Recipient of a batch payout. Every recipient gets its own slate and signs its own kernel,
+/// all kernels end up in the single batch transaction.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchPayoutEntry {
+	/// Slate exchanged with the recipient
+	pub slate_id: Uuid,
+	/// Address of the recipient
+	#[serde(default)]
+	pub address: Option<String>,
+	/// Amount paid to the recipient
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Part of the transaction fee paid by the recipient's kernel
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub fee: u64,
+	/// Recipient's kernel excess, known after the batch is finalized
+	#[serde(with = "secp_ser::option_commitment_serde")]
+	#[serde(default)]
+	pub kernel_excess: Option<pedersen::Commitment>,
+}
+
+/// Multisig account transaction. The funding transaction is recorded by the co-signer that
+/// pays for the shared output, the spending one by the co-signer that receives the funds.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigTxInfo {
+	/// Multisig account
+	pub account_id: Uuid,
+	/// True for the funding transaction, false for the spending one
+	pub funding: bool,
+	/// Shared output amount
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Shared output commitment, known once every co-signer joined the account
+	#[serde(with = "secp_ser::option_commitment_serde")]
+	#[serde(default)]
+	pub commit: Option<pedersen::Commitment>,
+}
+
 /// Payment proof information. Differs from what is sent via
 /// the slate
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1234,6 +1451,551 @@ impl ser::Readable for AcctPathMapping {
 	}
 }
 
+/// User defined metadata for an output. Stored separately from the output data,
+/// so it survives output updates from the node and wallet rescans.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub struct OutputMetadata {
+	/// Output commitment, hex encoded
+	pub commit: String,
+	/// Short label for the output
+	#[serde(default)]
+	pub label: Option<String>,
+	/// Free text note
+	#[serde(default)]
+	pub note: Option<String>,
+	/// Frozen outputs are never selected as transaction inputs
+	#[serde(default)]
+	pub frozen: bool,
+}
+
+impl OutputMetadata {
+	/// Create empty metadata for the commit
+	pub fn new(commit: String) -> Self {
+		OutputMetadata {
+			commit,
+			..Default::default()
+		}
+	}
+
+	/// True if there is nothing to store
+	pub fn is_empty(&self) -> bool {
+		self.label.is_none() && self.note.is_none() && !self.frozen
+	}
+}
+
+impl ser::Writeable for OutputMetadata {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("OutputMetadata to json conversion failed, {}", e))
+		})?;
+		if data.len() > ser::READ_CHUNK_LIMIT {
+			return Err(ser::Error::TooLargeWriteErr(format!(
+				"OutputMetadata data length is {}",
+				data.len()
+			)));
+		}
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for OutputMetadata {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<OutputMetadata, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to OutputMetadata conversion failed, {}", e))
+		})
+	}
+}
+
+/// Stage of a multisig account
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MultisigStatus {
+	/// Co-signers are joining the account
+	Joining,
+	/// Co-signers are building the shared output and signing the funding transaction
+	Funding,
+	/// Funding transaction is finalized, the shared output can be spent once it is confirmed
+	Funded,
+	/// Co-signers are signing the transaction that spends the shared output
+	Spending,
+	/// Spending transaction is finalized
+	Spent,
+}
+
+impl fmt::Display for MultisigStatus {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			MultisigStatus::Joining => write!(f, "Joining"),
+			MultisigStatus::Funding => write!(f, "Funding"),
+			MultisigStatus::Funded => write!(f, "Funded"),
+			MultisigStatus::Spending => write!(f, "Spending"),
+			MultisigStatus::Spent => write!(f, "Spent"),
+		}
+	}
+}
+
+/// Public data of a multisig account co-signer
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigParticipant {
+	/// Co-signer public key. The secret is the co-signer part of the shared output blinding factor
+	#[serde(with = "secp_ser::pubkey_serde")]
+	pub public_key: PublicKey,
+	/// Signature of the account id and the public key, proves that the co-signer knows the secret
+	#[serde(with = "secp_ser::sig_serde")]
+	pub key_proof: secp::Signature,
+	/// Co-signer data for the shared output bulletproof
+	pub bulletproof: MultisigParticipantData,
+	/// Commitments to the co-signer secret sharing polynomial, hex encoded. Empty if every
+	/// co-signer has to sign the spending transaction.
+	#[serde(default)]
+	pub commitments: Vec<String>,
+	/// Secret shares of the co-signer key, encrypted for every other co-signer, hex encoded
+	#[serde(default)]
+	pub shares: Vec<Option<String>>,
+}
+
+/// Transaction that spends the shared output of a multisig account
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigSpend {
+	/// Co-signers that sign the transaction. The first one started the spending, the funds
+	/// go to its wallet unless there is a recipient. The change goes to its wallet too.
+	pub signers: Vec<usize>,
+	/// Recipient address, None if the funds go to the wallet that started the spending
+	#[serde(default)]
+	pub address: Option<String>,
+	/// Spending transaction slate, signers are the slate participants in the same order.
+	/// The recipient receives it as a regular send slate, so it is the participant 1.
+	#[serde(deserialize_with = "slate_deser")]
+	pub slate: Slate,
+}
+
+impl MultisigSpend {
+	/// Slate participant id of the recipient
+	pub const RECIPIENT_ID: usize = 1;
+
+	/// Slate participant id of the signer at `pos` in `signers`
+	pub fn participant_id(&self, pos: usize) -> usize {
+		if self.address.is_some() && pos >= MultisigSpend::RECIPIENT_ID {
+			pos + 1
+		} else {
+			pos
+		}
+	}
+}
+
+/// Data of this wallet co-signer, it is never shared
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigLocal {
+	/// Index of this wallet co-signer
+	pub index: usize,
+	/// Derivation of the co-signer secret
+	pub key_id: Identifier,
+	/// Wallet account that funds or receives the shared output
+	pub parent_key_id: Identifier,
+}
+
+/// Multisig account. Co-signers pass the account to each other and every one of them
+/// processes it, until the shared output is funded. Spending goes the same way.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigAccount {
+	/// Account id
+	pub id: Uuid,
+	/// Number of co-signers
+	pub num_participants: usize,
+	/// Number of co-signers that are needed to spend the shared output
+	pub threshold: usize,
+	/// Shared output amount
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Nonce shared by the co-signers for the shared output bulletproof
+	#[serde(serialize_with = "seckey_to_hex", deserialize_with = "seckey_from_hex")]
+	pub common_nonce: SecretKey,
+	/// Amount that every co-signer pays for the shared output, in the order of joining.
+	/// Empty if the first co-signer pays for all of it.
+	#[serde(default)]
+	pub contributions: Vec<u64>,
+	/// Co-signers in the order of joining. The first one creates the funding transaction.
+	pub participants: Vec<MultisigParticipant>,
+	/// Funding transaction slate, co-signers are the slate participants
+	#[serde(deserialize_with = "slate_deser")]
+	pub funding: Slate,
+	/// Shared output commitment, known once every co-signer joined
+	#[serde(with = "secp_ser::option_commitment_serde")]
+	#[serde(default)]
+	pub commit: Option<pedersen::Commitment>,
+	/// Spending of the shared output, if started
+	#[serde(default)]
+	pub spend: Option<MultisigSpend>,
+	/// Stage of the account
+	pub status: MultisigStatus,
+	/// This wallet co-signer, None for the shared copy of the account
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub local: Option<MultisigLocal>,
+}
+
+impl MultisigAccount {
+	/// Copy of the account that can be passed to the other co-signers
+	pub fn export(&self) -> MultisigAccount {
+		let mut account = self.clone();
+		account.local = None;
+		account
+	}
+}
+
+impl ser::Writeable for MultisigAccount {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("MultisigAccount to json conversion failed, {}", e))
+		})?;
+		if data.len() > ser::READ_CHUNK_LIMIT {
+			return Err(ser::Error::TooLargeWriteErr(format!(
+				"MultisigAccount data length is {}",
+				data.len()
+			)));
+		}
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for MultisigAccount {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<MultisigAccount, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to MultisigAccount conversion failed, {}", e))
+		})
+	}
+}
+
+/// State of a scheduled payment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ScheduledPaymentStatus {
+	/// Payment is sent at the next run time
+	Active,
+	/// Payment is paused by the user
+	Paused,
+	/// One time payment is sent
+	Completed,
+	/// One time payment failed after all retries
+	Failed,
+}
+
+impl fmt::Display for ScheduledPaymentStatus {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ScheduledPaymentStatus::Active => write!(f, "Active"),
+			ScheduledPaymentStatus::Paused => write!(f, "Paused"),
+			ScheduledPaymentStatus::Completed => write!(f, "Completed"),
+			ScheduledPaymentStatus::Failed => write!(f, "Failed"),
+		}
+	}
+}
+
+/// Send attempt of a scheduled payment
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScheduledPaymentRun {
+	/// Time of the attempt
+	pub time: DateTime<Utc>,
+	/// Retry number, 0 for the first attempt
+	pub retry: u32,
+	/// Slate of the sent transaction
+	#[serde(default)]
+	pub tx_slate_id: Option<Uuid>,
+	/// Error if the send failed
+	#[serde(default)]
+	pub error: Option<String>,
+}
+
+/// Payment that the wallet sends at the scheduled time, once or with a fixed interval.
+/// Payments are sent by the updater thread with the configured sender.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScheduledPayment {
+	/// Payment id
+	pub id: Uuid,
+	/// Account to send from, the active account if None
+	#[serde(default)]
+	pub src_acct_name: Option<String>,
+	/// Amount to send
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Minimum confirmations of the spent outputs
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub minimum_confirmations: u64,
+	/// Transaction message
+	#[serde(default)]
+	pub message: Option<String>,
+	/// Send method, 'http' or 'mwcmqs'
+	pub method: String,
+	/// Destination address
+	pub dest: String,
+	/// Receiver wallet api secret, applicable to http only
+	#[serde(default)]
+	pub apisecret: Option<String>,
+	/// Whether to skip the dandelion relay when posting
+	#[serde(default)]
+	pub fluff: bool,
+	/// Interval between the payments in seconds, None for a one time payment
+	#[serde(default)]
+	pub interval_secs: Option<u64>,
+	/// Time of the next payment
+	pub next_run: DateTime<Utc>,
+	/// Time of the next retry of the failed payment
+	#[serde(default)]
+	pub retry_at: Option<DateTime<Utc>>,
+	/// Number of the failed attempts of the current payment
+	#[serde(default)]
+	pub retries: u32,
+	/// Number of retries before the payment is given up
+	pub max_retries: u32,
+	/// Payment state
+	pub status: ScheduledPaymentStatus,
+	/// Latest send attempts, newest last
+	#[serde(default)]
+	pub history: Vec<ScheduledPaymentRun>,
+	/// Slate id of the send that is in progress. It is saved before the send, so the
+	/// transaction is found if the send fails or the wallet stops before it is recorded.
+	#[serde(default)]
+	pub in_flight: Option<Uuid>,
+}
+
+impl ScheduledPayment {
+	/// Time when the payment should be sent next, None if it is not active
+	pub fn due_time(&self) -> Option<DateTime<Utc>> {
+		match self.status {
+			ScheduledPaymentStatus::Active => Some(self.retry_at.unwrap_or(self.next_run)),
+			_ => None,
+		}
+	}
+}
+
+impl ser::Writeable for ScheduledPayment {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("ScheduledPayment to json conversion failed, {}", e))
+		})?;
+		if data.len() > ser::READ_CHUNK_LIMIT {
+			return Err(ser::Error::TooLargeWriteErr(format!(
+				"ScheduledPayment data length is {}",
+				data.len()
+			)));
+		}
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for ScheduledPayment {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<ScheduledPayment, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to ScheduledPayment conversion failed, {}", e))
+		})
+	}
+}
+
+/// Address book entry. The name can be used instead of the address for send, swap and
+/// invoice, the defaults of the contact are applied if not specified explicitly.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Contact {
+	/// Contact name, unique in the wallet
+	pub name: String,
+	/// Transport address: http(s) url, Tor or MQS address
+	pub address: String,
+	/// Default send method, 'http' or 'mwcmqs'
+	pub method: String,
+	/// Public key for the payment proofs and the slatepack encryption
+	#[serde(
+		serialize_with = "proofaddress::option_as_string",
+		deserialize_with = "proofaddress::option_proof_address_from_string"
+	)]
+	#[serde(default)]
+	pub proof_address: Option<ProvableAddress>,
+	/// Receiver wallet api secret, applicable to http only
+	#[serde(default)]
+	pub apisecret: Option<String>,
+}
+
+impl ser::Writeable for Contact {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("Contact to json conversion failed, {}", e))
+		})?;
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for Contact {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<Contact, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to Contact conversion failed, {}", e))
+		})
+	}
+}
+
+/// State of a payment request
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PaymentRequestStatus {
+	/// Request is waiting for the payment
+	Open,
+	/// Transaction of the request is confirmed
+	Paid,
+	/// Request expired before it was paid
+	Expired,
+	/// Request is cancelled by the payee
+	Cancelled,
+}
+
+impl fmt::Display for PaymentRequestStatus {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			PaymentRequestStatus::Open => write!(f, "Open"),
+			PaymentRequestStatus::Paid => write!(f, "Paid"),
+			PaymentRequestStatus::Expired => write!(f, "Expired"),
+			PaymentRequestStatus::Cancelled => write!(f, "Cancelled"),
+		}
+	}
+}
+
+/// Request for a payment to this wallet. The request is shared as a URI. The paying wallet
+/// uses the request id as the slate id, the same is done for the invoice issued for the
+/// request, so the transaction is matched back to the request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PaymentRequest {
+	/// Request id, the slate id of the paying transaction
+	pub id: Uuid,
+	/// Requested amount
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Payee address to send the payment to: http(s) url, Tor or MQS address
+	pub address: String,
+	/// Payment description, used as the transaction message
+	#[serde(default)]
+	pub memo: Option<String>,
+	/// Request is not accepted after this height
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	#[serde(default)]
+	pub expiry_height: Option<u64>,
+	/// Request is not accepted after this time
+	#[serde(default)]
+	pub expiry_time: Option<DateTime<Utc>>,
+	/// If set, the payer must request a payment proof from this address
+	#[serde(
+		serialize_with = "proofaddress::option_as_string",
+		deserialize_with = "proofaddress::option_proof_address_from_string"
+	)]
+	#[serde(default)]
+	pub proof_address: Option<ProvableAddress>,
+	/// Time when the request was created
+	pub created: DateTime<Utc>,
+	/// Request state
+	pub status: PaymentRequestStatus,
+}
+
+impl PaymentRequest {
+	/// Check if the request is expired at this height and time
+	pub fn is_expired(&self, height: u64, time: DateTime<Utc>) -> bool {
+		self.expiry_height.map(|h| height > h).unwrap_or(false)
+			|| self.expiry_time.map(|t| time > t).unwrap_or(false)
+	}
+}
+
+impl ser::Writeable for PaymentRequest {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("PaymentRequest to json conversion failed, {}", e))
+		})?;
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for PaymentRequest {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<PaymentRequest, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to PaymentRequest conversion failed, {}", e))
+		})
+	}
+}
+
+/// Rewind-only key of the wallet, the hash of its public root key. It finds the wallet outputs
+/// on the chain and reads their amounts, but can't sign or spend. Outputs with the legacy
+/// range proofs (built before the first hard fork) can't be rewound with it.
+#[derive(Clone, PartialEq)]
+pub struct ViewKey {
+	rewind_hash: Vec<u8>,
+}
+
+impl ViewKey {
+	/// View key of the keychain
+	pub fn from_keychain<K: Keychain>(keychain: &K) -> ViewKey {
+		let root_public_key = keychain.public_root_key().serialize_vec(true);
+		ViewKey {
+			rewind_hash: blake2b(32, &[], &root_public_key[..]).as_bytes().to_vec(),
+		}
+	}
+
+	/// Restore the view key from its bytes
+	pub fn from_bytes(bytes: &[u8]) -> Result<ViewKey, Error> {
+		if bytes.len() != 32 {
+			return Err(ErrorKind::ViewKeyError(format!(
+				"Expecting 32 bytes, get {}",
+				bytes.len()
+			))
+			.into());
+		}
+		Ok(ViewKey {
+			rewind_hash: bytes.to_vec(),
+		})
+	}
+
+	/// Restore the view key from the exported hex string
+	pub fn from_hex(hex: &str) -> Result<ViewKey, Error> {
+		let bytes = grin_util::from_hex(hex.trim()).map_err(|e| {
+			ErrorKind::ViewKeyError(format!("Unable to parse view key {}, {}", hex, e))
+		})?;
+		ViewKey::from_bytes(&bytes)
+	}
+
+	/// Bytes of the key
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.rewind_hash
+	}
+
+	/// Hex string to export
+	pub fn to_hex(&self) -> String {
+		grin_util::to_hex(&self.rewind_hash)
+	}
+
+	/// Rewind the output range proof. Returns the amount, key id and switch commitment type
+	/// if the output belongs to the wallet.
+	pub fn rewind(
+		&self,
+		secp: &Secp256k1,
+		commit: &Commitment,
+		proof: &pedersen::RangeProof,
+	) -> Result<Option<(u64, Identifier, SwitchCommitmentType)>, Error> {
+		// Same nonce as the keychain proof builder uses
+		let nonce = blake2b(32, &commit.0, &self.rewind_hash);
+		let nonce = SecretKey::from_slice(nonce.as_bytes())?;
+		let info = match secp.rewind_bullet_proof(*commit, nonce, None, *proof) {
+			Ok(info) => info,
+			Err(_) => return Ok(None),
+		};
+
+		// Rewind checks the commitment, the message carries the switch type and the key id
+		let msg = info.message.as_bytes();
+		if msg.len() != 20 || msg[0] != 0 || msg[1] != 0 || msg[3] > 4 {
+			return Ok(None);
+		}
+		let switch = match msg[2] {
+			0 => SwitchCommitmentType::None,
+			1 => SwitchCommitmentType::Regular,
+			_ => return Ok(None),
+		};
+		let key_id = Identifier::from_bytes(&msg[3..]);
+		Ok(Some((info.value, key_id, switch)))
+	}
+}
+
 /// Store details of the last scanned block
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ScannedBlockInfo {
@@ -1281,6 +2043,97 @@ impl ser::Readable for ScannedBlockInfo {
 	}
 }
 
+/// Update and scan settings of the wallet, they come from the wallet config
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanConfig {
+	/// Number of threads for the UTXO scanning, the bulletproofs are rewound in parallel
+	pub threads: usize,
+	/// Private update mode, the wallet commits are never sent to the node. The outputs and
+	/// kernels are downloaded by height ranges and matched locally.
+	pub private_update: bool,
+	/// Deeper reorgs are not reverted by the wallet update, they need the manual scan
+	pub max_reorg_depth: u64,
+}
+
+impl Default for ScanConfig {
+	fn default() -> ScanConfig {
+		ScanConfig {
+			threads: 4,
+			private_update: false,
+			max_reorg_depth: 1440,
+		}
+	}
+}
+
+impl ScanConfig {
+	/// Settings from the wallet config, the defaults are used for the missing values
+	pub fn from_wallet_config(config: &WalletConfig) -> ScanConfig {
+		let default = ScanConfig::default();
+		ScanConfig {
+			threads: config.scan_threads.unwrap_or(default.threads),
+			private_update: config.private_update.unwrap_or(default.private_update),
+			max_reorg_depth: config.max_reorg_depth.unwrap_or(default.max_reorg_depth),
+		}
+	}
+}
+
+/// Progress of the wallet scan. The scan saves it after every batch of the PMMR range
+/// together with the outputs found so far, an interrupted scan is resumed from it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScanCheckpoint {
+	/// First scanned height
+	pub start_height: u64,
+	/// Last scanned height, the chain tip when the scan was started
+	pub end_height: u64,
+	/// Hash of the block at end_height. The scan can't be resumed if the chain was changed.
+	pub end_block_hash: String,
+	/// Whether the scan deletes unconfirmed outputs and transactions
+	pub delete_unconfirmed: bool,
+	/// Next PMMR index to scan, None until the scan of the PMMR range is started
+	pub next_pmmr_index: Option<u64>,
+}
+
+impl ScanCheckpoint {
+	/// Checkpoint of the new scan
+	pub fn new(
+		start_height: u64,
+		end_height: u64,
+		end_block_hash: String,
+		delete_unconfirmed: bool,
+	) -> ScanCheckpoint {
+		ScanCheckpoint {
+			start_height,
+			end_height,
+			end_block_hash,
+			delete_unconfirmed,
+			next_pmmr_index: None,
+		}
+	}
+
+	/// Whether the checkpoint belongs to the scan of this height range
+	pub fn is_scan_of(&self, start_height: u64, end_height: u64) -> bool {
+		self.start_height == start_height && self.end_height == end_height
+	}
+}
+
+impl ser::Writeable for ScanCheckpoint {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		let data = serde_json::to_vec(self).map_err(|e| {
+			ser::Error::CorruptedData(format!("ScanCheckpoint to json conversion failed, {}", e))
+		})?;
+		writer.write_bytes(&data)
+	}
+}
+
+impl ser::Readable for ScanCheckpoint {
+	fn read<R: ser::Reader>(reader: &mut R) -> Result<ScanCheckpoint, ser::Error> {
+		let data = reader.read_bytes_len_prefix()?;
+		serde_json::from_slice(&data[..]).map_err(|e| {
+			ser::Error::CorruptedData(format!("json to ScanCheckpoint conversion failed, {}", e))
+		})
+	}
+}
+
 /// Wrapper for reward output and kernel used when building a coinbase for a mining node.
 /// Note: Not serializable, must be converted to necesssary "versioned" representation
 /// before serializing to json to ensure compatibility with mining node.
@@ -25,7 +25,7 @@ use crate::core::global;
 use crate::util::init_logger;
 use clap::App;
 use grin_wallet_config as config;
-use grin_wallet_impls::HTTPNodeClient;
+use grin_wallet_impls::MultiNodeClient;
 use grin_wallet_util::grin_core as core;
 use grin_wallet_util::grin_util as util;
 use std::env;
@@ -162,8 +162,13 @@ fn real_main() -> i32 {
 	// Default derive index is 1 to match what mwc713 has by default...
 	proofaddress::set_address_index(wallet_config.grinbox_address_index.unwrap_or(0));
 
-	let node_client = HTTPNodeClient::new(&wallet_config.check_node_api_http_addr, None)
-		.expect("Unable create HTTP client for mwc-node connection");
+	let mut node_urls = vec![wallet_config.check_node_api_http_addr.clone()];
+	if let Some(addrs) = &wallet_config.backup_node_api_http_addrs {
+		node_urls.extend(addrs.iter().cloned());
+	}
+	let node_client =
+		MultiNodeClient::from_urls(&node_urls, None, wallet_config.node_quorum.unwrap_or(1))
+			.expect("Unable create HTTP client for mwc-node connection");
 
 	cmd::wallet_command(&args, config, node_client)
 }
@@ -20,8 +20,8 @@ use clap::App;
 use grin_wallet_api::Owner;
 use grin_wallet_config::{MQSConfig, TorConfig, WalletConfig};
 use grin_wallet_controller::command::GlobalArgs;
-use grin_wallet_controller::Error;
-use grin_wallet_impls::DefaultWalletImpl;
+use grin_wallet_controller::{Error, ErrorKind};
+use grin_wallet_impls::{create_event_notifier, DefaultWalletImpl};
 use grin_wallet_libwallet::{NodeClient, WalletInst, WalletLCProvider};
 use grin_wallet_util::grin_keychain as keychain;
 use rustyline::completion::{Completer, FilenameCompleter, Pair};
@@ -129,6 +129,11 @@ where
 	// catch updater messages
 	// mwc updater thread is better, it will be created for None
 	let mut owner_api = Owner::new(wallet_inst, None, None);
+	owner_api.set_event_notifier(
+		create_event_notifier(wallet_config).map_err(|e| {
+			ErrorKind::ArgumentError(format!("Invalid events configuration, {}", e))
+		})?,
+	);
 
 	// start the automatic updater
 	owner_api.start_updater((&keychain_mask).as_ref(), Duration::from_secs(60))?;
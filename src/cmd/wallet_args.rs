@@ -20,6 +20,7 @@ use crate::util::file::get_first_line;
 use crate::util::secp::key::SecretKey;
 use crate::util::{Mutex, ZeroingString};
 
+use chrono::prelude::*;
 /// Argument parsing and error handling for wallet commands
 use clap::ArgMatches;
 use ed25519_dalek::SecretKey as DalekSecretKey;
@@ -27,6 +28,7 @@ use failure::Fail;
 use grin_wallet_api::Owner;
 use grin_wallet_config::{MQSConfig, TorConfig, WalletConfig};
 use grin_wallet_controller::command;
+use grin_wallet_controller::hosted_wallets::WalletInstFactory;
 use grin_wallet_controller::{Error, ErrorKind};
 use grin_wallet_impls::tor::config::is_tor_address;
 use grin_wallet_impls::{DefaultLCProvider, DefaultWalletImpl};
@@ -35,7 +37,10 @@ use grin_wallet_libwallet::proof::proofaddress;
 use grin_wallet_libwallet::proof::proofaddress::ProvableAddress;
 use grin_wallet_libwallet::Slate;
 use grin_wallet_libwallet::{
-	IssueInvoiceTxArgs, NodeClient, SwapStartArgs, WalletInst, WalletLCProvider,
+	owner_contacts, owner_payment_requests, CoinSelectionStrategy, Contact, IssueInvoiceTxArgs,
+	NodeClient, PaymentRequestArgs, ScanConfig, SchedulePaymentArgs, SwapStartArgs, TxExportArgs,
+	TxExportFormat, TxLogEntryType, TxQueryArgs, TxQuerySortField, TxQuerySortOrder, WalletInst,
+	WalletLCProvider,
 };
 use grin_wallet_util::grin_core as core;
 use grin_wallet_util::grin_core::core::amount_to_hr_string;
@@ -44,6 +49,8 @@ use grin_wallet_util::grin_keychain as keychain;
 use linefeed::terminal::Signal;
 use linefeed::{Interface, ReadResult};
 use rpassword;
+use std::convert::TryFrom;
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -216,6 +223,8 @@ where
 		as Box<dyn WalletInst<'static, L, C, K>>;
 	let lc = wallet.lc_provider().unwrap();
 	let _ = lc.set_top_level_directory(&config.data_file_dir);
+	let _ = lc.set_db_backend(config.db_backend.unwrap_or_default());
+	let _ = lc.set_scan_config(ScanConfig::from_wallet_config(&config));
 	Ok(Arc::new(Mutex::new(wallet)))
 }
 
@@ -344,6 +353,11 @@ where
 		false => 32,
 		true => 16,
 	};
+	let view_key = args.value_of("view_key").map(|s| s.to_string());
+	if view_key.is_some() && args.is_present("recover") {
+		let msg = format!("view_key and recover can't be used together");
+		return Err(ParseError::ArgumentError(msg));
+	}
 	let recovery_phrase = match args.is_present("recover") {
 		true => Some(prompt_recovery_phrase(wallet)?),
 		false => None,
@@ -351,6 +365,8 @@ where
 
 	if recovery_phrase.is_some() {
 		println!("Please provide a new password for the recovered wallet");
+	} else if view_key.is_some() {
+		println!("Please enter a password for your new watch-only wallet");
 	} else {
 		println!("Please enter a password for your new wallet");
 	}
@@ -365,6 +381,7 @@ where
 		password: password,
 		config: config.clone(),
 		recovery_phrase: recovery_phrase,
+		view_key,
 		restore: false,
 	})
 }
@@ -380,6 +397,18 @@ where
 	})
 }
 
+pub fn parse_backup_args(
+	g_args: &command::GlobalArgs,
+	args: &ArgMatches,
+) -> Result<command::BackupArgs, ParseError> {
+	let file = parse_required(args, "file")?;
+	let password = prompt_password(&g_args.password);
+	Ok(command::BackupArgs {
+		password,
+		file: file.to_owned(),
+	})
+}
+
 pub fn parse_listen_args(
 	config: &mut WalletConfig,
 	tor_config: &mut TorConfig,
@@ -419,17 +448,29 @@ pub fn parse_account_args(account_args: &ArgMatches) -> Result<command::AccountA
 }
 
 pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseError> {
+	// max, send everything and pay the fee from the amount
+	let max = args.is_present("max");
+
 	// amount
-	let amount = parse_required(args, "amount")?;
-	let amount = core::core::amount_from_hr_string(amount);
-	let amount = match amount {
-		Ok(a) => a,
-		Err(e) => {
-			let msg = format!(
-				"Could not parse amount as a number with optional decimal point. e={}",
-				e
-			);
-			return Err(ParseError::ArgumentError(msg));
+	let amount = if max {
+		if args.is_present("amount") {
+			return Err(ParseError::ArgumentError(
+				"Amount can't be specified together with --max".to_string(),
+			));
+		}
+		0
+	} else {
+		let amount = parse_required(args, "amount")?;
+		let amount = core::core::amount_from_hr_string(amount);
+		match amount {
+			Ok(a) => a,
+			Err(e) => {
+				let msg = format!(
+					"Could not parse amount as a number with optional decimal point. e={}",
+					e
+				);
+				return Err(ParseError::ArgumentError(msg));
+			}
 		}
 	};
 
@@ -445,6 +486,8 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 
 	// selection_strategy
 	let selection_strategy = parse_required(args, "selection_strategy")?;
+	let selection_strategy = CoinSelectionStrategy::try_from(selection_strategy)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
 
 	// estimate_selection_strategies
 	let estimate_selection_strategies = args.is_present("estimate_selection_strategies");
@@ -481,7 +524,11 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 
 	let apisecret = args.value_of("apisecret").map(|s| String::from(s));
 
+	// dest can be an address book contact name, the send command resolves it into the address
+	let contact = (method == "http" || method == "mwcmqs") && owner_contacts::is_contact_name(dest);
+
 	if !estimate_selection_strategies
+		&& !contact
 		&& method == "http"
 		&& !dest.starts_with("http://")
 		&& !dest.starts_with("https://")
@@ -518,18 +565,20 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 		}
 	};
 
+	let request_payment_proof =
+		args.is_present("request_payment_proof") || (args.is_present("proof") && method != "file");
 	let payment_proof_address = {
-		match args.is_present("request_payment_proof")
-			|| (args.is_present("proof") && method != "file")
-		{
+		match request_payment_proof {
 			true => {
 				// if the destination address is a TOR address, we don't need the address
 				// separately
 
 				let proof_dest = proofaddress::address_to_pubkey(dest.to_string());
 				match ProvableAddress::from_str(&proof_dest) {
-					Ok(a) => Some(a),
-					Err(_) => {
+					Ok(a) if !contact => Some(a),
+					// the contact proof address is used
+					_ if contact && !args.is_present("proof_address") => None,
+					_ => {
 						let addr = parse_required(args, "proof_address")?;
 						match ProvableAddress::from_str(&proofaddress::address_to_pubkey(
 							addr.to_string(),
@@ -592,7 +641,7 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 			amount: amount,
 			message: message,
 			minimum_confirmations: min_c,
-			selection_strategy: selection_strategy.to_owned(),
+			selection_strategy,
 			estimate_selection_strategies,
 			method: method.to_owned(),
 			dest: dest.to_owned(),
@@ -609,6 +658,11 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 			outputs,
 			slatepack_recipient,
 			late_lock,
+			max,
+			contact,
+			method_is_set: args.occurrences_of("method") != 0,
+			request_payment_proof,
+			payment_request_id: None,
 		})
 	}
 }
@@ -660,11 +714,603 @@ pub fn parse_finalize_args(args: &ArgMatches) -> Result<command::FinalizeArgs, P
 	})
 }
 
+pub fn parse_send_batch_args(args: &ArgMatches) -> Result<command::SendBatchArgs, ParseError> {
+	// recipients file
+	let file = parse_required(args, "file")?;
+	let content = fs::read_to_string(file).map_err(|e| {
+		ParseError::ArgumentError(format!("Unable to read recipients file {}, {}", file, e))
+	})?;
+
+	// method
+	let method = parse_required(args, "method")?;
+
+	let mut recipients = vec![];
+	for (i, line) in content.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with("#") {
+			continue;
+		}
+		let fields: Vec<&str> = line.split(",").map(|s| s.trim()).collect();
+		if fields.len() < 2 || fields.len() > 3 || fields[0].is_empty() {
+			let msg = format!(
+				"Line {} of {}, expecting 'dest,amount[,slatepack_recipient]', get '{}'",
+				i + 1,
+				file,
+				line
+			);
+			return Err(ParseError::ArgumentError(msg));
+		}
+		let dest = fields[0];
+		if method == "http"
+			&& !dest.starts_with("http://")
+			&& !dest.starts_with("https://")
+			&& is_tor_address(&dest).is_err()
+		{
+			let msg = format!(
+				"HTTP Destination should start with http://: or https://: {}",
+				dest,
+			);
+			return Err(ParseError::ArgumentError(msg));
+		}
+		let amount = core::core::amount_from_hr_string(fields[1]).map_err(|e| {
+			ParseError::ArgumentError(format!(
+				"Line {} of {}, could not parse amount as a number with optional decimal point. e={}",
+				i + 1,
+				file,
+				e
+			))
+		})?;
+		let slatepack_recipient = match fields.get(2) {
+			Some(s) if !s.is_empty() => {
+				let addr = ProvableAddress::from_str(s).map_err(|e| {
+					ParseError::ArgumentError(format!(
+						"Unable to parse slatepack_recipient {}, {}",
+						s, e
+					))
+				})?;
+				if addr.tor_public_key().is_err() {
+					return Err(ParseError::ArgumentError(
+						"Expecting tor PK address as a slatepack recipient value".to_string(),
+					));
+				}
+				Some(addr)
+			}
+			_ => None,
+		};
+		recipients.push(command::BatchRecipient {
+			dest: dest.to_owned(),
+			amount,
+			slatepack_recipient,
+		});
+	}
+	if recipients.is_empty() {
+		let msg = format!("No recipients found at {}", file);
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	// message
+	let message = args.value_of("message").map(|s| s.to_owned());
+
+	// minimum_confirmations
+	let min_c = parse_required(args, "minimum_confirmations")?;
+	let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+	// selection_strategy
+	let selection_strategy = parse_required(args, "selection_strategy")?;
+	let selection_strategy = CoinSelectionStrategy::try_from(selection_strategy)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+
+	// change_outputs
+	let change_outputs = parse_required(args, "change_outputs")?;
+	let change_outputs = parse_u64(change_outputs, "change_outputs")? as usize;
+
+	// target slate version to create/send
+	let target_slate_version = match args.value_of("slate_version") {
+		Some(v) => Some(parse_u64(v, "slate_version")? as u16),
+		None => None,
+	};
+
+	Ok(command::SendBatchArgs {
+		recipients,
+		message,
+		minimum_confirmations: min_c,
+		selection_strategy,
+		method: method.to_owned(),
+		apisecret: args.value_of("apisecret").map(|s| String::from(s)),
+		change_outputs,
+		fluff: args.is_present("fluff"),
+		max_outputs: 500,
+		target_slate_version,
+		ttl_blocks: parse_u64_or_none(args.value_of("ttl_blocks")),
+	})
+}
+
+pub fn parse_consolidate_args(args: &ArgMatches) -> Result<command::ConsolidateArgs, ParseError> {
+	let num_outputs = match args.value_of("num_outputs") {
+		Some(n) => Some(parse_u64(n, "num_outputs")? as usize),
+		None => None,
+	};
+	let max_inputs = parse_required(args, "max_inputs")?;
+	let max_inputs = parse_u64(max_inputs, "max_inputs")? as usize;
+	let min_c = parse_required(args, "minimum_confirmations")?;
+	let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+	Ok(command::ConsolidateArgs {
+		num_outputs,
+		max_inputs,
+		minimum_confirmations: min_c,
+		preview: args.is_present("preview"),
+		fluff: args.is_present("fluff"),
+	})
+}
+
+pub fn parse_split_args(args: &ArgMatches) -> Result<command::SplitArgs, ParseError> {
+	let output = parse_required(args, "output")?;
+	let amounts = parse_required(args, "amounts")?;
+	let amounts = amounts
+		.split(",")
+		.map(|a| {
+			core::core::amount_from_hr_string(a.trim()).map_err(|e| {
+				ParseError::ArgumentError(format!(
+					"Could not parse amount {} as a number with optional decimal point. e={}",
+					a, e
+				))
+			})
+		})
+		.collect::<Result<Vec<u64>, ParseError>>()?;
+	let min_c = parse_required(args, "minimum_confirmations")?;
+	let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+	Ok(command::SplitArgs {
+		output: output.to_owned(),
+		amounts,
+		minimum_confirmations: min_c,
+		preview: args.is_present("preview"),
+		fluff: args.is_present("fluff"),
+	})
+}
+
+pub fn parse_estimate_fee_args(args: &ArgMatches) -> Result<command::EstimateFeeArgs, ParseError> {
+	// amount, zero means the maximum amount that can be sent
+	let amount = match args.value_of("amount") {
+		Some(a) => core::core::amount_from_hr_string(a).map_err(|e| {
+			ParseError::ArgumentError(format!(
+				"Could not parse amount as a number with optional decimal point. e={}",
+				e
+			))
+		})?,
+		None => 0,
+	};
+
+	// minimum_confirmations
+	let min_c = parse_required(args, "minimum_confirmations")?;
+	let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+	// selection_strategy
+	let selection_strategy = parse_required(args, "selection_strategy")?;
+	let selection_strategy = CoinSelectionStrategy::try_from(selection_strategy)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+
+	// change_outputs
+	let change_outputs = parse_required(args, "change_outputs")?;
+	let change_outputs = parse_u64(change_outputs, "change_outputs")? as usize;
+
+	let minimum_confirmations_change_outputs_is_present =
+		args.occurrences_of("minimum_confirmations_change_outputs") != 0;
+	let minimum_confirmations_change_outputs =
+		parse_required(args, "minimum_confirmations_change_outputs")?;
+	let minimum_confirmations_change_outputs = parse_u64(
+		minimum_confirmations_change_outputs,
+		"minimum_confirmations_change_outputs",
+	)?;
+	let exclude_change_outputs = args.is_present("exclude_change_outputs");
+	if minimum_confirmations_change_outputs_is_present && !exclude_change_outputs {
+		return Err(ArgumentError("minimum_confirmations_change_outputs may only be specified if exclude_change_outputs is set".to_string()));
+	}
+
+	let outputs = args
+		.value_of("outputs")
+		.map(|o| o.split(",").map(|s| s.to_string()).collect::<Vec<String>>());
+
+	Ok(command::EstimateFeeArgs {
+		amount,
+		minimum_confirmations: min_c,
+		selection_strategy,
+		change_outputs,
+		max_outputs: 500,
+		exclude_change_outputs,
+		minimum_confirmations_change_outputs,
+		outputs,
+	})
+}
+
+pub fn parse_finalize_batch_args(
+	args: &ArgMatches,
+) -> Result<command::FinalizeBatchArgs, ParseError> {
+	let input_files: Vec<String> = match args.values_of("file") {
+		Some(files) => files.map(|f| f.to_owned()).collect(),
+		None => {
+			return Err(ParseError::ArgumentError(
+				"Please specify 'file' argument for every recipient".to_string(),
+			))
+		}
+	};
+	for file in &input_files {
+		// validate input
+		if !Path::new(file).is_file() {
+			let msg = format!("File {} not found.", file);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	}
+
+	Ok(command::FinalizeBatchArgs {
+		input_files,
+		fluff: args.is_present("fluff"),
+		nopost: args.is_present("nopost"),
+	})
+}
+
+pub fn parse_multisig_args(args: &ArgMatches) -> Result<command::MultisigArgs, ParseError> {
+	let subcommand = if args.is_present("create") {
+		command::MultisigSubcommand::Create
+	} else if args.is_present("process") {
+		command::MultisigSubcommand::Process
+	} else if args.is_present("spend") {
+		command::MultisigSubcommand::Spend
+	} else if args.is_present("response") {
+		command::MultisigSubcommand::Response
+	} else if args.is_present("list") {
+		command::MultisigSubcommand::List
+	} else if args.is_present("show") {
+		command::MultisigSubcommand::Show
+	} else {
+		return Err(ParseError::ArgumentError(format!(
+			"Please define some action to do"
+		)));
+	};
+
+	let account_id = match args.value_of("account_id") {
+		None => None,
+		Some(id) => match id.parse() {
+			Ok(t) => Some(t),
+			Err(e) => {
+				let msg = format!("Could not parse account_id parameter. e={}", e);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	if account_id.is_none()
+		&& (subcommand == command::MultisigSubcommand::Spend
+			|| subcommand == command::MultisigSubcommand::Show)
+	{
+		let msg = format!("'account_id' (-d) argument is required.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	let input_file = args.value_of("input").map(|s| String::from(s));
+	let input_slatepack_message = args.value_of("content").map(|s| String::from(s));
+	if let Some(file) = &input_file {
+		if !Path::new(file).is_file() {
+			let msg = format!("File {} not found.", file);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	} else if input_slatepack_message.is_none()
+		&& (subcommand == command::MultisigSubcommand::Process
+			|| subcommand == command::MultisigSubcommand::Response)
+	{
+		let msg = format!("'input' (-i) or 'content' argument is required.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	let amount = match args.value_of("amount") {
+		Some(amount) => Some(core::core::amount_from_hr_string(amount).map_err(|e| {
+			ParseError::ArgumentError(format!(
+				"Could not parse amount as a number with optional decimal point. e={}",
+				e
+			))
+		})?),
+		None => None,
+	};
+
+	let (num_participants, threshold) = if subcommand == command::MultisigSubcommand::Create {
+		let num_participants = parse_required(args, "participants")?;
+		let num_participants = parse_u64(num_participants, "participants")? as usize;
+		let threshold = parse_required(args, "threshold")?;
+		let threshold = parse_u64(threshold, "threshold")? as usize;
+		if amount.is_none() {
+			let msg = format!("'amount' (-a) argument is required.");
+			return Err(ParseError::ArgumentError(msg));
+		}
+		(num_participants, threshold)
+	} else {
+		(0, 0)
+	};
+
+	let contributions = match args.value_of("contributions") {
+		Some(c) => {
+			let mut contributions = vec![];
+			for amount in c.split(",").map(|s| s.trim()) {
+				contributions.push(core::core::amount_from_hr_string(amount).map_err(|e| {
+					ParseError::ArgumentError(format!(
+						"Could not parse contributions as comma separated amounts. e={}",
+						e
+					))
+				})?);
+			}
+			Some(contributions)
+		}
+		None => None,
+	};
+
+	let mut signers = vec![];
+	if let Some(s) = args.value_of("signers") {
+		for index in s.split(",").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+			signers.push(parse_u64(index, "signers")? as usize);
+		}
+	}
+	if signers.is_empty() && subcommand == command::MultisigSubcommand::Spend {
+		let msg = format!("'signers' argument is required.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	// minimum_confirmations
+	let min_c = parse_required(args, "minimum_confirmations")?;
+	let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+	// selection_strategy
+	let selection_strategy = parse_required(args, "selection_strategy")?;
+	let selection_strategy = CoinSelectionStrategy::try_from(selection_strategy)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+
+	Ok(command::MultisigArgs {
+		subcommand,
+		account_id,
+		input_file,
+		input_slatepack_message,
+		output_file: args.value_of("output").map(|s| String::from(s)),
+		num_participants,
+		threshold,
+		amount,
+		contributions,
+		dest: args.value_of("dest").map(|s| String::from(s)),
+		minimum_confirmations: min_c,
+		selection_strategy,
+		signers,
+		fluff: args.is_present("fluff"),
+	})
+}
+
+pub fn parse_schedule_args(args: &ArgMatches) -> Result<command::ScheduleArgs, ParseError> {
+	let subcommand = if args.is_present("add") {
+		command::ScheduleSubcommand::Add
+	} else if args.is_present("list") {
+		command::ScheduleSubcommand::List
+	} else if args.is_present("pause") {
+		command::ScheduleSubcommand::Pause
+	} else if args.is_present("resume") {
+		command::ScheduleSubcommand::Resume
+	} else if args.is_present("delete") {
+		command::ScheduleSubcommand::Delete
+	} else {
+		return Err(ParseError::ArgumentError(format!(
+			"Please define some action to do"
+		)));
+	};
+
+	let id = match args.value_of("id") {
+		None => None,
+		Some(id) => match id.parse() {
+			Ok(t) => Some(t),
+			Err(e) => {
+				let msg = format!("Could not parse id parameter. e={}", e);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	if id.is_none()
+		&& subcommand != command::ScheduleSubcommand::Add
+		&& subcommand != command::ScheduleSubcommand::List
+	{
+		let msg = format!("'id' argument is required.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	let payment = if subcommand == command::ScheduleSubcommand::Add {
+		let amount = parse_required(args, "amount")?;
+		let amount = core::core::amount_from_hr_string(amount).map_err(|e| {
+			ParseError::ArgumentError(format!(
+				"Could not parse amount as a number with optional decimal point. e={}",
+				e
+			))
+		})?;
+		let start_time = match args.value_of("start") {
+			None => None,
+			Some(d) => Some(parse_date(d, "start", false)?),
+		};
+		let interval_secs = match args.value_of("interval") {
+			None => None,
+			Some(i) => Some(parse_interval(i)?),
+		};
+		let max_retries = parse_required(args, "max_retries")?;
+		let max_retries = parse_u64(max_retries, "max_retries")? as u32;
+		let min_c = parse_required(args, "minimum_confirmations")?;
+		let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+		Some(SchedulePaymentArgs {
+			src_acct_name: None,
+			amount,
+			minimum_confirmations: min_c,
+			message: args.value_of("message").map(|s| s.to_owned()),
+			method: parse_required(args, "method")?.to_owned(),
+			dest: parse_required(args, "dest")?.to_owned(),
+			apisecret: args.value_of("apisecret").map(|s| s.to_owned()),
+			fluff: args.is_present("fluff"),
+			start_time,
+			interval_secs,
+			max_retries,
+		})
+	} else {
+		None
+	};
+
+	Ok(command::ScheduleArgs {
+		subcommand,
+		id,
+		payment,
+	})
+}
+
+pub fn parse_contacts_args(args: &ArgMatches) -> Result<command::ContactsArgs, ParseError> {
+	let (subcommand, name) = if let Some(name) = args.value_of("add") {
+		(command::ContactsSubcommand::Add, name)
+	} else if args.is_present("list") {
+		(command::ContactsSubcommand::List, "")
+	} else if let Some(name) = args.value_of("delete") {
+		(command::ContactsSubcommand::Delete, name)
+	} else {
+		return Err(ParseError::ArgumentError(format!(
+			"Please define some action to do"
+		)));
+	};
+
+	let address = if subcommand == command::ContactsSubcommand::Add {
+		parse_required(args, "address")?
+	} else {
+		""
+	};
+
+	let proof_address = match args.value_of("proof_address") {
+		Some(a) => Some(
+			ProvableAddress::from_str(&proofaddress::address_to_pubkey(a.to_string())).map_err(
+				|e| ParseError::ArgumentError(format!("Invalid proof address: {:?}", e)),
+			)?,
+		),
+		None => None,
+	};
+
+	Ok(command::ContactsArgs {
+		subcommand,
+		contact: Contact {
+			name: name.to_owned(),
+			address: address.to_owned(),
+			method: parse_required(args, "method")?.to_owned(),
+			proof_address,
+			apisecret: args.value_of("apisecret").map(|s| s.to_owned()),
+		},
+	})
+}
+
+pub fn parse_payment_request_args(
+	args: &ArgMatches,
+) -> Result<command::PaymentRequestCmdArgs, ParseError> {
+	let subcommand = if args.is_present("create") {
+		command::PaymentRequestSubcommand::Create
+	} else if args.is_present("list") {
+		command::PaymentRequestSubcommand::List
+	} else if args.is_present("cancel") {
+		command::PaymentRequestSubcommand::Cancel
+	} else if args.is_present("uri") {
+		command::PaymentRequestSubcommand::Uri
+	} else {
+		return Err(ParseError::ArgumentError(format!(
+			"Please define some action to do"
+		)));
+	};
+
+	let id = match args.value_of("id") {
+		None => None,
+		Some(id) => match id.parse() {
+			Ok(t) => Some(t),
+			Err(e) => {
+				let msg = format!("Could not parse id parameter. e={}", e);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	if id.is_none()
+		&& subcommand != command::PaymentRequestSubcommand::Create
+		&& subcommand != command::PaymentRequestSubcommand::List
+	{
+		let msg = format!("'id' argument is required.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	let request = if subcommand == command::PaymentRequestSubcommand::Create {
+		let amount = parse_required(args, "amount")?;
+		let amount = core::core::amount_from_hr_string(amount).map_err(|e| {
+			ParseError::ArgumentError(format!(
+				"Could not parse amount as a number with optional decimal point. e={}",
+				e
+			))
+		})?;
+		let expiry_time = match args.value_of("expiry") {
+			None => None,
+			Some(d) => Some(parse_date(d, "expiry", true)?),
+		};
+
+		Some(PaymentRequestArgs {
+			amount,
+			address: parse_required(args, "address")?.to_owned(),
+			memo: args.value_of("memo").map(|s| s.to_owned()),
+			expiry_blocks: parse_u64_or_none(args.value_of("expiry_blocks")),
+			expiry_time,
+			require_proof: args.is_present("proof"),
+		})
+	} else {
+		None
+	};
+
+	Ok(command::PaymentRequestCmdArgs {
+		subcommand,
+		id,
+		request,
+	})
+}
+
+// parses an interval like 90s, 30m, 12h, 30d or 2w into seconds
+fn parse_interval(arg: &str) -> Result<u64, ParseError> {
+	let arg = arg.trim();
+	let (num, unit) = arg.split_at(arg.len() - arg.trim_start_matches(char::is_numeric).len());
+	let mult = match unit {
+		"s" | "" => 1,
+		"m" => 60,
+		"h" => 3600,
+		"d" => 24 * 3600,
+		"w" => 7 * 24 * 3600,
+		_ => {
+			let msg = format!(
+				"Could not parse interval {}, expected a number with s, m, h, d or w suffix",
+				arg
+			);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	};
+	let num = parse_u64(num, "interval")?;
+	if num == 0 {
+		let msg = format!("Interval can't be zero");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	Ok(num * mult)
+}
+
 pub fn parse_issue_invoice_args(
 	args: &ArgMatches,
 ) -> Result<command::IssueInvoiceArgs, ParseError> {
-	let amount = parse_required(args, "amount")?;
-	let amount = core::core::amount_from_hr_string(amount);
+	// the amount of the payment request is used if it is not specified
+	let payment_request_id = match args.value_of("request") {
+		None => None,
+		Some(id) => match id.parse() {
+			Ok(t) => Some(t),
+			Err(e) => {
+				let msg = format!("Could not parse request parameter. e={}", e);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	let amount = match payment_request_id {
+		Some(_) if !args.is_present("amount") => Ok(0),
+		_ => core::core::amount_from_hr_string(parse_required(args, "amount")?),
+	};
 	let amount = match amount {
 		Ok(a) => a,
 		Err(e) => {
@@ -691,7 +1337,14 @@ pub fn parse_issue_invoice_args(
 		}
 	};
 
+	// slatepack_recipient can be an address book contact name, it is resolved by the invoice command
+	let slatepack_contact = args
+		.value_of("slatepack_recipient")
+		.filter(|s| owner_contacts::is_contact_name(s))
+		.map(|s| s.to_owned());
+
 	let slatepack_recipient: Option<ProvableAddress> = match args.value_of("slatepack_recipient") {
+		Some(_) if slatepack_contact.is_some() => None,
 		Some(s) => {
 			let addr = ProvableAddress::from_str(s).map_err(|e| {
 				ParseError::ArgumentError(format!("Unable to parse slatepack_recipient, {}", e))
@@ -719,7 +1372,9 @@ pub fn parse_issue_invoice_args(
 			message,
 			target_slate_version,
 			slatepack_recipient,
+			payment_request_id,
 		},
+		slatepack_contact,
 	})
 }
 
@@ -741,6 +1396,8 @@ pub fn parse_process_invoice_args(
 
 	// selection_strategy
 	let selection_strategy = parse_required(args, "selection_strategy")?;
+	let selection_strategy = CoinSelectionStrategy::try_from(selection_strategy)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
 
 	// estimate_selection_strategies
 	let estimate_selection_strategies = args.is_present("estimate_selection_strategies");
@@ -803,7 +1460,7 @@ pub fn parse_process_invoice_args(
 	Ok(command::ProcessInvoiceArgs {
 		message: message,
 		minimum_confirmations: min_c,
-		selection_strategy: selection_strategy.to_owned(),
+		selection_strategy,
 		estimate_selection_strategies,
 		method: method.to_owned(),
 		dest: dest.to_owned(),
@@ -813,6 +1470,65 @@ pub fn parse_process_invoice_args(
 	})
 }
 
+// Pay the payment request directly, the method and the destination are taken from the request
+pub fn parse_pay_request_args(args: &ArgMatches) -> Result<command::SendArgs, ParseError> {
+	let request =
+		owner_payment_requests::parse_payment_request_uri(parse_required(args, "request")?)
+			.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+	if request.is_expired(0, Utc::now()) {
+		return Err(ParseError::ArgumentError(format!(
+			"Payment request {} is expired",
+			request.id
+		)));
+	}
+
+	// minimum_confirmations
+	let min_c = parse_required(args, "minimum_confirmations")?;
+	let min_c = parse_u64(min_c, "minimum_confirmations")?;
+
+	// selection_strategy
+	let selection_strategy = parse_required(args, "selection_strategy")?;
+	let selection_strategy = CoinSelectionStrategy::try_from(selection_strategy)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+
+	// message, the request memo by default
+	let message = args
+		.value_of("message")
+		.map(|s| s.to_owned())
+		.or(request.memo.clone());
+
+	let method = owner_payment_requests::payment_request_method(&request);
+	let request_payment_proof = request.proof_address.is_some();
+
+	Ok(command::SendArgs {
+		amount: request.amount,
+		message,
+		minimum_confirmations: min_c,
+		selection_strategy,
+		estimate_selection_strategies: args.is_present("estimate_selection_strategies"),
+		method: method.to_owned(),
+		dest: request.address.clone(),
+		apisecret: None,
+		change_outputs: 1,
+		fluff: false,
+		max_outputs: 500,
+		target_slate_version: None,
+		payment_proof_address: request.proof_address.clone(),
+		ttl_blocks: parse_u64_or_none(args.value_of("ttl_blocks")),
+		exclude_change_outputs: false,
+		minimum_confirmations_change_outputs: 1,
+		address: None,
+		outputs: None,
+		slatepack_recipient: None,
+		late_lock: false,
+		max: false,
+		contact: false,
+		method_is_set: true,
+		request_payment_proof,
+		payment_request_id: Some(request.id),
+	})
+}
+
 pub fn parse_info_args(args: &ArgMatches) -> Result<command::InfoArgs, ParseError> {
 	// minimum_confirmations
 	let mc = parse_required(args, "minimum_confirmations")?;
@@ -835,6 +1551,7 @@ pub fn parse_check_args(args: &ArgMatches) -> Result<command::CheckArgs, ParseEr
 		start_height,
 		backwards_from_tip,
 		delete_unconfirmed,
+		resume: args.is_present("resume"),
 	})
 }
 
@@ -857,9 +1574,154 @@ pub fn parse_txs_args(args: &ArgMatches) -> Result<command::TxsArgs, ParseError>
 		let msg = format!("At most one of 'id' (-i) or 'txid' (-t) may be provided.");
 		return Err(ParseError::ArgumentError(msg));
 	}
+
+	let tx_types = match args.value_of("type") {
+		None => None,
+		Some(types) => {
+			let mut tx_types = vec![];
+			for t in types.split(",") {
+				tx_types.push(match t.trim() {
+					"coinbase" => TxLogEntryType::ConfirmedCoinbase,
+					"received" => TxLogEntryType::TxReceived,
+					"sent" => TxLogEntryType::TxSent,
+					"received_cancelled" => TxLogEntryType::TxReceivedCancelled,
+					"sent_cancelled" => TxLogEntryType::TxSentCancelled,
+					_ => {
+						let msg = format!("Unknown transaction type '{}'", t);
+						return Err(ParseError::ArgumentError(msg));
+					}
+				});
+			}
+			Some(tx_types)
+		}
+	};
+	let confirmed = args.value_of("status").map(|s| s == "confirmed");
+	let cancelled = match args.value_of("cancelled") {
+		Some("exclude") => Some(false),
+		Some("only") => Some(true),
+		_ => None,
+	};
+	let start_date = match args.value_of("start_date") {
+		None => None,
+		Some(d) => Some(parse_date(d, "start_date", false)?),
+	};
+	let end_date = match args.value_of("end_date") {
+		None => None,
+		Some(d) => Some(parse_date(d, "end_date", true)?),
+	};
+	let start_height = match args.value_of("start_height") {
+		None => None,
+		Some(h) => Some(parse_u64(h, "start_height")?),
+	};
+	let end_height = match args.value_of("end_height") {
+		None => None,
+		Some(h) => Some(parse_u64(h, "end_height")?),
+	};
+	let parse_amount = |name: &str| -> Result<Option<u64>, ParseError> {
+		match args.value_of(name) {
+			None => Ok(None),
+			Some(a) => core::core::amount_from_hr_string(a)
+				.map(|a| Some(a))
+				.map_err(|e| {
+					ParseError::ArgumentError(format!(
+						"Could not parse {} as a number with optional decimal point. e={}",
+						name, e
+					))
+				}),
+		}
+	};
+	let sort_field = TxQuerySortField::try_from(parse_required(args, "sort")?)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+	let sort_order = match args.is_present("desc") {
+		true => TxQuerySortOrder::Desc,
+		false => TxQuerySortOrder::Asc,
+	};
+	let offset = match args.value_of("offset") {
+		None => None,
+		Some(o) => Some(parse_u64(o, "offset")? as u32),
+	};
+	let limit = match args.value_of("limit") {
+		None => None,
+		Some(l) => Some(parse_u64(l, "limit")? as u32),
+	};
+
 	Ok(command::TxsArgs {
 		id: tx_id,
 		tx_slate_id: tx_slate_id,
+		query: TxQueryArgs {
+			tx_types,
+			confirmed,
+			cancelled,
+			start_date,
+			end_date,
+			start_height,
+			end_height,
+			min_amount: parse_amount("min_amount")?,
+			max_amount: parse_amount("max_amount")?,
+			address: args.value_of("address").map(|a| a.to_owned()),
+			sort_field,
+			sort_order,
+			offset,
+			limit,
+		},
+	})
+}
+
+// parses a date, either YYYY-MM-DD (start or end of the day, UTC) or RFC 3339
+fn parse_date(arg: &str, name: &str, end_of_day: bool) -> Result<DateTime<Utc>, ParseError> {
+	if let Ok(d) = DateTime::parse_from_rfc3339(arg) {
+		return Ok(d.with_timezone(&Utc));
+	}
+	match NaiveDate::parse_from_str(arg, "%Y-%m-%d") {
+		Ok(d) => {
+			let t = if end_of_day {
+				d.and_hms(23, 59, 59)
+			} else {
+				d.and_hms(0, 0, 0)
+			};
+			Ok(DateTime::<Utc>::from_utc(t, Utc))
+		}
+		Err(e) => {
+			let msg = format!(
+				"Could not parse {} as a date, expected YYYY-MM-DD or RFC 3339. e={}",
+				name, e
+			);
+			Err(ParseError::ArgumentError(msg))
+		}
+	}
+}
+
+pub fn parse_export_txs_args(args: &ArgMatches) -> Result<command::ExportTxsArgs, ParseError> {
+	let format = TxExportFormat::try_from(parse_required(args, "format")?)
+		.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?;
+	let start_date = match args.value_of("start_date") {
+		None => None,
+		Some(d) => Some(parse_date(d, "start_date", false)?),
+	};
+	let end_date = match args.value_of("end_date") {
+		None => None,
+		Some(d) => Some(parse_date(d, "end_date", true)?),
+	};
+	let start_height = match args.value_of("start_height") {
+		None => None,
+		Some(h) => Some(parse_u64(h, "start_height")?),
+	};
+	let end_height = match args.value_of("end_height") {
+		None => None,
+		Some(h) => Some(parse_u64(h, "end_height")?),
+	};
+
+	Ok(command::ExportTxsArgs {
+		file: args.value_of("file").map(|f| f.to_owned()),
+		all_accounts: args.is_present("all_accounts"),
+		export_args: TxExportArgs {
+			account: None,
+			start_date,
+			end_date,
+			start_height,
+			end_height,
+			format,
+		},
 	})
 }
 
@@ -913,6 +1775,52 @@ pub fn parse_repost_args(args: &ArgMatches) -> Result<command::RepostArgs, Parse
 	})
 }
 
+pub fn parse_output_metadata_args(
+	args: &ArgMatches,
+) -> Result<command::OutputMetadataArgs, ParseError> {
+	let commit = parse_required(args, "commit")?;
+
+	let label = match (args.value_of("label"), args.is_present("clear_label")) {
+		(Some(_), true) => {
+			let msg = "'label' and 'clear-label' can't be used together".to_string();
+			return Err(ParseError::ArgumentError(msg));
+		}
+		(Some(l), false) => Some(Some(l.to_owned())),
+		(None, true) => Some(None),
+		(None, false) => None,
+	};
+	let note = match (args.value_of("note"), args.is_present("clear_note")) {
+		(Some(_), true) => {
+			let msg = "'note' and 'clear-note' can't be used together".to_string();
+			return Err(ParseError::ArgumentError(msg));
+		}
+		(Some(n), false) => Some(Some(n.to_owned())),
+		(None, true) => Some(None),
+		(None, false) => None,
+	};
+	let frozen = match (args.is_present("freeze"), args.is_present("unfreeze")) {
+		(true, true) => {
+			let msg = "'freeze' and 'unfreeze' can't be used together".to_string();
+			return Err(ParseError::ArgumentError(msg));
+		}
+		(true, false) => Some(true),
+		(false, true) => Some(false),
+		(false, false) => None,
+	};
+
+	if label.is_none() && note.is_none() && frozen.is_none() {
+		let msg = "Please specify what to change: label, note, freeze or unfreeze".to_string();
+		return Err(ParseError::ArgumentError(msg));
+	}
+
+	Ok(command::OutputMetadataArgs {
+		commit: commit.to_owned(),
+		label,
+		note,
+		frozen,
+	})
+}
+
 pub fn parse_cancel_args(args: &ArgMatches) -> Result<command::CancelArgs, ParseError> {
 	let mut tx_id_string = "";
 	let tx_id = match args.value_of("id") {
@@ -1220,6 +2128,21 @@ where
 		}
 	};
 
+	// Owner API can host more named wallets, every one needs its own instance
+	let wallet_factory: WalletInstFactory<
+		DefaultLCProvider<C, keychain::ExtKeychain>,
+		C,
+		keychain::ExtKeychain,
+	> = {
+		let config = wallet_config.clone();
+		let node_client = node_client.clone();
+		Arc::new(move || {
+			inst_wallet(config.clone(), node_client.clone()).map_err(|e| {
+				ErrorKind::GenericError(format!("Unable to create wallet instance, {}", e)).into()
+			})
+		})
+	};
+
 	// Instantiate wallet (doesn't open the wallet)
 	let wallet =
 		inst_wallet::<DefaultLCProvider<C, keychain::ExtKeychain>, C, keychain::ExtKeychain>(
@@ -1235,6 +2158,7 @@ where
 		let mut wallet_lock = wallet.lock();
 		let lc = wallet_lock.lc_provider().unwrap();
 		let _ = lc.set_top_level_directory(&wallet_config.data_file_dir);
+		let _ = lc.set_db_backend(wallet_config.db_backend.unwrap_or_default());
 	}
 
 	// provide wallet instance back to the caller (handy for testing with
@@ -1246,6 +2170,9 @@ where
 	match wallet_args.subcommand() {
 		("init", Some(_)) => open_wallet = false,
 		("recover", _) => open_wallet = false,
+		("backup", _) => open_wallet = false,
+		("restore", _) => open_wallet = false,
+		("migrate", _) => open_wallet = false,
 		("cli", _) => open_wallet = false,
 		("owner_api", _) => {
 			// If wallet exists, open it. Otherwise, that's fine too.
@@ -1354,6 +2281,25 @@ where
 			let a = arg_parse!(parse_recover_args(&global_wallet_args,));
 			command::recover(owner_api, a, wallet_config.wallet_data_dir.as_deref())
 		}
+		("backup", Some(args)) => {
+			let a = arg_parse!(parse_backup_args(&global_wallet_args, &args));
+			command::backup(owner_api, a, wallet_config.wallet_data_dir.as_deref())
+		}
+		("restore", Some(args)) => {
+			let a = arg_parse!(parse_backup_args(&global_wallet_args, &args));
+			command::restore(
+				owner_api,
+				&global_wallet_args,
+				a,
+				wallet_config.wallet_data_dir.as_deref(),
+			)
+		}
+		("migrate", Some(args)) => command::migrate(
+			owner_api,
+			args.is_present("dry_run"),
+			args.is_present("to_sqlite"),
+			wallet_config.wallet_data_dir.as_deref(),
+		),
 		("listen", Some(args)) => {
 			let mut c = wallet_config.clone();
 			let mut t = tor_config.clone();
@@ -1375,7 +2321,15 @@ where
 			let mut g = global_wallet_args.clone();
 			g.tls_conf = None;
 			arg_parse!(parse_owner_api_args(&mut c, &args));
-			command::owner_api(owner_api, keychain_mask, &c, &tor_config, &mqs_config, &g)
+			command::owner_api(
+				owner_api,
+				keychain_mask,
+				&c,
+				&tor_config,
+				&mqs_config,
+				&g,
+				Some(wallet_factory),
+			)
 		}
 		("web", Some(_)) => command::owner_api(
 			owner_api,
@@ -1384,6 +2338,7 @@ where
 			tor_config,
 			mqs_config,
 			global_wallet_args,
+			Some(wallet_factory),
 		),
 		("account", Some(args)) => {
 			let a = arg_parse!(parse_account_args(&args));
@@ -1403,6 +2358,45 @@ where
 				wallet_config.dark_background_color_scheme.unwrap_or(true),
 			)
 		}
+		("send_batch", Some(args)) => {
+			let a = arg_parse!(parse_send_batch_args(&args));
+			command::send_batch(
+				owner_api,
+				km,
+				wallet_config.api_listen_addr(),
+				global_wallet_args.tls_conf.clone(),
+				Some(tor_config.clone()),
+				Some(mqs_config.clone()),
+				a,
+			)
+		}
+		("estimate_fee", Some(args)) => {
+			let a = arg_parse!(parse_estimate_fee_args(&args));
+			command::estimate_fee(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
+		("consolidate", Some(args)) => {
+			let a = arg_parse!(parse_consolidate_args(&args));
+			command::consolidate(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
+		("split", Some(args)) => {
+			let a = arg_parse!(parse_split_args(&args));
+			command::split(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
 		("unpack", Some(args)) => {
 			let a = arg_parse!(parse_receive_unpack_args(&args));
 			command::unpack(owner_api, km, a)
@@ -1415,6 +2409,46 @@ where
 			let a = arg_parse!(parse_finalize_args(&args));
 			command::finalize(owner_api, km, a, false)
 		}
+		("finalize_batch", Some(args)) => {
+			let a = arg_parse!(parse_finalize_batch_args(&args));
+			command::finalize_batch(owner_api, km, a)
+		}
+		("multisig", Some(args)) => {
+			let a = arg_parse!(parse_multisig_args(&args));
+			command::multisig(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
+		("schedule", Some(args)) => {
+			let a = arg_parse!(parse_schedule_args(&args));
+			command::schedule(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
+		("contacts", Some(args)) => {
+			let a = arg_parse!(parse_contacts_args(&args));
+			command::contacts(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
+		("payment_request", Some(args)) => {
+			let a = arg_parse!(parse_payment_request_args(&args));
+			command::payment_request(
+				owner_api,
+				km,
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
 		("finalize_invoice", Some(args)) => {
 			let a = arg_parse!(parse_finalize_args(&args));
 			command::finalize(owner_api, km, a, true)
@@ -1423,6 +2457,20 @@ where
 			let a = arg_parse!(parse_issue_invoice_args(&args));
 			command::issue_invoice_tx(owner_api, km, a)
 		}
+		("pay", Some(args)) if args.is_present("request") => {
+			let a = arg_parse!(parse_pay_request_args(&args));
+			command::send(
+				owner_api,
+				&wallet_config,
+				km,
+				wallet_config.api_listen_addr(),
+				global_wallet_args.tls_conf.clone(),
+				Some(tor_config.clone()),
+				Some(mqs_config.clone()),
+				a,
+				wallet_config.dark_background_color_scheme.unwrap_or(true),
+			)
+		}
 		("pay", Some(args)) => {
 			let slatepack_secret = {
 				let mut w_lock = owner_api.wallet_inst.lock();
@@ -1472,6 +2520,10 @@ where
 				wallet_config.dark_background_color_scheme.unwrap_or(true),
 			)
 		}
+		("export_txs", Some(args)) => {
+			let a = arg_parse!(parse_export_txs_args(&args));
+			command::export_txs(owner_api, km, &global_wallet_args, a)
+		}
 		("post", Some(args)) => {
 			let a = arg_parse!(parse_post_args(&args));
 			command::post(owner_api, km, a)
@@ -1485,6 +2537,10 @@ where
 			let a = arg_parse!(parse_repost_args(&args));
 			command::repost(owner_api, km, a)
 		}
+		("output", Some(args)) => {
+			let a = arg_parse!(parse_output_metadata_args(&args));
+			command::output_metadata(owner_api, km, a)
+		}
 		("cancel", Some(args)) => {
 			let a = arg_parse!(parse_cancel_args(&args));
 			command::cancel(owner_api, km, a)
@@ -1498,6 +2554,7 @@ where
 			command::proof_verify(owner_api, km, a)
 		}
 		("address", Some(_)) => command::address(owner_api, &global_wallet_args, km),
+		("view_key", Some(_)) => command::view_key(owner_api, km),
 		("scan", Some(args)) => {
 			let a = arg_parse!(parse_check_args(&args));
 			command::scan(owner_api, km, a)